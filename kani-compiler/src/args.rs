@@ -1,6 +1,7 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use std::path::PathBuf;
 use strum_macros::{AsRefStr, Display, EnumString, VariantNames};
 use tracing_subscriber::filter::Directive;
 
@@ -32,6 +33,55 @@ pub enum ReachabilityType {
     Tests,
 }
 
+/// How arithmetic overflow is modeled.
+#[derive(Debug, Default, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum ArithmeticOverflow {
+    /// Overflow wraps around, and it is not a property.
+    Wrap,
+    /// Overflow is a verification failure, even in harnesses that are expected to panic.
+    Check,
+    /// Overflow panics, like code compiled with `overflow-checks` enabled.
+    #[default]
+    Panic,
+}
+
+/// How the address space is modeled.
+#[derive(Debug, Default, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum MemoryModel {
+    /// Pointer arithmetic may move a pointer out of its object, as long as it doesn't overflow.
+    #[default]
+    Flat,
+    /// Pointer arithmetic that moves a pointer out of its object is a verification failure.
+    Segmented,
+}
+
+/// The MIR written for each function reachable from a harness.
+#[derive(Debug, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "snake_case")]
+pub enum EmitMir {
+    /// The MIR generated by rustc, before the Kani transformations.
+    Original,
+    /// The MIR after the Kani transformations, e.g., stubbing and contracts.
+    Transformed,
+}
+
+/// The categories of warnings emitted while checking the crate items, which can be turned into
+/// errors with `--deny-warnings`.
+#[derive(Debug, Clone, Copy, AsRefStr, EnumString, VariantNames, PartialEq, Eq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum WarningCategory {
+    /// All the categories below.
+    All,
+    /// Global ASM that is ignored because of `--ignore-global-asm`.
+    GlobalAsm,
+    /// A function that is named like a harness, but isn't annotated with `#[kani::proof]`.
+    MissingProof,
+    /// A harness generated for a type with interior mutability, whose check may be unsound.
+    InteriorMut,
+}
+
 /// Command line arguments that this instance of the compiler run was called
 /// with. Usually stored in and accessible via [`crate::kani_queries::QueryDb`].
 #[derive(Debug, Default, Clone, clap::Parser)]
@@ -51,6 +101,14 @@ pub struct Arguments {
     /// Option used for suppressing global ASM error.
     #[clap(long)]
     pub ignore_global_asm: bool,
+    /// Don't warn about functions that are named like harnesses but aren't annotated with
+    /// `#[kani::proof]`.
+    #[clap(long)]
+    pub no_missing_proof_warning: bool,
+    /// Report the warnings of the given categories, which are emitted while checking the crate
+    /// items, as errors.
+    #[clap(long)]
+    pub deny_warnings: Vec<WarningCategory>,
     #[clap(long)]
     /// Option used to write JSON symbol tables instead of GOTO binaries.
     ///
@@ -61,6 +119,55 @@ pub struct Arguments {
     pub reachability_analysis: ReachabilityType,
     #[clap(long = "enable-stubbing")]
     pub stubbing_enabled: bool,
+    /// Replace the `std::fs` entry points by the file system model from `kani::fs` in every
+    /// harness.
+    #[clap(long)]
+    pub model_fs: bool,
+    /// Add a cover property to every arm of the `match` expressions of the local crate.
+    #[clap(long)]
+    pub cover_match_arms: bool,
+    /// Report reaching a `todo!()` or `unimplemented!()` call as unfinished code instead of a panic.
+    #[clap(long)]
+    pub check_unfinished_code: bool,
+    /// Assume that every call in the local crate that returns a `Result` returns `Ok`.
+    #[clap(long)]
+    pub assume_ok: bool,
+    /// Assume that the integer values written by the local crate fit in the given number of bits.
+    #[clap(long)]
+    pub int_abstraction_bits: Option<u32>,
+    /// Replace the body of the given functions by a nondeterministic value of their return type.
+    #[clap(long, conflicts_with = "reachability_allowlist")]
+    pub reachability_denylist: Vec<String>,
+    /// Replace the body of the local functions that are not given by a nondeterministic value of
+    /// their return type.
+    #[clap(long)]
+    pub reachability_allowlist: Vec<String>,
+    /// Replace the local functions marked `#[inline(never)]` by their contract, or by a
+    /// nondeterministic value of their return type if they don't have one.
+    #[clap(long, conflicts_with = "reachability_allowlist")]
+    pub reachability_inline_never: bool,
+    /// Model the spurious failures of the weak compare-and-exchange intrinsics.
+    #[clap(long)]
+    pub spurious_cas_failures: bool,
+    /// Write a crate with the local items that each harness depends on to the given directory.
+    #[clap(long)]
+    pub export_reproducer: Option<PathBuf>,
+    /// Only generate the metadata of the harnesses, without generating their models. This is used
+    /// to list the harnesses that would be verified.
+    #[clap(long)]
+    pub no_models: bool,
+    /// Write the MIR of each function reachable from a harness to `--emit-mir-dir`.
+    #[clap(long, requires = "emit_mir_dir")]
+    pub emit_mir: Option<EmitMir>,
+    /// The directory where `--emit-mir` writes the MIR, in a subdirectory per harness.
+    #[clap(long)]
+    pub emit_mir_dir: Option<PathBuf>,
+    /// Select how arithmetic overflow is modeled, unless the harness overrides it.
+    #[clap(long, default_value = "panic")]
+    pub arithmetic_overflow: ArithmeticOverflow,
+    /// Select how the address space is modeled.
+    #[clap(long, default_value = "flat")]
+    pub memory_model: MemoryModel,
     /// Option name used to define unstable features.
     #[clap(short = 'Z', long = "unstable")]
     pub unstable_features: Vec<String>,
@@ -100,4 +207,8 @@ pub enum ExtraChecks {
     Validity,
     /// Check for using uninitialized memory.
     Uninit,
+    /// Check that pointer offsets stay in-bounds of their allocation.
+    PointerOffset,
+    /// Check that pinned values are not moved out of their location.
+    Pin,
 }
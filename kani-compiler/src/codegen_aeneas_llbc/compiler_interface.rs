@@ -358,7 +358,7 @@ fn contract_metadata_for_harness(
     def_id: DefId,
 ) -> Result<Option<InternalDefId>, ErrorGuaranteed> {
     let attrs = KaniAttributes::for_def_id(tcx, def_id);
-    Ok(attrs.interpret_for_contract_attribute().map(|(_, id, _)| id))
+    Ok(attrs.checked_contract_fn())
 }
 
 /// Return a struct that contains information about the codegen results as expected by `rustc`.
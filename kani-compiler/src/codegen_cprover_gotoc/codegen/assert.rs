@@ -21,7 +21,10 @@
 use crate::codegen_cprover_gotoc::GotocCtx;
 use cbmc::InternedString;
 use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use kani_metadata::BuiltinCheck;
 use rustc_middle::mir::coverage::SourceRegion;
+use rustc_smir::rustc_internal;
+use rustc_span::sym;
 use stable_mir::mir::{Place, ProjectionElem};
 use stable_mir::ty::{Span as SpanStable, Ty};
 use strum_macros::{AsRefStr, EnumString};
@@ -75,6 +78,11 @@ pub enum PropertyClass {
     ///
     /// SPECIAL BEHAVIOR: None TODO: Why should this exist?
     FiniteCheck,
+    /// The bounds check of an indexing operation, which is a panic in Rust.
+    ///
+    /// SPECIAL BEHAVIOR: Reported as its own property for each indexing operation, but otherwise
+    /// handled as an `Assertion`, e.g., for `#[kani::should_panic]`.
+    IndexOutOfBounds,
     /// Checks added by Kani compiler to determine whether a property (e.g.
     /// `PropertyClass::Assertion` or `PropertyClass:Cover`) is reachable
     ReachabilityCheck,
@@ -92,11 +100,21 @@ pub enum PropertyClass {
     /// SPECIAL BEHAVIOR: Reachability of these assertions is notable, in order to measure Kani support.
     /// Also makes other properties UNDETERMINED.
     UnsupportedConstruct,
+    /// Checks that `offset`, `add` and `sub` keep a pointer in-bounds of its allocation, which
+    /// are only added with `-Z pointer-offset-checks`.
+    PointerOffset,
+    /// Reaching a `todo!()` or `unimplemented!()` call with `--check-unfinished-code`.
+    ///
+    /// SPECIAL BEHAVIOR: These are not counted as panics by `#[kani::should_panic]` harnesses,
+    /// and the reached ones are listed separately in the verification results.
+    UnfinishedCode,
     /// When Rust determines code is unreachable, this is the `assert(false)` we emit.
     ///
     /// SPECIAL BEHAVIOR: Kinda should be a SanityCheck, except that we emit it also for
     /// `std::intrinsics::unreachable()` and can't tell the difference between that case
     /// and other cases where the Rust compiler thinks things should be unreachable.
+    /// This is also the class of the points marked by `kani::must_not_reach!()`, which must not be
+    /// counted as panics.
     Unreachable,
 }
 
@@ -108,6 +126,12 @@ pub fn as_str(&self) -> &str {
 }
 
 impl GotocCtx<'_> {
+    /// Keep the statement that implements a built-in check, unless the check was disabled for
+    /// the harness with `#[kani::disable_checks]`.
+    pub fn codegen_builtin_check(&self, check: BuiltinCheck, stmt: Stmt) -> Stmt {
+        if self.disabled_checks.contains(&check) { Stmt::skip(*stmt.location()) } else { stmt }
+    }
+
     /// Generates a CBMC assertion. Note: Does _NOT_ assume.
     pub fn codegen_assert(
         &self,
@@ -231,9 +255,37 @@ pub fn codegen_panic(&self, span: SpanStable, fargs: Vec<Expr>) -> Stmt {
         let msg = self.extract_const_message(&fargs[0]).unwrap_or(String::from(
             "This is a placeholder message; Kani doesn't support message formatted at runtime",
         ));
+        if self.queries.args().check_unfinished_code
+            && let Some(macro_name) = self.unfinished_code_macro(span)
+        {
+            let msg = format!("reached `{macro_name}!`: {msg}");
+            return self.codegen_fatal_error(PropertyClass::UnfinishedCode, &msg, span);
+        }
         self.codegen_fatal_error(PropertyClass::Assertion, &msg, span)
     }
 
+    /// Return the name of the `todo!` or `unimplemented!` macro that the panic at `span` was
+    /// expanded from, if any.
+    ///
+    /// The panic call may be nested in other macros, e.g. `todo!("{x}")` expands to `panic!`, so
+    /// we walk up the expansion chain until we reach the user code.
+    fn unfinished_code_macro(&self, span: SpanStable) -> Option<&'static str> {
+        let mut span = rustc_internal::internal(self.tcx, span);
+        while span.from_expansion() {
+            let expn = span.ctxt().outer_expn_data();
+            if let Some(macro_def) = expn.macro_def_id {
+                if self.tcx.is_diagnostic_item(sym::todo_macro, macro_def) {
+                    return Some("todo");
+                }
+                if self.tcx.is_diagnostic_item(sym::unimplemented_macro, macro_def) {
+                    return Some("unimplemented");
+                }
+            }
+            span = expn.call_site;
+        }
+        None
+    }
+
     /// Kani does not currently support all MIR constructs.
     ///
     /// This action will
@@ -367,9 +419,9 @@ pub fn codegen_raw_ptr_deref_validity_check(
                 // Then generate an alignment check
                 let align_ok =
                     ptr.clone().cast_to(Type::size_t()).rem(align).eq(Type::size_t().zero());
-                let align_check = self.codegen_assert_assume(align_ok, PropertyClass::SafetyCheck,
+                let align_check = self.codegen_builtin_check(BuiltinCheck::UnalignedAccess, self.codegen_assert_assume(align_ok, PropertyClass::SafetyCheck,
                     "misaligned pointer to reference cast: address must be a multiple of its type's \
-                    alignment", *loc);
+                    alignment", *loc));
 
                 // Then, generate a __CPROVER_r_ok check.
                 let raw_ptr_read_ok_expr =
@@ -5,15 +5,25 @@
 
 use crate::codegen_cprover_gotoc::GotocCtx;
 use crate::codegen_cprover_gotoc::codegen::block::reverse_postorder;
+use crate::kani_middle::resolve::resolve_opaque_return;
 use cbmc::InternString;
-use cbmc::goto_program::{Expr, Stmt, Symbol};
+use cbmc::goto_program::{Expr, Stmt, Symbol, Type};
+use kani_metadata::TypeStateVariable;
+use rustc_middle::mir;
+use rustc_smir::rustc_internal;
 use stable_mir::CrateDef;
 use stable_mir::mir::mono::Instance;
 use stable_mir::mir::{Body, Local};
-use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::ty::{GenericArgKind, RigidTy, Span, Ty, TyKind};
 use std::collections::BTreeMap;
 use tracing::{debug, debug_span};
 
+/// The prefix of the flags that record the concrete type returned by the functions that are
+/// declared to return an `impl Trait`.
+///
+/// This must match the prefix used by the driver to render these types in a trace.
+const OPAQUE_RETURN_MARKER: &str = "kani::opaque_return::";
+
 /// Codegen MIR functions into gotoc
 impl GotocCtx<'_> {
     /// Declare variables according to their index.
@@ -40,6 +50,14 @@ fn codegen_declare_variables(&mut self, body: &Body) {
                     .with_is_hidden(!self.is_user_variable(&lc))
                     .with_is_parameter((lc > 0 && lc <= num_args) && !self.is_zst_stable(ldata.ty));
             let sym_e = sym.to_expr();
+            if self.is_user_variable(&lc) && self.is_type_state(ldata.ty) {
+                self.type_states.push(TypeStateVariable {
+                    symbol: sym.name.to_string(),
+                    variable: sym.base_name.unwrap().to_string(),
+                    function: self.current_fn().readable_name().to_string(),
+                    ty: self.pretty_ty(ldata.ty),
+                });
+            }
             self.symbol_table.insert(sym);
 
             // Index 0 represents the return value, which does not need to be
@@ -51,6 +69,20 @@ fn codegen_declare_variables(&mut self, body: &Body) {
         }
     }
 
+    /// Whether the type is an ADT with zero-sized type parameters that are themselves ADTs, such
+    /// as the marker types of a type-state machine.
+    ///
+    /// The value of these parameters is not part of the trace, so we record variables of this
+    /// type to render their type in the counterexample.
+    fn is_type_state(&self, ty: Ty) -> bool {
+        let TyKind::RigidTy(RigidTy::Adt(_, args)) = ty.kind() else { return false };
+        args.0.iter().any(|arg| {
+            matches!(arg, GenericArgKind::Type(arg_ty)
+                if matches!(arg_ty.kind(), TyKind::RigidTy(RigidTy::Adt(..)))
+                    && self.is_zst_stable(*arg_ty))
+        })
+    }
+
     pub fn codegen_function(&mut self, instance: Instance) {
         let name = instance.mangled_name();
         let old_sym = self.symbol_table.lookup(&name).unwrap();
@@ -78,12 +110,71 @@ pub fn codegen_function(&mut self, instance: Instance) {
     }
 
     /// Codegen changes required due to the function ABI.
-    /// We currently untuple arguments for RustCall ABI where the `spread_arg` is set.
+    /// We currently untuple arguments for RustCall ABI where the `spread_arg` is set, and declare
+    /// the extra parameter of `#[track_caller]` functions.
     fn codegen_function_prelude(&mut self, body: &Body) {
         debug!(spread_arg=?body.spread_arg(), "codegen_function_prelude");
         if let Some(spread_arg) = body.spread_arg() {
             self.codegen_spread_arg(body, spread_arg);
         }
+        if self.requires_caller_location(self.current_fn().instance_stable()) {
+            let param = self.caller_location_param();
+            self.symbol_table.insert(param);
+        }
+        self.codegen_mark_opaque_return();
+    }
+
+    /// Record that the current function was called, if it's declared to return an `impl Trait`.
+    ///
+    /// Each such function instance gets a global flag, named after the function and the concrete
+    /// type behind its `impl Trait`, that is set when the function is called. The driver uses the
+    /// assignments to this flag in a trace to name the concrete types in counterexamples.
+    fn codegen_mark_opaque_return(&mut self) {
+        let instance = self.current_fn().instance_stable();
+        let Some(concrete) = resolve_opaque_return(self.tcx, instance) else { return };
+        let name = format!(
+            "{OPAQUE_RETURN_MARKER}{} returns {}",
+            self.current_fn().readable_name(),
+            self.pretty_ty(concrete)
+        );
+        let loc = self.codegen_span_stable(instance.def.span());
+        let marker = self
+            .ensure_global_var_init(&name, false, false, Type::bool(), loc, |_, _| {
+                Expr::bool_false()
+            })
+            .to_expr();
+        self.current_fn_mut().push_onto_block(marker.assign(Expr::bool_true(), loc));
+    }
+
+    /// The extra parameter of the current function, which holds the location of its caller.
+    ///
+    /// It is only added to the signature of `#[track_caller]` functions in `fn_typ`.
+    fn caller_location_param(&mut self) -> Symbol {
+        let (name, base_name) = self.codegen_caller_location_name();
+        let typ = self.codegen_ty(self.tcx.caller_location_ty());
+        let loc = self.codegen_span_stable(self.current_fn().instance_stable().def.span());
+        Symbol::variable(name, base_name, typ, loc).with_is_parameter(true)
+    }
+
+    /// Generate the location returned by `Location::caller()` for a call at the given span, and
+    /// passed to the `#[track_caller]` functions called at this span.
+    ///
+    /// Inside a `#[track_caller]` function, this is the location of its own caller, so nested
+    /// `#[track_caller]` functions report the location of the outermost call that doesn't track
+    /// its caller. Otherwise, it's the location of the call itself.
+    ///
+    /// Note that this relies on `#[track_caller]` functions not being inlined in MIR, which is
+    /// the case since we don't enable MIR optimizations.
+    pub fn codegen_caller_location(&mut self, span: Span) -> Expr {
+        if self.requires_caller_location(self.current_fn().instance_stable()) {
+            return self.caller_location_param().to_expr();
+        }
+        let tcx = self.tcx;
+        let location = tcx.span_as_caller_location(rustc_internal::internal(tcx, span));
+        let constant =
+            rustc_internal::stable(mir::Const::from_value(location, tcx.caller_location_ty()));
+        let loc = self.codegen_span_stable(span);
+        self.codegen_const(&constant, loc)
     }
 
     /// MIR functions have a `spread_arg` field that specifies whether the
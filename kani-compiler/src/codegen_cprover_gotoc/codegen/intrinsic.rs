@@ -3,6 +3,7 @@
 //! this module handles intrinsics
 use super::typ;
 use super::{PropertyClass, bb_label};
+use crate::args::{ExtraChecks, MemoryModel};
 use crate::codegen_cprover_gotoc::codegen::ty_stable::pointee_type_stable;
 use crate::codegen_cprover_gotoc::{GotocCtx, utils};
 use crate::intrinsics::Intrinsic;
@@ -10,6 +11,7 @@
 use cbmc::goto_program::{
     ArithmeticOverflowResult, BinaryOperator, BuiltinFn, Expr, Location, Stmt, Type,
 };
+use kani_metadata::BuiltinCheck;
 use rustc_middle::ty::ParamEnv;
 use rustc_middle::ty::layout::ValidityRequirement;
 use rustc_smir::rustc_internal;
@@ -161,11 +163,15 @@ macro_rules! codegen_op_with_div_overflow_check {
                 let a = fargs.remove(0);
                 let b = fargs.remove(0);
                 let div_does_not_overflow = self.div_does_not_overflow(a.clone(), b.clone());
-                let div_overflow_check = self.codegen_assert_assume(
-                    div_does_not_overflow,
-                    PropertyClass::ArithmeticOverflow,
-                    format!("attempt to compute {} which would overflow", intrinsic_str).as_str(),
-                    loc,
+                let div_overflow_check = self.codegen_builtin_check(
+                    BuiltinCheck::ArithmeticOverflow,
+                    self.codegen_assert_assume(
+                        div_does_not_overflow,
+                        PropertyClass::ArithmeticOverflow,
+                        format!("attempt to compute {} which would overflow", intrinsic_str)
+                            .as_str(),
+                        loc,
+                    ),
                 );
                 let res = a.$f(b);
                 let expr_place = self.codegen_expr_to_place_stable(place, res, loc);
@@ -193,12 +199,43 @@ macro_rules! codegen_intrinsic_binop_with_mm {
             }};
         }
 
+        // Fail the type check of intrinsics whose first argument must be an integer
+        macro_rules! check_integer_arg {
+            () => {{
+                if !fargs[0].typ().is_integer() {
+                    self.intrinsics_typecheck_fail(
+                        span,
+                        intrinsic_str,
+                        "integer type",
+                        farg_types[0],
+                    )
+                }
+            }};
+        }
+
         // Intrinsics which encode count intrinsics (ctlz, cttz)
         // The `allow_zero` flag determines if calling these builtins with 0 causes UB
         macro_rules! codegen_count_intrinsic {
             ($builtin: ident, $allow_zero: expr) => {{
+                check_integer_arg!();
                 let arg = fargs.remove(0);
-                self.codegen_expr_to_place_stable(place, arg.$builtin($allow_zero), loc)
+                self.codegen_count_zeros(
+                    intrinsic_str,
+                    place,
+                    arg,
+                    $allow_zero,
+                    |arg| arg.$builtin(true),
+                    loc,
+                )
+            }};
+        }
+
+        // Intrinsics which encode a bit permutation of an integer (bswap, bitreverse, rotate_*)
+        macro_rules! codegen_bit_intrinsic {
+            ($f:expr) => {{
+                check_integer_arg!();
+                let expr = $f(&mut fargs);
+                self.codegen_expr_to_place_stable(place, expr, loc)
             }};
         }
 
@@ -316,8 +353,11 @@ macro_rules! unstable_codegen {
                 loc,
             ),
             Intrinsic::AtomicAnd(_) => codegen_atomic_binop!(bitand),
-            Intrinsic::AtomicCxchg(_) | Intrinsic::AtomicCxchgWeak(_) => {
-                self.codegen_atomic_cxchg(intrinsic_str, fargs, place, loc)
+            Intrinsic::AtomicCxchg(_) => {
+                self.codegen_atomic_cxchg(intrinsic_str, fargs, place, false, loc)
+            }
+            Intrinsic::AtomicCxchgWeak(_) => {
+                self.codegen_atomic_cxchg(intrinsic_str, fargs, place, true, loc)
             }
 
             Intrinsic::AtomicFence(_) => self.codegen_atomic_noop(intrinsic_str, loc),
@@ -337,14 +377,18 @@ macro_rules! unstable_codegen {
             Intrinsic::AtomicXor(_) => codegen_atomic_binop!(bitxor),
             Intrinsic::AtomicXsub(_) => codegen_atomic_binop!(sub),
             Intrinsic::Bitreverse => {
-                self.codegen_expr_to_place_stable(place, fargs.remove(0).bitreverse(), loc)
+                codegen_bit_intrinsic!(|fargs: &mut Vec<Expr>| fargs.remove(0).bitreverse())
             }
-            // black_box is an identity function that hints to the compiler
-            // to be maximally pessimistic to limit optimizations
-            Intrinsic::BlackBox => self.codegen_expr_to_place_stable(place, fargs.remove(0), loc),
+            Intrinsic::BlackBox => self.codegen_black_box(fargs, place, loc),
             Intrinsic::Breakpoint => Stmt::skip(loc),
-            Intrinsic::Bswap => {
-                self.codegen_expr_to_place_stable(place, fargs.remove(0).bswap(), loc)
+            Intrinsic::Bswap => codegen_bit_intrinsic!(|fargs: &mut Vec<Expr>| {
+                let arg = fargs.remove(0);
+                // Swapping the bytes of a single byte integer is a no-op.
+                if arg.typ().sizeof_in_bits(&self.symbol_table) == 8 { arg } else { arg.bswap() }
+            }),
+            Intrinsic::CallerLocation => {
+                let location = self.codegen_caller_location(span);
+                self.codegen_expr_to_place_stable(place, location, loc)
             }
             Intrinsic::CeilF32 => codegen_simple_intrinsic!(Ceilf),
             Intrinsic::CeilF64 => codegen_simple_intrinsic!(Ceil),
@@ -437,8 +481,12 @@ macro_rules! unstable_codegen {
             Intrinsic::RetagBoxToRaw => self.codegen_retag_box_to_raw(fargs, place, loc),
             Intrinsic::RintF32 => codegen_simple_intrinsic!(Rintf),
             Intrinsic::RintF64 => codegen_simple_intrinsic!(Rint),
-            Intrinsic::RotateLeft => codegen_intrinsic_binop!(rol),
-            Intrinsic::RotateRight => codegen_intrinsic_binop!(ror),
+            Intrinsic::RotateLeft => {
+                codegen_bit_intrinsic!(|fargs: &mut Vec<Expr>| self.codegen_rotate(fargs, true))
+            }
+            Intrinsic::RotateRight => {
+                codegen_bit_intrinsic!(|fargs: &mut Vec<Expr>| self.codegen_rotate(fargs, false))
+            }
             Intrinsic::RoundF32 => codegen_simple_intrinsic!(Roundf),
             Intrinsic::RoundF64 => codegen_simple_intrinsic!(Round),
             Intrinsic::SaturatingAdd => codegen_intrinsic_binop_with_mm!(saturating_add),
@@ -574,6 +622,51 @@ fn codegen_ctpop(
         }
     }
 
+    /// Code generation for the count intrinsics (`ctlz`, `cttz` and their `_nonzero` variants).
+    ///
+    /// For `0`, the count intrinsics return the bit width of the argument, while the `_nonzero`
+    /// variants have undefined behavior. We encode both cases explicitly rather than relying on
+    /// the backend semantics of counting the zeros of `0`.
+    fn codegen_count_zeros<F: FnOnce(Expr) -> Expr>(
+        &mut self,
+        intrinsic: &str,
+        target_place: &Place,
+        arg: Expr,
+        allow_zero: bool,
+        count: F,
+        loc: Location,
+    ) -> Stmt {
+        let width = arg.typ().sizeof_in_bits(&self.symbol_table);
+        let is_zero = arg.clone().is_zero();
+        let result =
+            is_zero.clone().ternary(Expr::int_constant(width, Type::unsigned_int(32)), count(arg));
+        let assign = self.codegen_expr_to_place_stable(target_place, result, loc);
+        if allow_zero {
+            assign
+        } else {
+            let check = self.codegen_assert_assume(
+                is_zero.not(),
+                PropertyClass::SafetyCheck,
+                &format!("`{intrinsic}` must not be called with 0"),
+                loc,
+            );
+            Stmt::block(vec![check, assign], loc)
+        }
+    }
+
+    /// Code generation for the `rotate_left` and `rotate_right` intrinsics.
+    ///
+    /// The rotation amount is taken modulo the bit width of the value, so we reduce it before
+    /// encoding the rotation.
+    fn codegen_rotate(&self, fargs: &mut Vec<Expr>, left: bool) -> Expr {
+        let value = fargs.remove(0);
+        let shift = fargs.remove(0);
+        let shift_typ = shift.typ().clone();
+        let width = value.typ().sizeof_in_bits(&self.symbol_table);
+        let shift = shift.rem(Expr::int_constant(width, shift_typ)).cast_to(value.typ().clone());
+        if left { value.rol(shift) } else { value.ror(shift) }
+    }
+
     /// Report that a delayed type check on an intrinsic failed.
     ///
     /// The idea is to blame one of the arguments on the failed type check and
@@ -699,6 +792,33 @@ fn codegen_exact_div(&mut self, mut fargs: Vec<Expr>, p: &Place, loc: Location)
         )
     }
 
+    /// `black_box` is an identity function that hints to the compiler to be maximally pessimistic
+    /// about what it does, so the code around it isn't optimized away.
+    ///
+    /// Assigning the argument to the result would let the simplifications of CBMC, e.g., constant
+    /// propagation, fold through the call. Instead, integers, which include `bool` and `char`, go
+    /// through a nondeterministic value that is assumed to be equal to the argument: the solver
+    /// still knows that the result is equal to the argument, but the simplifications don't.
+    ///
+    /// Other values, such as pointers, are assigned directly, since the pointer analysis of CBMC
+    /// needs to track them to resolve dereferences.
+    fn codegen_black_box(&mut self, mut fargs: Vec<Expr>, p: &Place, loc: Location) -> Stmt {
+        let arg = fargs.remove(0);
+        let typ = arg.typ().clone();
+        if !typ.is_integer() {
+            return self.codegen_expr_to_place_stable(p, arg, loc);
+        }
+        let (opaque, decl) = self.decl_temp_variable(typ.clone(), Some(typ.nondet()), loc);
+        Stmt::block(
+            vec![
+                decl,
+                self.codegen_assume(opaque.clone().eq(arg), loc),
+                self.codegen_expr_to_place_stable(p, opaque, loc),
+            ],
+            loc,
+        )
+    }
+
     /// Generates either a panic or no-op for `assert_*` intrinsics.
     /// These are intrinsics that statically compile to panics if the type
     /// layout is invalid so we get a message that mentions the offending type.
@@ -799,25 +919,30 @@ fn codegen_atomic_load(
     }
 
     /// An atomic compare-and-exchange updates the value referenced in
-    /// its primary argument and returns a tuple that contains:
+    /// its primary argument if it's equal to the expected value, and returns a tuple that
+    /// contains:
     ///  * the previous value
     ///  * a boolean value indicating whether the operation was successful or not
     ///
-    /// In a sequential context, the update is always sucessful so we assume the
-    /// second value to be true.
+    /// The weak version of the operation is allowed to fail even if the values are equal. With
+    /// `--spurious-cas-failures`, we model its spurious failures with a nondeterministic boolean.
+    /// They are off by default, since they make the retry loops built on the weak version, e.g.,
+    /// `fetch_update`, unbounded.
     /// -------------------------
     /// var = atomic_cxchg(var1, var2, var3)
     /// -------------------------
     /// unsigned char tmp;
     /// tmp = *var1;
-    /// if (*var1 == var2) *var1 = var3;
-    /// var = (tmp, true);
+    /// bool success = tmp == var2; // `tmp == var2 && nondet()` for spurious failures
+    /// if (success) *var1 = var3;
+    /// var = (tmp, success);
     /// -------------------------
     fn codegen_atomic_cxchg(
         &mut self,
         intrinsic: &str,
         mut fargs: Vec<Expr>,
         p: &Place,
+        weak: bool,
         loc: Location,
     ) -> Stmt {
         self.store_concurrent_construct(intrinsic, loc);
@@ -827,16 +952,26 @@ fn codegen_atomic_cxchg(
             self.decl_temp_variable(var1.typ().clone(), Some(var1.to_owned()), loc);
         let var2 = fargs.remove(0).with_location(loc);
         let var3 = fargs.remove(0).with_location(loc);
-        let eq_expr = (var1.clone()).eq(var2);
+        let eq_expr = tmp.clone().eq(var2);
+        let success_expr = if weak && self.queries.args().spurious_cas_failures {
+            eq_expr.and(Type::bool().nondet())
+        } else {
+            eq_expr
+        };
+        let (success, success_decl_stmt) =
+            self.decl_temp_variable(Type::bool(), Some(success_expr), loc);
         let assign_stmt = var1.assign(var3, loc);
-        let cond_update_stmt = Stmt::if_then_else(eq_expr, assign_stmt, None, loc);
+        let cond_update_stmt = Stmt::if_then_else(success.clone(), assign_stmt, None, loc);
         let place_type = self.place_ty_stable(p);
         let res_type = self.codegen_ty_stable(place_type);
-        let tuple_expr =
-            Expr::struct_expr_from_values(res_type, vec![tmp, Expr::c_true()], &self.symbol_table)
-                .with_location(loc);
+        let tuple_expr = Expr::struct_expr_from_values(
+            res_type,
+            vec![tmp, success.cast_to(Type::c_bool())],
+            &self.symbol_table,
+        )
+        .with_location(loc);
         let res_stmt = self.codegen_expr_to_place_stable(p, tuple_expr, loc);
-        Stmt::atomic_block(vec![decl_stmt, cond_update_stmt, res_stmt], loc)
+        Stmt::atomic_block(vec![decl_stmt, success_decl_stmt, cond_update_stmt, res_stmt], loc)
     }
 
     /// An atomic store updates the value referenced in
@@ -892,7 +1027,9 @@ fn codegen_atomic_noop(&mut self, intrinsic: &str, loc: Location) -> Stmt {
     ///    size.
     ///
     /// In addition, we check that computing `count` in bytes (i.e., the third
-    /// argument of the copy built-in call) would not overflow.
+    /// argument of the copy built-in call) would not overflow, and that both
+    /// regions are in-bounds of their allocation. The last two conditions are
+    /// checked by [Self::codegen_copy_regions_check] with their own properties.
     pub fn codegen_copy(
         &mut self,
         intrinsic: &str,
@@ -909,18 +1046,24 @@ pub fn codegen_copy(
 
         // Generate alignment checks for both pointers
         let src_align = self.is_ptr_aligned(farg_types[0], src.clone());
-        let src_align_check = self.codegen_assert_assume(
-            src_align,
-            PropertyClass::SafetyCheck,
-            "`src` must be properly aligned",
-            loc,
+        let src_align_check = self.codegen_builtin_check(
+            BuiltinCheck::UnalignedAccess,
+            self.codegen_assert_assume(
+                src_align,
+                PropertyClass::SafetyCheck,
+                "`src` must be properly aligned",
+                loc,
+            ),
         );
         let dst_align = self.is_ptr_aligned(farg_types[1], dst.clone());
-        let dst_align_check = self.codegen_assert_assume(
-            dst_align,
-            PropertyClass::SafetyCheck,
-            "`dst` must be properly aligned",
-            loc,
+        let dst_align_check = self.codegen_builtin_check(
+            BuiltinCheck::UnalignedAccess,
+            self.codegen_assert_assume(
+                dst_align,
+                PropertyClass::SafetyCheck,
+                "`dst` must be properly aligned",
+                loc,
+            ),
         );
 
         // Compute the number of bytes to be copied
@@ -928,6 +1071,16 @@ pub fn codegen_copy(
         let pointee_type = pointee_type_stable(farg_types[0]).unwrap();
         let (count_bytes, overflow_check) =
             self.count_in_bytes(count, pointee_type, Type::size_t(), intrinsic, loc);
+        let (count_bytes, count_decl) =
+            self.decl_temp_variable(Type::size_t(), Some(count_bytes), loc);
+        let regions_check = self.codegen_copy_regions_check(
+            intrinsic,
+            is_non_overlapping,
+            src.clone(),
+            dst.clone(),
+            count_bytes.clone(),
+            loc,
+        );
 
         // Build the call to the copy built-in (`memmove` or `memcpy`)
         let copy_builtin = if is_non_overlapping { BuiltinFn::Memcpy } else { BuiltinFn::Memmove };
@@ -946,7 +1099,80 @@ pub fn codegen_copy(
         } else {
             copy_if_nontrivial.as_stmt(loc)
         };
-        Stmt::block(vec![src_align_check, dst_align_check, overflow_check, copy_expr], loc)
+        Stmt::block(
+            vec![
+                src_align_check,
+                dst_align_check,
+                overflow_check,
+                count_decl,
+                regions_check,
+                copy_expr,
+            ],
+            loc,
+        )
+    }
+
+    /// Check that the regions of `count_bytes` bytes that start at `src` and `dst` are in-bounds
+    /// of their allocation, and that they don't overlap for a nonoverlapping copy.
+    ///
+    /// Both conditions are decided using the object and offset of each pointer, so a symbolic
+    /// `count` or symbolic pointers that may alias produce a counterexample where the regions
+    /// overlap. A copy of zero bytes is always allowed.
+    fn codegen_copy_regions_check(
+        &mut self,
+        intrinsic: &str,
+        is_non_overlapping: bool,
+        src: Expr,
+        dst: Expr,
+        count_bytes: Expr,
+        loc: Location,
+    ) -> Stmt {
+        let in_bounds = |ptr: &Expr| {
+            let offset = ptr.clone().pointer_offset();
+            let size = ptr.clone().object_size();
+            offset.clone().ge(Type::ssize_t().zero()).and(
+                count_bytes
+                    .clone()
+                    .le(size.clone())
+                    .and(offset.cast_to(Type::size_t()).le(size.sub(count_bytes.clone()))),
+            )
+        };
+        let is_empty = count_bytes.clone().is_zero();
+        let src_in_bounds = self.codegen_assert_assume(
+            is_empty.clone().or(in_bounds(&src)),
+            PropertyClass::SafetyCheck,
+            &format!(
+                "`{intrinsic}`: `src` must be in-bounds of its allocation for `count` elements"
+            ),
+            loc,
+        );
+        let dst_in_bounds = self.codegen_assert_assume(
+            is_empty.clone().or(in_bounds(&dst)),
+            PropertyClass::SafetyCheck,
+            &format!(
+                "`{intrinsic}`: `dst` must be in-bounds of its allocation for `count` elements"
+            ),
+            loc,
+        );
+        if !is_non_overlapping {
+            return Stmt::block(vec![src_in_bounds, dst_in_bounds], loc);
+        }
+        // Both regions are in-bounds at this point, so computing their end doesn't overflow.
+        let count = count_bytes.cast_to(Type::ssize_t());
+        let src_offset = src.clone().pointer_offset();
+        let dst_offset = dst.clone().pointer_offset();
+        let disjoint = src
+            .same_object(dst)
+            .not()
+            .or(src_offset.clone().plus(count.clone()).le(dst_offset.clone()))
+            .or(dst_offset.plus(count).le(src_offset));
+        let non_overlapping = self.codegen_assert_assume(
+            is_empty.or(disjoint),
+            PropertyClass::SafetyCheck,
+            &format!("`{intrinsic}`: the regions of `src` and `dst` must not overlap"),
+            loc,
+        );
+        Stmt::block(vec![src_in_bounds, dst_in_bounds, non_overlapping], loc)
     }
 
     /// This is an intrinsic that was added in
@@ -1024,7 +1250,76 @@ fn codegen_arith_offset(&mut self, mut fargs: Vec<Expr>, p: &Place, loc: Locatio
 
         // Compute `dst_ptr` with standard addition to avoid conversion
         let dst_ptr = src_ptr.plus(offset);
-        self.codegen_expr_to_place_stable(p, dst_ptr, loc)
+        let bounds_check = self.codegen_segmented_bounds_check(dst_ptr.clone(), loc);
+        Stmt::block(vec![bounds_check, self.codegen_expr_to_place_stable(p, dst_ptr, loc)], loc)
+    }
+
+    /// Check that the result of a pointer arithmetic operation is in-bounds of its object, or
+    /// one byte past its end, when the segmented memory model is selected.
+    ///
+    /// In the flat model, which is the default, pointer arithmetic is only checked for overflow,
+    /// since pointers can be moved out of their object as long as they aren't dereferenced.
+    /// Pointers that don't point to an object, e.g., dangling pointers created from an integer,
+    /// aren't checked in either model.
+    pub fn codegen_segmented_bounds_check(&mut self, ptr: Expr, loc: Location) -> Stmt {
+        if self.queries.args().memory_model != MemoryModel::Segmented {
+            return Stmt::skip(loc);
+        }
+        let offset = ptr.clone().pointer_offset();
+        let in_bounds = offset
+            .clone()
+            .ge(Type::ssize_t().zero())
+            .and(offset.cast_to(Type::size_t()).le(ptr.clone().object_size()));
+        let null = ptr.typ().null();
+        self.codegen_builtin_check(
+            BuiltinCheck::PointerOverflow,
+            self.codegen_assert_assume(
+                ptr.same_object(null).or(in_bounds),
+                PropertyClass::SafetyCheck,
+                "pointer arithmetic moves the pointer out of its object in the segmented memory \
+                model",
+                loc,
+            ),
+        )
+    }
+
+    /// Check that offsetting `src` by `offset_bytes` keeps the pointer in-bounds of the
+    /// allocation it points to, or one byte past its end, when `-Z pointer-offset-checks` is
+    /// enabled. This is one of the safety conditions of `offset`, `add` and `sub`:
+    /// <https://doc.rust-lang.org/std/primitive.pointer.html#safety-2>
+    ///
+    /// The offset is stored in a temporary variable, so its value appears in the counterexample
+    /// trace. Offsetting by zero is always allowed, and pointers that don't point to an
+    /// allocation, e.g., dangling pointers created from an integer, aren't checked.
+    pub fn codegen_offset_in_bounds_check(
+        &mut self,
+        src: Expr,
+        offset_bytes: Expr,
+        loc: Location,
+    ) -> Stmt {
+        if !self.queries.args().ub_check.contains(&ExtraChecks::PointerOffset) {
+            return Stmt::skip(loc);
+        }
+        let (offset, decl) = self.decl_temp_variable(Type::ssize_t(), Some(offset_bytes), loc);
+        let new_offset = src.clone().pointer_offset().plus(offset.clone());
+        let in_bounds = new_offset
+            .clone()
+            .ge(Type::ssize_t().zero())
+            .and(new_offset.cast_to(Type::size_t()).le(src.clone().object_size()));
+        let null = src.typ().null();
+        let cond = offset.is_zero().or(src.same_object(null)).or(in_bounds);
+        Stmt::block(
+            vec![
+                decl,
+                self.codegen_assert_assume(
+                    cond,
+                    PropertyClass::PointerOffset,
+                    "offset moves the pointer out of bounds of its allocation",
+                    loc,
+                ),
+            ],
+            loc,
+        )
     }
 
     /// ptr_offset_from returns the offset between two pointers
@@ -1035,11 +1330,14 @@ fn codegen_ptr_offset_from(&mut self, fargs: Vec<Expr>, p: &Place, loc: Location
         // Check that computing `offset` in bytes would not overflow an `isize`
         // These checks may allow a wrapping-around behavior in CBMC:
         // https://github.com/model-checking/kani/issues/1150
-        let overflow_check = self.codegen_assert_assume(
-            offset_overflow.overflowed.not(),
-            PropertyClass::ArithmeticOverflow,
-            "attempt to compute offset in bytes which would overflow an `isize`",
-            loc,
+        let overflow_check = self.codegen_builtin_check(
+            BuiltinCheck::PointerOverflow,
+            self.codegen_assert_assume(
+                offset_overflow.overflowed.not(),
+                PropertyClass::ArithmeticOverflow,
+                "attempt to compute offset in bytes which would overflow an `isize`",
+                loc,
+            ),
         );
 
         let offset_expr = self.codegen_expr_to_place_stable(p, offset_expr, loc);
@@ -1060,11 +1358,14 @@ fn codegen_ptr_offset_from_unsigned(
         // Check that computing `offset` in bytes would not overflow an `isize`
         // These checks may allow a wrapping-around behavior in CBMC:
         // https://github.com/model-checking/kani/issues/1150
-        let overflow_check = self.codegen_assert_assume(
-            offset_overflow.overflowed.not(),
-            PropertyClass::ArithmeticOverflow,
-            "attempt to compute offset in bytes which would overflow an `isize`",
-            loc,
+        let overflow_check = self.codegen_builtin_check(
+            BuiltinCheck::PointerOverflow,
+            self.codegen_assert_assume(
+                offset_overflow.overflowed.not(),
+                PropertyClass::ArithmeticOverflow,
+                "attempt to compute offset in bytes which would overflow an `isize`",
+                loc,
+            ),
         );
 
         let non_negative_check = self.codegen_assert_assume(
@@ -1530,7 +1831,8 @@ fn codegen_simd_div_with_overflow(
 
     /// Intrinsics which encode a SIMD arithmetic operation with overflow check.
     /// We expand the overflow check because CBMC overflow operations don't accept array as
-    /// argument.
+    /// argument. Operations on floating point lanes follow IEEE 754, so they don't have an
+    /// overflow check.
     fn codegen_simd_op_with_overflow<F: FnOnce(Expr, Expr) -> Expr, G: Fn(Expr, Expr) -> Expr>(
         &mut self,
         op_fun: F,
@@ -1540,6 +1842,9 @@ fn codegen_simd_op_with_overflow<F: FnOnce(Expr, Expr) -> Expr, G: Fn(Expr, Expr
         p: &Place,
         loc: Location,
     ) -> Stmt {
+        if fargs[0].typ().base_type().is_some_and(Type::is_floating_point) {
+            return self.binop(p, fargs, op_fun, loc);
+        }
         let a = fargs.remove(0);
         let b = fargs.remove(0);
 
@@ -1555,11 +1860,14 @@ fn codegen_simd_op_with_overflow<F: FnOnce(Expr, Expr) -> Expr, G: Fn(Expr, Expr
             let v_b = b.clone().index_array(index);
             check = check.or(overflow_fun(v_a, v_b));
         }
-        let check_stmt = self.codegen_assert_assume(
-            check.not(),
-            PropertyClass::ArithmeticOverflow,
-            format!("attempt to compute {intrinsic} which would overflow").as_str(),
-            loc,
+        let check_stmt = self.codegen_builtin_check(
+            BuiltinCheck::ArithmeticOverflow,
+            self.codegen_assert_assume(
+                check.not(),
+                PropertyClass::ArithmeticOverflow,
+                format!("attempt to compute {intrinsic} which would overflow").as_str(),
+                loc,
+            ),
         );
         let res = op_fun(a, b);
         let expr_place = self.codegen_expr_to_place_stable(p, res, loc);
@@ -1737,11 +2045,14 @@ fn codegen_volatile_load(
         let src = fargs.remove(0);
         let src_typ = farg_types[0];
         let align = self.is_ptr_aligned(src_typ, src.clone());
-        let align_check = self.codegen_assert_assume(
-            align,
-            PropertyClass::SafetyCheck,
-            "`src` must be properly aligned",
-            loc,
+        let align_check = self.codegen_builtin_check(
+            BuiltinCheck::UnalignedAccess,
+            self.codegen_assert_assume(
+                align,
+                PropertyClass::SafetyCheck,
+                "`src` must be properly aligned",
+                loc,
+            ),
         );
         let expr = src.dereference();
         let res_stmt = self.codegen_expr_to_place_stable(p, expr, loc);
@@ -1764,11 +2075,14 @@ fn codegen_volatile_store(
         let src = fargs.remove(0);
         let dst_typ = farg_types[0];
         let align = self.is_ptr_aligned(dst_typ, dst.clone());
-        let align_check = self.codegen_assert_assume(
-            align,
-            PropertyClass::SafetyCheck,
-            "`dst` must be properly aligned",
-            loc,
+        let align_check = self.codegen_builtin_check(
+            BuiltinCheck::UnalignedAccess,
+            self.codegen_assert_assume(
+                align,
+                PropertyClass::SafetyCheck,
+                "`dst` must be properly aligned",
+                loc,
+            ),
         );
         if self.is_zst_stable(pointee_type_stable(dst_typ).unwrap()) {
             // do not attempt to dereference (and assign) a ZST
@@ -1801,11 +2115,14 @@ fn codegen_write_bytes(
         // Check that `dst` must be properly aligned
         let dst_typ = farg_types[0];
         let align = self.is_ptr_aligned(dst_typ, dst.clone());
-        let align_check = self.codegen_assert_assume(
-            align,
-            PropertyClass::SafetyCheck,
-            "`dst` must be properly aligned",
-            loc,
+        let align_check = self.codegen_builtin_check(
+            BuiltinCheck::UnalignedAccess,
+            self.codegen_assert_assume(
+                align,
+                PropertyClass::SafetyCheck,
+                "`dst` must be properly aligned",
+                loc,
+            ),
         );
 
         // Check that computing `count` in bytes would not overflow
@@ -1841,11 +2158,14 @@ pub fn count_in_bytes(
         let size_of_count_elems = count.mul_overflow(size_of_elem);
         let message =
             format!("{intrinsic}: attempt to compute number in bytes which would overflow");
-        let assert_stmt = self.codegen_assert_assume(
-            size_of_count_elems.overflowed.not(),
-            PropertyClass::ArithmeticOverflow,
-            message.as_str(),
-            loc,
+        let assert_stmt = self.codegen_builtin_check(
+            BuiltinCheck::ArithmeticOverflow,
+            self.codegen_assert_assume(
+                size_of_count_elems.overflowed.not(),
+                PropertyClass::ArithmeticOverflow,
+                message.as_str(),
+                loc,
+            ),
         );
         (size_of_count_elems.result, assert_stmt)
     }
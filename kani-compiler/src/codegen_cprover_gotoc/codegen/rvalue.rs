@@ -16,9 +16,11 @@
     Stmt, Type, arithmetic_overflow_result_type,
 };
 use cbmc::{InternString, InternedString, btree_string_map};
+use kani_metadata::BuiltinCheck;
 use num::bigint::BigInt;
 use rustc_middle::ty::{ParamEnv, TyCtxt, VtblEntry};
 use rustc_smir::rustc_internal;
+use rustc_span::sym;
 use rustc_target::abi::{FieldsShape, TagEncoding, Variants};
 use stable_mir::abi::{Primitive, Scalar, ValueAbi};
 use stable_mir::mir::mono::Instance;
@@ -29,6 +31,11 @@
 use std::collections::BTreeMap;
 use tracing::{debug, trace, warn};
 
+/// The prefix of the flags that record the concrete errors converted into a `dyn Error`.
+///
+/// This must match the prefix used by the driver to render these errors in a trace.
+const DYN_ERROR_MARKER: &str = "kani::error_type";
+
 impl GotocCtx<'_> {
     fn codegen_comparison(&mut self, op: &BinOp, e1: &Operand, e2: &Operand) -> Expr {
         let left_op = self.codegen_operand_stable(e1);
@@ -207,14 +214,17 @@ fn codegen_binop_with_overflow_check(
         // store the result in a temporary variable
         let (var, decl) = self.decl_temp_variable(struct_tag, Some(res), loc);
         // cast into result type
-        let check = self.codegen_assert(
-            var.clone()
-                .member(ARITH_OVERFLOW_OVERFLOWED_FIELD, &self.symbol_table)
-                .cast_to(Type::c_bool())
-                .not(),
-            PropertyClass::ArithmeticOverflow,
-            format!("attempt to compute `{op_name}` which would overflow").as_str(),
-            loc,
+        let check = self.codegen_builtin_check(
+            BuiltinCheck::ArithmeticOverflow,
+            self.codegen_assert(
+                var.clone()
+                    .member(ARITH_OVERFLOW_OVERFLOWED_FIELD, &self.symbol_table)
+                    .cast_to(Type::c_bool())
+                    .not(),
+                PropertyClass::ArithmeticOverflow,
+                format!("attempt to compute `{op_name}` which would overflow").as_str(),
+                loc,
+            ),
         );
         Expr::statement_expression(
             vec![
@@ -420,23 +430,36 @@ fn codegen_rvalue_binary_op(
                 // https://doc.rust-lang.org/std/primitive.pointer.html#method.offset
                 // These checks may allow a wrapping-around behavior in CBMC:
                 // https://github.com/model-checking/kani/issues/1150
-                // Note(std): We don't check that the starting or resulting pointer stay
-                // within bounds of the object they point to. Doing so causes spurious
+                // Note(std): By default, we don't check that the starting or resulting pointer
+                // stay within bounds of the object they point to. Doing so causes spurious
                 // failures due to the usage of these intrinsics in the standard library.
                 // See <https://github.com/model-checking/kani/issues/1233> for more details.
                 // Note that this is one of the safety conditions for `offset`:
                 // <https://doc.rust-lang.org/std/primitive.pointer.html#safety-2>
+                // It is checked with `-Z pointer-offset-checks`.
+                let in_bounds_check =
+                    self.codegen_offset_in_bounds_check(ce1.clone(), offset_bytes.clone(), loc);
 
                 let overflow_res = ce1.clone().cast_to(Type::ssize_t()).add_overflow(offset_bytes);
-                let overflow_check = self.codegen_assert_assume(
-                    overflow_res.overflowed.not(),
-                    PropertyClass::ArithmeticOverflow,
-                    "attempt to compute offset which would overflow",
-                    loc,
+                let overflow_check = self.codegen_builtin_check(
+                    BuiltinCheck::PointerOverflow,
+                    self.codegen_assert_assume(
+                        overflow_res.overflowed.not(),
+                        PropertyClass::ArithmeticOverflow,
+                        "attempt to compute offset which would overflow",
+                        loc,
+                    ),
                 );
                 let res = ce1.clone().plus(ce2);
+                let bounds_check = self.codegen_segmented_bounds_check(res.clone(), loc);
                 Expr::statement_expression(
-                    vec![bytes_overflow_check, overflow_check, res.as_stmt(loc)],
+                    vec![
+                        bytes_overflow_check,
+                        overflow_check,
+                        in_bounds_check,
+                        bounds_check,
+                        res.as_stmt(loc),
+                    ],
                     ce1.typ().clone(),
                     loc,
                 )
@@ -462,11 +485,14 @@ fn check_div_overflow(
         } else {
             "attempt to divide by zero"
         };
-        let div_by_zero_check = self.codegen_assert_assume(
-            divisor_expr.clone().is_zero().not(),
-            PropertyClass::ArithmeticOverflow,
-            msg,
-            loc,
+        let div_by_zero_check = self.codegen_builtin_check(
+            BuiltinCheck::DivByZero,
+            self.codegen_assert_assume(
+                divisor_expr.clone().is_zero().not(),
+                PropertyClass::ArithmeticOverflow,
+                msg,
+                loc,
+            ),
         );
         if self.operand_ty_stable(dividend).kind().is_signed() {
             let dividend_expr = self.codegen_operand_stable(dividend);
@@ -479,11 +505,14 @@ fn check_div_overflow(
                 .clone()
                 .eq(dividend_expr.typ().min_int_expr(self.symbol_table.machine_model()))
                 .and(divisor_expr.clone().eq(Expr::int_constant(-1, divisor_expr.typ().clone())));
-            let overflow_check = self.codegen_assert_assume(
-                overflow_expr.not(),
-                PropertyClass::ArithmeticOverflow,
-                overflow_msg,
-                loc,
+            let overflow_check = self.codegen_builtin_check(
+                BuiltinCheck::ArithmeticOverflow,
+                self.codegen_assert_assume(
+                    overflow_expr.not(),
+                    PropertyClass::ArithmeticOverflow,
+                    overflow_msg,
+                    loc,
+                ),
             );
             Stmt::block(vec![overflow_check, div_by_zero_check], loc)
         } else {
@@ -1184,7 +1213,10 @@ fn codegen_pointer_cast(
         match coercion {
             PointerCoercion::ReifyFnPointer => match self.operand_ty_stable(operand).kind() {
                 TyKind::RigidTy(RigidTy::FnDef(def, args)) => {
-                    let instance = Instance::resolve(def, &args).unwrap();
+                    // Use the same instance as the reachability analysis, which may be a shim,
+                    // e.g., for `#[track_caller]` functions. Otherwise, the body of the function
+                    // may be missing when it's only called through the pointer.
+                    let instance = Instance::resolve_for_fn_ptr(def, &args).unwrap();
                     // We need to handle this case in a special way because `codegen_operand_stable` compiles FnDefs to dummy structs.
                     // (cf. the function documentation)
                     self.codegen_func_expr(instance, loc).address_of()
@@ -1526,6 +1558,39 @@ fn codegen_vtable(&mut self, src_mir_type: Ty, dst_mir_type: Ty, loc: Location)
         .to_expr()
     }
 
+    /// Whether the given type is a trait object of `core::error::Error`, e.g. `dyn Error + Send`.
+    fn is_error_trait_object(&self, ty: Ty) -> bool {
+        let rustc_middle::ty::Dynamic(predicates, ..) =
+            rustc_internal::internal(self.tcx, ty).kind()
+        else {
+            return false;
+        };
+        predicates
+            .principal_def_id()
+            .is_some_and(|principal| self.tcx.is_diagnostic_item(sym::Error, principal))
+    }
+
+    /// Record that a value of type `src_ty` was converted into a `dyn Error`.
+    ///
+    /// Each concrete error type gets a global flag that is set whenever a value of that type is
+    /// unsized into an error trait object, e.g. by `Box::<dyn Error>::from` when `?` propagates
+    /// an error. The driver uses the assignments to this flag in a trace to render the concrete
+    /// errors that were produced.
+    fn codegen_mark_error_type(&mut self, fat_ptr: Expr, src_ty: Ty, loc: Location) -> Expr {
+        let name = format!("{DYN_ERROR_MARKER}::<{}>", self.pretty_ty(src_ty));
+        let marker = self
+            .ensure_global_var_init(&name, false, false, Type::bool(), loc, |_, _| {
+                Expr::bool_false()
+            })
+            .to_expr();
+        let typ = fat_ptr.typ().clone();
+        Expr::statement_expression(
+            vec![marker.assign(Expr::bool_true(), loc), fat_ptr.as_stmt(loc)],
+            typ,
+            loc,
+        )
+    }
+
     /// Cast a pointer to a fat pointer.
     /// The fat pointer will have two elements:
     ///  1. `data` which will point to the same address as the source object.
@@ -1588,7 +1653,13 @@ fn codegen_cast_to_fat_pointer(
                 let dst_data_expr = src_goto_expr.cast_to(dst_data_type);
                 let vtable = self.codegen_vtable(metadata_src_type, metadata_dst_type, loc);
                 let vtable_expr = vtable.address_of();
-                dynamic_fat_ptr(fat_ptr_type, dst_data_expr, vtable_expr, &self.symbol_table)
+                let fat_ptr =
+                    dynamic_fat_ptr(fat_ptr_type, dst_data_expr, vtable_expr, &self.symbol_table);
+                if self.is_error_trait_object(metadata_dst_type) {
+                    self.codegen_mark_error_type(fat_ptr, metadata_src_type, loc)
+                } else {
+                    fat_ptr
+                }
             }
             (src_kind, dst_kind) => {
                 unreachable!("Unexpected unsized cast from type {:?} to {:?}", src_kind, dst_kind)
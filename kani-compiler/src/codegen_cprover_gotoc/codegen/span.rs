@@ -4,7 +4,9 @@
 //! MIR Span related functions
 
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::kani_middle::user_written_span;
 use cbmc::goto_program::Location;
+use kani_metadata::BuiltinCheck;
 use lazy_static::lazy_static;
 use rustc_ast::Attribute;
 use rustc_smir::rustc_internal;
@@ -64,6 +66,7 @@ pub fn codegen_span_stable(&self, sp: SpanStable) -> Location {
                         PRAGMAS.keys()
                     ).as_str())
                 })
+                .chain(self.disabled_checks.iter().flat_map(|check| cbmc_pragmas(*check)).copied())
                 .collect::<Vec<_>>()
                 .leak() // This is to preserve `Location` being Copy, but could blow up the memory utilization of compiler. 
         };
@@ -79,6 +82,14 @@ pub fn codegen_span_stable(&self, sp: SpanStable) -> Location {
         )
     }
 
+    /// Get the location of a property. If the property comes from the expansion of a macro
+    /// invoked in the local crate, this is the location of the outermost invocation.
+    /// See [user_written_span] for more details.
+    pub fn codegen_property_span_stable(&self, sp: SpanStable) -> Location {
+        let span = rustc_internal::internal(self.tcx, sp);
+        self.codegen_span(&user_written_span(self.tcx, span))
+    }
+
     pub fn codegen_caller_span_stable(&self, sp: SpanStable) -> Location {
         self.codegen_caller_span(&rustc_internal::internal(self.tcx, sp))
     }
@@ -93,6 +104,20 @@ pub fn codegen_caller_span(&self, span: &Span) -> Location {
     }
 }
 
+/// The pragmas that disable the checks instrumented by CBMC that correspond to a built-in check.
+fn cbmc_pragmas(check: BuiltinCheck) -> &'static [&'static str] {
+    match check {
+        BuiltinCheck::PointerOverflow => &["disable:pointer-overflow-check"],
+        BuiltinCheck::DivByZero => {
+            &["disable:div-by-zero-check", "disable:float-div-by-zero-check"]
+        }
+        BuiltinCheck::FloatOverflow => &["disable:float-overflow-check"],
+        BuiltinCheck::Nan => &["disable:nan-check"],
+        // These checks are generated by Kani.
+        BuiltinCheck::ArithmeticOverflow | BuiltinCheck::UnalignedAccess => &[],
+    }
+}
+
 /// Extracts the single argument from the attribute provided as a string.
 /// For example, `disable_checks(foo)` return `Some("foo")`
 fn parse_word(attr: &Attribute) -> Option<String> {
@@ -3,10 +3,13 @@
 use super::typ::FN_RETURN_VOID_VAR_NAME;
 use super::typ::TypeExt;
 use super::{PropertyClass, bb_label};
+use crate::args::ArithmeticOverflow;
 use crate::codegen_cprover_gotoc::codegen::function::rustc_smir::region_from_coverage_opaque;
 use crate::codegen_cprover_gotoc::{GotocCtx, VtableCtx};
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
+use cbmc::InternedString;
 use cbmc::goto_program::{Expr, Location, Stmt, Type};
+use kani_metadata::BuiltinCheck;
 use rustc_middle::ty::layout::LayoutOf;
 use rustc_middle::ty::{List, ParamEnv};
 use rustc_smir::rustc_internal;
@@ -246,37 +249,75 @@ pub fn codegen_terminator(&mut self, term: &Terminator) -> Stmt {
                 self.codegen_funcall(func, args, destination, target, term.span)
             }
             TerminatorKind::Assert { cond, expected, msg, target, .. } => {
+                let is_overflow =
+                    matches!(msg, AssertMessage::Overflow(..) | AssertMessage::OverflowNeg(..));
+                let builtin_check = match msg {
+                    AssertMessage::Overflow(..) | AssertMessage::OverflowNeg(..) => {
+                        Some(BuiltinCheck::ArithmeticOverflow)
+                    }
+                    AssertMessage::DivisionByZero(..) | AssertMessage::RemainderByZero(..) => {
+                        Some(BuiltinCheck::DivByZero)
+                    }
+                    AssertMessage::MisalignedPointerDereference { .. } => {
+                        Some(BuiltinCheck::UnalignedAccess)
+                    }
+                    _ => None,
+                };
+                if builtin_check.is_some_and(|check| self.disabled_checks.contains(&check)) {
+                    // The check was disabled for this harness, so the execution always continues.
+                    return Stmt::goto(bb_label(*target), loc);
+                }
+                let property_class = match self.arithmetic_overflow {
+                    ArithmeticOverflow::Wrap if is_overflow => {
+                        // The result of the operation already wraps around.
+                        return Stmt::goto(bb_label(*target), loc);
+                    }
+                    ArithmeticOverflow::Check if is_overflow => PropertyClass::ArithmeticOverflow,
+                    _ if matches!(msg, AssertMessage::BoundsCheck { .. }) => {
+                        PropertyClass::IndexOutOfBounds
+                    }
+                    _ => PropertyClass::Assertion,
+                };
                 let cond = {
                     let r = self.codegen_operand_stable(cond);
                     if *expected { r } else { Expr::not(r) }
                 };
 
-                let msg = if let AssertMessage::BoundsCheck { .. } = msg {
+                let msg = if let AssertMessage::BoundsCheck { index, .. } = msg {
                     // For bounds check the following panic message is generated at runtime:
                     // "index out of bounds: the length is {len} but the index is {index}",
                     // but CBMC only accepts static messages so we don't add values to the message.
-                    "index out of bounds: the length is less than or equal to the given index"
+                    // Instead, we name the index if it's a user variable, so the property can be
+                    // matched with the value of the variable in the trace.
+                    let msg =
+                        "index out of bounds: the length is less than or equal to the given index";
+                    match self.index_variable_name(index) {
+                        Some(name) => format!("{msg} `{name}`"),
+                        None => msg.to_string(),
+                    }
                 } else if let AssertMessage::MisalignedPointerDereference { .. } = msg {
                     // Misaligned pointer dereference check messages is also a runtime messages.
                     // Generate a generic one here.
                     "misaligned pointer dereference: address must be a multiple of its type's \
                     alignment"
+                        .to_string()
                 } else {
                     // For all other assert kind we can get the static message.
-                    msg.description().unwrap()
+                    msg.description().unwrap().to_string()
                 };
 
-                let (msg_str, reach_stmt) =
-                    self.codegen_reachability_check(msg.to_owned(), term.span);
+                let (msg_str, reach_stmt) = self.codegen_reachability_check(msg, term.span);
+                // Report the property at the macro invocation written by the user, if any.
+                let property_loc = self.codegen_property_span_stable(term.span);
 
                 Stmt::block(
                     vec![
                         reach_stmt,
                         self.codegen_assert_assume(
                             cond.cast_to(Type::bool()),
-                            PropertyClass::Assertion,
+                            property_class,
                             &msg_str,
-                            loc,
+                            property_loc,
                         ),
                         Stmt::goto(bb_label(*target), loc),
                     ],
@@ -380,6 +421,16 @@ fn codegen_deinit(&mut self, place: &Place, loc: Location) -> Stmt {
         }
     }
 
+    /// The name of the user variable used as the index of a bounds check, if any.
+    fn index_variable_name(&self, index: &Operand) -> Option<InternedString> {
+        match index {
+            Operand::Copy(place) | Operand::Move(place) if place.projection.is_empty() => {
+                self.current_fn().local_name(place.local)
+            }
+            _ => None,
+        }
+    }
+
     /// A special case handler to codegen `return ();`
     fn codegen_ret_unit(&mut self, loc: Location) -> Stmt {
         let is_file_local = false;
@@ -627,6 +678,9 @@ fn codegen_funcall(
                 if let Some(hk) = self.hooks.hook_applies(self.tcx, instance) {
                     return hk.handle(self, instance, fargs, destination, *target, span);
                 }
+                if self.requires_caller_location(instance) {
+                    fargs.push(self.codegen_caller_location(span));
+                }
 
                 let mut stmts: Vec<Stmt> = match instance.kind {
                     // Here an empty drop glue is invoked; we just ignore it.
@@ -1044,7 +1044,7 @@ fn codegen_dynamic_function_sig(&mut self, instance: InstanceStable) -> Type {
         let mut is_first = true;
         let fn_abi = instance.fn_abi().unwrap();
         let args = self.codegen_args(instance, &fn_abi);
-        let params = args
+        let mut params: Vec<_> = args
             .map(|(_, arg_abi)| {
                 let arg_ty_stable = arg_abi.ty;
                 let kind = arg_ty_stable.kind();
@@ -1069,6 +1069,9 @@ fn codegen_dynamic_function_sig(&mut self, instance: InstanceStable) -> Type {
                 }
             })
             .collect();
+        if self.requires_caller_location(instance) {
+            params.push(self.codegen_ty(self.tcx.caller_location_ty()));
+        }
 
         Type::code_with_unnamed_parameters(params, self.codegen_ty_stable(fn_abi.ret.ty))
     }
@@ -1469,7 +1472,7 @@ fn codegen_vector(&mut self, ty: Ty<'tcx>) -> Type {
     /// the function type of the current instance
     pub fn fn_typ(&mut self, instance: InstanceStable, body: &Body) -> Type {
         let fn_abi = instance.fn_abi().unwrap();
-        let params: Vec<Parameter> = self
+        let mut params: Vec<Parameter> = self
             .codegen_args(instance, &fn_abi)
             .filter_map(|(i, arg_abi)| {
                 let ty = arg_abi.ty;
@@ -1501,6 +1504,11 @@ pub fn fn_typ(&mut self, instance: InstanceStable, body: &Body) -> Type {
                 }
             })
             .collect();
+        if self.requires_caller_location(instance) {
+            let (name, base_name) = self.codegen_caller_location_name();
+            let typ = self.codegen_ty(self.tcx.caller_location_ty());
+            params.push(typ.as_parameter(Some(name.into()), Some(base_name.into())));
+        }
 
         debug!(?params, ?fn_abi, "function_type");
         let ret_type = self.codegen_ty_stable(fn_abi.ret.ty);
@@ -1648,8 +1656,9 @@ fn next(&mut self) -> Option<Self::Item> {
     /// Allow us to retrieve the instance arguments in a consistent way.
     /// There are two corner cases that we currently handle:
     /// 1. In some cases, an argument can be ignored (e.g.: ZST arguments in regular Rust calls).
-    /// 2. We currently don't support `track_caller`, so we ignore the extra argument that is added to support that.
-    ///    Tracked here: <https://github.com/model-checking/kani/issues/374>
+    /// 2. Functions annotated with `#[track_caller]` have an extra argument with the location of
+    ///    their caller, which doesn't correspond to any MIR local. It is ignored here, and
+    ///    declared separately, see [GotocCtx::codegen_caller_location].
     pub fn codegen_args<'a>(
         &self,
         instance: InstanceStable,
@@ -5,16 +5,19 @@
 
 use crate::args::ReachabilityType;
 use crate::codegen_cprover_gotoc::GotocCtx;
+use crate::codegen_cprover_gotoc::utils::{collect_properties, model_fingerprint};
 use crate::kani_middle::analysis;
 use crate::kani_middle::attributes::{KaniAttributes, is_test_harness_description};
 use crate::kani_middle::check_reachable_items;
 use crate::kani_middle::codegen_units::{CodegenUnit, CodegenUnits};
 use crate::kani_middle::metadata::gen_test_metadata;
 use crate::kani_middle::provide;
+use crate::kani_middle::pruning::report_pruned;
 use crate::kani_middle::reachability::{
     collect_reachable_items, filter_const_crate_items, filter_crate_items,
 };
-use crate::kani_middle::transform::{BodyTransformation, GlobalPasses};
+use crate::kani_middle::reproducer::export_reproducer;
+use crate::kani_middle::transform::{BodyTransformation, GlobalPasses, assumed_ok_fns};
 use crate::kani_queries::QueryDb;
 use cbmc::RoundingMode;
 use cbmc::goto_program::Location;
@@ -54,7 +57,7 @@
 use std::process::Command;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
-use tracing::{debug, error, info};
+use tracing::{debug, debug_span, error, info};
 
 pub type UnsupportedConstructs = FxHashMap<InternedString, Vec<Location>>;
 
@@ -130,6 +133,14 @@ fn codegen_items<'tcx>(
         // https://rustc-dev-guide.rust-lang.org/conventions.html#naming-conventions
         let mut gcx =
             GotocCtx::new(tcx, (*self.queries.lock().unwrap()).clone(), machine_model, transformer);
+        // A harness may override how arithmetic overflow is modeled, and disable built-in checks.
+        if let [MonoItem::Fn(harness)] = starting_items {
+            let attributes = KaniAttributes::for_instance(tcx, *harness);
+            if let Some(mode) = attributes.arithmetic_overflow() {
+                gcx.arithmetic_overflow = mode;
+            }
+            gcx.disabled_checks = attributes.disabled_checks();
+        }
         check_reachable_items(gcx.tcx, &gcx.queries, &items);
 
         let contract_info = with_timer(
@@ -263,30 +274,59 @@ fn codegen_crate(
                 ReachabilityType::Harnesses => {
                     let mut units = CodegenUnits::new(&queries, tcx);
                     let mut modifies_instances = vec![];
+                    let mut harness_properties = vec![];
+                    let mut harness_type_states = vec![];
+                    let mut harness_fingerprints = vec![];
+                    let mut harness_assumed_ok = vec![];
                     // Cross-crate collecting of all items that are reachable from the crate harnesses.
-                    for unit in units.iter() {
-                        // We reset the body cache for now because each codegen unit has different
-                        // configurations that affect how we transform the instance body.
-                        for harness in &unit.harnesses {
-                            let transformer = BodyTransformation::new(&queries, tcx, &unit);
-                            let model_path = units.harness_model_path(*harness).unwrap();
-                            let contract_metadata =
-                                contract_metadata_for_harness(tcx, harness.def.def_id());
-                            let (gcx, items, contract_info) = self.codegen_items(
-                                tcx,
-                                &[MonoItem::Fn(*harness)],
-                                model_path,
-                                &results.machine_model,
-                                contract_metadata,
-                                transformer,
-                            );
-                            results.extend(gcx, items, None);
-                            if let Some(assigns_contract) = contract_info {
-                                modifies_instances.push((*harness, assigns_contract));
+                    // When only listing the harnesses, their metadata is all we need.
+                    let skip_models = queries.args().no_models;
+                    if !skip_models {
+                        for unit in units.iter() {
+                            // We reset the body cache for now because each codegen unit has different
+                            // configurations that affect how we transform the instance body.
+                            for harness in &unit.harnesses {
+                                let _guard =
+                                    debug_span!("codegen_harness", harness = harness.name())
+                                        .entered();
+                                let transformer = BodyTransformation::new(&queries, tcx, &unit);
+                                let model_path = units.harness_model_path(*harness).unwrap();
+                                let contract_metadata =
+                                    contract_metadata_for_harness(tcx, harness.def.def_id());
+                                let (gcx, items, contract_info) = self.codegen_items(
+                                    tcx,
+                                    &[MonoItem::Fn(*harness)],
+                                    model_path,
+                                    &results.machine_model,
+                                    contract_metadata,
+                                    transformer,
+                                );
+                                harness_properties
+                                    .push((*harness, collect_properties(&gcx.symbol_table)));
+                                harness_type_states.push((*harness, gcx.type_states.clone()));
+                                harness_fingerprints
+                                    .push((*harness, model_fingerprint(model_path)));
+                                if queries.args().assume_ok {
+                                    harness_assumed_ok
+                                        .push((*harness, assumed_ok_fns(tcx, &items)));
+                                }
+                                if let Some(dir) = &queries.args().export_reproducer {
+                                    export_reproducer(tcx, *harness, &items, dir);
+                                }
+                                report_pruned(tcx, &unit.policy, *harness, &items);
+                                results.extend(gcx, items, None);
+                                if let Some(assigns_contract) = contract_info {
+                                    modifies_instances.push((*harness, assigns_contract));
+                                }
                             }
                         }
                     }
                     units.store_modifies(&modifies_instances);
+                    units.store_properties(harness_properties);
+                    units.store_type_states(harness_type_states);
+                    units.store_fingerprints(harness_fingerprints);
+                    units.store_assumed_ok(harness_assumed_ok);
+                    units.store_unsupported_features(results.unsupported_features());
                     units.write_metadata(&queries, tcx);
                 }
                 ReachabilityType::Tests => {
@@ -454,7 +494,7 @@ fn new_archive_builder<'a>(&self, sess: &'a Session) -> Box<dyn ArchiveBuilder +
 
 fn contract_metadata_for_harness(tcx: TyCtxt, def_id: DefId) -> Option<InternalDefId> {
     let attrs = KaniAttributes::for_def_id(tcx, def_id);
-    attrs.interpret_for_contract_attribute().map(|(_, id, _)| id)
+    attrs.checked_contract_fn()
 }
 
 fn check_target(session: &Session) {
@@ -609,12 +649,11 @@ pub fn new(tcx: TyCtxt, reachability: ReachabilityType) -> Self {
             machine_model: new_machine_model(tcx.sess),
         }
     }
-    /// Method that generates `KaniMetadata` from the given compilation results.
-    pub fn generate_metadata(&self) -> KaniMetadata {
-        // Maps the goto-context "unsupported features" data into the KaniMetadata "unsupported features" format.
-        // TODO: Do we really need different formats??
-        let unsupported_features = self
-            .unsupported_constructs
+    /// Maps the goto-context "unsupported features" data into the KaniMetadata "unsupported
+    /// features" format.
+    // TODO: Do we really need different formats??
+    pub fn unsupported_features(&self) -> Vec<UnsupportedFeature> {
+        self.unsupported_constructs
             .iter()
             .map(|(construct, location)| UnsupportedFeature {
                 feature: construct.to_string(),
@@ -631,7 +670,12 @@ pub fn generate_metadata(&self) -> KaniMetadata {
                     })
                     .collect(),
             })
-            .collect();
+            .collect()
+    }
+
+    /// Method that generates `KaniMetadata` from the given compilation results.
+    pub fn generate_metadata(&self) -> KaniMetadata {
+        let unsupported_features = self.unsupported_features();
         let (proofs, tests) = if self.reachability == ReachabilityType::Harnesses {
             (self.harnesses.clone(), vec![])
         } else {
@@ -691,6 +735,10 @@ fn print_report(&self, tcx: TyCtxt) {
             for (construct, locations) in self.concurrent_constructs.iter() {
                 writeln!(&mut msg, "    - {construct} ({})", locations.len()).unwrap();
             }
+            if self.concurrent_constructs.keys().any(|construct| construct.map(has_weak_ordering)) {
+                msg += "\nThe `Relaxed`, `Acquire`, `Release` and `AcqRel` memory orderings of \
+                atomic operations are approximated as `SeqCst`.";
+            }
             tcx.dcx().warn(msg);
         }
 
@@ -701,6 +749,15 @@ fn print_report(&self, tcx: TyCtxt) {
     }
 }
 
+/// Whether the construct is an atomic intrinsic with an ordering weaker than `SeqCst`, e.g.,
+/// `atomic_load_relaxed` or `atomic_cxchg_release_seqcst`.
+fn has_weak_ordering(construct: &str) -> bool {
+    construct.starts_with("atomic_")
+        && construct
+            .split('_')
+            .any(|part| matches!(part, "relaxed" | "acquire" | "release" | "acqrel"))
+}
+
 /// Builds a machine model which is required by CBMC
 fn new_machine_model(sess: &Session) -> MachineModel {
     // The model assumes a `x86_64-unknown-linux-gnu`, `x86_64-apple-darwin`
@@ -16,6 +16,7 @@
 //! this structure as input.
 use super::current_fn::CurrentFnCtx;
 use super::vtable_ctx::VtableCtx;
+use crate::args::ArithmeticOverflow;
 use crate::codegen_cprover_gotoc::UnsupportedConstructs;
 use crate::codegen_cprover_gotoc::overrides::{GotocHooks, fn_hooks};
 use crate::codegen_cprover_gotoc::utils::full_crate_name;
@@ -26,6 +27,7 @@
 };
 use cbmc::utils::aggr_tag;
 use cbmc::{InternedString, MachineModel};
+use kani_metadata::{BuiltinCheck, TypeStateVariable};
 use rustc_data_structures::fx::FxHashMap;
 use rustc_middle::span_bug;
 use rustc_middle::ty::layout::{
@@ -74,6 +76,12 @@ pub struct GotocCtx<'tcx> {
     pub concurrent_constructs: UnsupportedConstructs,
     /// The body transformation agent.
     pub transformer: BodyTransformation,
+    /// How arithmetic overflow is modeled in the code being generated.
+    pub arithmetic_overflow: ArithmeticOverflow,
+    /// The built-in checks that are not generated for the harness being codegen'd.
+    pub disabled_checks: Vec<BuiltinCheck>,
+    /// The user variables whose type carries zero-sized type parameters.
+    pub type_states: Vec<TypeStateVariable>,
 }
 
 /// Constructor
@@ -87,6 +95,7 @@ pub fn new(
         let fhks = fn_hooks();
         let symbol_table = SymbolTable::new(machine_model.clone());
         let emit_vtable_restrictions = queries.args().emit_vtable_restrictions;
+        let arithmetic_overflow = queries.args().arithmetic_overflow;
         GotocCtx {
             tcx,
             queries,
@@ -103,6 +112,9 @@ pub fn new(
             unsupported_constructs: FxHashMap::default(),
             concurrent_constructs: FxHashMap::default(),
             transformer,
+            arithmetic_overflow,
+            disabled_checks: vec![],
+            type_states: vec![],
         }
     }
 }
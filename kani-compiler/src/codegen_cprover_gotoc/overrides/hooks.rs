@@ -12,15 +12,18 @@
 use crate::codegen_cprover_gotoc::codegen::{PropertyClass, bb_label};
 use crate::kani_middle::attributes::KaniAttributes;
 use crate::kani_middle::attributes::matches_diagnostic as matches_function;
+use crate::kani_middle::resolve::resolve_fn;
 use crate::unwrap_or_return_codegen_unimplemented_stmt;
 use cbmc::goto_program::CIntType;
 use cbmc::goto_program::{BuiltinFn, Expr, Stmt, Type};
+use rustc_hir::def_id::{CRATE_DEF_ID, DefId};
 use rustc_middle::ty::TyCtxt;
 use rustc_smir::rustc_internal;
-use rustc_span::Symbol;
+use rustc_span::{Symbol, sym};
 use stable_mir::mir::mono::Instance;
 use stable_mir::mir::{BasicBlockIdx, Place};
 use stable_mir::{CrateDef, ty::Span};
+use std::cell::OnceCell;
 use std::rc::Rc;
 use tracing::debug;
 
@@ -245,6 +248,82 @@ fn handle(
     }
 }
 
+/// A hook for Kani's `unreachable` function, which is called by the `kani::must_not_reach!` macro.
+/// The function takes the message of the failure as its only argument.
+/// Reaching the call is a verification failure, which has the `unreachable` property class so it
+/// is not counted as a panic by `#[kani::should_panic]` harnesses.
+struct Unreachable;
+impl GotocHook for Unreachable {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance.def, "KaniUnreachable")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        fargs: Vec<Expr>,
+        _assign_to: &Place,
+        _target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 1);
+        let msg = gcx.extract_const_message(&fargs[0]).unwrap();
+        let caller_loc = gcx.codegen_caller_span_stable(span);
+
+        let (msg, reach_stmt) = gcx.codegen_reachability_check(msg, span);
+
+        Stmt::block(
+            vec![
+                reach_stmt,
+                gcx.codegen_assert_assume_false(PropertyClass::Unreachable, &msg, caller_loc),
+            ],
+            caller_loc,
+        )
+    }
+}
+
+/// A hook for Kani's `implies` function (declared in `library/kani_core/src/lib.rs`).
+///
+/// The hook encodes the call as a single CBMC implication on the arguments, which have already
+/// been evaluated by the caller. Thus, there is no branch on the premise, and nesting calls
+/// produces a single expression.
+struct Implies;
+impl GotocHook for Implies {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        matches_function(tcx, instance.def, "KaniImplies")
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        _instance: Instance,
+        mut fargs: Vec<Expr>,
+        assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        assert_eq!(fargs.len(), 2);
+        let conclusion = fargs.pop().unwrap();
+        let premise = fargs.pop().unwrap();
+        let target = target.unwrap();
+        let loc = gcx.codegen_caller_span_stable(span);
+        let ret_place = unwrap_or_return_codegen_unimplemented_stmt!(
+            gcx,
+            gcx.codegen_place_stable(assign_to, loc)
+        );
+        let ret_type = ret_place.goto_expr.typ().clone();
+
+        Stmt::block(
+            vec![
+                ret_place.goto_expr.assign(premise.implies(conclusion).cast_to(ret_type), loc),
+                Stmt::goto(bb_label(target), loc),
+            ],
+            loc,
+        )
+    }
+}
+
 /// Encodes __CPROVER_r_ok(ptr, size)
 struct IsAllocated;
 impl GotocHook for IsAllocated {
@@ -396,6 +475,73 @@ fn handle(
     }
 }
 
+/// The functions of the standard library that register the destructor of a thread-local
+/// variable, which run it when the thread exits. Only the ones of the target platform exist.
+///
+/// These functions are private, so they don't have a diagnostic item. We resolve their paths
+/// instead, and fail if none of them exists, since the destructors would be registered through
+/// the platform support otherwise.
+const TLS_DTOR_REGISTER: [&str; 2] = [
+    "::std::sys::thread_local::destructors::linux_like::register",
+    "::std::sys::thread_local::destructors::list::register",
+];
+
+/// This hook skips the registration of the destructor of a thread-local variable.
+///
+/// The harness runs in a single thread, so thread-local variables are modeled like statics:
+/// they are lazily initialized by the standard library on their first access, and they are never
+/// dropped. Registering the destructor would otherwise go through the platform support for
+/// thread-exit callbacks, which reads weak foreign symbols that Kani doesn't model.
+#[derive(Default)]
+struct ThreadLocalDtorRegister {
+    /// The registration functions of the target, resolved the first time the hook is queried.
+    register_fns: OnceCell<Vec<DefId>>,
+}
+
+impl ThreadLocalDtorRegister {
+    fn register_fns(&self, tcx: TyCtxt) -> &[DefId] {
+        self.register_fns.get_or_init(|| {
+            // Crates that don't use `std` can't register thread-local destructors.
+            if !tcx.used_crates(()).iter().any(|krate| tcx.crate_name(*krate) == sym::std) {
+                return vec![];
+            }
+            let register_fns: Vec<_> = TLS_DTOR_REGISTER
+                .iter()
+                .filter_map(|path| resolve_fn(tcx, CRATE_DEF_ID, path).ok())
+                .collect();
+            if register_fns.is_empty() {
+                tcx.dcx().fatal(format!(
+                    "Kani couldn't find the function of the standard library that registers the \
+                    destructor of a thread-local variable. Expected one of: `{}`",
+                    TLS_DTOR_REGISTER.join("`, `")
+                ));
+            }
+            register_fns
+        })
+    }
+}
+
+impl GotocHook for ThreadLocalDtorRegister {
+    fn hook_applies(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        self.register_fns(tcx).contains(&def_id)
+    }
+
+    fn handle(
+        &self,
+        gcx: &mut GotocCtx,
+        instance: Instance,
+        _fargs: Vec<Expr>,
+        _assign_to: &Place,
+        target: Option<BasicBlockIdx>,
+        span: Span,
+    ) -> Stmt {
+        debug!(?instance, "Skip thread-local destructor registration");
+        let loc = gcx.codegen_span_stable(span);
+        Stmt::goto(bb_label(target.unwrap()), loc)
+    }
+}
+
 /// This hook intercepts calls to `memcmp` and skips CBMC's pointer checks if the number of bytes to be compared is zero.
 /// See issue <https://github.com/model-checking/kani/issues/1489>
 ///
@@ -593,10 +739,12 @@ pub fn fn_hooks() -> GotocHooks {
     GotocHooks {
         hooks: vec![
             Rc::new(Panic),
+            Rc::new(Unreachable),
             Rc::new(Assume),
             Rc::new(Assert),
             Rc::new(Check),
             Rc::new(Cover),
+            Rc::new(Implies),
             Rc::new(Nondet),
             Rc::new(IsAllocated),
             Rc::new(PointerObject),
@@ -606,6 +754,7 @@ pub fn fn_hooks() -> GotocHooks {
             Rc::new(UntrackedDeref),
             Rc::new(InitContracts),
             Rc::new(LoopInvariantRegister),
+            Rc::new(ThreadLocalDtorRegister::default()),
         ],
     }
 }
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Compute a fingerprint of the model generated for a harness, so the driver can tell whether
+//! a harness has to be verified again.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::path::Path;
+
+/// Compute the fingerprint of the goto model stored in the given file.
+///
+/// The model contains the code of every item that is reachable from the harness after stubs,
+/// contracts and other transformations are applied, so any change to the harness dependencies
+/// results in a different fingerprint.
+///
+/// Returns `None` if the model wasn't generated, e.g., if code generation was disabled.
+pub fn model_fingerprint(model_path: &Path) -> Option<String> {
+    let model = std::fs::read(model_path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    hasher.write(&model);
+    Some(format!("{:016x}", hasher.finish()))
+}
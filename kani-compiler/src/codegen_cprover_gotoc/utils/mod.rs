@@ -4,13 +4,17 @@
 //! This module provides utils used across Kani
 
 mod debug;
+mod fingerprint;
 mod names;
+mod properties;
 #[allow(clippy::module_inception)]
 mod utils;
 
 // TODO clean this up
 
+pub use fingerprint::model_fingerprint;
 pub use names::*;
+pub use properties::collect_properties;
 pub use utils::*;
 
 pub use debug::init;
@@ -45,6 +45,15 @@ pub fn codegen_spread_arg_name(&self, l: &Local) -> (String, String) {
         (name, base_name)
     }
 
+    /// The name of the extra parameter of a `#[track_caller]` function, which holds the
+    /// location of its caller.
+    pub fn codegen_caller_location_name(&self) -> (String, String) {
+        let fname = self.current_fn().name();
+        let base_name = "caller_location".to_string();
+        let name = format!("{fname}::1::{base_name}");
+        (name, base_name)
+    }
+
     /// The name for a tuple field
     pub fn tuple_fld_name(n: usize) -> String {
         format!("{n}")
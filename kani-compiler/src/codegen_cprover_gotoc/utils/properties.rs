@@ -0,0 +1,161 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Extract the properties (assertions, safety checks, covers, ...) that were generated for a
+//! model, so they can be reported before verification runs.
+
+use cbmc::goto_program::{Expr, ExprValue, Location, Stmt, StmtBody, SymbolTable, SymbolValues};
+use kani_metadata::{PropertyLocation, PropertyMetadata};
+
+/// Collect every property in the given symbol table.
+///
+/// Properties are sorted by the name of the function that contains them, and then by the order
+/// in which they appear in the function body. Thus, the order is stable across compilations of
+/// the same code.
+pub fn collect_properties(symbol_table: &SymbolTable) -> Vec<PropertyMetadata> {
+    let mut properties = vec![];
+    // The symbol table is ordered by interned string, which isn't the order of the names.
+    let mut symbols: Vec<_> = symbol_table.iter().collect();
+    symbols.sort_by_cached_key(|(name, _)| name.to_string());
+    for (_, symbol) in symbols {
+        match &symbol.value {
+            SymbolValues::Stmt(stmt) => collect_stmt(stmt, &mut properties),
+            SymbolValues::Expr(expr) => collect_expr(expr, &mut properties),
+            SymbolValues::None => {}
+        }
+    }
+    properties
+}
+
+fn collect_stmt(stmt: &Stmt, properties: &mut Vec<PropertyMetadata>) {
+    match stmt.body() {
+        StmtBody::Assert { cond, property_class, msg } => {
+            collect_expr(cond, properties);
+            properties.push(PropertyMetadata {
+                property_class: property_class.to_string(),
+                description: msg.to_string(),
+                location: property_location(stmt.location()),
+            });
+        }
+        StmtBody::Assign { lhs, rhs } => {
+            collect_expr(lhs, properties);
+            collect_expr(rhs, properties);
+        }
+        StmtBody::Assume { cond } => collect_expr(cond, properties),
+        StmtBody::AtomicBlock(stmts) | StmtBody::Block(stmts) => {
+            stmts.iter().for_each(|stmt| collect_stmt(stmt, properties))
+        }
+        StmtBody::Dead(expr) | StmtBody::Deinit(expr) | StmtBody::Expression(expr) => {
+            collect_expr(expr, properties)
+        }
+        StmtBody::Decl { lhs, value } => {
+            collect_expr(lhs, properties);
+            value.iter().for_each(|value| collect_expr(value, properties));
+        }
+        StmtBody::For { init, cond, update, body } => {
+            collect_stmt(init, properties);
+            collect_expr(cond, properties);
+            collect_stmt(update, properties);
+            collect_stmt(body, properties);
+        }
+        StmtBody::FunctionCall { lhs, function, arguments } => {
+            lhs.iter().for_each(|lhs| collect_expr(lhs, properties));
+            collect_expr(function, properties);
+            arguments.iter().for_each(|arg| collect_expr(arg, properties));
+        }
+        StmtBody::Goto { loop_invariants, .. } => {
+            loop_invariants.iter().for_each(|inv| collect_expr(inv, properties))
+        }
+        StmtBody::Ifthenelse { i, t, e } => {
+            collect_expr(i, properties);
+            collect_stmt(t, properties);
+            e.iter().for_each(|e| collect_stmt(e, properties));
+        }
+        StmtBody::Label { body, .. } => collect_stmt(body, properties),
+        StmtBody::Return(expr) => expr.iter().for_each(|expr| collect_expr(expr, properties)),
+        StmtBody::Switch { control, cases, default } => {
+            collect_expr(control, properties);
+            for case in cases {
+                collect_expr(case.case(), properties);
+                collect_stmt(case.body(), properties);
+            }
+            default.iter().for_each(|default| collect_stmt(default, properties));
+        }
+        StmtBody::While { cond, body } => {
+            collect_expr(cond, properties);
+            collect_stmt(body, properties);
+        }
+        StmtBody::Break | StmtBody::Continue | StmtBody::Skip => {}
+    }
+}
+
+/// Expressions may contain statements, e.g., when an operation has to be checked.
+fn collect_expr(expr: &Expr, properties: &mut Vec<PropertyMetadata>) {
+    match expr.value() {
+        ExprValue::StatementExpression { statements, .. } => {
+            statements.iter().for_each(|stmt| collect_stmt(stmt, properties))
+        }
+        ExprValue::AddressOf(e)
+        | ExprValue::ArrayOf { elem: e }
+        | ExprValue::ByteExtract { e, .. }
+        | ExprValue::Dereference(e)
+        | ExprValue::Member { lhs: e, .. }
+        | ExprValue::SelfOp { e, .. }
+        | ExprValue::Typecast(e)
+        | ExprValue::Union { value: e, .. }
+        | ExprValue::UnOp { e, .. } => collect_expr(e, properties),
+        ExprValue::Assign { left: lhs, right: rhs }
+        | ExprValue::BinOp { lhs, rhs, .. }
+        | ExprValue::Index { array: lhs, index: rhs }
+        | ExprValue::ReadOk { ptr: lhs, size: rhs } => {
+            collect_expr(lhs, properties);
+            collect_expr(rhs, properties);
+        }
+        ExprValue::Array { elems }
+        | ExprValue::Struct { values: elems }
+        | ExprValue::Vector { elems } => {
+            elems.iter().for_each(|elem| collect_expr(elem, properties))
+        }
+        ExprValue::FunctionCall { function, arguments } => {
+            collect_expr(function, properties);
+            arguments.iter().for_each(|arg| collect_expr(arg, properties));
+        }
+        ExprValue::If { c, t, e } => {
+            collect_expr(c, properties);
+            collect_expr(t, properties);
+            collect_expr(e, properties);
+        }
+        ExprValue::BoolConstant(_)
+        | ExprValue::CBoolConstant(_)
+        | ExprValue::DoubleConstant(_)
+        | ExprValue::EmptyUnion
+        | ExprValue::Float16Constant(_)
+        | ExprValue::FloatConstant(_)
+        | ExprValue::Float128Constant(_)
+        | ExprValue::IntConstant(_)
+        | ExprValue::Nondet
+        | ExprValue::PointerConstant(_)
+        | ExprValue::StringConstant { .. }
+        | ExprValue::Symbol { .. } => {}
+    }
+}
+
+fn property_location(loc: &Location) -> Option<PropertyLocation> {
+    match loc {
+        Location::Property { file, function, line, col, .. } => Some(PropertyLocation {
+            file: file.to_string(),
+            function: function.map(|function| function.to_string()),
+            line: *line,
+            column: *col,
+        }),
+        Location::Loc { file, function, start_line, start_col, .. } => Some(PropertyLocation {
+            file: file.to_string(),
+            function: function.map(|function| function.to_string()),
+            line: *start_line,
+            column: *start_col,
+        }),
+        Location::None
+        | Location::BuiltinFunction { .. }
+        | Location::PropertyUnknownLocation { .. } => None,
+    }
+}
@@ -40,6 +40,7 @@ pub enum Intrinsic {
     BlackBox,
     Breakpoint,
     Bswap,
+    CallerLocation,
     CeilF32,
     CeilF64,
     CompareBytes,
@@ -224,10 +225,7 @@ pub fn from_instance(intrinsic_instance: &Instance) -> Self {
             }
             "caller_location" => {
                 assert_sig_matches!(sig, => RigidTy::Ref(_, _, Mutability::Not));
-                Self::Unimplemented {
-                    name: intrinsic_str,
-                    issue_link: "https://github.com/model-checking/kani/issues/374".into(),
-                }
+                Self::CallerLocation
             }
             "catch_unwind" => {
                 assert_sig_matches!(sig, RigidTy::FnPtr(_), RigidTy::RawPtr(_, Mutability::Mut), RigidTy::FnPtr(_) => RigidTy::Int(IntTy::I32));
@@ -34,6 +34,9 @@
 use std::sync::{Arc, Mutex};
 use tracing::debug;
 
+/// The cfg that is enabled when compiling code for verification.
+const KANI_CFG: &str = "kani";
+
 /// Run the Kani flavour of the compiler.
 /// This may require multiple runs of the rustc driver ([RunCompiler::run]).
 pub fn run(args: Vec<String>) -> ExitCode {
@@ -108,6 +111,21 @@ pub fn run(&mut self, args: Vec<String>) -> Result<(), ErrorGuaranteed> {
     }
 }
 
+/// Make sure that code under `#[cfg(kani)]` is active, and that code under `#[cfg(not(kani))]`
+/// is removed, regardless of the flags used to invoke the compiler.
+///
+/// The `kani` cfg is only declared as expected if cfg checking is enabled, since declaring it
+/// would otherwise enable the checks.
+fn enable_kani_cfg(config: &mut Config) {
+    if !config.crate_cfg.iter().any(|cfg| cfg == KANI_CFG) {
+        config.crate_cfg.push(KANI_CFG.to_string());
+    }
+    let check_cfg = format!("cfg({KANI_CFG})");
+    if !config.crate_check_cfg.is_empty() && !config.crate_check_cfg.contains(&check_cfg) {
+        config.crate_check_cfg.push(check_cfg);
+    }
+}
+
 /// Use default function implementations.
 impl Callbacks for KaniCompiler {
     /// Configure the [KaniCompiler] `self` object during the [CompilationStage::Init].
@@ -116,6 +134,7 @@ fn config(&mut self, config: &mut Config) {
         args.extend(config.opts.cg.llvm_args.iter().cloned());
         let args = Arguments::parse_from(args);
         init_session(&args, matches!(config.opts.error_format, ErrorOutputType::Json { .. }));
+        enable_kani_cfg(config);
 
         // Configure queries.
         let queries = &mut (*self.queries.lock().unwrap());
@@ -131,7 +150,7 @@ fn after_analysis<'tcx>(
     ) -> Compilation {
         rustc_queries.global_ctxt().unwrap().enter(|tcx| {
             rustc_internal::run(tcx, || {
-                check_crate_items(tcx, self.queries.lock().unwrap().args().ignore_global_asm);
+                check_crate_items(tcx, self.queries.lock().unwrap().args());
             })
             .unwrap()
         });
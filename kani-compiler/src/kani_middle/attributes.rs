@@ -4,20 +4,22 @@
 
 use std::collections::{BTreeMap, HashSet};
 
-use kani_metadata::{CbmcSolver, HarnessAttributes, HarnessKind, Stub};
+use crate::args::{ArithmeticOverflow, WarningCategory};
+use kani_metadata::{BuiltinCheck, CbmcSolver, HarnessAttributes, HarnessKind, Stub};
 use quote::ToTokens;
 use rustc_ast::{
     AttrArgs, AttrArgsEq, AttrKind, Attribute, ExprKind, LitKind, MetaItem, MetaItemKind, attr,
 };
 use rustc_errors::ErrorGuaranteed;
-use rustc_hir::{def::DefKind, def_id::DefId};
-use rustc_middle::ty::{Instance, TyCtxt, TyKind};
+use rustc_hir::{def::DefKind, def_id::DefId, def_id::LocalDefId};
+use rustc_middle::ty::{self, GenericParamDefKind, Instance, ParamEnv, TyCtxt, TyKind};
 use rustc_session::Session;
 use rustc_smir::rustc_internal;
 use rustc_span::{Span, Symbol};
 use stable_mir::mir::mono::Instance as InstanceStable;
 use stable_mir::{CrateDef, DefId as StableDefId};
 use std::str::FromStr;
+use strum::VariantNames;
 use strum_macros::{AsRefStr, EnumString};
 use syn::parse::Parser;
 use syn::punctuated::Punctuated;
@@ -25,15 +27,34 @@
 
 use tracing::{debug, trace};
 
-use super::resolve::{FnResolution, ResolveError, resolve_fn, resolve_fn_path};
+use super::SourceLocation;
+use super::is_interior_mut;
+use super::resolve::{
+    FnResolution, ResolveError, resolution_scope, resolve_fn, resolve_fn_path, resolve_impl_item,
+    resolve_ty,
+};
+use super::send_safety;
+use super::struct_warn;
 
 #[derive(Debug, Clone, Copy, AsRefStr, EnumString, PartialEq, Eq, PartialOrd, Ord)]
 #[strum(serialize_all = "snake_case")]
 enum KaniAttributeKind {
     Proof,
+    /// Attribute used to override how arithmetic overflow is modeled in a harness.
+    ArithmeticOverflow,
+    /// Attribute used to disable some of the built-in checks in a harness. This is generated by
+    /// `#[kani::disable_checks]`, and it is different from [`Self::DisableChecks`].
+    DisableHarnessChecks,
     ShouldPanic,
     Solver,
     Stub,
+    /// Replace the drop glue of a type by a function that takes `&mut Type`.
+    StubDrop,
+    /// Apply the stubs of the stub sets declared with `kani::stub_set!`.
+    UseStubs,
+    /// Attribute on the function generated by `kani::stub_set!`, with one attribute for each stub
+    /// of the set.
+    StubSet,
     /// Attribute used to mark unstable APIs.
     Unstable,
     Unwind,
@@ -44,9 +65,68 @@ enum KaniAttributeKind {
     /// contract, e.g. the contract check is substituted for the target function
     /// before the the verification runs.
     ProofForContract,
+    /// Attribute added to a [`Self::ProofForContract`] harness to select which of the named
+    /// contracts of the target should be checked.
+    CheckedContract,
+    /// Attribute added to a [`Self::ProofForContract`] harness to only check that the target
+    /// doesn't panic when its preconditions hold, instead of checking its postconditions.
+    PanicFree,
+    /// Attribute added to a [`Self::Proof`] harness with generic parameters to verify it for
+    /// every combination of the values or types listed for each parameter.
+    ProofOver,
+    /// Attribute added to a [`Self::Proof`] harness to also check that the given type, which
+    /// implements `Send`, doesn't expose thread-unsafe interior mutability.
+    ProofSendSafe,
+    /// Attribute added to a [`Self::Proof`] harness that checks that equal values of the given
+    /// type have equal hashes.
+    ProofHashEq,
+    /// Attribute added to a [`Self::Proof`] harness that checks that the order implementations of
+    /// the given type are consistent.
+    ProofOrdConsistent,
+    /// Attribute added to a [`Self::Proof`] harness that checks that mutating a clone of the
+    /// given type doesn't change the original value.
+    ProofCloneIndependent,
+    /// Attribute added to a [`Self::Proof`] harness that checks that the default value of the
+    /// given type satisfies the invariant of the type.
+    ProofDefaultValid,
+    /// Attribute added to a [`Self::Proof`] harness that checks that the given allocator upholds
+    /// the contract of `GlobalAlloc` or `Allocator`.
+    ProofAllocator,
+    /// Attribute added to a [`Self::Proof`] harness that checks that the given `Future`
+    /// implementation registers the waker of its context when it returns `Pending`.
+    ProofFuture,
+    /// Attribute added to a [`Self::Proof`] harness that checks that the given `FusedIterator`
+    /// implementation keeps returning `None` once it returned `None`.
+    ProofFused,
+    /// Attribute added to a [`Self::Proof`] harness that checks that formatting the given type
+    /// with its `Display` and `Debug` implementations doesn't panic.
+    ProofDisplaySafe,
+    /// Attribute added to a [`Self::Proof`] harness that checks that the given `From` conversion
+    /// round-trips.
+    ProofLossless,
+    /// Attribute added to a [`Self::Proof`] harness that checks that indexing the given type only
+    /// panics when the index is out of bounds.
+    ProofIndexSafe,
+    /// Attribute added to a [`Self::Proof`] harness that checks that serializing and deserializing
+    /// the given type round-trips.
+    ProofRoundtrip,
+    /// Attribute added to a generic [`Self::Proof`] harness that is instantiated for every public
+    /// method of the given type to check that the method preserves the invariant of the type.
+    ProofInvariantPreserved,
     /// Attribute on a function with a contract that identifies the code
     /// implementing the check for this contract.
     CheckedWith,
+    /// Attribute on a function with named contracts that holds the name of one of them.
+    NamedContract,
+    /// Attribute on a function with a contract that holds the condition of one of its
+    /// `requires` clauses.
+    Requires,
+    /// Attribute on a function with a contract that holds the closure of one of its `ensures`
+    /// clauses.
+    Ensures,
+    /// Attribute on a function with a contract that holds the targets of one of its `modifies`
+    /// clauses.
+    Modifies,
     /// Internal attribute of the contracts implementation that identifies the
     /// name of the function which was generated as the sound stub from the
     /// contract of this function.
@@ -75,18 +155,90 @@ enum KaniAttributeKind {
     /// Used to mark functions where generating automatic pointer checks should be disabled. This is
     /// used later to automatically attach pragma statements to locations.
     DisableChecks,
+    /// Attribute used to silence the warning emitted when an `ensures` clause reads the
+    /// post-state of a mutable argument without ever taking a snapshot of it with `old`.
+    AllowPoststateRead,
+    /// Attribute used to silence the warning emitted when a `requires` or `ensures` clause is
+    /// always true or always false.
+    AllowTrivialContract,
+    /// Attribute on a module whose calls to functions outside of the module are replaced by the
+    /// contracts of these functions.
+    Boundary,
+    /// Attribute on a function whose panics are allowed by [`Self::PanicFree`] harnesses.
+    MayPanic,
+    /// Attribute on an `unsafe fn` that holds its safety precondition, which is checked at every
+    /// call site.
+    SafetyRequires,
+    /// Attribute on a function that creates the value passed to the harnesses of a group.
+    Fixture,
+    /// Attribute added to a [`Self::Proof`] harness that takes the value of the fixture of the
+    /// given group.
+    FixtureGroup,
+    /// Attribute on the function generated by `#[kani::global_assume]`, which evaluates an
+    /// assumption that is re-established after every havoc.
+    GlobalAssume,
 }
 
+/// The attributes that cannot be used on the same item, with the reason why they conflict.
+const CONFLICTING_ATTRIBUTES: &[(KaniAttributeKind, KaniAttributeKind, &str)] = &[
+    (
+        KaniAttributeKind::Proof,
+        KaniAttributeKind::ProofForContract,
+        "a harness either verifies its body or the contract of a function",
+    ),
+    (
+        KaniAttributeKind::ShouldPanic,
+        KaniAttributeKind::PanicFree,
+        "a `panic_free` harness checks that the function never panics",
+    ),
+    (
+        KaniAttributeKind::ProofOver,
+        KaniAttributeKind::ProofInvariantPreserved,
+        "both attributes select the instantiations of a generic harness",
+    ),
+    (
+        KaniAttributeKind::Fixture,
+        KaniAttributeKind::Proof,
+        "a fixture creates the value of a harness, but it's not a harness itself",
+    ),
+    (
+        KaniAttributeKind::Fixture,
+        KaniAttributeKind::ProofForContract,
+        "a fixture creates the value of a harness, but it's not a harness itself",
+    ),
+];
+
 impl KaniAttributeKind {
     /// Returns whether an item is only relevant for harnesses.
     pub fn is_harness_only(self) -> bool {
         match self {
             KaniAttributeKind::Proof
+            | KaniAttributeKind::ArithmeticOverflow
+            | KaniAttributeKind::DisableHarnessChecks
             | KaniAttributeKind::ShouldPanic
             | KaniAttributeKind::Solver
             | KaniAttributeKind::Stub
+            | KaniAttributeKind::StubDrop
+            | KaniAttributeKind::UseStubs
             | KaniAttributeKind::ProofForContract
+            | KaniAttributeKind::CheckedContract
+            | KaniAttributeKind::PanicFree
             | KaniAttributeKind::StubVerified
+            | KaniAttributeKind::ProofSendSafe
+            | KaniAttributeKind::ProofHashEq
+            | KaniAttributeKind::ProofOrdConsistent
+            | KaniAttributeKind::ProofCloneIndependent
+            | KaniAttributeKind::ProofDefaultValid
+            | KaniAttributeKind::ProofAllocator
+            | KaniAttributeKind::ProofFuture
+            | KaniAttributeKind::ProofFused
+            | KaniAttributeKind::ProofDisplaySafe
+            | KaniAttributeKind::ProofLossless
+            | KaniAttributeKind::ProofIndexSafe
+            | KaniAttributeKind::ProofRoundtrip
+            | KaniAttributeKind::ProofInvariantPreserved
+            | KaniAttributeKind::ProofOver
+            | KaniAttributeKind::FixtureGroup
             | KaniAttributeKind::Unwind => true,
             KaniAttributeKind::Unstable
             | KaniAttributeKind::FnMarker
@@ -95,9 +247,21 @@ pub fn is_harness_only(self) -> bool {
             | KaniAttributeKind::ReplacedWith
             | KaniAttributeKind::RecursionCheck
             | KaniAttributeKind::CheckedWith
+            | KaniAttributeKind::NamedContract
+            | KaniAttributeKind::Requires
+            | KaniAttributeKind::Ensures
+            | KaniAttributeKind::Modifies
             | KaniAttributeKind::ModifiesWrapper
             | KaniAttributeKind::IsContractGenerated
-            | KaniAttributeKind::DisableChecks => false,
+            | KaniAttributeKind::DisableChecks
+            | KaniAttributeKind::AllowPoststateRead
+            | KaniAttributeKind::AllowTrivialContract
+            | KaniAttributeKind::Boundary
+            | KaniAttributeKind::Fixture
+            | KaniAttributeKind::GlobalAssume
+            | KaniAttributeKind::StubSet
+            | KaniAttributeKind::MayPanic
+            | KaniAttributeKind::SafetyRequires => false,
         }
     }
 
@@ -140,6 +304,26 @@ pub struct ContractAttributes {
     pub modifies_wrapper: Symbol,
 }
 
+/// The clauses of the contract of a function, in the order they were declared.
+#[derive(Debug)]
+pub struct Contract {
+    /// The conditions of the `requires` clauses.
+    pub requires: Vec<ContractClause>,
+    /// The closures of the `ensures` clauses, e.g. `|result: &u32| *result > x`.
+    pub ensures: Vec<ContractClause>,
+    /// The targets of the `modifies` clauses. A clause may list more than one target.
+    pub modifies: Vec<ContractClause>,
+}
+
+/// A clause of a function contract.
+#[derive(Debug)]
+pub struct ContractClause {
+    /// The arguments of the clause attribute as written by the user.
+    pub expr: String,
+    /// The location of the clause attribute, e.g. `#[kani::requires(x < 10)]`.
+    pub location: SourceLocation,
+}
+
 impl std::fmt::Debug for KaniAttributes<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("KaniAttributes")
@@ -182,12 +366,16 @@ pub fn for_item(tcx: TyCtxt<'tcx>, def_id: DefId) -> Self {
     fn expect_maybe_one(&self, kind: KaniAttributeKind) -> Option<&'tcx Attribute> {
         match self.map.get(&kind)?.as_slice() {
             [one] => Some(one),
-            _ => {
-                self.tcx.dcx().err(format!(
-                    "Too many {} attributes on {}, expected 0 or 1",
-                    kind.as_ref(),
-                    self.tcx.def_path_debug_str(self.item)
-                ));
+            attrs => {
+                let spans: Vec<Span> = attrs.iter().map(|attr| attr.span).collect();
+                self.tcx.dcx().span_err(
+                    spans,
+                    format!(
+                        "Too many {} attributes on {}, expected 0 or 1",
+                        kind.as_ref(),
+                        self.tcx.def_path_debug_str(self.item)
+                    ),
+                );
                 None
             }
         }
@@ -227,6 +415,35 @@ pub(crate) fn has_recursion(&self) -> bool {
         self.map.contains_key(&KaniAttributeKind::Recursion)
     }
 
+    /// Whether the warnings about clauses that are always true or false are silenced.
+    pub(crate) fn allow_trivial_contract(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::AllowTrivialContract)
+    }
+
+    /// Whether this harness only checks that the target of the contract doesn't panic.
+    pub(crate) fn is_panic_free(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::PanicFree)
+    }
+
+    /// Whether this function is allowed to panic in a `panic_free` harness.
+    pub(crate) fn may_panic(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::MayPanic)
+    }
+
+    /// Whether this harness checks a `Future` implementation, and may poll it after it completed.
+    pub(crate) fn is_proof_future(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::ProofFuture)
+    }
+
+    /// The safety precondition of this `unsafe fn`, if it has one, together with the function
+    /// generated by `#[kani::safety_requires]` that evaluates it.
+    pub(crate) fn safety_requires(&self) -> Option<(Symbol, DefId)> {
+        let condition = self.attribute_value(KaniAttributeKind::SafetyRequires)?;
+        let checker = format!("__kani_safety_requires_{}", self.tcx.item_name(self.item));
+        let checker = sibling_fn(self.tcx, self.item, &checker)?;
+        Some((condition, checker))
+    }
+
     /// Parse and extract the `proof_for_contract(TARGET)` attribute. The
     /// returned symbol and DefId are respectively the name and id of `TARGET`,
     /// the span in the span for the attribute (contents).
@@ -255,6 +472,40 @@ pub fn proof_for_contract(&self) -> Option<Result<Symbol, ErrorGuaranteed>> {
             .map(|target| expect_key_string_value(self.tcx.sess, target))
     }
 
+    /// Return the name of the named contract selected by a `proof_for_contract` harness, if any.
+    pub fn checked_contract(&self) -> Option<Symbol> {
+        self.attribute_value(KaniAttributeKind::CheckedContract)
+    }
+
+    /// Resolve the function whose contract is checked by this `proof_for_contract` harness.
+    ///
+    /// This is the target of the harness, unless the harness selects one of the named contracts
+    /// of the target. In that case, this is the function generated for that contract.
+    /// Errors are reported by `handle_proof_for_contract`.
+    pub(crate) fn checked_contract_fn(&self) -> Option<DefId> {
+        let (_, target, _) = self.interpret_for_contract_attribute()?;
+        match self.checked_contract() {
+            Some(contract) => named_contract_fn(self.tcx, target, contract),
+            None => Some(target),
+        }
+    }
+
+    /// Return the names of the named contracts of this function.
+    pub fn named_contracts(&self) -> Vec<Symbol> {
+        self.map.get(&KaniAttributeKind::NamedContract).map_or(vec![], |attrs| {
+            attrs
+                .iter()
+                .filter_map(|attr| expect_key_string_value(self.tcx.sess, attr).ok())
+                .collect()
+        })
+    }
+
+    /// Check if this item was generated by the contract macros, e.g.: the function generated
+    /// for a named contract.
+    pub fn is_contract_generated(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::IsContractGenerated)
+    }
+
     /// Extract the name of the local that represents this function's contract is
     /// checked with (if any).
     ///
@@ -289,6 +540,41 @@ pub fn contract_attributes(&self) -> Option<ContractAttributes> {
         })
     }
 
+    /// Return the clauses of the contract of this function, or `None` if it doesn't have one.
+    ///
+    /// The functions generated for named contracts have their own contract, while the function
+    /// that declares them doesn't.
+    pub fn contract(&self) -> Option<Contract> {
+        if !self.has_contract() {
+            return None;
+        }
+        let clauses = |kind| {
+            let mut clauses: Vec<_> = self
+                .map
+                .get(&kind)
+                .map_or([].as_slice(), Vec::as_slice)
+                .iter()
+                .filter_map(|attr| {
+                    let expr = expect_key_string_value(self.tcx.sess, attr).ok()?;
+                    // The attribute is generated by the contract macros, so we use the span of
+                    // the attribute that the user wrote.
+                    let location =
+                        SourceLocation::new(rustc_internal::stable(attr.span.source_callsite()));
+                    Some(ContractClause { expr: expr.to_string(), location })
+                })
+                .collect();
+            clauses.sort_by_key(|clause: &ContractClause| {
+                (clause.location.start_line, clause.location.start_col)
+            });
+            clauses
+        };
+        Some(Contract {
+            requires: clauses(KaniAttributeKind::Requires),
+            ensures: clauses(KaniAttributeKind::Ensures),
+            modifies: clauses(KaniAttributeKind::Modifies),
+        })
+    }
+
     /// Return a function marker if any.
     pub fn fn_marker(&self) -> Option<Symbol> {
         self.attribute_value(KaniAttributeKind::FnMarker)
@@ -299,21 +585,57 @@ pub fn has_contract(&self) -> bool {
         self.map.contains_key(&KaniAttributeKind::CheckedWith)
     }
 
-    /// Resolve a path starting from this item's module context.
+    /// Return how arithmetic overflow should be modeled in this harness, if it overrides the
+    /// command line option.
+    pub fn arithmetic_overflow(&self) -> Option<ArithmeticOverflow> {
+        self.map
+            .get(&KaniAttributeKind::ArithmeticOverflow)
+            .and_then(|attrs| parse_arithmetic_overflow(self.tcx, attrs[0]))
+    }
+
+    /// Return the built-in checks that are disabled in this harness.
+    pub fn disabled_checks(&self) -> Vec<BuiltinCheck> {
+        self.map
+            .get(&KaniAttributeKind::DisableHarnessChecks)
+            .map(|attrs| parse_disabled_checks(self.tcx, attrs))
+            .unwrap_or_default()
+    }
+
+    /// Check if this item is a module annotated with `#[kani::boundary]`.
+    pub fn is_boundary(&self) -> bool {
+        self.map.contains_key(&KaniAttributeKind::Boundary)
+    }
+
+    /// Return the group of the fixture defined by this function, if any.
+    pub fn fixture(&self) -> Option<Symbol> {
+        self.attribute_value(KaniAttributeKind::Fixture)
+    }
+
+    /// Return the fixture group of this harness, if any.
+    pub fn fixture_group(&self) -> Option<Symbol> {
+        self.attribute_value(KaniAttributeKind::FixtureGroup)
+    }
+
+    /// Return the name of the static whose global assumption is evaluated by this function, if
+    /// any.
+    pub fn global_assume(&self) -> Option<Symbol> {
+        self.attribute_value(KaniAttributeKind::GlobalAssume)
+    }
+
+    /// Resolve a path starting from this item's module context, or from its impl block.
     fn resolve_from_mod(&self, path_str: &str) -> Result<DefId, ResolveError<'tcx>> {
-        resolve_fn(
-            self.tcx,
-            self.tcx.parent_module_from_def_id(self.item.expect_local()).to_local_def_id(),
-            path_str,
-        )
+        resolve_fn(self.tcx, resolution_scope(self.tcx, self.item.expect_local()), path_str)
     }
 
     /// Check that all attributes assigned to an item is valid.
     /// Errors will be added to the session. Invoke self.tcx.sess.abort_if_errors() to terminate
     /// the session and emit all errors found.
-    pub(super) fn check_attributes(&self) {
+    ///
+    /// The warnings of the categories in `deny_warnings` are reported as errors.
+    pub(super) fn check_attributes(&self, deny_warnings: &[WarningCategory]) {
         // Check that all attributes are correctly used and well formed.
         let is_harness = self.is_proof_harness();
+        self.check_conflicts();
         for (&kind, attrs) in self.map.iter() {
             let local_error = |msg| self.tcx.dcx().span_err(attrs[0].span, msg);
 
@@ -330,21 +652,51 @@ pub(super) fn check_attributes(&self) {
                         expect_no_args(self.tcx, kind, attr);
                     })
                 }
-                KaniAttributeKind::Recursion => {
+                KaniAttributeKind::Recursion
+                | KaniAttributeKind::AllowPoststateRead
+                | KaniAttributeKind::AllowTrivialContract
+                | KaniAttributeKind::MayPanic => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
                         expect_no_args(self.tcx, kind, attr);
                     })
                 }
+                KaniAttributeKind::Boundary => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        expect_no_args(self.tcx, kind, attr);
+                    });
+                    if self.tcx.def_kind(self.item) != DefKind::Mod {
+                        local_error("the `boundary` attribute can only be used on modules".into());
+                    }
+                }
                 KaniAttributeKind::Solver => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
                         parse_solver(self.tcx, attr);
                     })
                 }
+                KaniAttributeKind::ArithmeticOverflow => {
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        parse_arithmetic_overflow(self.tcx, attr);
+                    })
+                }
+                KaniAttributeKind::DisableHarnessChecks => {
+                    parse_disabled_checks(self.tcx, attrs);
+                }
                 KaniAttributeKind::Stub => {
                     parse_stubs(self.tcx, self.item, attrs);
                 }
+                KaniAttributeKind::StubDrop => {
+                    parse_drop_stubs(self.tcx, self.item, attrs);
+                }
+                KaniAttributeKind::UseStubs => {
+                    self.stub_sets();
+                }
+                KaniAttributeKind::StubSet => {
+                    parse_stubs(self.tcx, self.item, attrs);
+                }
                 KaniAttributeKind::Unwind => {
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| {
@@ -352,11 +704,6 @@ pub(super) fn check_attributes(&self) {
                     })
                 }
                 KaniAttributeKind::Proof => {
-                    if self.map.contains_key(&KaniAttributeKind::ProofForContract) {
-                        local_error(
-                            "`proof` and `proof_for_contract` may not be used on the same function.".to_string(),
-                        );
-                    }
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| self.check_proof_attribute(kind, attr))
                 }
@@ -364,18 +711,73 @@ pub(super) fn check_attributes(&self) {
                     let _ = UnstableAttribute::try_from(*attr).map_err(|err| err.report(self.tcx));
                 }),
                 KaniAttributeKind::ProofForContract => {
-                    if self.map.contains_key(&KaniAttributeKind::Proof) {
-                        local_error(
-                            "`proof` and `proof_for_contract` may not be used on the same function.".to_string(),
-                        );
-                    }
                     expect_single(self.tcx, kind, &attrs);
                     attrs.iter().for_each(|attr| self.check_proof_attribute(kind, attr))
                 }
                 KaniAttributeKind::StubVerified => {
                     expect_single(self.tcx, kind, &attrs);
                 }
+                KaniAttributeKind::CheckedContract => {
+                    if !self.map.contains_key(&KaniAttributeKind::ProofForContract) {
+                        local_error(format!(
+                            "the `{}` attribute also requires the `#[kani::proof_for_contract]` \
+                            attribute",
+                            kind.as_ref()
+                        ));
+                    }
+                    self.attribute_value(kind);
+                }
+                KaniAttributeKind::PanicFree => {
+                    if !self.map.contains_key(&KaniAttributeKind::ProofForContract) {
+                        local_error(format!(
+                            "the `{}` attribute also requires the `#[kani::proof_for_contract]` \
+                            attribute",
+                            kind.as_ref()
+                        ));
+                    }
+                    expect_single(self.tcx, kind, &attrs);
+                    attrs.iter().for_each(|attr| {
+                        expect_no_args(self.tcx, kind, attr);
+                    })
+                }
+                KaniAttributeKind::NamedContract
+                | KaniAttributeKind::Requires
+                | KaniAttributeKind::Ensures
+                | KaniAttributeKind::Modifies => attrs.iter().for_each(|attr| {
+                    let _ = expect_key_string_value(self.tcx.sess, attr);
+                }),
+                KaniAttributeKind::ProofSendSafe
+                | KaniAttributeKind::ProofHashEq
+                | KaniAttributeKind::ProofCloneIndependent
+                | KaniAttributeKind::ProofDefaultValid
+                | KaniAttributeKind::ProofOrdConsistent
+                | KaniAttributeKind::ProofAllocator
+                | KaniAttributeKind::ProofFuture
+                | KaniAttributeKind::ProofFused
+                | KaniAttributeKind::ProofDisplaySafe
+                | KaniAttributeKind::ProofLossless
+                | KaniAttributeKind::ProofIndexSafe
+                | KaniAttributeKind::ProofRoundtrip => {
+                    let attr = expect_single(self.tcx, kind, &attrs);
+                    if let Ok(target) = expect_key_string_value(self.tcx.sess, attr) {
+                        self.check_proof_target(kind, target, attr.span, deny_warnings);
+                    }
+                }
+                KaniAttributeKind::ProofOver => {
+                    let attr = expect_single(self.tcx, kind, &attrs);
+                    self.parse_proof_over(attr);
+                }
+                KaniAttributeKind::ProofInvariantPreserved => {
+                    self.invariant_preserved_target();
+                }
+                KaniAttributeKind::SafetyRequires => {
+                    expect_single(self.tcx, kind, &attrs);
+                    self.attribute_value(kind);
+                }
                 KaniAttributeKind::FnMarker
+                | KaniAttributeKind::Fixture
+                | KaniAttributeKind::FixtureGroup
+                | KaniAttributeKind::GlobalAssume
                 | KaniAttributeKind::CheckedWith
                 | KaniAttributeKind::ModifiesWrapper
                 | KaniAttributeKind::RecursionCheck
@@ -396,6 +798,71 @@ pub(super) fn check_attributes(&self) {
                 }
             }
         }
+        self.check_duplicate_clauses();
+    }
+
+    /// Warn about the clauses of the contract of this function that are declared more than once.
+    fn check_duplicate_clauses(&self) {
+        let Some(contract) = self.contract() else { return };
+        let location = |clause: &ContractClause| {
+            let loc = &clause.location;
+            format!("{}:{}:{}", loc.filename, loc.start_line, loc.start_col)
+        };
+        for (kind, clauses) in [
+            ("requires", &contract.requires),
+            ("ensures", &contract.ensures),
+            ("modifies", &contract.modifies),
+        ] {
+            for (idx, clause) in clauses.iter().enumerate() {
+                let Some(first) = clauses[..idx].iter().find(|first| first.expr == clause.expr)
+                else {
+                    continue;
+                };
+                self.tcx
+                    .dcx()
+                    .struct_span_warn(
+                        self.tcx.def_span(self.item),
+                        format!(
+                            "the `{kind}` clause `{}` of `{}` is declared more than once",
+                            clause.expr,
+                            self.tcx.def_path_str(self.item),
+                        ),
+                    )
+                    .with_note(format!(
+                        "it's declared at {} and at {}",
+                        location(first),
+                        location(clause)
+                    ))
+                    .emit();
+            }
+        }
+    }
+
+    /// Report every pair of attributes of this item that cannot be used together, pointing to all
+    /// the attributes involved.
+    fn check_conflicts(&self) {
+        for (first, second, reason) in CONFLICTING_ATTRIBUTES {
+            let (Some(first_attrs), Some(second_attrs)) =
+                (self.map.get(first), self.map.get(second))
+            else {
+                continue;
+            };
+            let spans: Vec<Span> =
+                first_attrs.iter().chain(second_attrs).map(|attr| attr.span).collect();
+            self.tcx
+                .dcx()
+                .struct_span_err(
+                    spans,
+                    format!(
+                        "the `#[kani::{}]` and `#[kani::{}]` attributes cannot be used on the same \
+                        item",
+                        first.as_ref(),
+                        second.as_ref()
+                    ),
+                )
+                .with_note(*reason)
+                .emit();
+        }
     }
 
     /// Get the value of an attribute if one exists.
@@ -469,7 +936,7 @@ fn report_unstable_forbidden(&self, unstable_attr: &UnstableAttribute) -> ErrorG
 
     /// Is this item a harness? (either `proof` or `proof_for_contract`
     /// attribute are present)
-    fn is_proof_harness(&self) -> bool {
+    pub fn is_proof_harness(&self) -> bool {
         self.map.contains_key(&KaniAttributeKind::Proof)
             || self.map.contains_key(&KaniAttributeKind::ProofForContract)
     }
@@ -500,7 +967,10 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
         trace!(?self, "extract_harness_attributes");
         assert!(self.is_proof_harness());
         let harness_attrs = if let Some(Ok(harness)) = self.proof_for_contract() {
-            HarnessAttributes::new(HarnessKind::ProofForContract { target_fn: harness.to_string() })
+            HarnessAttributes::new(HarnessKind::ProofForContract {
+                target_fn: harness.to_string(),
+                contract: self.checked_contract().map(|name| name.to_string()),
+            })
         } else {
             HarnessAttributes::new(HarnessKind::Proof)
         };
@@ -516,9 +986,19 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                 KaniAttributeKind::Stub => {
                     harness.stubs.extend_from_slice(&parse_stubs(self.tcx, self.item, attributes));
                 }
+                KaniAttributeKind::StubDrop => {
+                    let drop_stubs = parse_drop_stubs(self.tcx, self.item, attributes);
+                    harness.drop_stubs.extend_from_slice(&drop_stubs);
+                }
+                KaniAttributeKind::UseStubs => {
+                    harness.stub_sets.extend(self.stub_sets().into_iter().map(|(name, _)| name));
+                }
                 KaniAttributeKind::Unwind => {
                     harness.unwind_value = parse_unwind(self.tcx, attributes[0])
                 }
+                KaniAttributeKind::DisableHarnessChecks => {
+                    harness.disabled_checks = parse_disabled_checks(self.tcx, attributes);
+                }
                 KaniAttributeKind::Proof => { /* no-op */ }
                 KaniAttributeKind::ProofForContract => self.handle_proof_for_contract(&mut harness),
                 KaniAttributeKind::StubVerified => self.handle_stub_verified(&mut harness),
@@ -527,6 +1007,10 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                     unreachable!()
                 }
                 KaniAttributeKind::CheckedWith
+                | KaniAttributeKind::NamedContract
+                | KaniAttributeKind::Requires
+                | KaniAttributeKind::Ensures
+                | KaniAttributeKind::Modifies
                 | KaniAttributeKind::IsContractGenerated
                 | KaniAttributeKind::ModifiesWrapper
                 | KaniAttributeKind::RecursionCheck
@@ -538,7 +1022,33 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
                     // Internal attribute which shouldn't exist here.
                     unreachable!()
                 }
-                KaniAttributeKind::FnMarker => {
+                KaniAttributeKind::FnMarker
+                | KaniAttributeKind::AllowPoststateRead
+                | KaniAttributeKind::AllowTrivialContract
+                | KaniAttributeKind::ArithmeticOverflow
+                | KaniAttributeKind::CheckedContract
+                | KaniAttributeKind::PanicFree
+                | KaniAttributeKind::MayPanic
+                | KaniAttributeKind::ProofOver
+                | KaniAttributeKind::ProofSendSafe
+                | KaniAttributeKind::ProofHashEq
+                | KaniAttributeKind::ProofOrdConsistent
+                | KaniAttributeKind::ProofCloneIndependent
+                | KaniAttributeKind::ProofDefaultValid
+                | KaniAttributeKind::ProofAllocator
+                | KaniAttributeKind::ProofFuture
+                | KaniAttributeKind::ProofFused
+                | KaniAttributeKind::ProofDisplaySafe
+                | KaniAttributeKind::ProofLossless
+                | KaniAttributeKind::ProofIndexSafe
+                | KaniAttributeKind::ProofRoundtrip
+                | KaniAttributeKind::ProofInvariantPreserved
+                | KaniAttributeKind::Fixture
+                | KaniAttributeKind::FixtureGroup
+                | KaniAttributeKind::GlobalAssume
+                | KaniAttributeKind::StubSet
+                | KaniAttributeKind::SafetyRequires
+                | KaniAttributeKind::Boundary => {
                     /* no-op */
                 }
             };
@@ -546,6 +1056,48 @@ pub fn harness_attributes(&self) -> HarnessAttributes {
         })
     }
 
+    /// Parse and resolve the stub sets of `use_stubs(SET, ...)`, which are returned with the path
+    /// used to refer to them.
+    ///
+    /// Emits an error for every argument that doesn't resolve to a stub set.
+    fn stub_sets(&self) -> Vec<(String, LocalDefId)> {
+        let Some(attrs) = self.map.get(&KaniAttributeKind::UseStubs) else { return vec![] };
+        attrs
+            .iter()
+            .flat_map(|attr| {
+                let paths = parse_paths(attr).unwrap_or_else(|_| {
+                    self.tcx.dcx().span_err(
+                        attr.span,
+                        format!(
+                            "attribute `kani::{}` takes path arguments; found argument that is \
+                            not a path",
+                            KaniAttributeKind::UseStubs.as_ref()
+                        ),
+                    );
+                    vec![]
+                });
+                paths.into_iter().filter_map(|path| {
+                    let name = path.to_token_stream().to_string();
+                    match resolve_stub_set(self.tcx, self.item, &name) {
+                        Ok(set) => Some((name, set)),
+                        Err(msg) => {
+                            self.tcx.dcx().span_err(attr.span, msg);
+                            None
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// The stubs of a stub set declared with `kani::stub_set!`, whose paths are resolved from the
+    /// module that declares the set.
+    pub fn stub_set_entries(&self) -> Vec<Stub> {
+        self.map
+            .get(&KaniAttributeKind::StubSet)
+            .map_or_else(Vec::new, |attrs| parse_stubs(self.tcx, self.item, attrs))
+    }
+
     fn handle_proof_for_contract(&self, harness: &mut HarnessAttributes) {
         let dcx = self.tcx.dcx();
         let (name, id, span) = match self.interpret_for_contract_attribute() {
@@ -553,9 +1105,36 @@ fn handle_proof_for_contract(&self, harness: &mut HarnessAttributes) {
             Some(values) => values,
         };
         assert!(matches!(
-                &harness.kind, HarnessKind::ProofForContract { target_fn }
+                &harness.kind, HarnessKind::ProofForContract { target_fn, .. }
                 if *target_fn == name.to_string()));
-        if KaniAttributes::for_item(self.tcx, id).contract_attributes().is_none() {
+        if let Some(contract) = self.checked_contract() {
+            if named_contract_fn(self.tcx, id, contract).is_none() {
+                let available = KaniAttributes::for_item(self.tcx, id).named_contracts();
+                let note = if available.is_empty() {
+                    "Try adding a named contract to this function with \
+                    `#[kani::contract(name = \"...\", ...)]`."
+                        .to_string()
+                } else {
+                    format!(
+                        "The contracts of this function are: {}.",
+                        available
+                            .iter()
+                            .map(|name| format!("`{name}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                };
+                dcx.struct_span_err(
+                    span,
+                    format!(
+                        "Failed to check contract: Function `{name}` has no contract named \
+                        `{contract}`."
+                    ),
+                )
+                .with_span_note(self.tcx.def_span(id), note)
+                .emit();
+            }
+        } else if KaniAttributes::for_item(self.tcx, id).contract_attributes().is_none() {
             dcx.struct_span_err(
                 span,
                 format!(
@@ -593,6 +1172,353 @@ fn handle_stub_verified(&self, harness: &mut HarnessAttributes) {
         }
     }
 
+    /// Check the target of an attribute that generates a harness for it, such as
+    /// `proof_hash_eq(TARGET)`.
+    ///
+    /// Most targets are only checked by rustc, since they're used to instantiate the check of the
+    /// harness.
+    fn check_proof_target(
+        &self,
+        kind: KaniAttributeKind,
+        target: Symbol,
+        span: Span,
+        deny_warnings: &[WarningCategory],
+    ) {
+        match kind {
+            KaniAttributeKind::ProofSendSafe => self.check_send_safe(target, span),
+            KaniAttributeKind::ProofHashEq => self.check_interior_mut_target(
+                kind,
+                target,
+                span,
+                deny_warnings,
+                "its `Hash` implementation may depend on state that is not compared by its `Eq` \
+                implementation",
+            ),
+            KaniAttributeKind::ProofCloneIndependent => self.check_interior_mut_target(
+                kind,
+                target,
+                span,
+                deny_warnings,
+                "its clones may legitimately share state with the original value, e.g., the \
+                value of an `Rc`",
+            ),
+            _ => {}
+        }
+    }
+
+    /// Resolve the target of `proof_send_safe(TARGET)` and check that it is safe to send values
+    /// of this type to another thread.
+    fn check_send_safe(&self, ty_name: Symbol, span: Span) {
+        let current_module = self.tcx.parent_module_from_def_id(self.item.expect_local());
+        match resolve_ty(self.tcx, current_module.to_local_def_id(), ty_name.as_str()) {
+            Ok(ty) => send_safety::check_send_safe(
+                self.tcx,
+                self.item,
+                rustc_internal::internal(self.tcx, ty),
+                span,
+            ),
+            Err(err) => {
+                self.tcx.dcx().span_err(
+                    span,
+                    format!("Failed to resolve type `{}` for `proof_send_safe`: {err}", ty_name),
+                );
+            }
+        }
+    }
+
+    /// Resolve the target of an attribute that generates a harness for a type, such as
+    /// `proof_hash_eq(TARGET)`, and warn if the type has interior mutability, since the check
+    /// performed by the harness may not hold for such types for the given reason.
+    fn check_interior_mut_target(
+        &self,
+        kind: KaniAttributeKind,
+        ty_name: Symbol,
+        span: Span,
+        deny_warnings: &[WarningCategory],
+        reason: &str,
+    ) {
+        let current_module = self.tcx.parent_module_from_def_id(self.item.expect_local());
+        match resolve_ty(self.tcx, current_module.to_local_def_id(), ty_name.as_str()) {
+            Ok(ty) if is_interior_mut(self.tcx, ty) => {
+                struct_warn(
+                    self.tcx,
+                    deny_warnings,
+                    WarningCategory::InteriorMut,
+                    Some(span),
+                    format!("the `{}` check may be unsound for type `{ty}`", kind.as_ref()),
+                )
+                .with_note(format!("the type has interior mutability, so {reason}"))
+                .emit();
+            }
+            Ok(_) => {}
+            Err(err) => {
+                self.tcx.dcx().span_err(
+                    span,
+                    format!("Failed to resolve type `{}` for `{}`: {err}", ty_name, kind.as_ref()),
+                );
+            }
+        }
+    }
+
+    /// Resolve the type whose methods are checked by a `proof_invariant_preserved` harness.
+    ///
+    /// In the case of an error, this function will emit the error and return `None`.
+    pub fn invariant_preserved_target(&self) -> Option<ty::Ty<'tcx>> {
+        let kind = KaniAttributeKind::ProofInvariantPreserved;
+        let ty_name = self.attribute_value(kind)?;
+        let current_module = self.tcx.parent_module_from_def_id(self.item.expect_local());
+        match resolve_ty(self.tcx, current_module.to_local_def_id(), ty_name.as_str()) {
+            Ok(ty) => Some(rustc_internal::internal(self.tcx, ty)),
+            Err(err) => {
+                self.tcx.dcx().span_err(
+                    self.map[&kind][0].span,
+                    format!("Failed to resolve type `{}` for `{}`: {err}", ty_name, kind.as_ref()),
+                );
+                None
+            }
+        }
+    }
+
+    /// Instantiate a harness annotated with `proof_over` for every combination of the values
+    /// and types listed for its generic parameters.
+    ///
+    /// Returns an empty vector if this is not a `proof_over` harness, or if its arguments are
+    /// invalid, which should have been reported by [`Self::check_attributes`].
+    pub fn proof_over_instances(&self) -> Vec<InstanceStable> {
+        let Some(attr) = self.map.get(&KaniAttributeKind::ProofOver).map(|attrs| attrs[0]) else {
+            return vec![];
+        };
+        let Some(param_values) = self.parse_proof_over(attr) else { return vec![] };
+        let combinations = param_values.into_iter().fold(vec![vec![]], |prefixes, values| {
+            prefixes
+                .into_iter()
+                .flat_map(|prefix: Vec<ty::GenericArg<'tcx>>| {
+                    values.iter().map(move |value| {
+                        let mut combination = prefix.clone();
+                        combination.push(*value);
+                        combination
+                    })
+                })
+                .collect()
+        });
+        combinations
+            .into_iter()
+            .map(|values| {
+                let mut values = values.into_iter();
+                let args =
+                    ty::GenericArgs::for_item(self.tcx, self.item, |param, _| match param.kind {
+                        GenericParamDefKind::Const { .. } | GenericParamDefKind::Type { .. } => {
+                            values.next().unwrap()
+                        }
+                        GenericParamDefKind::Lifetime => self.tcx.lifetimes.re_erased.into(),
+                    });
+                let instance = Instance::expect_resolve(
+                    self.tcx,
+                    ParamEnv::reveal_all(),
+                    self.item,
+                    args,
+                    attr.span,
+                );
+                rustc_internal::stable(instance)
+            })
+            .collect()
+    }
+
+    /// Parse the values given to each generic parameter in `proof_over(N = [...], T = [...])`.
+    ///
+    /// The result follows the order in which the parameters are declared. Emit an error and
+    /// return `None` if a parameter is not covered, if a value doesn't fit the parameter type, or
+    /// if a type doesn't satisfy the bounds of the harness.
+    fn parse_proof_over(&self, attr: &Attribute) -> Option<Vec<Vec<ty::GenericArg<'tcx>>>> {
+        let tcx = self.tcx;
+        let err = |msg: String| {
+            tcx.dcx().span_err(attr.span, msg);
+        };
+        let mut values = match parse_proof_over_args(attr) {
+            Ok(values) => values,
+            Err(msg) => {
+                err(format!("invalid arguments for `kani::proof_over`: {msg}"));
+                return None;
+            }
+        };
+        let generics = tcx.generics_of(self.item);
+        if generics.parent.is_some() {
+            err("`kani::proof_over` cannot be used on associated functions".to_string());
+            return None;
+        }
+        let mut result = Some(vec![]);
+        for param in &generics.own_params {
+            if matches!(param.kind, GenericParamDefKind::Lifetime) {
+                continue;
+            }
+            let Some(param_values) = values.remove(param.name.as_str()) else {
+                let kind = if matches!(param.kind, GenericParamDefKind::Type { .. }) {
+                    "type"
+                } else {
+                    "const"
+                };
+                err(format!("missing values for {kind} parameter `{}`", param.name));
+                result = None;
+                continue;
+            };
+            let args = match param.kind {
+                GenericParamDefKind::Lifetime => unreachable!(),
+                GenericParamDefKind::Type { .. } => {
+                    self.proof_over_types(attr.span, param, param_values)
+                }
+                GenericParamDefKind::Const { .. } => {
+                    self.proof_over_consts(attr.span, param, param_values)
+                }
+            };
+            match (&mut result, args) {
+                (Some(result), Some(args)) => result.push(args),
+                _ => result = None,
+            }
+        }
+        for name in values.keys() {
+            err(format!("`{name}` is not a generic parameter of this harness"));
+            result = None;
+        }
+        result.filter(|result| self.check_proof_over_bounds(attr.span, result))
+    }
+
+    /// Resolve the types listed for a type parameter of a `proof_over` harness.
+    fn proof_over_types(
+        &self,
+        span: Span,
+        param: &ty::GenericParamDef,
+        values: Vec<ProofOverValue>,
+    ) -> Option<Vec<ty::GenericArg<'tcx>>> {
+        let tcx = self.tcx;
+        let current_module = tcx.parent_module_from_def_id(self.item.expect_local());
+        let mut result = Some(vec![]);
+        for value in values {
+            let ProofOverValue::Type(typ) = value else {
+                tcx.dcx().span_err(
+                    span,
+                    format!("expected a type for type parameter `{}`, found `{value}`", param.name),
+                );
+                result = None;
+                continue;
+            };
+            match resolve_ty(tcx, current_module.to_local_def_id(), &typ) {
+                Ok(ty) => {
+                    if let Some(result) = &mut result {
+                        result.push(rustc_internal::internal(tcx, ty).into());
+                    }
+                }
+                Err(error) => {
+                    tcx.dcx().span_err(
+                        span,
+                        format!("failed to resolve type `{typ}` for `{}`: {error}", param.name),
+                    );
+                    result = None;
+                }
+            }
+        }
+        result
+    }
+
+    /// Build the constants listed for a const parameter of a `proof_over` harness.
+    fn proof_over_consts(
+        &self,
+        span: Span,
+        param: &ty::GenericParamDef,
+        values: Vec<ProofOverValue>,
+    ) -> Option<Vec<ty::GenericArg<'tcx>>> {
+        let tcx = self.tcx;
+        let ty = tcx.type_of(param.def_id).instantiate_identity();
+        if !ty.is_integral() {
+            tcx.dcx().span_err(
+                span,
+                format!(
+                    "`kani::proof_over` only supports integer parameters, but `{}` has \
+                    type `{ty}`",
+                    param.name
+                ),
+            );
+            return None;
+        }
+        let size = tcx.layout_of(ParamEnv::reveal_all().and(ty)).unwrap().size;
+        let (min, max) = if ty.is_signed() {
+            (size.signed_int_min(), size.signed_int_max())
+        } else {
+            (0, i128::try_from(size.unsigned_int_max()).unwrap_or(i128::MAX))
+        };
+        let values = values.into_iter().flat_map(|value| match value {
+            ProofOverValue::Boundary => BOUNDARY_VALUES
+                .into_iter()
+                .filter(|value| *value >= min)
+                .map(ProofOverValue::Int)
+                .collect(),
+            value => vec![value],
+        });
+        let mut result = Some(vec![]);
+        for value in values {
+            let ProofOverValue::Int(value) = value else {
+                tcx.dcx().span_err(
+                    span,
+                    format!(
+                        "expected an integer for const parameter `{}`, found `{value}`",
+                        param.name
+                    ),
+                );
+                result = None;
+                continue;
+            };
+            if value < min || value > max {
+                tcx.dcx().span_err(
+                    span,
+                    format!("value `{value}` for `{}` does not fit in type `{ty}`", param.name),
+                );
+                result = None;
+            } else if let Some(result) = &mut result {
+                let bits = size.truncate(value as u128);
+                let ty_env = ParamEnv::reveal_all().and(ty);
+                result.push(ty::Const::from_bits(tcx, bits, ty_env).into());
+            }
+        }
+        result
+    }
+
+    /// Report the types listed in `proof_over` that don't satisfy the bounds of the harness, since
+    /// it cannot be instantiated with them. Return whether all types satisfy the bounds.
+    fn check_proof_over_bounds(
+        &self,
+        span: Span,
+        param_values: &[Vec<ty::GenericArg<'tcx>>],
+    ) -> bool {
+        let tcx = self.tcx;
+        let mut valid = true;
+        let params = tcx
+            .generics_of(self.item)
+            .own_params
+            .iter()
+            .filter(|param| !matches!(param.kind, GenericParamDefKind::Lifetime));
+        for (param, values) in params.zip(param_values) {
+            let GenericParamDefKind::Type { .. } = param.kind else { continue };
+            for value in values {
+                // Only instantiate the parameter being checked, so the error points at the type
+                // that doesn't satisfy its bounds. Predicates with other parameters are ignored.
+                let args = ty::GenericArgs::for_item(tcx, self.item, |other, _| {
+                    if other.index == param.index { *value } else { tcx.mk_param_from_def(other) }
+                });
+                if tcx.instantiate_and_check_impossible_predicates((self.item, args)) {
+                    tcx.dcx().span_err(
+                        span,
+                        format!(
+                            "type `{value}` listed for `{}` does not satisfy the bounds of the \
+                            harness",
+                            param.name
+                        ),
+                    );
+                    valid = false;
+                }
+            }
+        }
+        valid
+    }
+
     fn item_name(&self) -> Symbol {
         self.tcx.item_name(self.item)
     }
@@ -613,6 +1539,13 @@ fn check_proof_attribute(&self, kind: KaniAttributeKind, proof_attribute: &Attri
                     kind.as_ref()
                 ),
             );
+        } else if self.map.contains_key(&KaniAttributeKind::ProofOver)
+            || self.map.contains_key(&KaniAttributeKind::ProofInvariantPreserved)
+        {
+            // The generic parameters are checked when the harness is instantiated.
+            if !tcx.fn_sig(self.item).skip_binder().inputs().skip_binder().is_empty() {
+                tcx.dcx().span_err(span, "functions used as harnesses cannot have any arguments");
+            }
         } else if tcx.generics_of(self.item).requires_monomorphization(tcx) {
             tcx.dcx().span_err(
                 span,
@@ -624,7 +1557,13 @@ fn check_proof_attribute(&self, kind: KaniAttributeKind, proof_attribute: &Attri
         } else {
             let instance = Instance::mono(tcx, self.item);
             if !super::fn_abi(tcx, instance).args.is_empty() {
-                tcx.dcx().span_err(span, "functions used as harnesses cannot have any arguments");
+                tcx.dcx()
+                    .struct_span_err(span, "functions used as harnesses cannot have any arguments")
+                    .with_help(
+                        "mark the arguments with `#[kani::any]` to give them a nondeterministic \
+                        value",
+                    )
+                    .emit();
             }
         }
     }
@@ -641,6 +1580,20 @@ fn has_kani_attribute<F: Fn(KaniAttributeKind) -> bool>(
     tcx.get_attrs_unchecked(def_id).iter().filter_map(|a| attr_kind(tcx, a)).any(predicate)
 }
 
+/// Resolve the path of a stub set used by `harness` to the function generated by
+/// `kani::stub_set!`.
+pub fn resolve_stub_set(tcx: TyCtxt, harness: DefId, path: &str) -> Result<LocalDefId, String> {
+    let current_module = resolution_scope(tcx, harness.expect_local());
+    let def_id = resolve_fn(tcx, current_module, path)
+        .map_err(|err| format!("failed to resolve stub set `{path}`: {err}"))?;
+    if !KaniAttributes::for_item(tcx, def_id).map.contains_key(&KaniAttributeKind::StubSet) {
+        return Err(format!("`{path}` is not a stub set declared with `kani::stub_set!`"));
+    }
+    def_id.as_local().ok_or_else(|| {
+        format!("stub set `{path}` must be declared in the crate under verification")
+    })
+}
+
 /// Same as [`KaniAttributes::is_proof_harness`] but more efficient because less
 /// attribute parsing is performed.
 pub fn is_proof_harness(tcx: TyCtxt, instance: InstanceStable) -> bool {
@@ -665,6 +1618,39 @@ pub fn test_harness_name(tcx: TyCtxt, def: &impl CrateDef) -> String {
     parse_str_value(&marker).unwrap()
 }
 
+/// Find the function generated by `#[kani::contract(name = "...", ...)]` for the named contract
+/// `contract` of `target`.
+///
+/// The generated function is defined next to the target, and its name is derived from the names
+/// of the target and the contract.
+pub(crate) fn named_contract_fn(tcx: TyCtxt, target: DefId, contract: Symbol) -> Option<DefId> {
+    let contract_fn = format!("__kani_contract_{}_{contract}", tcx.item_name(target));
+    sibling_fn(tcx, target, &contract_fn)
+}
+
+/// Find the function named `name` that is defined in the same `impl` block or module as `item`.
+fn sibling_fn(tcx: TyCtxt, item: DefId, name: &str) -> Option<DefId> {
+    let parent = tcx.opt_parent(item)?;
+    match tcx.def_kind(parent) {
+        DefKind::Impl { .. } => tcx
+            .associated_item_def_ids(parent)
+            .iter()
+            .copied()
+            .find(|def_id| tcx.item_name(*def_id).as_str() == name),
+        DefKind::Mod => {
+            let children = match parent.as_local() {
+                Some(local_parent) => tcx.module_children_local(local_parent),
+                None => tcx.module_children(parent),
+            };
+            children
+                .iter()
+                .find_map(|child| (child.ident.as_str() == name).then_some(child.res))
+                .and_then(|res| res.opt_def_id())
+        }
+        _ => None,
+    }
+}
+
 /// Expect the contents of this attribute to be of the format #[attribute =
 /// "value"] and return the `"value"`.
 fn expect_key_string_value(
@@ -713,8 +1699,9 @@ fn expect_single<'a>(
         .first()
         .expect(&format!("expected at least one attribute {} in {attributes:?}", kind.as_ref()));
     if attributes.len() > 1 {
+        let spans: Vec<Span> = attributes.iter().map(|attr| attr.span).collect();
         tcx.dcx().span_err(
-            attr.span,
+            spans,
             format!("only one '#[kani::{}]' attribute is allowed per harness", kind.as_ref()),
         );
     }
@@ -817,18 +1804,84 @@ fn parse_unwind(tcx: TyCtxt, attr: &Attribute) -> Option<u32> {
     }
 }
 
+fn parse_arithmetic_overflow(tcx: TyCtxt, attr: &Attribute) -> Option<ArithmeticOverflow> {
+    let mode = match attr.meta_item_list().as_deref() {
+        Some([arg]) if arg.is_word() => {
+            arg.ident().and_then(|ident| ArithmeticOverflow::from_str(ident.as_str()).ok())
+        }
+        _ => None,
+    };
+    if mode.is_none() {
+        tcx.dcx().span_err(
+            attr.span,
+            "invalid argument for `arithmetic_overflow` attribute, expected one of `wrap`, \
+            `check` or `panic`",
+        );
+    }
+    mode
+}
+
+/// Parse the checks listed by `#[kani::disable_checks(...)]` attributes, and report the ones that
+/// are not built-in checks.
+fn parse_disabled_checks(tcx: TyCtxt, attributes: &[&Attribute]) -> Vec<BuiltinCheck> {
+    let mut checks = vec![];
+    for attr in attributes {
+        let args = attr.meta_item_list().unwrap_or_default();
+        if args.is_empty() {
+            tcx.dcx().span_err(
+                attr.span,
+                "the `#[kani::disable_checks]` attribute expects at least one check",
+            );
+        }
+        for arg in &args {
+            let check = arg
+                .ident()
+                .filter(|_| arg.is_word())
+                .and_then(|ident| BuiltinCheck::from_str(ident.as_str()).ok());
+            match check {
+                Some(check) if !checks.contains(&check) => checks.push(check),
+                Some(_) => {}
+                None => {
+                    tcx.dcx()
+                        .struct_span_err(
+                            arg.span(),
+                            "invalid argument for `#[kani::disable_checks]` attribute",
+                        )
+                        .with_note(format!(
+                            "expected one of {}",
+                            BuiltinCheck::VARIANTS
+                                .iter()
+                                .map(|name| format!("`{name}`"))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ))
+                        .emit();
+                }
+            }
+        }
+    }
+    checks
+}
+
 fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<Stub> {
-    let current_module = tcx.parent_module_from_def_id(harness.expect_local());
+    let current_module = resolution_scope(tcx, harness.expect_local());
     let check_resolve = |attr: &Attribute, path: &TypePath| {
-        let result = resolve_fn_path(tcx, current_module.to_local_def_id(), path);
+        // Trait methods are stubbed in the implementation for the given type.
+        let result =
+            resolve_fn_path(tcx, current_module, path).and_then(|resolved| match resolved {
+                FnResolution::Fn(def) => {
+                    if rustc_internal::internal(tcx, def.def_id()) == harness {
+                        report_self_stub(tcx, harness, attr);
+                    }
+                    Ok(())
+                }
+                FnResolution::FnImpl { def, ty } => {
+                    let trait_fn = rustc_internal::internal(tcx, def.def_id());
+                    resolve_impl_item(tcx, trait_fn, ty).map(|_| ())
+                }
+            });
         match result {
-            Ok(FnResolution::Fn(_)) => { /* no-op */ }
-            Ok(FnResolution::FnImpl { .. }) => {
-                tcx.dcx().span_err(
-                    attr.span,
-                    "Kani currently does not support stubbing trait implementations.",
-                );
-            }
+            Ok(()) => { /* no-op */ }
             Err(err) => {
                 tcx.dcx().span_err(
                     attr.span,
@@ -877,6 +1930,173 @@ fn parse_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<St
         .collect()
 }
 
+/// Report a stub that replaces the harness that declares it, or that replaces a function with it.
+fn report_self_stub(tcx: TyCtxt, harness: DefId, stub: &Attribute) {
+    let harness_attrs = KaniAttributes::for_item(tcx, harness);
+    let spans: Vec<Span> = [KaniAttributeKind::Proof, KaniAttributeKind::ProofForContract]
+        .iter()
+        .filter_map(|kind| harness_attrs.map.get(kind))
+        .flatten()
+        .map(|attr| attr.span)
+        .chain([stub.span])
+        .collect();
+    tcx.dcx().span_err(
+        spans,
+        format!(
+            "the `#[kani::stub]` attribute of harness `{}` cannot target the harness itself",
+            tcx.def_path_str(harness)
+        ),
+    );
+}
+
+/// Parse the `stub_drop(Type, with = function)` attributes of a harness.
+///
+/// Emits an error for every attribute that is malformed or that cannot be resolved.
+fn parse_drop_stubs(tcx: TyCtxt, harness: DefId, attributes: &[&Attribute]) -> Vec<Stub> {
+    let current_module = resolution_scope(tcx, harness.expect_local());
+    attributes
+        .iter()
+        .filter_map(|attr| {
+            let (ty, replace) = parse_drop_stub_args(attr)
+                .map_err(|err| {
+                    tcx.dcx().span_err(
+                        attr.span,
+                        format!(
+                            "attribute `kani::{}` expects a type and a `with = function` \
+                            argument: {err}",
+                            KaniAttributeKind::StubDrop.as_ref()
+                        ),
+                    );
+                })
+                .ok()?;
+            let ty = ty.to_token_stream().to_string();
+            if let Err(err) = resolve_ty(tcx, current_module, &ty) {
+                tcx.dcx().span_err(attr.span, format!("failed to resolve `{ty}`: {err}"));
+                return None;
+            }
+            match resolve_fn_path(tcx, current_module, &replace) {
+                Ok(FnResolution::Fn(_)) => {
+                    Some(Stub { original: ty, replacement: replace.to_token_stream().to_string() })
+                }
+                Ok(FnResolution::FnImpl { .. }) => {
+                    tcx.dcx().span_err(
+                        attr.span,
+                        "Kani currently does not support stubbing with trait implementations.",
+                    );
+                    None
+                }
+                Err(err) => {
+                    tcx.dcx().span_err(
+                        attr.span,
+                        format!("failed to resolve `{}`: {err}", pretty_type_path(&replace)),
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Parse the arguments of `stub_drop(Type, with = function)`.
+fn parse_drop_stub_args(attr: &Attribute) -> Result<(syn::Type, TypePath), syn::Error> {
+    let syn_attr = syn_attr(attr);
+    syn_attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let ty = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let key: syn::Ident = input.parse()?;
+        if key != "with" {
+            return Err(syn::Error::new(key.span(), format!("expected `with`, found `{key}`")));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let replace = input.parse()?;
+        input.parse::<Option<syn::Token![,]>>()?;
+        Ok((ty, replace))
+    })
+}
+
+/// Parse the arguments of `proof_over(N = [0, 1], M = [-1, 1])` into a map from the name of each
+/// parameter to its values.
+fn parse_proof_over_args(
+    attr: &Attribute,
+) -> Result<BTreeMap<String, Vec<ProofOverValue>>, syn::Error> {
+    let syn_attr = syn_attr(attr);
+    syn_attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let mut result = BTreeMap::new();
+        loop {
+            let name: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            let values = if input.peek(syn::token::Bracket) {
+                let content;
+                syn::bracketed!(content in input);
+                let values =
+                    Punctuated::<ProofOverValue, syn::Token![,]>::parse_terminated(&content)?;
+                if values.is_empty() {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("no values given for `{name}`"),
+                    ));
+                }
+                values.into_iter().collect()
+            } else {
+                let set: syn::Ident = input.parse()?;
+                if set != "boundary" {
+                    return Err(syn::Error::new(
+                        set.span(),
+                        format!("expected a list of values or `boundary` for `{name}`"),
+                    ));
+                }
+                vec![ProofOverValue::Boundary]
+            };
+            if result.insert(name.to_string(), values).is_some() {
+                return Err(syn::Error::new(name.span(), format!("duplicated `{name}`")));
+            }
+            if input.parse::<Option<syn::Token![,]>>()?.is_none() || input.is_empty() {
+                break;
+            }
+        }
+        Ok(result)
+    })
+}
+
+/// A value listed for a generic parameter in `proof_over`, which is either an integer literal,
+/// optionally negated, e.g.: `10` or `-1`, or a type, e.g.: `u8` or `Vec<char>`.
+///
+/// `N = boundary` stands for the boundary values of a const parameter, see [BOUNDARY_VALUES].
+enum ProofOverValue {
+    Int(i128),
+    Type(String),
+    Boundary,
+}
+
+/// The values of a const parameter given with `N = boundary` in `proof_over`, which are the ones
+/// where implicit invariants such as `N > 0` are usually broken. Negative values are only used
+/// for signed parameters.
+const BOUNDARY_VALUES: [i128; 4] = [-1, 0, 1, 2];
+
+impl syn::parse::Parse for ProofOverValue {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitInt) || input.peek(syn::Token![-]) {
+            let negative = input.parse::<Option<syn::Token![-]>>()?.is_some();
+            let lit: syn::LitInt = input.parse()?;
+            let value: i128 = lit.base10_parse()?;
+            Ok(ProofOverValue::Int(if negative { -value } else { value }))
+        } else {
+            let typ: syn::Type = input.parse()?;
+            Ok(ProofOverValue::Type(typ.to_token_stream().to_string()))
+        }
+    }
+}
+
+impl std::fmt::Display for ProofOverValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofOverValue::Int(value) => write!(f, "{value}"),
+            ProofOverValue::Type(typ) => write!(f, "{typ}"),
+            ProofOverValue::Boundary => write!(f, "boundary"),
+        }
+    }
+}
+
 fn parse_solver(tcx: TyCtxt, attr: &Attribute) -> Option<CbmcSolver> {
     // TODO: Argument validation should be done as part of the `kani_macros` crate
     // <https://github.com/model-checking/kani/issues/2192>
@@ -0,0 +1,210 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Support for modules annotated with `#[kani::boundary]`.
+//!
+//! Every function outside of a boundary module that is called from inside the module is replaced
+//! by its contract, as if every harness had a `#[kani::stub_verified]` attribute for it.
+//! Functions without a contract are kept as is, and we emit a warning for each one of them,
+//! since their body is verified along with the module.
+//!
+//! Note that the replacement applies to the entire harness, including calls that do not come
+//! from the boundary module.
+//!
+//! With `--reachability-inline-never`, the local functions marked `#[inline(never)]` are also
+//! boundaries: they are replaced by their contract if they have one, and they are pruned
+//! otherwise, i.e., their return value is nondeterministic. See [crate::kani_middle::pruning].
+//! Functions without a contract whose return type doesn't implement `kani::Arbitrary` are not
+//! boundaries, since there's no valid nondeterministic value for them to return.
+
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::pruning::implements_arbitrary;
+use crate::kani_queries::QueryDb;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{TyCtxt, TypeVisitableExt};
+use rustc_smir::rustc_internal;
+use rustc_span::sym;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::mir::mono::Instance;
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::{CrateDef, CrateItem};
+use std::collections::HashSet;
+
+/// Collect the functions that should be replaced by their contracts because they are called from
+/// a boundary module.
+///
+/// Emit a warning for every function called from a boundary module that doesn't have a contract,
+/// and an error if the `function-contracts` feature is not enabled.
+pub fn boundary_contracts(tcx: TyCtxt, queries: &QueryDb) -> HashSet<DefId> {
+    let modules: Vec<DefId> = tcx
+        .hir()
+        .items()
+        .map(|item| item.owner_id.def_id.to_def_id())
+        .filter(|def_id| {
+            tcx.def_kind(*def_id) == DefKind::Mod
+                && KaniAttributes::for_item(tcx, *def_id).is_boundary()
+        })
+        .collect();
+    if modules.is_empty() {
+        return HashSet::default();
+    }
+    if !queries.args().unstable_features.iter().any(|feature| feature == "function-contracts") {
+        for module in &modules {
+            tcx.dcx().span_err(
+                tcx.def_span(*module),
+                "Using the boundary attribute requires activating the unstable \
+                `function-contracts` feature",
+            );
+        }
+        return HashSet::default();
+    }
+
+    let items = stable_mir::all_local_items();
+    let mut contracts = HashSet::new();
+    let mut reported = HashSet::new();
+    for module in modules {
+        for item in items.iter().filter(|item| {
+            let def_id = rustc_internal::internal(tcx, item.0);
+            tcx.is_descendant_of(def_id, module) && tcx.is_mir_available(def_id)
+        }) {
+            for (callee, span) in callees(tcx, item) {
+                if tcx.is_descendant_of(callee, module) {
+                    continue;
+                }
+                let attributes = KaniAttributes::for_item(tcx, callee);
+                if attributes.has_contract() {
+                    contracts.insert(callee);
+                } else if callee.is_local()
+                    && tcx.trait_of_item(callee).is_none()
+                    && !attributes.is_contract_generated()
+                    && reported.insert(callee)
+                {
+                    tcx.dcx()
+                        .struct_span_warn(
+                            rustc_internal::internal(tcx, span),
+                            format!(
+                                "function `{}` is called from the verification boundary `{}`, \
+                                but it doesn't have a contract",
+                                tcx.def_path_str(callee),
+                                tcx.def_path_str(module),
+                            ),
+                        )
+                        .with_note("its body will be verified along with the module")
+                        .emit();
+                }
+            }
+        }
+    }
+    contracts
+}
+
+/// The local functions marked `#[inline(never)]` that are analysis boundaries.
+#[derive(Debug, Default)]
+pub struct InlineNeverBoundaries {
+    /// The functions that are replaced by their contract.
+    pub contracts: HashSet<DefId>,
+    /// The functions without a contract, which are pruned.
+    pub pruned: HashSet<DefId>,
+}
+
+/// Collect the local functions marked `#[inline(never)]` if `--reachability-inline-never` is set.
+///
+/// Emit a warning for every function without a contract, since pruning it over-approximates its
+/// return value and ignores its other side effects, or since it's analyzed instead if its return
+/// type doesn't implement `kani::Arbitrary`.
+pub fn inline_never_boundaries(tcx: TyCtxt, queries: &QueryDb) -> InlineNeverBoundaries {
+    let mut boundaries = InlineNeverBoundaries::default();
+    if !queries.args().reachability_inline_never {
+        return boundaries;
+    }
+    let contracts_enabled =
+        queries.args().unstable_features.iter().any(|feature| feature == "function-contracts");
+    for item in stable_mir::all_local_items() {
+        let def_id = rustc_internal::internal(tcx, item.0);
+        if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            || !is_inline_never(tcx, def_id)
+        {
+            continue;
+        }
+        let attributes = KaniAttributes::for_item(tcx, def_id);
+        if attributes.is_proof_harness() || attributes.is_contract_generated() {
+            continue;
+        }
+        if attributes.has_contract() {
+            if !contracts_enabled {
+                tcx.dcx().span_err(
+                    tcx.def_span(def_id),
+                    "Replacing `#[inline(never)]` functions by their contracts requires \
+                    activating the unstable `function-contracts` feature",
+                );
+            }
+            boundaries.contracts.insert(def_id);
+            continue;
+        }
+        // The return type of generic functions is checked for each instance when pruning.
+        let sig = tcx.fn_sig(def_id).instantiate_identity();
+        let ret_ty = tcx.instantiate_bound_regions_with_erased(sig).output();
+        if !ret_ty.has_param() && !implements_arbitrary(tcx, ret_ty) {
+            tcx.dcx()
+                .struct_span_warn(
+                    tcx.def_span(def_id),
+                    format!(
+                        "function `{}` is marked `#[inline(never)]`, but it's not an analysis \
+                        boundary because it doesn't have a contract and its return type `{}` \
+                        doesn't implement `kani::Arbitrary`",
+                        tcx.def_path_str(def_id),
+                        ret_ty,
+                    ),
+                )
+                .with_note("its body will be analyzed")
+                .emit();
+            continue;
+        }
+        tcx.dcx()
+            .struct_span_warn(
+                tcx.def_span(def_id),
+                format!(
+                    "function `{}` is an analysis boundary because it's marked \
+                    `#[inline(never)]`, but it doesn't have a contract",
+                    tcx.def_path_str(def_id),
+                ),
+            )
+            .with_note(
+                "its return value will be nondeterministic, and its other side effects, such as \
+                writes through its arguments, will not be modeled",
+            )
+            .emit();
+        boundaries.pruned.insert(def_id);
+    }
+    boundaries
+}
+
+/// Whether the function is marked `#[inline(never)]`.
+fn is_inline_never(tcx: TyCtxt, def_id: DefId) -> bool {
+    tcx.get_attrs(def_id, sym::inline).any(|attr| {
+        attr.meta_item_list()
+            .is_some_and(|items| items.iter().any(|item| item.has_name(sym::never)))
+    })
+}
+
+/// Return the functions called by `item` and the span of each call.
+///
+/// Calls to trait methods are resolved to their implementation when the receiver type is known.
+fn callees(tcx: TyCtxt, item: &CrateItem) -> Vec<(DefId, stable_mir::ty::Span)> {
+    let body = item.body();
+    body.blocks
+        .iter()
+        .filter_map(|block| {
+            let TerminatorKind::Call { func, .. } = &block.terminator.kind else { return None };
+            let TyKind::RigidTy(RigidTy::FnDef(def, args)) = func.ty(body.locals()).ok()?.kind()
+            else {
+                return None;
+            };
+            let callee = Instance::resolve(def, &args)
+                .ok()
+                .and_then(|instance| instance.ty().kind().fn_def().map(|(def, _)| def))
+                .unwrap_or(def);
+            Some((rustc_internal::internal(tcx, callee.def_id()), block.terminator.span))
+        })
+        .collect()
+}
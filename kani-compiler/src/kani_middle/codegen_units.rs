@@ -8,25 +8,36 @@
 //! according to their stub configuration.
 
 use crate::args::ReachabilityType;
-use crate::kani_middle::attributes::is_proof_harness;
+use crate::kani_middle::attributes::{KaniAttributes, is_proof_harness, named_contract_fn};
+use crate::kani_middle::boundary::{boundary_contracts, inline_never_boundaries};
+use crate::kani_middle::invariant_preserved::invariant_preserved_harnesses;
 use crate::kani_middle::metadata::{gen_contracts_metadata, gen_proof_metadata};
+use crate::kani_middle::pruning::ReachabilityPolicy;
 use crate::kani_middle::reachability::filter_crate_items;
 use crate::kani_middle::resolve::expect_resolve_fn;
-use crate::kani_middle::stubbing::{check_compatibility, harness_stub_map};
+use crate::kani_middle::stable_fn_def;
+use crate::kani_middle::stubbing::{
+    check_compatibility, check_drop_stub_compatibility, fs_model_stubs, harness_drop_stub_map,
+    harness_stub_map,
+};
 use crate::kani_queries::QueryDb;
-use kani_metadata::{ArtifactType, AssignsContract, HarnessKind, HarnessMetadata, KaniMetadata};
+use kani_metadata::{
+    ArtifactType, AssignsContract, HarnessKind, HarnessMetadata, KaniMetadata, PropertyMetadata,
+    TypeStateVariable, UnsupportedFeature,
+};
 use rustc_hir::def_id::DefId;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::config::OutputType;
 use rustc_smir::rustc_internal;
-use stable_mir::CrateDef;
+use rustc_span::Symbol;
 use stable_mir::mir::mono::Instance;
-use stable_mir::ty::{FnDef, IndexedVal, RigidTy, TyKind};
+use stable_mir::ty::{FnDef, IndexedVal, RigidTy, Ty, TyKind};
+use stable_mir::{CrateDef, ItemKind};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
-use tracing::debug;
+use tracing::{debug, debug_span};
 
 /// An identifier for the harness function.
 type Harness = Instance;
@@ -34,6 +45,9 @@
 /// A set of stubs.
 pub type Stubs = HashMap<FnDef, FnDef>;
 
+/// A map from a type to the function that replaces its drop glue.
+pub type DropStubs = HashMap<Ty, FnDef>;
+
 /// Store some relevant information about the crate compilation.
 #[derive(Clone, Debug)]
 struct CrateInfo {
@@ -46,37 +60,69 @@ pub struct CodegenUnits {
     units: Vec<CodegenUnit>,
     harness_info: HashMap<Harness, HarnessMetadata>,
     crate_info: CrateInfo,
+    /// The unsupported constructs that are reachable from the harnesses.
+    unsupported_features: Vec<UnsupportedFeature>,
 }
 
 #[derive(Clone, Default, Debug)]
 pub struct CodegenUnit {
     pub harnesses: Vec<Harness>,
     pub stubs: Stubs,
+    pub drop_stubs: DropStubs,
+    /// Functions that are replaced by their contracts because they are called from a boundary
+    /// module.
+    pub boundary_stubs: HashSet<DefId>,
+    /// The functions that are replaced by a nondeterministic value of their return type.
+    pub policy: ReachabilityPolicy,
 }
 
 impl CodegenUnits {
     pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
         let crate_info = CrateInfo { name: stable_mir::local_crate().name.as_str().into() };
+        let _guard = debug_span!("codegen_units", crate_name = crate_info.name.as_str()).entered();
         if queries.args().reachability_analysis == ReachabilityType::Harnesses {
             let base_filepath = tcx.output_filenames(()).path(OutputType::Object);
             let base_filename = base_filepath.as_path();
-            let harnesses = filter_crate_items(tcx, |_, instance| is_proof_harness(tcx, instance));
+            let mut harnesses =
+                filter_crate_items(tcx, |_, instance| is_proof_harness(tcx, instance));
+            harnesses.extend(proof_over_harnesses(tcx));
+            harnesses.extend(invariant_preserved_harnesses(tcx));
             let all_harnesses = harnesses
                 .into_iter()
                 .map(|harness| {
-                    let metadata = gen_proof_metadata(tcx, harness, &base_filename);
+                    let mut metadata = gen_proof_metadata(tcx, harness, &base_filename);
+                    metadata.int_abstraction_bits = queries.args().int_abstraction_bits;
                     (harness, metadata)
                 })
                 .collect::<HashMap<_, _>>();
 
+            let model_stubs =
+                if queries.args().model_fs { fs_model_stubs(tcx) } else { HashMap::default() };
+            let inline_never = inline_never_boundaries(tcx, queries);
+            let mut boundary_stubs = boundary_contracts(tcx, queries);
+            boundary_stubs.extend(inline_never.contracts);
             // Even if no_stubs is empty we still need to store rustc metadata.
-            let units = group_by_stubs(tcx, &all_harnesses);
+            let mut units = group_by_stubs(tcx, &all_harnesses, &model_stubs, &boundary_stubs);
+            let policy = ReachabilityPolicy::new(tcx, queries, inline_never.pruned);
+            for unit in &mut units {
+                unit.policy = policy.clone();
+            }
             validate_units(tcx, &units);
             debug!(?units, "CodegenUnits::new");
-            CodegenUnits { units, harness_info: all_harnesses, crate_info }
+            CodegenUnits {
+                units,
+                harness_info: all_harnesses,
+                crate_info,
+                unsupported_features: vec![],
+            }
         } else {
             // Leave other reachability type handling as is for now.
-            CodegenUnits { units: vec![], harness_info: HashMap::default(), crate_info }
+            CodegenUnits {
+                units: vec![],
+                harness_info: HashMap::default(),
+                crate_info,
+                unsupported_features: vec![],
+            }
         }
     }
 
@@ -91,6 +137,42 @@ pub fn store_modifies(&mut self, harness_modifies: &[(Harness, AssignsContract)]
         }
     }
 
+    /// We store the properties that were generated for each harness.
+    pub fn store_properties(&mut self, harness_properties: Vec<(Harness, Vec<PropertyMetadata>)>) {
+        for (harness, properties) in harness_properties {
+            self.harness_info.get_mut(&harness).unwrap().properties = properties;
+        }
+    }
+
+    /// We store the type-state variables that were found in each harness.
+    pub fn store_type_states(
+        &mut self,
+        harness_type_states: Vec<(Harness, Vec<TypeStateVariable>)>,
+    ) {
+        for (harness, type_states) in harness_type_states {
+            self.harness_info.get_mut(&harness).unwrap().type_states = type_states;
+        }
+    }
+
+    /// We store the fingerprint of the model that was generated for each harness.
+    pub fn store_fingerprints(&mut self, harness_fingerprints: Vec<(Harness, Option<String>)>) {
+        for (harness, fingerprint) in harness_fingerprints {
+            self.harness_info.get_mut(&harness).unwrap().fingerprint = fingerprint;
+        }
+    }
+
+    /// We store the functions whose calls were assumed to return `Ok` in each harness.
+    pub fn store_assumed_ok(&mut self, harness_assumed_ok: Vec<(Harness, Vec<String>)>) {
+        for (harness, assumed_ok) in harness_assumed_ok {
+            self.harness_info.get_mut(&harness).unwrap().assumed_ok = assumed_ok;
+        }
+    }
+
+    /// We store the unsupported constructs that were found in all harnesses.
+    pub fn store_unsupported_features(&mut self, unsupported_features: Vec<UnsupportedFeature>) {
+        self.unsupported_features = unsupported_features;
+    }
+
     /// Write compilation metadata into a file.
     pub fn write_metadata(&self, queries: &QueryDb, tcx: TyCtxt) {
         let metadata = self.generate_metadata(tcx);
@@ -109,13 +191,23 @@ fn generate_metadata(&self, tcx: TyCtxt) -> KaniMetadata {
         KaniMetadata {
             crate_name: self.crate_info.name.clone(),
             proof_harnesses,
-            unsupported_features: vec![],
+            unsupported_features: self.unsupported_features.clone(),
             test_harnesses,
             contracted_functions: gen_contracts_metadata(tcx),
         }
     }
 }
 
+/// Instantiate the generic harnesses annotated with `#[kani::proof_over]` for each one of
+/// the values provided by the user.
+fn proof_over_harnesses(tcx: TyCtxt) -> Vec<Harness> {
+    stable_mir::all_local_items()
+        .into_iter()
+        .filter(|item| matches!(item.kind(), ItemKind::Fn))
+        .flat_map(|item| KaniAttributes::for_def_id(tcx, item.def_id()).proof_over_instances())
+        .collect()
+}
+
 fn stub_def(tcx: TyCtxt, def_id: DefId) -> FnDef {
     let ty_internal = tcx.type_of(def_id).instantiate_identity();
     let ty = rustc_internal::stable(ty_internal);
@@ -127,19 +219,46 @@ fn stub_def(tcx: TyCtxt, def_id: DefId) -> FnDef {
 }
 
 /// Group the harnesses by their stubs and contract usage.
+///
+/// The `model_stubs` are applied to every harness, unless the harness stubs the same function.
+/// The `boundary_stubs` are replaced by their contracts in every harness, except for the function
+/// whose contract is checked by the harness.
 fn group_by_stubs(
     tcx: TyCtxt,
     all_harnesses: &HashMap<Harness, HarnessMetadata>,
+    model_stubs: &HashMap<DefId, DefId>,
+    boundary_stubs: &HashSet<DefId>,
 ) -> Vec<CodegenUnit> {
     let mut per_stubs: HashMap<_, CodegenUnit> = HashMap::default();
     for (harness, metadata) in all_harnesses {
-        let stub_ids = harness_stub_map(tcx, *harness, metadata);
-        let contracts = extract_contracts(tcx, *harness, metadata);
+        let _guard = debug_span!("group_by_stubs", harness = harness.name()).entered();
+        let mut stub_ids = harness_stub_map(tcx, *harness, metadata);
+        for (from, to) in model_stubs {
+            stub_ids.entry(*from).or_insert(*to);
+        }
+        let drop_stub_ids = harness_drop_stub_map(tcx, *harness, metadata);
+        let mut contracts = extract_contracts(tcx, *harness, metadata);
+        let harness_boundary_stubs = boundary_stubs
+            .iter()
+            .copied()
+            .filter(|def_id| {
+                let Some(fn_def) = stable_fn_def(tcx, *def_id) else { return false };
+                let index = fn_def.def_id().to_index();
+                !contracts.contains(&ContractUsage::Check(index))
+            })
+            .collect::<HashSet<_>>();
+        contracts.extend(harness_boundary_stubs.iter().map(|def_id| {
+            ContractUsage::Stub(stable_fn_def(tcx, *def_id).unwrap().def_id().to_index())
+        }));
         let stub_map = stub_ids
             .iter()
             .map(|(k, v)| (tcx.def_path_hash(*k), tcx.def_path_hash(*v)))
             .collect::<BTreeMap<_, _>>();
-        let key = (contracts, stub_map);
+        let drop_stub_map = drop_stub_ids
+            .iter()
+            .map(|(ty, v)| (ty.to_string(), tcx.def_path_hash(*v)))
+            .collect::<BTreeMap<_, _>>();
+        let key = (contracts, stub_map, drop_stub_map);
         if let Some(unit) = per_stubs.get_mut(&key) {
             unit.harnesses.push(*harness);
         } else {
@@ -148,7 +267,17 @@ fn group_by_stubs(
                 .map(|(from, to)| (stub_def(tcx, *from), stub_def(tcx, *to)))
                 .collect::<HashMap<_, _>>();
             let stubs = apply_transitivity(tcx, *harness, stubs);
-            per_stubs.insert(key, CodegenUnit { stubs, harnesses: vec![*harness] });
+            let drop_stubs = drop_stub_ids
+                .iter()
+                .map(|(ty, to)| (*ty, stub_def(tcx, *to)))
+                .collect::<HashMap<_, _>>();
+            per_stubs.insert(key, CodegenUnit {
+                stubs,
+                drop_stubs,
+                boundary_stubs: harness_boundary_stubs,
+                harnesses: vec![*harness],
+                policy: ReachabilityPolicy::Full,
+            });
         }
     }
     per_stubs.into_values().collect()
@@ -171,9 +300,20 @@ fn extract_contracts(
 ) -> BTreeSet<ContractUsage> {
     let def = harness.def;
     let mut result = BTreeSet::new();
-    if let HarnessKind::ProofForContract { target_fn } = &metadata.attributes.kind {
-        if let Ok(check_def) = expect_resolve_fn(tcx, def, target_fn, "proof_for_contract") {
-            result.insert(ContractUsage::Check(check_def.def_id().to_index()));
+    if let HarnessKind::ProofForContract { target_fn, contract } = &metadata.attributes.kind {
+        if let Ok(target_def) = expect_resolve_fn(tcx, def, target_fn, "proof_for_contract") {
+            // Harnesses that check different named contracts of the same target cannot be grouped.
+            let check_def = match contract {
+                Some(contract) => {
+                    let target = rustc_internal::internal(tcx, target_def.def_id());
+                    named_contract_fn(tcx, target, Symbol::intern(contract))
+                        .and_then(|def_id| stable_fn_def(tcx, def_id))
+                }
+                None => Some(target_def),
+            };
+            if let Some(check_def) = check_def {
+                result.insert(ContractUsage::Check(check_def.def_id().to_index()));
+            }
         }
     }
 
@@ -213,6 +353,11 @@ fn validate_units(tcx: TyCtxt, units: &[CodegenUnit]) {
             let span = unit.harnesses.first().unwrap().def.span();
             tcx.dcx().span_err(rustc_internal::internal(tcx, span), msg);
         }
+        for (ty, to) in &unit.drop_stubs {
+            let Err(msg) = check_drop_stub_compatibility(tcx, *ty, *to) else { continue };
+            let span = unit.harnesses.first().unwrap().def.span();
+            tcx.dcx().span_err(rustc_internal::internal(tcx, span), msg);
+        }
     }
     tcx.dcx().abort_if_errors();
 }
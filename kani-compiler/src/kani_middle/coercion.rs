@@ -12,6 +12,12 @@
 //! references inside structures, allowing the unsized coercions of smart pointers. The
 //! definition of custom coercions for smart pointers can be found in the
 //! [RFC 982 DST Coercion](https://rust-lang.github.io/rfcs/0982-dst-coercion.html).
+//!
+//! Deref coercions, e.g. from `&Box<Name>` to `&str` through `Name: Deref<Target = String>`, don't
+//! need to be handled here. Each step of the chain is explicit in the MIR, either as a
+//! dereference of a builtin pointer, such as `Box`, or as a call to `Deref::deref` or
+//! `DerefMut::deref_mut`. Thus, the reachability analysis collects every implementation in the
+//! chain like any other call, and their stubs and contracts apply to the chain as well.
 
 use rustc_hir::lang_items::LangItem;
 use rustc_middle::traits::{ImplSource, ImplSourceUserDefinedData};
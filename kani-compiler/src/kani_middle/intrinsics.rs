@@ -1,7 +1,8 @@
 // Copyright Kani Contributors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! This module contains a MIR pass that replaces some intrinsics by rust intrinsics models as
-//! well as validation logic that can only be added during monomorphization.
+//! well as validation logic that can only be added during monomorphization. It also replaces calls
+//! to some of the LLVM intrinsics used by `core::arch` by their models.
 use rustc_index::IndexVec;
 use rustc_middle::mir::{Body, Const as mirConst, ConstValue, Operand, TerminatorKind};
 use rustc_middle::mir::{Local, LocalDecl};
@@ -39,6 +40,8 @@ pub fn transform(&self, body: &mut Body<'tcx>) {
                     if intrinsic_name == sym::simd_bitmask {
                         self.replace_simd_bitmask(func, args, generics)
                     }
+                } else if let Some(model) = llvm_intrinsic_model(self.tcx, func_ty) {
+                    self.replace_llvm_intrinsic(func, func_ty, model)
                 }
             }
         }
@@ -83,6 +86,62 @@ fn replace_simd_bitmask(
             debug!(?arg_ty, "replace_simd_bitmask failed");
         }
     }
+
+    /// Change the call to an LLVM intrinsic to use its model.
+    ///
+    /// The models take the type of the first argument and the return type of the intrinsic as
+    /// generic arguments, since the intrinsics are declared with types that are private to
+    /// `core::arch`.
+    fn replace_llvm_intrinsic(&self, func: &mut Operand<'tcx>, func_ty: Ty<'tcx>, model: &str) {
+        let tcx = self.tcx;
+        let Some(model_id) = tcx.get_diagnostic_item(Symbol::intern(model)) else {
+            // This should only happen when verifying the standard library.
+            // The backend will report the foreign function as unsupported.
+            return;
+        };
+        let sig = func_ty.fn_sig(tcx).skip_binder();
+        let (Some(arg_ty), ret_ty) = (sig.inputs().first(), sig.output()) else {
+            debug!(?func_ty, "replace_llvm_intrinsic failed");
+            return;
+        };
+        debug!(?func, ?model_id, "replace_llvm_intrinsic");
+        let Operand::Constant(fn_def) = func else { unreachable!() };
+        fn_def.const_ = mirConst::from_value(
+            ConstValue::ZeroSized,
+            tcx.type_of(model_id).instantiate(tcx, &[(*arg_ty).into(), ret_ty.into()]),
+        );
+    }
+}
+
+/// The LLVM intrinsics used by `core::arch` that have a model in our library, and the diagnostic
+/// item of their model. Calls to other LLVM intrinsics are reported as unsupported foreign
+/// functions by the backend.
+const LLVM_INTRINSIC_MODELS: &[(&str, &str)] = &[
+    ("llvm.x86.sse.cmp.ps", "KaniModelX86SseCmpPs"),
+    ("llvm.x86.sse.cmp.ss", "KaniModelX86SseCmpSs"),
+    ("llvm.x86.sse.min.ps", "KaniModelX86SseMinPs"),
+    ("llvm.x86.sse.min.ss", "KaniModelX86SseMinSs"),
+    ("llvm.x86.sse.max.ps", "KaniModelX86SseMaxPs"),
+    ("llvm.x86.sse.max.ss", "KaniModelX86SseMaxSs"),
+    ("llvm.x86.sse2.cmp.pd", "KaniModelX86Sse2CmpPd"),
+    ("llvm.x86.sse2.cmp.sd", "KaniModelX86Sse2CmpSd"),
+    ("llvm.x86.sse2.min.pd", "KaniModelX86Sse2MinPd"),
+    ("llvm.x86.sse2.min.sd", "KaniModelX86Sse2MinSd"),
+    ("llvm.x86.sse2.max.pd", "KaniModelX86Sse2MaxPd"),
+    ("llvm.x86.sse2.max.sd", "KaniModelX86Sse2MaxSd"),
+    ("llvm.x86.sse2.pmadd.wd", "KaniModelX86Sse2PmaddWd"),
+];
+
+/// Return the model of the given function if it's a foreign LLVM intrinsic that we model.
+fn llvm_intrinsic_model<'tcx>(tcx: TyCtxt<'tcx>, func_ty: Ty<'tcx>) -> Option<&'static str> {
+    let ty::FnDef(def_id, _) = *func_ty.kind() else { return None };
+    if !tcx.is_foreign_item(def_id) {
+        return None;
+    }
+    let link_name = tcx.codegen_fn_attrs(def_id).link_name?;
+    LLVM_INTRINSIC_MODELS
+        .iter()
+        .find_map(|(name, model)| (link_name.as_str() == *name).then_some(*model))
 }
 
 fn simd_len_and_type<'tcx>(tcx: TyCtxt<'tcx>, simd_ty: Ty<'tcx>) -> (Const<'tcx>, Ty<'tcx>) {
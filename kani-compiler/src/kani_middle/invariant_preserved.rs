@@ -0,0 +1,246 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Instantiate the harnesses annotated with `#[kani::proof_invariant_preserved(Type)]`.
+//!
+//! The macro turns the harness into a function that is generic over a type implementing
+//! `kani::invariant_preserved::PreservedCheck`. For every inherent public method of `Type` that
+//! takes `self`, we instantiate the harness with the check type that matches how the method takes
+//! `self`, e.g., `ByMut<Type, (u8, bool), {Type::push}>` for `fn push(&mut self, u8, bool)`.
+//!
+//! The methods that cannot be checked are skipped with a warning:
+//!   - Methods with generic parameters other than lifetimes.
+//!   - Methods whose other arguments don't implement `kani::Arbitrary`.
+//!   - Methods with too many arguments.
+//!
+//! Methods that take `self` through another type, such as `Box<Self>`, and methods that take
+//! `self` by value and don't return `Self` are not checked, since the invariant cannot be checked
+//! after they return.
+
+use crate::kani_middle::attributes::KaniAttributes;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{
+    self, GenericArgsRef, GenericParamDefKind, Instance, ParamEnv, Ty, TyCtxt, TypingMode,
+};
+use rustc_smir::rustc_internal;
+use rustc_span::Symbol;
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance as InstanceStable;
+use stable_mir::ty::{RigidTy, TyKind};
+
+/// The maximum number of arguments of a method, other than `self`.
+const MAX_ARGS: usize = 6;
+
+/// Instantiate the harnesses annotated with `#[kani::proof_invariant_preserved]` for each public
+/// method of their target type.
+pub fn invariant_preserved_harnesses(tcx: TyCtxt) -> Vec<InstanceStable> {
+    stable_mir::all_local_items()
+        .into_iter()
+        .filter(|item| matches!(item.kind(), stable_mir::ItemKind::Fn))
+        .flat_map(|item| {
+            let harness = rustc_internal::internal(tcx, item.def_id());
+            let attributes = KaniAttributes::for_item(tcx, harness);
+            match attributes.invariant_preserved_target() {
+                Some(target) => instantiate(tcx, harness, target),
+                None => vec![],
+            }
+        })
+        .collect()
+}
+
+/// The name of an instance of a `proof_invariant_preserved` harness, which includes the method it
+/// checks, e.g.: `check_stack::<Stack::push>`.
+///
+/// Returns `None` if the instance is not one of these harnesses.
+pub fn harness_name(tcx: TyCtxt, harness: InstanceStable) -> Option<String> {
+    let args = harness.args();
+    let [check] = args.0.as_slice() else { return None };
+    let TyKind::RigidTy(RigidTy::Adt(_, check_args)) = check.ty()?.kind() else { return None };
+    let method = check_args.0.get(2)?.ty()?.kind().fn_def()?.0;
+    let harness = rustc_internal::internal(tcx, harness.def.def_id());
+    let method = rustc_internal::internal(tcx, method.def_id());
+    KaniAttributes::for_item(tcx, harness)
+        .invariant_preserved_target()
+        .map(|_| format!("{}::<{}>", tcx.def_path_str(harness), tcx.def_path_str(method)))
+}
+
+/// How a method takes `self`, which selects the check type used to instantiate the harness.
+#[derive(Debug, Clone, Copy)]
+enum Receiver {
+    ByRef,
+    ByMut,
+    ByValue,
+}
+
+impl Receiver {
+    fn check_type(self) -> &'static str {
+        match self {
+            Receiver::ByRef => "KaniPreservedByRef",
+            Receiver::ByMut => "KaniPreservedByMut",
+            Receiver::ByValue => "KaniPreservedByValue",
+        }
+    }
+}
+
+fn instantiate<'tcx>(tcx: TyCtxt<'tcx>, harness: DefId, target: Ty<'tcx>) -> Vec<InstanceStable> {
+    let span = tcx.def_span(harness);
+    let ty::Adt(adt_def, target_args) = target.kind() else {
+        tcx.dcx().span_err(
+            span,
+            format!("`proof_invariant_preserved` expected a struct or enum, but found `{target}`"),
+        );
+        return vec![];
+    };
+    let Some(arbitrary) = tcx.get_diagnostic_item(Symbol::intern("KaniArbitrary")) else {
+        return vec![];
+    };
+    let implements = |ty: Ty<'tcx>, trait_def: DefId| {
+        tcx.infer_ctxt()
+            .build(TypingMode::PostAnalysis)
+            .type_implements_trait(trait_def, [ty], ParamEnv::reveal_all())
+            .must_apply_modulo_regions()
+    };
+    if !implements(target, arbitrary) {
+        tcx.dcx().span_err(
+            span,
+            format!(
+                "`proof_invariant_preserved` requires `{target}` to implement `kani::Arbitrary`"
+            ),
+        );
+        return vec![];
+    }
+
+    let methods = tcx
+        .inherent_impls(adt_def.did())
+        .iter()
+        .filter_map(|impl_def| impl_args(tcx, *impl_def, target, target_args))
+        .flat_map(|impl_args| {
+            let (impl_def, args) = impl_args;
+            tcx.associated_items(impl_def)
+                .in_definition_order()
+                .filter(|item| {
+                    item.fn_has_self_parameter && tcx.visibility(item.def_id).is_public()
+                })
+                .map(move |item| (item.def_id, args))
+        });
+
+    let mut harnesses = vec![];
+    for (method, impl_args) in methods {
+        let warn = |reason: String| {
+            tcx.dcx()
+                .struct_span_warn(
+                    tcx.def_span(method),
+                    format!(
+                        "method `{}` is not checked by `proof_invariant_preserved` harness `{}`",
+                        tcx.def_path_str(method),
+                        tcx.def_path_str(harness)
+                    ),
+                )
+                .with_note(reason)
+                .emit();
+        };
+        let generics = tcx.generics_of(method);
+        if generics
+            .own_params
+            .iter()
+            .any(|param| !matches!(param.kind, GenericParamDefKind::Lifetime))
+        {
+            warn("methods with generic parameters are not supported".into());
+            continue;
+        }
+        let method_args = ty::GenericArgs::for_item(tcx, method, |param, _| match param.kind {
+            GenericParamDefKind::Lifetime => tcx.lifetimes.re_erased.into(),
+            _ => impl_args[param.index as usize],
+        });
+        let sig = tcx.instantiate_bound_regions_with_erased(
+            tcx.fn_sig(method).instantiate(tcx, method_args),
+        );
+        let (self_arg, args) = sig.inputs().split_first().unwrap();
+        let receiver = match self_arg.kind() {
+            _ if *self_arg == target && sig.output() == target => Receiver::ByValue,
+            ty::Ref(_, inner, ty::Mutability::Not) if *inner == target => Receiver::ByRef,
+            ty::Ref(_, inner, ty::Mutability::Mut) if *inner == target => Receiver::ByMut,
+            _ => continue,
+        };
+        if args.len() > MAX_ARGS {
+            warn(format!("only methods with up to {MAX_ARGS} arguments are supported"));
+            continue;
+        }
+        let non_arbitrary: Vec<_> = args
+            .iter()
+            .filter(|arg| !implements(**arg, arbitrary))
+            .map(|arg| format!("`{arg}`"))
+            .collect();
+        if !non_arbitrary.is_empty() {
+            warn(format!(
+                "the following argument types don't implement `kani::Arbitrary`: {}",
+                non_arbitrary.join(", ")
+            ));
+            continue;
+        }
+        let Some(check_def) = tcx.get_diagnostic_item(Symbol::intern(receiver.check_type())) else {
+            return vec![];
+        };
+        let check = Ty::new_adt(
+            tcx,
+            tcx.adt_def(check_def),
+            tcx.mk_args(&[
+                target.into(),
+                Ty::new_tup(tcx, args).into(),
+                Ty::new_fn_def(tcx, method, method_args).into(),
+            ]),
+        );
+        let harness_args = tcx.mk_args(&[check.into()]);
+        if tcx.instantiate_and_check_impossible_predicates((harness, harness_args)) {
+            warn(format!("`{target}` must implement `kani::Invariant`"));
+            continue;
+        }
+        let instance =
+            Instance::expect_resolve(tcx, ParamEnv::reveal_all(), harness, harness_args, span);
+        harnesses.push(rustc_internal::stable(instance));
+    }
+    if harnesses.is_empty() {
+        tcx.dcx().span_warn(
+            span,
+            format!("`{target}` doesn't have any public method that can be checked"),
+        );
+    }
+    harnesses
+}
+
+/// The generic arguments of an inherent impl of the target type, if it applies to the target.
+///
+/// We support impls without generic parameters for the exact target type, e.g., `impl Stack<u8>`,
+/// and impls for the generic type, e.g., `impl<T> Stack<T>`.
+fn impl_args<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    impl_def: DefId,
+    target: Ty<'tcx>,
+    target_args: GenericArgsRef<'tcx>,
+) -> Option<(DefId, GenericArgsRef<'tcx>)> {
+    let self_ty = tcx.erase_regions(tcx.type_of(impl_def).instantiate_identity());
+    let generics = tcx.generics_of(impl_def);
+    let has_params = generics
+        .own_params
+        .iter()
+        .any(|param| !matches!(param.kind, GenericParamDefKind::Lifetime));
+    if !has_params {
+        return (self_ty == tcx.erase_regions(target)).then_some((impl_def, ty::List::empty()));
+    }
+    let ty::Adt(_, self_args) = self_ty.kind() else { return None };
+    // The impl parameters must be the parameters of the type in the same order.
+    let identity = self_args.iter().zip(&generics.own_params).all(|(arg, param)| {
+        match (arg.unpack(), &param.kind) {
+            (ty::GenericArgKind::Lifetime(_), GenericParamDefKind::Lifetime) => true,
+            (ty::GenericArgKind::Type(ty), GenericParamDefKind::Type { .. }) => {
+                matches!(ty.kind(), ty::Param(p) if p.index == param.index)
+            }
+            (ty::GenericArgKind::Const(ct), GenericParamDefKind::Const { .. }) => {
+                matches!(ct.kind(), ty::ConstKind::Param(p) if p.index == param.index)
+            }
+            _ => false,
+        }
+    });
+    (identity && self_args.len() == generics.own_params.len())
+        .then(|| (impl_def, tcx.erase_regions(target_args)))
+}
@@ -7,7 +7,7 @@
 use std::path::Path;
 
 use crate::kani_middle::attributes::{KaniAttributes, test_harness_name};
-use crate::kani_middle::{SourceLocation, stable_fn_def};
+use crate::kani_middle::{SourceLocation, invariant_preserved, stable_fn_def};
 use kani_metadata::ContractedFunction;
 use kani_metadata::{ArtifactType, HarnessAttributes, HarnessKind, HarnessMetadata};
 use rustc_middle::ty::TyCtxt;
@@ -18,7 +18,8 @@
 pub fn gen_proof_metadata(tcx: TyCtxt, instance: Instance, base_name: &Path) -> HarnessMetadata {
     let def = instance.def;
     let kani_attributes = KaniAttributes::for_instance(tcx, instance);
-    let pretty_name = instance.name();
+    let pretty_name =
+        invariant_preserved::harness_name(tcx, instance).unwrap_or_else(|| instance.name());
     let mangled_name = instance.mangled_name();
 
     // We get the body span to include the entire function definition.
@@ -38,6 +39,11 @@ pub fn gen_proof_metadata(tcx: TyCtxt, instance: Instance, base_name: &Path) ->
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
         contract: Default::default(),
+        properties: vec![],
+        type_states: vec![],
+        fingerprint: None,
+        assumed_ok: vec![],
+        int_abstraction_bits: None,
     }
 }
 
@@ -56,7 +62,12 @@ pub fn gen_contracts_metadata(tcx: TyCtxt) -> Vec<ContractedFunction> {
         let file = SourceLocation::new(item.span()).filename;
         let attributes = KaniAttributes::for_def_id(tcx, item.def_id());
 
-        if attributes.has_contract() {
+        if attributes.is_contract_generated() {
+            // Skip the functions generated for named contracts, which are reported as contracts
+            // of their original function.
+            continue;
+        }
+        if attributes.contract().is_some() || !attributes.named_contracts().is_empty() {
             fn_to_data.insert(item.def_id(), ContractedFunction {
                 function,
                 file,
@@ -108,5 +119,10 @@ pub fn gen_test_metadata(
         // TODO: This no longer needs to be an Option.
         goto_file: Some(model_file),
         contract: Default::default(),
+        properties: vec![],
+        type_states: vec![],
+        fingerprint: None,
+        assumed_ok: vec![],
+        int_abstraction_bits: None,
     }
 }
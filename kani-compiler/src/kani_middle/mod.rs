@@ -5,7 +5,9 @@
 
 use std::collections::HashSet;
 
+use crate::args::{Arguments, ReachabilityType, WarningCategory};
 use crate::kani_queries::QueryDb;
+use rustc_errors::{Diag, Level};
 use rustc_hir::{def::DefKind, def_id::DefId as InternalDefId, def_id::LOCAL_CRATE};
 use rustc_middle::span_bug;
 use rustc_middle::ty::layout::{
@@ -14,8 +16,9 @@
 };
 use rustc_middle::ty::{self, Instance as InstanceInternal, Ty as TyInternal, TyCtxt};
 use rustc_smir::rustc_internal;
-use rustc_span::Span;
+use rustc_span::hygiene::{ExpnKind, MacroKind};
 use rustc_span::source_map::respan;
+use rustc_span::{Span, sym};
 use rustc_target::abi::call::FnAbi;
 use rustc_target::abi::{HasDataLayout, TargetDataLayout};
 use stable_mir::CrateDef;
@@ -28,27 +31,39 @@
 
 pub mod analysis;
 pub mod attributes;
+pub mod boundary;
 pub mod codegen_units;
 pub mod coercion;
 mod intrinsics;
+pub mod invariant_preserved;
 pub mod metadata;
 pub mod points_to;
 pub mod provide;
+pub mod pruning;
 pub mod reachability;
+pub mod reproducer;
 pub mod resolve;
+pub mod send_safety;
 pub mod stubbing;
 pub mod transform;
 
 /// Check that all crate items are supported and there's no misconfiguration.
 /// This method will exhaustively print any error / warning and it will abort at the end if any
 /// error was found.
-pub fn check_crate_items(tcx: TyCtxt, ignore_asm: bool) {
+pub fn check_crate_items(tcx: TyCtxt, args: &Arguments) {
     let krate = tcx.crate_name(LOCAL_CRATE);
+    // Only nudge users about missing harness annotations in the crate being verified.
+    let warn_missing_proof = args.reachability_analysis == ReachabilityType::Harnesses
+        && !args.build_std
+        && !args.no_missing_proof_warning;
     for item in tcx.hir().items() {
         let def_id = item.owner_id.def_id.to_def_id();
-        KaniAttributes::for_item(tcx, def_id).check_attributes();
+        KaniAttributes::for_item(tcx, def_id).check_attributes(&args.deny_warnings);
+        if warn_missing_proof {
+            check_missing_proof(tcx, def_id, &args.deny_warnings);
+        }
         if tcx.def_kind(def_id) == DefKind::GlobalAsm {
-            if !ignore_asm {
+            if !args.ignore_global_asm {
                 let error_msg = format!(
                     "Crate {krate} contains global ASM, which is not supported by Kani. Rerun with \
                     `--enable-unstable --ignore-global-asm` to suppress this error \
@@ -56,15 +71,76 @@ pub fn check_crate_items(tcx: TyCtxt, ignore_asm: bool) {
                 );
                 tcx.dcx().err(error_msg);
             } else {
-                tcx.dcx().warn(format!(
-                    "Ignoring global ASM in crate {krate}. Verification results may be impacted.",
-                ));
+                struct_warn(
+                    tcx,
+                    &args.deny_warnings,
+                    WarningCategory::GlobalAsm,
+                    None,
+                    format!(
+                        "Ignoring global ASM in crate {krate}. Verification results may be impacted."
+                    ),
+                )
+                .emit();
             }
         }
     }
     tcx.dcx().abort_if_errors();
 }
 
+/// Warn about a function without arguments that is named like a harness, i.e., `proof_*`,
+/// `*_proof` or `verify_*`, but isn't annotated with `#[kani::proof]`, since the annotation was
+/// likely forgotten.
+fn check_missing_proof(tcx: TyCtxt, def_id: InternalDefId, deny_warnings: &[WarningCategory]) {
+    if tcx.def_kind(def_id) != DefKind::Fn {
+        return;
+    }
+    let name = tcx.item_name(def_id);
+    let name = name.as_str();
+    if !(name.starts_with("proof_") || name.ends_with("_proof") || name.starts_with("verify_")) {
+        return;
+    }
+    if !tcx.fn_sig(def_id).skip_binder().inputs().skip_binder().is_empty() {
+        return;
+    }
+    let attributes = KaniAttributes::for_item(tcx, def_id);
+    if attributes.is_proof_harness() || attributes.is_contract_generated() {
+        return;
+    }
+    struct_warn(
+        tcx,
+        deny_warnings,
+        WarningCategory::MissingProof,
+        Some(tcx.def_span(def_id)),
+        format!("function `{name}` is named like a harness, but it is not a harness"),
+    )
+    .with_help("add `#[kani::proof]` to verify it")
+    .with_note("use `--no-missing-proof-warning` to disable this warning")
+    .emit();
+}
+
+/// Create a warning of the given category, which is reported as an error instead if the category
+/// is denied by `--deny-warnings`.
+pub fn struct_warn<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    deny_warnings: &[WarningCategory],
+    category: WarningCategory,
+    span: Option<Span>,
+    msg: String,
+) -> Diag<'tcx, ()> {
+    // Report the category itself if it's denied, since it's the more specific flag.
+    let denied_by =
+        [category, WarningCategory::All].into_iter().find(|denied| deny_warnings.contains(denied));
+    let level = if denied_by.is_some() { Level::Error } else { Level::Warning };
+    let mut diag = Diag::new(tcx.dcx(), level, msg);
+    if let Some(span) = span {
+        diag.span(span);
+    }
+    if let Some(denied_by) = denied_by {
+        diag.note(format!("this warning is denied by `--deny-warnings={}`", denied_by.as_ref()));
+    }
+    diag
+}
+
 /// Traverse the type definition to see if the type contains interior mutability.
 ///
 /// See <https://doc.rust-lang.org/reference/interior-mutability.html> for more details.
@@ -131,6 +207,7 @@ pub fn check_reachable_items(tcx: TyCtxt, queries: &QueryDb, items: &[MonoItem])
 /// Structure that represents the source location of a definition.
 /// TODO: Use `InternedString` once we move it out of the cprover_bindings.
 /// <https://github.com/model-checking/kani/issues/2435>
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SourceLocation {
     pub filename: String,
     pub start_line: usize,
@@ -153,6 +230,28 @@ pub fn new(span: SpanStable) -> Self {
     }
 }
 
+/// The span written by the user for code that comes from the expansion of a macro.
+///
+/// This walks the macro backtrace of the span up to the outermost invocation of a function-like
+/// macro in the local crate, e.g., the call to `my_assert!(..)` for the code that `my_assert`
+/// expands to, even if it does so through other macros. The walk stops at `include!` boundaries,
+/// so the code of an included file keeps its own locations.
+///
+/// The span is returned unchanged if it doesn't come from a macro invoked in the local crate.
+/// In particular, the macros of the standard library keep the location of their definition,
+/// which is more precise than their invocation.
+pub fn user_written_span(tcx: TyCtxt, span: Span) -> Span {
+    let source_map = tcx.sess.source_map();
+    span.macro_backtrace()
+        .take_while(|expn| expn.kind != ExpnKind::Macro(MacroKind::Bang, sym::include))
+        .filter(|expn| {
+            matches!(expn.kind, ExpnKind::Macro(MacroKind::Bang, _))
+                && !source_map.is_imported(expn.call_site)
+        })
+        .last()
+        .map_or(span, |expn| expn.call_site)
+}
+
 /// Get the FnAbi of a given instance with no extra variadic arguments.
 /// TODO: Get rid of this. Use instance.fn_abi() instead.
 /// <https://github.com/model-checking/kani/issues/1365>
@@ -164,6 +263,11 @@ pub fn fn_abi<'tcx>(
     helper.fn_abi_of_instance(instance, ty::List::empty())
 }
 
+/// Helper used to invoke the rustc layout and ABI queries.
+///
+/// Note that we don't need to cache the result of these queries. They are memoized by the rustc
+/// query system, which uses the `ParamEnv` together with the instance and extra arguments as the
+/// cache key, so repeated queries for the same instance are cheap and never stale.
 struct CompilerHelpers<'tcx> {
     tcx: TyCtxt<'tcx>,
 }
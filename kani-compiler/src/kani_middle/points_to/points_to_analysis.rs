@@ -606,6 +606,7 @@ fn is_identity_aliasing_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::BlackBox
         | Intrinsic::Breakpoint
         | Intrinsic::Bswap
+        | Intrinsic::CallerLocation
         | Intrinsic::CeilF32
         | Intrinsic::CeilF64
         | Intrinsic::CompareBytes
@@ -0,0 +1,176 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Support for the `--denylist` and `--allowlist` options, which restrict the functions that
+//! reachability analysis descends into.
+//!
+//! The body of a pruned function is replaced by `kani::any()` for its return type, so none of the
+//! items it depends on are collected. Note that the function's other side effects, such as writes
+//! through its arguments, are not modeled.
+//!
+//! A function whose return type doesn't implement `kani::Arbitrary`, e.g. one that returns a
+//! reference, has no valid nondeterministic value to return, so it's analyzed instead and
+//! reported with a warning.
+//!
+//! A function is pruned if:
+//! - With `--denylist`, it's one of the listed functions.
+//! - With `--allowlist`, it's a function of the local crate that is not listed.
+//! - With `--reachability-inline-never`, it's a function of the local crate marked
+//!   `#[inline(never)]` that doesn't have a contract.
+//!
+//! Closures are pruned along with the function that defines them. The harnesses, the items
+//! generated by the contract macros and the functions that never return are never pruned.
+
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::stubbing::resolve_root_fn;
+use crate::kani_queries::QueryDb;
+use itertools::Itertools;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{ParamEnv, Ty, TyCtxt, TypingMode};
+use rustc_smir::rustc_internal;
+use rustc_span::Symbol;
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::{Instance, InstanceKind, MonoItem};
+use stable_mir::ty::{RigidTy, TyKind};
+use std::collections::HashSet;
+
+/// The functions that reachability analysis should not descend into.
+#[derive(Clone, Debug, Default)]
+pub enum ReachabilityPolicy {
+    /// Analyze every reachable function.
+    #[default]
+    Full,
+    /// Prune the given functions.
+    Deny(HashSet<DefId>),
+    /// Prune the local functions that are not in the given set.
+    Allow(HashSet<DefId>),
+}
+
+impl ReachabilityPolicy {
+    /// Resolve the paths given to `--denylist` or `--allowlist` from the crate root. The
+    /// `boundaries` are pruned along with the functions given to `--denylist`.
+    ///
+    /// An error is emitted for every path that cannot be resolved.
+    pub fn new(tcx: TyCtxt, queries: &QueryDb, boundaries: HashSet<DefId>) -> Self {
+        let args = queries.args();
+        let resolve = |option: &str, paths: &[String]| {
+            let mut functions = HashSet::new();
+            for path in paths {
+                match resolve_root_fn(tcx, path) {
+                    Ok(def_id) => {
+                        functions.insert(def_id);
+                    }
+                    Err(err) => {
+                        tcx.dcx().err(format!("failed to resolve `{path}` for `{option}`: {err}"));
+                    }
+                }
+            }
+            functions
+        };
+        if !args.reachability_denylist.is_empty() {
+            let mut functions = resolve("--denylist", &args.reachability_denylist);
+            functions.extend(boundaries);
+            ReachabilityPolicy::Deny(functions)
+        } else if !args.reachability_allowlist.is_empty() {
+            ReachabilityPolicy::Allow(resolve("--allowlist", &args.reachability_allowlist))
+        } else if !boundaries.is_empty() {
+            ReachabilityPolicy::Deny(boundaries)
+        } else {
+            ReachabilityPolicy::Full
+        }
+    }
+
+    /// Whether every reachable function is analyzed.
+    pub fn is_full(&self) -> bool {
+        matches!(self, ReachabilityPolicy::Full)
+    }
+
+    /// Whether the body of the given instance should be replaced by a nondeterministic value.
+    pub fn prunes(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        self.selects(tcx, instance) && returns_arbitrary(tcx, instance)
+    }
+
+    /// Whether the given instance is selected to be pruned, regardless of its return type.
+    fn selects(&self, tcx: TyCtxt, instance: Instance) -> bool {
+        if instance.kind != InstanceKind::Item {
+            return false;
+        }
+        let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+        let root = tcx.typeck_root_def_id(def_id);
+        let listed = match self {
+            ReachabilityPolicy::Full => false,
+            ReachabilityPolicy::Deny(functions) => functions.contains(&root),
+            ReachabilityPolicy::Allow(functions) => root.is_local() && !functions.contains(&root),
+        };
+        if !listed {
+            return false;
+        }
+        let attributes = KaniAttributes::for_item(tcx, root);
+        if attributes.is_proof_harness() || attributes.is_contract_generated() {
+            return false;
+        }
+        instance
+            .fn_abi()
+            .is_ok_and(|abi| !matches!(abi.ret.ty.kind(), TyKind::RigidTy(RigidTy::Never)))
+    }
+}
+
+/// Whether the return type of the instance implements `kani::Arbitrary`, so `kani::any()` can
+/// return any valid value of it.
+fn returns_arbitrary(tcx: TyCtxt, instance: Instance) -> bool {
+    instance
+        .fn_abi()
+        .is_ok_and(|abi| implements_arbitrary(tcx, rustc_internal::internal(tcx, abi.ret.ty)))
+}
+
+/// Whether the given type implements `kani::Arbitrary`.
+pub fn implements_arbitrary<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+    let Some(arbitrary) = tcx.get_diagnostic_item(Symbol::intern("KaniArbitrary")) else {
+        return false;
+    };
+    tcx.infer_ctxt()
+        .build(TypingMode::PostAnalysis)
+        .type_implements_trait(arbitrary, [ty], ParamEnv::reveal_all())
+        .must_apply_modulo_regions()
+}
+
+/// Emit a note with the functions that were pruned from the given harness, and a warning with
+/// the ones that were selected but couldn't be pruned.
+pub fn report_pruned(
+    tcx: TyCtxt,
+    policy: &ReachabilityPolicy,
+    harness: Instance,
+    items: &[MonoItem],
+) {
+    let (pruned, kept): (Vec<_>, Vec<_>) = items
+        .iter()
+        .filter_map(|item| match item {
+            MonoItem::Fn(instance) if instance.has_body() && policy.selects(tcx, *instance) => {
+                let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+                let name = tcx.def_path_str(tcx.typeck_root_def_id(def_id));
+                Some((name, returns_arbitrary(tcx, *instance)))
+            }
+            _ => None,
+        })
+        .sorted()
+        .dedup()
+        .partition(|(_, pruned)| *pruned);
+    let names = |functions: Vec<(String, bool)>| {
+        functions.iter().map(|(name, _)| format!("`{name}`")).join(", ")
+    };
+    let harness_def = rustc_internal::internal(tcx, harness.def.def_id());
+    let harness_name = tcx.def_path_str(harness_def);
+    if !pruned.is_empty() {
+        tcx.dcx().note(format!(
+            "harness `{harness_name}` treats the following functions as nondeterministic: {}",
+            names(pruned)
+        ));
+    }
+    if !kept.is_empty() {
+        tcx.dcx().warn(format!(
+            "harness `{harness_name}` analyzes the following functions instead of pruning them, \
+            because their return type doesn't implement `kani::Arbitrary`: {}",
+            names(kept)
+        ));
+    }
+}
@@ -21,7 +21,8 @@
 use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
-use rustc_middle::ty::{TyCtxt, VtblEntry};
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{self, GenericArgKind, TyCtxt, VtblEntry};
 use rustc_session::config::OutputType;
 use rustc_smir::rustc_internal;
 use stable_mir::CrateItem;
@@ -31,7 +32,9 @@
     Body, CastKind, ConstOperand, MirVisitor, PointerCoercion, Rvalue, Terminator, TerminatorKind,
     visit::Location,
 };
-use stable_mir::ty::{Allocation, ClosureKind, ConstantKind, RigidTy, Ty, TyKind};
+use stable_mir::ty::{
+    Allocation, ClosureKind, ConstantKind, FnDef, GenericArgs, RigidTy, Span, Ty, TyKind,
+};
 use stable_mir::{CrateDef, ItemKind};
 use std::fmt::{Display, Formatter};
 use std::{
@@ -42,6 +45,7 @@
 
 use crate::kani_middle::coercion;
 use crate::kani_middle::coercion::CoercionBase;
+use crate::kani_middle::resolve::resolve_opaque_return;
 use crate::kani_middle::transform::BodyTransformation;
 
 /// Collect all reachable items starting from the given starting points.
@@ -50,6 +54,7 @@ pub fn collect_reachable_items(
     transformer: &mut BodyTransformation,
     starting_points: &[MonoItem],
 ) -> (Vec<MonoItem>, CallGraph) {
+    let _guard = debug_span!("collect_reachable_items", roots = starting_points.len()).entered();
     // For each harness, collect items using the same collector.
     // I.e.: This will return any item that is reachable from one or more of the starting points.
     let mut collector = MonoItemsCollector::new(tcx, transformer);
@@ -201,7 +206,11 @@ fn reachable_items(&mut self) {
 
     /// Visit a function and collect all mono-items reachable from its instructions.
     fn visit_fn(&mut self, instance: Instance) -> Vec<CollectedItem> {
-        let _guard = debug_span!("visit_fn", function=?instance).entered();
+        let _guard = debug_span!("visit_fn", function = instance.name()).entered();
+        if let Some(concrete) = resolve_opaque_return(self.tcx, instance) {
+            let concrete = rustc_internal::internal(self.tcx, concrete).to_string();
+            self.call_graph.add_opaque_return(instance.into(), concrete);
+        }
         let body = self.transformer.body(self.tcx, instance);
         let mut collector =
             MonoItemsFnCollector { tcx: self.tcx, collected: FxHashSet::default(), body: &body };
@@ -211,7 +220,7 @@ fn visit_fn(&mut self, instance: Instance) -> Vec<CollectedItem> {
 
     /// Visit a static object and collect drop / initialization functions.
     fn visit_static(&mut self, def: StaticDef) -> Vec<CollectedItem> {
-        let _guard = debug_span!("visit_static", ?def).entered();
+        let _guard = debug_span!("visit_static", static_def = def.name()).entered();
         let mut next_items = vec![];
 
         // Collect drop function.
@@ -332,6 +341,44 @@ fn collect_allocation(&mut self, alloc: &Allocation) {
             )
         }
     }
+
+    /// Report a function that cannot be resolved for the given arguments instead of crashing.
+    ///
+    /// Resolution can fail when the signature of the function projects a generic associated type
+    /// that cannot be normalized for the concrete arguments, so we call this case out.
+    fn report_unresolved(&self, fn_def: FnDef, args: &GenericArgs, span: Span) {
+        let def_id = rustc_internal::internal(self.tcx, fn_def.def_id());
+        let args = rustc_internal::internal(self.tcx, args);
+        let span = rustc_internal::internal(self.tcx, span);
+        let name = self.tcx.def_path_str_with_args(def_id, args);
+        if let Some(gat) = find_gat_projection(self.tcx, def_id) {
+            self.tcx.dcx().span_err(
+                span,
+                format!(
+                    "unsupported GAT pattern: Kani cannot resolve `{name}`, which uses the \
+                    generic associated type `{gat}` in its signature"
+                ),
+            );
+        } else {
+            self.tcx.dcx().span_err(span, format!("Kani cannot resolve the call to `{name}`"));
+        }
+    }
+}
+
+/// Find a projection of a generic associated type in the signature of the given function.
+fn find_gat_projection(tcx: TyCtxt, def_id: DefId) -> Option<String> {
+    let sig = tcx.fn_sig(def_id).instantiate_identity().skip_binder();
+    sig.inputs_and_output.iter().flat_map(|ty| ty.walk()).find_map(|arg| match arg.unpack() {
+        GenericArgKind::Type(ty) => match ty.kind() {
+            ty::Alias(ty::Projection, alias)
+                if !tcx.generics_of(alias.def_id).own_params.is_empty() =>
+            {
+                Some(tcx.def_path_str(alias.def_id))
+            }
+            _ => None,
+        },
+        _ => None,
+    })
 }
 
 /// Visit every instruction in a function and collect the following:
@@ -381,8 +428,10 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
             ) => {
                 let fn_kind = operand.ty(self.body.locals()).unwrap().kind();
                 if let RigidTy::FnDef(fn_def, args) = fn_kind.rigid().unwrap() {
-                    let instance = Instance::resolve_for_fn_ptr(*fn_def, args).unwrap();
-                    self.collect_instance(instance, false);
+                    match Instance::resolve_for_fn_ptr(*fn_def, args) {
+                        Ok(instance) => self.collect_instance(instance, false),
+                        Err(_) => self.report_unresolved(*fn_def, args, location.span()),
+                    }
                 } else {
                     unreachable!("Expected FnDef type, but got: {:?}", fn_kind);
                 }
@@ -443,8 +492,10 @@ fn visit_terminator(&mut self, terminator: &Terminator, location: Location) {
             TerminatorKind::Call { ref func, .. } => {
                 let fn_ty = func.ty(self.body.locals()).unwrap();
                 if let TyKind::RigidTy(RigidTy::FnDef(fn_def, args)) = fn_ty.kind() {
-                    let instance = Instance::resolve(fn_def, &args).unwrap();
-                    self.collect_instance(instance, true);
+                    match Instance::resolve(fn_def, &args) {
+                        Ok(instance) => self.collect_instance(instance, true),
+                        Err(_) => self.report_unresolved(fn_def, &args, terminator.span),
+                    }
                 } else {
                     assert!(
                         matches!(fn_ty.kind().rigid(), Some(RigidTy::FnPtr(..))),
@@ -534,6 +585,8 @@ pub struct CallGraph {
     edges: HashMap<Node, Vec<CollectedNode>>,
     /// Since the graph is directed, we also store back edges.
     back_edges: HashMap<Node, Vec<CollectedNode>>,
+    /// The concrete type returned by functions that are declared to return `impl Trait`.
+    opaque_returns: HashMap<Node, String>,
 }
 
 /// Newtype around MonoItem.
@@ -577,6 +630,20 @@ fn add_edges(&mut self, from: MonoItem, to: &[CollectedItem]) {
         }
     }
 
+    /// Record the concrete type behind the `impl Trait` returned by the given function.
+    fn add_opaque_return(&mut self, item: MonoItem, concrete: String) {
+        self.opaque_returns.insert(Node(item), concrete);
+    }
+
+    /// Write a node, labeled with its concrete return type if it returns an `impl Trait`.
+    fn dump_node<W: Write>(&self, writer: &mut W, node: &Node) -> std::io::Result<()> {
+        if let Some(concrete) = self.opaque_returns.get(node) {
+            writeln!(writer, r#""{node}" [label="{node}\nreturns {concrete}"]"#)
+        } else {
+            writeln!(writer, r#""{node}""#)
+        }
+    }
+
     /// Print the graph in DOT format to a file.
     /// See <https://graphviz.org/doc/info/lang.html> for more information.
     fn dump_dot(&self, tcx: TyCtxt, initial: Option<MonoItem>) -> std::io::Result<()> {
@@ -610,7 +677,7 @@ fn dump_dot(&self, tcx: TyCtxt, initial: Option<MonoItem>) -> std::io::Result<()
     fn dump_all<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         tracing::info!(nodes=?self.nodes.len(), edges=?self.edges.len(), "dump_all");
         for node in &self.nodes {
-            writeln!(writer, r#""{node}""#)?;
+            self.dump_node(writer, node)?;
             for succ in self.edges.get(node).unwrap() {
                 let reason = succ.0.reason;
                 writeln!(writer, r#""{node}" -> "{succ}" [label={reason:?}] "#)?;
@@ -639,7 +706,7 @@ fn dump_reason<W: Write>(&self, writer: &mut W, target: &str) -> std::io::Result
         }
 
         for node in &visited {
-            writeln!(writer, r#""{node}""#)?;
+            self.dump_node(writer, node)?;
             let edges = self.edges.get(node).unwrap();
             for succ in edges.iter().filter(|item| {
                 let node = Node::from((*item).clone());
@@ -0,0 +1,524 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Export the local items that a harness depends on as a standalone crate, which can be shared to
+//! reproduce an issue without the rest of the crate.
+//!
+//! We start from the local items that are reachable from the harness, and we add the local items
+//! that their source refers to, such as types, constants and traits, so the result compiles. Trait
+//! implementations of the kept types are kept as well, since they may be required by trait bounds.
+//!
+//! The items are emitted with their original source text inside their original modules, except
+//! for:
+//!   - Inherent implementations, which only keep their reachable items.
+//!   - Items generated by a `derive`, which are regenerated by the attribute of their type.
+//!   - Items generated by other macros, which are emitted as their expanded code with a note.
+//!
+//! Dependencies other than the standard library and Kani are not included in the reproducer.
+
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{CRATE_DEF_ID, DefId, LOCAL_CRATE, LocalDefId};
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_hir::{BodyId, Expr, HirId, ImplItemRef, ItemKind, Node, Path, QPath, UseKind};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::{self, TyCtxt, TypeckResults};
+use rustc_smir::rustc_internal;
+use rustc_span::hygiene::{ExpnKind, MacroKind};
+use rustc_span::{BytePos, Span};
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::{Instance, MonoItem};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path as FilePath;
+
+/// Write a crate with the dependencies of the harness to the given directory.
+///
+/// The crate is written to a file named after the harness, and we emit a note with its path.
+pub fn export_reproducer(tcx: TyCtxt, harness: Instance, items: &[MonoItem], dir: &FilePath) {
+    let mut reproducer = Reproducer::new(tcx);
+    for item in items {
+        let def_id = match item {
+            MonoItem::Fn(instance) => rustc_internal::internal(tcx, instance.def.def_id()),
+            MonoItem::Static(def) => rustc_internal::internal(tcx, def.0),
+            MonoItem::GlobalAsm(_) => continue,
+        };
+        reproducer.keep(def_id);
+    }
+    reproducer.close();
+
+    let harness_def = rustc_internal::internal(tcx, harness.def.def_id());
+    let harness_name = tcx.def_path_str(harness_def);
+    let file_name: String = harness_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    let path = dir.join(format!("{file_name}.rs"));
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&path, reproducer.source(&harness_name)));
+    match result {
+        Ok(_) => tcx.dcx().note(format!(
+            "wrote the reproducer of harness `{harness_name}` to `{}`",
+            path.display()
+        )),
+        Err(err) => {
+            tcx.dcx().err(format!(
+                "failed to write the reproducer of harness `{harness_name}` to `{}`: {err}",
+                path.display()
+            ));
+        }
+    }
+}
+
+struct Reproducer<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    /// The items to emit whose parent is a module.
+    items: HashSet<LocalDefId>,
+    /// The associated items to emit.
+    assoc_items: HashSet<LocalDefId>,
+    /// The items that haven't been visited yet.
+    queue: Vec<LocalDefId>,
+    /// The typeck results of the body being visited.
+    typeck: Option<&'tcx TypeckResults<'tcx>>,
+}
+
+impl<'tcx> Reproducer<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>) -> Self {
+        Reproducer {
+            tcx,
+            items: HashSet::new(),
+            assoc_items: HashSet::new(),
+            queue: vec![],
+            typeck: None,
+        }
+    }
+
+    /// Keep the item that contains the given definition, if it's local.
+    ///
+    /// Definitions that are not emitted on their own, such as closures, fields and items nested
+    /// in a function, are kept through their enclosing item.
+    fn keep(&mut self, def_id: DefId) {
+        let Some(mut local) = def_id.as_local() else { return };
+        loop {
+            match self.tcx.def_kind(local) {
+                DefKind::Mod | DefKind::Use | DefKind::ExternCrate | DefKind::GlobalAsm => return,
+                DefKind::AssocFn | DefKind::AssocConst | DefKind::AssocTy => {
+                    if self.assoc_items.insert(local) {
+                        self.queue.push(local);
+                    }
+                    local = self.tcx.local_parent(local);
+                }
+                _ => {
+                    let parent = self.tcx.local_parent(local);
+                    match self.tcx.def_kind(parent) {
+                        DefKind::Mod => {
+                            if self.items.insert(local) {
+                                self.queue.push(local);
+                            }
+                            return;
+                        }
+                        _ => local = parent,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keep every item that the kept items refer to, until no new item is found.
+    fn close(&mut self) {
+        loop {
+            while let Some(local) = self.queue.pop() {
+                self.visit_def(local);
+            }
+            // Keep the trait implementations of the kept types, which may be required by trait
+            // bounds that the references don't show.
+            let impls: Vec<LocalDefId> = self
+                .tcx
+                .hir()
+                .items()
+                .map(|id| id.owner_id.def_id)
+                .filter(|def_id| !self.items.contains(def_id))
+                .filter(|def_id| {
+                    matches!(self.tcx.def_kind(*def_id), DefKind::Impl { of_trait: true })
+                        && self.is_kept_type(self.tcx.type_of(*def_id).instantiate_identity())
+                })
+                .collect();
+            if impls.is_empty() {
+                break;
+            }
+            for def_id in impls {
+                self.keep(def_id.to_def_id());
+            }
+        }
+    }
+
+    fn is_kept_type(&self, ty: ty::Ty<'tcx>) -> bool {
+        match ty.kind() {
+            ty::Adt(def, _) => {
+                def.did().as_local().is_some_and(|local| self.items.contains(&local))
+            }
+            _ => false,
+        }
+    }
+
+    fn visit_def(&mut self, local: LocalDefId) {
+        match self.tcx.hir_node_by_def_id(local) {
+            Node::Item(item) => match &item.kind {
+                ItemKind::Impl(impl_) => {
+                    self.visit_generics(impl_.generics);
+                    if let Some(trait_ref) = &impl_.of_trait {
+                        self.visit_trait_ref(trait_ref);
+                        // Implementations of a trait must define all of its required items.
+                        for item in impl_.items {
+                            self.keep(item.id.owner_id.to_def_id());
+                        }
+                    }
+                    self.visit_ty(impl_.self_ty);
+                }
+                ItemKind::Trait(.., items) => {
+                    intravisit::walk_item(self, item);
+                    for item in *items {
+                        self.keep(item.id.owner_id.to_def_id());
+                    }
+                }
+                _ => intravisit::walk_item(self, item),
+            },
+            Node::ImplItem(item) => intravisit::walk_impl_item(self, item),
+            Node::TraitItem(item) => intravisit::walk_trait_item(self, item),
+            _ => {}
+        }
+    }
+
+    /// Generate the source of the reproducer.
+    fn source(&self, harness_name: &str) -> String {
+        let tcx = self.tcx;
+        let mut source = String::new();
+        writeln!(source, "// Reproducer of the harness `{harness_name}` generated by Kani.")
+            .unwrap();
+        writeln!(source, "//").unwrap();
+        writeln!(
+            source,
+            "// It contains the items of crate `{}` that the harness depends on, and it was \
+            compiled with edition {}.",
+            tcx.crate_name(LOCAL_CRATE),
+            tcx.sess.edition()
+        )
+        .unwrap();
+        writeln!(
+            source,
+            "// Dependencies other than the standard library and Kani must be added back."
+        )
+        .unwrap();
+        for attr in tcx.hir().krate_attrs() {
+            if let Some(snippet) = self.snippet(attr.span) {
+                writeln!(source, "{snippet}").unwrap();
+            }
+        }
+
+        // Group the items by module, sorted by their position in the source.
+        let mut modules: HashMap<LocalDefId, Vec<(Span, String)>> = HashMap::new();
+        for local in &self.items {
+            if let Some(text) = self.item_source(*local) {
+                let module = tcx.parent_module_from_def_id(*local).to_local_def_id();
+                modules.entry(module).or_default().push((tcx.source_span(*local), text));
+            }
+        }
+        let mut emitted_modules = HashSet::new();
+        for module in modules.keys().copied().collect::<Vec<_>>() {
+            let mut module = module;
+            while emitted_modules.insert(module) && module != CRATE_DEF_ID {
+                module = tcx.parent_module_from_def_id(module).to_local_def_id();
+            }
+        }
+        for id in tcx.hir().items() {
+            let local = id.owner_id.def_id;
+            let module = tcx.parent_module_from_def_id(local).to_local_def_id();
+            if !emitted_modules.contains(&module) {
+                continue;
+            }
+            let text = match tcx.def_kind(local) {
+                DefKind::Use => self.use_source(local, &emitted_modules),
+                DefKind::ExternCrate => self.snippet(tcx.source_span(local)),
+                DefKind::Mod => {
+                    if emitted_modules.contains(&local) {
+                        modules.entry(local).or_default();
+                    }
+                    None
+                }
+                _ => None,
+            };
+            if let Some(text) = text {
+                modules.entry(module).or_default().push((tcx.source_span(local), text));
+            }
+        }
+        self.write_module(&mut source, CRATE_DEF_ID, &mut modules);
+        source
+    }
+
+    /// Write the content of a module, including its nested modules.
+    fn write_module(
+        &self,
+        out: &mut String,
+        module: LocalDefId,
+        modules: &mut HashMap<LocalDefId, Vec<(Span, String)>>,
+    ) {
+        let tcx = self.tcx;
+        let mut entries: Vec<(Span, Option<LocalDefId>, String)> = modules
+            .remove(&module)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(span, text)| (span, None, text))
+            .collect();
+        let submodules: Vec<LocalDefId> = modules
+            .keys()
+            .copied()
+            .filter(|sub| tcx.def_kind(*sub) == DefKind::Mod && tcx.local_parent(*sub) == module)
+            .collect();
+        for sub in submodules {
+            entries.push((tcx.def_span(sub), Some(sub), String::new()));
+        }
+        entries.sort_by_key(|(span, _, _)| span.lo());
+        let indent = self.indentation(module);
+        for (idx, (_, sub, text)) in entries.into_iter().enumerate() {
+            if idx > 0 || module == CRATE_DEF_ID {
+                writeln!(out).unwrap();
+            }
+            match sub {
+                Some(sub) => {
+                    let vis = if tcx.visibility(sub).is_public() { "pub " } else { "" };
+                    let name = tcx.item_name(sub.to_def_id());
+                    writeln!(out, "{indent}{vis}mod {name} {{").unwrap();
+                    self.write_module(out, sub, modules);
+                    writeln!(out, "{indent}}}").unwrap();
+                }
+                None => writeln!(out, "{text}").unwrap(),
+            }
+        }
+    }
+
+    /// The source of an item whose parent is a module, or `None` if it's derived.
+    fn item_source(&self, local: LocalDefId) -> Option<String> {
+        let tcx = self.tcx;
+        let item = tcx.hir().expect_item(local);
+        if let ItemKind::Impl(impl_) = &item.kind
+            && impl_.of_trait.is_none()
+            && !tcx.source_span(local).from_expansion()
+        {
+            return self.inherent_impl_source(local, impl_.self_ty.span, impl_.items);
+        }
+        self.def_source(local)
+    }
+
+    /// The source of an inherent implementation with only the items that are kept.
+    fn inherent_impl_source(
+        &self,
+        local: LocalDefId,
+        self_ty: Span,
+        items: &[ImplItemRef],
+    ) -> Option<String> {
+        let sm = self.tcx.sess.source_map();
+        let span = with_outer_attributes(self.tcx, self.tcx.source_span(local));
+        let header = sm.span_to_snippet(span.with_hi(self_ty.hi())).ok()?;
+        let rest = sm.span_to_snippet(span.with_lo(self_ty.hi())).ok()?;
+        let brace = rest.find('{')?;
+        let mut text = format!("{header}{}", &rest[..=brace]);
+        let item_texts = items
+            .iter()
+            .map(|item| item.id.owner_id.def_id)
+            .filter(|item| self.assoc_items.contains(item))
+            .filter_map(|item| self.def_source(item));
+        for (idx, item_text) in item_texts.enumerate() {
+            let separator = if idx == 0 { "\n" } else { "\n\n" };
+            write!(text, "{separator}{item_text}").unwrap();
+        }
+        let indent = &header[..header.len() - header.trim_start().len()];
+        write!(text, "\n{indent}}}").unwrap();
+        Some(text)
+    }
+
+    /// The source of a definition, including its attributes.
+    ///
+    /// Definitions generated by a macro other than a `derive` are rendered from their HIR, since
+    /// they don't have a source text.
+    fn def_source(&self, local: LocalDefId) -> Option<String> {
+        let tcx = self.tcx;
+        let span = tcx.source_span(local);
+        if span.from_expansion() {
+            return match span.ctxt().outer_expn_data().kind {
+                ExpnKind::Macro(MacroKind::Derive | MacroKind::Attr, _) => None,
+                _ => {
+                    let hir_id = tcx.local_def_id_to_hir_id(local);
+                    let text = rustc_hir_pretty::id_to_string(&tcx.hir(), hir_id);
+                    Some(format!(
+                        "// Note: This item was generated by a macro, and it's emitted as its \
+                        expansion.\n{text}"
+                    ))
+                }
+            };
+        }
+        self.snippet(with_outer_attributes(tcx, span))
+    }
+
+    /// Regenerate a `use` item, if everything that it imports is emitted.
+    fn use_source(&self, local: LocalDefId, modules: &HashSet<LocalDefId>) -> Option<String> {
+        let item = self.tcx.hir().expect_item(local);
+        let ItemKind::Use(path, kind) = &item.kind else { return None };
+        // Skip the imports that are injected by the compiler, such as the prelude.
+        if item.span.from_expansion() || item.span.is_dummy() {
+            return None;
+        }
+        let is_emitted = |res: &Res| match res {
+            Res::Def(DefKind::Mod, def_id) => {
+                def_id.as_local().is_none_or(|local| modules.contains(&local))
+            }
+            Res::Def(DefKind::Macro(_), def_id) => !def_id.is_local(),
+            Res::Def(_, def_id) => def_id.as_local().is_none_or(|local| self.is_emitted(local)),
+            _ => true,
+        };
+        if !path.res.iter().all(is_emitted) {
+            return None;
+        }
+        let segments: Vec<String> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .filter(|segment| segment != "{{root}}")
+            .collect();
+        let path_str = segments.join("::");
+        let vis = match self.tcx.visibility(local) {
+            ty::Visibility::Public => "pub ",
+            ty::Visibility::Restricted(module)
+                if module == self.tcx.parent_module_from_def_id(local).to_def_id() =>
+            {
+                ""
+            }
+            ty::Visibility::Restricted(_) => "pub(crate) ",
+        };
+        let indent = self.indentation(self.tcx.parent_module_from_def_id(local).to_local_def_id());
+        match kind {
+            UseKind::Single if segments.last() != Some(&item.ident.to_string()) => {
+                Some(format!("{indent}{vis}use {path_str} as {};", item.ident))
+            }
+            UseKind::Single => Some(format!("{indent}{vis}use {path_str};")),
+            UseKind::Glob => Some(format!("{indent}{vis}use {path_str}::*;")),
+            UseKind::ListStem => None,
+        }
+    }
+
+    /// The indentation of the items of a module, according to how deeply it's nested.
+    fn indentation(&self, module: LocalDefId) -> String {
+        let mut depth = 0;
+        let mut module = module;
+        while module != CRATE_DEF_ID {
+            depth += 1;
+            module = self.tcx.local_parent(module);
+        }
+        "    ".repeat(depth)
+    }
+
+    /// Whether the item that contains the given definition is emitted.
+    fn is_emitted(&self, local: LocalDefId) -> bool {
+        let mut local = local;
+        loop {
+            if self.items.contains(&local) || self.assoc_items.contains(&local) {
+                return true;
+            }
+            if self.tcx.def_kind(local) == DefKind::Mod {
+                return false;
+            }
+            local = self.tcx.local_parent(local);
+        }
+    }
+
+    /// The source text of a span of a source file.
+    fn snippet(&self, span: Span) -> Option<String> {
+        let sm = self.tcx.sess.source_map();
+        if span.is_dummy()
+            || span.from_expansion()
+            || !sm.lookup_source_file(span.lo()).name.is_real()
+        {
+            return None;
+        }
+        sm.span_to_snippet(span).ok()
+    }
+}
+
+/// Extend the span of an item to the attributes and doc comments in the lines above it, which
+/// are not part of its span, and to the indentation of its first line.
+fn with_outer_attributes(tcx: TyCtxt, span: Span) -> Span {
+    let file = tcx.sess.source_map().lookup_source_file(span.lo());
+    let Some(src) = file.src.as_ref() else { return span };
+    let is_attr = |line: &str| line.starts_with("#[") || line.starts_with("///");
+    let mut lo = (span.lo() - file.start_pos).0 as usize;
+    loop {
+        let line_start = src[..lo].rfind('\n').map_or(0, |idx| idx + 1);
+        if line_start == 0 || !src[line_start..lo].trim().is_empty() {
+            break;
+        }
+        let prev_start = src[..line_start - 1].rfind('\n').map_or(0, |idx| idx + 1);
+        let prev = src[prev_start..line_start - 1].trim();
+        let attr_start = if is_attr(prev) {
+            Some(prev_start)
+        } else if prev.ends_with(']') {
+            // The end of an attribute that spans multiple lines.
+            src[..prev_start]
+                .match_indices('\n')
+                .rev()
+                .take(10)
+                .map(|(idx, _)| idx + 1)
+                .take_while(|start| {
+                    let line = src[*start..].lines().next().unwrap_or_default().trim();
+                    !line.ends_with(';') && !line.ends_with('}')
+                })
+                .find(|start| src[*start..].trim_start().starts_with("#["))
+        } else {
+            None
+        };
+        let Some(attr_start) = attr_start else { break };
+        lo = attr_start + (src[attr_start..].len() - src[attr_start..].trim_start().len());
+    }
+    let line_start = src[..lo].rfind('\n').map_or(0, |idx| idx + 1);
+    if src[line_start..lo].trim().is_empty() {
+        lo = line_start;
+    }
+    span.with_lo(file.start_pos + BytePos(lo as u32))
+}
+
+impl<'tcx> Visitor<'tcx> for Reproducer<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_nested_body(&mut self, id: BodyId) {
+        let typeck = self.typeck.replace(self.tcx.typeck_body(id));
+        intravisit::walk_body(self, self.tcx.hir().body(id));
+        self.typeck = typeck;
+    }
+
+    fn visit_path(&mut self, path: &Path<'tcx>, _id: HirId) {
+        if let Res::Def(_, def_id) = path.res {
+            self.keep(def_id);
+        }
+        intravisit::walk_path(self, path)
+    }
+
+    /// Keep the definitions of type relative paths, e.g. `Foo::new`.
+    fn visit_qpath(&mut self, qpath: &'tcx QPath<'tcx>, id: HirId, _span: Span) {
+        if let Some(typeck) = self.typeck
+            && let Res::Def(_, def_id) = typeck.qpath_res(qpath, id)
+        {
+            self.keep(def_id);
+        }
+        intravisit::walk_qpath(self, qpath, id)
+    }
+
+    /// Keep the methods that are called.
+    fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if let Some(def_id) =
+            self.typeck.and_then(|typeck| typeck.type_dependent_def_id(expr.hir_id))
+        {
+            self.keep(def_id);
+        }
+        intravisit::walk_expr(self, expr)
+    }
+}
@@ -5,8 +5,15 @@
 //! `DefId`s for functions and methods. For the definition of a path, see
 //! <https://doc.rust-lang.org/reference/paths.html>.
 //!
-//! TODO: Change `resolve_fn` in order to return information about trait implementations.
-//! <https://github.com/model-checking/kani/issues/1997>
+//! Qualified paths of trait items, like `<Type as Trait>::method`, are resolved to the trait item
+//! and the type, and `resolve_fn` returns the method of the implementation for that type. Paths
+//! of methods that a type gets from a trait, like `Type::method` or `<Type>::method`, are resolved
+//! to the inherent method if there is one, like rustc does, and otherwise to the method of the
+//! unique trait that the type implements and that declares it. Note that, unlike rustc, the trait
+//! doesn't need to be in scope.
+//!
+//! Paths are resolved from a module, or from an impl block, in which case `Self` refers to the
+//! self type of the impl. See [resolution_scope].
 //!
 //! Note that glob use statements can form loops. The paths can also walk through the loop.
 
@@ -16,10 +23,13 @@
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{CRATE_DEF_INDEX, DefId, LOCAL_CRATE, LocalDefId, LocalModDefId};
 use rustc_hir::{ItemKind, UseKind};
-use rustc_middle::ty::TyCtxt;
 use rustc_middle::ty::fast_reject::{self, TreatParams};
+use rustc_middle::ty::{self, AssocKind, ParamEnv, Ty as TyInternal, TyCtxt, TypingMode};
 use rustc_smir::rustc_internal;
+use rustc_span::{DUMMY_SP, Symbol};
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
 use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
 use stable_mir::ty::{FnDef, RigidTy, Ty, TyKind};
 use std::collections::HashSet;
 use std::fmt;
@@ -62,12 +72,31 @@ pub fn resolve_fn_path<'tcx>(
             let ty = type_resolution::resolve_ty(tcx, current_module, &syn_ty)?;
             let def_id = resolve_path(tcx, current_module, &path.path)?;
             validate_kind!(tcx, def_id, "function / method", DefKind::Fn | DefKind::AssocFn)?;
+            let Some(trait_id) = tcx.trait_of_item(def_id) else {
+                return Err(ResolveError::UnexpectedType {
+                    tcx,
+                    item: def_id,
+                    expected: "trait method",
+                });
+            };
+            if !implements_trait(tcx, trait_id, ty) {
+                return Err(ResolveError::MissingTraitImpl { tcx, trait_id, ty });
+            }
             Ok(FnResolution::FnImpl { def: stable_fn_def(tcx, def_id).unwrap(), ty })
         }
         // Qualified path for a primitive type, such as `<[u8]::sort>`.
         Some(QSelf { ty: syn_ty, .. }) if type_resolution::is_type_primitive(syn_ty) => {
             let ty = type_resolution::resolve_ty(tcx, current_module, &syn_ty)?;
-            let resolved = resolve_in_primitive(tcx, ty, path.path.segments.iter())?;
+            let resolved = match resolve_in_primitive(tcx, ty, path.path.segments.iter()) {
+                Err(ResolveError::MissingPrimitiveItem { base, unresolved })
+                    if path.path.segments.len() == 1 =>
+                {
+                    return resolve_in_trait_impls(tcx, base, &unresolved).map_err(|err| {
+                        err.unwrap_or(ResolveError::MissingPrimitiveItem { base, unresolved })
+                    });
+                }
+                resolved => resolved?,
+            };
             if resolved.segments.is_empty() {
                 Ok(FnResolution::Fn(stable_fn_def(tcx, resolved.base).unwrap()))
             } else {
@@ -77,22 +106,148 @@ pub fn resolve_fn_path<'tcx>(
         // Qualified path for a non-primitive type, such as `<Bar>::foo>`.
         Some(QSelf { ty: syn_ty, .. }) => {
             let ty = type_resolution::resolve_ty(tcx, current_module, &syn_ty)?;
-            let def_id = resolve_in_user_type(tcx, ty, path.path.segments.iter())?;
+            let def_id = match resolve_in_user_type(tcx, ty, path.path.segments.iter()) {
+                Err(err @ ResolveError::MissingItem { .. }) => {
+                    let name = path.path.segments.last().unwrap().ident.to_string();
+                    return resolve_in_trait_impls(tcx, ty, &name).map_err(|e| e.unwrap_or(err));
+                }
+                def_id => def_id?,
+            };
             validate_kind!(tcx, def_id, "function / method", DefKind::Fn | DefKind::AssocFn)?;
             Ok(FnResolution::Fn(stable_fn_def(tcx, def_id).unwrap()))
         }
         // Simple path
         None => {
-            let def_id = resolve_path(tcx, current_module, &path.path)?;
+            let def_id = match resolve_path(tcx, current_module, &path.path) {
+                // The method of a trait implemented by a type, such as `Bar::foo` or `u8::foo`.
+                Err(err) if is_missing_method(tcx, &err, &path.path) => {
+                    let ty = match &err {
+                        ResolveError::MissingItem { base, .. } => {
+                            rustc_internal::stable(tcx.type_of(*base).instantiate_identity())
+                        }
+                        ResolveError::MissingPrimitiveItem { base, .. } => *base,
+                        _ => unreachable!(),
+                    };
+                    let name = path.path.segments.last().unwrap().ident.to_string();
+                    return resolve_in_trait_impls(tcx, ty, &name).map_err(|e| e.unwrap_or(err));
+                }
+                def_id => def_id?,
+            };
             validate_kind!(tcx, def_id, "function / method", DefKind::Fn | DefKind::AssocFn)?;
             Ok(FnResolution::Fn(stable_fn_def(tcx, def_id).unwrap()))
         }
     }
 }
 
-/// Attempts to resolve a *simple path* (in the form of a string) to a function / method `DefId`.
+/// Whether the error is about the last segment of the path, which a type doesn't define as an
+/// inherent item. In that case, the item may come from a trait that the type implements.
+fn is_missing_method(tcx: TyCtxt, err: &ResolveError, path: &syn::Path) -> bool {
+    let last = path.segments.last().unwrap().ident.to_string();
+    match err {
+        ResolveError::MissingItem { base, unresolved, .. } => {
+            *unresolved == last
+                && matches!(tcx.def_kind(*base), DefKind::Struct | DefKind::Enum | DefKind::Union)
+        }
+        ResolveError::MissingPrimitiveItem { unresolved, .. } => {
+            *unresolved == last && path.segments.len() == 2
+        }
+        _ => false,
+    }
+}
+
+/// Resolve a method that a type gets from one of the traits it implements, since the type
+/// doesn't have an inherent method with that name.
+///
+/// Returns `Err(None)` if no trait implemented by the type declares such a method.
+fn resolve_in_trait_impls<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty,
+    name: &str,
+) -> Result<FnResolution, Option<ResolveError<'tcx>>> {
+    debug!(?name, ?ty, "resolve_in_trait_impls");
+    let symbol = Symbol::intern(name);
+    let candidates: Vec<DefId> = tcx
+        .all_traits()
+        .filter_map(|trait_id| {
+            tcx.associated_items(trait_id)
+                .filter_by_name_unhygienic(symbol)
+                .find(|item| item.kind == AssocKind::Fn)
+                .map(|item| item.def_id)
+        })
+        .filter(|item| implements_trait(tcx, tcx.trait_of_item(*item).unwrap(), ty))
+        .collect();
+    match candidates.as_slice() {
+        [] => Err(None),
+        [item] => Ok(FnResolution::FnImpl { def: stable_fn_def(tcx, *item).unwrap(), ty }),
+        _ => Err(Some(ResolveError::AmbiguousItem { tcx, ty, name: name.to_string(), candidates })),
+    }
+}
+
+/// Whether the type implements the trait, for some value of the other generic parameters of the
+/// trait and of the type.
+fn implements_trait(tcx: TyCtxt, trait_id: DefId, ty: Ty) -> bool {
+    let infcx = tcx.infer_ctxt().build(TypingMode::PostAnalysis);
+    let ty = match rustc_internal::internal(tcx, ty).kind() {
+        ty::Adt(def, _) => {
+            TyInternal::new_adt(tcx, *def, infcx.fresh_args_for_item(DUMMY_SP, def.did()))
+        }
+        _ => rustc_internal::internal(tcx, ty),
+    };
+    let args = ty::GenericArgs::for_item(tcx, trait_id, |param, _| {
+        if param.index == 0 { ty.into() } else { infcx.var_for_def(DUMMY_SP, param) }
+    });
+    infcx.type_implements_trait(trait_id, args, ParamEnv::reveal_all()).may_apply()
+}
+
+/// Resolve the item that implements the given trait item for the type.
 ///
-/// Use `[resolve_fn_path]` if you want to handle qualified paths and simple paths.
+/// This only finds implementations for the exact type, not blanket or generic implementations,
+/// and fails if the implementation uses the default definition of the trait item.
+pub fn resolve_impl_item<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_item: DefId,
+    ty: Ty,
+) -> Result<DefId, ResolveError<'tcx>> {
+    let self_ty = rustc_internal::internal(tcx, ty);
+    let trait_id = tcx.trait_of_item(trait_item).unwrap();
+    let impls: Vec<_> = tcx
+        .all_impls(trait_id)
+        .filter(|impl_id| tcx.impl_trait_ref(*impl_id).unwrap().skip_binder().self_ty() == self_ty)
+        .collect();
+    if impls.is_empty() && !implements_trait(tcx, trait_id, ty) {
+        return Err(ResolveError::MissingTraitImpl { tcx, trait_id, ty });
+    }
+    let candidates: Vec<_> = impls
+        .iter()
+        .filter_map(|impl_id| tcx.impl_item_implementor_ids(*impl_id).get(&trait_item).copied())
+        .collect();
+    match candidates.as_slice() {
+        [] => Err(ResolveError::MissingImplItem { tcx, item: trait_item, ty }),
+        [item] => Ok(*item),
+        _ => Err(ResolveError::AmbiguousItem {
+            tcx,
+            ty,
+            name: tcx.item_name(trait_item).to_string(),
+            candidates,
+        }),
+    }
+}
+
+/// The scope that the paths used by the given item are resolved from: the impl block that the
+/// item belongs to, so the paths can use `Self`, or otherwise the module of the item.
+pub fn resolution_scope(tcx: TyCtxt, item: LocalDefId) -> LocalDefId {
+    let parent = tcx.local_parent(item);
+    if matches!(tcx.def_kind(parent), DefKind::Impl { .. }) {
+        parent
+    } else {
+        tcx.parent_module_from_def_id(item).to_local_def_id()
+    }
+}
+
+/// Attempts to resolve a path (in the form of a string) to a function / method `DefId`.
+///
+/// For trait methods, this returns the method of the implementation for the given type. Use
+/// `[resolve_fn_path]` if you want the trait method instead.
 pub fn resolve_fn<'tcx>(
     tcx: TyCtxt<'tcx>,
     current_module: LocalDefId,
@@ -102,14 +257,55 @@ pub fn resolve_fn<'tcx>(
     let path = syn::parse_str(path_str).map_err(|err| ResolveError::InvalidPath {
         msg: format!("Expected a path, but found `{path_str}`. {err}"),
     })?;
-    let result = resolve_fn_path(tcx, current_module, &path)?;
-    if let FnResolution::Fn(def) = result {
-        Ok(rustc_internal::internal(tcx, def.def_id()))
-    } else {
-        Err(ResolveError::UnsupportedPath { kind: "qualified paths" })
+    match resolve_fn_path(tcx, current_module, &path)? {
+        FnResolution::Fn(def) => Ok(rustc_internal::internal(tcx, def.def_id())),
+        FnResolution::FnImpl { def, ty } => {
+            resolve_impl_item(tcx, rustc_internal::internal(tcx, def.def_id()), ty)
+        }
     }
 }
 
+/// Attempts to resolve a type (in the form of a string) from the context of the given module.
+pub fn resolve_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    current_module: LocalDefId,
+    ty_str: &str,
+) -> Result<Ty, ResolveError<'tcx>> {
+    let _span = debug_span!("resolve_ty", ?ty_str, ?current_module).entered();
+    let typ = syn::parse_str(ty_str).map_err(|err| ResolveError::InvalidPath {
+        msg: format!("Expected a type, but found `{ty_str}`. {err}"),
+    })?;
+    type_resolution::resolve_ty(tcx, current_module, &typ)
+}
+
+/// Resolve the concrete type behind the `impl Trait` return type of a function instance.
+///
+/// Return `None` if the declared return type of the function doesn't contain an `impl Trait`.
+/// Opaque types may also be nested, e.g.: `Box<impl Trait>`, and they can be declared in a trait
+/// method (RPITIT), in which case the declared type is a projection of the trait's hidden
+/// associated type.
+pub fn resolve_opaque_return(tcx: TyCtxt, instance: Instance) -> Option<Ty> {
+    let internal = rustc_internal::internal(tcx, instance);
+    let def_id = internal.def_id();
+    if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) {
+        return None;
+    }
+    let is_opaque = |ty: TyInternal| match ty.kind() {
+        ty::Alias(ty::Opaque, _) => true,
+        ty::Alias(ty::Projection, alias) => tcx.is_impl_trait_in_trait(alias.def_id),
+        _ => false,
+    };
+    let declared = tcx.fn_sig(def_id).instantiate_identity().output().skip_binder();
+    if !declared.walk().any(|arg| arg.as_type().is_some_and(is_opaque)) {
+        return None;
+    }
+    // Revealing the opaque types gives us the type inferred by the compiler for this instance.
+    let sig = tcx.fn_sig(def_id).instantiate(tcx, internal.args);
+    let concrete = tcx.normalize_erasing_late_bound_regions(ParamEnv::reveal_all(), sig).output();
+    debug!(?declared, ?concrete, "resolve_opaque_return");
+    Some(rustc_internal::stable(concrete))
+}
+
 /// Resolve the name of a function from the context of the definition provided.
 ///
 /// Ideally this should pass a more precise span, but we don't keep them around.
@@ -120,8 +316,8 @@ pub fn expect_resolve_fn<T: CrateDef>(
     reason: &str,
 ) -> Result<FnDef, ErrorGuaranteed> {
     let internal_def_id = rustc_internal::internal(tcx, res_cx.def_id());
-    let current_module = tcx.parent_module_from_def_id(internal_def_id.as_local().unwrap());
-    let maybe_resolved = resolve_fn(tcx, current_module.to_local_def_id(), name);
+    let current_module = resolution_scope(tcx, internal_def_id.as_local().unwrap());
+    let maybe_resolved = resolve_fn(tcx, current_module, name);
     let resolved = maybe_resolved.map_err(|err| {
         tcx.dcx().span_err(
             rustc_internal::internal(tcx, res_cx.span()),
@@ -170,6 +366,8 @@ fn resolve_path<'tcx>(
 pub enum ResolveError<'tcx> {
     /// Ambiguous glob resolution.
     AmbiguousGlob { tcx: TyCtxt<'tcx>, name: String, base: DefId, candidates: Vec<DefId> },
+    /// Ambiguous associated item, which is declared by multiple traits or implementations.
+    AmbiguousItem { tcx: TyCtxt<'tcx>, ty: Ty, name: String, candidates: Vec<DefId> },
     /// Use super past the root of a crate.
     ExtraSuper,
     /// Invalid path.
@@ -178,6 +376,10 @@ pub enum ResolveError<'tcx> {
     MissingItem { tcx: TyCtxt<'tcx>, base: DefId, unresolved: String },
     /// Unable to find an item in a primitive type.
     MissingPrimitiveItem { base: Ty, unresolved: String },
+    /// The type doesn't implement the trait of a qualified path.
+    MissingTraitImpl { tcx: TyCtxt<'tcx>, trait_id: DefId, ty: Ty },
+    /// The implementation of a trait for a type doesn't define the trait item.
+    MissingImplItem { tcx: TyCtxt<'tcx>, item: DefId, ty: Ty },
     /// Error triggered when the identifier points to an item with unexpected type.
     UnexpectedType { tcx: TyCtxt<'tcx>, item: DefId, expected: &'static str },
     /// Error triggered when the identifier is not currently supported.
@@ -208,6 +410,18 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                         .collect::<String>()
                 )
             }
+            ResolveError::AmbiguousItem { tcx, ty, name, candidates } => {
+                write!(
+                    f,
+                    "`{name}` is ambiguous for `{ty}`. Use a qualified path, like \
+                    `<{ty} as Trait>::{name}`, to select one of:\n{}",
+                    candidates
+                        .iter()
+                        .map(|def_id| tcx.def_path_str(*def_id))
+                        .intersperse("\n".to_string())
+                        .collect::<String>()
+                )
+            }
             ResolveError::InvalidPath { msg } => write!(f, "{msg}"),
             ResolveError::UnexpectedType { tcx, item: def_id, expected } => write!(
                 f,
@@ -222,6 +436,15 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             ResolveError::MissingPrimitiveItem { base, unresolved } => {
                 write!(f, "unable to find `{unresolved}` inside `{base}`")
             }
+            ResolveError::MissingTraitImpl { tcx, trait_id, ty } => {
+                write!(f, "`{ty}` doesn't implement trait `{}`", tcx.def_path_str(*trait_id))
+            }
+            ResolveError::MissingImplItem { tcx, item, ty } => write!(
+                f,
+                "unable to find an implementation of `{}` for `{ty}` that defines `{}`",
+                tcx.def_path_str(tcx.trait_of_item(*item).unwrap()),
+                tcx.item_name(*item)
+            ),
             ResolveError::UnsupportedPath { kind } => {
                 write!(f, "Kani currently cannot resolve {kind}")
             }
@@ -247,6 +470,8 @@ struct Path {
 const SELF: &str = "self";
 /// Identifier for the parent of the current module.
 const SUPER: &str = "super";
+/// Identifier for the implementing type inside of an `impl` or a trait.
+const SELF_TY: &str = "Self";
 
 /// Takes a string representation of a path and turns it into a `Path` data
 /// structure, resolving prefix qualifiers (like `crate`, `self`, etc.) along the way.
@@ -257,6 +482,14 @@ fn resolve_prefix<'tcx>(
 ) -> Result<Path, ResolveError<'tcx>> {
     debug!(?path, ?current_module, "resolve_prefix");
 
+    // Paths resolved from an impl block are relative to the module of the impl.
+    let (current_module, current_impl) =
+        if matches!(tcx.def_kind(current_module), DefKind::Impl { .. }) {
+            (tcx.parent_module_from_def_id(current_module).to_local_def_id(), Some(current_module))
+        } else {
+            (current_module, None)
+        };
+
     // Split the string into segments separated by `::`. Trim the whitespace
     // since path strings generated from macros sometimes add spaces around
     // `::`.
@@ -302,6 +535,28 @@ fn resolve_prefix<'tcx>(
         (None, Some(segment)) if segment.ident == SUPER => {
             resolve_super(tcx, current_module, path.segments.iter().peekable())
         }
+        // Path starting with "Self::", which refers to the self type of the impl.
+        (None, Some(segment)) if segment.ident == SELF_TY => {
+            let Some(current_impl) = current_impl else {
+                return Err(ResolveError::InvalidPath {
+                    msg: "`Self` can only be used inside of an `impl` or a trait. Use the name \
+                        of the type instead"
+                        .to_string(),
+                });
+            };
+            let self_ty = tcx.type_of(current_impl).instantiate_identity();
+            match self_ty.ty_adt_def() {
+                Some(adt_def) => {
+                    Ok(Path { base: adt_def.did(), segments: segments.cloned().collect() })
+                }
+                None => Err(ResolveError::InvalidPath {
+                    msg: format!(
+                        "`Self` is `{self_ty}`, which isn't a struct, an enum or a union. Use \
+                        the name of the type instead"
+                    ),
+                }),
+            }
+        }
         // Path starting with a primitive, such as "u8::"
         (None, Some(segment)) if type_resolution::is_primitive(&segment) => {
             let syn_ty = syn::parse2(segment.to_token_stream()).unwrap();
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! This module contains code used for resolve type / trait names
 
-use crate::kani_middle::resolve::{ResolveError, resolve_path, validate_kind};
+use crate::kani_middle::resolve::{
+    ResolveError, is_item_name, resolve_impl_item, resolve_path, validate_kind,
+};
 use quote::ToTokens;
 use rustc_hir::def::DefKind;
 use rustc_middle::ty::TyCtxt;
@@ -31,8 +33,33 @@ pub fn resolve_ty<'tcx>(
     };
     #[warn(non_exhaustive_omitted_patterns)]
     match typ {
-        Type::Path(TypePath { qself, path }) => {
-            assert_eq!(*qself, None, "Unexpected qualified path");
+        // Associated type of a trait implementation, such as `<Foo as Iterator>::Item`.
+        Type::Path(TypePath { qself: Some(qself), path }) if qself.position > 0 => {
+            let self_ty = resolve_ty(tcx, current_module, &qself.ty)?;
+            let segments: Vec<_> = path.segments.iter().cloned().collect();
+            let (trait_segments, item_segments) = segments.split_at(qself.position);
+            let [item_segment] = item_segments else {
+                return invalid("path with more than one associated item");
+            };
+            let trait_path = syn::Path {
+                leading_colon: path.leading_colon,
+                segments: trait_segments.iter().cloned().collect(),
+            };
+            let trait_id = resolve_path(tcx, current_module, &trait_path)?;
+            let trait_id = validate_kind!(tcx, trait_id, "trait", DefKind::Trait)?;
+            let name = item_segment.ident.to_string();
+            let Some(item) = tcx.associated_item_def_ids(trait_id).iter().copied().find(|item| {
+                tcx.def_kind(*item) == DefKind::AssocTy && is_item_name(tcx, *item, &name)
+            }) else {
+                return Err(ResolveError::MissingItem { tcx, base: trait_id, unresolved: name });
+            };
+            let impl_item = resolve_impl_item(tcx, item, self_ty)?;
+            Ok(rustc_internal::stable(tcx.type_of(impl_item).instantiate_identity()))
+        }
+        Type::Path(TypePath { qself: Some(_), .. }) => {
+            unsupported("associated types without a trait")
+        }
+        Type::Path(TypePath { qself: None, path }) => {
             if let Some(primitive) =
                 path.get_ident().and_then(|ident| PrimitiveIdent::from_str(&ident.to_string()).ok())
             {
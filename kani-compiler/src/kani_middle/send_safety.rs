@@ -0,0 +1,134 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Structural check used by `#[kani::proof_send_safe(Type)]` harnesses.
+//!
+//! The check looks for state that can be reached from a value of the given type and that could
+//! be mutated concurrently by two different threads once the value is sent to another thread.
+//! For example, a `Rc` buried inside a type with a manual `unsafe impl Send`.
+//!
+//! This analysis is a heuristic and it has the following limitations:
+//!   - Types that implement `Send` without a manual implementation are trusted, and so are the
+//!     manual implementations of types other than the one under analysis (e.g.: `Arc`).
+//!   - Raw pointers are assumed to be uniquely owned by the type unless they point to data with
+//!     interior mutability that is not `Sync`.
+//!   - Trait objects, closures and function pointers that are not `Send` are reported without
+//!     further analysis, since their concrete types are unknown.
+//!   - Generic types are not supported.
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{self, ParamEnv, Ty, TyCtxt, TypeVisitableExt, TypingMode};
+use rustc_span::{Span, sym};
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
+use std::collections::HashSet;
+
+/// Check that the type `ty` doesn't expose thread-unsafe interior mutability through any of its
+/// fields.
+///
+/// Every finding is reported as an error on the offending field.
+pub fn check_send_safe<'tcx>(tcx: TyCtxt<'tcx>, harness: DefId, ty: Ty<'tcx>, span: Span) {
+    let ty::Adt(adt_def, args) = ty.kind() else {
+        tcx.dcx().span_err(
+            span,
+            format!(
+                "expected a struct, enum or union to check for `Send` safety, but found `{ty}`"
+            ),
+        );
+        return;
+    };
+    if ty.has_param() {
+        tcx.dcx().span_err(
+            span,
+            format!(
+                "`Send` safety can only be checked for non-generic types, but `{ty}` is generic"
+            ),
+        );
+        return;
+    }
+
+    let mut checker = SendSafety::new(tcx);
+    if !checker.implements(ty, checker.send) {
+        tcx.dcx().span_err(span, format!("type `{ty}` does not implement `Send`"));
+        return;
+    }
+    for field in adt_def.all_fields() {
+        let field_ty = field.ty(tcx, args);
+        if let Some(reason) = checker.find_issue(field_ty) {
+            let field_span = tcx.def_span(field.did);
+            tcx.dcx()
+                .struct_span_err(
+                    field_span,
+                    format!(
+                        "field `{}` of `{ty}` may expose thread-unsafe interior mutability",
+                        field.name
+                    ),
+                )
+                .with_note(reason)
+                .with_span_note(
+                    tcx.def_span(harness),
+                    format!("found while checking that `{ty}` is safe to send to another thread"),
+                )
+                .emit();
+        }
+    }
+}
+
+struct SendSafety<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    send: DefId,
+    sync: DefId,
+    /// Types that have already been analyzed.
+    visited: HashSet<Ty<'tcx>>,
+}
+
+impl<'tcx> SendSafety<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>) -> Self {
+        SendSafety {
+            tcx,
+            send: tcx.get_diagnostic_item(sym::Send).unwrap(),
+            sync: tcx.get_diagnostic_item(sym::Sync).unwrap(),
+            visited: HashSet::new(),
+        }
+    }
+
+    fn implements(&self, ty: Ty<'tcx>, trait_def: DefId) -> bool {
+        let infcx = self.tcx.infer_ctxt().build(TypingMode::PostAnalysis);
+        infcx
+            .type_implements_trait(trait_def, [ty], ParamEnv::reveal_all())
+            .must_apply_modulo_regions()
+    }
+
+    /// Whether `ty` contains an `UnsafeCell` that is not behind a pointer, and it is not `Sync`.
+    fn has_unsync_interior_mut(&self, ty: Ty<'tcx>) -> bool {
+        !ty.is_freeze(self.tcx, ParamEnv::reveal_all()) && !self.implements(ty, self.sync)
+    }
+
+    /// Return why sending a value of type `ty` to another thread may not be safe, if any.
+    fn find_issue(&mut self, ty: Ty<'tcx>) -> Option<String> {
+        if !self.visited.insert(ty) || self.implements(ty, self.send) {
+            return None;
+        }
+        match ty.kind() {
+            ty::Ref(_, pointee, ty::Mutability::Not) | ty::RawPtr(pointee, _) => {
+                if self.has_unsync_interior_mut(*pointee) {
+                    Some(format!(
+                        "`{ty}` points to data of type `{pointee}`, which can be mutated through \
+                        a shared reference and is not `Sync`"
+                    ))
+                } else {
+                    self.find_issue(*pointee)
+                }
+            }
+            ty::Ref(_, pointee, ty::Mutability::Mut) => self.find_issue(*pointee),
+            ty::Adt(adt_def, args) => adt_def
+                .all_fields()
+                .find_map(|field| self.find_issue(field.ty(self.tcx, args)))
+                .map(|reason| format!("`{ty}` is not `Send`: {reason}")),
+            ty::Array(elem, _) | ty::Slice(elem) => self.find_issue(*elem),
+            ty::Tuple(elems) => elems.iter().find_map(|elem| self.find_issue(elem)),
+            ty::Dynamic(..) | ty::Closure(..) | ty::FnPtr(..) => {
+                Some(format!("`{ty}` is not `Send` and its contents cannot be analyzed"))
+            }
+            _ => None,
+        }
+    }
+}
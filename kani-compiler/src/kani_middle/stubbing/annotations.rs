@@ -7,17 +7,21 @@
 use kani_metadata::Stub;
 use rustc_hir::def_id::{DefId, LocalDefId};
 use rustc_middle::ty::TyCtxt;
+use stable_mir::ty::Ty;
 
-use crate::kani_middle::resolve::resolve_fn;
+use crate::kani_middle::resolve::{resolution_scope, resolve_fn, resolve_ty};
 
 /// Given a `kani::stub` attribute, tries to extract a pair of paths (the
 /// original function/method, and its stub). Returns `None` and errors if the
 /// attribute's arguments are not two paths.
-fn stub_def_ids(tcx: TyCtxt, harness: LocalDefId, stub: &Stub) -> Option<(DefId, DefId)> {
+///
+/// The paths are resolved from the module or the impl block of `source`, which
+/// is the harness or the stub set that declared the stub.
+fn stub_def_ids(tcx: TyCtxt, source: LocalDefId, stub: &Stub) -> Option<(DefId, DefId)> {
     // Resolve the attribute arguments to `DefId`s
-    let current_module = tcx.parent_module_from_def_id(harness);
+    let current_module = resolution_scope(tcx, source);
     let resolve = |name: &str| -> Option<DefId> {
-        let maybe_resolved = resolve_fn(tcx, current_module.to_local_def_id(), name);
+        let maybe_resolved = resolve_fn(tcx, current_module, name);
         match maybe_resolved {
             Ok(def_id) => {
                 tracing::debug!(?def_id, "Resolved {name} to {}", tcx.def_path_str(def_id));
@@ -25,7 +29,7 @@ fn stub_def_ids(tcx: TyCtxt, harness: LocalDefId, stub: &Stub) -> Option<(DefId,
             }
             Err(err) => {
                 tcx.dcx()
-                    .span_err(tcx.def_span(harness), format!("failed to resolve `{name}`: {err}"));
+                    .span_err(tcx.def_span(source), format!("failed to resolve `{name}`: {err}"));
                 None
             }
         }
@@ -36,18 +40,21 @@ fn stub_def_ids(tcx: TyCtxt, harness: LocalDefId, stub: &Stub) -> Option<(DefId,
 }
 
 /// Updates the running map `stub_pairs` that maps a function/method to its
-/// stub. Errors if a function/method is mapped more than once.
+/// stub and to the item that declared the stub, i.e., the harness or one of its
+/// stub sets. The paths of the stub are resolved from the module of that item.
+/// Errors if a function/method is mapped more than once.
 pub fn update_stub_mapping(
     tcx: TyCtxt,
     harness: LocalDefId,
+    source: LocalDefId,
     stub: &Stub,
-    stub_pairs: &mut HashMap<DefId, DefId>,
+    stub_pairs: &mut HashMap<DefId, (DefId, LocalDefId)>,
 ) {
-    if let Some((orig_id, stub_id)) = stub_def_ids(tcx, harness, stub) {
-        let other_opt = stub_pairs.insert(orig_id, stub_id);
-        if let Some(other) = other_opt {
+    if let Some((orig_id, stub_id)) = stub_def_ids(tcx, source, stub) {
+        let other_opt = stub_pairs.insert(orig_id, (stub_id, source));
+        if let Some((other, other_source)) = other_opt {
             if other != stub_id {
-                tcx.dcx().span_err(
+                let mut diag = tcx.dcx().struct_span_err(
                     tcx.def_span(harness),
                     format!(
                         "duplicate stub mapping: {} mapped to {} and {}",
@@ -56,7 +63,55 @@ pub fn update_stub_mapping(
                         tcx.def_path_str(other)
                     ),
                 );
+                // Point to the stub sets that declared the conflicting stubs.
+                for (stub_id, source) in [(other, other_source), (stub_id, source)] {
+                    if source != harness {
+                        diag.span_note(
+                            tcx.def_span(source),
+                            format!(
+                                "stub set `{}` maps it to {}",
+                                tcx.item_name(source.to_def_id()),
+                                tcx.def_path_str(stub_id)
+                            ),
+                        );
+                    }
+                }
+                diag.emit();
             }
         }
     }
 }
+
+/// Updates the running map `drop_stubs` that maps a type to the function that replaces its drop
+/// glue. Errors if a type is mapped more than once.
+pub fn update_drop_stub_mapping(
+    tcx: TyCtxt,
+    harness: LocalDefId,
+    stub: &Stub,
+    drop_stubs: &mut HashMap<Ty, DefId>,
+) {
+    let current_module = resolution_scope(tcx, harness);
+    let ty = resolve_ty(tcx, current_module, &stub.original);
+    let replacement = resolve_fn(tcx, current_module, &stub.replacement);
+    let (ty, stub_id) = match (ty, replacement) {
+        (Ok(ty), Ok(stub_id)) => (ty, stub_id),
+        (Err(err), _) | (_, Err(err)) => {
+            // This error should have been reported when the attribute was parsed.
+            tcx.dcx()
+                .span_err(tcx.def_span(harness), format!("failed to resolve drop stub: {err}"));
+            return;
+        }
+    };
+    if let Some(other) = drop_stubs.insert(ty, stub_id) {
+        if other != stub_id {
+            tcx.dcx().span_err(
+                tcx.def_span(harness),
+                format!(
+                    "duplicate drop stub: drop glue of `{ty}` mapped to {} and {}",
+                    tcx.def_path_str(stub_id),
+                    tcx.def_path_str(other)
+                ),
+            );
+        }
+    }
+}
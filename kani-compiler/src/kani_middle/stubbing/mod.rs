@@ -5,22 +5,27 @@
 mod annotations;
 
 use itertools::Itertools;
-use rustc_span::DUMMY_SP;
+use rustc_span::{DUMMY_SP, Symbol};
 use std::collections::HashMap;
-use tracing::{debug, trace};
+use tracing::{debug, debug_span, trace};
 
 use kani_metadata::HarnessMetadata;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{CRATE_DEF_ID, DefId};
 use rustc_middle::mir::Const;
-use rustc_middle::ty::{self, EarlyBinder, ParamEnv, TyCtxt, TypeFoldable};
+use rustc_middle::ty::{
+    self, EarlyBinder, ParamEnv, TyCtxt, TypeFoldable, TypeVisitableExt, TypingMode,
+};
 use rustc_smir::rustc_internal;
-use stable_mir::mir::ConstOperand;
+use rustc_trait_selection::infer::{InferCtxtExt, TyCtxtInferExt};
 use stable_mir::mir::mono::Instance;
 use stable_mir::mir::visit::{Location, MirVisitor};
-use stable_mir::ty::{FnDef, RigidTy, TyKind};
+use stable_mir::mir::{ConstOperand, Mutability};
+use stable_mir::ty::{FnDef, RigidTy, Ty, TyKind};
 use stable_mir::{CrateDef, CrateItem};
 
-use self::annotations::update_stub_mapping;
+use self::annotations::{update_drop_stub_mapping, update_stub_mapping};
+use crate::kani_middle::attributes::{KaniAttributes, resolve_stub_set};
+use crate::kani_middle::resolve::{FnResolution, resolve_fn_path, resolve_impl_item};
 
 /// Collects the stubs from the harnesses in a crate.
 pub fn harness_stub_map(
@@ -28,20 +33,133 @@ pub fn harness_stub_map(
     harness: Instance,
     metadata: &HarnessMetadata,
 ) -> HashMap<DefId, DefId> {
+    let _guard = debug_span!("harness_stub_map", harness = harness.name()).entered();
     let def_id = rustc_internal::internal(tcx, harness.def.def_id());
     let attrs = &metadata.attributes;
+    let local_id = def_id.expect_local();
     let mut stub_pairs = HashMap::default();
     for stubs in &attrs.stubs {
-        update_stub_mapping(tcx, def_id.expect_local(), stubs, &mut stub_pairs);
+        update_stub_mapping(tcx, local_id, local_id, stubs, &mut stub_pairs);
+    }
+    for set in &attrs.stub_sets {
+        // Any error was reported when the attributes of the harness were checked.
+        let Ok(set_id) = resolve_stub_set(tcx, def_id, set) else { continue };
+        for stub in KaniAttributes::for_item(tcx, set_id.to_def_id()).stub_set_entries() {
+            update_stub_mapping(tcx, local_id, set_id, &stub, &mut stub_pairs);
+        }
+    }
+    stub_pairs.into_iter().map(|(orig, (stub, _))| (orig, stub)).collect()
+}
+
+/// Collects the drop stubs of a harness, i.e., the types whose drop glue should be replaced and
+/// their replacement function.
+pub fn harness_drop_stub_map(
+    tcx: TyCtxt,
+    harness: Instance,
+    metadata: &HarnessMetadata,
+) -> HashMap<Ty, DefId> {
+    let def_id = rustc_internal::internal(tcx, harness.def.def_id());
+    let mut drop_stubs = HashMap::default();
+    for stub in &metadata.attributes.drop_stubs {
+        update_drop_stub_mapping(tcx, def_id.expect_local(), stub, &mut drop_stubs);
+    }
+    drop_stubs
+}
+
+/// The `std::fs` entry points replaced by the file system model from `kani::fs` and their model.
+const FS_MODEL_STUBS: &[(&str, &str)] = &[
+    ("std::fs::File::open", "kani::fs::open"),
+    ("std::fs::File::create", "kani::fs::create"),
+    ("std::fs::File::metadata", "kani::fs::file_metadata"),
+    ("<std::fs::File as std::io::Read>::read", "kani::fs::file_read"),
+    ("<std::fs::File as std::io::Read>::read_to_end", "kani::fs::file_read_to_end"),
+    ("<std::fs::File as std::io::Read>::read_to_string", "kani::fs::file_read_to_string"),
+    ("<std::fs::File as std::io::Write>::write", "kani::fs::file_write"),
+    ("<std::fs::File as std::io::Write>::flush", "kani::fs::file_flush"),
+    ("std::fs::read", "kani::fs::read"),
+    ("std::fs::read_to_string", "kani::fs::read_to_string"),
+    ("std::fs::write", "kani::fs::write"),
+    ("std::fs::metadata", "kani::fs::metadata"),
+    ("std::fs::Metadata::len", "kani::fs::metadata_len"),
+    ("std::fs::Metadata::is_file", "kani::fs::metadata_is_file"),
+    ("std::fs::Metadata::is_dir", "kani::fs::metadata_is_dir"),
+];
+
+/// Collects the stubs that replace the `std::fs` entry points by the file system model, which
+/// are used by every harness when `--model-fs` is enabled.
+///
+/// Unlike user stubs, the model also replaces the `Read` and `Write` implementations of `File`.
+pub fn fs_model_stubs(tcx: TyCtxt) -> HashMap<DefId, DefId> {
+    let _guard = debug_span!("fs_model_stubs").entered();
+    let mut stub_pairs = HashMap::default();
+    for (original, replacement) in FS_MODEL_STUBS {
+        let resolved = resolve_root_fn(tcx, original)
+            .and_then(|orig_id| Ok((orig_id, resolve_root_fn(tcx, replacement)?)));
+        match resolved {
+            Ok((orig_id, stub_id)) => {
+                stub_pairs.insert(orig_id, stub_id);
+            }
+            Err(err) => {
+                tcx.dcx().err(format!(
+                    "failed to resolve `{original}` for the file system model: {err}"
+                ));
+            }
+        }
     }
     stub_pairs
 }
 
+/// Resolve a function path from the crate root. For qualified paths of trait methods, return the
+/// method of the implementation for the given type.
+pub fn resolve_root_fn(tcx: TyCtxt, path_str: &str) -> Result<DefId, String> {
+    let path = syn::parse_str(path_str).map_err(|err| err.to_string())?;
+    let resolved = resolve_fn_path(tcx, CRATE_DEF_ID, &path).map_err(|err| err.to_string())?;
+    match resolved {
+        FnResolution::Fn(def) => Ok(rustc_internal::internal(tcx, def.def_id())),
+        FnResolution::FnImpl { def, ty } => {
+            let trait_fn = rustc_internal::internal(tcx, def.def_id());
+            resolve_impl_item(tcx, trait_fn, ty).map_err(|err| err.to_string())
+        }
+    }
+}
+
+/// Checks whether `stub` can replace the drop glue of `ty`: the type must be concrete and have
+/// drop glue, and the stub must be a non-generic function that takes `&mut ty` and returns `()`.
+pub fn check_drop_stub_compatibility(tcx: TyCtxt, ty: Ty, stub: FnDef) -> Result<(), String> {
+    if rustc_internal::internal(tcx, ty).has_param() {
+        return Err(format!("Cannot stub the drop glue of `{ty}`, since it is a generic type"));
+    }
+    if Instance::resolve_drop_in_place(ty).is_empty_shim() {
+        return Err(format!(
+            "Cannot stub the drop glue of `{ty}`, since dropping it doesn't run any code"
+        ));
+    }
+    let stub_def_id = rustc_internal::internal(tcx, stub.def_id());
+    if tcx.generics_of(stub_def_id).count() > 0 {
+        return Err(format!(
+            "Cannot stub the drop glue of `{ty}` by `{}`, since the stub is generic",
+            stub.name()
+        ));
+    }
+    let sig = stub.fn_sig().skip_binder();
+    let expected = format!("Expected `{}` to have signature `fn(&mut {ty})`", stub.name());
+    match sig.inputs() {
+        [arg] if sig.output().kind().is_unit() => match arg.kind() {
+            TyKind::RigidTy(RigidTy::Ref(_, pointee, Mutability::Mut)) if pointee == ty => Ok(()),
+            _ => Err(format!("{expected}, but found parameter of type `{arg}`")),
+        },
+        [_] => Err(format!("{expected}, but found return type `{}`", sig.output())),
+        args => Err(format!("{expected}, but it takes {} argument(s)", args.len())),
+    }
+}
+
 /// Checks whether the stub is compatible with the original function/method: do
 /// the arities and types (of the parameters and return values) match up? This
 /// does **NOT** check whether the type variables are constrained to implement
 /// the same traits; trait mismatches are checked during monomorphization.
 pub fn check_compatibility(tcx: TyCtxt, old_def: FnDef, new_def: FnDef) -> Result<(), String> {
+    let _guard =
+        debug_span!("check_compatibility", old = old_def.name(), new = new_def.name()).entered();
     // TODO: Validate stubs that do not have body.
     // We could potentially look at the function signature to see if they match.
     // However, they will include region information which can make types different.
@@ -118,6 +236,56 @@ pub fn check_compatibility(tcx: TyCtxt, old_def: FnDef, new_def: FnDef) -> Resul
     }
 }
 
+/// Validate that the `kani::Arbitrary` bounds of a stub instance are met.
+///
+/// A stub may create nondeterministic values of a generic type, e.g.: `kani::any::<T>()`, which
+/// requires `T: kani::Arbitrary`. Since we delay checking the generic bounds of a stub, an unmet
+/// bound would otherwise only surface later as a failure to resolve `T::any()`.
+pub fn validate_stub_arbitrary(tcx: TyCtxt, old_def: FnDef, instance: Instance) -> bool {
+    let Some(arbitrary) = tcx.get_diagnostic_item(Symbol::intern("KaniArbitrary")) else {
+        return true;
+    };
+    let internal_instance = rustc_internal::internal(tcx, instance);
+    let predicates =
+        tcx.predicates_of(internal_instance.def_id()).instantiate(tcx, internal_instance.args);
+    let infcx = tcx.infer_ctxt().build(TypingMode::PostAnalysis);
+    let mut is_valid = true;
+    for (clause, span) in predicates {
+        let Some(predicate) = clause.as_trait_clause() else { continue };
+        let trait_ref = tcx.instantiate_bound_regions_with_erased(predicate).trait_ref;
+        if trait_ref.def_id != arbitrary
+            || infcx
+                .type_implements_trait(arbitrary, trait_ref.args, ParamEnv::reveal_all())
+                .must_apply_modulo_regions()
+        {
+            continue;
+        }
+        is_valid = false;
+        let ty = trait_ref.self_ty();
+        let help = if ty.ty_adt_def().is_some_and(|adt_def| adt_def.did().is_local()) {
+            format!(
+                "consider adding `#[derive(kani::Arbitrary)]` to `{ty}` or implementing \
+                `kani::Arbitrary` for it"
+            )
+        } else {
+            format!("consider using a stub that doesn't create nondeterministic values of `{ty}`")
+        };
+        tcx.dcx()
+            .struct_span_err(
+                span,
+                format!(
+                    "`{ty}` doesn't implement `kani::Arbitrary`. The function `{}` cannot be \
+                    stubbed by `{}`, since it requires nondeterministic values of `{ty}`",
+                    old_def.name(),
+                    instance.def.name(),
+                ),
+            )
+            .with_help(help)
+            .emit();
+    }
+    is_valid
+}
+
 /// Validate that an instance body can be instantiated.
 ///
 /// Stubbing may cause an instance to not be correctly instantiated since we delay checking its
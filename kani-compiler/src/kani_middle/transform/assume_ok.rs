@@ -0,0 +1,161 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement `--assume-ok`, which only verifies the paths where no error occurs.
+//!
+//! For every call in the local crate that returns a `Result`, this pass assumes that the call
+//! returns `Ok`, which prunes the error paths of the caller. I.e.:
+//!
+//! ```ignore
+//! bb0: _2 = parse(move _3) -> bb1;
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! bb0: _2 = parse(move _3) -> bb2;
+//! bb2: _4 = &_2;
+//!      _5 = kani::internal::assume_ok::<u8, ParseError>(move _4) -> bb1;
+//! ```
+//!
+//! The `?` operator propagates an error with a call to `FromResidual::from_residual`, which returns
+//! a `Result` that is never `Ok`. The assumption after this call prunes the propagation, so errors
+//! that don't come from a call are not propagated either. These calls are not reported, since they
+//! only propagate errors. Calls to the Kani library are skipped, so e.g.
+//! `kani::any::<Result<T, E>>()` may still return an error.
+//!
+//! This is an under-approximation, so the functions whose calls are assumed to return `Ok` are
+//! recorded in the metadata of each harness, and reported with its verification result.
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use itertools::Itertools;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_span::symbol::sym;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::{Instance, MonoItem};
+use stable_mir::mir::{
+    BasicBlockIdx, Body, BorrowKind, Mutability, Operand, Place, Rvalue, TerminatorKind,
+};
+use stable_mir::ty::{FnDef, GenericArgs, Region, RegionKind, RigidTy, Ty, TyKind};
+use tracing::trace;
+
+/// Assume that the calls of the local crate that return a `Result` return `Ok`.
+#[derive(Debug)]
+pub struct AssumeOkPass {
+    /// The function that assumes that a result is `Ok`, if the `kani` crate is available.
+    assume_ok: Option<FnDef>,
+}
+
+impl TransformPass for AssumeOkPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().assume_ok && self.assume_ok.is_some()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "AssumeOkPass::transform");
+        if !instance.def.krate().is_local {
+            return (false, body);
+        }
+        let calls = result_calls(tcx, &body);
+        if calls.is_empty() {
+            return (false, body);
+        }
+        let mut new_body = MutableBody::from(body);
+        for call in calls {
+            // Inserting after a terminator keeps the index of its block, so the remaining
+            // indices are still valid.
+            let mut source = SourceInstruction::Terminator { bb: call.bb };
+            let borrow = Rvalue::Ref(
+                Region { kind: RegionKind::ReErased },
+                BorrowKind::Shared,
+                call.destination,
+            );
+            let result = new_body.insert_assignment(borrow, &mut source, InsertPosition::After);
+            let assume_ok = Instance::resolve(self.assume_ok.unwrap(), &call.args).unwrap();
+            let span = source.span(new_body.blocks());
+            let unit = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            new_body.insert_call(
+                &assume_ok,
+                &mut source,
+                InsertPosition::After,
+                vec![Operand::Move(Place::from(result))],
+                Place::from(unit),
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+impl AssumeOkPass {
+    pub fn new(tcx: TyCtxt) -> AssumeOkPass {
+        AssumeOkPass { assume_ok: find_fn_def(tcx, "KaniAssumeOk") }
+    }
+}
+
+/// A call that returns a `Result`.
+struct ResultCall {
+    /// The block whose terminator is the call.
+    bb: BasicBlockIdx,
+    /// The function that is called, unless it's called through a function pointer.
+    callee: Option<FnDef>,
+    /// The place where the result is stored.
+    destination: Place,
+    /// The generic arguments of the `Result`.
+    args: GenericArgs,
+}
+
+/// Collect the calls of the given body that return a `Result`, except for the calls to the Kani
+/// library and the calls that never return.
+fn result_calls(tcx: TyCtxt, body: &Body) -> Vec<ResultCall> {
+    body.blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(bb, block)| {
+            let TerminatorKind::Call { func, destination, target: Some(_), .. } =
+                &block.terminator.kind
+            else {
+                return None;
+            };
+            let callee = func.ty(body.locals()).ok()?.kind().fn_def().map(|(def, _)| def);
+            if callee.is_some_and(|def| def.krate().name == "kani") {
+                return None;
+            }
+            let ty = destination.ty(body.locals()).ok()?;
+            let TyKind::RigidTy(RigidTy::Adt(def, args)) = ty.kind() else { return None };
+            tcx.is_diagnostic_item(sym::Result, rustc_internal::internal(tcx, def.def_id()))
+                .then(|| ResultCall { bb, callee, destination: destination.clone(), args })
+        })
+        .collect()
+}
+
+/// The functions whose calls are assumed to return `Ok` in the local functions among the given
+/// items, except for the functions that propagate errors with the `?` operator.
+pub fn assumed_ok_fns(tcx: TyCtxt, items: &[MonoItem]) -> Vec<String> {
+    let from_residual = tcx.lang_items().from_residual_fn();
+    items
+        .iter()
+        .filter_map(|item| match item {
+            MonoItem::Fn(instance) if instance.def.krate().is_local => instance.body(),
+            _ => None,
+        })
+        .flat_map(|body| result_calls(tcx, &body))
+        .filter_map(|call| call.callee)
+        .filter(|callee| Some(rustc_internal::internal(tcx, callee.def_id())) != from_residual)
+        .map(|callee| callee.name())
+        .sorted()
+        .dedup()
+        .collect()
+}
@@ -0,0 +1,243 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement the snapshots used by `kani::assume_unchanged!()`.
+//!
+//! The macro expands to `kani::internal::assume_unchanged(&place, &place)`, which assumes that
+//! both of its arguments point to the same bytes. This pass finds the call to a function replaced
+//! by its contract that precedes each use of the macro, copies the place right before the call,
+//! and redirects the second reference to the copy. I.e.:
+//!
+//! ```ignore
+//! bb0: _3 = &mut _1;
+//!      _2 = stubbed(move _3) -> bb1;
+//! bb1: _5 = &(_1.1);
+//!      _6 = &(_1.1);
+//!      _4 = assume_unchanged(move _5, move _6) -> bb2;
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! bb0: _3 = &mut _1;
+//!      _7 = copy (_1.1);
+//!      _2 = stubbed(move _3) -> bb1;
+//! bb1: _5 = &(_1.1);
+//!      _6 = &_7;
+//!      _4 = assume_unchanged(move _5, move _6) -> bb2;
+//! ```
+//!
+//! The preceding call is the last call along the unique path of blocks that leads to the macro.
+//! If there's no such call, or if it's not replaced by a contract, the macro has no effect and we
+//! emit a warning.
+use crate::kani_middle::codegen_units::CodegenUnit;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::contracts::replaced_fns;
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def_id::DefId as InternalDefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    BasicBlockIdx, Body, Local, Operand, Place, Rvalue, Statement, StatementKind, TerminatorKind,
+};
+use stable_mir::ty::{FnDef, Span};
+use std::collections::HashSet;
+use tracing::trace;
+
+/// Take the snapshots compared by `kani::assume_unchanged!()`.
+#[derive(Debug)]
+pub struct AssumeUnchangedPass {
+    /// The function the macro expands to, if the `kani` crate is available.
+    assume_unchanged: Option<FnDef>,
+    /// Functions that are stubbed by their contract in this harness.
+    replace_fns: HashSet<InternalDefId>,
+}
+
+impl TransformPass for AssumeUnchangedPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        self.assume_unchanged.is_some()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "AssumeUnchangedPass::transform");
+        let uses = self.collect_uses(&body);
+        if uses.is_empty() {
+            return (false, body);
+        }
+        let predecessors = predecessors(&body);
+        let mut new_body = MutableBody::from(body);
+        let mut changed = false;
+        for use_ in uses {
+            match self.find_stubbed_call(tcx, &new_body, &predecessors, &use_) {
+                Ok(call_bb) => {
+                    // Copy the location right before the call, and point the snapshot argument
+                    // to the copy.
+                    let mut source = SourceInstruction::Terminator { bb: call_bb };
+                    let snapshot = new_body.insert_assignment(
+                        Rvalue::Use(Operand::Copy(use_.place.clone())),
+                        &mut source,
+                        InsertPosition::Before,
+                    );
+                    let block = &new_body.blocks()[use_.bb];
+                    let mut statements = block.statements.clone();
+                    let StatementKind::Assign(dest, Rvalue::Ref(region, kind, _)) =
+                        &statements[use_.stmt].kind
+                    else {
+                        unreachable!("expected the reference to the snapshot")
+                    };
+                    let rvalue = Rvalue::Ref(region.clone(), kind.clone(), Place::from(snapshot));
+                    statements[use_.stmt] = Statement {
+                        kind: StatementKind::Assign(dest.clone(), rvalue),
+                        span: statements[use_.stmt].span,
+                    };
+                    new_body.replace_statements(
+                        &SourceInstruction::Terminator { bb: use_.bb },
+                        statements,
+                    );
+                    changed = true;
+                }
+                Err(reason) => {
+                    tcx.dcx().span_warn(
+                        rustc_internal::internal(tcx, use_.span),
+                        format!("`kani::assume_unchanged!` has no effect: {reason}"),
+                    );
+                }
+            }
+        }
+        (changed, new_body.into())
+    }
+}
+
+/// A use of the `kani::assume_unchanged!` macro.
+#[derive(Debug)]
+struct AssumeUnchanged {
+    /// The block whose terminator is the call to `kani::internal::assume_unchanged`.
+    bb: BasicBlockIdx,
+    /// The statement of the block that takes the reference to the snapshot.
+    stmt: usize,
+    /// The location that is assumed to be unchanged.
+    place: Place,
+    /// The span of the macro.
+    span: Span,
+}
+
+impl AssumeUnchangedPass {
+    pub fn new(tcx: TyCtxt, unit: &CodegenUnit) -> AssumeUnchangedPass {
+        AssumeUnchangedPass {
+            assume_unchanged: find_fn_def(tcx, "KaniAssumeUnchanged"),
+            replace_fns: replaced_fns(tcx, unit),
+        }
+    }
+
+    /// Collect the calls to `kani::internal::assume_unchanged` whose snapshot argument is a
+    /// reference taken in the same block.
+    fn collect_uses(&self, body: &Body) -> Vec<AssumeUnchanged> {
+        let assume_unchanged = self.assume_unchanged.unwrap();
+        body.blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(bb, block)| {
+                let TerminatorKind::Call { func, args, .. } = &block.terminator.kind else {
+                    return None;
+                };
+                let (callee, _) = func.ty(body.locals()).ok()?.kind().fn_def()?;
+                if callee != assume_unchanged {
+                    return None;
+                }
+                let [_, Operand::Move(snapshot_ref) | Operand::Copy(snapshot_ref)] =
+                    args.as_slice()
+                else {
+                    return None;
+                };
+                let (stmt, place) = block.statements.iter().enumerate().rev().find_map(
+                    |(idx, stmt)| match &stmt.kind {
+                        StatementKind::Assign(dest, Rvalue::Ref(_, _, place))
+                            if dest == snapshot_ref =>
+                        {
+                            Some((idx, place.clone()))
+                        }
+                        _ => None,
+                    },
+                )?;
+                Some(AssumeUnchanged { bb, stmt, place, span: block.terminator.span })
+            })
+            .collect()
+    }
+
+    /// Find the block of the call that precedes the given use of the macro, and check that the
+    /// call is to a function replaced by its contract, and that the location can be copied
+    /// before the call.
+    fn find_stubbed_call(
+        &self,
+        tcx: TyCtxt,
+        body: &MutableBody,
+        predecessors: &[Vec<BasicBlockIdx>],
+        use_: &AssumeUnchanged,
+    ) -> Result<BasicBlockIdx, String> {
+        let blocks = body.blocks();
+        // The locals the location depends on must not be assigned between the call and the macro.
+        let mut assigned: HashSet<Local> =
+            assigned_locals(&blocks[use_.bb].statements[..use_.stmt]).collect();
+        let mut bb = use_.bb;
+        loop {
+            let [pred] = predecessors[bb].as_slice() else {
+                return Err(
+                    "it does not follow a call to a function stubbed by its contract".into()
+                );
+            };
+            bb = *pred;
+            if let TerminatorKind::Call { func, destination, .. } = &blocks[bb].terminator.kind {
+                let callee = func
+                    .ty(body.locals())
+                    .ok()
+                    .and_then(|ty| ty.kind().fn_def())
+                    .map(|(def, _)| rustc_internal::internal(tcx, def.def_id()));
+                if !callee.is_some_and(|callee| self.replace_fns.contains(&callee)) {
+                    return Err(
+                        "the preceding call is not to a function stubbed by its contract".into()
+                    );
+                }
+                assigned.insert(destination.local);
+                if assigned.contains(&use_.place.local) {
+                    return Err("the location is not initialized before the preceding call".into());
+                }
+                return Ok(bb);
+            }
+            assigned.extend(assigned_locals(&blocks[bb].statements));
+        }
+    }
+}
+
+/// The locals that are fully overwritten by the given statements.
+fn assigned_locals(statements: &[Statement]) -> impl Iterator<Item = Local> + '_ {
+    statements.iter().filter_map(|stmt| match &stmt.kind {
+        StatementKind::Assign(place, _) if place.projection.is_empty() => Some(place.local),
+        StatementKind::StorageLive(local) | StatementKind::StorageDead(local) => Some(*local),
+        _ => None,
+    })
+}
+
+/// The predecessors of each block.
+fn predecessors(body: &Body) -> Vec<Vec<BasicBlockIdx>> {
+    let mut predecessors = vec![vec![]; body.blocks.len()];
+    for (bb, block) in body.blocks.iter().enumerate() {
+        for succ in block.terminator.successors() {
+            predecessors[succ].push(bb);
+        }
+    }
+    predecessors
+}
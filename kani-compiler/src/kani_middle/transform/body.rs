@@ -495,6 +495,20 @@ pub fn new_assert(tcx: TyCtxt) -> CheckType {
             CheckType::NoCore
         }
     }
+
+    /// This will create the type of check that is available in the current crate, attempting to
+    /// create a cover property for the condition.
+    ///
+    /// If `kani` crate is available, this will return [CheckType::Assert], and the instance will
+    /// point to `kani::cover`. Otherwise, we return [CheckType::NoCore], since a cover property
+    /// cannot be modeled as a panic.
+    pub fn new_cover(tcx: TyCtxt) -> CheckType {
+        if let Some(instance) = find_instance(tcx, "KaniCover") {
+            CheckType::Assert(instance)
+        } else {
+            CheckType::NoCore
+        }
+    }
 }
 
 /// We store the index of an instruction to avoid borrow checker issues and unnecessary copies.
@@ -0,0 +1,313 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement a transformation pass that checks that pinned values are not moved out of their
+//! location, which is enabled by `-Z pin-checks`.
+//!
+//! Once a value of a type that doesn't implement `Unpin` is pinned, the code that has access to
+//! its location (e.g., through `Pin::get_unchecked_mut`) must not move the value out of it until
+//! the value is dropped. The pass instruments the following operations for such types:
+//!   - Creating a `Pin` from a reference or a `Box` marks the pointee location as pinned. This
+//!     covers `Pin::new_unchecked`, `Box::pin` and the `pin!` macro.
+//!   - Dropping a value, or ending the storage of a local, unpins its location.
+//!   - Moving a value out of a location through a pointer checks that the location isn't pinned.
+//!     This includes the bitwise copies done by `ptr::read`, `mem::replace` and `mem::take`, and
+//!     the `typed_swap` intrinsic used by `mem::swap`.
+//!
+//! Types that implement `Copy` are not checked, since copying a value doesn't invalidate it.
+//!
+//! Only one pinned location is tracked at a time, and it is chosen non-deterministically. See
+//! `kani_core::pin` for more details.
+use crate::args::ExtraChecks;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::lang_items::LangItem;
+use rustc_middle::ty::{ParamEnv, TyCtxt};
+use rustc_smir::rustc_internal;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    AggregateKind, Body, CastKind, Mutability, Operand, Place, ProjectionElem, Rvalue,
+    StatementKind, TerminatorKind, UnOp,
+};
+use stable_mir::ty::{FnDef, GenericArgKind, GenericArgs, Ty};
+use tracing::trace;
+
+/// Instrument the code with checks that pinned values are not moved.
+#[derive(Debug)]
+pub struct PinCheckPass {
+    check_type: CheckType,
+    /// The functions that track the pinned location, if the `kani` crate is available.
+    pin_fns: Option<PinFns>,
+}
+
+/// The functions of the Kani library that track the pinned location.
+#[derive(Debug)]
+struct PinFns {
+    pin: FnDef,
+    unpin: FnDef,
+    is_pinned: FnDef,
+}
+
+impl TransformPass for PinCheckPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().ub_check.contains(&ExtraChecks::Pin) && self.pin_fns.is_some()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let ops = collect_pin_ops(tcx, &body);
+        if ops.is_empty() {
+            return (false, body);
+        }
+        let pin_fns = self.pin_fns.as_ref().unwrap();
+        let mut new_body = MutableBody::from(body);
+        // The operations are collected in order, so instrumenting them in reverse order keeps the
+        // source of the remaining ones valid.
+        for op in ops.into_iter().rev() {
+            let mut source = op.source;
+            match op.kind {
+                PinOpKind::Pin => {
+                    let ptr =
+                        new_body.insert_assignment(op.ptr, &mut source, InsertPosition::After);
+                    let unit = new_body.new_local(
+                        Ty::new_tuple(&[]),
+                        source.span(new_body.blocks()),
+                        Mutability::Not,
+                    );
+                    new_body.insert_call(
+                        &resolve(pin_fns.pin, op.ty),
+                        &mut source,
+                        InsertPosition::After,
+                        vec![Operand::Move(Place::from(ptr))],
+                        Place::from(unit),
+                    );
+                }
+                PinOpKind::Unpin => {
+                    let ptr =
+                        new_body.insert_assignment(op.ptr, &mut source, InsertPosition::Before);
+                    let unit = new_body.new_local(
+                        Ty::new_tuple(&[]),
+                        source.span(new_body.blocks()),
+                        Mutability::Not,
+                    );
+                    new_body.insert_call(
+                        &resolve(pin_fns.unpin, op.ty),
+                        &mut source,
+                        InsertPosition::Before,
+                        vec![Operand::Move(Place::from(ptr))],
+                        Place::from(unit),
+                    );
+                }
+                PinOpKind::MoveOut => {
+                    let ptr =
+                        new_body.insert_assignment(op.ptr, &mut source, InsertPosition::Before);
+                    let pinned = new_body.new_local(
+                        Ty::bool_ty(),
+                        source.span(new_body.blocks()),
+                        Mutability::Not,
+                    );
+                    new_body.insert_call(
+                        &resolve(pin_fns.is_pinned, op.ty),
+                        &mut source,
+                        InsertPosition::Before,
+                        vec![Operand::Move(Place::from(ptr))],
+                        Place::from(pinned),
+                    );
+                    let not_pinned = new_body.insert_assignment(
+                        Rvalue::UnaryOp(UnOp::Not, Operand::Move(Place::from(pinned))),
+                        &mut source,
+                        InsertPosition::Before,
+                    );
+                    let msg = format!(
+                        "Pin violation: moving a pinned value of type `{}` out of its location",
+                        op.ty
+                    );
+                    new_body.insert_check(
+                        tcx,
+                        &self.check_type,
+                        &mut source,
+                        InsertPosition::Before,
+                        not_pinned,
+                        &msg,
+                    );
+                }
+            }
+        }
+        (true, new_body.into())
+    }
+}
+
+impl PinCheckPass {
+    pub fn new(tcx: TyCtxt, check_type: CheckType) -> Self {
+        let pin_fns = (|| {
+            Some(PinFns {
+                pin: find_fn_def(tcx, "KaniPinPointer")?,
+                unpin: find_fn_def(tcx, "KaniUnpinPointer")?,
+                is_pinned: find_fn_def(tcx, "KaniIsPointerPinned")?,
+            })
+        })();
+        PinCheckPass { check_type, pin_fns }
+    }
+}
+
+/// An operation that changes or depends on whether a location is pinned.
+#[derive(Debug)]
+struct PinOp {
+    kind: PinOpKind,
+    source: SourceInstruction,
+    /// The raw pointer to the location, as a `*const T` or `*mut T`.
+    ptr: Rvalue,
+    /// The type of the pinned value.
+    ty: Ty,
+}
+
+#[derive(Debug)]
+enum PinOpKind {
+    /// A `Pin` was created for the location, which is instrumented after the source.
+    Pin,
+    /// The value at the location is about to be dropped.
+    Unpin,
+    /// The value at the location is about to be moved out of it.
+    MoveOut,
+}
+
+/// Collect the operations of the body that pin, unpin or move out values that must be checked,
+/// in the order they appear in the body.
+fn collect_pin_ops(tcx: TyCtxt, body: &Body) -> Vec<PinOp> {
+    let locals = body.locals();
+    let address_of = |place: &Place| Rvalue::AddressOf(Mutability::Not, place.clone());
+    let mut ops = vec![];
+    for (bb, block) in body.blocks.iter().enumerate() {
+        for (idx, stmt) in block.statements.iter().enumerate() {
+            let source = SourceInstruction::Statement { idx, bb };
+            match &stmt.kind {
+                StatementKind::Assign(
+                    dest,
+                    Rvalue::Aggregate(AggregateKind::Adt(def, ..), args),
+                ) if tcx
+                    .is_lang_item(rustc_internal::internal(tcx, def.def_id()), LangItem::Pin) =>
+                {
+                    let [pointer] = args.as_slice() else { continue };
+                    let pointer_ty = pointer.ty(locals).unwrap();
+                    // Only references and boxes can be dereferenced to find the pinned location.
+                    let Some(pointee) = pointer_ty.kind().builtin_deref(false) else { continue };
+                    if !needs_pin_check(tcx, pointee.ty) {
+                        continue;
+                    }
+                    let mut pinned = dest.clone();
+                    pinned.projection.push(ProjectionElem::Field(0, pointer_ty));
+                    pinned.projection.push(ProjectionElem::Deref);
+                    ops.push(PinOp {
+                        kind: PinOpKind::Pin,
+                        source,
+                        ptr: address_of(&pinned),
+                        ty: pointee.ty,
+                    });
+                }
+                StatementKind::Assign(
+                    _,
+                    Rvalue::Use(Operand::Copy(place) | Operand::Move(place)),
+                ) => {
+                    if let Some(ty) = moved_out_ty(tcx, body, place) {
+                        let kind = PinOpKind::MoveOut;
+                        ops.push(PinOp { kind, source, ptr: address_of(place), ty });
+                    }
+                }
+                StatementKind::StorageDead(local) => {
+                    let ty = locals[*local].ty;
+                    if needs_pin_check(tcx, ty) {
+                        let ptr = address_of(&Place::from(*local));
+                        ops.push(PinOp { kind: PinOpKind::Unpin, source, ptr, ty });
+                    }
+                }
+                _ => {}
+            }
+        }
+        let source = SourceInstruction::Terminator { bb };
+        match &block.terminator.kind {
+            TerminatorKind::Drop { place, .. } => {
+                let ty = place.ty(locals).unwrap();
+                if needs_pin_check(tcx, ty) {
+                    ops.push(PinOp { kind: PinOpKind::Unpin, source, ptr: address_of(place), ty });
+                }
+            }
+            TerminatorKind::Call { func, args, .. } => {
+                if let Some(ty) = typed_swap_ty(func, body).filter(|ty| needs_pin_check(tcx, *ty)) {
+                    for arg in args {
+                        let arg = match arg {
+                            Operand::Copy(place) | Operand::Move(place) => {
+                                Operand::Copy(place.clone())
+                            }
+                            Operand::Constant(_) => arg.clone(),
+                        };
+                        let ptr =
+                            Rvalue::Cast(CastKind::PtrToPtr, arg, Ty::new_ptr(ty, Mutability::Not));
+                        ops.push(PinOp { kind: PinOpKind::MoveOut, source, ptr, ty });
+                    }
+                    continue;
+                }
+                for arg in args {
+                    if let Operand::Move(place) = arg
+                        && let Some(ty) = moved_out_ty(tcx, body, place)
+                    {
+                        let kind = PinOpKind::MoveOut;
+                        ops.push(PinOp { kind, source, ptr: address_of(place), ty });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    ops
+}
+
+/// The type of the value moved out of `place`, if it's read through a pointer and it must be
+/// checked.
+fn moved_out_ty(tcx: TyCtxt, body: &Body, place: &Place) -> Option<Ty> {
+    if place.projection.last() != Some(&ProjectionElem::Deref) {
+        return None;
+    }
+    let ty = place.ty(body.locals()).ok()?;
+    needs_pin_check(tcx, ty).then_some(ty)
+}
+
+/// The type of the values swapped by a call to the `typed_swap` intrinsic.
+fn typed_swap_ty(func: &Operand, body: &Body) -> Option<Ty> {
+    let (def, args) = func.ty(body.locals()).ok()?.kind().fn_def()?;
+    if !def.is_intrinsic() {
+        return None;
+    }
+    let instance = Instance::resolve(def, &args).ok()?;
+    if instance.intrinsic_name()?.as_str() != "typed_swap" {
+        return None;
+    }
+    args.0.first()?.ty().copied()
+}
+
+/// Whether moving a value of this type out of a pinned location must be checked.
+fn needs_pin_check(tcx: TyCtxt, ty: Ty) -> bool {
+    let ty = rustc_internal::internal(tcx, ty);
+    let param_env = ParamEnv::reveal_all();
+    ty.is_sized(tcx, param_env)
+        && !ty.is_unpin(tcx, param_env)
+        && !ty.is_copy_modulo_regions(tcx, param_env)
+}
+
+fn resolve(def: FnDef, ty: Ty) -> Instance {
+    Instance::resolve(def, &GenericArgs(vec![GenericArgKind::Type(ty)])).unwrap()
+}
@@ -13,6 +13,7 @@
 use rustc_smir::rustc_internal;
 use stable_mir::{
     CrateDef,
+    abi::FieldsShape,
     mir::{
         AggregateKind, BasicBlock, Body, ConstOperand, Mutability, Operand, Place, Rvalue,
         Statement, StatementKind, Terminator, TerminatorKind, UnwindAction, mono::Instance,
@@ -20,6 +21,7 @@
     ty::{FnDef, GenericArgKind, GenericArgs, MirConst, RigidTy, Ty, TyConst, TyKind, UintTy},
 };
 use std::collections::HashMap;
+use std::ops::Range;
 
 pub use delayed_ub::DelayedUbPass;
 pub use ptr_uninit::UninitPass;
@@ -194,6 +196,9 @@ fn build_check_for_operation(
             MemoryInitOp::StoreArgument { .. } | MemoryInitOp::LoadArgument { .. } => {
                 self.build_argument_operation(body, source, operation, pointee_info)
             }
+            MemoryInitOp::CheckAssumeInit { .. } => {
+                self.build_check_assume_init(body, source, operation, pointee_info)
+            }
             MemoryInitOp::Unsupported { .. } | MemoryInitOp::TriviallyUnsafe { .. } => {
                 unreachable!()
             }
@@ -327,6 +332,74 @@ fn build_get_and_check(
         )
     }
 
+    /// Inject a check that the data bytes of the value wrapped by a `MaybeUninit` are initialized.
+    /// Each contiguous range of data bytes is checked separately, so the failed checks report
+    /// which bytes are uninitialized, e.g., the fields that were not written to.
+    fn build_check_assume_init(
+        &mut self,
+        body: &mut MutableBody,
+        source: &mut SourceInstruction,
+        operation: MemoryInitOp,
+        pointee_info: PointeeInfo,
+    ) {
+        let MemoryInitOp::CheckAssumeInit { method, .. } = &operation else { unreachable!() };
+        let ty = *pointee_info.ty();
+        let PointeeLayout::Sized { layout } = pointee_info.layout() else {
+            let reason = format!(
+                "Kani does not support checking memory initialization in `MaybeUninit::{method}` for `{ty}`."
+            );
+            self.inject_assert_false(self.tcx, body, source, operation.position(), &reason);
+            return;
+        };
+        let is_ptr_initialized_instance = resolve_mem_init_fn(
+            get_mem_init_fn_def(
+                self.tcx,
+                KANI_IS_PTR_INITIALIZED_DIAGNOSTIC,
+                &mut self.mem_init_fn_cache,
+            ),
+            layout.len(),
+            ty,
+        );
+        for range in data_byte_ranges(ty, layout) {
+            let ret_place = Place {
+                local: body.new_local(Ty::bool_ty(), source.span(body.blocks()), Mutability::Not),
+                projection: vec![],
+            };
+            let mut statements = vec![];
+            let ptr_operand = operation.mk_operand(body, &mut statements, source);
+            // Only check the bytes in the range, and treat the others as padding.
+            let mut range_layout = vec![false; layout.len()];
+            range_layout[range.clone()].fill(true);
+            let layout_operand = mk_layout_operand(body, &mut statements, source, &range_layout);
+            let terminator = Terminator {
+                kind: TerminatorKind::Call {
+                    func: Operand::Copy(Place::from(body.new_local(
+                        is_ptr_initialized_instance.ty(),
+                        source.span(body.blocks()),
+                        Mutability::Not,
+                    ))),
+                    args: vec![ptr_operand, layout_operand],
+                    destination: ret_place.clone(),
+                    target: Some(0), // this will be overriden in add_bb
+                    unwind: UnwindAction::Terminate,
+                },
+                span: source.span(body.blocks()),
+            };
+            body.insert_bb(BasicBlock { statements, terminator }, source, operation.position());
+            body.insert_check(
+                self.tcx,
+                &self.check_type,
+                source,
+                operation.position(),
+                ret_place.local,
+                &format!(
+                    "Undefined Behavior: Calling `MaybeUninit::{method}` with uninitialized bytes {}..{} of `{ty}`",
+                    range.start, range.end
+                ),
+            );
+        }
+    }
+
     /// Inject a store into memory initialization state to initialize or deinitialize all
     /// non-padding bytes.
     fn build_set(
@@ -698,6 +771,31 @@ pub fn mk_layout_operand(
     Operand::Move(Place { local: result, projection: vec![] })
 }
 
+/// The ranges of contiguous data bytes of a type, e.g., `[0..2, 3..4]` for the byte mask
+/// `[true, true, false, true]`. Ranges are also split where the fields of the type start, so
+/// that a range doesn't span several fields.
+fn data_byte_ranges(ty: Ty, layout_byte_mask: &[bool]) -> Vec<Range<usize>> {
+    let field_offsets: Vec<usize> = match ty.layout().map(|layout| layout.shape().fields) {
+        Ok(FieldsShape::Arbitrary { offsets }) => {
+            offsets.iter().map(|offset| offset.bytes()).collect()
+        }
+        _ => vec![],
+    };
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for (offset, is_data) in layout_byte_mask.iter().enumerate() {
+        if !is_data {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(range) if range.end == offset && !field_offsets.contains(&offset) => {
+                range.end += 1
+            }
+            _ => ranges.push(offset..offset + 1),
+        }
+    }
+    ranges
+}
+
 /// Retrieve a function definition by diagnostic string, caching the result.
 pub fn get_mem_init_fn_def(
     tcx: TyCtxt,
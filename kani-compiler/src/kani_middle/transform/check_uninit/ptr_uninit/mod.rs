@@ -5,12 +5,14 @@
 //! uninitialized memory via raw pointers.
 
 use crate::args::ExtraChecks;
+use crate::kani_middle::stable_fn_def;
 use crate::kani_middle::transform::{
     TransformPass, TransformationType,
     body::{CheckType, InsertPosition, MutableBody, SourceInstruction},
     check_uninit::{UninitInstrumenter, get_mem_init_fn_def},
 };
 use crate::kani_queries::QueryDb;
+use rustc_hir::lang_items::LangItem;
 use rustc_middle::ty::TyCtxt;
 use rustc_smir::rustc_internal;
 use stable_mir::{
@@ -21,7 +23,7 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use tracing::trace;
-use uninit_visitor::CheckUninitVisitor;
+use uninit_visitor::{ASSUME_INIT_METHODS, CheckUninitVisitor};
 
 mod uninit_visitor;
 
@@ -31,6 +33,9 @@
 pub struct UninitPass {
     pub check_type: CheckType,
     pub mem_init_fn_cache: HashMap<&'static str, FnDef>,
+    /// The methods of `MaybeUninit` that assume that the wrapped value is initialized, which are
+    /// collected the first time they are needed.
+    pub assume_init_cache: Option<HashMap<FnDef, &'static str>>,
 }
 
 impl TransformPass for UninitPass {
@@ -62,19 +67,38 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
         }
 
         // Call a helper that performs the actual instrumentation.
+        let assume_init_methods =
+            self.assume_init_cache.get_or_insert_with(|| assume_init_methods(tcx));
         let (instrumentation_added, body) = UninitInstrumenter::run(
             new_body.into(),
             tcx,
             instance,
             self.check_type.clone(),
             &mut self.mem_init_fn_cache,
-            CheckUninitVisitor::new(),
+            CheckUninitVisitor::new(assume_init_methods),
         );
 
         (changed || instrumentation_added, body)
     }
 }
 
+/// Collect the methods of `MaybeUninit` that assume that the wrapped value is initialized, with
+/// their names.
+fn assume_init_methods(tcx: TyCtxt) -> HashMap<FnDef, &'static str> {
+    let Some(maybe_uninit) = tcx.lang_items().get(LangItem::MaybeUninit) else {
+        return HashMap::new();
+    };
+    tcx.inherent_impls(maybe_uninit)
+        .iter()
+        .flat_map(|impl_id| tcx.associated_item_def_ids(impl_id))
+        .filter_map(|item| {
+            let name = tcx.item_name(*item);
+            let method = ASSUME_INIT_METHODS.iter().find(|method| name.as_str() == **method)?;
+            Some((stable_fn_def(tcx, *item)?, *method))
+        })
+        .collect()
+}
+
 /// Checks if the instance is a harness -- an entry point of Kani analysis.
 fn is_harness(instance: Instance, tcx: TyCtxt) -> bool {
     let harness_identifiers = [
@@ -17,24 +17,35 @@
 use stable_mir::{
     mir::{
         AggregateKind, CastKind, LocalDecl, MirVisitor, NonDivergingIntrinsic, Operand, Place,
-        PointerCoercion, ProjectionElem, Rvalue, Statement, StatementKind, Terminator,
-        TerminatorKind,
+        PointerCoercion, ProjectionElem, RETURN_LOCAL, Rvalue, Statement, StatementKind,
+        Terminator, TerminatorKind,
         alloc::GlobalAlloc,
         mono::{Instance, InstanceKind},
         visit::{Location, PlaceContext},
     },
-    ty::{AdtKind, ConstantKind, RigidTy, TyKind},
+    ty::{AdtKind, ConstantKind, FnDef, RigidTy, TyKind},
 };
+use std::collections::HashMap;
 
-pub struct CheckUninitVisitor {
+/// The methods of `MaybeUninit` that assume that the wrapped value is initialized.
+pub const ASSUME_INIT_METHODS: &[&str] =
+    &["assume_init", "assume_init_read", "assume_init_ref", "assume_init_mut", "assume_init_drop"];
+
+pub struct CheckUninitVisitor<'a> {
     locals: Vec<LocalDecl>,
+    /// The methods of `MaybeUninit` that assume that the wrapped value is initialized, with their
+    /// names.
+    assume_init_methods: &'a HashMap<FnDef, &'static str>,
     /// All target instructions in the body.
     targets: Vec<InitRelevantInstruction>,
     /// Current analysis target, eventually needs to be added to a list of all targets.
     current_target: InitRelevantInstruction,
+    /// Whether the place being visited is the operand of `&raw`, which doesn't read the memory
+    /// behind its last dereference.
+    taking_address: bool,
 }
 
-impl TargetFinder for CheckUninitVisitor {
+impl TargetFinder for CheckUninitVisitor<'_> {
     fn find_all(mut self, body: &MutableBody) -> Vec<InitRelevantInstruction> {
         self.locals = body.locals().to_vec();
         for (bb_idx, bb) in body.blocks().iter().enumerate() {
@@ -66,6 +77,7 @@ fn find_all(mut self, body: &MutableBody) -> Vec<InitRelevantInstruction> {
                         self.push_target(MemoryInitOp::LoadArgument {
                             operand: Operand::Copy(Place { local: idx, projection: vec![] }),
                             argument_no: idx,
+                            position: InsertPosition::Before,
                         })
                     }
                 }
@@ -76,16 +88,18 @@ fn find_all(mut self, body: &MutableBody) -> Vec<InitRelevantInstruction> {
     }
 }
 
-impl CheckUninitVisitor {
-    pub fn new() -> Self {
+impl<'a> CheckUninitVisitor<'a> {
+    pub fn new(assume_init_methods: &'a HashMap<FnDef, &'static str>) -> Self {
         Self {
             locals: vec![],
+            assume_init_methods,
             targets: vec![],
             current_target: InitRelevantInstruction {
                 source: SourceInstruction::Statement { idx: 0, bb: 0 },
                 before_instruction: vec![],
                 after_instruction: vec![],
             },
+            taking_address: false,
         }
     }
 
@@ -94,7 +108,7 @@ fn push_target(&mut self, source_op: MemoryInitOp) {
     }
 }
 
-impl MirVisitor for CheckUninitVisitor {
+impl MirVisitor for CheckUninitVisitor<'_> {
     fn visit_statement(&mut self, stmt: &Statement, location: Location) {
         // Leave it as an exhaustive match to be notified when a new kind is added.
         match &stmt.kind {
@@ -156,8 +170,12 @@ fn visit_statement(&mut self, stmt: &Statement, location: Location) {
                     }
                 }
                 // Check whether Rvalue creates a new initialized pointer previously not captured inside shadow memory.
+                // Unions are already tracked when they are created and updated, e.g., the pointer
+                // to the value wrapped by a `MaybeUninit`.
                 if place.ty(&self.locals).unwrap().kind().is_raw_ptr() {
-                    if let Rvalue::AddressOf(..) = rvalue {
+                    if let Rvalue::AddressOf(_, pointee) = rvalue
+                        && !pointee.ty(&self.locals).unwrap().kind().is_union()
+                    {
                         self.push_target(MemoryInitOp::Set {
                             operand: Operand::Copy(place.clone()),
                             value: true,
@@ -387,6 +405,14 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                                 _ => {}
                             }
                         } else {
+                            if let Some(operation) = check_assume_init(
+                                &self.locals,
+                                self.assume_init_methods,
+                                &instance,
+                                args,
+                            ) {
+                                self.push_target(operation);
+                            }
                             let union_args: Vec<_> = args
                                 .iter()
                                 .enumerate()
@@ -400,6 +426,14 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                                     })
                                 }
                             }
+                            // The callee stores a returned union as argument number 0.
+                            if destination.ty(&self.locals).unwrap().kind().is_union() {
+                                self.push_target(MemoryInitOp::LoadArgument {
+                                    operand: Operand::Copy(destination.clone()),
+                                    argument_no: 0,
+                                    position: InsertPosition::After,
+                                })
+                            }
                         }
                     }
                     _ => {}
@@ -437,11 +471,20 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
                     });
                 }
             }
+            TerminatorKind::Return => {
+                self.super_terminator(term, location);
+                // Pass the initialization state of a returned union to the caller.
+                if self.locals[RETURN_LOCAL].ty.kind().is_union() {
+                    self.push_target(MemoryInitOp::StoreArgument {
+                        operand: Operand::Copy(Place::from(RETURN_LOCAL)),
+                        argument_no: 0,
+                    })
+                }
+            }
             TerminatorKind::Goto { .. }
             | TerminatorKind::SwitchInt { .. }
             | TerminatorKind::Resume
             | TerminatorKind::Abort
-            | TerminatorKind::Return
             | TerminatorKind::Unreachable
             | TerminatorKind::Assert { .. }
             | TerminatorKind::InlineAsm { .. } => self.super_terminator(term, location),
@@ -451,13 +494,18 @@ fn visit_terminator(&mut self, term: &Terminator, location: Location) {
     }
 
     fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        // Taking the address of a field behind a pointer, e.g., `&raw mut (*ptr).field`, doesn't
+        // read the memory the pointer points to.
+        let last_deref = place.projection.iter().rposition(|elem| *elem == ProjectionElem::Deref);
         for (idx, elem) in place.projection.iter().enumerate() {
             let intermediate_place =
                 Place { local: place.local, projection: place.projection[..idx].to_vec() };
             match elem {
                 ProjectionElem::Deref => {
                     let ptr_ty = intermediate_place.ty(&self.locals).unwrap();
-                    if ptr_ty.kind().is_raw_ptr() {
+                    if ptr_ty.kind().is_raw_ptr()
+                        && !(self.taking_address && last_deref == Some(idx))
+                    {
                         self.push_target(MemoryInitOp::Check {
                             operand: Operand::Copy(intermediate_place.clone()),
                         });
@@ -564,7 +612,9 @@ fn visit_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
                 _ => {}
             }
         };
+        self.taking_address = matches!(rvalue, Rvalue::AddressOf(..));
         self.super_rvalue(rvalue, location);
+        self.taking_address = false;
     }
 }
 
@@ -582,6 +632,7 @@ fn can_skip_intrinsic(intrinsic: Intrinsic) -> bool {
         | Intrinsic::BlackBox
         | Intrinsic::Breakpoint
         | Intrinsic::Bswap
+        | Intrinsic::CallerLocation
         | Intrinsic::CeilF32
         | Intrinsic::CeilF64
         | Intrinsic::CopySignF32
@@ -712,6 +763,33 @@ fn try_remove_topmost_deref(place: &Place) -> Option<Place> {
     }
 }
 
+/// If `instance` is one of the methods of `MaybeUninit` that assume that the wrapped value is
+/// initialized, return the check that the value is initialized before the call.
+fn check_assume_init(
+    locals: &[LocalDecl],
+    assume_init_methods: &HashMap<FnDef, &'static str>,
+    instance: &Instance,
+    args: &[Operand],
+) -> Option<MemoryInitOp> {
+    let (fn_def, _) = instance.ty().kind().fn_def()?;
+    let method = assume_init_methods.get(&fn_def)?.to_string();
+    let receiver = args.first()?;
+    let (place, receiver_ty) = match receiver {
+        Operand::Copy(place) | Operand::Move(place) => (place, receiver.ty(locals).unwrap()),
+        Operand::Constant(_) => return None,
+    };
+    // Methods that take `self` by reference check the value behind the reference.
+    let place = match receiver_ty.kind() {
+        TyKind::RigidTy(RigidTy::Ref(..)) => {
+            let mut projection = place.projection.clone();
+            projection.push(ProjectionElem::Deref);
+            Place { local: place.local, projection }
+        }
+        _ => place.clone(),
+    };
+    Some(MemoryInitOp::CheckAssumeInit { operand: Operand::Copy(place), method })
+}
+
 /// Try retrieving instance for the given function operand.
 fn try_resolve_instance(locals: &[LocalDecl], func: &Operand) -> Result<Instance, String> {
     let ty = func.ty(locals).unwrap();
@@ -6,7 +6,7 @@
 
 use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
 use stable_mir::{
-    mir::{FieldIdx, Mutability, Operand, Place, Rvalue, Statement, StatementKind},
+    mir::{CastKind, FieldIdx, Mutability, Operand, Place, Rvalue, Statement, StatementKind},
     ty::{RigidTy, Ty},
 };
 use strum_macros::AsRefStr;
@@ -43,11 +43,15 @@ pub enum MemoryInitOp {
     /// Create a union from scratch with a given field index and store it in the provided operand.
     CreateUnion { operand: Operand, field: FieldIdx },
     /// Load argument containing a union from the argument buffer together if the argument number
-    /// provided matches.
-    LoadArgument { operand: Operand, argument_no: usize },
+    /// provided matches. The return value is loaded as argument number 0 after the call.
+    LoadArgument { operand: Operand, argument_no: usize, position: InsertPosition },
     /// Store argument containing a union into the argument buffer together with the argument number
-    /// provided.
+    /// provided. The return value is stored as argument number 0 before returning.
     StoreArgument { operand: Operand, argument_no: usize },
+    /// Check that the data bytes of the value wrapped by the `MaybeUninit<T>` `operand` are
+    /// initialized before `method` assumes they are. Each contiguous range of data bytes of `T` is
+    /// checked separately to report which bytes are uninitialized.
+    CheckAssumeInit { operand: Operand, method: String },
 }
 
 impl MemoryInitOp {
@@ -72,6 +76,13 @@ pub fn mk_operand(
             | MemoryInitOp::StoreArgument { operand, .. } => {
                 mk_ref(operand, body, statements, source)
             }
+            MemoryInitOp::CheckAssumeInit { operand, .. } => {
+                // `MaybeUninit<T>` is `repr(transparent)`, so the pointer to it is also a pointer
+                // to the wrapped value.
+                let maybe_uninit_ptr = mk_ref(operand, body, statements, source);
+                let ptr_ty = self.operand_ty(body);
+                mk_ptr_cast(maybe_uninit_ptr, ptr_ty, body, statements, source)
+            }
             MemoryInitOp::Copy { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::Unsupported { .. }
@@ -126,6 +137,13 @@ pub fn operand_ty(&self, body: &MutableBody) -> Ty {
                 let rvalue = Rvalue::AddressOf(Mutability::Not, place.clone());
                 rvalue.ty(body.locals()).unwrap()
             }
+            MemoryInitOp::CheckAssumeInit { operand, .. } => {
+                let maybe_uninit_ty = operand.ty(body.locals()).unwrap();
+                let RigidTy::Adt(_, args) = maybe_uninit_ty.kind().rigid().unwrap().clone() else {
+                    unreachable!("expected `MaybeUninit<T>`, found `{maybe_uninit_ty}`")
+                };
+                Ty::new_ptr(*args.0[0].expect_ty(), Mutability::Not)
+            }
             MemoryInitOp::Unsupported { .. } | MemoryInitOp::TriviallyUnsafe { .. } => {
                 unreachable!("operands do not exist for this operation")
             }
@@ -167,7 +185,8 @@ pub fn expect_count(&self) -> Operand {
             | MemoryInitOp::Unsupported { .. }
             | MemoryInitOp::TriviallyUnsafe { .. }
             | MemoryInitOp::StoreArgument { .. }
-            | MemoryInitOp::LoadArgument { .. } => unreachable!(),
+            | MemoryInitOp::LoadArgument { .. }
+            | MemoryInitOp::CheckAssumeInit { .. } => unreachable!(),
         }
     }
 
@@ -185,7 +204,8 @@ pub fn expect_value(&self) -> bool {
             | MemoryInitOp::Copy { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::StoreArgument { .. }
-            | MemoryInitOp::LoadArgument { .. } => unreachable!(),
+            | MemoryInitOp::LoadArgument { .. }
+            | MemoryInitOp::CheckAssumeInit { .. } => unreachable!(),
         }
     }
 
@@ -203,7 +223,8 @@ pub fn union_field(&self) -> Option<FieldIdx> {
             | MemoryInitOp::Copy { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::StoreArgument { .. }
-            | MemoryInitOp::LoadArgument { .. } => None,
+            | MemoryInitOp::LoadArgument { .. }
+            | MemoryInitOp::CheckAssumeInit { .. } => None,
         }
     }
 
@@ -211,14 +232,15 @@ pub fn position(&self) -> InsertPosition {
         match self {
             MemoryInitOp::Set { position, .. }
             | MemoryInitOp::SetSliceChunk { position, .. }
-            | MemoryInitOp::SetRef { position, .. } => *position,
+            | MemoryInitOp::SetRef { position, .. }
+            | MemoryInitOp::LoadArgument { position, .. } => *position,
             MemoryInitOp::Check { .. }
             | MemoryInitOp::CheckSliceChunk { .. }
             | MemoryInitOp::CheckRef { .. }
+            | MemoryInitOp::CheckAssumeInit { .. }
             | MemoryInitOp::Unsupported { .. }
             | MemoryInitOp::TriviallyUnsafe { .. }
-            | MemoryInitOp::StoreArgument { .. }
-            | MemoryInitOp::LoadArgument { .. } => InsertPosition::Before,
+            | MemoryInitOp::StoreArgument { .. } => InsertPosition::Before,
             MemoryInitOp::Copy { .. }
             | MemoryInitOp::AssignUnion { .. }
             | MemoryInitOp::CreateUnion { .. } => InsertPosition::After,
@@ -281,3 +303,21 @@ fn mk_ref(
 
     Operand::Copy(Place { local: ref_local, projection: vec![] })
 }
+
+/// A helper to generate instrumentation for casting a raw pointer to a raw pointer of type `ty`.
+/// Returns the operand with the result of the cast and stores all instrumentation in the
+/// statements vector passed.
+fn mk_ptr_cast(
+    operand: Operand,
+    ty: Ty,
+    body: &mut MutableBody,
+    statements: &mut Vec<Statement>,
+    source: &mut SourceInstruction,
+) -> Operand {
+    let span = source.span(body.blocks());
+    let result = body.new_local(ty, span, Mutability::Not);
+    let rvalue = Rvalue::Cast(CastKind::PtrToPtr, operand, ty);
+    let stmt = Statement { kind: StatementKind::Assign(Place::from(result), rvalue), span };
+    statements.push(stmt);
+    Operand::Copy(Place { local: result, projection: vec![] })
+}
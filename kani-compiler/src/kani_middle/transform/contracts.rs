@@ -3,21 +3,25 @@
 //! This module contains code related to the MIR-to-MIR pass to enable contracts.
 use crate::kani_middle::attributes::KaniAttributes;
 use crate::kani_middle::codegen_units::CodegenUnit;
-use crate::kani_middle::find_fn_def;
 use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
 use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_middle::{SourceLocation, find_fn_def, stable_fn_def};
 use crate::kani_queries::QueryDb;
 use cbmc::{InternString, InternedString};
 use rustc_hir::def_id::DefId as InternalDefId;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{Instance as InstanceInternal, TyCtxt};
 use rustc_smir::rustc_internal;
-use rustc_span::Symbol;
+use rustc_span::hygiene::{ExpnKind, MacroKind};
+use rustc_span::{Span as SpanInternal, Symbol};
 use stable_mir::CrateDef;
 use stable_mir::mir::mono::Instance;
 use stable_mir::mir::{
-    Body, ConstOperand, Operand, Rvalue, Terminator, TerminatorKind, VarDebugInfoContents,
+    BinOp, Body, ConstOperand, Local, Operand, Place, RETURN_LOCAL, Rvalue, StatementKind,
+    Terminator, TerminatorKind, UnOp, VarDebugInfoContents,
+};
+use stable_mir::ty::{
+    ClosureDef, ConstantKind, FnDef, GenericArgs, MirConst, RigidTy, TyKind, TypeAndMut, UintTy,
 };
-use stable_mir::ty::{ClosureDef, FnDef, MirConst, RigidTy, TyKind, TypeAndMut, UintTy};
 use std::collections::HashSet;
 use std::fmt::Debug;
 use tracing::{debug, trace};
@@ -260,10 +264,14 @@ fn any_body(&self, tcx: TyCtxt, mut body: Body) -> (bool, Body) {
 ///    - Replace the non-used generated closures body with unreachable.
 /// 3. Replace the body of `kani_register_contract` by `kani::internal::run_contract_fn` to
 ///    invoke the closure.
+/// 4. For harnesses that check a named contract, replace the body of the target function by a
+///    call to the function generated for that contract.
 #[derive(Debug, Default)]
 pub struct FunctionWithContractPass {
     /// Function that is being checked, if any.
     check_fn: Option<InternalDefId>,
+    /// The target of a named contract check, and the function generated for the contract.
+    named_contract: Option<(InternalDefId, FnDef)>,
     /// Functions that should be stubbed by their contract.
     replace_fns: HashSet<InternalDefId>,
     /// Functions annotated with contract attributes will contain contract closures even if they
@@ -272,6 +280,12 @@ pub struct FunctionWithContractPass {
     unused_closures: HashSet<ClosureDef>,
     /// Cache KaniRunContract function used to implement contracts.
     run_contract_fn: Option<FnDef>,
+    /// Functions whose clauses were already checked for constant conditions.
+    checked_clauses: HashSet<FnDef>,
+    /// Whether the harness only checks that the target doesn't panic when its preconditions
+    /// hold, i.e., whether it has the `#[kani::panic_free]` attribute. In that case, the
+    /// `ensures` clauses of the target are not asserted.
+    panic_free: bool,
 }
 
 impl TransformPass for FunctionWithContractPass {
@@ -294,7 +308,13 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
         trace!(function=?instance.name(), "FunctionWithContractPass::transform");
         match instance.ty().kind().rigid().unwrap() {
             RigidTy::FnDef(def, args) => {
-                if let Some(mode) = self.contract_mode(tcx, *def) {
+                if let Some((target, contract_fn)) = self.named_contract
+                    && rustc_internal::internal(tcx, def.def_id()) == target
+                {
+                    let contract_instance = Instance::resolve(contract_fn, args).unwrap();
+                    (true, redirect_to(body, &contract_instance))
+                } else if let Some(mode) = self.contract_mode(tcx, *def) {
+                    self.check_trivial_clauses(tcx, *def, &body);
                     self.mark_unused(tcx, *def, &body, mode);
                     let new_body = self.set_mode(tcx, body, mode);
                     (true, new_body)
@@ -314,6 +334,9 @@ fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, B
                     let mut new_body = MutableBody::from(body);
                     new_body.clear_body(TerminatorKind::Unreachable);
                     (true, new_body.into())
+                } else if self.panic_free {
+                    // Only the check closures of the target have `ensures` assertions.
+                    remove_ensures(tcx, body)
                 } else {
                     // Not a contract annotated function
                     (false, body)
@@ -333,19 +356,22 @@ impl FunctionWithContractPass {
     pub fn new(tcx: TyCtxt, unit: &CodegenUnit) -> FunctionWithContractPass {
         if let Some(harness) = unit.harnesses.first() {
             let attrs = KaniAttributes::for_instance(tcx, *harness);
-            let check_fn = attrs.interpret_for_contract_attribute().map(|(_, def_id, _)| def_id);
-            let replace_fns: HashSet<_> = attrs
-                .interpret_stub_verified_attribute()
-                .iter()
-                .map(|(_, def_id, _)| *def_id)
-                .collect();
+            let check_fn = attrs.checked_contract_fn();
+            let named_contract = attrs.checked_contract().and_then(|_| {
+                let (_, target, _) = attrs.interpret_for_contract_attribute()?;
+                Some((target, stable_fn_def(tcx, check_fn?)?))
+            });
+            let replace_fns = replaced_fns(tcx, unit);
             let run_contract_fn = find_fn_def(tcx, "KaniRunContract");
             assert!(run_contract_fn.is_some(), "Failed to find Kani run contract function");
             FunctionWithContractPass {
                 check_fn,
+                named_contract,
                 replace_fns,
                 unused_closures: Default::default(),
                 run_contract_fn,
+                checked_clauses: Default::default(),
+                panic_free: attrs.is_panic_free(),
             }
         } else {
             // If reachability mode is PubFns or Tests, we just remove any contract logic.
@@ -477,6 +503,259 @@ fn mark_unused(&mut self, tcx: TyCtxt, fn_def: FnDef, body: &Body, mode: Contrac
     }
 }
 
+impl FunctionWithContractPass {
+    /// Warn about the `requires` and `ensures` clauses of a function that are always true or
+    /// always false, which are usually a mistake.
+    ///
+    /// We inspect the check closure of the function, where each precondition is assumed and each
+    /// postcondition is asserted, and we fold the constants that their conditions are computed
+    /// from. The warnings are silenced by `#[kani::allow_trivial_contract]`.
+    fn check_trivial_clauses(&mut self, tcx: TyCtxt, fn_def: FnDef, body: &Body) {
+        if !self.checked_clauses.insert(fn_def) {
+            return;
+        }
+        let attributes = KaniAttributes::for_def_id(tcx, fn_def.def_id());
+        if attributes.allow_trivial_contract() {
+            return;
+        }
+        let contract = attributes.contract_attributes().unwrap();
+        let Some(check_body) = closure_body(tcx, body, contract.checked_with.as_str()) else {
+            return;
+        };
+        let declared = attributes.contract().unwrap();
+        for bb in &check_body.blocks {
+            let Some((is_requires, call_site)) = clause_check(tcx, &check_body, &bb.terminator)
+            else {
+                continue;
+            };
+            let TerminatorKind::Call { args, .. } = &bb.terminator.kind else { unreachable!() };
+            let Some(ConstValue::Bool(value)) = eval_operand(tcx, &check_body, &args[0], 2) else {
+                continue;
+            };
+            let clauses = if is_requires { &declared.requires } else { &declared.ensures };
+            let location = SourceLocation::new(rustc_internal::stable(call_site));
+            let clause = clauses
+                .iter()
+                .find(|clause| clause.location == location)
+                .map_or(String::new(), |clause| format!(" `{}`", clause.expr));
+            let msg = match (is_requires, value) {
+                (true, true) => format!(
+                    "this precondition{clause} is always true, so it doesn't restrict the \
+                    inputs of the function"
+                ),
+                (true, false) => {
+                    format!("this precondition{clause} is always false, so no input can satisfy it")
+                }
+                (false, true) => {
+                    format!(
+                        "this postcondition{clause} is always true, so it doesn't check anything"
+                    )
+                }
+                (false, false) => format!(
+                    "this postcondition{clause} is always false, so the function can never \
+                    satisfy it"
+                ),
+            };
+            tcx.dcx()
+                .struct_span_warn(call_site, msg)
+                .with_note(format!(
+                    "add `#[kani::allow_trivial_contract]` to `{}` if this is intended",
+                    fn_def.name()
+                ))
+                .emit();
+        }
+    }
+}
+
+/// The body of the closure with the given name in the body of a function with contract.
+fn closure_body(tcx: TyCtxt, body: &Body, name: &str) -> Option<Body> {
+    body.var_debug_info.iter().find_map(|var_info| {
+        if var_info.name.as_str() != name {
+            return None;
+        }
+        let VarDebugInfoContents::Place(place) = &var_info.value else { return None };
+        let TyKind::RigidTy(RigidTy::Closure(def, args)) = place.ty(body.locals()).ok()?.kind()
+        else {
+            return None;
+        };
+        closure_instance(tcx, def, &args).body()
+    })
+}
+
+/// The instance of the body of a closure, as opposed to the `FnOnce` shim of the closure.
+fn closure_instance(tcx: TyCtxt, def: ClosureDef, args: &GenericArgs) -> Instance {
+    let def_id = rustc_internal::internal(tcx, def.def_id());
+    let args = rustc_internal::internal(tcx, args);
+    rustc_internal::stable(InstanceInternal::new(def_id, args))
+}
+
+/// A constant that the condition of a clause is computed from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ConstValue {
+    Bool(bool),
+    Int(i128),
+}
+
+/// Fold the value of an operand, if it's computed from constants by a sequence of assignments.
+///
+/// We only fold comparisons, logical operations, and the conditions of `ensures` clauses, whose
+/// value is the result of a closure. The `depth` bounds how many closures we look into.
+fn eval_operand(tcx: TyCtxt, body: &Body, operand: &Operand, depth: usize) -> Option<ConstValue> {
+    match operand {
+        Operand::Constant(constant) => {
+            let ConstantKind::Allocated(alloc) = constant.const_.kind() else { return None };
+            match constant.ty().kind().rigid()? {
+                RigidTy::Bool => alloc.read_bool().ok().map(ConstValue::Bool),
+                RigidTy::Int(_) => alloc.read_int().ok().map(ConstValue::Int),
+                RigidTy::Uint(_) => {
+                    alloc.read_uint().ok().and_then(|val| val.try_into().ok()).map(ConstValue::Int)
+                }
+                _ => None,
+            }
+        }
+        Operand::Copy(place) | Operand::Move(place) if place.projection.is_empty() => {
+            eval_local(tcx, body, place.local, depth)
+        }
+        Operand::Copy(_) | Operand::Move(_) => None,
+    }
+}
+
+/// Fold the value of a local that is assigned exactly once.
+fn eval_local(tcx: TyCtxt, body: &Body, local: Local, depth: usize) -> Option<ConstValue> {
+    let mut assignments = body.blocks.iter().flat_map(|bb| {
+        let statements = bb.statements.iter().filter_map(move |stmt| match &stmt.kind {
+            StatementKind::Assign(place, rvalue) if place.local == local => {
+                Some(place.projection.is_empty().then_some(Ok(rvalue)))
+            }
+            _ => None,
+        });
+        let call = match &bb.terminator.kind {
+            TerminatorKind::Call { func, args, destination, .. } if destination.local == local => {
+                Some(destination.projection.is_empty().then_some(Err((func, args))))
+            }
+            _ => None,
+        };
+        statements.chain(call)
+    });
+    let assignment = assignments.next()??;
+    if assignments.next().is_some() {
+        return None;
+    }
+    match assignment {
+        Ok(Rvalue::Use(operand)) => eval_operand(tcx, body, operand, depth),
+        Ok(Rvalue::UnaryOp(UnOp::Not, operand)) => match eval_operand(tcx, body, operand, depth)? {
+            ConstValue::Bool(value) => Some(ConstValue::Bool(!value)),
+            ConstValue::Int(_) => None,
+        },
+        Ok(Rvalue::BinaryOp(op, lhs, rhs)) => {
+            let lhs = eval_operand(tcx, body, lhs, depth)?;
+            let rhs = eval_operand(tcx, body, rhs, depth)?;
+            let value = match (op, lhs, rhs) {
+                (BinOp::Eq, _, _) => lhs == rhs,
+                (BinOp::Ne, _, _) => lhs != rhs,
+                (BinOp::Lt, _, _) => lhs < rhs,
+                (BinOp::Le, _, _) => lhs <= rhs,
+                (BinOp::Gt, _, _) => lhs > rhs,
+                (BinOp::Ge, _, _) => lhs >= rhs,
+                (BinOp::BitAnd, ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => lhs & rhs,
+                (BinOp::BitOr, ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => lhs | rhs,
+                (BinOp::BitXor, ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => lhs ^ rhs,
+                _ => return None,
+            };
+            Some(ConstValue::Bool(value))
+        }
+        Err((func, args)) if depth > 0 => {
+            // The condition of an `ensures` clause is computed by `apply_closure(clause, &result)`.
+            let (callee, _) = func.ty(body.locals()).ok()?.kind().fn_def()?;
+            let callee = rustc_internal::internal(tcx, callee.def_id());
+            if !tcx.is_diagnostic_item(Symbol::intern("KaniApplyClosure"), callee) {
+                return None;
+            }
+            let TyKind::RigidTy(RigidTy::Closure(def, closure_args)) =
+                args.first()?.ty(body.locals()).ok()?.kind()
+            else {
+                return None;
+            };
+            let closure_body = closure_instance(tcx, def, &closure_args).body()?;
+            eval_local(tcx, &closure_body, RETURN_LOCAL, depth - 1)
+        }
+        _ => None,
+    }
+}
+
+/// Check if the terminator of a check closure is the assumption of a `requires` clause or the
+/// assertion of an `ensures` clause, and return whether it's a `requires`, together with the span
+/// of the clause attribute.
+fn clause_check(tcx: TyCtxt, body: &Body, terminator: &Terminator) -> Option<(bool, SpanInternal)> {
+    let TerminatorKind::Call { func, .. } = &terminator.kind else { return None };
+    let (callee, _) = func.ty(body.locals()).unwrap().kind().fn_def()?;
+    // The clauses are the conditions of the calls generated by the contract attributes.
+    let span = rustc_internal::internal(tcx, terminator.span);
+    let expn = span.ctxt().outer_expn_data();
+    let ExpnKind::Macro(MacroKind::Attr, macro_name) = expn.kind else { return None };
+    let callee = tcx.get_diagnostic_name(rustc_internal::internal(tcx, callee.def_id()));
+    let is_requires = match callee.as_ref().map(Symbol::as_str) {
+        Some("KaniAssume") if macro_name.as_str().ends_with("requires") => true,
+        Some("KaniAssert") if macro_name.as_str().ends_with("ensures") => false,
+        _ => return None,
+    };
+    Some((is_requires, expn.call_site))
+}
+
+/// Remove the assertions of the `ensures` clauses from the body of a check closure.
+fn remove_ensures(tcx: TyCtxt, body: Body) -> (bool, Body) {
+    let ensures: Vec<_> = body
+        .blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, bb)| clause_check(tcx, &body, &bb.terminator).is_some_and(|(req, _)| !req))
+        .map(|(idx, _)| idx)
+        .collect();
+    if ensures.is_empty() {
+        return (false, body);
+    }
+    let mut new_body = MutableBody::from(body);
+    for bb in ensures {
+        let terminator = &new_body.blocks()[bb].terminator;
+        let TerminatorKind::Call { target: Some(target), .. } = terminator.kind else {
+            unreachable!("expected the assertion of an `ensures` clause")
+        };
+        let kind = TerminatorKind::Goto { target };
+        let span = terminator.span;
+        new_body
+            .replace_terminator(&SourceInstruction::Terminator { bb }, Terminator { kind, span });
+    }
+    (true, new_body.into())
+}
+
+/// The functions that are replaced by their contract in the harness of the given unit.
+pub fn replaced_fns(tcx: TyCtxt, unit: &CodegenUnit) -> HashSet<InternalDefId> {
+    let Some(harness) = unit.harnesses.first() else { return HashSet::default() };
+    KaniAttributes::for_instance(tcx, *harness)
+        .interpret_stub_verified_attribute()
+        .iter()
+        .map(|(_, def_id, _)| *def_id)
+        .chain(unit.boundary_stubs.iter().copied())
+        .collect()
+}
+
+/// Replace the body of a function by a call to `callee`, which has the same signature.
+fn redirect_to(body: Body, callee: &Instance) -> Body {
+    let arg_count = body.arg_locals().len();
+    let mut new_body = MutableBody::from(body);
+    new_body.clear_body(TerminatorKind::Return);
+    let mut source = SourceInstruction::Terminator { bb: 0 };
+    let args = (1..=arg_count).map(|local| Operand::Move(Place::from(local))).collect();
+    new_body.insert_call(
+        callee,
+        &mut source,
+        InsertPosition::Before,
+        args,
+        Place::from(RETURN_LOCAL),
+    );
+    new_body.into()
+}
+
 /// Enumeration that store the value of which implementation should be selected.
 ///
 /// Keep the discriminant values in sync with [kani::internal::mode].
@@ -2,43 +2,61 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! Global transformation pass, which does not modify bodies but dumps MIR whenever the appropriate debug flag is passed.
+//!
+//! The MIR is dumped in two ways:
+//!   - With `--emit mir`, all bodies of a codegen unit are written to a single `.kani.mir` file
+//!     next to the other compiler artifacts.
+//!   - With `--emit-mir`, the body of each function reachable from a harness is written to its
+//!     own file in a directory named after the harness. In the transformed MIR, the lines that
+//!     don't have a counterpart in the original body are marked with [INJECTED_MARKER].
 
+use crate::args::{Arguments, EmitMir};
 use crate::kani_middle::reachability::CallGraph;
 use crate::kani_middle::transform::GlobalPass;
 use crate::kani_queries::QueryDb;
 use kani_metadata::ArtifactType;
+use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::config::OutputType;
+use stable_mir::mir::Body;
 use stable_mir::mir::mono::{Instance, MonoItem};
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::BufWriter;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use super::BodyTransformation;
 
+/// The marker of the lines added by the Kani transformations.
+const INJECTED_MARKER: &str = "// injected by Kani";
+
+/// The maximum length of the name of a file with the MIR of a function.
+const MAX_FILE_NAME_LEN: usize = 200;
+
 /// Dump all MIR bodies.
 #[derive(Debug)]
 pub struct DumpMirPass {
-    enabled: bool,
+    /// Whether the rustc option `--emit mir` was given.
+    emit_unit: bool,
+    /// The kind of MIR written for each function, and the directory where it's written.
+    emit_functions: Option<(EmitMir, PathBuf)>,
 }
 
 impl DumpMirPass {
-    pub fn new(tcx: TyCtxt) -> Self {
-        Self { enabled: tcx.sess.opts.output_types.contains_key(&OutputType::Mir) }
-    }
-}
-
-impl GlobalPass for DumpMirPass {
-    fn is_enabled(&self, _query_db: &QueryDb) -> bool {
-        self.enabled
+    pub fn new(tcx: TyCtxt, args: &Arguments) -> Self {
+        Self {
+            emit_unit: tcx.sess.opts.output_types.contains_key(&OutputType::Mir),
+            emit_functions: args.emit_mir.zip(args.emit_mir_dir.clone()),
+        }
     }
 
-    fn transform(
-        &mut self,
+    /// Dump all the bodies of the codegen unit to a single file.
+    fn dump_unit(
+        &self,
         tcx: TyCtxt,
-        _call_graph: &CallGraph,
         starting_items: &[MonoItem],
-        instances: Vec<Instance>,
+        instances: &[Instance],
         transformer: &mut BodyTransformation,
     ) {
         // Create output buffer.
@@ -66,4 +84,145 @@ fn transform(
             let _ = transformer.body(tcx, *instance).dump(&mut writer, &instance.name());
         }
     }
+
+    /// Dump the body of each function to its own file, in a directory named after the harness.
+    fn dump_functions(
+        &self,
+        tcx: TyCtxt,
+        kind: EmitMir,
+        dir: &Path,
+        starting_items: &[MonoItem],
+        instances: &[Instance],
+        transformer: &mut BodyTransformation,
+    ) {
+        let harness = match starting_items {
+            [MonoItem::Fn(harness)] => harness.name(),
+            _ => tcx.crate_name(LOCAL_CRATE).to_string(),
+        };
+        let harness_dir = dir.join(file_name(&harness));
+        let mut used_names = HashSet::new();
+        let result = std::fs::create_dir_all(&harness_dir).and_then(|_| {
+            for instance in instances {
+                let name = instance.name();
+                let mut file_stem = file_name(&name);
+                if !used_names.insert(file_stem.clone()) {
+                    file_stem = format!("{file_stem}_{}", instance.mangled_name());
+                    used_names.insert(file_stem.clone());
+                }
+                let out_file = File::create(harness_dir.join(file_stem).with_extension("mir"))?;
+                let mut writer = BufWriter::new(out_file);
+                writeln!(writer, "// Item: {name} ({})", instance.mangled_name())?;
+                let original = instance.body().unwrap();
+                match kind {
+                    EmitMir::Original => original.dump(&mut writer, &name)?,
+                    EmitMir::Transformed => {
+                        let transformed = transformer.body(tcx, *instance);
+                        dump_marked(&mut writer, &original, &transformed, &name)?
+                    }
+                }
+            }
+            Ok(())
+        });
+        match result {
+            Ok(_) => tcx.dcx().note(format!(
+                "wrote the {} MIR of harness `{harness}` to `{}`",
+                kind.as_ref(),
+                harness_dir.display()
+            )),
+            Err(err) => {
+                tcx.dcx().err(format!(
+                    "failed to write the MIR of harness `{harness}` to `{}`: {err}",
+                    harness_dir.display()
+                ));
+            }
+        }
+    }
+}
+
+impl GlobalPass for DumpMirPass {
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool {
+        self.emit_unit || self.emit_functions.is_some()
+    }
+
+    fn transform(
+        &mut self,
+        tcx: TyCtxt,
+        _call_graph: &CallGraph,
+        starting_items: &[MonoItem],
+        instances: Vec<Instance>,
+        transformer: &mut BodyTransformation,
+    ) {
+        if self.emit_unit {
+            self.dump_unit(tcx, starting_items, &instances, transformer);
+        }
+        if let Some((kind, dir)) = &self.emit_functions {
+            self.dump_functions(tcx, *kind, dir, starting_items, &instances, transformer);
+        }
+    }
+}
+
+/// A file name derived from the name of an item, which only contains `[a-zA-Z0-9_]`.
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .take(MAX_FILE_NAME_LEN)
+        .collect()
+}
+
+/// Dump the transformed body, and mark the lines that don't have a counterpart in the original
+/// body, e.g., the assumptions, assertions and snapshots added by the Kani transformations.
+///
+/// Lines are compared without their successors, since the transformations split the blocks of
+/// the original body, which changes the targets of the original terminators.
+fn dump_marked<W: Write>(
+    writer: &mut W,
+    original: &Body,
+    transformed: &Body,
+    name: &str,
+) -> std::io::Result<()> {
+    let mut original_dump = vec![];
+    original.dump(&mut original_dump, name)?;
+    let original_dump = String::from_utf8_lossy(&original_dump);
+    let mut original_lines: HashMap<&str, usize> = HashMap::new();
+    for line in original_dump.lines() {
+        *original_lines.entry(without_successors(line)).or_default() += 1;
+    }
+
+    let mut transformed_dump = vec![];
+    transformed.dump(&mut transformed_dump, name)?;
+    let original_locals = original.locals().len();
+    for line in String::from_utf8_lossy(&transformed_dump).lines() {
+        let trimmed = line.trim_start();
+        let injected = if let Some(local) = declared_local(trimmed) {
+            local >= original_locals
+        } else if trimmed.starts_with("bb") || trimmed == "}" {
+            false
+        } else {
+            match original_lines.get_mut(without_successors(line)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        };
+        if injected {
+            writeln!(writer, "{line} {INJECTED_MARKER}")?;
+        } else {
+            writeln!(writer, "{line}")?;
+        }
+    }
+    Ok(())
+}
+
+/// The line of a statement or terminator without the blocks it jumps to.
+fn without_successors(line: &str) -> &str {
+    line.split(" -> ").next().unwrap()
+}
+
+/// The index of the local declared in the given line, e.g. `let mut _3: u8;`.
+fn declared_local(line: &str) -> Option<usize> {
+    let decl = line.strip_prefix("let ")?;
+    let decl = decl.strip_prefix("mut ").unwrap_or(decl);
+    decl.strip_prefix('_')?.split(':').next()?.parse().ok()
 }
@@ -0,0 +1,162 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Inject the value of the fixture of a group into the harnesses of the group.
+//!
+//! A harness annotated with `#[kani::proof(group = "db")]` declares its argument at the beginning
+//! of its body with a call to `kani::internal::fixture::<T>()`. This pass replaces that call by a
+//! call to the function annotated with `#[kani::fixture(group = "db")]`. I.e.:
+//!
+//! ```ignore
+//! _1 = kani::internal::fixture::<Database>() -> bb1;
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! _1 = db() -> bb1;
+//! ```
+//!
+//! The fixture is called once in each harness, so the values it creates with `kani::any()` are
+//! independent between harnesses.
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::codegen_units::CodegenUnit;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_span::Symbol;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{Body, ConstOperand, Operand, TerminatorKind};
+use stable_mir::ty::{FnDef, GenericArgs, MirConst, RigidTy, Span, TyKind};
+use std::collections::HashMap;
+use tracing::trace;
+
+/// Replace the placeholder of the fixture value in the harnesses of a group.
+#[derive(Debug)]
+pub struct FixturePass {
+    /// The placeholder function, if the `kani` crate is available.
+    fixture: Option<FnDef>,
+    /// The group of each harness of this unit that belongs to one.
+    groups: HashMap<Instance, Symbol>,
+    /// The fixtures of each group in the local crate.
+    fixtures: HashMap<Symbol, Vec<FnDef>>,
+}
+
+impl TransformPass for FixturePass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        self.fixture.is_some() && !self.groups.is_empty()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, mut body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "FixturePass::transform");
+        let Some(group) = self.groups.get(&instance) else { return (false, body) };
+        let placeholder = self.fixture.unwrap();
+        let locals = body.locals().to_vec();
+        let mut changed = false;
+        for block in body.blocks.iter_mut() {
+            let span = block.terminator.span;
+            let TerminatorKind::Call { func, .. } = &mut block.terminator.kind else {
+                continue;
+            };
+            let Ok(func_ty) = func.ty(&locals) else { continue };
+            let kind = func_ty.kind();
+            let Some((def, args)) = kind.fn_def() else { continue };
+            if def != placeholder {
+                continue;
+            }
+            let expected = *args.0[0].expect_ty();
+            let Some(fixture) = self.resolve_fixture(tcx, *group, span) else { continue };
+            let fixture = Instance::resolve(fixture, &GenericArgs(vec![])).unwrap();
+            let output = fixture.ty().kind().fn_sig().unwrap().skip_binder().output();
+            if output != expected {
+                tcx.dcx().span_err(
+                    rustc_internal::internal(tcx, span),
+                    format!(
+                        "the fixture `{}` of group `{group}` returns `{output}`, but the harness \
+                        takes a value of type `{expected}`",
+                        fixture.name()
+                    ),
+                );
+                continue;
+            }
+            let literal = MirConst::try_new_zero_sized(fixture.ty()).unwrap();
+            *func = Operand::Constant(ConstOperand { span, user_ty: None, const_: literal });
+            changed = true;
+        }
+        (changed, body)
+    }
+}
+
+impl FixturePass {
+    pub fn new(tcx: TyCtxt, unit: &CodegenUnit) -> FixturePass {
+        let groups: HashMap<_, _> = unit
+            .harnesses
+            .iter()
+            .filter_map(|harness| {
+                KaniAttributes::for_instance(tcx, *harness)
+                    .fixture_group()
+                    .map(|group| (*harness, group))
+            })
+            .collect();
+        let fixtures = if groups.is_empty() { HashMap::new() } else { collect_fixtures(tcx) };
+        FixturePass { fixture: find_fn_def(tcx, "KaniFixture"), groups, fixtures }
+    }
+
+    /// Find the unique fixture of the given group, or emit an error.
+    fn resolve_fixture(&self, tcx: TyCtxt, group: Symbol, span: Span) -> Option<FnDef> {
+        let span = rustc_internal::internal(tcx, span);
+        match self.fixtures.get(&group).map(Vec::as_slice) {
+            Some([fixture]) => Some(*fixture),
+            None | Some([]) => {
+                tcx.dcx()
+                    .struct_span_err(span, format!("no fixture found for group `{group}`"))
+                    .with_help(format!(
+                        "add `#[kani::fixture(group = \"{group}\")]` to the function that \
+                        creates the value of the group"
+                    ))
+                    .emit();
+                None
+            }
+            Some(fixtures) => {
+                let names: Vec<_> =
+                    fixtures.iter().map(|fixture| format!("`{}`", fixture.name())).collect();
+                tcx.dcx().span_err(
+                    span,
+                    format!(
+                        "found {} fixtures for group `{group}`: {}",
+                        fixtures.len(),
+                        names.join(", ")
+                    ),
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Collect the functions annotated with `#[kani::fixture]` in the local crate, by group.
+fn collect_fixtures(tcx: TyCtxt) -> HashMap<Symbol, Vec<FnDef>> {
+    let mut fixtures: HashMap<Symbol, Vec<FnDef>> = HashMap::new();
+    for item in stable_mir::all_local_items() {
+        let Some(group) = KaniAttributes::for_def_id(tcx, item.def_id()).fixture() else {
+            continue;
+        };
+        if let TyKind::RigidTy(RigidTy::FnDef(def, _)) = item.ty().kind() {
+            fixtures.entry(group).or_default().push(def);
+        }
+    }
+    fixtures
+}
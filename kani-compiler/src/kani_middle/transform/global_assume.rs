@@ -0,0 +1,202 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Re-establish the global assumptions after a contract havocs memory.
+//!
+//! `#[kani::global_assume(expr)]` on a static generates a function that evaluates `expr`. When a
+//! function is replaced by its contract, the targets of its `modifies` clause are havocked with
+//! calls to `kani::internal::write_any` and its variants. This pass assumes every global
+//! assumption of the crate right after each of these calls. I.e.:
+//!
+//! ```ignore
+//! _5 = write_any_slim::<i32>(move _4) -> bb3;
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//!      _5 = write_any_slim::<i32>(move _4) -> bb4;
+//! bb4: _6 = kani_global_assume_COUNTER() -> bb5;
+//! bb5: _7 = kani::assume(move _6) -> bb3;
+//! ```
+//!
+//! Only the global assumptions declared in the local crate are collected.
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::visit::{Location, PlaceContext};
+use stable_mir::mir::{
+    BasicBlockIdx, Body, Local, MirVisitor, Mutability, Operand, Place, ProjectionElem, Rvalue,
+    StatementKind, TerminatorKind,
+};
+use stable_mir::ty::{FnDef, GenericArgs, RigidTy, Span, Ty, TyKind};
+use std::collections::HashSet;
+use tracing::trace;
+
+/// The functions that havoc the `modifies` targets of a contract.
+const HAVOC_FNS: [&str; 4] =
+    ["KaniWriteAny", "KaniWriteAnySlim", "KaniWriteAnySlice", "KaniWriteAnyStr"];
+
+/// Assume the global assumptions after every havoc.
+#[derive(Debug)]
+pub struct GlobalAssumePass {
+    /// The functions that havoc memory.
+    havoc_fns: Vec<FnDef>,
+    /// The `kani::assume` function, if the `kani` crate is available.
+    assume: Option<Instance>,
+    /// The functions generated for the global assumptions of the local crate.
+    assumptions: Vec<Instance>,
+}
+
+impl TransformPass for GlobalAssumePass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        self.assume.is_some() && !self.assumptions.is_empty()
+    }
+
+    fn transform(&mut self, _tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "GlobalAssumePass::transform");
+        let havocs = self.collect_havocs(&body);
+        if havocs.is_empty() {
+            return (false, body);
+        }
+        let assume = self.assume.unwrap();
+        let mut new_body = MutableBody::from(body);
+        for bb in havocs {
+            // Inserting after a terminator keeps the index of its block, so the remaining
+            // indices are still valid.
+            let mut source = SourceInstruction::Terminator { bb };
+            for assumption in &self.assumptions {
+                let span = source.span(new_body.blocks());
+                let holds = new_body.new_local(Ty::bool_ty(), span, Mutability::Not);
+                new_body.insert_call(
+                    assumption,
+                    &mut source,
+                    InsertPosition::After,
+                    vec![],
+                    Place::from(holds),
+                );
+                let unit = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+                new_body.insert_call(
+                    &assume,
+                    &mut source,
+                    InsertPosition::After,
+                    vec![Operand::Move(Place::from(holds))],
+                    Place::from(unit),
+                );
+            }
+        }
+        (true, new_body.into())
+    }
+}
+
+impl GlobalAssumePass {
+    pub fn new(tcx: TyCtxt) -> GlobalAssumePass {
+        let havoc_fns = HAVOC_FNS.iter().filter_map(|name| find_fn_def(tcx, name)).collect();
+        let assume = find_fn_def(tcx, "KaniAssume")
+            .and_then(|def| Instance::resolve(def, &GenericArgs(vec![])).ok());
+        GlobalAssumePass { havoc_fns, assume, assumptions: global_assumptions(tcx) }
+    }
+
+    /// Collect the blocks whose terminator is a call that havocs memory.
+    fn collect_havocs(&self, body: &Body) -> Vec<BasicBlockIdx> {
+        body.blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(bb, block)| {
+                let TerminatorKind::Call { func, target: Some(_), .. } = &block.terminator.kind
+                else {
+                    return None;
+                };
+                let (callee, _) = func.ty(body.locals()).ok()?.kind().fn_def()?;
+                self.havoc_fns.contains(&callee).then_some(bb)
+            })
+            .collect()
+    }
+}
+
+/// Collect the functions generated for `#[kani::global_assume]` in the local crate, and warn
+/// about the ones that may not hold across havocs.
+fn global_assumptions(tcx: TyCtxt) -> Vec<Instance> {
+    stable_mir::all_local_items()
+        .into_iter()
+        .filter_map(|item| {
+            let target = KaniAttributes::for_def_id(tcx, item.def_id()).global_assume()?;
+            let TyKind::RigidTy(RigidTy::FnDef(def, _)) = item.ty().kind() else { return None };
+            let assumption = Instance::resolve(def, &GenericArgs(vec![])).ok()?;
+            check_stable_state(tcx, &assumption, target.as_str());
+            Some(assumption)
+        })
+        .collect()
+}
+
+/// Warn if the expression of a global assumption modifies memory, or reads memory through a raw
+/// pointer that isn't the address of a static, since that memory may be havocked without the
+/// assumption being re-established for it.
+fn check_stable_state(tcx: TyCtxt, assumption: &Instance, target: &str) {
+    let Some(body) = assumption.body() else { return };
+    let statics = body
+        .blocks
+        .iter()
+        .flat_map(|block| &block.statements)
+        .filter_map(|stmt| match &stmt.kind {
+            StatementKind::Assign(place, Rvalue::Use(Operand::Constant(constant)))
+                if place.projection.is_empty() && constant.ty().kind().is_any_ptr() =>
+            {
+                Some(place.local)
+            }
+            _ => None,
+        })
+        .collect();
+    let mut checker = StableStateChecker { body: &body, statics, issues: vec![] };
+    checker.visit_body(&body);
+    for (span, issue) in checker.issues {
+        tcx.dcx()
+            .struct_span_warn(
+                rustc_internal::internal(tcx, span),
+                format!("the global assumption of `{target}` {issue}"),
+            )
+            .with_note(
+                "global assumptions must be pure and only read static variables, since they are \
+                only re-established after a contract havocs memory",
+            )
+            .emit();
+    }
+}
+
+struct StableStateChecker<'a> {
+    body: &'a Body,
+    /// The locals that hold the address of a static.
+    statics: HashSet<Local>,
+    issues: Vec<(Span, &'static str)>,
+}
+
+impl MirVisitor for StableStateChecker<'_> {
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        if place.projection.first() == Some(&ProjectionElem::Deref) {
+            if ptx.is_mutating() {
+                self.issues.push((location.span(), "modifies memory"));
+            } else if !self.statics.contains(&place.local)
+                && self.body.locals()[place.local].ty.kind().is_raw_ptr()
+            {
+                self.issues.push((location.span(), "reads memory through a raw pointer"));
+            }
+        }
+        self.super_place(place, ptx, location)
+    }
+}
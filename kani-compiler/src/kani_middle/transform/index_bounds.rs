@@ -0,0 +1,156 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement a transformation pass that adds a bounds check to every call to `Index::index` and
+//! `IndexMut::index_mut` on a `Vec` or a slice with a `usize` index in the local crate.
+//!
+//! Indexing a `Vec` is implemented by the standard library, so the bounds check that would fail is
+//! the one inside `core::slice::index`, and it is shared by every indexing operation of the crate.
+//! Instead, we add an `Assert` terminator with a `BoundsCheck` message right before the call,
+//! which gets a property of its own at the location of the indexing expression. Chained indexing,
+//! such as `m[i][j]`, results in one call and one property per indexing operation.
+//!
+//! Indexing arrays and slices directly is lowered by rustc to a built-in bounds check, so there's
+//! no call to instrument in that case.
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::lang_items::LangItem;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_span::sym;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    AssertMessage, BasicBlockIdx, BinOp, Body, Operand, Place, ProjectionElem, Rvalue, Terminator,
+    TerminatorKind, UnwindAction,
+};
+use stable_mir::ty::{RigidTy, Ty, TyKind, UintTy};
+use tracing::trace;
+
+/// Add a bounds check before the calls that index a `Vec` or a slice.
+#[derive(Debug)]
+pub struct IndexBoundsPass;
+
+impl TransformPass for IndexBoundsPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if !instance.def.krate().is_local {
+            return (false, body);
+        }
+        let accesses = collect_accesses(tcx, &body);
+        if accesses.is_empty() {
+            return (false, body);
+        }
+        let mut new_body = MutableBody::from(body);
+        for access in accesses {
+            let mut source = SourceInstruction::Terminator { bb: access.bb };
+            let span = source.span(new_body.blocks());
+            let len = new_body.insert_assignment(access.len, &mut source, InsertPosition::Before);
+            let cond = new_body.insert_binary_op(
+                BinOp::Lt,
+                access.index.clone(),
+                Operand::Copy(Place::from(len)),
+                &mut source,
+                InsertPosition::Before,
+            );
+            let kind = TerminatorKind::Assert {
+                cond: Operand::Move(Place::from(cond)),
+                expected: true,
+                msg: AssertMessage::BoundsCheck {
+                    len: Operand::Copy(Place::from(len)),
+                    index: access.index,
+                },
+                target: new_body.blocks().len(),
+                unwind: UnwindAction::Terminate,
+            };
+            new_body
+                .insert_terminator(&mut source, InsertPosition::Before, Terminator { kind, span });
+        }
+        (true, new_body.into())
+    }
+}
+
+/// A call that indexes a `Vec` or a slice.
+#[derive(Debug)]
+struct IndexAccess {
+    /// The block whose terminator is the call.
+    bb: BasicBlockIdx,
+    /// The length of the collection being indexed.
+    len: Rvalue,
+    /// The index being accessed.
+    index: Operand,
+}
+
+/// Collect the calls to `Index::index` and `IndexMut::index_mut` with a `usize` index, where the
+/// indexed type is a `Vec` or a slice.
+fn collect_accesses(tcx: TyCtxt, body: &Body) -> Vec<IndexAccess> {
+    body.blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(bb, block)| {
+            let TerminatorKind::Call { func, args, target: Some(_), .. } = &block.terminator.kind
+            else {
+                return None;
+            };
+            let (callee, _) = func.ty(body.locals()).ok()?.kind().fn_def()?;
+            let callee = rustc_internal::internal(tcx, callee.def_id());
+            let trait_def = tcx.trait_of_item(callee)?;
+            if !tcx.is_lang_item(trait_def, LangItem::Index)
+                && !tcx.is_lang_item(trait_def, LangItem::IndexMut)
+            {
+                return None;
+            }
+            let [Operand::Copy(collection) | Operand::Move(collection), index] = args.as_slice()
+            else {
+                return None;
+            };
+            if index.ty(body.locals()).ok()?.kind() != TyKind::RigidTy(RigidTy::Uint(UintTy::Usize))
+            {
+                return None;
+            }
+            let pointee_ty = collection.ty(body.locals()).ok()?.kind().builtin_deref(true)?.ty;
+            let len = collection_len(tcx, collection, pointee_ty)?;
+            let index = match index {
+                Operand::Copy(place) | Operand::Move(place) => Operand::Copy(place.clone()),
+                Operand::Constant(_) => index.clone(),
+            };
+            Some(IndexAccess { bb, len, index })
+        })
+        .collect()
+}
+
+/// Build the rvalue that computes the length of the collection pointed to by `collection`, if the
+/// collection is a `Vec` or a slice.
+///
+/// For a `Vec`, we read its `len` field directly, which avoids adding a call to `Vec::len`.
+fn collection_len(tcx: TyCtxt, collection: &Place, pointee_ty: Ty) -> Option<Rvalue> {
+    let mut place = collection.clone();
+    place.projection.push(ProjectionElem::Deref);
+    match pointee_ty.kind() {
+        TyKind::RigidTy(RigidTy::Slice(_)) => Some(Rvalue::Len(place)),
+        TyKind::RigidTy(RigidTy::Adt(def, _))
+            if tcx.is_diagnostic_item(sym::Vec, rustc_internal::internal(tcx, def.def_id())) =>
+        {
+            let fields = def.variants_iter().next()?.fields();
+            let idx = fields.iter().position(|field| field.name == "len")?;
+            place.projection.push(ProjectionElem::Field(idx, Ty::usize_ty()));
+            Some(Rvalue::Use(Operand::Copy(place)))
+        }
+        _ => None,
+    }
+}
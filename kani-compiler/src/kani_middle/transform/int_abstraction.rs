@@ -0,0 +1,228 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement `--int-abstraction=bits:N`, which restricts the integers of the local crate to `N`
+//! bits for a faster, first-pass verification.
+//!
+//! The layout of the integer types doesn't change, since it's observable by unsafe code and by
+//! the code of other crates. Instead, every time that a value of an integer type wider than `N`
+//! bits is written by the local crate, we assume that it fits in `N` bits, i.e., that it's
+//! preserved by a sign or zero extension from `N` bits:
+//!
+//! ```ignore
+//! _2 = Add(copy _1, const 1_u64);
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! _2 = Add(copy _1, const 1_u64);
+//! _3 = Shl(copy _2, const 48_u32);
+//! _4 = Shr(move _3, const 48_u32);
+//! _5 = Eq(move _4, copy _2);
+//! _6 = kani::assume(move _5);
+//! ```
+//!
+//! This includes the values returned by calls, such as `kani::any()`, so the solver only explores
+//! values that fit in `N` bits. The executions where a value doesn't fit, e.g., because of an
+//! overflow of the reduced width, are pruned. This is an under-approximation, so the number of
+//! bits is recorded in the metadata of each harness and reported with its verification result.
+//!
+//! Constants are concrete, so they're not restricted, and neither are `usize` and `isize` values,
+//! which hold lengths and addresses that often don't fit in `N` bits, e.g. the length of
+//! `vec![0u8; 300]`. Since a harness may still have no execution left, a cover is added at the end
+//! of each harness so the driver can report it.
+use crate::kani_middle::attributes::is_proof_harness;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use kani_metadata::INT_ABSTRACTION_COVER;
+use rustc_middle::ty::TyCtxt;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    BinOp, Body, ConstOperand, Mutability, Operand, Place, Rvalue, StatementKind, TerminatorKind,
+};
+use stable_mir::ty::{GenericArgs, IntTy, MirConst, RigidTy, Ty, TyKind, UintTy};
+use tracing::trace;
+
+/// Assume that the integer values written by the local crate fit in a reduced number of bits.
+#[derive(Debug)]
+pub struct IntAbstractionPass {
+    /// The `kani::assume` function, if the `kani` crate is available.
+    assume: Option<Instance>,
+    /// The `kani::cover` function, if the `kani` crate is available.
+    cover: Option<Instance>,
+    /// The number of bits, if the integers are abstracted.
+    bits: Option<u32>,
+}
+
+impl TransformPass for IntAbstractionPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        self.bits.is_some() && self.assume.is_some() && self.cover.is_some()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if !instance.def.krate().is_local {
+            return (false, body);
+        }
+        let bits = self.bits.unwrap();
+        let writes = collect_writes(&body, bits);
+        let is_harness = is_proof_harness(tcx, instance);
+        if writes.is_empty() && !is_harness {
+            return (false, body);
+        }
+        let assume = self.assume.unwrap();
+        let mut new_body = MutableBody::from(body);
+        // Covering the returns first doesn't move the statements and the calls that write integers.
+        if is_harness {
+            self.cover_returns(&mut new_body);
+        }
+        // The writes are sorted so that inserting after one of them doesn't move the ones that
+        // are still to be instrumented.
+        for write in writes {
+            let mut source = write.source;
+            let span = source.span(new_body.blocks());
+            let shift = new_body.new_uint_operand((write.width - bits).into(), UintTy::U32, span);
+            let shifted = new_body.insert_binary_op(
+                BinOp::Shl,
+                Operand::Copy(write.place.clone()),
+                shift.clone(),
+                &mut source,
+                InsertPosition::After,
+            );
+            let extended = new_body.insert_binary_op(
+                BinOp::Shr,
+                Operand::Move(Place::from(shifted)),
+                shift,
+                &mut source,
+                InsertPosition::After,
+            );
+            let fits = new_body.insert_binary_op(
+                BinOp::Eq,
+                Operand::Move(Place::from(extended)),
+                Operand::Copy(write.place),
+                &mut source,
+                InsertPosition::After,
+            );
+            let unit = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            new_body.insert_call(
+                &assume,
+                &mut source,
+                InsertPosition::After,
+                vec![Operand::Move(Place::from(fits))],
+                Place::from(unit),
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+impl IntAbstractionPass {
+    pub fn new(tcx: TyCtxt, queries: &QueryDb) -> IntAbstractionPass {
+        let resolve = |name| {
+            find_fn_def(tcx, name).and_then(|def| Instance::resolve(def, &GenericArgs(vec![])).ok())
+        };
+        IntAbstractionPass {
+            assume: resolve("KaniAssume"),
+            cover: resolve("KaniCover"),
+            bits: queries.args().int_abstraction_bits,
+        }
+    }
+
+    /// Add a cover before every return of a harness, which is unsatisfiable if the assumptions
+    /// prune every execution of the harness.
+    fn cover_returns(&self, body: &mut MutableBody) {
+        let cover = self.cover.unwrap();
+        let returns: Vec<_> = body
+            .blocks()
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| matches!(block.terminator.kind, TerminatorKind::Return))
+            .map(|(bb, _)| bb)
+            .collect();
+        for bb in returns {
+            let mut source = SourceInstruction::Terminator { bb };
+            let span = source.span(body.blocks());
+            let cond = Operand::Constant(ConstOperand {
+                span,
+                user_ty: None,
+                const_: MirConst::from_bool(true),
+            });
+            let msg = body.new_str_operand(INT_ABSTRACTION_COVER, span);
+            let unit = body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            body.insert_call(
+                &cover,
+                &mut source,
+                InsertPosition::Before,
+                vec![cond, msg],
+                Place::from(unit),
+            );
+        }
+    }
+}
+
+/// A write of an integer value that is wider than the abstraction.
+#[derive(Debug)]
+struct IntWrite {
+    /// The statement or the call that writes the value.
+    source: SourceInstruction,
+    /// The place where the value is written.
+    place: Place,
+    /// The width of the integer type in bits.
+    width: u32,
+}
+
+/// Collect the assignments and the calls that write an integer that is wider than `bits`, except
+/// for the assignments of constants and the writes of `usize` and `isize` values.
+///
+/// In each block, the call is collected before the statements, and the statements are collected
+/// in reverse order.
+fn collect_writes(body: &Body, bits: u32) -> Vec<IntWrite> {
+    let width = |place: &Place| {
+        let width = int_width(place.ty(body.locals()).ok()?)?;
+        (width > bits).then_some(width)
+    };
+    let mut writes = vec![];
+    for (bb, block) in body.blocks.iter().enumerate() {
+        if let TerminatorKind::Call { destination, target: Some(_), .. } = &block.terminator.kind
+            && let Some(width) = width(destination)
+        {
+            let source = SourceInstruction::Terminator { bb };
+            writes.push(IntWrite { source, place: destination.clone(), width });
+        }
+        for (idx, stmt) in block.statements.iter().enumerate().rev() {
+            if let StatementKind::Assign(place, rvalue) = &stmt.kind
+                && !matches!(rvalue, Rvalue::Use(Operand::Constant(_)))
+                && let Some(width) = width(place)
+            {
+                let source = SourceInstruction::Statement { idx, bb };
+                writes.push(IntWrite { source, place: place.clone(), width });
+            }
+        }
+    }
+    writes
+}
+
+/// The width in bits of an integer type, other than `usize` and `isize`.
+fn int_width(ty: Ty) -> Option<u32> {
+    let bytes = match ty.kind() {
+        TyKind::RigidTy(RigidTy::Int(IntTy::Isize) | RigidTy::Uint(UintTy::Usize)) => return None,
+        TyKind::RigidTy(RigidTy::Int(int_ty)) => int_ty.num_bytes(),
+        TyKind::RigidTy(RigidTy::Uint(uint_ty)) => uint_ty.num_bytes(),
+        _ => return None,
+    };
+    Some(bytes as u32 * 8)
+}
@@ -0,0 +1,192 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement a transformation pass that adds a cover property to every arm of the `match`
+//! expressions of the local crate.
+//!
+//! The result of each cover property tells whether the arm is reachable by the harness. This is
+//! mostly useful for defensive code, where a catch-all arm that is `UNSATISFIABLE` is dead code.
+//!
+//! We instrument the targets of the `SwitchInt` terminators whose span doesn't come from a
+//! desugaring or a macro expansion, such as the ones generated for `for` loops and the `?`
+//! operator. Switches on a boolean are skipped, since they come from `if` expressions.
+//!
+//! Note that a `match` with nested patterns or guards may be lowered to more than one switch, in
+//! which case we add a cover property for each target of each one of them. Also, the catch-all arm
+//! of a `match` on an enum is labeled with the variants it covers, since the switch lists every
+//! variant explicitly.
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    BasicBlockIdx, Body, ConstOperand, Operand, Rvalue, StatementKind, TerminatorKind,
+};
+use stable_mir::ty::{MirConst, RigidTy, Span, TyKind};
+use std::collections::HashSet;
+use tracing::trace;
+
+/// Instrument the arms of `match` expressions with cover properties.
+#[derive(Debug)]
+pub struct MatchArmCoverPass {
+    pub check_type: CheckType,
+}
+
+impl TransformPass for MatchArmCoverPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().cover_match_arms
+    }
+
+    /// Add a cover property at the beginning of the target of every arm.
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if !instance.def.krate().is_local {
+            return (false, body);
+        }
+        let arms = collect_arms(tcx, &body);
+        if arms.is_empty() {
+            return (false, body);
+        }
+        let mut new_body = MutableBody::from(body);
+        for arm in arms {
+            let mut source = if new_body.blocks()[arm.target].statements.is_empty() {
+                SourceInstruction::Terminator { bb: arm.target }
+            } else {
+                SourceInstruction::Statement { idx: 0, bb: arm.target }
+            };
+            let span = source.span(new_body.blocks());
+            let cond = new_body.insert_assignment(
+                Rvalue::Use(true_operand(span)),
+                &mut source,
+                InsertPosition::Before,
+            );
+            let msg = format!("match arm `{}` of `{}`", arm.label, arm.scrutinee);
+            new_body.insert_check(
+                tcx,
+                &self.check_type,
+                &mut source,
+                InsertPosition::Before,
+                cond,
+                &msg,
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+/// An arm of a `match` expression, i.e., a target of a `SwitchInt` terminator.
+#[derive(Debug)]
+struct MatchArm {
+    target: BasicBlockIdx,
+    /// The values that lead to this arm, e.g. `Some` or `1 | 2`, or `_` for the catch-all.
+    label: String,
+    /// The type of the value being matched.
+    scrutinee: String,
+}
+
+/// Collect the arms of the `match` expressions of the given body.
+///
+/// Targets that are reached by more than one switch are only instrumented once, with the label of
+/// the first switch.
+fn collect_arms(tcx: TyCtxt, body: &Body) -> Vec<MatchArm> {
+    let mut arms = vec![];
+    let mut visited = HashSet::new();
+    for bb in &body.blocks {
+        let TerminatorKind::SwitchInt { discr, targets } = &bb.terminator.kind else { continue };
+        if is_from_expansion(tcx, bb.terminator.span) {
+            continue;
+        }
+        let Ok(discr_ty) = discr.ty(body.locals()) else { continue };
+        if discr_ty.kind().is_bool() {
+            continue;
+        }
+        // Find the enum whose discriminant is being matched, if any.
+        let adt = match discr {
+            Operand::Copy(place) | Operand::Move(place) => Some(place),
+            Operand::Constant(_) => None,
+        }
+        .and_then(|place| {
+            bb.statements.iter().rev().find_map(|stmt| match &stmt.kind {
+                StatementKind::Assign(lhs, Rvalue::Discriminant(matched)) if lhs == place => {
+                    match matched.ty(body.locals()).ok()?.kind() {
+                        TyKind::RigidTy(RigidTy::Adt(def, _)) => Some(def),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+        });
+        let scrutinee = adt.map_or_else(|| discr_ty.to_string(), |def| def.trimmed_name());
+        let label = |value: u128| match adt {
+            Some(def) => {
+                let internal_def = rustc_internal::internal(tcx, def);
+                internal_def
+                    .discriminants(tcx)
+                    .find(|(_, discr)| discr.val == value)
+                    .map(|(idx, _)| internal_def.variant(idx).name.to_string())
+                    .unwrap_or_else(|| value.to_string())
+            }
+            None => match discr_ty.kind() {
+                TyKind::RigidTy(RigidTy::Int(int_ty)) => {
+                    let shift = 128 - 8 * int_ty.num_bytes() as u32;
+                    (((value << shift) as i128) >> shift).to_string()
+                }
+                TyKind::RigidTy(RigidTy::Char) => {
+                    char::from_u32(value as u32).map_or(value.to_string(), |c| format!("{c:?}"))
+                }
+                _ => value.to_string(),
+            },
+        };
+
+        let mut switch_arms: Vec<(BasicBlockIdx, Vec<String>)> = vec![];
+        let branches = targets.branches().map(|(value, target)| (label(value), target));
+        for (label, target) in branches.chain([("_".to_string(), targets.otherwise())]) {
+            // Skip the `otherwise` target of exhaustive matches, which is unreachable by
+            // construction.
+            let target_bb = &body.blocks[target];
+            if target_bb.statements.is_empty()
+                && matches!(target_bb.terminator.kind, TerminatorKind::Unreachable)
+            {
+                continue;
+            }
+            match switch_arms.iter_mut().find(|(arm_target, _)| *arm_target == target) {
+                Some((_, labels)) => labels.push(label),
+                None => switch_arms.push((target, vec![label])),
+            }
+        }
+        for (target, labels) in switch_arms {
+            if visited.insert(target) {
+                arms.push(MatchArm {
+                    target,
+                    label: labels.join(" | "),
+                    scrutinee: scrutinee.clone(),
+                });
+            }
+        }
+    }
+    arms
+}
+
+/// Whether the span comes from a desugaring or a macro expansion.
+fn is_from_expansion(tcx: TyCtxt, span: Span) -> bool {
+    rustc_internal::internal(tcx, span).from_expansion()
+}
+
+fn true_operand(span: Span) -> Operand {
+    Operand::Constant(ConstOperand { span, user_ty: None, const_: MirConst::from_bool(true) })
+}
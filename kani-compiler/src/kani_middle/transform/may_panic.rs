@@ -0,0 +1,172 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement a transformation pass that allows the functions annotated with `#[kani::may_panic]`
+//! to panic in a `#[kani::panic_free]` harness.
+//!
+//! A `panic_free` harness checks that the target of the harness doesn't panic for any input that
+//! satisfies its preconditions. Some functions document that they panic even when their
+//! preconditions hold, and annotating them with `#[kani::may_panic]` excludes the executions that
+//! reach one of their panics from the verification:
+//! - Each call to a panic entry point in the body of the annotated function is replaced by
+//!   `kani::assume(false)`.
+//! - Each `Assert` terminator in the body, such as the ones that check for arithmetic overflow or
+//!   out of bounds indexing, and each call to `kani::assert`, which the `assert!` macros expand to,
+//!   is replaced by an assumption of its condition.
+//!
+//! Panics raised by the functions that the annotated function calls, such as `Option::unwrap`,
+//! are not affected.
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::codegen_units::CodegenUnit;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use rustc_span::{Symbol, sym};
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{
+    BasicBlockIdx, Body, ConstOperand, Mutability, Operand, Place, Rvalue, Terminator,
+    TerminatorKind, UnOp,
+};
+use stable_mir::ty::{GenericArgs, MirConst, Ty};
+use tracing::trace;
+
+/// Turn the panics of the functions annotated with `#[kani::may_panic]` into assumptions.
+#[derive(Debug)]
+pub struct MayPanicPass {
+    /// The `kani::assume` function, if the `kani` crate is available.
+    assume: Option<Instance>,
+    /// Whether the harness has the `#[kani::panic_free]` attribute.
+    panic_free: bool,
+}
+
+impl TransformPass for MayPanicPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        self.panic_free && self.assume.is_some()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if !KaniAttributes::for_instance(tcx, instance).may_panic() {
+            return (false, body);
+        }
+        let panics = collect_panics(tcx, &body);
+        if panics.is_empty() {
+            return (false, body);
+        }
+        let assume = self.assume.unwrap();
+        let mut new_body = MutableBody::from(body);
+        for (bb, panic) in panics {
+            let span = new_body.blocks()[bb].terminator.span;
+            let (cond, kind) = match panic {
+                Panic::Call => {
+                    let cond = Operand::Constant(ConstOperand {
+                        span,
+                        user_ty: None,
+                        const_: MirConst::from_bool(false),
+                    });
+                    (cond, TerminatorKind::Unreachable)
+                }
+                Panic::Assert { cond, expected, target } => {
+                    let mut source = SourceInstruction::Terminator { bb };
+                    let cond = if expected {
+                        cond
+                    } else {
+                        let negated = new_body.insert_assignment(
+                            Rvalue::UnaryOp(UnOp::Not, cond),
+                            &mut source,
+                            InsertPosition::Before,
+                        );
+                        Operand::Move(Place::from(negated))
+                    };
+                    (cond, TerminatorKind::Goto { target })
+                }
+            };
+            let mut source = SourceInstruction::Terminator { bb };
+            new_body.replace_terminator(&source, Terminator { kind, span });
+            let unit = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            new_body.insert_call(
+                &assume,
+                &mut source,
+                InsertPosition::Before,
+                vec![cond],
+                Place::from(unit),
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+impl MayPanicPass {
+    pub fn new(tcx: TyCtxt, unit: &CodegenUnit) -> MayPanicPass {
+        let panic_free = unit
+            .harnesses
+            .first()
+            .is_some_and(|harness| KaniAttributes::for_instance(tcx, *harness).is_panic_free());
+        let assume = find_fn_def(tcx, "KaniAssume")
+            .and_then(|def| Instance::resolve(def, &GenericArgs(vec![])).ok());
+        MayPanicPass { assume, panic_free }
+    }
+}
+
+/// A terminator that may panic.
+#[derive(Debug)]
+pub(super) enum Panic {
+    /// A call to a function that panics.
+    Call,
+    /// An assertion that panics if its condition is not the expected value, followed by `target`.
+    Assert { cond: Operand, expected: bool, target: BasicBlockIdx },
+}
+
+/// Collect the blocks whose terminator may panic.
+pub(super) fn collect_panics(tcx: TyCtxt, body: &Body) -> Vec<(BasicBlockIdx, Panic)> {
+    body.blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(bb, block)| match &block.terminator.kind {
+            TerminatorKind::Call { func, args, target, .. } => {
+                let (callee, _) = func.ty(body.locals()).ok()?.kind().fn_def()?;
+                let callee = rustc_internal::internal(tcx, callee.def_id());
+                if tcx.is_diagnostic_item(Symbol::intern("KaniAssert"), callee) {
+                    // The `assert!` macros of the Kani library call `kani::assert`.
+                    let cond = args[0].clone();
+                    Some((bb, Panic::Assert { cond, expected: true, target: (*target)? }))
+                } else {
+                    is_panic_fn(tcx, callee).then_some((bb, Panic::Call))
+                }
+            }
+            TerminatorKind::Assert { cond, expected, target, .. } => Some((bb, Panic::Assert {
+                cond: cond.clone(),
+                expected: *expected,
+                target: *target,
+            })),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether the function is one of the entry points of a panic, which are the functions that the
+/// `panic!` and `unreachable!` macros expand to.
+fn is_panic_fn(tcx: TyCtxt, def_id: DefId) -> bool {
+    Some(def_id) == tcx.lang_items().panic_fn()
+        || tcx.has_attr(def_id, sym::rustc_const_panic_str)
+        || Some(def_id) == tcx.lang_items().panic_fmt()
+        || Some(def_id) == tcx.lang_items().begin_panic_fn()
+        || tcx.is_diagnostic_item(Symbol::intern("KaniPanic"), def_id)
+        || (tcx.crate_name(def_id.krate) == sym::core
+            && tcx.item_name(def_id) == Symbol::intern("assert_failed"))
+}
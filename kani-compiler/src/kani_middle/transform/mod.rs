@@ -18,13 +18,26 @@
 //! case is added.
 use crate::kani_middle::codegen_units::CodegenUnit;
 use crate::kani_middle::reachability::CallGraph;
+use crate::kani_middle::transform::assume_ok::AssumeOkPass;
+use crate::kani_middle::transform::assume_unchanged::AssumeUnchangedPass;
 use crate::kani_middle::transform::body::CheckType;
+use crate::kani_middle::transform::check_pin::PinCheckPass;
 use crate::kani_middle::transform::check_uninit::{DelayedUbPass, UninitPass};
 use crate::kani_middle::transform::check_values::ValidValuePass;
 use crate::kani_middle::transform::contracts::{AnyModifiesPass, FunctionWithContractPass};
+use crate::kani_middle::transform::fixtures::FixturePass;
+use crate::kani_middle::transform::global_assume::GlobalAssumePass;
+use crate::kani_middle::transform::index_bounds::IndexBoundsPass;
+use crate::kani_middle::transform::int_abstraction::IntAbstractionPass;
 use crate::kani_middle::transform::kani_intrinsics::IntrinsicGeneratorPass;
 use crate::kani_middle::transform::loop_contracts::LoopContractPass;
-use crate::kani_middle::transform::stubs::{ExternFnStubPass, FnStubPass};
+use crate::kani_middle::transform::match_arms::MatchArmCoverPass;
+use crate::kani_middle::transform::may_panic::MayPanicPass;
+use crate::kani_middle::transform::poll_after_ready::PollAfterReadyPass;
+use crate::kani_middle::transform::prune::PrunePass;
+use crate::kani_middle::transform::safety_requires::SafetyRequiresPass;
+use crate::kani_middle::transform::slice_patterns::SlicePatternPass;
+use crate::kani_middle::transform::stubs::{DropStubPass, ExternFnStubPass, FnStubPass};
 use crate::kani_queries::QueryDb;
 use dump_mir_pass::DumpMirPass;
 use rustc_middle::ty::TyCtxt;
@@ -32,17 +45,32 @@
 use stable_mir::mir::mono::{Instance, MonoItem};
 use std::collections::HashMap;
 use std::fmt::Debug;
+use tracing::debug_span;
 
+pub use assume_ok::assumed_ok_fns;
 pub use internal_mir::RustcInternalMir;
 
+mod assume_ok;
+mod assume_unchanged;
 pub(crate) mod body;
+mod check_pin;
 mod check_uninit;
 mod check_values;
 mod contracts;
 mod dump_mir_pass;
+mod fixtures;
+mod global_assume;
+mod index_bounds;
+mod int_abstraction;
 mod internal_mir;
 mod kani_intrinsics;
 mod loop_contracts;
+mod match_arms;
+mod may_panic;
+mod poll_after_ready;
+mod prune;
+mod safety_requires;
+mod slice_patterns;
 mod stubs;
 
 /// Object used to retrieve a transformed instance body.
@@ -71,10 +99,17 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt, unit: &CodegenUnit) -> Self {
         let check_type = CheckType::new_assert_assume(tcx);
         transformer.add_pass(queries, FnStubPass::new(&unit.stubs));
         transformer.add_pass(queries, ExternFnStubPass::new(&unit.stubs));
+        transformer.add_pass(queries, DropStubPass::new(&unit.drop_stubs));
+        transformer.add_pass(queries, PrunePass::new(tcx, &unit));
+        transformer.add_pass(queries, AssumeOkPass::new(tcx));
         transformer.add_pass(queries, FunctionWithContractPass::new(tcx, &unit));
         // This has to come after the contract pass since we want this to only replace the closure
         // body that is relevant for this harness.
         transformer.add_pass(queries, AnyModifiesPass::new(tcx, &unit));
+        // This has to come after `AnyModifiesPass`, which replaces the calls that havoc memory.
+        transformer.add_pass(queries, GlobalAssumePass::new(tcx));
+        transformer.add_pass(queries, AssumeUnchangedPass::new(tcx, &unit));
+        transformer.add_pass(queries, FixturePass::new(tcx, &unit));
         transformer.add_pass(queries, ValidValuePass { check_type: check_type.clone() });
         // Putting `UninitPass` after `ValidValuePass` makes sure that the code generated by
         // `UninitPass` does not get unnecessarily instrumented by valid value checks. However, it
@@ -85,13 +120,26 @@ pub fn new(queries: &QueryDb, tcx: TyCtxt, unit: &CodegenUnit) -> Self {
             // Since this uses demonic non-determinism under the hood, should not assume the assertion.
             check_type: CheckType::new_assert(tcx),
             mem_init_fn_cache: HashMap::new(),
+            assume_init_cache: None,
         });
+        // Putting `PinCheckPass` after `UninitPass` makes sure that the calls it adds to track the
+        // pinned location are not instrumented by memory initialization checks. The tracked
+        // location is chosen non-deterministically, so the check should not be assumed either.
+        transformer.add_pass(queries, PinCheckPass::new(tcx, CheckType::new_assert(tcx)));
         transformer.add_pass(queries, IntrinsicGeneratorPass {
             check_type,
             mem_init_fn_cache: HashMap::new(),
             arguments: queries.args().clone(),
         });
+        transformer.add_pass(queries, IndexBoundsPass);
+        transformer.add_pass(queries, SlicePatternPass::new(tcx));
+        transformer.add_pass(queries, SafetyRequiresPass::new(tcx));
+        transformer.add_pass(queries, IntAbstractionPass::new(tcx, queries));
+        // This has to come after the passes that add `Assert` terminators for panics.
+        transformer.add_pass(queries, MayPanicPass::new(tcx, &unit));
+        transformer.add_pass(queries, PollAfterReadyPass::new(tcx, &unit));
         transformer.add_pass(queries, LoopContractPass::new(tcx, &unit));
+        transformer.add_pass(queries, MatchArmCoverPass { check_type: CheckType::new_cover(tcx) });
         transformer
     }
 
@@ -105,6 +153,7 @@ pub fn body(&mut self, tcx: TyCtxt, instance: Instance) -> Body {
             Some(TransformationResult::Modified(body)) => body.clone(),
             Some(TransformationResult::NotModified) => instance.body().unwrap(),
             None => {
+                let _guard = debug_span!("transform_body", function = instance.name()).entered();
                 let mut body = instance.body().unwrap();
                 let mut modified = false;
                 for pass in self.stub_passes.iter_mut().chain(self.inst_passes.iter_mut()) {
@@ -193,7 +242,7 @@ impl GlobalPasses {
     pub fn new(queries: &QueryDb, tcx: TyCtxt) -> Self {
         let mut global_passes = GlobalPasses { global_passes: vec![] };
         global_passes.add_global_pass(queries, DelayedUbPass::new(CheckType::new_assert(tcx)));
-        global_passes.add_global_pass(queries, DumpMirPass::new(tcx));
+        global_passes.add_global_pass(queries, DumpMirPass::new(tcx, queries.args()));
         global_passes
     }
 
@@ -212,6 +261,7 @@ pub fn run_global_passes(
         instances: Vec<Instance>,
         call_graph: CallGraph,
     ) {
+        let _guard = debug_span!("run_global_passes", instances = instances.len()).entered();
         for global_pass in self.global_passes.iter_mut() {
             global_pass.transform(tcx, &call_graph, starting_items, instances.clone(), transformer);
         }
@@ -0,0 +1,126 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement a transformation pass that allows the `poll` implementations of the local crate to
+//! panic when a `#[kani::proof_future]` harness polls a future after it completed.
+//!
+//! The contract of `Future` allows `poll` to panic when the future is polled again after it
+//! returned `Ready`, which is a common way to report this misuse. The harness polls the future
+//! once more after it completes, and before each panic in the body of a `Future::poll`
+//! implementation, we call `kani::internal::panic_after_ready` with whether the panic is about to
+//! happen. This prunes the execution if the future is being polled after it completed, and has no
+//! effect otherwise, so the other panics of `poll` are still reported.
+//!
+//! Like `#[kani::may_panic]`, this covers the panic entry points and the `Assert` terminators in
+//! the body of `poll`, but not the panics raised by the functions that it calls.
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::codegen_units::CodegenUnit;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::may_panic::{Panic, collect_panics};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_hir::lang_items::LangItem;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{Body, ConstOperand, Mutability, Operand, Place, Rvalue, UnOp};
+use stable_mir::ty::{GenericArgs, MirConst, Ty};
+use tracing::trace;
+
+/// Prune the panics of `Future::poll` when a future is polled after it completed.
+#[derive(Debug)]
+pub struct PollAfterReadyPass {
+    /// The `kani::internal::panic_after_ready` function, if the `kani` crate is available.
+    panic_after_ready: Option<Instance>,
+    /// Whether one of the harnesses has the `#[kani::proof_future]` attribute.
+    proof_future: bool,
+}
+
+impl TransformPass for PollAfterReadyPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        self.proof_future && self.panic_after_ready.is_some()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if !instance.def.krate().is_local || !is_future_poll(tcx, instance) {
+            return (false, body);
+        }
+        let panics = collect_panics(tcx, &body);
+        if panics.is_empty() {
+            return (false, body);
+        }
+        let panic_after_ready = self.panic_after_ready.unwrap();
+        let mut new_body = MutableBody::from(body);
+        for (bb, panic) in panics {
+            let mut source = SourceInstruction::Terminator { bb };
+            let span = source.span(new_body.blocks());
+            let panics = match panic {
+                Panic::Call => Operand::Constant(ConstOperand {
+                    span,
+                    user_ty: None,
+                    const_: MirConst::from_bool(true),
+                }),
+                Panic::Assert { cond, expected, .. } => {
+                    // The condition is still used by the assertion, so we don't move it.
+                    let cond = match cond {
+                        Operand::Move(place) => Operand::Copy(place),
+                        cond => cond,
+                    };
+                    if expected {
+                        let negated = new_body.insert_assignment(
+                            Rvalue::UnaryOp(UnOp::Not, cond),
+                            &mut source,
+                            InsertPosition::Before,
+                        );
+                        Operand::Move(Place::from(negated))
+                    } else {
+                        cond
+                    }
+                }
+            };
+            let unit = new_body.new_local(Ty::new_tuple(&[]), span, Mutability::Not);
+            new_body.insert_call(
+                &panic_after_ready,
+                &mut source,
+                InsertPosition::Before,
+                vec![panics],
+                Place::from(unit),
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+impl PollAfterReadyPass {
+    pub fn new(tcx: TyCtxt, unit: &CodegenUnit) -> PollAfterReadyPass {
+        let proof_future = unit
+            .harnesses
+            .iter()
+            .any(|harness| KaniAttributes::for_instance(tcx, *harness).is_proof_future());
+        let panic_after_ready = find_fn_def(tcx, "KaniPanicAfterReady")
+            .and_then(|def| Instance::resolve(def, &GenericArgs(vec![])).ok());
+        PollAfterReadyPass { panic_after_ready, proof_future }
+    }
+}
+
+/// Whether the instance is the `poll` method of an implementation of `Future`.
+fn is_future_poll(tcx: TyCtxt, instance: Instance) -> bool {
+    let def_id = rustc_internal::internal(tcx, instance.def.def_id());
+    tcx.impl_of_method(def_id)
+        .and_then(|impl_id| tcx.trait_id_of_impl(impl_id))
+        .is_some_and(|trait_id| tcx.is_lang_item(trait_id, LangItem::Future))
+        && tcx.item_name(def_id).as_str() == "poll"
+}
@@ -0,0 +1,69 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement a transformation pass that replaces the body of the functions pruned by the
+//! `--denylist` and `--allowlist` options by `kani::any()` for their return type.
+//!
+//! See [crate::kani_middle::pruning] for which functions are pruned.
+use crate::kani_middle::codegen_units::CodegenUnit;
+use crate::kani_middle::find_fn_def;
+use crate::kani_middle::pruning::ReachabilityPolicy;
+use crate::kani_middle::transform::body::{InsertPosition, MutableBody, SourceInstruction};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{Body, Place, RETURN_LOCAL, TerminatorKind};
+use stable_mir::ty::{FnDef, GenericArgKind, GenericArgs};
+use tracing::trace;
+
+/// Replace the body of the pruned functions by `kani::any()`.
+#[derive(Debug)]
+pub struct PrunePass {
+    /// The functions to prune.
+    policy: ReachabilityPolicy,
+    /// The function that returns a nondeterministic value, if the `kani` crate is available.
+    any: Option<FnDef>,
+}
+
+impl TransformPass for PrunePass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        !self.policy.is_full() && self.any.is_some()
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if !self.policy.prunes(tcx, instance) {
+            return (false, body);
+        }
+        let args = GenericArgs(vec![GenericArgKind::Type(body.ret_local().ty)]);
+        let any = Instance::resolve(self.any.unwrap(), &args).unwrap();
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Return);
+        let mut source = SourceInstruction::Terminator { bb: 0 };
+        new_body.insert_call(
+            &any,
+            &mut source,
+            InsertPosition::Before,
+            vec![],
+            Place::from(RETURN_LOCAL),
+        );
+        (true, new_body.into())
+    }
+}
+
+impl PrunePass {
+    pub fn new(tcx: TyCtxt, unit: &CodegenUnit) -> PrunePass {
+        PrunePass { policy: unit.policy.clone(), any: find_fn_def(tcx, "KaniAny") }
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement a transformation pass that checks the safety preconditions declared with
+//! `#[kani::safety_requires]` at every call to the annotated `unsafe fn`.
+//!
+//! Unlike the `requires` clause of a contract, which is assumed when the function is checked and
+//! asserted when the function is replaced by its contract, a safety precondition is always checked
+//! by the caller: the safety contract of an `unsafe fn` is an obligation of the code that calls
+//! it, and the harnesses and safe wrappers that call the function must establish it.
+//!
+//! The `#[kani::safety_requires]` attribute generates a function next to the annotated one that
+//! takes the same arguments and evaluates the precondition. Before each call to the annotated
+//! function, we call the generated function with the same arguments and check its result. Each
+//! call site gets a property of its own at the location of the call, so a counterexample points
+//! to the caller that didn't establish the precondition.
+//!
+//! Calls through function pointers are not checked, since the target of the call is not known.
+use crate::kani_middle::attributes::KaniAttributes;
+use crate::kani_middle::stable_fn_def;
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use rustc_smir::rustc_internal;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{BasicBlockIdx, Body, Mutability, Operand, Place, TerminatorKind};
+use stable_mir::ty::Ty;
+use tracing::trace;
+
+/// Check the safety preconditions of the `unsafe fn`s called by a function.
+#[derive(Debug)]
+pub struct SafetyRequiresPass {
+    check_type: CheckType,
+}
+
+impl TransformPass for SafetyRequiresPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let calls = collect_calls(tcx, &body);
+        if calls.is_empty() {
+            return (false, body);
+        }
+        let mut new_body = MutableBody::from(body);
+        for call in calls {
+            let mut source = SourceInstruction::Terminator { bb: call.bb };
+            let span = source.span(new_body.blocks());
+            let holds = new_body.new_local(Ty::bool_ty(), span, Mutability::Not);
+            new_body.insert_call(
+                &call.checker,
+                &mut source,
+                InsertPosition::Before,
+                call.args,
+                Place::from(holds),
+            );
+            let msg = format!("safety precondition of `{}`: {}", call.callee, call.condition);
+            new_body.insert_check(
+                tcx,
+                &self.check_type,
+                &mut source,
+                InsertPosition::Before,
+                holds,
+                &msg,
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+impl SafetyRequiresPass {
+    pub fn new(tcx: TyCtxt) -> SafetyRequiresPass {
+        SafetyRequiresPass { check_type: CheckType::new_assert(tcx) }
+    }
+}
+
+/// A call to an `unsafe fn` with a safety precondition.
+#[derive(Debug)]
+struct UnsafeCall {
+    /// The block whose terminator is the call.
+    bb: BasicBlockIdx,
+    /// The name of the called function.
+    callee: String,
+    /// The safety precondition as written by the user.
+    condition: String,
+    /// The instance of the function that evaluates the precondition for this call.
+    checker: Instance,
+    /// The arguments of the call, which are copied to evaluate the precondition before the call.
+    args: Vec<Operand>,
+}
+
+/// Collect the calls to functions annotated with `#[kani::safety_requires]`.
+fn collect_calls(tcx: TyCtxt, body: &Body) -> Vec<UnsafeCall> {
+    body.blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(bb, block)| {
+            let TerminatorKind::Call { func, args, .. } = &block.terminator.kind else {
+                return None;
+            };
+            let (callee, generic_args) = func.ty(body.locals()).ok()?.kind().fn_def()?;
+            let def_id = rustc_internal::internal(tcx, callee.def_id());
+            let (condition, checker) = KaniAttributes::for_item(tcx, def_id).safety_requires()?;
+            let checker = Instance::resolve(stable_fn_def(tcx, checker)?, &generic_args).ok()?;
+            let args = args
+                .iter()
+                .map(|arg| match arg {
+                    Operand::Move(place) => Operand::Copy(place.clone()),
+                    Operand::Copy(_) | Operand::Constant(_) => arg.clone(),
+                })
+                .collect();
+            Some(UnsafeCall {
+                bb,
+                callee: callee.trimmed_name(),
+                condition: condition.to_string(),
+                checker,
+                args,
+            })
+        })
+        .collect()
+}
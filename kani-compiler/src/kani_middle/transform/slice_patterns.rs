@@ -0,0 +1,180 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Implement a transformation pass that checks the length of the slices matched by slice
+//! patterns in the local crate.
+//!
+//! A slice pattern such as `[first, .., last]` is lowered by rustc to a comparison of the length of
+//! the slice, which selects the arm, followed by projections that read the elements at a constant
+//! offset from the start or from the end of the slice, e.g.:
+//!
+//! ```ignore
+//! _3 = Len((*_1));
+//! _4 = Ge(move _3, const 2_usize);
+//! switchInt(move _4) -> [0: bb2, otherwise: bb1];
+//! bb1: {
+//!     _5 = &(*_1)[0 of 2];
+//!     _6 = &(*_1)[-1 of 2];
+//!     _7 = &(*_1)[1:1];
+//! }
+//! ```
+//!
+//! The projections of a slice are only in bounds if the slice has at least the number of elements
+//! required by the pattern. Before every statement with such a projection, we check that the
+//! length of the slice satisfies this requirement. Each check gets a property of its own at the
+//! location of the binding, so an arm whose pattern requires more elements than the slice can
+//! have, e.g., more than the maximum length of a slice created by
+//! `kani::slice::any_slice_of_array`, is reported as unreachable.
+use crate::kani_middle::transform::body::{
+    CheckType, InsertPosition, MutableBody, SourceInstruction,
+};
+use crate::kani_middle::transform::{TransformPass, TransformationType};
+use crate::kani_queries::QueryDb;
+use rustc_middle::ty::TyCtxt;
+use stable_mir::CrateDef;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::visit::{Location, PlaceContext, PlaceRef};
+use stable_mir::mir::{
+    BasicBlockIdx, BinOp, Body, LocalDecl, MirVisitor, Operand, Place, ProjectionElem, Rvalue,
+    Statement, Terminator,
+};
+use stable_mir::ty::{RigidTy, TyKind, UintTy};
+use tracing::trace;
+
+/// Check the length of the slices matched by slice patterns.
+#[derive(Debug)]
+pub struct SlicePatternPass {
+    check_type: CheckType,
+}
+
+impl TransformPass for SlicePatternPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Instrumentation
+    }
+
+    fn is_enabled(&self, _query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
+
+    fn transform(&mut self, tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        if !instance.def.krate().is_local {
+            return (false, body);
+        }
+        let checks = collect_checks(&body);
+        if checks.is_empty() {
+            return (false, body);
+        }
+        let mut new_body = MutableBody::from(body);
+        // The checks are sorted so that splitting a block before one of them doesn't move the
+        // ones that are still to be instrumented.
+        for check in checks {
+            let mut source = SourceInstruction::Statement { idx: check.idx, bb: check.bb };
+            let span = source.span(new_body.blocks());
+            let len = new_body.insert_assignment(
+                Rvalue::Len(check.slice),
+                &mut source,
+                InsertPosition::Before,
+            );
+            let min_len = new_body.new_uint_operand(check.min_len.into(), UintTy::Usize, span);
+            let holds = new_body.insert_binary_op(
+                BinOp::Ge,
+                Operand::Move(Place::from(len)),
+                min_len,
+                &mut source,
+                InsertPosition::Before,
+            );
+            let msg = format!("slice pattern requires a length of at least {}", check.min_len);
+            new_body.insert_check(
+                tcx,
+                &self.check_type,
+                &mut source,
+                InsertPosition::Before,
+                holds,
+                &msg,
+            );
+        }
+        (true, new_body.into())
+    }
+}
+
+impl SlicePatternPass {
+    pub fn new(tcx: TyCtxt) -> SlicePatternPass {
+        SlicePatternPass { check_type: CheckType::new_assert(tcx) }
+    }
+}
+
+/// A statement that projects a slice with a slice pattern.
+#[derive(Debug)]
+struct LengthCheck {
+    bb: BasicBlockIdx,
+    idx: usize,
+    /// The slice that is projected.
+    slice: Place,
+    /// The number of elements that the slice must have for the projection to be in bounds.
+    min_len: u64,
+}
+
+/// Collect the statements that project a slice with a constant index or a subslice.
+///
+/// The checks of each block are collected in reverse order.
+fn collect_checks(body: &Body) -> Vec<LengthCheck> {
+    let mut checks = vec![];
+    for (bb, block) in body.blocks.iter().enumerate() {
+        let mut visitor = SlicePatternVisitor { locals: body.locals(), bb, idx: 0, checks: vec![] };
+        visitor.visit_basic_block(block);
+        checks.extend(visitor.checks.into_iter().rev());
+    }
+    checks
+}
+
+struct SlicePatternVisitor<'a> {
+    locals: &'a [LocalDecl],
+    bb: BasicBlockIdx,
+    /// The index of the statement being visited.
+    idx: usize,
+    checks: Vec<LengthCheck>,
+}
+
+impl MirVisitor for SlicePatternVisitor<'_> {
+    fn visit_statement(&mut self, stmt: &Statement, location: Location) {
+        self.super_statement(stmt, location);
+        self.idx += 1;
+    }
+
+    fn visit_terminator(&mut self, _term: &Terminator, _location: Location) {
+        // The operands of terminators don't project slices with slice patterns.
+    }
+
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        for (idx, elem) in place.projection.iter().enumerate() {
+            let min_len = match elem {
+                ProjectionElem::ConstantIndex { min_length, .. } => *min_length,
+                ProjectionElem::Subslice { from, to, from_end: true } => from + to,
+                ProjectionElem::Subslice { to, from_end: false, .. } => *to,
+                _ => continue,
+            };
+            let slice = PlaceRef { local: place.local, projection: &place.projection[..idx] };
+            if let Ok(ty) = slice.ty(self.locals)
+                && matches!(ty.kind(), TyKind::RigidTy(RigidTy::Slice(_)))
+                && min_len > 0
+            {
+                let slice =
+                    Place { local: place.local, projection: place.projection[..idx].to_vec() };
+                let covered = self.checks.iter().any(|check| {
+                    check.idx == self.idx && check.slice == slice && check.min_len >= min_len
+                });
+                if !covered {
+                    self.checks.push(LengthCheck { bb: self.bb, idx: self.idx, slice, min_len });
+                }
+            }
+        }
+        self.super_place(place, ptx, location)
+    }
+}
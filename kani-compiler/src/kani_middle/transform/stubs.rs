@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! This module contains code related to the MIR-to-MIR pass that performs the
 //! stubbing of functions and methods.
-use crate::kani_middle::codegen_units::Stubs;
-use crate::kani_middle::stubbing::validate_stub_const;
-use crate::kani_middle::transform::body::{MutMirVisitor, MutableBody};
+use crate::kani_middle::codegen_units::{DropStubs, Stubs};
+use crate::kani_middle::stubbing::{validate_stub_arbitrary, validate_stub_const};
+use crate::kani_middle::transform::body::{
+    InsertPosition, MutMirVisitor, MutableBody, SourceInstruction,
+};
 use crate::kani_middle::transform::{TransformPass, TransformationType};
 use crate::kani_queries::QueryDb;
 use rustc_middle::ty::TyCtxt;
@@ -12,8 +14,11 @@
 use stable_mir::CrateDef;
 use stable_mir::mir::mono::Instance;
 use stable_mir::mir::visit::{Location, MirVisitor};
-use stable_mir::mir::{Body, ConstOperand, LocalDecl, Operand, Terminator, TerminatorKind};
-use stable_mir::ty::{FnDef, MirConst, RigidTy, TyKind};
+use stable_mir::mir::{
+    Body, BorrowKind, ConstOperand, LocalDecl, MutBorrowKind, Operand, Place, ProjectionElem,
+    RETURN_LOCAL, Rvalue, Terminator, TerminatorKind,
+};
+use stable_mir::ty::{FnDef, GenericArgs, MirConst, Region, RegionKind, RigidTy, TyKind};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use tracing::{debug, trace};
@@ -123,6 +128,73 @@ pub fn new(all_stubs: &Stubs) -> ExternFnStubPass {
     }
 }
 
+/// Replace the drop glue of a type by a call to its drop stub.
+///
+/// The new body of `drop_in_place::<T>(ptr: *mut T)` is simply a call to `stub(&mut *ptr)`.
+/// Thus, the fields of `T` are only dropped if the stub drops them explicitly.
+#[derive(Debug)]
+pub struct DropStubPass {
+    /// Map the drop glue instance to its replacement.
+    stubs: HashMap<Instance, Instance>,
+}
+
+impl TransformPass for DropStubPass {
+    fn transformation_type() -> TransformationType
+    where
+        Self: Sized,
+    {
+        TransformationType::Stubbing
+    }
+
+    fn is_enabled(&self, query_db: &QueryDb) -> bool
+    where
+        Self: Sized,
+    {
+        query_db.args().stubbing_enabled && !self.stubs.is_empty()
+    }
+
+    /// Replace the body of the drop glue of a stubbed type.
+    fn transform(&mut self, _tcx: TyCtxt, body: Body, instance: Instance) -> (bool, Body) {
+        trace!(function=?instance.name(), "transform");
+        let Some(stub) = self.stubs.get(&instance) else { return (false, body) };
+        debug!(from=?instance.name(), to=?stub.name(), "DropStubPass::transform");
+        let mut new_body = MutableBody::from(body);
+        new_body.clear_body(TerminatorKind::Return);
+        let mut source = SourceInstruction::Terminator { bb: 0 };
+        // The only argument of the drop glue is a pointer to the value being dropped.
+        let arg = Place { local: 1, projection: vec![ProjectionElem::Deref] };
+        let borrow = Rvalue::Ref(
+            Region { kind: RegionKind::ReErased },
+            BorrowKind::Mut { kind: MutBorrowKind::Default },
+            arg,
+        );
+        let arg_ref = new_body.insert_assignment(borrow, &mut source, InsertPosition::Before);
+        new_body.insert_call(
+            stub,
+            &mut source,
+            InsertPosition::Before,
+            vec![Operand::Move(Place::from(arg_ref))],
+            Place::from(RETURN_LOCAL),
+        );
+        (true, new_body.into())
+    }
+}
+
+impl DropStubPass {
+    /// Build the pass with the drop stubs of this unit.
+    pub fn new(drop_stubs: &DropStubs) -> DropStubPass {
+        let stubs = drop_stubs
+            .iter()
+            .map(|(ty, to)| {
+                let drop_glue = Instance::resolve_drop_in_place(*ty);
+                let stub = Instance::resolve(*to, &GenericArgs(vec![])).unwrap();
+                (drop_glue, stub)
+            })
+            .collect::<HashMap<_, _>>();
+        DropStubPass { stubs }
+    }
+}
+
 fn has_body(def: FnDef) -> bool {
     def.body().is_some()
 }
@@ -137,7 +209,9 @@ struct FnStubValidator<'a, 'tcx> {
 
 impl FnStubValidator<'_, '_> {
     fn validate(tcx: TyCtxt, stub: (FnDef, FnDef), new_instance: Instance) -> Option<Body> {
-        if validate_stub_const(tcx, new_instance) {
+        if validate_stub_arbitrary(tcx, stub.0, new_instance)
+            && validate_stub_const(tcx, new_instance)
+        {
             let body = new_instance.body().unwrap();
             let mut validator =
                 FnStubValidator { stub, tcx, locals: body.locals(), is_valid: true };
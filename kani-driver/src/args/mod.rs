@@ -63,6 +63,15 @@ pub fn print_deprecated(verbosity: &CommonArgs, option: &str, alternative: &str)
 // By default we configure CBMC to use 16 bits to represent the object bits in pointers.
 const DEFAULT_OBJECT_BITS: u32 = 16;
 
+/// The targets that Kani knows how to model.
+/// This must be kept in sync with the targets accepted by the compiler.
+const SUPPORTED_TARGETS: &[&str] = &[
+    "x86_64-unknown-linux-gnu",
+    "aarch64-unknown-linux-gnu",
+    "x86_64-apple-darwin",
+    "aarch64-apple-darwin",
+];
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, strum_macros::EnumString)]
 enum TimeUnit {
     #[strum(serialize = "s")]
@@ -110,6 +119,62 @@ fn from(timeout: Timeout) -> Self {
     }
 }
 
+/// Whether the failures of a kind of property fail the verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, strum_macros::EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum Severity {
+    /// The failures are reported and fail the verification. This is the default.
+    Error,
+    /// The failures are reported, but don't fail the verification.
+    Warning,
+}
+
+/// The severity of a property class, given with `--property-severity CLASS=SEVERITY`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PropertySeverity {
+    pub class: String,
+    pub severity: Severity,
+}
+
+impl FromStr for PropertySeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (class, severity) = s.split_once('=').ok_or(
+            "Expected a property class and a severity, e.g., `arithmetic_overflow=warning`",
+        )?;
+        if class.is_empty() {
+            return Err("Missing property class".into());
+        }
+        let severity = Severity::from_str(severity)
+            .map_err(|_| "Invalid severity. Use `error` or `warning`")?;
+        Ok(PropertySeverity { class: class.to_string(), severity })
+    }
+}
+
+/// The abstraction of the integers of the crate under verification, given with
+/// `--int-abstraction`, e.g., `bits:16`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntAbstraction {
+    /// The number of bits that the integers are restricted to.
+    pub bits: u32,
+}
+
+impl FromStr for IntAbstraction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = s
+            .strip_prefix("bits:")
+            .ok_or("Expected an abstraction of the form `bits:N`, e.g., `bits:16`")?;
+        let bits = bits.parse::<u32>().map_err(|_| "Invalid number of bits")?;
+        if !(1..128).contains(&bits) {
+            return Err("The number of bits must be between 1 and 127".into());
+        }
+        Ok(IntAbstraction { bits })
+    }
+}
+
 #[derive(Debug, clap::Parser)]
 #[command(
     version,
@@ -198,6 +263,27 @@ pub struct VerificationArgs {
         value_enum
     )]
     pub concrete_playback: Option<ConcretePlaybackMode>,
+
+    /// Shrink the inputs of the concrete playback tests to a simpler input that still fails.
+    /// The candidates are checked by running the harness with concrete playback. This option is
+    /// only supported when verifying a single file.
+    #[arg(long, requires("concrete_playback"))]
+    pub minimize: bool,
+
+    /// Before verifying the harnesses, play back the inputs recorded in the given JSON file and check that each harness produces the expected output, which it records with `kani::oracle_output`. This option is only supported when verifying a single file, and it requires `-Z concrete-playback` to be used.
+    #[arg(long, value_name = "FILE")]
+    pub oracle: Option<PathBuf>,
+
+    /// Instead of verifying the harnesses, run each of them as a randomized test, with the values of `kani::any()` generated from the given seed. The same seed reproduces the same runs. This option is only supported when verifying a single file, and it requires `-Z concrete-playback` to be used.
+    #[arg(long, value_name = "SEED", conflicts_with_all(["concrete_playback", "oracle"]))]
+    pub concretize_seed: Option<u64>,
+
+    /// Fail verification if the harnesses reach unsupported constructs that aren't listed in the given JSON baseline file. The new constructs are listed with their locations. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "FILE")]
+    pub unsupported_baseline: Option<PathBuf>,
+    /// Remove the constructs that are no longer reachable from the file given to `--unsupported-baseline`, or create the file with all the reachable unsupported constructs if it doesn't exist.
+    #[arg(long, requires("unsupported_baseline"))]
+    pub update_unsupported_baseline: bool,
     /// Keep temporary files generated throughout Kani process. This is already the default
     /// behavior for `cargo-kani`.
     #[arg(long, hide_short_help = true)]
@@ -254,6 +340,17 @@ pub struct VerificationArgs {
     /// If no solver is specified (with --solver or harness attribute), Kani will use CaDiCaL.
     #[arg(long, value_parser = CbmcSolverValueParser::new(CbmcSolver::VARIANTS))]
     pub solver: Option<CbmcSolver>,
+    /// Select how arithmetic overflow is modeled. Harnesses can override it with the
+    /// `arithmetic_overflow` attribute.
+    /// If no mode is specified, Kani follows the `overflow-checks` setting of the Cargo profile,
+    /// i.e.: `panic` if overflow checks are enabled (the default), and `wrap` otherwise.
+    #[arg(long)]
+    pub arithmetic_overflow: Option<ArithmeticOverflow>,
+    /// Select how the address space is modeled. The `segmented` model also reports pointer
+    /// arithmetic that moves a pointer out of its object, at the cost of extra checks on every
+    /// pointer offset. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "MODEL")]
+    pub memory_model: Option<MemoryModel>,
     /// Pass through directly to CBMC; must be the last flag.
     /// This feature is unstable and it requires `--enable_unstable` to be used
     #[arg(
@@ -292,6 +389,15 @@ pub struct VerificationArgs {
     #[arg(long, hide_short_help = true, requires("enable_unstable"))]
     pub ignore_global_asm: bool,
 
+    /// Do not warn about functions without arguments that are named like harnesses, i.e.,
+    /// `proof_*`, `*_proof` or `verify_*`, but are not annotated with `#[kani::proof]`.
+    #[arg(long, hide_short_help = true)]
+    pub no_missing_proof_warning: bool,
+
+    /// Report the warnings of the given category, which are emitted while checking the items of the crate, as errors. Without a category, all these warnings are reported as errors. This option can be provided multiple times. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, num_args(0..=1), default_missing_value = "all", value_name = "CATEGORY")]
+    pub deny_warnings: Vec<WarningCategory>,
+
     /// Write the GotoC symbol table to a file in JSON format instead of goto binary format.
     #[arg(long, hide_short_help = true)]
     pub write_json_symtab: bool,
@@ -333,6 +439,121 @@ pub struct VerificationArgs {
     #[arg(long)]
     pub harness_timeout: Option<Timeout>,
 
+    /// Stop verifying new harnesses once the verification of all harnesses took longer than this timeout, with optional suffix ('s': seconds, 'm': minutes, 'h': hours). Default is seconds. The harnesses that were not verified are reported as skipped, and they make the run fail. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long)]
+    pub total_timeout: Option<Timeout>,
+
+    /// What to do with the harnesses that are being verified when the `--total-timeout` is reached.
+    #[arg(long, requires("total_timeout"), default_value = "finish")]
+    pub total_timeout_policy: TotalTimeoutPolicy,
+
+    /// Verify the code using the data layout of the given target triple, e.g., the size of pointers and the alignment of types. Kani must be able to model the target. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long = "target", value_name = "TRIPLE")]
+    pub target_triple: Option<String>,
+
+    /// Write the verification results to the given file in SARIF format, e.g., for code scanning integration. By default, only failed and undetermined properties are included. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "PATH")]
+    pub sarif: Option<PathBuf>,
+
+    /// Also include cover properties and properties that passed in the SARIF output.
+    #[arg(long, requires("sarif"))]
+    pub sarif_all_properties: bool,
+
+    /// Print the number of properties of each outcome for every harness at the end of the run, and write this summary to the given file in JSON format, e.g., for CI dashboards. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "PATH", conflicts_with = "visualize")]
+    pub summary_json: Option<PathBuf>,
+
+    /// Write the verification results to the given file in JUnit XML format, e.g., for CI test dashboards. Each harness is reported as a test case, and each failed property as a failure. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "PATH")]
+    pub junit_xml: Option<PathBuf>,
+
+    /// Run the given shell command after each harness is verified, with the result of the harness in JSON format on its standard input. The result includes the harness name, its status and the properties that failed. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "COMMAND")]
+    pub post_verification_hook: Option<String>,
+
+    /// Abort the run if the post-verification hook exits with a nonzero status. By default, a warning is printed instead.
+    #[arg(long, requires("post_verification_hook"))]
+    pub fail_on_hook_error: bool,
+
+    /// Only verify the harnesses whose code or options changed since the previous run with this option, and report the cached result of the other harnesses. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, conflicts_with_all = ["concrete_playback", "coverage", "junit_xml", "sarif", "summary_json", "visualize"])]
+    pub changed_only: bool,
+
+    /// Add a cover property to every arm of the `match` expressions of the crate under verification, which reports whether each arm is reachable by the harness. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long)]
+    pub cover_match_arms: bool,
+
+    /// Report the `todo!()` and `unimplemented!()` calls reachable by each harness as unfinished code, separately from other panics, and list the ones that were reached. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long)]
+    pub check_unfinished_code: bool,
+
+    /// Assume that every call in the crate under verification that returns a `Result` returns `Ok`, which restricts the verification to the paths where no error occurs. This is an under-approximation, and the functions whose calls were assumed to return `Ok` are reported with the verification result of each harness. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long)]
+    pub assume_ok: bool,
+
+    /// Restrict the integers of the crate under verification to a reduced number of bits, e.g., `bits:16`, for a faster first-pass verification. The layout of the integer types doesn't change, but the executions where an integer value written by the crate doesn't fit in the reduced number of bits are not verified. This is an under-approximation, and it's reported with the verification result of each harness. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "ABSTRACTION")]
+    pub int_abstraction: Option<IntAbstraction>,
+
+    /// Write a crate with only the items that each harness depends on to the given directory, which can be shared to reproduce an issue. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "DIR")]
+    pub export_reproducer: Option<PathBuf>,
+
+    /// Write the MIR of each function reachable from a harness to its own file, in the `kani-mir/<harness>` directory. With `transformed`, the lines added by Kani's instrumentation, stubbing and contracts are marked with `// injected by Kani`. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, value_name = "KIND")]
+    pub emit_mir: Option<EmitMir>,
+
+    /// List the harnesses that would be verified, selected by `--harness` and `--exact`, with their unwind value, solver and stubs, in the given format. The harnesses are neither compiled to a model nor verified. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, num_args(0..=1), default_missing_value = "pretty", value_name = "FORMAT")]
+    pub list_harnesses: Option<list_args::Format>,
+
+    /// Set the severity of the properties of the given class, e.g., `arithmetic_overflow=warning`. The failures of properties with the `warning` severity are reported, but don't fail the verification. Properties have the `error` severity by default. This option can be provided multiple times, and the last severity given for a class is used. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, num_args(1), value_name = "CLASS=SEVERITY")]
+    pub property_severity: Vec<PropertySeverity>,
+
+    /// Don't analyze the body of the given function, and treat its return value as nondeterministic instead. The path is resolved from the root of the crate under verification. This option can be provided multiple times. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, num_args(1), value_name = "FUNCTION_PATH", conflicts_with = "allowlist")]
+    pub denylist: Vec<String>,
+
+    /// Only analyze the body of the given functions among the functions of the crate under verification, and treat the return value of the others as nondeterministic. The path is resolved from the root of the crate under verification. This option can be provided multiple times. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, num_args(1), value_name = "FUNCTION_PATH")]
+    pub allowlist: Vec<String>,
+
+    /// Treat the functions of the crate under verification that are marked `#[inline(never)]` as analysis boundaries: replace them by their contract if they have one, and treat their return value as nondeterministic otherwise. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, conflicts_with = "allowlist")]
+    pub inline_never_boundaries: bool,
+
+    /// Find the minimal unwind bound of each harness: verify the harness with increasing unwind bounds until the unwinding assertions hold, up to the given cap (64 by default), and then verify it with the minimal bound found. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(
+        long,
+        num_args(0..=1),
+        default_missing_value = "64",
+        value_name = "CAP",
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with_all = ["unwind", "no_unwinding_checks", "synthesize_loop_contracts", "changed_only", "replay", "visualize"]
+    )]
+    pub find_unwind: Option<u32>,
+
+    /// Model the spurious failures of the weak compare-and-exchange operations, e.g., `compare_exchange_weak`, which may fail even if the current value is the expected one. Retry loops built on these operations, such as `fetch_update`, may then need an unwind bound. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long)]
+    pub spurious_cas_failures: bool,
+
+    /// Replace the `std::fs` entry points by an in-memory symbolic file system, whose bounds can be configured with `kani::fs`. This option is experimental and requires `-Z stubbing` to be used.
+    #[arg(long)]
+    pub model_fs: bool,
+
+    /// Write a replay file for each verified harness next to the metadata of its crate, which records the options passed to the solver, the seed used to randomize the layout of types and the outcome of the verification. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(long, conflicts_with_all = ["replay", "visualize"])]
+    pub record_replay: bool,
+
+    /// Verify the harness recorded in the given replay file with the recorded configuration, and fail if the outcome differs from the recorded one. This option is experimental and requires `-Z unstable-options` to be used.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["harnesses", "randomize_layout", "harness_timeout", "changed_only", "visualize"]
+    )]
+    pub replay: Option<PathBuf>,
+
     /// Arguments to pass down to Cargo
     #[command(flatten)]
     pub cargo: CargoCommonArgs,
@@ -365,6 +586,11 @@ pub fn cbmc_object_bits(&self) -> Option<u32> {
         }
     }
 
+    /// The target triple used to compile the code under verification.
+    pub fn build_target(&self) -> &str {
+        self.target_triple.as_deref().unwrap_or(env!("TARGET"))
+    }
+
     /// Computes how many threads should be used to verify harnesses.
     pub fn jobs(&self) -> Option<usize> {
         match self.jobs {
@@ -374,6 +600,16 @@ pub fn jobs(&self) -> Option<usize> {
         }
     }
 
+    /// The severity of the properties of the given class.
+    /// Classes without a `--property-severity` are errors.
+    pub fn property_severity(&self, class: &str) -> Severity {
+        self.property_severity
+            .iter()
+            .rev()
+            .find(|entry| entry.class == class)
+            .map_or(Severity::Error, |entry| entry.severity)
+    }
+
     /// Are experimental function contracts enabled?
     pub fn is_function_contracts_enabled(&self) -> bool {
         self.common_args.unstable_features.contains(UnstableFeature::FunctionContracts)
@@ -394,6 +630,52 @@ pub enum ConcretePlaybackMode {
     InPlace,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ArithmeticOverflow {
+    /// Overflow wraps around, and it is not a property.
+    Wrap,
+    /// Overflow is a verification failure, even in harnesses that are expected to panic.
+    Check,
+    /// Overflow is a panic.
+    Panic,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MemoryModel {
+    /// Pointers may be moved out of their object as long as they aren't dereferenced.
+    Flat,
+    /// Moving a pointer out of its object is a verification failure.
+    Segmented,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TotalTimeoutPolicy {
+    /// Let the harnesses that are being verified finish.
+    Finish,
+    /// Stop the harnesses that are being verified, and report them as skipped.
+    Kill,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum EmitMir {
+    /// The MIR generated by rustc, before Kani's transformations.
+    Original,
+    /// The MIR after Kani's transformations.
+    Transformed,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum WarningCategory {
+    /// All the categories below.
+    All,
+    /// Global ASM that is ignored because of `--ignore-global-asm`.
+    GlobalAsm,
+    /// A function that is named like a harness, but isn't annotated with `#[kani::proof]`.
+    MissingProof,
+    /// A harness generated for a type with interior mutability, whose check may be unsound.
+    InteriorMut,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     Regular,
@@ -649,6 +931,30 @@ fn validate(&self) -> Result<(), Error> {
             }
         }
 
+        if self.oracle.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::ConcretePlayback)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--oracle` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::ConcretePlayback
+                ),
+            ));
+        }
+
+        if self.concretize_seed.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::ConcretePlayback)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--concretize-seed` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::ConcretePlayback
+                ),
+            ));
+        }
+
         if !self.c_lib.is_empty()
             && !self.common_args.unstable_features.contains(UnstableFeature::CFfi)
         {
@@ -701,82 +1007,392 @@ fn validate(&self) -> Result<(), Error> {
                 ),
             ));
         }
-        Ok(())
-    }
-}
 
-/// clap parser for `CbmcSolver`
-#[derive(Clone, Debug)]
-pub struct CbmcSolverValueParser(Vec<PossibleValue>);
+        if self.total_timeout.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--total-timeout` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-impl CbmcSolverValueParser {
-    pub fn new(values: impl Into<CbmcSolverValueParser>) -> Self {
-        values.into()
-    }
-}
+        if self.sarif.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--sarif` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-impl TypedValueParser for CbmcSolverValueParser {
-    type Value = CbmcSolver;
+        if self.summary_json.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--summary-json` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-    fn parse_ref(
-        &self,
-        cmd: &clap::builder::Command,
-        arg: Option<&clap::builder::Arg>,
-        value: &std::ffi::OsStr,
-    ) -> Result<Self::Value, clap::error::Error> {
-        let value = value.to_str().unwrap();
-        // `value` is one of the possible `CbmcSolver` values or `bin=<binary>`
-        let segments: Vec<&str> = value.split('=').collect();
+        if self.junit_xml.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--junit-xml` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-        let mut err = clap::Error::new(ErrorKind::InvalidValue).with_cmd(cmd);
-        err.insert(ContextKind::InvalidArg, ContextValue::String(arg.unwrap().to_string()));
-        err.insert(ContextKind::InvalidValue, ContextValue::String(value.to_string()));
+        if self.post_verification_hook.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--post-verification-hook` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-        if segments.len() == 2 {
-            if segments[0] != "bin" {
-                return Err(err);
-            }
-            return Ok(CbmcSolver::Binary(segments[1].into()));
-        } else if segments.len() == 1 {
-            let solver = CbmcSolver::from_str(value);
-            return solver.map_err(|_| err);
+        if self.changed_only
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--changed-only` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
         }
-        Err(err)
-    }
 
-    /// Used for the help message
-    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
-        Some(Box::new(self.0.iter().cloned()))
-    }
-}
+        if self.cover_match_arms
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--cover-match-arms` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-impl<I, T> From<I> for CbmcSolverValueParser
-where
-    I: IntoIterator<Item = T>,
-    T: Into<PossibleValue>,
-{
-    fn from(values: I) -> Self {
-        Self(values.into_iter().map(|t| t.into()).collect())
-    }
-}
+        if self.check_unfinished_code
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--check-unfinished-code` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-#[cfg(test)]
-mod tests {
-    use clap::Parser;
+        if self.assume_ok
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--assume-ok` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-    use super::*;
+        if self.int_abstraction.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--int-abstraction` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
 
-    #[test]
-    fn check_arg_parsing() {
-        let a = StandaloneArgs::try_parse_from(vec![
-            "kani",
-            "file.rs",
-            "--enable-unstable",
-            "--cbmc-args",
-            "--multiple",
-            "args",
-            "--here",
-        ])
+        if self.export_reproducer.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--export-reproducer` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if self.emit_mir.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--emit-mir` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if self.list_harnesses.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--list-harnesses` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if !self.deny_warnings.is_empty()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--deny-warnings` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if !self.property_severity.is_empty()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--property-severity` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if (!self.denylist.is_empty() || !self.allowlist.is_empty())
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--denylist` and `--allowlist` arguments are unstable and require `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if self.inline_never_boundaries
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--inline-never-boundaries` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if self.spurious_cas_failures
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--spurious-cas-failures` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if self.find_unwind.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--find-unwind` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if let Some(path) = self
+            .denylist
+            .iter()
+            .chain(&self.allowlist)
+            .find(|path| path.contains(char::is_whitespace))
+        {
+            return Err(Error::raw(
+                ErrorKind::ValueValidation,
+                format!(
+                    "Invalid function path `{path}`. The `--denylist` and `--allowlist` arguments only support simple paths, such as `module::function`."
+                ),
+            ));
+        }
+
+        if (self.record_replay || self.replay.is_some())
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--record-replay` and `--replay` arguments are unstable and require `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if self.model_fs && !self.is_stubbing_enabled() {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--model-fs` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::Stubbing
+                ),
+            ));
+        }
+
+        if let Some(target) = &self.target_triple {
+            if !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions) {
+                return Err(Error::raw(
+                    ErrorKind::MissingRequiredArgument,
+                    format!(
+                        "The `--target` argument is unstable and requires `-Z {}` to be used.",
+                        UnstableFeature::UnstableOptions
+                    ),
+                ));
+            }
+            if !SUPPORTED_TARGETS.contains(&target.as_str()) {
+                return Err(Error::raw(
+                    ErrorKind::InvalidValue,
+                    format!(
+                        "Kani cannot model target `{target}`. The supported targets are: `{}`.",
+                        SUPPORTED_TARGETS.join("`, `")
+                    ),
+                ));
+            }
+        }
+
+        if self.memory_model.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--memory-model` argument is unstable and requires `-Z {}` to be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+
+        if self.unsupported_baseline.is_some()
+            && !self.common_args.unstable_features.contains(UnstableFeature::UnstableOptions)
+        {
+            return Err(Error::raw(
+                ErrorKind::MissingRequiredArgument,
+                format!(
+                    "The `--unsupported-baseline` argument is unstable and requires `-Z {}` to \
+                    be used.",
+                    UnstableFeature::UnstableOptions
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// clap parser for `CbmcSolver`
+#[derive(Clone, Debug)]
+pub struct CbmcSolverValueParser(Vec<PossibleValue>);
+
+impl CbmcSolverValueParser {
+    pub fn new(values: impl Into<CbmcSolverValueParser>) -> Self {
+        values.into()
+    }
+}
+
+impl TypedValueParser for CbmcSolverValueParser {
+    type Value = CbmcSolver;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::builder::Command,
+        arg: Option<&clap::builder::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::error::Error> {
+        let value = value.to_str().unwrap();
+        // `value` is one of the possible `CbmcSolver` values or `bin=<binary>`
+        let segments: Vec<&str> = value.split('=').collect();
+
+        let mut err = clap::Error::new(ErrorKind::InvalidValue).with_cmd(cmd);
+        err.insert(ContextKind::InvalidArg, ContextValue::String(arg.unwrap().to_string()));
+        err.insert(ContextKind::InvalidValue, ContextValue::String(value.to_string()));
+
+        if segments.len() == 2 {
+            if segments[0] != "bin" {
+                return Err(err);
+            }
+            return Ok(CbmcSolver::Binary(segments[1].into()));
+        } else if segments.len() == 1 {
+            let solver = CbmcSolver::from_str(value);
+            return solver.map_err(|_| err);
+        }
+        Err(err)
+    }
+
+    /// Used for the help message
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(self.0.iter().cloned()))
+    }
+}
+
+impl<I, T> From<I> for CbmcSolverValueParser
+where
+    I: IntoIterator<Item = T>,
+    T: Into<PossibleValue>,
+{
+    fn from(values: I) -> Self {
+        Self(values.into_iter().map(|t| t.into()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn check_arg_parsing() {
+        let a = StandaloneArgs::try_parse_from(vec![
+            "kani",
+            "file.rs",
+            "--enable-unstable",
+            "--cbmc-args",
+            "--multiple",
+            "args",
+            "--here",
+        ])
         .unwrap();
         assert_eq!(a.verify_opts.cbmc_args, vec!["--multiple", "args", "--here"]);
         let _b = StandaloneArgs::try_parse_from(vec![
@@ -913,6 +1529,55 @@ fn check_concrete_playback_unstable() {
         check("kani file.rs --concrete-playback=print");
     }
 
+    #[test]
+    fn check_oracle_unstable() {
+        let res = parse_unstable_disabled("--harness foo --oracle oracle.json").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo --oracle oracle.json -Z concrete-playback")
+                .unwrap();
+        assert_eq!(res.verify_opts.oracle, Some(PathBuf::from("oracle.json")));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+    }
+
+    #[test]
+    fn check_concretize_seed() {
+        let res = parse_unstable_disabled("--harness foo --concretize-seed 42").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo --concretize-seed 42 -Z concrete-playback")
+                .unwrap();
+        assert_eq!(res.verify_opts.concretize_seed, Some(42));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let res = parse_unstable_disabled(
+            "--concretize-seed 42 --concrete-playback=print -Z concrete-playback",
+        );
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_unsupported_baseline() {
+        let res = parse_unstable_disabled("--unsupported-baseline baseline.json").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled(
+            "--unsupported-baseline baseline.json --update-unsupported-baseline -Z unstable-options",
+        )
+        .unwrap();
+        assert_eq!(res.verify_opts.unsupported_baseline, Some(PathBuf::from("baseline.json")));
+        assert!(res.verify_opts.update_unsupported_baseline);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let err = parse_unstable_disabled("--update-unsupported-baseline").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
     /// Check if parsing the given argument string results in the given error.
     fn expect_validation_error(arg: &str, err: ErrorKind) {
         let args = StandaloneArgs::try_parse_from(arg.split_whitespace()).unwrap();
@@ -948,6 +1613,329 @@ fn check_enable_stubbing() {
         assert!(matches!(res.verify_opts.validate(), Ok(())));
     }
 
+    #[test]
+    fn check_model_fs() {
+        let res = parse_unstable_disabled("--harness foo --model-fs").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled("--harness foo --model-fs -Z stubbing").unwrap();
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+    }
+
+    #[test]
+    fn check_cover_match_arms() {
+        let res = parse_unstable_disabled("--harness foo --cover-match-arms").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled("--harness foo --cover-match-arms -Z unstable-options")
+            .unwrap();
+        assert!(res.verify_opts.cover_match_arms);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+    }
+
+    #[test]
+    fn check_check_unfinished_code() {
+        let res = parse_unstable_disabled("--harness foo --check-unfinished-code").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo --check-unfinished-code -Z unstable-options")
+                .unwrap();
+        assert!(res.verify_opts.check_unfinished_code);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+    }
+
+    #[test]
+    fn check_assume_ok() {
+        let res = parse_unstable_disabled("--harness foo --assume-ok").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled("--harness foo --assume-ok -Z unstable-options").unwrap();
+        assert!(res.verify_opts.assume_ok);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+    }
+
+    #[test]
+    fn check_junit_xml() {
+        let res = parse_unstable_disabled("--harness foo --junit-xml results.xml").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo --junit-xml results.xml -Z unstable-options")
+                .unwrap();
+        assert_eq!(res.verify_opts.junit_xml, Some(PathBuf::from("results.xml")));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+    }
+
+    #[test]
+    fn check_int_abstraction() {
+        let res = parse_unstable_disabled("--harness foo --int-abstraction bits:16").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo --int-abstraction=bits:16 -Z unstable-options")
+                .unwrap();
+        assert_eq!(res.verify_opts.int_abstraction, Some(IntAbstraction { bits: 16 }));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        assert!(parse_unstable_disabled("--harness foo --int-abstraction=16").is_err());
+        assert!(parse_unstable_disabled("--harness foo --int-abstraction=bits:0").is_err());
+        assert!(parse_unstable_disabled("--harness foo --int-abstraction=bits:128").is_err());
+    }
+
+    #[test]
+    fn check_list_harnesses() {
+        let res = parse_unstable_disabled("--list-harnesses").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled("--list-harnesses -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.list_harnesses, Some(list_args::Format::Pretty));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let res =
+            parse_unstable_disabled("--list-harnesses json --harness foo -Z unstable-options")
+                .unwrap();
+        assert_eq!(res.verify_opts.list_harnesses, Some(list_args::Format::Json));
+    }
+
+    #[test]
+    fn check_export_reproducer() {
+        let res = parse_unstable_disabled("--harness foo --export-reproducer out").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo --export-reproducer out -Z unstable-options")
+                .unwrap();
+        assert_eq!(res.verify_opts.export_reproducer, Some(PathBuf::from("out")));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+    }
+
+    #[test]
+    fn check_emit_mir() {
+        let res = parse_unstable_disabled("--harness foo --emit-mir transformed").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled("--harness foo --emit-mir=original -Z unstable-options")
+            .unwrap();
+        assert_eq!(res.verify_opts.emit_mir, Some(EmitMir::Original));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        assert!(parse_unstable_disabled("--harness foo --emit-mir=optimized").is_err());
+    }
+
+    #[test]
+    fn check_deny_warnings() {
+        let res = parse_unstable_disabled("--harness foo --deny-warnings").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo -Z unstable-options --deny-warnings").unwrap();
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+        assert_eq!(res.verify_opts.deny_warnings, vec![WarningCategory::All]);
+
+        let res = parse_unstable_disabled(
+            "--harness foo -Z unstable-options --deny-warnings global-asm \
+            --deny-warnings=missing-proof",
+        )
+        .unwrap();
+        assert_eq!(res.verify_opts.deny_warnings, vec![
+            WarningCategory::GlobalAsm,
+            WarningCategory::MissingProof
+        ]);
+
+        assert!(parse_unstable_disabled("--harness foo --deny-warnings=unused").is_err());
+    }
+
+    #[test]
+    fn check_property_severity() {
+        let res =
+            parse_unstable_disabled("--harness foo --property-severity assertion=warning").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled(
+            "--harness foo -Z unstable-options --property-severity arithmetic_overflow=warning \
+            --property-severity assertion=warning --property-severity assertion=error",
+        )
+        .unwrap();
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+        assert_eq!(res.verify_opts.property_severity("arithmetic_overflow"), Severity::Warning);
+        assert_eq!(res.verify_opts.property_severity("assertion"), Severity::Error);
+        assert_eq!(res.verify_opts.property_severity("pointer_dereference"), Severity::Error);
+
+        assert!(parse_unstable_disabled("--property-severity assertion=info").is_err());
+        assert!(parse_unstable_disabled("--property-severity assertion").is_err());
+        assert!(parse_unstable_disabled("--property-severity =warning").is_err());
+    }
+
+    #[test]
+    fn check_total_timeout() {
+        let res = parse_unstable_disabled("--harness foo --total-timeout 10m").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled("--harness foo -Z unstable-options --total-timeout 10m")
+            .unwrap();
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+        assert_eq!(res.verify_opts.total_timeout_policy, TotalTimeoutPolicy::Finish);
+
+        let res = parse_unstable_disabled(
+            "-Z unstable-options --total-timeout 1h --total-timeout-policy kill",
+        )
+        .unwrap();
+        assert_eq!(res.verify_opts.total_timeout_policy, TotalTimeoutPolicy::Kill);
+
+        // The policy is meaningless without a timeout.
+        assert!(parse_unstable_disabled("--total-timeout-policy kill").is_err());
+    }
+
+    #[test]
+    fn check_inline_never_boundaries() {
+        let res = parse_unstable_disabled("--harness foo --inline-never-boundaries").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled(
+            "--harness foo --inline-never-boundaries --denylist a -Z unstable-options",
+        )
+        .unwrap();
+        assert!(res.verify_opts.inline_never_boundaries);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let err = parse_unstable_disabled("--harness foo --inline-never-boundaries --allowlist b")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_spurious_cas_failures() {
+        let res = parse_unstable_disabled("--harness foo --spurious-cas-failures").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo --spurious-cas-failures -Z unstable-options")
+                .unwrap();
+        assert!(res.verify_opts.spurious_cas_failures);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+    }
+
+    #[test]
+    fn check_find_unwind() {
+        let res = parse_unstable_disabled("--harness foo --find-unwind").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--harness foo --find-unwind -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.find_unwind, Some(64));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let res =
+            parse_unstable_disabled("--harness foo --find-unwind=10 -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.find_unwind, Some(10));
+
+        let err = parse_unstable_disabled("--harness foo --find-unwind=0").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+
+        let err = parse_unstable_disabled("--harness foo --find-unwind --unwind 2").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+
+        let err = parse_unstable_disabled("--harness foo --find-unwind --no-unwinding-checks")
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_denylist_allowlist() {
+        let res = parse_unstable_disabled("--harness foo --denylist a::b").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled(
+            "--harness foo --denylist a::b --denylist c -Z unstable-options",
+        )
+        .unwrap();
+        assert_eq!(res.verify_opts.denylist, ["a::b", "c"]);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let res =
+            parse_unstable_disabled("--harness foo --allowlist a::b -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.allowlist, ["a::b"]);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let err = parse_unstable_disabled("--harness foo --denylist a --allowlist b").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+
+        let mut res =
+            parse_unstable_disabled("--harness foo --denylist a -Z unstable-options").unwrap();
+        res.verify_opts.denylist = vec!["<A as B>::f".into()];
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn check_post_verification_hook() {
+        let res = parse_unstable_disabled("--harness foo --post-verification-hook cat").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled(
+            "--harness foo --post-verification-hook cat --fail-on-hook-error -Z unstable-options",
+        )
+        .unwrap();
+        assert_eq!(res.verify_opts.post_verification_hook.as_deref(), Some("cat"));
+        assert!(res.verify_opts.fail_on_hook_error);
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let err = parse_unstable_disabled("--harness foo --fail-on-hook-error").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn check_replay() {
+        let res = parse_unstable_disabled("--harness foo --record-replay").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res = parse_unstable_disabled("--replay foo.kani-replay.json").unwrap();
+        let err = res.verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let res =
+            parse_unstable_disabled("--replay foo.kani-replay.json -Z unstable-options").unwrap();
+        assert_eq!(res.verify_opts.replay, Some(PathBuf::from("foo.kani-replay.json")));
+        assert!(matches!(res.verify_opts.validate(), Ok(())));
+
+        let err =
+            parse_unstable_disabled("--harness foo --replay foo.kani-replay.json").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+        let err =
+            parse_unstable_disabled("--record-replay --replay foo.kani-replay.json").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn check_arithmetic_overflow() {
+        let res = parse_unstable_disabled("--harness foo").unwrap();
+        assert_eq!(res.verify_opts.arithmetic_overflow, None);
+
+        let res = parse_unstable_disabled("--harness foo --arithmetic-overflow wrap").unwrap();
+        assert_eq!(res.verify_opts.arithmetic_overflow, Some(ArithmeticOverflow::Wrap));
+
+        assert!(parse_unstable_disabled("--harness foo --arithmetic-overflow saturate").is_err());
+    }
+
     #[test]
     fn check_features_parsing() {
         fn parse(args: &[&str]) -> Vec<String> {
@@ -999,4 +1987,43 @@ fn check_cbmc_args_lean_backend() {
         let err = StandaloneArgs::try_parse_from(args).unwrap().validate().unwrap_err();
         assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
     }
+
+    #[test]
+    fn check_target_validation() {
+        let parse = |args: &str| StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap();
+
+        let args = parse("kani input.rs --target aarch64-unknown-linux-gnu -Z unstable-options");
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.build_target(), "aarch64-unknown-linux-gnu");
+
+        let err = parse("kani input.rs --target aarch64-unknown-linux-gnu")
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let err = parse("kani input.rs --target thumbv7em-none-eabihf -Z unstable-options")
+            .verify_opts
+            .validate()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn check_memory_model() {
+        let parse = |args: &str| StandaloneArgs::try_parse_from(args.split_whitespace()).unwrap();
+
+        let args = parse("kani input.rs");
+        assert_eq!(args.verify_opts.memory_model, None);
+
+        let args = parse("kani input.rs --memory-model segmented -Z unstable-options");
+        assert!(args.verify_opts.validate().is_ok());
+        assert_eq!(args.verify_opts.memory_model, Some(MemoryModel::Segmented));
+
+        let err = parse("kani input.rs --memory-model=flat").verify_opts.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+        let args = StandaloneArgs::try_parse_from(["kani", "input.rs", "--memory-model=paged"]);
+        assert_eq!(args.unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
 }
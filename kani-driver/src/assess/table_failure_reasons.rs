@@ -38,6 +38,7 @@ pub(crate) fn build(results: &[HarnessResult]) -> TableBuilder<FailureReasonsTab
         let classification = if let Err(exit_status) = r.result.results {
             match exit_status {
                 ExitStatus::Timeout => String::from("CBMC timed out"),
+                ExitStatus::Skipped => String::from("skipped (budget exhausted)"),
                 ExitStatus::OutOfMemory => String::from("CBMC ran out of memory"),
                 ExitStatus::Other(exit_code) => format!("CBMC failed with status {exit_code}"),
             }
@@ -91,7 +91,7 @@ pub fn cargo_build_std(&self, std_path: &Path, krate_path: &Path) -> Result<Vec<
         rustc_args.push(to_rustc_arg(vec!["--ignore-global-asm".to_string()]).into());
 
         let mut cargo_args: Vec<OsString> = vec!["build".into()];
-        cargo_args.append(&mut cargo_config_args());
+        cargo_args.append(&mut cargo_config_args(self.args.build_target()));
 
         // Configuration needed to parse cargo compilation status.
         cargo_args.push("--message-format".into());
@@ -136,7 +136,7 @@ pub fn cargo_build_std(&self, std_path: &Path, krate_path: &Path) -> Result<Vec<
 
     /// Calls `cargo_build` to generate `*.symtab.json` files in `target_dir`
     pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
-        let build_target = env!("TARGET"); // see build.rs
+        let build_target = self.args.build_target();
         let metadata = self.cargo_metadata(build_target)?;
         let target_dir = self
             .args
@@ -153,7 +153,13 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
 
         let lib_path = lib_folder().unwrap();
         let mut rustc_args = self.kani_rustc_flags(LibConfig::new(lib_path));
-        rustc_args.push(to_rustc_arg(self.kani_compiler_flags()).into());
+        let mut compiler_flags = self.kani_compiler_flags();
+        if self.args.arithmetic_overflow.is_none()
+            && !profile_overflow_checks(&metadata, self.args.tests)
+        {
+            compiler_flags.push("--arithmetic-overflow=wrap".into());
+        }
+        rustc_args.push(to_rustc_arg(compiler_flags).into());
 
         let mut cargo_args: Vec<OsString> = vec!["rustc".into()];
         if let Some(path) = &self.args.cargo.manifest_path {
@@ -171,7 +177,7 @@ pub fn cargo_build(&self, keep_going: bool) -> Result<CargoOutputs> {
             cargo_args.push(format!("--features={}", features.join(",")).into());
         }
 
-        cargo_args.append(&mut cargo_config_args());
+        cargo_args.append(&mut cargo_config_args(build_target));
 
         cargo_args.push("--target-dir".into());
         cargo_args.push(target_dir.into());
@@ -455,10 +461,10 @@ fn packages_to_verify<'b>(
     }
 }
 
-pub fn cargo_config_args() -> Vec<OsString> {
+pub fn cargo_config_args(target: &str) -> Vec<OsString> {
     [
         "--target",
-        env!("TARGET"),
+        target,
         // Propagate `--cfg=kani_host` to build scripts.
         "-Zhost-config",
         "-Ztarget-applies-to-host",
@@ -478,6 +484,28 @@ fn print_msg(diagnostic: &Diagnostic, use_rendered: bool) -> Result<()> {
     Ok(())
 }
 
+/// Return whether the Cargo profile used to build the harnesses enables overflow checks.
+///
+/// Kani always builds the code with overflow checks, so we look up the setting of the profile in
+/// the environment and in the workspace manifest. Harnesses are built with the `dev` profile, or
+/// with the `test` profile, which inherits from `dev`, when `--tests` is given.
+fn profile_overflow_checks(metadata: &Metadata, tests: bool) -> bool {
+    let profiles: &[&str] = if tests { &["test", "dev"] } else { &["dev"] };
+    let manifest = fs::read_to_string(metadata.workspace_root.join("Cargo.toml"))
+        .ok()
+        .and_then(|contents| contents.parse::<toml::Table>().ok());
+    profiles
+        .iter()
+        .find_map(|profile| {
+            let var = format!("CARGO_PROFILE_{}_OVERFLOW_CHECKS", profile.to_uppercase());
+            if let Ok(value) = std::env::var(var) {
+                return Some(value == "true");
+            }
+            manifest.as_ref()?.get("profile")?.get(*profile)?.get("overflow-checks")?.as_bool()
+        })
+        .unwrap_or(true)
+}
+
 /// Extract Kani artifact that might've been generated from a given rustc artifact.
 /// Not every rustc artifact will map to a kani artifact, hence the `Option<>`.
 ///
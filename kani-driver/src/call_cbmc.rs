@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{Result, bail};
-use kani_metadata::{CbmcSolver, HarnessMetadata};
+use kani_metadata::{CbmcSolver, HarnessMetadata, INT_ABSTRACTION_COVER};
 use regex::Regex;
 use rustc_demangle::demangle;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::collections::btree_map::Entry;
 use std::ffi::OsString;
@@ -16,11 +17,13 @@
 use tokio::process::Command as TokioCommand;
 
 use crate::args::common::Verbosity;
-use crate::args::{OutputFormat, VerificationArgs};
+use crate::args::{OutputFormat, Severity, TotalTimeoutPolicy, VerificationArgs};
 use crate::cbmc_output_parser::{
     CheckStatus, Property, VerificationOutput, extract_results, process_cbmc_output,
 };
-use crate::cbmc_property_renderer::{format_coverage, format_result, kani_cbmc_output_filter};
+use crate::cbmc_property_renderer::{
+    captured_names, format_coverage, format_result, kani_cbmc_output_filter,
+};
 use crate::coverage::cov_results::{CoverageCheck, CoverageResults};
 use crate::coverage::cov_results::{CoverageRegion, CoverageTerm};
 use crate::session::KaniSession;
@@ -30,7 +33,7 @@
 /// Note: Kissat was marginally better, but it is an external solver which could be more unstable.
 static DEFAULT_SOLVER: CbmcSolver = CbmcSolver::Cadical;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VerificationStatus {
     Success,
     Failure,
@@ -38,7 +41,7 @@ pub enum VerificationStatus {
 
 /// Represents failed properties in three different categories.
 /// This simplifies the process to determine and format verification results.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum FailedProperties {
     // No failures
     None,
@@ -52,6 +55,9 @@ pub enum FailedProperties {
 #[derive(Clone, Copy, Debug)]
 pub enum ExitStatus {
     Timeout,
+    /// The harness was not verified, or its verification was stopped, because the
+    /// `--total-timeout` was reached.
+    Skipped,
     OutOfMemory,
     /// the integer is the process exit status
     Other(i32),
@@ -75,12 +81,18 @@ pub struct VerificationResult {
     pub generated_concrete_test: bool,
     /// The coverage results
     pub coverage_results: Option<CoverageResults>,
+    /// The names of the failed properties with the `warning` severity, which don't fail the
+    /// verification.
+    pub warnings: Vec<String>,
 }
 
 impl KaniSession {
     /// Verify a goto binary that's been prepared with goto-instrument
     pub fn run_cbmc(&self, file: &Path, harness: &HarnessMetadata) -> Result<VerificationResult> {
-        let args: Vec<OsString> = self.cbmc_flags(file, harness)?;
+        let args: Vec<OsString> = match &self.replay {
+            Some(replay) => replay.cbmc_args(file),
+            None => self.cbmc_flags(file, harness)?,
+        };
 
         // TODO get cbmc path from self
         let mut cmd = TokioCommand::new("cbmc");
@@ -119,9 +131,18 @@ async fn run_cbmc_piped(
 
         let start_time = Instant::now();
 
-        let res = if let Some(timeout) = self.args.harness_timeout {
+        // With the `kill` policy, the harness is also stopped when the total timeout is reached.
+        let budget = (self.args.total_timeout_policy == TotalTimeoutPolicy::Kill)
+            .then(|| self.remaining_budget())
+            .flatten();
+        let harness_timeout = self.harness_timeout();
+        let stopped_by_budget =
+            budget.is_some_and(|budget| harness_timeout.is_none_or(|t| budget < t));
+        let timeout = if stopped_by_budget { budget } else { harness_timeout };
+
+        let res = if let Some(timeout) = timeout {
             tokio::time::timeout(
-                timeout.into(),
+                timeout,
                 process_cbmc_output(&mut cbmc_process, |i| {
                     kani_cbmc_output_filter(
                         i,
@@ -153,15 +174,25 @@ async fn run_cbmc_piped(
             VerificationResult {
                 status: VerificationStatus::Failure,
                 failed_properties: FailedProperties::None,
-                results: Err(ExitStatus::Timeout),
+                results: Err(if stopped_by_budget {
+                    ExitStatus::Skipped
+                } else {
+                    ExitStatus::Timeout
+                }),
                 runtime: start_time.elapsed(),
                 generated_concrete_test: false,
                 coverage_results: None,
+                warnings: vec![],
             }
         } else {
             // The timeout wasn't reached
             let output = res.unwrap()?;
-            VerificationResult::from(output, harness.attributes.should_panic, start_time)
+            VerificationResult::from(
+                output,
+                harness.attributes.should_panic,
+                |prop| self.args.property_severity(&prop.property_class()) == Severity::Warning,
+                start_time,
+            )
         };
 
         Ok(verification_results)
@@ -209,14 +240,22 @@ pub fn cbmc_flags(
             args.push("--validate-ssa-equation".into());
         }
 
+        // Traces are also needed to render the values captured by `assert!` messages.
+        // Note that slicing would remove the assignments to the captured values.
+        let has_captured_values = harness_metadata
+            .properties
+            .iter()
+            .any(|property| !captured_names(&property.description).is_empty());
+
         if !self.args.visualize
             && self.args.concrete_playback.is_none()
+            && !has_captured_values
             && !self.args.no_slice_formula
         {
             args.push("--slice-formula".into());
         }
 
-        if self.args.concrete_playback.is_some() {
+        if self.args.concrete_playback.is_some() || has_captured_values {
             args.push("--trace".into());
         }
 
@@ -289,16 +328,7 @@ pub fn handle_solver_args(
         harness_solver: &Option<CbmcSolver>,
         args: &mut Vec<OsString>,
     ) -> Result<()> {
-        let solver = if let Some(solver) = &self.args.solver {
-            // `--solver` option takes precedence over attributes
-            solver
-        } else if let Some(solver) = harness_solver {
-            solver
-        } else {
-            &DEFAULT_SOLVER
-        };
-
-        match solver {
+        match resolve_solver(&self.args, harness_solver) {
             CbmcSolver::Cadical => {
                 args.push("--sat-solver".into());
                 args.push("cadical".into());
@@ -324,6 +354,16 @@ pub fn handle_solver_args(
     }
 }
 
+/// Whether the cover at the end of a harness verified with `--int-abstraction` isn't satisfied,
+/// i.e., whether the abstraction pruned every execution of the harness.
+fn is_end_unreachable(properties: &[Property]) -> bool {
+    properties.iter().any(|property| {
+        property.is_cover_property()
+            && property.description == INT_ABSTRACTION_COVER
+            && property.status != CheckStatus::Satisfied
+    })
+}
+
 impl VerificationResult {
     /// Computes a `VerificationResult` (kani-driver's notion of the result of a CBMC call) from a
     /// `VerificationOutput` (cbmc_output_parser's idea of CBMC results).
@@ -333,9 +373,13 @@ impl VerificationResult {
     ///       (CBMC will regularly report "failure" but that's just our cover checks.)
     ///   2. Positively checking for the presence of results.
     ///       (Do not mistake lack of results for success: report it as failure.)
+    ///
+    /// The failed properties for which `is_warning` holds are reported as warnings, and don't
+    /// affect the verification status.
     fn from(
         output: VerificationOutput,
         should_panic: bool,
+        is_warning: impl Fn(&Property) -> bool,
         start_time: Instant,
     ) -> VerificationResult {
         let runtime = start_time.elapsed();
@@ -343,8 +387,13 @@ fn from(
 
         if let Some(results) = results {
             let (status, failed_properties) =
-                verification_outcome_from_properties(&results, should_panic);
+                verification_outcome_from_properties(&results, should_panic, &is_warning);
             let coverage_results = coverage_results_from_properties(&results);
+            let warnings = results
+                .iter()
+                .filter(|prop| prop.status == CheckStatus::Failure && is_warning(prop))
+                .map(Property::property_name)
+                .collect();
             VerificationResult {
                 status,
                 failed_properties,
@@ -352,6 +401,7 @@ fn from(
                 runtime,
                 generated_concrete_test: false,
                 coverage_results,
+                warnings,
             }
         } else {
             // We never got results from CBMC - something went wrong (e.g. crash) so it's failure
@@ -367,6 +417,7 @@ fn from(
                 runtime,
                 generated_concrete_test: false,
                 coverage_results: None,
+                warnings: vec![],
             }
         }
     }
@@ -379,9 +430,31 @@ pub fn mock_success() -> VerificationResult {
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
             coverage_results: None,
+            warnings: vec![],
         }
     }
 
+    /// The result of a harness that was not verified because the `--total-timeout` was reached.
+    ///
+    /// A harness that wasn't verified isn't proven, so its status is a failure, and it makes the
+    /// run fail like a harness that failed verification. It's only reported separately.
+    pub fn skipped() -> VerificationResult {
+        VerificationResult {
+            status: VerificationStatus::Failure,
+            failed_properties: FailedProperties::None,
+            results: Err(ExitStatus::Skipped),
+            runtime: Duration::from_secs(0),
+            generated_concrete_test: false,
+            coverage_results: None,
+            warnings: vec![],
+        }
+    }
+
+    /// Whether the harness was not verified because the `--total-timeout` was reached.
+    pub fn is_skipped(&self) -> bool {
+        matches!(self.results, Err(ExitStatus::Skipped))
+    }
+
     fn mock_failure() -> VerificationResult {
         VerificationResult {
             status: VerificationStatus::Failure,
@@ -393,10 +466,12 @@ fn mock_failure() -> VerificationResult {
             runtime: Duration::from_secs(0),
             generated_concrete_test: false,
             coverage_results: None,
+            warnings: vec![],
         }
     }
 
-    pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String {
+    pub fn render(&self, output_format: &OutputFormat, harness: &HarnessMetadata) -> String {
+        let should_panic = harness.attributes.should_panic;
         match &self.results {
             Ok(results) => {
                 let status = self.status;
@@ -411,13 +486,84 @@ pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String
                         should_panic,
                         failed_properties,
                         show_checks,
+                        &harness.type_states,
                     )
                 } else {
-                    format_result(results, status, should_panic, failed_properties, show_checks)
+                    format_result(
+                        results,
+                        status,
+                        should_panic,
+                        failed_properties,
+                        show_checks,
+                        &harness.type_states,
+                    )
                 };
+                if !harness.attributes.disabled_checks.is_empty() {
+                    let checks: Vec<_> = harness
+                        .attributes
+                        .disabled_checks
+                        .iter()
+                        .map(|check| format!("`{}`", check.as_ref()))
+                        .collect();
+                    writeln!(
+                        result,
+                        "** WARNING: The following checks were disabled for this harness, so the \
+                        properties they cover were not verified: {}",
+                        checks.join(", ")
+                    )
+                    .unwrap();
+                }
+                if !harness.assumed_ok.is_empty() {
+                    let names: Vec<_> =
+                        harness.assumed_ok.iter().map(|name| format!("`{name}`")).collect();
+                    writeln!(
+                        result,
+                        "** WARNING: The calls to the following functions were assumed to return \
+                        `Ok` with `--assume-ok`, so this result doesn't cover their error paths: {}",
+                        names.join(", ")
+                    )
+                    .unwrap();
+                }
+                if let Some(bits) = harness.int_abstraction_bits {
+                    writeln!(
+                        result,
+                        "** WARNING: The integers of the crate under verification were restricted \
+                        to {bits} bits with `--int-abstraction`, so this result doesn't cover the \
+                        executions where an integer doesn't fit in {bits} bits"
+                    )
+                    .unwrap();
+                    if !should_panic && is_end_unreachable(results) {
+                        writeln!(
+                            result,
+                            "** WARNING: No execution of this harness reaches its end with \
+                            `--int-abstraction`, so its result may be vacuous. Check it with more \
+                            bits or without the abstraction"
+                        )
+                        .unwrap();
+                    }
+                }
+                if !self.warnings.is_empty() {
+                    let names: Vec<_> =
+                        self.warnings.iter().map(|name| format!("`{name}`")).collect();
+                    writeln!(
+                        result,
+                        "** WARNING: {} failed properties have the `warning` severity, so they \
+                        don't fail the verification: {}",
+                        names.len(),
+                        names.join(", ")
+                    )
+                    .unwrap();
+                }
                 writeln!(result, "Verification Time: {}s", self.runtime.as_secs_f32()).unwrap();
                 result
             }
+            Err(ExitStatus::Skipped) => {
+                let verification_result = console::style("SKIPPED").yellow();
+                format!(
+                    "\nThe total timeout was reached before the harness was verified\n\
+                    VERIFICATION:- {verification_result} (budget exhausted)\n"
+                )
+            }
             Err(exit_status) => {
                 let verification_result = console::style("FAILED").red();
                 let (header, explanation) = match exit_status {
@@ -435,6 +581,7 @@ pub fn render(&self, output_format: &OutputFormat, should_panic: bool) -> String
                     ExitStatus::Other(exit_status) => {
                         (format!("CBMC failed with status {exit_status}"), "")
                     }
+                    ExitStatus::Skipped => unreachable!(),
                 };
                 format!(
                     "\n{header}\n\
@@ -456,12 +603,14 @@ pub fn failed_properties(&self) -> Vec<&Property> {
     }
 }
 
-/// We decide if verification succeeded based on properties, not (typically) on exit code
+/// We decide if verification succeeded based on properties, not (typically) on exit code.
+/// The failed properties with the `warning` severity are ignored.
 fn verification_outcome_from_properties(
     properties: &[Property],
     should_panic: bool,
+    is_warning: impl Fn(&Property) -> bool,
 ) -> (VerificationStatus, FailedProperties) {
-    let failed_properties = determine_failed_properties(properties);
+    let failed_properties = determine_failed_properties(properties, is_warning);
     let status = if should_panic {
         match failed_properties {
             FailedProperties::None | FailedProperties::Other => VerificationStatus::Failure,
@@ -477,17 +626,24 @@ fn verification_outcome_from_properties(
 }
 
 /// Determines the `FailedProperties` variant that corresponds to an array of properties
-fn determine_failed_properties(properties: &[Property]) -> FailedProperties {
-    let failed_properties: Vec<&Property> =
-        properties.iter().filter(|prop| prop.status == CheckStatus::Failure).collect();
+fn determine_failed_properties(
+    properties: &[Property],
+    is_warning: impl Fn(&Property) -> bool,
+) -> FailedProperties {
+    let failed_properties: Vec<&Property> = properties
+        .iter()
+        .filter(|prop| prop.status == CheckStatus::Failure && !is_warning(prop))
+        .collect();
     // Return `FAILURE` if there isn't at least one failed property
     if failed_properties.is_empty() {
         FailedProperties::None
     } else {
         // Check if all failed properties correspond to the `assertion` class.
-        // Note: Panics caused by `panic!` and `assert!` fall into this class.
-        let all_failed_checks_are_panics =
-            failed_properties.iter().all(|prop| prop.property_class() == "assertion");
+        // Note: Panics caused by `panic!` and `assert!` fall into this class, while the bounds
+        // checks of indexing operations have a class of their own.
+        let all_failed_checks_are_panics = failed_properties.iter().all(|prop| {
+            prop.property_class() == "assertion" || prop.property_class() == "index_out_of_bounds"
+        });
         if all_failed_checks_are_panics {
             FailedProperties::PanicsOnly
         } else {
@@ -564,6 +720,19 @@ fn coverage_results_from_properties(properties: &[Property]) -> Option<CoverageR
 
     Some(CoverageResults::new(coverage_results))
 }
+
+/// The solver used for a harness, which is the first one given by:
+/// 1. The `--solver` option.
+/// 2. The `#[kani::solver]` attribute of the harness.
+/// 3. The default solver.
+pub fn resolve_solver<'a>(
+    args: &'a VerificationArgs,
+    harness_solver: &'a Option<CbmcSolver>,
+) -> &'a CbmcSolver {
+    // `--solver` option takes precedence over attributes
+    args.solver.as_ref().or(harness_solver.as_ref()).unwrap_or(&DEFAULT_SOLVER)
+}
+
 /// Solve Unwind Value from conflicting inputs of unwind values. (--default-unwind, annotation-unwind, --unwind)
 pub fn resolve_unwind_value(
     args: &VerificationArgs,
@@ -577,7 +746,7 @@ pub fn resolve_unwind_value(
 #[cfg(test)]
 mod tests {
     use crate::args;
-    use crate::metadata::tests::mock_proof_harness;
+    use crate::metadata::tests::{mock_proof_harness, mock_property};
     use clap::Parser;
 
     use super::*;
@@ -613,4 +782,16 @@ fn resolve(args: &[&str], harness: &HarnessMetadata) -> Option<u32> {
         assert_eq!(resolve(&args_only_harness, &harness_some), Some(1));
         assert_eq!(resolve(&args_both, &harness_some), Some(1));
     }
+
+    #[test]
+    fn check_is_end_unreachable() {
+        let mut end = mock_property("cover", CheckStatus::Satisfied);
+        end.description = INT_ABSTRACTION_COVER.into();
+        assert!(!is_end_unreachable(&[end.clone()]));
+        end.status = CheckStatus::Unreachable;
+        assert!(is_end_unreachable(&[end.clone()]));
+        end.status = CheckStatus::Unsatisfiable;
+        assert!(is_end_unreachable(&[end]));
+        assert!(!is_end_unreachable(&[mock_property("cover", CheckStatus::Unsatisfiable)]));
+    }
 }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::Result;
+use clap::ValueEnum;
 use kani_metadata::UnstableFeature;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
@@ -64,6 +65,10 @@ pub fn compile_single_rust_file(
         rustc_args.push(OsString::from(outdir.as_os_str()));
         rustc_args.push("--crate-name".into());
         rustc_args.push(crate_name.into());
+        if let Some(target) = &self.args.target_triple {
+            rustc_args.push("--target".into());
+            rustc_args.push(target.into());
+        }
 
         if self.args.tests {
             // e.g. `tests/kani/Options/check_tests.rs` will fail because it already has it
@@ -128,6 +133,15 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
         if self.args.ignore_global_asm {
             flags.push("--ignore-global-asm".into());
         }
+        if self.args.no_missing_proof_warning {
+            flags.push("--no-missing-proof-warning".into());
+        }
+        for category in &self.args.deny_warnings {
+            flags.push(format!(
+                "--deny-warnings={}",
+                category.to_possible_value().unwrap().get_name()
+            ));
+        }
 
         // Users activate it via the command line switch
         if self.args.write_json_symtab {
@@ -138,6 +152,70 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
             flags.push("--enable-stubbing".into());
         }
 
+        if self.args.model_fs {
+            flags.push("--model-fs".into());
+        }
+
+        if self.args.cover_match_arms {
+            flags.push("--cover-match-arms".into());
+        }
+
+        if self.args.check_unfinished_code {
+            flags.push("--check-unfinished-code".into());
+        }
+
+        if self.args.assume_ok {
+            flags.push("--assume-ok".into());
+        }
+
+        if let Some(abstraction) = self.args.int_abstraction {
+            flags.push(format!("--int-abstraction-bits={}", abstraction.bits));
+        }
+
+        for path in &self.args.denylist {
+            flags.push(format!("--reachability-denylist={path}"));
+        }
+
+        for path in &self.args.allowlist {
+            flags.push(format!("--reachability-allowlist={path}"));
+        }
+
+        if self.args.inline_never_boundaries {
+            flags.push("--reachability-inline-never".into());
+        }
+
+        if self.args.spurious_cas_failures {
+            flags.push("--spurious-cas-failures".into());
+        }
+
+        if let Some(dir) = &self.args.export_reproducer {
+            // The compiler may run in a different directory, e.g., when invoked by cargo.
+            let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.clone());
+            flags.push(format!("--export-reproducer={}", dir.display()));
+        }
+
+        if self.args.list_harnesses.is_some() {
+            flags.push("--no-models".into());
+        }
+
+        if let Some(kind) = self.args.emit_mir {
+            // The compiler may run in a different directory, e.g., when invoked by cargo.
+            let dir = std::path::absolute("kani-mir").unwrap_or_else(|_| PathBuf::from("kani-mir"));
+            flags.push(format!("--emit-mir={}", kind.to_possible_value().unwrap().get_name()));
+            flags.push(format!("--emit-mir-dir={}", dir.display()));
+        }
+
+        if let Some(mode) = self.args.arithmetic_overflow {
+            flags.push(format!(
+                "--arithmetic-overflow={}",
+                mode.to_possible_value().unwrap().get_name()
+            ));
+        }
+
+        if let Some(model) = self.args.memory_model {
+            flags.push(format!("--memory-model={}", model.to_possible_value().unwrap().get_name()));
+        }
+
         if self.args.coverage {
             flags.push("--coverage-checks".into());
         }
@@ -153,6 +231,14 @@ pub fn kani_compiler_flags(&self) -> Vec<String> {
             flags.push("--ub-check=uninit".into());
         }
 
+        if self.args.common_args.unstable_features.contains(UnstableFeature::PointerOffsetChecks) {
+            flags.push("--ub-check=pointer_offset".into())
+        }
+
+        if self.args.common_args.unstable_features.contains(UnstableFeature::PinChecks) {
+            flags.push("--ub-check=pin".into())
+        }
+
         if self.args.print_llbc {
             flags.push("--print-llbc".into());
         }
@@ -181,7 +267,6 @@ pub fn kani_rustc_flags(&self, lib_config: LibConfig) -> Vec<OsString> {
                 "panic_abort_tests=yes",
                 "-Z",
                 "mir-enable-passes=-RemoveStorageMarkers",
-                "--check-cfg=cfg(kani)",
             ]
             .map(OsString::from),
         );
@@ -221,6 +306,7 @@ pub fn base_rustc_flags(lib_config: LibConfig) -> Vec<OsString> {
         "-Z",
         "always-encode-mir",
         "--cfg=kani",
+        "--check-cfg=cfg(kani)",
         "-Z",
         "crate-attr=feature(register_tool)",
         "-Z",
@@ -97,6 +97,7 @@ pub struct PropertyId {
 impl Property {
     const COVER_PROPERTY_CLASS: &'static str = "cover";
     const COVERAGE_PROPERTY_CLASS: &'static str = "code_coverage";
+    const UNFINISHED_CODE_PROPERTY_CLASS: &'static str = "unfinished_code";
 
     pub fn property_class(&self) -> String {
         self.property_id.class.clone()
@@ -112,6 +113,12 @@ pub fn is_cover_property(&self) -> bool {
         self.property_id.class == Self::COVER_PROPERTY_CLASS
     }
 
+    /// Returns true if this property checks that a `todo!()` or `unimplemented!()` call is not
+    /// reached
+    pub fn is_unfinished_code_property(&self) -> bool {
+        self.property_id.class == Self::UNFINISHED_CODE_PROPERTY_CLASS
+    }
+
     pub fn property_name(&self) -> String {
         let class = &self.property_id.class;
         let id = self.property_id.id;
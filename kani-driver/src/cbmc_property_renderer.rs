@@ -3,14 +3,27 @@
 
 use crate::args::OutputFormat;
 use crate::call_cbmc::{FailedProperties, VerificationStatus};
-use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, TraceItem};
+use crate::cbmc_output_parser::{CheckStatus, ParserItem, Property, TraceData, TraceItem};
 use crate::coverage::cov_results::CoverageResults;
 use console::style;
+use kani_metadata::TypeStateVariable;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use rustc_demangle::demangle;
 use std::collections::HashMap;
 
+/// Function used by `assert!` to store the values captured by its message.
+const CAPTURE_VALUE_FUNCTION: &str = "kani::internal::capture_value";
+
+/// Prefix of the flags set by the compiler whenever a value is converted into a `dyn Error`.
+///
+/// The concrete type of the error follows the prefix, e.g. `kani::error_type::<ParseError>`.
+const DYN_ERROR_MARKER: &str = "kani::error_type::<";
+const OPAQUE_RETURN_MARKER: &str = "kani::opaque_return::";
+
+/// Matches escaped braces and the arguments of a format string.
+static FORMAT_ARG_PAT: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{\{|\}\}|\{([^{}]*)\}").unwrap());
+
 type CbmcAltDescriptions = HashMap<&'static str, Vec<(&'static str, Option<&'static str>)>>;
 
 /// Hash map that relates property classes with descriptions, used by
@@ -219,6 +232,7 @@ pub fn format_result(
     should_panic: bool,
     failed_properties: FailedProperties,
     show_checks: bool,
+    type_states: &[TypeStateVariable],
 ) -> String {
     let mut result_str = String::new();
     let mut number_checks_failed = 0;
@@ -352,8 +366,15 @@ pub fn format_result(
     for prop in failed_tests {
         let failure_message = build_failure_message(prop.description.clone(), &prop.trace.clone());
         result_str.push_str(&failure_message);
+        if let Some(trace) = &prop.trace {
+            result_str.push_str(&render_type_states(trace, type_states));
+            result_str.push_str(&render_error_types(trace));
+            result_str.push_str(&render_opaque_returns(trace));
+        }
     }
 
+    result_str.push_str(&render_unfinished_code(properties));
+
     let verification_result = if status == VerificationStatus::Success {
         style("SUCCESSFUL").green()
     } else {
@@ -405,12 +426,19 @@ pub fn format_coverage(
     should_panic: bool,
     failed_properties: FailedProperties,
     show_checks: bool,
+    type_states: &[TypeStateVariable],
 ) -> String {
     let (_coverage_checks, non_coverage_checks): (Vec<Property>, Vec<Property>) =
         properties.iter().cloned().partition(|x| x.property_class() == "code_coverage");
 
-    let verification_output =
-        format_result(&non_coverage_checks, status, should_panic, failed_properties, show_checks);
+    let verification_output = format_result(
+        &non_coverage_checks,
+        status,
+        should_panic,
+        failed_properties,
+        show_checks,
+        type_states,
+    );
     let cov_results_intro = "Source-based code coverage results:";
     let result = format!("{}\n{}\n\n{}", verification_output, cov_results_intro, cov_results);
 
@@ -446,6 +474,131 @@ fn build_failure_message(description: String, trace: &Option<Vec<TraceItem>>) ->
     backup_failure_message
 }
 
+/// Render the type of the type-state variables that were assigned in the trace of a failed
+/// property, e.g.:
+///
+/// ```text
+///  Type states at failure:
+///   - `door: Door<Open>` in check_door
+/// ```
+///
+/// Shadowed variables share the same name, so we only render the variable that was assigned
+/// last for each name, which is the one that holds the state at the time of the failure.
+fn render_type_states(trace: &[TraceItem], type_states: &[TypeStateVariable]) -> String {
+    let mut last_states: Vec<&TypeStateVariable> = vec![];
+    for item in trace.iter().filter(|item| item.step_type == "assignment") {
+        let Some(lhs) = &item.lhs else { continue };
+        let Some(state) = type_states.iter().find(|state| {
+            lhs.strip_prefix(state.symbol.as_str()).is_some_and(|rest| {
+                !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == ':')
+            })
+        }) else {
+            continue;
+        };
+        last_states.retain(|last| {
+            (last.function.as_str(), last.variable.as_str())
+                != (state.function.as_str(), state.variable.as_str())
+        });
+        last_states.push(state);
+    }
+    if last_states.is_empty() {
+        return String::new();
+    }
+    let mut result = String::from(" Type states at failure:\n");
+    for state in last_states {
+        result.push_str(&format!("  - `{}: {}` in {}\n", state.variable, state.ty, state.function));
+    }
+    result
+}
+
+/// Render the concrete types of the errors that were converted into a `dyn Error` in the trace,
+/// in the order they were first produced. This usually happens when `?` converts an error into
+/// a `Box<dyn Error>`.
+fn render_error_types(trace: &[TraceItem]) -> String {
+    let mut error_types: Vec<&str> = vec![];
+    for item in trace.iter().filter(|item| item.step_type == "assignment") {
+        let Some(error_type) = item
+            .lhs
+            .as_deref()
+            .and_then(|lhs| lhs.strip_prefix(DYN_ERROR_MARKER))
+            .and_then(|rest| rest.strip_suffix('>'))
+        else {
+            continue;
+        };
+        // Skip the initialization of the flag.
+        let is_set = item.value.as_ref().and_then(|value| value.data.as_ref()).is_some_and(
+            |data| match data {
+                TraceData::Bool(value) => *value,
+                TraceData::NonBool(value) => value == "true" || value == "1",
+            },
+        );
+        if is_set && !error_types.contains(&error_type) {
+            error_types.push(error_type);
+        }
+    }
+    if error_types.is_empty() {
+        return String::new();
+    }
+    let error_types: Vec<String> = error_types.iter().map(|ty| format!("`{ty}`")).collect();
+    format!(" Errors converted to `dyn Error`: {}\n", error_types.join(", "))
+}
+
+/// Render the concrete types returned by the functions declared to return an `impl Trait` that
+/// were called in the trace, in the order they were first called, e.g.:
+///
+/// ```text
+///  Opaque return types:
+///   - `evens` returns `std::iter::Filter<std::ops::Range<u8>, {closure@src/main.rs:8:25}>`
+/// ```
+fn render_opaque_returns(trace: &[TraceItem]) -> String {
+    let mut returns: Vec<(&str, &str)> = vec![];
+    for item in trace.iter().filter(|item| item.step_type == "assignment") {
+        let Some((function, concrete)) = item
+            .lhs
+            .as_deref()
+            .and_then(|lhs| lhs.strip_prefix(OPAQUE_RETURN_MARKER))
+            .and_then(|rest| rest.split_once(" returns "))
+        else {
+            continue;
+        };
+        // Skip the initialization of the flag.
+        let is_set = item.value.as_ref().and_then(|value| value.data.as_ref()).is_some_and(
+            |data| match data {
+                TraceData::Bool(value) => *value,
+                TraceData::NonBool(value) => value == "true" || value == "1",
+            },
+        );
+        if is_set && !returns.contains(&(function, concrete)) {
+            returns.push((function, concrete));
+        }
+    }
+    if returns.is_empty() {
+        return String::new();
+    }
+    let mut result = String::from(" Opaque return types:\n");
+    for (function, concrete) in returns {
+        result.push_str(&format!("  - `{function}` returns `{concrete}`\n"));
+    }
+    result
+}
+
+/// List the `todo!()` and `unimplemented!()` calls that were reached, which are only reported as
+/// unfinished code with `--check-unfinished-code`.
+fn render_unfinished_code(properties: &[Property]) -> String {
+    let reached: Vec<&Property> = properties
+        .iter()
+        .filter(|prop| prop.is_unfinished_code_property() && prop.status == CheckStatus::Failure)
+        .collect();
+    if reached.is_empty() {
+        return String::new();
+    }
+    let mut result = String::from("\nReached unfinished code:\n");
+    for prop in reached {
+        result.push_str(&format!(" - {}\n   at {}\n", prop.description, prop.source_location));
+    }
+    result
+}
+
 /// Edits an error message.
 ///
 /// At present, we only know one case where CBMC emits an error message, related
@@ -513,7 +666,8 @@ pub fn postprocess_result(properties: Vec<Property>, extra_ptr_checks: bool) ->
     let updated_properties =
         update_properties_with_reach_status(properties_filtered, has_fundamental_failures);
     let results_after_code_coverage = update_results_of_code_covererage_checks(updated_properties);
-    update_results_of_cover_checks(results_after_code_coverage)
+    let results_after_cover = update_results_of_cover_checks(results_after_code_coverage);
+    render_captured_values(results_after_cover)
 }
 
 /// Determines if there is property with status `FAILURE` and the given description
@@ -527,7 +681,7 @@ fn has_check_failure(properties: &Vec<Property>, description: &str) -> bool {
 }
 
 // Determines if there were unwinding assertion failures in a set of properties
-fn has_unwinding_assertion_failures(properties: &Vec<Property>) -> bool {
+pub(crate) fn has_unwinding_assertion_failures(properties: &Vec<Property>) -> bool {
     has_check_failure(&properties, UNWINDING_ASSERT_DESC)
         || has_check_failure(&properties, UNWINDING_ASSERT_REC_DESC)
 }
@@ -774,3 +928,208 @@ fn annotate_properties_with_reach_results(
     }
     properties
 }
+
+/// Return the names of the variables captured by a property description, in the order they
+/// appear, e.g. `["x", "y"]` for `"x={x} y={y:?}"`.
+///
+/// Values are only captured if every format argument is a variable name. For any other
+/// description, this returns an empty vector.
+pub fn captured_names(description: &str) -> Vec<&str> {
+    let mut names = vec![];
+    for arg in FORMAT_ARG_PAT.captures_iter(description).filter_map(|capture| capture.get(1)) {
+        let name = arg.as_str().split(':').next().unwrap().trim();
+        let mut chars = name.chars();
+        let is_ident = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_');
+        if !is_ident {
+            return vec![];
+        }
+        names.push(name);
+    }
+    names
+}
+
+/// Replace the variables captured in the description of failed `assert!` properties by
+/// the values they had in the counterexample.
+///
+/// The values are recorded right before the assertion fails, so we use the last values captured
+/// in the trace.
+fn render_captured_values(mut properties: Vec<Property>) -> Vec<Property> {
+    for prop in properties.iter_mut().filter(|prop| prop.status == CheckStatus::Failure) {
+        let num_captures = captured_names(&prop.description).len();
+        let Some(trace) = &prop.trace else { continue };
+        if num_captures == 0 {
+            continue;
+        }
+        let values: Vec<String> = trace
+            .iter()
+            .filter(|item| {
+                item.step_type == "assignment"
+                    && item.lhs.as_ref().is_some_and(|lhs| lhs.contains("captured_value"))
+                    && item.source_location.as_ref().is_some_and(|loc| {
+                        loc.function
+                            .as_ref()
+                            .is_some_and(|function| function.starts_with(CAPTURE_VALUE_FUNCTION))
+                    })
+            })
+            .filter_map(|item| Some(item.value.as_ref()?.data.as_ref()?.to_string()))
+            .collect();
+        if values.len() < num_captures {
+            continue;
+        }
+        let mut captured = values[values.len() - num_captures..].iter();
+        prop.description = FORMAT_ARG_PAT
+            .replace_all(&prop.description, |capture: &regex::Captures| {
+                match capture.get(0).unwrap().as_str() {
+                    "{{" => "{".to_string(),
+                    "}}" => "}".to_string(),
+                    _ => captured.next().unwrap().clone(),
+                }
+            })
+            .to_string();
+    }
+    properties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::TraceValue;
+
+    #[test]
+    fn check_captured_names() {
+        assert_eq!(captured_names("x={x} y={y:?}"), vec!["x", "y"]);
+        assert_eq!(captured_names("{{x}} = {_x1:>4}"), vec!["_x1"]);
+        assert!(captured_names("assertion failed: x < y").is_empty());
+        assert!(captured_names("\"{}\", x").is_empty());
+        assert!(captured_names("{0} {x}").is_empty());
+    }
+
+    #[test]
+    fn check_render_captured_values() {
+        let capture = |value: &str| {
+            format!(
+                r#"{{ "stepType": "assignment", "lhs": "captured_value",
+                "sourceLocation": {{ "function": "kani::internal::capture_value::<u8>" }},
+                "value": {{ "data": "{value}" }} }}"#
+            )
+        };
+        let property = format!(
+            r#"{{ "description": "x={{x}} y={{y}}", "property": "check.assertion.1",
+            "sourceLocation": {{}}, "status": "FAILURE",
+            "trace": [{}, {}, {}] }}"#,
+            capture("1"),
+            capture("3"),
+            capture("2")
+        );
+        let property: Property = serde_json::from_str(&property).unwrap();
+        let rendered = render_captured_values(vec![property]);
+        assert_eq!(rendered[0].description, "x=3 y=2");
+    }
+
+    #[test]
+    fn check_render_type_states() {
+        let state = |symbol: &str, ty: &str| TypeStateVariable {
+            symbol: symbol.to_string(),
+            variable: "door".to_string(),
+            function: "check_door".to_string(),
+            ty: ty.to_string(),
+        };
+        let type_states = [
+            state("_RNv::1::var_1::door", "Door<Closed>"),
+            state("_RNv::1::var_2::door", "Door<Open>"),
+        ];
+        let assign = |lhs: &str| TraceItem {
+            step_type: "assignment".to_string(),
+            lhs: Some(lhs.to_string()),
+            source_location: None,
+            value: None,
+        };
+        let trace = [
+            assign("_RNv::1::var_1::door"),
+            assign("_RNv::1::var_2::door.id"),
+            assign("_RNv::1::var_10::door"),
+        ];
+        assert_eq!(
+            render_type_states(&trace, &type_states),
+            " Type states at failure:\n  - `door: Door<Open>` in check_door\n"
+        );
+        assert!(render_type_states(&trace[2..], &type_states).is_empty());
+    }
+
+    #[test]
+    fn check_render_error_types() {
+        let assign = |lhs: &str, value: bool| TraceItem {
+            step_type: "assignment".to_string(),
+            lhs: Some(lhs.to_string()),
+            source_location: None,
+            value: Some(TraceValue {
+                binary: None,
+                data: Some(TraceData::Bool(value)),
+                width: None,
+            }),
+        };
+        let trace = [
+            assign("kani::error_type::<ParseError>", false),
+            assign("kani::error_type::<std::num::ParseIntError>", true),
+            assign("kani::error_type::<ParseError>", true),
+            assign("kani::error_type::<std::num::ParseIntError>", true),
+            assign("_RNv::1::var_1::error", true),
+        ];
+        assert_eq!(
+            render_error_types(&trace),
+            " Errors converted to `dyn Error`: `std::num::ParseIntError`, `ParseError`\n"
+        );
+        assert!(render_error_types(&trace[..1]).is_empty());
+    }
+
+    #[test]
+    fn check_render_opaque_returns() {
+        let assign = |lhs: &str, value: bool| TraceItem {
+            step_type: "assignment".to_string(),
+            lhs: Some(lhs.to_string()),
+            source_location: None,
+            value: Some(TraceValue {
+                binary: None,
+                data: Some(TraceData::Bool(value)),
+                width: None,
+            }),
+        };
+        let trace = [
+            assign("kani::opaque_return::make_shape returns Square", false),
+            assign("kani::opaque_return::digits returns std::ops::Range<u8>", true),
+            assign("kani::opaque_return::make_shape returns Square", true),
+            assign("kani::opaque_return::digits returns std::ops::Range<u8>", true),
+            assign("_RNv::1::var_1::shape", true),
+        ];
+        assert_eq!(
+            render_opaque_returns(&trace),
+            " Opaque return types:\n  - `digits` returns `std::ops::Range<u8>`\n  - `make_shape` \
+            returns `Square`\n"
+        );
+        assert!(render_opaque_returns(&trace[..1]).is_empty());
+    }
+
+    #[test]
+    fn check_render_unfinished_code() {
+        let property = |class: &str, status: &str, line: u32| {
+            let property = format!(
+                r#"{{ "description": "reached `todo!`: not yet implemented",
+                "property": "check.{class}.{line}",
+                "sourceLocation": {{ "file": "src/lib.rs", "line": "{line}", "column": "5" }},
+                "status": "{status}" }}"#
+            );
+            serde_json::from_str::<Property>(&property).unwrap()
+        };
+        let properties = [
+            property("unfinished_code", "FAILURE", 1),
+            property("unfinished_code", "SUCCESS", 2),
+            property("assertion", "FAILURE", 3),
+        ];
+        assert_eq!(
+            render_unfinished_code(&properties),
+            "\nReached unfinished code:\n - reached `todo!`: not yet implemented\n   at src/lib.rs:1:5\n"
+        );
+        assert!(render_unfinished_code(&properties[1..]).is_empty());
+    }
+}
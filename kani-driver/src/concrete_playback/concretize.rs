@@ -0,0 +1,154 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Run the harnesses as randomized tests, with the values of `kani::any()` generated from a seed.
+//!
+//! This is used to quickly exercise the harnesses without the solver, e.g., to test the soundness
+//! of Kani itself by comparing the concrete runs with the verification results. The values are
+//! generated by the Kani library during concrete playback, so the same seed reproduces the same
+//! run.
+//!
+//! Runs that violate a `kani::assume` are discarded, and the harness is run again with the next
+//! seed, up to [MAX_ATTEMPTS] times.
+
+use super::replay::PlaybackTest;
+use crate::session::KaniSession;
+use anyhow::{Result, bail};
+use kani_metadata::HarnessMetadata;
+use std::path::Path;
+use std::process::Output;
+
+/// The maximum number of runs of a harness, including the ones that are discarded.
+const MAX_ATTEMPTS: u64 = 100;
+
+/// The message of the panic raised by `kani::assume` during concrete playback.
+const ASSUME_PANIC_MSG: &str = "`kani::assume` should always hold";
+
+/// The outcome of a concrete run of a harness.
+#[derive(Debug, PartialEq, Eq)]
+enum RunOutcome {
+    Passed,
+    /// The values violated an assumption of the harness.
+    Discarded,
+    /// The harness panicked, with the given panic messages.
+    Failed(Vec<String>),
+}
+
+impl KaniSession {
+    /// Run each of the given harnesses with values generated from the seed, instead of verifying
+    /// them, and fail if any of them panics.
+    pub(crate) fn run_concretized(
+        &self,
+        harnesses: &[&HarnessMetadata],
+        input: Option<&Path>,
+        seed: u64,
+    ) -> Result<()> {
+        let Some(input) = input else {
+            bail!(
+                "The `--concretize-seed` argument is only supported when verifying a single file."
+            );
+        };
+
+        let mut failed = 0;
+        for harness in harnesses {
+            let playback = PlaybackTest::build(self, harness, input)?;
+            let mut outcome = RunOutcome::Discarded;
+            let mut attempt = 0;
+            while outcome == RunOutcome::Discarded && attempt < MAX_ATTEMPTS {
+                outcome = run_outcome(&playback.run_seeded(seed.wrapping_add(attempt))?);
+                attempt += 1;
+            }
+            let last_seed = seed.wrapping_add(attempt - 1);
+            match outcome {
+                RunOutcome::Passed => {
+                    if !self.args.common_args.quiet {
+                        println!("Harness `{}` passed with seed {last_seed}", harness.pretty_name);
+                    }
+                }
+                RunOutcome::Discarded => println!(
+                    "WARNING: All the {MAX_ATTEMPTS} runs of harness `{}` violated its \
+                    assumptions, starting from seed {seed}",
+                    harness.pretty_name
+                ),
+                RunOutcome::Failed(panics) => {
+                    failed += 1;
+                    println!("{}", render_failure(harness, last_seed, &panics));
+                }
+            }
+        }
+        if failed > 0 {
+            bail!("{failed} of {} harnesses failed with seed {seed}", harnesses.len());
+        }
+        Ok(())
+    }
+}
+
+fn run_outcome(output: &Output) -> RunOutcome {
+    if output.status.success() {
+        return RunOutcome::Passed;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stdout.contains(ASSUME_PANIC_MSG) || stderr.contains(ASSUME_PANIC_MSG) {
+        return RunOutcome::Discarded;
+    }
+    RunOutcome::Failed(
+        stdout
+            .lines()
+            .chain(stderr.lines())
+            .filter(|line| line.contains("panicked at"))
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+fn render_failure(harness: &HarnessMetadata, seed: u64, panics: &[String]) -> String {
+    let panics = if panics.is_empty() {
+        "the playback test failed".to_string()
+    } else {
+        panics.join("\n    ")
+    };
+    format!("Harness `{}` failed with seed {seed}:\n    {panics}", harness.pretty_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    fn mock_output(code: i32, stderr: &str) -> Output {
+        Output {
+            status: ExitStatus::from_raw(code << 8),
+            stdout: b"running 1 test\n".to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn check_run_outcome() {
+        assert_eq!(run_outcome(&mock_output(0, "")), RunOutcome::Passed);
+        let assume = "thread 'kani_replay' panicked at lib.rs:10:5:\n\
+            `kani::assume` should always hold\n";
+        assert_eq!(run_outcome(&mock_output(101, assume)), RunOutcome::Discarded);
+        let panic = "thread 'kani_replay' panicked at main.rs:3:5:\nassertion failed: x < 10\n";
+        assert_eq!(
+            run_outcome(&mock_output(101, panic)),
+            RunOutcome::Failed(vec!["thread 'kani_replay' panicked at main.rs:3:5:".into()])
+        );
+    }
+
+    #[test]
+    fn check_render_failure() {
+        let harness = mock_proof_harness("module::check_add", None, None, None);
+        assert_eq!(
+            render_failure(&harness, 7, &["panicked at main.rs:3:5:".into()]),
+            "Harness `module::check_add` failed with seed 7:\n    panicked at main.rs:3:5:"
+        );
+        assert_eq!(
+            render_failure(&harness, 7, &[]),
+            "Harness `module::check_add` failed with seed 7:\n    the playback test failed"
+        );
+    }
+}
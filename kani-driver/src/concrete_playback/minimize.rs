@@ -0,0 +1,260 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Shrink the concrete values of a counterexample into a simpler input that still fails.
+//!
+//! Candidates are checked with concrete playback: we build a test that plays back the harness
+//! once, and we run it with each candidate given through the `KANI_CONCRETE_VALS` environment
+//! variable. A candidate is accepted if the test panics at the same location as the original
+//! failure. Each value is shrunk in turn until none of them can be simplified.
+
+use super::replay::PlaybackTest;
+use super::test_generator::concrete_vals_extractor::ConcreteVal;
+use crate::cbmc_output_parser::Property;
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use std::path::Path;
+
+/// We stop shrinking after running this many candidates.
+const MAX_RUNS: usize = 1000;
+
+impl KaniSession {
+    /// Minimize the concrete values of every failure of the given harness.
+    ///
+    /// The values are kept as is for cover statements and for failures that concrete playback
+    /// doesn't reproduce.
+    pub(super) fn minimize_harness_values<'a>(
+        &self,
+        harness: &HarnessMetadata,
+        input: Option<&Path>,
+        harness_values: Vec<(&'a Property, Vec<ConcreteVal>)>,
+    ) -> Result<Vec<(&'a Property, Vec<ConcreteVal>)>> {
+        let Some(input) = input else {
+            println!(
+                "WARNING: Kani could not minimize the inputs of `{}` because `--minimize` is only \
+                supported when verifying a single file.",
+                harness.pretty_name
+            );
+            return Ok(harness_values);
+        };
+        if harness_values.iter().all(|(_, values)| values.is_empty()) {
+            return Ok(harness_values);
+        }
+        let playback = PlaybackTest::build(self, harness, input)?;
+        harness_values
+            .into_iter()
+            .map(|(property, values)| {
+                // Cover statements don't panic, so there is no failure to reproduce.
+                if property.is_cover_property() {
+                    return Ok((property, values));
+                }
+                let values = playback.minimize(self, harness, property, values)?;
+                Ok((property, values))
+            })
+            .collect()
+    }
+}
+
+impl PlaybackTest {
+    /// Return whether the test fails at the location of the property with the given values.
+    fn fails(&self, property: &Property, values: &[Vec<u8>]) -> Result<bool> {
+        let output = self.run(values)?;
+        if output.status.success() {
+            return Ok(false);
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(is_same_failure(property, &format!("{stdout}{stderr}")))
+    }
+
+    /// Shrink each value in turn, and start over whenever a value changes.
+    fn minimize(
+        &self,
+        session: &KaniSession,
+        harness: &HarnessMetadata,
+        property: &Property,
+        mut values: Vec<ConcreteVal>,
+    ) -> Result<Vec<ConcreteVal>> {
+        let mut bytes: Vec<Vec<u8>> = values.iter().map(|value| value.byte_arr.clone()).collect();
+        if bytes.is_empty() {
+            return Ok(values);
+        }
+        if !self.fails(property, &bytes)? {
+            println!(
+                "WARNING: Kani could not minimize the inputs of `{}` for `{}` because concrete \
+                playback does not reproduce the failure.",
+                harness.pretty_name, property.description
+            );
+            return Ok(values);
+        }
+        let mut runs = 1;
+        let mut changed = true;
+        while changed && runs < MAX_RUNS {
+            changed = false;
+            for idx in 0..values.len() {
+                for candidate in shrink_candidates(&values[idx]) {
+                    if runs >= MAX_RUNS {
+                        break;
+                    }
+                    runs += 1;
+                    let original = std::mem::replace(&mut bytes[idx], candidate.clone());
+                    if self.fails(property, &bytes)? {
+                        values[idx] = simplified_value(&values[idx], candidate);
+                        changed = true;
+                        break;
+                    }
+                    bytes[idx] = original;
+                }
+            }
+        }
+        if !session.args.common_args.quiet {
+            println!(
+                "INFO: Minimized the inputs of `{}` for `{}` after {runs} playback runs.",
+                harness.pretty_name, property.description
+            );
+        }
+        Ok(values)
+    }
+}
+
+/// Whether the output of a failed test shows a panic at the location of the property.
+fn is_same_failure(property: &Property, output: &str) -> bool {
+    let location = &property.source_location;
+    let (Some(file), Some(line)) = (&location.file, &location.line) else {
+        // Without a location, accept any failure other than an unsatisfied assumption.
+        return !output.contains("`kani::assume` should always hold");
+    };
+    let file_name = Path::new(file).file_name().unwrap().to_string_lossy();
+    output.lines().any(|output_line| {
+        output_line.split_once("panicked at ").is_some_and(|(_, panic_location)| {
+            panic_location
+                .split_once(&format!(":{line}:"))
+                .is_some_and(|(path, _)| path.ends_with(file_name.as_ref()))
+        })
+    })
+}
+
+/// Return the candidates that may replace a value, from the simplest to the most complex.
+///
+/// Integers shrink towards zero by halving their distance to it, floats towards zero and their
+/// integral part, and values of any other type towards all bytes being zero.
+fn shrink_candidates(value: &ConcreteVal) -> Vec<Vec<u8>> {
+    let bytes = &value.byte_arr;
+    let zero = vec![0; bytes.len()];
+    if *bytes == zero {
+        return vec![];
+    }
+    match value.ty.as_deref() {
+        Some(ty @ ("f32" | "f64")) => {
+            let float = if ty == "f32" {
+                f32::from_le_bytes(bytes[..].try_into().unwrap()) as f64
+            } else {
+                f64::from_le_bytes(bytes[..].try_into().unwrap())
+            };
+            let mut candidates = vec![zero];
+            if float.is_finite() && float.trunc() != float {
+                let trunc = float.trunc();
+                candidates.push(if ty == "f32" {
+                    (trunc as f32).to_le_bytes().to_vec()
+                } else {
+                    trunc.to_le_bytes().to_vec()
+                });
+            }
+            candidates
+        }
+        Some(ty) if is_integer(ty) && bytes.len() <= 16 => {
+            let value = read_int(bytes, ty.starts_with('i'));
+            let mut candidates = vec![];
+            let mut distance = value;
+            while distance != 0 {
+                candidates.push(write_int(value - distance, bytes.len()));
+                distance /= 2;
+            }
+            candidates
+        }
+        _ => vec![zero],
+    }
+}
+
+/// Create the value for the given bytes, and render it for the generated test.
+fn simplified_value(original: &ConcreteVal, byte_arr: Vec<u8>) -> ConcreteVal {
+    let interp_val = match original.ty.as_deref() {
+        Some("f32") => f32::from_le_bytes(byte_arr[..].try_into().unwrap()).to_string(),
+        Some("f64") => f64::from_le_bytes(byte_arr[..].try_into().unwrap()).to_string(),
+        Some(ty) if is_integer(ty) && byte_arr.len() <= 16 => {
+            read_int(&byte_arr, ty.starts_with('i')).to_string()
+        }
+        _ => format!("{byte_arr:?}"),
+    };
+    ConcreteVal { byte_arr, interp_val, ty: original.ty.clone() }
+}
+
+fn is_integer(ty: &str) -> bool {
+    matches!(
+        ty,
+        "u8" | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "bool"
+            | "char"
+    )
+}
+
+/// Read a little-endian integer, sign-extending it if it's signed.
+fn read_int(bytes: &[u8], signed: bool) -> i128 {
+    let mut buf = [0u8; 16];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    let bits = 8 * bytes.len() as u32;
+    let value = u128::from_le_bytes(buf);
+    if signed && bits < 128 && (value >> (bits - 1)) & 1 == 1 {
+        (value | (u128::MAX << bits)) as i128
+    } else {
+        value as i128
+    }
+}
+
+/// Write an integer as little-endian bytes, truncated to the given size.
+fn write_int(value: i128, size: usize) -> Vec<u8> {
+    value.to_le_bytes()[..size].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(byte_arr: Vec<u8>, ty: &str) -> ConcreteVal {
+        ConcreteVal { byte_arr, interp_val: String::new(), ty: Some(ty.to_string()) }
+    }
+
+    #[test]
+    fn check_shrink_unsigned() {
+        let candidates = shrink_candidates(&value(vec![200], "u8"));
+        assert_eq!(candidates.first(), Some(&vec![0]));
+        assert_eq!(candidates.last(), Some(&vec![199]));
+        assert!(shrink_candidates(&value(vec![0, 0], "u16")).is_empty());
+    }
+
+    #[test]
+    fn check_shrink_signed() {
+        // -6 shrinks towards zero instead of towards `i8::MIN`.
+        let candidates = shrink_candidates(&value(vec![0xfa], "i8"));
+        let values: Vec<i128> = candidates.iter().map(|bytes| read_int(bytes, true)).collect();
+        assert_eq!(values, vec![0, -3, -5]);
+    }
+
+    #[test]
+    fn check_shrink_other() {
+        assert_eq!(shrink_candidates(&value(vec![1, 2], "Foo")), vec![vec![0, 0]]);
+        let float = value(2.5f32.to_le_bytes().to_vec(), "f32");
+        assert_eq!(shrink_candidates(&float), vec![vec![0; 4], 2.0f32.to_le_bytes().to_vec()]);
+    }
+}
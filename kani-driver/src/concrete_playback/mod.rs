@@ -2,5 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 //! Implements the logic related to concrete playback
 
+mod concretize;
+mod minimize;
+mod oracle;
 pub mod playback;
+mod replay;
 pub mod test_generator;
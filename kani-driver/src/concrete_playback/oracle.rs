@@ -0,0 +1,181 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the harnesses against a recorded oracle of expected outputs before verifying them.
+//!
+//! The oracle is a JSON file with a list of cases, each with the harness it applies to, the
+//! concrete values of its `kani::any()` calls, and the expected output:
+//!
+//! ```json
+//! [{ "harness": "check_add", "inputs": [[1, 0, 0, 0], [2, 0, 0, 0]], "expected": "3" }]
+//! ```
+//!
+//! Each case is played back concretely, and the outputs recorded by `kani::oracle_output` are
+//! compared with the expected output using their `Debug` representation. This is a fast smoke
+//! check, so the run is aborted if any of the cases fails.
+
+use super::replay::PlaybackTest;
+use crate::session::KaniSession;
+use anyhow::{Context, Result, bail};
+use kani_metadata::HarnessMetadata;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// The prefix of the lines printed by `kani::oracle_output` during concrete playback.
+const ORACLE_OUTPUT_PREFIX: &str = "kani-oracle-output: ";
+
+/// An input of a harness and the output it is expected to produce.
+#[derive(Debug, Deserialize)]
+struct OracleCase {
+    /// The name of the harness, which may be qualified.
+    harness: String,
+    /// The concrete values of the `kani::any()` calls of the harness, in the order of the calls.
+    inputs: Vec<Vec<u8>>,
+    /// The `Debug` representation of the expected output.
+    expected: String,
+}
+
+/// The outcome of playing back a case that doesn't produce the expected output.
+#[derive(Debug, PartialEq, Eq)]
+enum Mismatch {
+    /// The harness produced a different output.
+    Output(String),
+    /// The harness panicked, with the given panic messages.
+    Panic(Vec<String>),
+}
+
+impl KaniSession {
+    /// Play back the cases of the oracle for each of the given harnesses, and fail if any of them
+    /// doesn't produce the expected output.
+    pub(crate) fn check_oracle(
+        &self,
+        harnesses: &[&HarnessMetadata],
+        input: Option<&Path>,
+        oracle: &Path,
+    ) -> Result<()> {
+        let cases: Vec<OracleCase> = serde_json::from_reader(BufReader::new(
+            File::open(oracle)
+                .with_context(|| format!("Failed to open the oracle `{}`", oracle.display()))?,
+        ))
+        .with_context(|| format!("Failed to parse the oracle `{}`", oracle.display()))?;
+        let Some(input) = input else {
+            bail!("The `--oracle` argument is only supported when verifying a single file.");
+        };
+
+        let mut checked = 0;
+        let mut failed = 0;
+        let mut skipped = BTreeSet::new();
+        for harness in harnesses {
+            let harness_cases: Vec<_> =
+                cases.iter().filter(|case| is_case_of(case, harness)).collect();
+            if harness_cases.is_empty() {
+                continue;
+            }
+            let playback = PlaybackTest::build(self, harness, input)?;
+            for case in harness_cases {
+                checked += 1;
+                if let Some(mismatch) = check_case(&playback, case)? {
+                    failed += 1;
+                    println!("{}", render_mismatch(harness, case, &mismatch));
+                }
+            }
+        }
+        for case in &cases {
+            if !harnesses.iter().any(|harness| is_case_of(case, harness)) {
+                skipped.insert(case.harness.as_str());
+            }
+        }
+        if !skipped.is_empty() {
+            println!(
+                "WARNING: Skipped the oracle cases of the harnesses that were not selected for \
+                verification: `{}`",
+                skipped.into_iter().collect::<Vec<_>>().join("`, `")
+            );
+        }
+        if failed > 0 {
+            bail!("{failed} of {checked} oracle cases did not produce the expected output");
+        }
+        if !self.args.common_args.quiet {
+            println!("Checked {checked} oracle cases");
+        }
+        Ok(())
+    }
+}
+
+/// Whether the case applies to the given harness, either by its qualified or unqualified name.
+fn is_case_of(case: &OracleCase, harness: &HarnessMetadata) -> bool {
+    case.harness == harness.pretty_name || case.harness == harness.get_harness_name_unqualified()
+}
+
+/// Play back the case, and return how it differs from the expected output, if it does.
+fn check_case(playback: &PlaybackTest, case: &OracleCase) -> Result<Option<Mismatch>> {
+    let output = playback.run(&case.inputs)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let panics = stdout
+            .lines()
+            .chain(stderr.lines())
+            .filter(|line| line.contains("panicked at"))
+            .map(str::to_string)
+            .collect();
+        return Ok(Some(Mismatch::Panic(panics)));
+    }
+    let actual = recorded_output(&stdout);
+    Ok((actual != case.expected).then_some(Mismatch::Output(actual)))
+}
+
+/// Extract the outputs recorded by the harness, in the order they were recorded.
+fn recorded_output(stdout: &str) -> String {
+    stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix(ORACLE_OUTPUT_PREFIX))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_mismatch(harness: &HarnessMetadata, case: &OracleCase, mismatch: &Mismatch) -> String {
+    let actual = match mismatch {
+        Mismatch::Output(output) if output.is_empty() => "no output was recorded".to_string(),
+        Mismatch::Output(output) => output.clone(),
+        Mismatch::Panic(panics) if panics.is_empty() => "the playback test failed".to_string(),
+        Mismatch::Panic(panics) => panics.join("\n          "),
+    };
+    format!(
+        "Oracle mismatch for harness `{}`:\n  input:    {:?}\n  expected: {}\n  actual:   {actual}",
+        harness.pretty_name, case.inputs, case.expected
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+
+    #[test]
+    fn check_recorded_output() {
+        let stdout = "running 1 test\nkani-oracle-output: 3\nkani-oracle-output: \"a\"\nok\n";
+        assert_eq!(recorded_output(stdout), "3\n\"a\"");
+        assert_eq!(recorded_output("running 1 test\n"), "");
+    }
+
+    #[test]
+    fn check_render_mismatch() {
+        let harness = mock_proof_harness("module::check_add", None, None, None);
+        let case: OracleCase = serde_json::from_str(
+            r#"{ "harness": "check_add", "inputs": [[1, 0], [2, 0]], "expected": "3" }"#,
+        )
+        .unwrap();
+        assert!(is_case_of(&case, &harness));
+        assert_eq!(
+            render_mismatch(&harness, &case, &Mismatch::Output("4".into())),
+            "Oracle mismatch for harness `module::check_add`:\n  \
+            input:    [[1, 0], [2, 0]]\n  \
+            expected: 3\n  \
+            actual:   4"
+        );
+    }
+}
@@ -114,7 +114,7 @@ fn cargo_test(args: CargoPlaybackArgs) -> Result<()> {
     }
 
     cargo_args.append(&mut args.cargo.to_cargo_args());
-    cargo_args.append(&mut cargo_config_args());
+    cargo_args.append(&mut cargo_config_args(env!("TARGET")));
 
     // These have to be the last arguments to cargo test.
     if !args.playback.test_args.is_empty() {
@@ -0,0 +1,96 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Build a test that plays back a harness with concrete values given at runtime.
+//!
+//! The values are given through the `KANI_CONCRETE_VALS` environment variable, which overrides
+//! the values of `kani::concrete_playback_run`. This lets us run the harness with many inputs
+//! without recompiling it, e.g., to minimize a counterexample or to check an oracle. The values can
+//! also be generated from a seed given by the `KANI_CONCRETIZE_SEED` environment variable.
+
+use crate::call_single_file::{LibConfig, base_rustc_flags};
+use crate::session::{KaniSession, lib_playback_folder};
+use anyhow::{Context, Result};
+use kani_metadata::HarnessMetadata;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// The environment variable read by `kani::concrete_playback_run` to override its values.
+const CONCRETE_VALS_VAR: &str = "KANI_CONCRETE_VALS";
+
+/// The environment variable read by `kani::concrete_playback_run` to generate values from a seed.
+const CONCRETIZE_SEED_VAR: &str = "KANI_CONCRETIZE_SEED";
+
+/// The name of the test that plays back the harness.
+const TEST_NAME: &str = "kani_replay";
+
+/// A test binary that plays back a harness with the values given by the environment.
+pub(super) struct PlaybackTest {
+    binary: PathBuf,
+    /// Keep the directory with the binary until we are done with it.
+    _dir: TempDir,
+}
+
+impl PlaybackTest {
+    /// Compile the crate of the given input file with a test that plays back the harness.
+    ///
+    /// We include the input file in a new crate root, so the harness path doesn't change.
+    pub(super) fn build(
+        session: &KaniSession,
+        harness: &HarnessMetadata,
+        input: &Path,
+    ) -> Result<Self> {
+        let input = input.canonicalize()?;
+        // Create the crate root next to the input, so the module paths resolve the same way.
+        let mut root = tempfile::Builder::new()
+            .prefix("kani_replay_")
+            .suffix(".rs")
+            .tempfile_in(input.parent().unwrap())?;
+        writeln!(root, "include!({:?});", input)?;
+        writeln!(root, "#[test]")?;
+        writeln!(root, "fn {TEST_NAME}() {{")?;
+        writeln!(root, "    kani::concrete_playback_run(vec![], {});", harness.pretty_name)?;
+        writeln!(root, "}}")?;
+
+        let dir = TempDir::new()?;
+        let binary = dir.path().join(TEST_NAME);
+        let mut rustc_args = base_rustc_flags(LibConfig::new(lib_playback_folder()?));
+        rustc_args.push("--test".into());
+        rustc_args.push(root.path().into());
+        rustc_args.push(format!("--crate-name={TEST_NAME}").into());
+        rustc_args.push("-o".into());
+        rustc_args.push(binary.clone().into());
+        let mut cmd = Command::new(&session.kani_compiler);
+        cmd.args(rustc_args);
+        session.run_suppress(cmd).with_context(|| {
+            format!("Failed to build the playback test of `{}`", harness.pretty_name)
+        })?;
+        Ok(PlaybackTest { binary, _dir: dir })
+    }
+
+    /// Run the test with the given values, and return its output.
+    ///
+    /// The output of the harness is not captured, so it's included in the output of the test.
+    pub(super) fn run(&self, values: &[Vec<u8>]) -> Result<Output> {
+        let encoded: Vec<String> = values
+            .iter()
+            .map(|value| value.iter().map(u8::to_string).collect::<Vec<_>>().join(","))
+            .collect();
+        let output = self.command().env(CONCRETE_VALS_VAR, encoded.join(";")).output()?;
+        Ok(output)
+    }
+
+    /// Run the test with values generated from the given seed, and return its output.
+    pub(super) fn run_seeded(&self, seed: u64) -> Result<Output> {
+        let output = self.command().env(CONCRETIZE_SEED_VAR, seed.to_string()).output()?;
+        Ok(output)
+    }
+
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.binary);
+        cmd.args([TEST_NAME, "--exact", "--test-threads=1", "--nocapture"]);
+        cmd
+    }
+}
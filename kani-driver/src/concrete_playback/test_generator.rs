@@ -25,6 +25,7 @@ impl KaniSession {
     pub fn gen_and_add_concrete_playback(
         &self,
         harness: &HarnessMetadata,
+        input: Option<&Path>,
         verification_result: &mut VerificationResult,
     ) -> Result<()> {
         let playback_mode = match self.args.concrete_playback {
@@ -33,7 +34,10 @@ pub fn gen_and_add_concrete_playback(
         };
 
         if let Ok(result_items) = &verification_result.results {
-            let harness_values = extract_harness_values(result_items);
+            let mut harness_values = extract_harness_values(result_items);
+            if self.args.minimize {
+                harness_values = self.minimize_harness_values(harness, input, harness_values)?;
+            }
 
             if harness_values.is_empty() {
                 println!(
@@ -240,12 +244,19 @@ fn gen_test_doc(harness: &HarnessMetadata, property: &Property) -> String {
         HarnessKind::Proof => {
             format!("/// Test generated for harness `{}` \n", harness.pretty_name)
         }
-        HarnessKind::ProofForContract { target_fn } => {
+        HarnessKind::ProofForContract { target_fn, contract: None } => {
             format!(
                 "/// Test generated for harness `{}` that checks contract for `{target_fn}`\n",
                 harness.pretty_name
             )
         }
+        HarnessKind::ProofForContract { target_fn, contract: Some(contract) } => {
+            format!(
+                "/// Test generated for harness `{}` that checks contract `{contract}` for \
+                `{target_fn}`\n",
+                harness.pretty_name
+            )
+        }
         HarnessKind::Test => {
             unreachable!("Concrete playback for tests is not supported")
         }
@@ -256,7 +267,10 @@ fn gen_test_doc(harness: &HarnessMetadata, property: &Property) -> String {
         property.property_class(),
         property.description
     ));
-    if !harness.attributes.stubs.is_empty() {
+    if !harness.attributes.stubs.is_empty()
+        || !harness.attributes.stub_sets.is_empty()
+        || !harness.attributes.drop_stubs.is_empty()
+    {
         doc_str.push_str(
             r#"///
 /// # Warning
@@ -361,13 +375,23 @@ struct UnitTest {
 ///         ..., ] }
 ///     ..., ] }
 /// ```
-mod concrete_vals_extractor {
+pub(super) mod concrete_vals_extractor {
     use crate::cbmc_output_parser::{CheckStatus, Property, TraceItem};
+    use std::hash::{Hash, Hasher};
 
-    #[derive(Hash)]
     pub struct ConcreteVal {
         pub byte_arr: Vec<u8>,
         pub interp_val: String,
+        /// The type of the value, e.g. `u8`, if it can be found in the trace.
+        pub ty: Option<String>,
+    }
+
+    /// The type is not hashed, so the name of the generated tests only depends on the values.
+    impl Hash for ConcreteVal {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.byte_arr.hash(state);
+            self.interp_val.hash(state);
+        }
     }
 
     /// Extract a set of concrete values that trigger one assertion
@@ -447,7 +471,12 @@ fn extract_from_trace_item(trace_item: &TraceItem) -> Option<ConcreteVal> {
                         }
                     };
 
-                    return Some(ConcreteVal { byte_arr: next_num, interp_val });
+                    // The type is the first generic argument, e.g., `u8` in
+                    // `kani::any_raw_internal::<u8, 1_usize>`.
+                    let ty = func
+                        .split_once("::<")
+                        .map(|(_, args)| args.split([',', '>']).next().unwrap().trim().to_string());
+                    return Some(ConcreteVal { byte_arr: next_num, interp_val, ty });
                 }
             }
         }
@@ -500,11 +529,14 @@ fn format_zero_concrete_vals() {
     /// Check that the generated unit tests have the right formatting and indentation
     #[test]
     fn format_two_concrete_vals() {
-        let concrete_vals =
-            [ConcreteVal { byte_arr: vec![0, 0], interp_val: "0".to_string() }, ConcreteVal {
+        let concrete_vals = [
+            ConcreteVal { byte_arr: vec![0, 0], interp_val: "0".to_string(), ty: None },
+            ConcreteVal {
                 byte_arr: vec![0, 0, 0, 0, 0, 0, 0, 0],
                 interp_val: "0l".to_string(),
-            }];
+                ty: None,
+            },
+        ];
         let actual: Vec<_> = format_concrete_vals(&concrete_vals).collect();
         let expected = vec![
             format!("{:<8}// 0", " "),
@@ -536,7 +568,8 @@ fn split_unit_test_name(unit_test_name: &str) -> SplitUnitTestName {
     fn format_unit_test_full_func() {
         let doc_str = "/// Test documentation";
         let harness_name = "test_proof_harness";
-        let concrete_vals = [ConcreteVal { byte_arr: vec![0, 0], interp_val: "0".to_string() }];
+        let concrete_vals =
+            [ConcreteVal { byte_arr: vec![0, 0], interp_val: "0".to_string(), ty: None }];
         let unit_test = format_unit_test(harness_name, &concrete_vals, doc_str.to_string());
         let full_func = unit_test.code;
         let split_unit_test_name = split_unit_test_name(&unit_test.name);
@@ -574,9 +607,12 @@ fn extract_hash_from_unit_test(harness_name: &str, concrete_vals: &[ConcreteVal]
     fn check_hashes_are_unique() {
         let harness_name_1 = "test_proof_harness1";
         let harness_name_2 = "test_proof_harness2";
-        let concrete_vals_1 = [ConcreteVal { byte_arr: vec![0, 0], interp_val: "0".to_string() }];
-        let concrete_vals_2 = [ConcreteVal { byte_arr: vec![1, 0], interp_val: "0".to_string() }];
-        let concrete_vals_3 = [ConcreteVal { byte_arr: vec![0, 0], interp_val: "1".to_string() }];
+        let concrete_vals_1 =
+            [ConcreteVal { byte_arr: vec![0, 0], interp_val: "0".to_string(), ty: None }];
+        let concrete_vals_2 =
+            [ConcreteVal { byte_arr: vec![1, 0], interp_val: "0".to_string(), ty: None }];
+        let concrete_vals_3 =
+            [ConcreteVal { byte_arr: vec![0, 0], interp_val: "1".to_string(), ty: None }];
 
         let hash_base = extract_hash_from_unit_test(harness_name_1, &concrete_vals_1);
         let hash_diff_harness_name = extract_hash_from_unit_test(harness_name_2, &concrete_vals_1);
@@ -665,5 +701,6 @@ fn check_concrete_vals_extractor() {
 
         assert_eq!(concrete_val.byte_arr, vec![1, 3]);
         assert_eq!(concrete_val.interp_val, "385");
+        assert_eq!(concrete_val.ty.as_deref(), Some("u8"));
     }
 }
@@ -0,0 +1,164 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implement `--find-unwind`, which searches for the minimal unwind bound of each harness.
+//!
+//! The harness is verified with increasing unwind bounds, which are doubled until CBMC reports
+//! that the unwinding assertions hold, i.e., that every loop is fully unrolled. The minimal bound
+//! is then found by a binary search between the last bound that was insufficient and the first one
+//! that was sufficient. The unwinding assertions that hold for a bound also hold for any larger
+//! bound, so a failure of another property doesn't affect the search.
+//!
+//! A loop that doesn't terminate for some input can't be fully unrolled, so the search gives up
+//! once the bound reaches the cap given by the user.
+
+use std::path::Path;
+
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+
+use crate::cbmc_property_renderer::has_unwinding_assertion_failures;
+use crate::session::KaniSession;
+
+/// The outcome of the search for the minimal unwind bound of a harness.
+#[derive(Debug, PartialEq, Eq)]
+enum UnwindSearch {
+    /// The minimal bound for which the unwinding assertions hold.
+    Found(u32),
+    /// The unwinding assertions don't hold for the cap.
+    Exhausted,
+    /// The verification with the given bound didn't complete, e.g., because of a timeout.
+    Incomplete(u32),
+}
+
+impl KaniSession {
+    /// Find the minimal unwind bound of a harness, and return the harness with this bound.
+    ///
+    /// If no bound up to `cap` is sufficient, the harness is returned with the cap instead, so its
+    /// verification reports the unwinding assertions that fail. If the search can't complete, the
+    /// harness is returned unchanged.
+    pub(crate) fn find_unwind(
+        &self,
+        binary: &Path,
+        harness: &HarnessMetadata,
+        cap: u32,
+    ) -> Result<HarnessMetadata> {
+        let search = search_unwind(cap, |unwind| {
+            if !self.args.common_args.quiet {
+                println!("Checking harness {} with unwind {unwind}...", harness.pretty_name);
+            }
+            let result = self.run_cbmc(binary, &with_unwind(harness, unwind))?;
+            Ok(result.results.ok().map(|properties| !has_unwinding_assertion_failures(&properties)))
+        })?;
+
+        let quiet = self.args.common_args.quiet;
+        match search {
+            UnwindSearch::Found(unwind) => {
+                if !quiet {
+                    println!(
+                        "Harness {}: the minimal unwind bound for which the unwinding assertions \
+                        hold is {unwind}. Add `#[kani::unwind({unwind})]` to the harness to use it.",
+                        harness.pretty_name
+                    );
+                }
+                Ok(with_unwind(harness, unwind))
+            }
+            UnwindSearch::Exhausted => {
+                if !quiet {
+                    println!(
+                        "Harness {}: the unwinding assertions don't hold for any unwind bound up \
+                        to {cap}. A loop or a recursive call of the harness might not terminate \
+                        for some inputs. Use `--find-unwind=<CAP>` to search for a larger bound, \
+                        or bound the inputs with `kani::assume`.",
+                        harness.pretty_name
+                    );
+                }
+                Ok(with_unwind(harness, cap))
+            }
+            UnwindSearch::Incomplete(unwind) => {
+                if !quiet {
+                    println!(
+                        "Harness {}: couldn't find the minimal unwind bound because the \
+                        verification with unwind {unwind} didn't complete.",
+                        harness.pretty_name
+                    );
+                }
+                Ok(harness.clone())
+            }
+        }
+    }
+}
+
+/// Return a copy of the harness that uses the given unwind bound.
+fn with_unwind(harness: &HarnessMetadata, unwind: u32) -> HarnessMetadata {
+    let mut harness = harness.clone();
+    harness.attributes.unwind_value = Some(unwind);
+    harness
+}
+
+/// Search for the minimal unwind bound up to `cap` that is sufficient according to `check`.
+///
+/// `check` returns whether the unwinding assertions hold for the given bound, or `None` if the
+/// verification didn't complete.
+fn search_unwind(
+    cap: u32,
+    mut check: impl FnMut(u32) -> Result<Option<bool>>,
+) -> Result<UnwindSearch> {
+    // The largest bound known to be insufficient, and the smallest one known to be sufficient.
+    let mut insufficient = 0;
+    let mut unwind = 1;
+    let mut sufficient = loop {
+        match check(unwind)? {
+            Some(true) => break unwind,
+            Some(false) if unwind == cap => return Ok(UnwindSearch::Exhausted),
+            Some(false) => {
+                insufficient = unwind;
+                unwind = unwind.saturating_mul(2).min(cap);
+            }
+            None => return Ok(UnwindSearch::Incomplete(unwind)),
+        }
+    };
+    while sufficient - insufficient > 1 {
+        let unwind = insufficient + (sufficient - insufficient) / 2;
+        match check(unwind)? {
+            Some(true) => sufficient = unwind,
+            Some(false) => insufficient = unwind,
+            None => return Ok(UnwindSearch::Incomplete(unwind)),
+        }
+    }
+    Ok(UnwindSearch::Found(sufficient))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Search with a harness whose unwinding assertions hold from `minimal` on, and return the
+    /// outcome with the bounds that were checked.
+    fn search(cap: u32, minimal: u32) -> (UnwindSearch, Vec<u32>) {
+        let mut checked = vec![];
+        let search = search_unwind(cap, |unwind| {
+            checked.push(unwind);
+            Ok(Some(unwind >= minimal))
+        })
+        .unwrap();
+        (search, checked)
+    }
+
+    #[test]
+    fn check_search_unwind() {
+        assert_eq!(search(64, 1), (UnwindSearch::Found(1), vec![1]));
+        assert_eq!(search(64, 2), (UnwindSearch::Found(2), vec![1, 2]));
+        assert_eq!(search(64, 11), (UnwindSearch::Found(11), vec![1, 2, 4, 8, 16, 12, 10, 11]));
+        assert_eq!(search(20, 20), (UnwindSearch::Found(20), vec![1, 2, 4, 8, 16, 20, 18, 19]));
+    }
+
+    #[test]
+    fn check_search_unwind_gives_up() {
+        assert_eq!(search(20, u32::MAX), (UnwindSearch::Exhausted, vec![1, 2, 4, 8, 16, 20]));
+        assert_eq!(search(1, u32::MAX), (UnwindSearch::Exhausted, vec![1]));
+
+        let search = search_unwind(64, |unwind| Ok((unwind < 8).then_some(false))).unwrap();
+        assert_eq!(search, UnwindSearch::Incomplete(8));
+    }
+}
@@ -2,14 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use anyhow::{Result, bail};
-use kani_metadata::{ArtifactType, HarnessMetadata};
+use kani_metadata::{ArtifactType, HarnessKind, HarnessMetadata};
 use rayon::prelude::*;
+use std::collections::HashSet;
 use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::args::OutputFormat;
 use crate::call_cbmc::{VerificationResult, VerificationStatus};
+use crate::incremental::VerificationCache;
 use crate::project::Project;
 use crate::session::KaniSession;
+use crate::util::warning;
 
 /// A HarnessRunner is responsible for checking all proof harnesses. The data in this structure represents
 /// "background information" that the controlling driver (e.g. cargo-kani or kani) computed.
@@ -39,6 +44,11 @@ pub(crate) fn check_all_harnesses(
         self.check_stubbing(harnesses)?;
 
         let sorted_harnesses = crate::metadata::sort_harnesses_by_loc(harnesses);
+        let cache = self
+            .sess
+            .args
+            .changed_only
+            .then(|| Mutex::new(VerificationCache::load(&self.project.outdir)));
 
         let pool = {
             let mut builder = rayon::ThreadPoolBuilder::new();
@@ -48,30 +58,128 @@ pub(crate) fn check_all_harnesses(
             builder.build()?
         };
 
-        let results = pool.install(|| -> Result<Vec<HarnessResult<'pr>>> {
+        // Stop verifying new harnesses after an error, but keep the results that were completed
+        // so they can still be summarized.
+        let aborted = AtomicBool::new(false);
+        self.sess.start_verification_budget();
+        let outcomes = pool.install(|| -> Vec<Option<Result<HarnessResult<'pr>>>> {
             sorted_harnesses
                 .par_iter()
-                .map(|harness| -> Result<HarnessResult<'pr>> {
-                    let harness_filename = harness.pretty_name.replace("::", "-");
-                    let report_dir = self.project.outdir.join(format!("report-{harness_filename}"));
-                    let goto_file =
-                        self.project.get_harness_artifact(&harness, ArtifactType::Goto).unwrap();
-
-                    self.sess.instrument_model(goto_file, goto_file, &self.project, &harness)?;
-
-                    if self.sess.args.synthesize_loop_contracts {
-                        self.sess.synthesize_loop_contracts(goto_file, &goto_file, &harness)?;
+                .map(|harness| {
+                    if aborted.load(Ordering::Relaxed) {
+                        return None;
                     }
-
-                    let result = self.sess.check_harness(goto_file, &report_dir, harness)?;
-                    Ok(HarnessResult { harness, result })
+                    if self.sess.remaining_budget().is_some_and(|budget| budget.is_zero()) {
+                        return Some(self.skip_harness(harness));
+                    }
+                    let outcome = self.check_one_harness(harness, cache.as_ref());
+                    if outcome.is_err() {
+                        aborted.store(true, Ordering::Relaxed);
+                    }
+                    Some(outcome)
                 })
-                .collect::<Result<Vec<_>>>()
-        })?;
+                .collect()
+        });
+        let mut results = vec![];
+        let mut error = None;
+        for outcome in outcomes.into_iter().flatten() {
+            match outcome {
+                Ok(result) => results.push(result),
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+        if let Some(error) = error {
+            // Don't hide the error that aborted the run if the summary can't be written.
+            if let Some(path) = &self.sess.args.summary_json
+                && let Err(summary_error) =
+                    self.sess.report_summary(&results, harnesses.len(), path)
+            {
+                eprintln!("WARNING: Failed to write the verification summary: {summary_error}");
+            }
+            return Err(error);
+        }
+
+        if let Some(cache) = cache {
+            cache.into_inner().unwrap().save(&self.project.outdir)?;
+        }
 
         Ok(results)
     }
 
+    /// Report a harness that is not verified because the `--total-timeout` was reached.
+    ///
+    /// The post-verification hook doesn't run for these harnesses, since they weren't verified.
+    fn skip_harness(&self, harness: &'pr HarnessMetadata) -> Result<HarnessResult<'pr>> {
+        if !self.sess.args.common_args.quiet {
+            println!("Skipping harness {}... (budget exhausted)", harness.pretty_name);
+        }
+        Ok(HarnessResult { harness, result: VerificationResult::skipped() })
+    }
+
+    /// Verify a single harness, or reuse its result from the cache of a previous run.
+    fn check_one_harness(
+        &self,
+        harness: &'pr HarnessMetadata,
+        cache: Option<&Mutex<VerificationCache>>,
+    ) -> Result<HarnessResult<'pr>> {
+        let harness_filename = harness.pretty_name.replace("::", "-");
+        let report_dir = self.project.outdir.join(format!("report-{harness_filename}"));
+        let goto_file = self.project.get_harness_artifact(&harness, ArtifactType::Goto).unwrap();
+
+        let cache_key = match cache {
+            Some(_) => self.sess.verification_cache_key(goto_file, harness)?,
+            None => None,
+        };
+        if let (Some(cache), Some(key)) = (cache, &cache_key)
+            && let Some((result, output)) = cache.lock().unwrap().lookup(harness, key)
+        {
+            if !self.sess.args.common_args.quiet {
+                println!("Checking harness {}... (cached)", harness.pretty_name);
+                println!("{output}");
+            }
+            let result = HarnessResult { harness, result };
+            self.sess.run_result_hook(&result)?;
+            return Ok(result);
+        }
+
+        self.sess.check_replay_model(harness)?;
+        self.sess.instrument_model(goto_file, goto_file, &self.project, &harness)?;
+
+        if self.sess.args.synthesize_loop_contracts {
+            self.sess.synthesize_loop_contracts(goto_file, &goto_file, &harness)?;
+        }
+
+        // Verify the harness with the minimal unwind bound, if the user asked to search for it.
+        let searched;
+        let checked_harness = match self.sess.args.find_unwind {
+            Some(cap) => {
+                searched = self.sess.find_unwind(goto_file, harness, cap)?;
+                &searched
+            }
+            None => harness,
+        };
+        let result = self.sess.check_harness(
+            goto_file,
+            &report_dir,
+            checked_harness,
+            self.project.input.as_deref(),
+        )?;
+        self.sess.record_or_check_replay(
+            goto_file,
+            &self.project.outdir,
+            checked_harness,
+            &result,
+        )?;
+        if let (Some(cache), Some(key)) = (cache, cache_key) {
+            cache.lock().unwrap().insert(harness, key, &result, &self.sess.args.output_format);
+        }
+        let result = HarnessResult { harness, result };
+        self.sess.run_result_hook(&result)?;
+        Ok(result)
+    }
+
     /// Return an error if the user is trying to verify a harness with stubs without enabling the
     /// experimental feature.
     fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
@@ -79,7 +187,11 @@ fn check_stubbing(&self, harnesses: &[&HarnessMetadata]) -> Result<()> {
             let with_stubs: Vec<_> = harnesses
                 .iter()
                 .filter_map(|harness| {
-                    (!harness.attributes.stubs.is_empty()).then_some(harness.pretty_name.as_str())
+                    let attributes = &harness.attributes;
+                    (!attributes.stubs.is_empty()
+                        || !attributes.stub_sets.is_empty()
+                        || !attributes.drop_stubs.is_empty())
+                    .then_some(harness.pretty_name.as_str())
                 })
                 .collect();
             match with_stubs.as_slice() {
@@ -107,9 +219,19 @@ pub(crate) fn check_harness(
         binary: &Path,
         report_dir: &Path,
         harness: &HarnessMetadata,
+        input: Option<&Path>,
     ) -> Result<VerificationResult> {
         if !self.args.common_args.quiet {
-            println!("Checking harness {}...", harness.pretty_name);
+            if let HarnessKind::ProofForContract { target_fn, contract: Some(contract) } =
+                &harness.attributes.kind
+            {
+                println!(
+                    "Checking harness {} against contract `{contract}` of `{target_fn}`...",
+                    harness.pretty_name
+                );
+            } else {
+                println!("Checking harness {}...", harness.pretty_name);
+            }
         }
 
         if self.args.visualize {
@@ -122,12 +244,9 @@ pub(crate) fn check_harness(
             // When quiet, we don't want to print anything at all.
             // When output is old, we also don't have real results to print.
             if !self.args.common_args.quiet && self.args.output_format != OutputFormat::Old {
-                println!(
-                    "{}",
-                    result.render(&self.args.output_format, harness.attributes.should_panic)
-                );
+                println!("{}", result.render(&self.args.output_format, harness));
             }
-            self.gen_and_add_concrete_playback(harness, &mut result)?;
+            self.gen_and_add_concrete_playback(harness, input, &mut result)?;
             Ok(result)
         }
     }
@@ -138,12 +257,14 @@ pub(crate) fn check_harness(
     /// Note: Takes `self` "by ownership". This function wants to be able to drop before
     /// exiting with an error code, if needed.
     pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result<()> {
+        let (skipped, verified): (Vec<_>, Vec<_>) =
+            results.iter().partition(|r| r.result.is_skipped());
         let (successes, failures): (Vec<_>, Vec<_>) =
-            results.iter().partition(|r| r.result.status == VerificationStatus::Success);
+            verified.into_iter().partition(|r| r.result.status == VerificationStatus::Success);
 
         let succeeding = successes.len();
         let failing = failures.len();
-        let total = succeeding + failing;
+        let total = succeeding + failing + skipped.len();
 
         if self.args.concrete_playback.is_some()
             && !self.args.common_args.quiet
@@ -163,7 +284,19 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
                 println!("Verification failed for - {}", failure.harness.pretty_name);
             }
 
-            if total > 0 {
+            for skip in skipped.iter() {
+                println!(
+                    "Verification skipped (budget exhausted) for - {}",
+                    skip.harness.pretty_name
+                );
+            }
+
+            if !skipped.is_empty() {
+                println!(
+                    "Incomplete - {succeeding} successfully verified harnesses, {failing} failures, {} skipped (budget exhausted), {total} total.",
+                    skipped.len()
+                );
+            } else if total > 0 {
                 println!(
                     "Complete - {succeeding} successfully verified harnesses, {failing} failures, {total} total."
                 );
@@ -188,11 +321,16 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
             }
         }
 
+        if !self.args.common_args.quiet {
+            self.warn_unmatched_property_severities(results);
+        }
+
         if self.args.coverage {
             self.show_coverage_summary()?;
         }
 
-        if failing > 0 {
+        // A harness that was skipped wasn't verified, so the run must not pass.
+        if failing > 0 || !skipped.is_empty() {
             // Failure exit code without additional error message
             drop(self);
             std::process::exit(1);
@@ -201,6 +339,27 @@ pub(crate) fn print_final_summary(self, results: &[HarnessResult<'_>]) -> Result
         Ok(())
     }
 
+    /// Warn about the classes given to `--property-severity` that match no property of the
+    /// verified harnesses, e.g., because the class name is misspelled.
+    fn warn_unmatched_property_severities(&self, results: &[HarnessResult<'_>]) {
+        let classes: HashSet<String> = results
+            .iter()
+            .filter_map(|r| r.result.results.as_ref().ok())
+            .flatten()
+            .map(|prop| prop.property_class())
+            .collect();
+        let mut reported = HashSet::new();
+        for entry in &self.args.property_severity {
+            if !classes.contains(&entry.class) && reported.insert(&entry.class) {
+                warning(&format!(
+                    "`--property-severity {}=...` has no effect, since no property of class `{}` \
+                    was checked",
+                    entry.class, entry.class
+                ));
+            }
+        }
+    }
+
     /// Show a coverage summary.
     ///
     /// This is just a placeholder for now.
@@ -0,0 +1,156 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for `--changed-only`, which only verifies the harnesses that changed since the
+//! previous run.
+//!
+//! The compiler stores a fingerprint of the model of each harness in its metadata. Since the
+//! model includes every item reachable from the harness after stubs and contracts are applied,
+//! a change to any dependency of a harness also changes its fingerprint. We combine it with the
+//! options that affect verification into a cache key, and we reuse the result of the previous
+//! run if the key of a harness didn't change.
+
+use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use kani_metadata::HarnessMetadata;
+use serde::{Deserialize, Serialize};
+
+use crate::args::OutputFormat;
+use crate::call_cbmc::{FailedProperties, VerificationResult, VerificationStatus};
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+
+/// The name of the file, inside the output directory, that stores the results of the last run.
+const CACHE_FILE: &str = "kani-verification-cache.json";
+
+/// The results of previous runs indexed by the harness name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct VerificationCache {
+    harnesses: BTreeMap<String, CachedResult>,
+}
+
+/// The result of a harness that was verified in a previous run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedResult {
+    /// The key of the harness when it was verified.
+    key: String,
+    status: VerificationStatus,
+    failed_properties: FailedProperties,
+    /// The verification output that was printed for this harness.
+    output: String,
+}
+
+impl VerificationCache {
+    fn path(outdir: &Path) -> PathBuf {
+        outdir.join(CACHE_FILE)
+    }
+
+    /// Load the cache stored in the given output directory.
+    ///
+    /// A missing or invalid cache is ignored, which only means that all harnesses are verified.
+    pub fn load(outdir: &Path) -> VerificationCache {
+        File::open(Self::path(outdir))
+            .ok()
+            .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+            .unwrap_or_default()
+    }
+
+    /// Store the cache in the given output directory.
+    pub fn save(&self, outdir: &Path) -> Result<()> {
+        let writer = BufWriter::new(File::create(Self::path(outdir))?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+
+    /// Return the result of a previous run of the harness if its key didn't change.
+    pub fn lookup(
+        &self,
+        harness: &HarnessMetadata,
+        key: &str,
+    ) -> Option<(VerificationResult, &str)> {
+        let cached = self.harnesses.get(&harness.pretty_name).filter(|cached| cached.key == key)?;
+        let result = VerificationResult {
+            status: cached.status,
+            failed_properties: cached.failed_properties,
+            results: Ok(vec![]),
+            runtime: Duration::from_secs(0),
+            generated_concrete_test: false,
+            coverage_results: None,
+            warnings: vec![],
+        };
+        Some((result, &cached.output))
+    }
+
+    /// Record the result of a harness that was verified in this run.
+    ///
+    /// Results that don't come from a complete CBMC run, e.g. a timeout, are not cached.
+    pub fn insert(
+        &mut self,
+        harness: &HarnessMetadata,
+        key: String,
+        result: &VerificationResult,
+        output_format: &OutputFormat,
+    ) {
+        if result.results.is_err() {
+            self.harnesses.remove(&harness.pretty_name);
+            return;
+        }
+        let cached = CachedResult {
+            key,
+            status: result.status,
+            failed_properties: result.failed_properties,
+            output: result.render(output_format, harness),
+        };
+        self.harnesses.insert(harness.pretty_name.clone(), cached);
+    }
+}
+
+impl KaniSession {
+    /// Compute the cache key of a harness, which changes whenever its model or the options that
+    /// affect its verification change.
+    ///
+    /// Returns `None` if the compiler didn't record a fingerprint for the harness, in which case
+    /// we always verify it.
+    pub(crate) fn verification_cache_key(
+        &self,
+        file: &Path,
+        harness: &HarnessMetadata,
+    ) -> Result<Option<String>> {
+        let Some(fingerprint) = &harness.fingerprint else { return Ok(None) };
+        let mut hasher = DefaultHasher::new();
+        KANI_VERSION.hash(&mut hasher);
+        fingerprint.hash(&mut hasher);
+        self.cbmc_flags(file, harness)?.hash(&mut hasher);
+        self.args.run_sanity_checks.hash(&mut hasher);
+        self.args.checks.undefined_function_on().hash(&mut hasher);
+        self.args.synthesize_loop_contracts.hash(&mut hasher);
+        self.args.property_severity.hash(&mut hasher);
+        Ok(Some(format!("{:016x}", hasher.finish())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::mock_proof_harness;
+
+    #[test]
+    fn check_cache_lookup() {
+        let harness = mock_proof_harness("check_one", None, None, None);
+        let mut cache = VerificationCache::default();
+        assert!(cache.lookup(&harness, "key").is_none());
+
+        let result = VerificationResult::mock_success();
+        cache.insert(&harness, "key".to_string(), &result, &OutputFormat::Terse);
+        let (cached, _) = cache.lookup(&harness, "key").unwrap();
+        assert_eq!(cached.status, VerificationStatus::Success);
+        assert!(cache.lookup(&harness, "other_key").is_none());
+    }
+}
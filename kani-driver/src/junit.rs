@@ -0,0 +1,294 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Export the verification results in the JUnit XML format, so they show up in the test
+//! dashboards of CI systems.
+//!
+//! Each harness becomes a test case, and each property that failed becomes a failure of its test
+//! case. A harness whose verification didn't complete, e.g., because of a timeout, is reported as
+//! an error, and a harness that was skipped or whose properties could not be determined is
+//! reported as skipped, since it was neither proven nor refuted.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use crate::call_cbmc::{ExitStatus, VerificationStatus};
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+
+/// The outcome of a test case that didn't pass.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    /// The properties that failed.
+    Failures(Vec<Failure>),
+    /// The verification didn't complete.
+    Error { kind: &'static str, message: String },
+    /// The harness wasn't verified, or its result is inconclusive.
+    Skipped { message: String },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Failure {
+    /// The class of the property, e.g., `assertion`.
+    class: String,
+    /// The description of the property and its location.
+    message: String,
+}
+
+#[derive(Debug)]
+struct TestCase {
+    name: String,
+    classname: String,
+    time: Duration,
+    outcome: Option<Outcome>,
+}
+
+impl KaniSession {
+    /// Write the results of all harnesses to the JUnit XML file given by the user.
+    pub(crate) fn write_junit_xml(&self, results: &[HarnessResult<'_>], path: &Path) -> Result<()> {
+        std::fs::write(path, junit_xml(results))?;
+        if !self.args.common_args.quiet {
+            println!("Wrote JUnit XML results to {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Render the given results as a JUnit XML report with a single test suite.
+pub fn junit_xml(results: &[HarnessResult<'_>]) -> String {
+    let test_cases: Vec<_> = results.iter().map(test_case).collect();
+    let count = |f: fn(&Outcome) -> bool| {
+        test_cases.iter().filter(|case| case.outcome.as_ref().is_some_and(f)).count()
+    };
+    let failures = count(|outcome| matches!(outcome, Outcome::Failures(_)));
+    let errors = count(|outcome| matches!(outcome, Outcome::Error { .. }));
+    let skipped = count(|outcome| matches!(outcome, Outcome::Skipped { .. }));
+    let time: Duration = test_cases.iter().map(|case| case.time).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let suite_attrs = format!(
+        "name=\"Kani\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" \
+        skipped=\"{skipped}\" time=\"{}\"",
+        test_cases.len(),
+        seconds(time)
+    );
+    writeln!(xml, "<testsuites {suite_attrs}>").unwrap();
+    writeln!(xml, "  <testsuite {suite_attrs}>").unwrap();
+    for case in &test_cases {
+        write!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+            escape(&case.name),
+            escape(&case.classname),
+            seconds(case.time)
+        )
+        .unwrap();
+        let Some(outcome) = &case.outcome else {
+            xml.push_str("/>\n");
+            continue;
+        };
+        xml.push_str(">\n");
+        match outcome {
+            Outcome::Failures(failures) => {
+                for failure in failures {
+                    writeln!(
+                        xml,
+                        "      <failure type=\"{}\" message=\"{}\"/>",
+                        escape(&failure.class),
+                        escape(&failure.message)
+                    )
+                    .unwrap();
+                }
+            }
+            Outcome::Error { kind, message } => {
+                writeln!(xml, "      <error type=\"{kind}\" message=\"{}\"/>", escape(message))
+                    .unwrap();
+            }
+            Outcome::Skipped { message } => {
+                writeln!(xml, "      <skipped message=\"{}\"/>", escape(message)).unwrap();
+            }
+        }
+        xml.push_str("    </testcase>\n");
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn test_case(harness_result: &HarnessResult<'_>) -> TestCase {
+    let harness = harness_result.harness;
+    let result = &harness_result.result;
+    // Group the harnesses by module, like test reporters do with test classes.
+    let classname = match harness.pretty_name.rsplit_once("::") {
+        Some((module, _)) => module.to_string(),
+        None => harness.crate_name.clone(),
+    };
+    TestCase {
+        name: harness.pretty_name.clone(),
+        classname,
+        time: result.runtime,
+        outcome: outcome(result.status, &result.results),
+    }
+}
+
+/// The outcome of a harness, or `None` if it passed.
+fn outcome(
+    status: VerificationStatus,
+    results: &Result<Vec<Property>, ExitStatus>,
+) -> Option<Outcome> {
+    let properties = match results {
+        Ok(properties) => properties,
+        Err(ExitStatus::Skipped) => {
+            return Some(Outcome::Skipped {
+                message: "the harness was not verified because the total timeout was reached"
+                    .to_string(),
+            });
+        }
+        Err(ExitStatus::Timeout) => {
+            return Some(Outcome::Error { kind: "timeout", message: "CBMC timed out".to_string() });
+        }
+        Err(ExitStatus::OutOfMemory) => {
+            return Some(Outcome::Error {
+                kind: "out_of_memory",
+                message: "CBMC ran out of memory".to_string(),
+            });
+        }
+        Err(ExitStatus::Other(code)) => {
+            return Some(Outcome::Error {
+                kind: "cbmc_error",
+                message: format!("CBMC failed with exit status {code}"),
+            });
+        }
+    };
+    if status == VerificationStatus::Success {
+        return None;
+    }
+    let failures: Vec<_> = properties
+        .iter()
+        .filter(|property| property.status == CheckStatus::Failure)
+        .map(|property| Failure {
+            class: property.property_class(),
+            message: if property.source_location.is_missing() {
+                property.description.clone()
+            } else {
+                format!("{} at {}", property.description, property.source_location)
+            },
+        })
+        .collect();
+    if !failures.is_empty() {
+        return Some(Outcome::Failures(failures));
+    }
+    let undetermined = properties
+        .iter()
+        .filter(|property| {
+            matches!(property.status, CheckStatus::Undetermined | CheckStatus::Unknown)
+        })
+        .count();
+    if undetermined > 0 {
+        return Some(Outcome::Skipped {
+            message: format!("{undetermined} properties could not be determined"),
+        });
+    }
+    // E.g., a harness that should panic but none of its properties failed.
+    Some(Outcome::Failures(vec![Failure {
+        class: "verification".to_string(),
+        message: "the harness failed verification".to_string(),
+    }]))
+}
+
+/// Format a duration in seconds, as expected by the `time` attribute.
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Escape the characters that are not allowed in XML attribute values.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::metadata::tests::{mock_proof_harness, mock_property, mock_result};
+
+    #[test]
+    fn check_junit_outcomes() {
+        let passed = mock_proof_harness("mod_a::passed", None, None, None);
+        let failed = mock_proof_harness("mod_a::failed", None, None, None);
+        let timeout = mock_proof_harness("timeout", None, None, None);
+        let undetermined = mock_proof_harness("undetermined", None, None, None);
+        let mock_result = |status, results| VerificationResult {
+            runtime: Duration::from_millis(1500),
+            ..mock_result(status, results)
+        };
+        let mut failure = mock_property("assertion", CheckStatus::Failure);
+        failure.description = "assertion <check>".into();
+        let results = [
+            HarnessResult {
+                harness: &passed,
+                result: mock_result(
+                    VerificationStatus::Success,
+                    Ok(vec![mock_property("assertion", CheckStatus::Success)]),
+                ),
+            },
+            HarnessResult {
+                harness: &failed,
+                result: mock_result(
+                    VerificationStatus::Failure,
+                    Ok(vec![failure, mock_property("assertion", CheckStatus::Success)]),
+                ),
+            },
+            HarnessResult {
+                harness: &timeout,
+                result: mock_result(VerificationStatus::Failure, Err(ExitStatus::Timeout)),
+            },
+            HarnessResult {
+                harness: &undetermined,
+                result: mock_result(
+                    VerificationStatus::Failure,
+                    Ok(vec![mock_property("arithmetic_overflow", CheckStatus::Undetermined)]),
+                ),
+            },
+        ];
+        let xml = junit_xml(&results);
+        assert!(xml.contains(
+            "<testsuite name=\"Kani\" tests=\"4\" failures=\"1\" errors=\"1\" skipped=\"1\" \
+            time=\"6.000\">"
+        ));
+        assert!(
+            xml.contains("<testcase name=\"mod_a::passed\" classname=\"mod_a\" time=\"1.500\"/>")
+        );
+        assert!(xml.contains(
+            "<failure type=\"assertion\" message=\"assertion &lt;check&gt; at src/lib.rs:10:5 in \
+            function check\"/>"
+        ));
+        assert!(xml.contains("<error type=\"timeout\" message=\"CBMC timed out\"/>"));
+        assert!(xml.contains("<skipped message=\"1 properties could not be determined\"/>"));
+        assert_eq!(xml.matches("<failure ").count(), 1);
+    }
+
+    #[test]
+    fn check_junit_skipped() {
+        let harness = mock_proof_harness("check", None, Some("my_crate"), None);
+        let results = [HarnessResult { harness: &harness, result: VerificationResult::skipped() }];
+        let xml = junit_xml(&results);
+        assert!(xml.contains("classname=\"my_crate\" time=\"0.000\""));
+        assert!(xml.contains("<skipped message=\"the harness was not verified"));
+    }
+}
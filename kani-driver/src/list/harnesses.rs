@@ -0,0 +1,93 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Implements `--list-harnesses`, which prints the harnesses that would be verified with the
+//! configuration they would be verified with, without generating their models.
+
+use crate::args::list_args::Format;
+use crate::call_cbmc::{resolve_solver, resolve_unwind_value};
+use crate::session::KaniSession;
+use anyhow::Result;
+use kani_metadata::{CbmcSolver, HarnessKind, HarnessMetadata};
+use serde_json::json;
+
+impl KaniSession {
+    /// Print the given harnesses, which were selected by the harness filters, in the given format.
+    ///
+    /// The harnesses are printed in the order they appear in their files.
+    pub fn list_harnesses(&self, harnesses: &[&HarnessMetadata], format: Format) -> Result<()> {
+        let mut harnesses = harnesses.to_vec();
+        harnesses.sort_by(|first, second| {
+            (&first.original_file, first.original_start_line)
+                .cmp(&(&second.original_file, second.original_start_line))
+        });
+        match format {
+            Format::Pretty => {
+                println!("Harnesses that would be verified:");
+                for harness in &harnesses {
+                    self.print_harness(harness);
+                }
+                println!("Total: {} harnesses.", harnesses.len());
+            }
+            Format::Json => {
+                let harnesses: Vec<_> =
+                    harnesses.iter().map(|harness| self.harness_json(harness)).collect();
+                println!("{}", serde_json::to_string_pretty(&json!({ "harnesses": harnesses }))?);
+            }
+        }
+        Ok(())
+    }
+
+    fn print_harness(&self, harness: &HarnessMetadata) {
+        println!(
+            " - {} ({}:{})",
+            harness.pretty_name, harness.original_file, harness.original_start_line
+        );
+        if let HarnessKind::ProofForContract { target_fn, .. } = &harness.attributes.kind {
+            println!("   contract of: {target_fn}");
+        }
+        let unwind = resolve_unwind_value(&self.args, harness)
+            .map_or_else(|| "none".to_string(), |unwind| unwind.to_string());
+        let solver = solver_name(resolve_solver(&self.args, &harness.attributes.solver));
+        println!("   unwind: {unwind}, solver: {solver}");
+        for stub in &harness.attributes.stubs {
+            println!("   stub: {} -> {}", stub.original, stub.replacement);
+        }
+        for set in &harness.attributes.stub_sets {
+            println!("   stub set: {set}");
+        }
+        for stub in &harness.attributes.drop_stubs {
+            println!("   drop stub: {} -> {}", stub.original, stub.replacement);
+        }
+        for function in &harness.attributes.verified_stubs {
+            println!("   verified stub: {function}");
+        }
+    }
+
+    fn harness_json(&self, harness: &HarnessMetadata) -> serde_json::Value {
+        let attributes = &harness.attributes;
+        let contract_of = match &attributes.kind {
+            HarnessKind::ProofForContract { target_fn, .. } => Some(target_fn),
+            HarnessKind::Proof | HarnessKind::Test => None,
+        };
+        json!({
+            "name": harness.pretty_name,
+            "file": harness.original_file,
+            "line": harness.original_start_line,
+            "contract-of": contract_of,
+            "unwind": resolve_unwind_value(&self.args, harness),
+            "solver": solver_name(resolve_solver(&self.args, &attributes.solver)),
+            "stubs": attributes.stubs,
+            "stub-sets": attributes.stub_sets,
+            "drop-stubs": attributes.drop_stubs,
+            "verified-stubs": attributes.verified_stubs,
+        })
+    }
+}
+
+/// The name of a solver, as given to `--solver`.
+fn solver_name(solver: &CbmcSolver) -> String {
+    match solver {
+        CbmcSolver::Binary(binary) => format!("bin={binary}"),
+        solver => solver.as_ref().to_string(),
+    }
+}
@@ -3,6 +3,7 @@
 // Implements the list subcommand logic
 
 pub mod collect_metadata;
+mod harnesses;
 mod output;
 
 /// Stores the total count of standard harnesses, contract harnesses,
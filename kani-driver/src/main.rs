@@ -33,11 +33,19 @@
 mod cbmc_property_renderer;
 mod concrete_playback;
 mod coverage;
+mod find_unwind;
 mod harness_runner;
+mod incremental;
+mod junit;
 mod list;
 mod metadata;
 mod project;
+mod replay;
+mod result_hook;
+mod sarif;
 mod session;
+mod summary;
+mod unsupported_baseline;
 mod util;
 mod version;
 
@@ -135,6 +143,22 @@ fn verify_project(project: Project, session: KaniSession) -> Result<()> {
     let harnesses = session.determine_targets(&project.get_all_harnesses())?;
     debug!(n = harnesses.len(), ?harnesses, "verify_project");
 
+    if let Some(format) = session.args.list_harnesses {
+        return session.list_harnesses(&harnesses, format);
+    }
+
+    if let Some(seed) = session.args.concretize_seed {
+        return session.run_concretized(&harnesses, project.input.as_deref(), seed);
+    }
+
+    if let Some(oracle) = &session.args.oracle {
+        session.check_oracle(&harnesses, project.input.as_deref(), oracle)?;
+    }
+
+    if let Some(baseline) = &session.args.unsupported_baseline {
+        session.check_unsupported_baseline(&project, baseline)?;
+    }
+
     // Verification
     let runner = harness_runner::HarnessRunner { sess: &session, project: &project };
     let results = runner.check_all_harnesses(&harnesses)?;
@@ -156,6 +180,18 @@ fn verify_project(project: Project, session: KaniSession) -> Result<()> {
         session.save_coverage_results(&project, &results, &timestamp)?;
     }
 
+    if let Some(path) = &session.args.sarif {
+        session.write_sarif(&results, path)?;
+    }
+
+    if let Some(path) = &session.args.junit_xml {
+        session.write_junit_xml(&results, path)?;
+    }
+
+    if let Some(path) = &session.args.summary_json {
+        session.report_summary(&results, harnesses.len(), path)?;
+    }
+
     session.print_final_summary(&results)
 }
 
@@ -202,8 +202,11 @@ fn find_proof_harnesses<'a>(
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::call_cbmc::{ExitStatus, FailedProperties, VerificationResult, VerificationStatus};
+    use crate::cbmc_output_parser::{CheckStatus, Property, PropertyId, SourceLocation};
     use kani_metadata::{HarnessAttributes, HarnessKind};
     use std::path::PathBuf;
+    use std::time::Duration;
 
     pub fn mock_proof_harness(
         name: &str,
@@ -223,6 +226,43 @@ pub fn mock_proof_harness(
             attributes,
             goto_file: model_file,
             contract: Default::default(),
+            properties: vec![],
+            type_states: vec![],
+            fingerprint: None,
+            assumed_ok: vec![],
+            int_abstraction_bits: None,
+        }
+    }
+
+    /// A property of the given class and status, located at `src/lib.rs:10:5` in `check`.
+    pub fn mock_property(class: &str, status: CheckStatus) -> Property {
+        Property {
+            description: format!("{class} check"),
+            property_id: PropertyId { fn_name: Some("check".into()), class: class.into(), id: 1 },
+            source_location: SourceLocation {
+                column: Some("5".into()),
+                file: Some("src/lib.rs".into()),
+                function: Some("check".into()),
+                line: Some("10".into()),
+            },
+            status,
+            reach: None,
+            trace: None,
+        }
+    }
+
+    pub fn mock_result(
+        status: VerificationStatus,
+        results: Result<Vec<Property>, ExitStatus>,
+    ) -> VerificationResult {
+        VerificationResult {
+            status,
+            failed_properties: FailedProperties::None,
+            results,
+            runtime: Duration::from_secs(0),
+            generated_concrete_test: false,
+            coverage_results: None,
+            warnings: vec![],
         }
     }
 
@@ -92,7 +92,18 @@ fn try_new(
     ) -> Result<Self> {
         // For each harness (test or proof) from each metadata, read the path for the goto
         // SymTabGoto file. Use that path to find all the other artifacts.
+        // The compiler doesn't generate any model when we only list the harnesses.
         let mut artifacts = vec![];
+        if session.args.list_harnesses.is_some() {
+            return Ok(Project {
+                outdir,
+                input,
+                metadata,
+                artifacts,
+                cargo_metadata,
+                failed_targets,
+            });
+        }
         for crate_metadata in &metadata {
             for harness_metadata in
                 crate_metadata.test_harnesses.iter().chain(crate_metadata.proof_harnesses.iter())
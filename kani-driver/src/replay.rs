@@ -0,0 +1,235 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for `--record-replay` and `--replay`, which reproduce the verification of a harness.
+//!
+//! The outcome of a harness may depend on more than its code: the solver and the options given
+//! to CBMC, the timeout of the harness, and the seed used to randomize the layout of types. With
+//! `--record-replay`, we store these in a replay file next to the metadata of the crate, together
+//! with the outcome of the verification. With `--replay <FILE>`, we verify the recorded harness
+//! again with the recorded configuration instead of the one given in the command line, and we
+//! report an error if the outcome is different.
+//!
+//! The options that affect the compilation are covered by the fingerprint of the harness model,
+//! so a replay is rejected if the model of the harness changed since it was recorded. A replay is
+//! also rejected if the file was recorded by another version of Kani, whose models and CBMC
+//! arguments may differ.
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use kani_metadata::HarnessMetadata;
+use serde::{Deserialize, Serialize};
+
+use crate::args::VerificationArgs;
+use crate::call_cbmc::{ExitStatus, VerificationResult, VerificationStatus};
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+
+/// The extension of the replay files, which are stored in the output directory.
+const REPLAY_EXTENSION: &str = "kani-replay.json";
+
+/// The configuration and the outcome of the verification of a harness.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayFile {
+    /// The version of Kani that recorded this file.
+    kani_version: String,
+    /// The fully qualified name of the harness.
+    harness: String,
+    /// The fingerprint of the harness model, if the compiler recorded one.
+    fingerprint: Option<String>,
+    /// The arguments that were passed to CBMC, except for the model file.
+    cbmc_args: Vec<String>,
+    /// The timeout of the harness in milliseconds, if there was one.
+    timeout_ms: Option<u64>,
+    /// The seed used to randomize the layout of types, if the layout was randomized.
+    layout_seed: Option<u64>,
+    /// The outcome of the verification.
+    outcome: ReplayOutcome,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, strum_macros::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+enum ReplayOutcome {
+    Success,
+    Failure,
+    /// The harness didn't complete before its timeout.
+    Timeout,
+    /// CBMC didn't complete for another reason, e.g. it ran out of memory.
+    Error,
+}
+
+impl ReplayOutcome {
+    fn of(result: &VerificationResult) -> ReplayOutcome {
+        match (&result.results, result.status) {
+            (Err(ExitStatus::Timeout), _) => ReplayOutcome::Timeout,
+            (Err(_), _) => ReplayOutcome::Error,
+            (Ok(_), VerificationStatus::Success) => ReplayOutcome::Success,
+            (Ok(_), VerificationStatus::Failure) => ReplayOutcome::Failure,
+        }
+    }
+}
+
+impl ReplayFile {
+    /// The path of the replay file of a harness in the given output directory.
+    pub fn path(outdir: &Path, harness: &HarnessMetadata) -> PathBuf {
+        let harness_filename = harness.pretty_name.replace("::", "-");
+        outdir.join(format!("{harness_filename}.{REPLAY_EXTENSION}"))
+    }
+
+    pub fn load(path: &Path) -> Result<ReplayFile> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open replay file `{}`", path.display()))?;
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("Failed to parse replay file `{}`", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+
+    /// Override the arguments that select the harness and affect its compilation with the
+    /// recorded ones.
+    pub fn apply(&self, args: &mut VerificationArgs) {
+        args.harnesses = vec![self.harness.clone()];
+        args.exact = true;
+        args.randomize_layout = self.layout_seed.map(Some);
+    }
+
+    /// The arguments to pass to CBMC to verify the given model file.
+    pub fn cbmc_args(&self, file: &Path) -> Vec<OsString> {
+        let mut args: Vec<OsString> = self.cbmc_args.iter().map(OsString::from).collect();
+        args.push(file.to_owned().into_os_string());
+        args
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms.map(Duration::from_millis)
+    }
+
+    /// Return an error if this file was recorded by another version of Kani.
+    fn check_version(&self) -> Result<()> {
+        if self.kani_version != KANI_VERSION {
+            bail!(
+                "The replay file of harness `{}` was recorded by Kani {}, but this is Kani {}, so \
+                its outcome cannot be reproduced",
+                self.harness,
+                self.kani_version,
+                KANI_VERSION
+            );
+        }
+        Ok(())
+    }
+}
+
+impl KaniSession {
+    /// The timeout of each harness, which is the recorded one when replaying.
+    pub fn harness_timeout(&self) -> Option<Duration> {
+        match &self.replay {
+            Some(replay) => replay.timeout(),
+            None => self.args.harness_timeout.map(Duration::from),
+        }
+    }
+
+    /// Return an error if the model of the harness changed since the replay file was recorded,
+    /// or if the file was recorded by another version of Kani.
+    pub(crate) fn check_replay_model(&self, harness: &HarnessMetadata) -> Result<()> {
+        let Some(replay) = &self.replay else { return Ok(()) };
+        replay.check_version()?;
+        if replay.fingerprint != harness.fingerprint {
+            bail!(
+                "The code of harness `{}` changed since the replay file was recorded, so its \
+                outcome cannot be reproduced",
+                harness.pretty_name
+            );
+        }
+        Ok(())
+    }
+
+    /// Record the configuration and the outcome of a harness with `--record-replay`, or check that
+    /// the outcome is the recorded one with `--replay`.
+    pub(crate) fn record_or_check_replay(
+        &self,
+        file: &Path,
+        outdir: &Path,
+        harness: &HarnessMetadata,
+        result: &VerificationResult,
+    ) -> Result<()> {
+        let outcome = ReplayOutcome::of(result);
+        if let Some(replay) = &self.replay {
+            if outcome != replay.outcome {
+                bail!(
+                    "The outcome of harness `{}` is `{outcome}`, but the recorded outcome is `{}`",
+                    harness.pretty_name,
+                    replay.outcome
+                );
+            }
+            if !self.args.common_args.quiet {
+                println!(
+                    "Replay of harness {} reproduced the recorded outcome",
+                    harness.pretty_name
+                );
+            }
+        } else if self.args.record_replay {
+            let cbmc_args = self
+                .cbmc_flags(file, harness)?
+                .into_iter()
+                .filter(|arg| arg != file.as_os_str())
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect();
+            let replay = ReplayFile {
+                kani_version: KANI_VERSION.to_string(),
+                harness: harness.pretty_name.clone(),
+                fingerprint: harness.fingerprint.clone(),
+                cbmc_args,
+                timeout_ms: self.harness_timeout().map(|timeout| timeout.as_millis() as u64),
+                layout_seed: self.args.randomize_layout.flatten(),
+                outcome,
+            };
+            let path = ReplayFile::path(outdir, harness);
+            replay.save(&path)?;
+            if !self.args.common_args.quiet {
+                println!("Replay file written to {}", path.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_replay_outcome() {
+        let success = VerificationResult::mock_success();
+        assert_eq!(ReplayOutcome::of(&success), ReplayOutcome::Success);
+        let timeout = VerificationResult { results: Err(ExitStatus::Timeout), ..success };
+        assert_eq!(ReplayOutcome::of(&timeout), ReplayOutcome::Timeout);
+        assert_eq!(ReplayOutcome::Timeout.to_string(), "timeout");
+    }
+
+    #[test]
+    fn check_replay_version() {
+        let mut replay = ReplayFile {
+            kani_version: KANI_VERSION.to_string(),
+            harness: "check_foo".to_string(),
+            fingerprint: None,
+            cbmc_args: vec![],
+            timeout_ms: None,
+            layout_seed: None,
+            outcome: ReplayOutcome::Success,
+        };
+        assert!(replay.check_version().is_ok());
+        replay.kani_version = "0.0.1".to_string();
+        let err = replay.check_version().unwrap_err().to_string();
+        assert!(err.contains("recorded by Kani 0.0.1"), "{err}");
+    }
+}
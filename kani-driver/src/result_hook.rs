@@ -0,0 +1,169 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Run a user command after each harness is verified, e.g., to post the result to a dashboard or
+//! to notify the user of a failure.
+//!
+//! The command is run by the shell once per harness, and it receives the result of the harness
+//! as a JSON object on its standard input. A nonzero exit status of the command is reported as a
+//! warning, unless `--fail-on-hook-error` is given, in which case it aborts the run.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+
+/// The result of a harness, as given to the hook.
+#[derive(Debug, Serialize)]
+pub struct HookPayload<'a> {
+    kani_version: &'static str,
+    harness: &'a str,
+    crate_name: &'a str,
+    file: &'a str,
+    line: usize,
+    status: VerificationStatus,
+    /// Whether CBMC reported the result of every property, e.g., it didn't time out.
+    complete: bool,
+    failed_properties: Vec<FailedProperty<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct FailedProperty<'a> {
+    /// The unique name of the property, e.g., `check.assertion.1`.
+    property: String,
+    class: &'a str,
+    description: &'a str,
+    /// The location of the property, if CBMC reported one.
+    location: Option<String>,
+}
+
+impl KaniSession {
+    /// Run the command given to `--post-verification-hook` with the given result on its standard
+    /// input.
+    pub(crate) fn run_result_hook(&self, result: &HarnessResult<'_>) -> Result<()> {
+        let Some(hook) = &self.args.post_verification_hook else {
+            return Ok(());
+        };
+        let payload = serde_json::to_string(&hook_payload(result))?;
+        match run_hook(self, hook, &payload) {
+            Err(err) if !self.args.fail_on_hook_error => {
+                eprintln!(
+                    "WARNING: The post-verification hook failed for harness `{}`: {err:#}",
+                    result.harness.pretty_name
+                );
+                Ok(())
+            }
+            outcome => outcome.with_context(|| {
+                format!(
+                    "The post-verification hook failed for harness `{}`",
+                    result.harness.pretty_name
+                )
+            }),
+        }
+    }
+}
+
+/// Build the payload of the hook for the given result.
+pub fn hook_payload<'a>(harness_result: &'a HarnessResult<'_>) -> HookPayload<'a> {
+    let harness = harness_result.harness;
+    let result = &harness_result.result;
+    let failed_properties = result
+        .results
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .filter(|prop| prop.status == CheckStatus::Failure)
+        .map(failed_property)
+        .collect();
+    HookPayload {
+        kani_version: KANI_VERSION,
+        harness: &harness.pretty_name,
+        crate_name: &harness.crate_name,
+        file: &harness.original_file,
+        line: harness.original_start_line,
+        status: result.status,
+        complete: result.results.is_ok(),
+        failed_properties,
+    }
+}
+
+fn failed_property(property: &Property) -> FailedProperty<'_> {
+    let location = &property.source_location;
+    FailedProperty {
+        property: property.property_name(),
+        class: &property.property_id.class,
+        description: &property.description,
+        location: (!location.is_missing()).then(|| location.to_string()),
+    }
+}
+
+/// Run the hook with the shell and write the payload to its standard input.
+fn run_hook(session: &KaniSession, hook: &str, payload: &str) -> Result<()> {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(hook).stdin(Stdio::piped());
+    if session.args.common_args.quiet {
+        cmd.stdout(Stdio::null());
+        cmd.stderr(Stdio::null());
+    }
+    if session.args.common_args.verbose {
+        println!("[Kani] Running post-verification hook: `{hook}`");
+    }
+    let mut child = cmd.spawn().context("Failed to invoke the shell")?;
+    // The hook may exit without reading its input, so a broken pipe is not an error.
+    let mut stdin = child.stdin.take().unwrap();
+    if let Err(err) = stdin.write_all(payload.as_bytes())
+        && err.kind() != std::io::ErrorKind::BrokenPipe
+    {
+        return Err(err.into());
+    }
+    drop(stdin);
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("`{hook}` failed with {status}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cbmc_output_parser::SourceLocation;
+    use crate::metadata::tests::{mock_proof_harness, mock_property, mock_result};
+
+    #[test]
+    fn check_hook_payload() {
+        let harness = mock_proof_harness("check", None, None, None);
+        let mut unknown_location = mock_property("pointer_dereference", CheckStatus::Failure);
+        unknown_location.source_location =
+            SourceLocation { column: None, file: None, function: None, line: None };
+        let result = HarnessResult {
+            harness: &harness,
+            result: mock_result(
+                VerificationStatus::Failure,
+                Ok(vec![
+                    mock_property("assertion", CheckStatus::Failure),
+                    mock_property("arithmetic_overflow", CheckStatus::Success),
+                    unknown_location,
+                ]),
+            ),
+        };
+        let json = serde_json::to_value(hook_payload(&result)).unwrap();
+        assert_eq!(json["harness"], "check");
+        assert_eq!(json["status"], "Failure");
+        assert_eq!(json["complete"], true);
+        let failed = json["failed_properties"].as_array().unwrap();
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0]["property"], "check.assertion.1");
+        assert_eq!(failed[0]["class"], "assertion");
+        assert_eq!(failed[0]["description"], "assertion check");
+        assert_eq!(failed[0]["location"], "src/lib.rs:10:5 in function check");
+        assert_eq!(failed[1]["location"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Export the verification results in the [SARIF](https://sarifweb.azurewebsites.net/) format,
+//! so they can be consumed by code scanning tools, e.g., to report failed checks inline in a PR.
+//!
+//! Each property reported by CBMC becomes a SARIF result, whose rule is the property class.
+//! By default, we only report the properties that failed or that could not be determined, since
+//! those are the ones that require attention.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{CheckStatus, SourceLocation};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const KANI_URI: &str = "https://github.com/model-checking/kani";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct Tool {
+    driver: ToolComponent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolComponent {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Message,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    level: Level,
+    kind: ResultKind,
+    message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    properties: ResultProperties,
+}
+
+/// The severity of a result. SARIF requires the level to be `none` for results that didn't fail.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum Level {
+    None,
+    Warning,
+    Error,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum ResultKind {
+    Pass,
+    Fail,
+    Review,
+    NotApplicable,
+    Informational,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<PhysicalLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Debug, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    fully_qualified_name: String,
+    kind: &'static str,
+}
+
+/// Kani specific information about a result.
+#[derive(Debug, Serialize)]
+struct ResultProperties {
+    harness: String,
+    status: CheckStatus,
+}
+
+impl KaniSession {
+    /// Write the results of all harnesses to the SARIF file given by the user.
+    pub(crate) fn write_sarif(&self, results: &[HarnessResult<'_>], path: &Path) -> Result<()> {
+        let log = sarif_log(results, self.args.sarif_all_properties);
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &log)?;
+        if !self.args.common_args.quiet {
+            println!("Wrote SARIF results to {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Build the SARIF log for the given results.
+///
+/// Cover properties and properties that passed are only included if `all_properties` is set.
+pub fn sarif_log(results: &[HarnessResult<'_>], all_properties: bool) -> SarifLog {
+    let mut sarif_results = vec![];
+    for harness_result in results {
+        let Ok(properties) = &harness_result.result.results else { continue };
+        let harness = &harness_result.harness;
+        // Failures are expected if the harness should panic and verification succeeded.
+        let expected_failure = harness.attributes.should_panic
+            && harness_result.result.status == VerificationStatus::Success;
+        for property in properties {
+            if property.is_code_coverage_property()
+                || (property.is_cover_property() && !all_properties)
+            {
+                continue;
+            }
+            let (level, kind) = classify(property.status, expected_failure);
+            if kind != ResultKind::Fail && kind != ResultKind::Review && !all_properties {
+                continue;
+            }
+            sarif_results.push(SarifResult {
+                rule_id: property.property_class(),
+                level,
+                kind,
+                message: Message { text: property.description.clone() },
+                locations: location(&property.source_location).into_iter().collect(),
+                properties: ResultProperties {
+                    harness: harness.pretty_name.clone(),
+                    status: property.status,
+                },
+            });
+        }
+    }
+    let rules = sarif_results
+        .iter()
+        .map(|result| result.rule_id.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|id| Rule { short_description: Message { text: format!("Kani `{id}` check") }, id })
+        .collect();
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: ToolComponent {
+                    name: "Kani",
+                    version: KANI_VERSION,
+                    information_uri: KANI_URI,
+                    rules,
+                },
+            },
+            results: sarif_results,
+        }],
+    }
+}
+
+/// Map the status of a property to the level and kind of SARIF result.
+fn classify(status: CheckStatus, expected_failure: bool) -> (Level, ResultKind) {
+    match status {
+        CheckStatus::Failure if expected_failure => (Level::None, ResultKind::Pass),
+        CheckStatus::Failure => (Level::Error, ResultKind::Fail),
+        CheckStatus::Undetermined | CheckStatus::Unknown => (Level::Warning, ResultKind::Review),
+        CheckStatus::Success | CheckStatus::Satisfied | CheckStatus::Covered => {
+            (Level::None, ResultKind::Pass)
+        }
+        CheckStatus::Unreachable => (Level::None, ResultKind::NotApplicable),
+        CheckStatus::Unsatisfiable | CheckStatus::Uncovered => {
+            (Level::None, ResultKind::Informational)
+        }
+    }
+}
+
+fn location(source_location: &SourceLocation) -> Option<Location> {
+    if source_location.is_missing() {
+        return None;
+    }
+    let physical_location = source_location.file.as_ref().map(|file| PhysicalLocation {
+        artifact_location: ArtifactLocation { uri: artifact_uri(file) },
+        region: source_location.line.as_ref().and_then(|line| line.parse().ok()).map(|line| {
+            Region {
+                start_line: line,
+                start_column: source_location.column.as_ref().and_then(|col| col.parse().ok()),
+            }
+        }),
+    });
+    let logical_locations = source_location
+        .function
+        .iter()
+        .map(|function| LogicalLocation {
+            fully_qualified_name: function.clone(),
+            kind: "function",
+        })
+        .collect();
+    Some(Location { physical_location, logical_locations })
+}
+
+/// Code scanning tools expect paths that are relative to the repository root. Thus, we make the
+/// path relative to the current directory when possible, and use a `file` URI otherwise.
+fn artifact_uri(file: &str) -> String {
+    let path = Path::new(file);
+    if path.is_relative() {
+        return file.to_string();
+    }
+    let relative = std::env::current_dir().ok().and_then(|cwd| {
+        path.strip_prefix(cwd).ok().map(|relative| relative.to_string_lossy().to_string())
+    });
+    relative.unwrap_or_else(|| format!("file://{file}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::tests::{mock_proof_harness, mock_property, mock_result};
+
+    #[test]
+    fn check_sarif_failures_only() {
+        let harness = mock_proof_harness("check", None, None, None);
+        let result = mock_result(
+            VerificationStatus::Failure,
+            Ok(vec![
+                mock_property("assertion", CheckStatus::Failure),
+                mock_property("assertion", CheckStatus::Success),
+                mock_property("arithmetic_overflow", CheckStatus::Undetermined),
+                mock_property("cover", CheckStatus::Satisfied),
+            ]),
+        );
+        let log = sarif_log(&[HarnessResult { harness: &harness, result }], false);
+        let json = serde_json::to_value(&log).unwrap();
+        let results = json["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], "assertion");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["kind"], "fail");
+        assert_eq!(results[0]["properties"]["status"], "FAILURE");
+        assert_eq!(results[0]["properties"]["harness"], "check");
+        let physical = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(physical["region"]["startLine"], 10);
+        assert_eq!(physical["region"]["startColumn"], 5);
+        assert_eq!(results[1]["ruleId"], "arithmetic_overflow");
+        assert_eq!(results[1]["level"], "warning");
+        let rules = json["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "arithmetic_overflow");
+    }
+
+    #[test]
+    fn check_sarif_all_properties() {
+        let harness = mock_proof_harness("check", None, None, None);
+        let result = mock_result(
+            VerificationStatus::Failure,
+            Ok(vec![
+                mock_property("assertion", CheckStatus::Success),
+                mock_property("cover", CheckStatus::Satisfied),
+                mock_property("code_coverage", CheckStatus::Covered),
+            ]),
+        );
+        let log = sarif_log(&[HarnessResult { harness: &harness, result }], true);
+        let json = serde_json::to_value(&log).unwrap();
+        let results = json["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result["level"] == "none"));
+        assert!(results.iter().all(|result| result["kind"] == "pass"));
+    }
+}
@@ -4,14 +4,15 @@
 use crate::args::Timeout;
 use crate::args::VerificationArgs;
 use crate::args::common::Verbosity;
+use crate::replay::ReplayFile;
 use crate::util::render_command;
 use anyhow::{Context, Result, bail};
 use std::io::IsTerminal;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Stdio};
-use std::sync::Mutex;
-use std::time::Instant;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use strum_macros::Display;
 use tokio::process::Command as TokioCommand;
 use tracing::level_filters::LevelFilter;
@@ -43,6 +44,14 @@ pub struct KaniSession {
 
     /// The tokio runtime
     pub runtime: tokio::runtime::Runtime,
+
+    /// The time when the `--total-timeout` is reached, which is set when the verification of the
+    /// harnesses starts.
+    pub verification_deadline: OnceLock<Instant>,
+
+    /// The replay file given with `--replay`, whose configuration overrides the one given in the
+    /// command line.
+    pub replay: Option<ReplayFile>,
 }
 
 /// Represents where we detected Kani, with helper methods for using that information to find critical paths
@@ -56,9 +65,19 @@ pub enum InstallType {
 }
 
 impl KaniSession {
-    pub fn new(args: VerificationArgs) -> Result<Self> {
+    pub fn new(mut args: VerificationArgs) -> Result<Self> {
         init_logger(&args);
+        let replay = args.replay.as_deref().map(ReplayFile::load).transpose()?;
+        if let Some(replay) = &replay {
+            replay.apply(&mut args);
+        } else if args.record_replay && args.randomize_layout == Some(None) {
+            // Pick the seed ourselves so it can be recorded.
+            args.randomize_layout = Some(Some(rand::random()));
+        }
         let install = InstallType::new()?;
+        if let Some(target) = &args.target_triple {
+            check_target_libs(target)?;
+        }
 
         Ok(KaniSession {
             args,
@@ -67,9 +86,24 @@ pub fn new(args: VerificationArgs) -> Result<Self> {
             kani_lib_c: install.kani_lib_c()?,
             temporaries: Mutex::new(vec![]),
             runtime: tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap(),
+            verification_deadline: OnceLock::new(),
+            replay,
         })
     }
 
+    /// Start the `--total-timeout` budget, if the user gave one. Later calls don't restart it.
+    pub fn start_verification_budget(&self) {
+        if let Some(timeout) = self.args.total_timeout {
+            self.verification_deadline.get_or_init(|| Instant::now() + Duration::from(timeout));
+        }
+    }
+
+    /// The time left before the `--total-timeout` is reached, if the budget was started.
+    pub fn remaining_budget(&self) -> Option<Duration> {
+        let deadline = self.verification_deadline.get()?;
+        Some(deadline.saturating_duration_since(Instant::now()))
+    }
+
     /// Record a temporary file so we can cleanup after ourselves at the end.
     /// Note that there will be no failure if the file does not exist.
     pub fn record_temporary_file<T: AsRef<Path>>(&self, temp: &T) {
@@ -336,6 +370,19 @@ pub fn lib_no_core_folder() -> Result<PathBuf> {
     Ok(base_folder()?.join("no_core/lib"))
 }
 
+/// Return an error if Kani's pre-compiled libraries were not built for the given target.
+fn check_target_libs(target: &str) -> Result<()> {
+    let target_libs = lib_folder()?.join("rustlib").join(target);
+    if !target_libs.exists() {
+        bail!(
+            "Kani's standard library was not built for target `{target}`. \
+            Expected to find it in `{}`.",
+            target_libs.display()
+        );
+    }
+    Ok(())
+}
+
 /// Return the base folder for the entire kani installation.
 pub fn base_folder() -> Result<PathBuf> {
     Ok(bin_folder()?
@@ -0,0 +1,302 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Summarize the outcome of the properties checked in a run, both for the whole run and for each
+//! harness. The summary is printed at the end of the run and written to a JSON file, e.g., for CI
+//! dashboards.
+//!
+//! The summary is also reported if the run is aborted by an error, in which case it only covers
+//! the harnesses that were verified before the error.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::call_cbmc::VerificationStatus;
+use crate::cbmc_output_parser::{CheckStatus, Property};
+use crate::harness_runner::HarnessResult;
+use crate::session::KaniSession;
+use crate::version::KANI_VERSION;
+
+/// The number of properties of each outcome.
+///
+/// Cover properties that are satisfied count as passed, and the ones that can't be satisfied are
+/// counted separately. Code coverage properties are not counted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PropertyCounts {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub undetermined: usize,
+    pub unreachable: usize,
+    pub unsatisfiable: usize,
+}
+
+impl PropertyCounts {
+    fn from_properties(properties: &[Property]) -> PropertyCounts {
+        let mut counts = PropertyCounts::default();
+        for property in properties.iter().filter(|prop| !prop.is_code_coverage_property()) {
+            counts.total += 1;
+            match property.status {
+                CheckStatus::Success | CheckStatus::Satisfied | CheckStatus::Covered => {
+                    counts.passed += 1
+                }
+                CheckStatus::Failure => counts.failed += 1,
+                CheckStatus::Undetermined | CheckStatus::Unknown => counts.undetermined += 1,
+                CheckStatus::Unreachable => counts.unreachable += 1,
+                CheckStatus::Unsatisfiable | CheckStatus::Uncovered => counts.unsatisfiable += 1,
+            }
+        }
+        counts
+    }
+
+    fn add(&mut self, other: &PropertyCounts) {
+        self.total += other.total;
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.undetermined += other.undetermined;
+        self.unreachable += other.unreachable;
+        self.unsatisfiable += other.unsatisfiable;
+    }
+}
+
+impl std::fmt::Display for PropertyCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} properties, {} passed, {} failed, {} undetermined, {} unreachable",
+            self.total, self.passed, self.failed, self.undetermined, self.unreachable
+        )?;
+        if self.unsatisfiable > 0 {
+            write!(f, ", {} unsatisfiable", self.unsatisfiable)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerificationSummary {
+    kani_version: &'static str,
+    /// Whether every harness was verified, i.e., the run was not aborted by an error and no
+    /// harness was skipped because the `--total-timeout` was reached.
+    complete: bool,
+    /// The number of harnesses selected for verification.
+    total_harnesses: usize,
+    /// The number of harnesses skipped because the `--total-timeout` was reached.
+    skipped_harnesses: usize,
+    properties: PropertyCounts,
+    harnesses: Vec<HarnessSummary>,
+}
+
+#[derive(Debug, Serialize)]
+struct HarnessSummary {
+    harness: String,
+    status: VerificationStatus,
+    /// Whether CBMC reported the result of every property, e.g., it didn't time out.
+    complete: bool,
+    /// Whether the harness wasn't verified, or its verification was stopped, because the
+    /// `--total-timeout` was reached.
+    skipped: bool,
+    properties: PropertyCounts,
+}
+
+impl KaniSession {
+    /// Print the summary of the given results, and write it to the JSON file given by the user.
+    ///
+    /// `total_harnesses` is the number of harnesses that were selected for verification, which is
+    /// larger than the number of results if the run was aborted.
+    pub(crate) fn report_summary(
+        &self,
+        results: &[HarnessResult<'_>],
+        total_harnesses: usize,
+        path: &Path,
+    ) -> Result<()> {
+        let summary = verification_summary(results, total_harnesses);
+        if !self.args.common_args.quiet {
+            print!("{}", render_summary(&summary));
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &summary)?;
+        if !self.args.common_args.quiet {
+            println!("Wrote the verification summary to {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Build the summary of the given results.
+pub fn verification_summary(
+    results: &[HarnessResult<'_>],
+    total_harnesses: usize,
+) -> VerificationSummary {
+    let mut properties = PropertyCounts::default();
+    let harnesses = results
+        .iter()
+        .map(|harness_result| {
+            let result = &harness_result.result;
+            let counts = result.results.as_ref().map_or_else(
+                |_| PropertyCounts::default(),
+                |props| PropertyCounts::from_properties(props),
+            );
+            properties.add(&counts);
+            HarnessSummary {
+                harness: harness_result.harness.pretty_name.clone(),
+                status: result.status,
+                complete: result.results.is_ok(),
+                skipped: result.is_skipped(),
+                properties: counts,
+            }
+        })
+        .collect();
+    let skipped_harnesses = results.iter().filter(|result| result.result.is_skipped()).count();
+    VerificationSummary {
+        kani_version: KANI_VERSION,
+        complete: results.len() == total_harnesses && skipped_harnesses == 0,
+        total_harnesses,
+        skipped_harnesses,
+        properties,
+        harnesses,
+    }
+}
+
+fn render_summary(summary: &VerificationSummary) -> String {
+    let mut output = String::new();
+    if summary.complete {
+        output.push_str("Property summary:\n");
+    } else if summary.skipped_harnesses > 0 && summary.harnesses.len() == summary.total_harnesses {
+        output.push_str(&format!(
+            "Property summary (incomplete, {} of {} harnesses were skipped because the total \
+            timeout was reached):\n",
+            summary.skipped_harnesses, summary.total_harnesses
+        ));
+    } else {
+        output.push_str(&format!(
+            "Property summary (incomplete, the run was aborted after verifying {} of {} \
+            harnesses):\n",
+            summary.harnesses.len(),
+            summary.total_harnesses
+        ));
+    }
+    for harness in &summary.harnesses {
+        if harness.skipped {
+            output.push_str(&format!(" - {}: skipped (budget exhausted)\n", harness.harness));
+            continue;
+        }
+        let incomplete = if harness.complete { "" } else { " (incomplete)" };
+        output.push_str(&format!(" - {}: {}{incomplete}\n", harness.harness, harness.properties));
+    }
+    output.push_str(&format!("Total: {}\n", summary.properties));
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::call_cbmc::VerificationResult;
+    use crate::metadata::tests::{mock_proof_harness, mock_property, mock_result};
+
+    #[test]
+    fn check_summary_counts() {
+        let first = mock_proof_harness("first", None, None, None);
+        let second = mock_proof_harness("second", None, None, None);
+        let results = [
+            HarnessResult {
+                harness: &first,
+                result: mock_result(
+                    VerificationStatus::Failure,
+                    Ok(vec![
+                        mock_property("assertion", CheckStatus::Failure),
+                        mock_property("assertion", CheckStatus::Success),
+                        mock_property("arithmetic_overflow", CheckStatus::Unreachable),
+                        mock_property("code_coverage", CheckStatus::Covered),
+                    ]),
+                ),
+            },
+            HarnessResult {
+                harness: &second,
+                result: mock_result(
+                    VerificationStatus::Success,
+                    Ok(vec![
+                        mock_property("cover", CheckStatus::Satisfied),
+                        mock_property("cover", CheckStatus::Unsatisfiable),
+                        mock_property("assertion", CheckStatus::Undetermined),
+                    ]),
+                ),
+            },
+        ];
+        let summary = verification_summary(&results, 2);
+        assert!(summary.complete);
+        assert_eq!(summary.properties, PropertyCounts {
+            total: 6,
+            passed: 2,
+            failed: 1,
+            undetermined: 1,
+            unreachable: 1,
+            unsatisfiable: 1,
+        });
+        assert_eq!(summary.harnesses[0].properties.total, 3);
+        assert_eq!(
+            render_summary(&summary),
+            "Property summary:\n \
+            - first: 3 properties, 1 passed, 1 failed, 0 undetermined, 1 unreachable\n \
+            - second: 3 properties, 1 passed, 0 failed, 1 undetermined, 0 unreachable, \
+            1 unsatisfiable\n\
+            Total: 6 properties, 2 passed, 1 failed, 1 undetermined, 1 unreachable, \
+            1 unsatisfiable\n"
+        );
+    }
+
+    #[test]
+    fn check_partial_summary() {
+        let harness = mock_proof_harness("first", None, None, None);
+        let results = [HarnessResult {
+            harness: &harness,
+            result: mock_result(
+                VerificationStatus::Success,
+                Ok(vec![mock_property("assertion", CheckStatus::Success)]),
+            ),
+        }];
+        let summary = verification_summary(&results, 3);
+        assert!(!summary.complete);
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["complete"], false);
+        assert_eq!(json["total_harnesses"], 3);
+        assert_eq!(json["harnesses"][0]["harness"], "first");
+        assert_eq!(json["properties"]["passed"], 1);
+        assert!(render_summary(&summary).starts_with(
+            "Property summary (incomplete, the run was aborted after verifying 1 of 3 harnesses):"
+        ));
+    }
+
+    #[test]
+    fn check_skipped_summary() {
+        let first = mock_proof_harness("first", None, None, None);
+        let second = mock_proof_harness("second", None, None, None);
+        let results = [
+            HarnessResult {
+                harness: &first,
+                result: mock_result(
+                    VerificationStatus::Success,
+                    Ok(vec![mock_property("assertion", CheckStatus::Success)]),
+                ),
+            },
+            HarnessResult { harness: &second, result: VerificationResult::skipped() },
+        ];
+        let summary = verification_summary(&results, 2);
+        assert!(!summary.complete);
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["skipped_harnesses"], 1);
+        assert_eq!(json["harnesses"][1]["skipped"], true);
+        assert_eq!(
+            render_summary(&summary),
+            "Property summary (incomplete, 1 of 2 harnesses were skipped because the total \
+            timeout was reached):\n \
+            - first: 1 properties, 1 passed, 0 failed, 0 undetermined, 0 unreachable\n \
+            - second: skipped (budget exhausted)\n\
+            Total: 1 properties, 1 passed, 0 failed, 0 undetermined, 0 unreachable\n"
+        );
+    }
+}
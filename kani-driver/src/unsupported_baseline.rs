@@ -0,0 +1,188 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Compare the unsupported constructs reached by the harnesses against a baseline of known ones.
+//!
+//! The baseline is a JSON file with the list of known unsupported constructs and the files where
+//! they are found:
+//!
+//! ```json
+//! [{ "feature": "foreign function", "filename": "src/ffi.rs" }]
+//! ```
+//!
+//! Constructs are identified by file rather than by line, so that the baseline doesn't become
+//! stale when the code around them moves. Verification fails if the harnesses reach a construct
+//! that isn't in the baseline, which prevents the verified code from silently losing coverage.
+
+use crate::project::Project;
+use crate::session::KaniSession;
+use anyhow::{Context, Result, bail};
+use kani_metadata::KaniMetadata;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// An unsupported construct in a given file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct BaselineEntry {
+    feature: String,
+    filename: String,
+}
+
+impl KaniSession {
+    /// Fail if the project reaches unsupported constructs that aren't in the baseline.
+    ///
+    /// With `--update-unsupported-baseline`, the constructs that are no longer reachable are
+    /// removed from the baseline, which is created if it doesn't exist.
+    pub(crate) fn check_unsupported_baseline(
+        &self,
+        project: &Project,
+        baseline: &Path,
+    ) -> Result<()> {
+        let reached = reached_constructs(&project.metadata);
+        if !baseline.exists() {
+            if !self.args.update_unsupported_baseline {
+                bail!(
+                    "the unsupported baseline `{}` doesn't exist, use \
+                    `--update-unsupported-baseline` to create it",
+                    baseline.display()
+                );
+            }
+            write_baseline(baseline, reached.keys())?;
+            if !self.args.common_args.quiet {
+                println!(
+                    "Created the unsupported baseline `{}` with {} constructs",
+                    baseline.display(),
+                    reached.len()
+                );
+            }
+            return Ok(());
+        }
+
+        let known: BTreeSet<BaselineEntry> =
+            serde_json::from_reader(BufReader::new(File::open(baseline)?)).with_context(|| {
+                format!("invalid unsupported baseline `{}`", baseline.display())
+            })?;
+        if self.args.update_unsupported_baseline {
+            let kept: Vec<_> = known.iter().filter(|entry| reached.contains_key(entry)).collect();
+            if kept.len() < known.len() {
+                write_baseline(baseline, kept.iter().copied())?;
+                if !self.args.common_args.quiet {
+                    println!(
+                        "Removed {} constructs that are no longer reachable from the unsupported \
+                        baseline `{}`",
+                        known.len() - kept.len(),
+                        baseline.display()
+                    );
+                }
+            }
+        }
+
+        let new = new_constructs(&reached, &known);
+        if !new.is_empty() {
+            bail!(
+                "found {} unsupported constructs that aren't in the baseline `{}`:\n{}",
+                new.len(),
+                baseline.display(),
+                render_constructs(&new)
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The unsupported constructs reached in each file, and the lines where they are found.
+fn reached_constructs(metadata: &[KaniMetadata]) -> BTreeMap<BaselineEntry, BTreeSet<u64>> {
+    let mut reached: BTreeMap<BaselineEntry, BTreeSet<u64>> = BTreeMap::new();
+    for feature in metadata.iter().flat_map(|crate_metadata| &crate_metadata.unsupported_features) {
+        for location in &feature.locations {
+            let entry = BaselineEntry {
+                feature: feature.feature.clone(),
+                filename: location.filename.clone(),
+            };
+            reached.entry(entry).or_default().insert(location.start_line);
+        }
+    }
+    reached
+}
+
+/// The reached constructs that aren't in the baseline.
+fn new_constructs<'a>(
+    reached: &'a BTreeMap<BaselineEntry, BTreeSet<u64>>,
+    known: &BTreeSet<BaselineEntry>,
+) -> Vec<(&'a BaselineEntry, &'a BTreeSet<u64>)> {
+    reached.iter().filter(|(entry, _)| !known.contains(entry)).collect()
+}
+
+fn render_constructs(constructs: &[(&BaselineEntry, &BTreeSet<u64>)]) -> String {
+    let mut msg = String::new();
+    for (entry, lines) in constructs {
+        let lines: Vec<_> = lines.iter().map(|line| format!("{}:{line}", entry.filename)).collect();
+        writeln!(&mut msg, "    - {} at {}", entry.feature, lines.join(", ")).unwrap();
+    }
+    msg
+}
+
+fn write_baseline<'a>(
+    baseline: &Path,
+    entries: impl Iterator<Item = &'a BaselineEntry>,
+) -> Result<()> {
+    let entries: Vec<_> = entries.collect();
+    let writer = BufWriter::new(File::create(baseline)?);
+    serde_json::to_writer_pretty(writer, &entries)
+        .with_context(|| format!("failed to write unsupported baseline `{}`", baseline.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kani_metadata::{Location, UnsupportedFeature};
+
+    fn mock_metadata(features: &[(&str, &str, u64)]) -> KaniMetadata {
+        KaniMetadata {
+            crate_name: "mock".into(),
+            proof_harnesses: vec![],
+            unsupported_features: features
+                .iter()
+                .map(|(feature, filename, line)| UnsupportedFeature {
+                    feature: feature.to_string(),
+                    locations: [Location { filename: filename.to_string(), start_line: *line }]
+                        .into(),
+                })
+                .collect(),
+            test_harnesses: vec![],
+            contracted_functions: vec![],
+        }
+    }
+
+    #[test]
+    fn check_new_constructs() {
+        let metadata = mock_metadata(&[
+            ("foreign function", "src/ffi.rs", 10),
+            ("foreign function", "src/ffi.rs", 20),
+            ("foreign function", "src/lib.rs", 5),
+            ("inline assembly", "src/asm.rs", 3),
+        ]);
+        let reached = reached_constructs(&[metadata]);
+        assert_eq!(reached.len(), 3);
+
+        let known: BTreeSet<BaselineEntry> = serde_json::from_str(
+            r#"[
+                { "feature": "foreign function", "filename": "src/ffi.rs" },
+                { "feature": "simd", "filename": "src/ffi.rs" }
+            ]"#,
+        )
+        .unwrap();
+        let new = new_constructs(&reached, &known);
+        assert_eq!(
+            render_constructs(&new),
+            "    - foreign function at src/lib.rs:5\n    - inline assembly at src/asm.rs:3\n"
+        );
+
+        let known: BTreeSet<_> = reached.keys().cloned().collect();
+        assert!(new_constructs(&reached, &known).is_empty());
+    }
+}
@@ -4,6 +4,7 @@
 use crate::CbmcSolver;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use strum_macros::{AsRefStr, EnumString, VariantNames};
 
 /// A CBMC-level `assigns` contract that needs to be enforced on a function.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -36,6 +37,60 @@ pub struct HarnessMetadata {
     pub attributes: HarnessAttributes,
     /// A CBMC-level assigns contract that should be enforced when running this harness.
     pub contract: Option<AssignsContract>,
+    /// The properties generated by the compiler for this harness in a stable order.
+    ///
+    /// This is only populated for proof harnesses, and it does not include the checks that
+    /// CBMC instruments on its own.
+    pub properties: Vec<PropertyMetadata>,
+    /// The user variables reachable from this harness whose type carries zero-sized type
+    /// parameters, e.g. the state of a type-state machine.
+    pub type_states: Vec<TypeStateVariable>,
+    /// A fingerprint of the model generated for this harness, which changes whenever the code
+    /// reachable from the harness changes.
+    pub fingerprint: Option<String>,
+    /// The functions whose calls were assumed to return `Ok` with `--assume-ok`, so the error
+    /// paths of these calls were not verified.
+    pub assumed_ok: Vec<String>,
+    /// The number of bits that the integers of the crate under verification were restricted to
+    /// with `--int-abstraction`, so the executions where an integer doesn't fit were not verified.
+    pub int_abstraction_bits: Option<u32>,
+}
+
+/// A property that will be checked during verification, e.g.: an assertion, a safety check or a
+/// cover statement.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PropertyMetadata {
+    /// The class of the property, e.g.: `assertion`, `cover` or `safety_check`.
+    pub property_class: String,
+    /// The description that will be used when reporting the verification result.
+    pub description: String,
+    /// The source location of the property, if there is one.
+    pub location: Option<PropertyLocation>,
+}
+
+/// A variable whose type has zero-sized type parameters, such as `Door<Open>` where `Open` is a
+/// marker type. These parameters don't show up in the values of a trace, so we keep track of
+/// them to render the state of the variable in a counterexample.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TypeStateVariable {
+    /// The name of the variable in the CBMC symbol table.
+    pub symbol: String,
+    /// The name of the variable in the source code.
+    pub variable: String,
+    /// The function that declares the variable.
+    pub function: String,
+    /// The type of the variable including its type parameters.
+    pub ty: String,
+}
+
+/// The location of a property in the source code.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PropertyLocation {
+    pub file: String,
+    /// The function that contains the property.
+    pub function: Option<String>,
+    pub line: u64,
+    pub column: Option<u64>,
 }
 
 /// The attributes added by the user to control how a harness is executed.
@@ -51,8 +106,46 @@ pub struct HarnessAttributes {
     pub unwind_value: Option<u32>,
     /// The stubs used in this harness.
     pub stubs: Vec<Stub>,
+    /// The paths of the stub sets used in this harness, whose stubs are not included in `stubs`.
+    pub stub_sets: Vec<String>,
     /// The name of the functions being stubbed by their contract.
     pub verified_stubs: Vec<String>,
+    /// The types whose drop glue is replaced, where `original` is the type and `replacement`
+    /// is the function that is called instead.
+    pub drop_stubs: Vec<Stub>,
+    /// The built-in checks that are not generated for this harness.
+    pub disabled_checks: Vec<BuiltinCheck>,
+}
+
+/// The built-in checks that can be disabled for a harness with `#[kani::disable_checks]`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    AsRefStr,
+    EnumString,
+    VariantNames,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize
+)]
+#[strum(serialize_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltinCheck {
+    /// Overflow of integer arithmetic, including the overflow panics of Rust.
+    ArithmeticOverflow,
+    /// Overflow of pointer arithmetic, e.g., when computing an offset.
+    PointerOverflow,
+    /// Accesses through a pointer that is not aligned to its type.
+    UnalignedAccess,
+    /// Division by zero, which is checked by CBMC.
+    DivByZero,
+    /// Floating point operations whose result is infinite, which is checked by CBMC.
+    FloatOverflow,
+    /// Floating point operations whose result is NaN, which is checked by CBMC.
+    Nan,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
@@ -60,7 +153,10 @@ pub enum HarnessKind {
     /// Function was annotated with `#[kani::proof]`.
     Proof,
     /// Function was annotated with `#[kani::proof_for_contract(target_fn)]`.
-    ProofForContract { target_fn: String },
+    ///
+    /// The `contract` is the name of the named contract of `target_fn` that should be checked,
+    /// if any.
+    ProofForContract { target_fn: String, contract: Option<String> },
     /// This is a test harness annotated with `#[test]`.
     Test,
 }
@@ -74,7 +170,10 @@ pub fn new(kind: HarnessKind) -> HarnessAttributes {
             solver: None,
             unwind_value: None,
             stubs: vec![],
+            stub_sets: vec![],
             verified_stubs: vec![],
+            drop_stubs: vec![],
+            disabled_checks: vec![],
         }
     }
 
@@ -19,6 +19,11 @@
 
 pub use unstable::{EnabledUnstableFeatures, UnstableFeature};
 
+/// The description of the cover that the compiler adds at the end of each harness with
+/// `--int-abstraction`. The driver reports the harnesses for which it's not satisfied.
+pub const INT_ABSTRACTION_COVER: &str =
+    "the end of the harness is reachable with --int-abstraction";
+
 /// The structure of `.kani-metadata.json` files, which are emitted for each crate
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KaniMetadata {
@@ -96,6 +96,11 @@ pub enum UnstableFeature {
     GhostState,
     /// Automatically check that uninitialized memory is not used.
     UninitChecks,
+    /// Automatically check that `offset`, `add` and `sub` keep pointers in-bounds of their
+    /// allocation.
+    PointerOffsetChecks,
+    /// Automatically check that pinned values are not moved out of their location.
+    PinChecks,
     /// Enable an unstable option or subcommand.
     UnstableOptions,
     /// The list subcommand [RFC 13](https://model-checking.github.io/kani/rfc/rfcs/0013-list.html)
@@ -15,11 +15,31 @@ fn any() -> Self {
     }
 }
 
-impl Arbitrary for std::time::Duration {
+/// Generate a reference to a freshly allocated nondeterministic value.
+///
+/// The referent is leaked, so it lives until the end of the harness. See [`crate::arena`] for
+/// references that may point to the same value.
+///
+/// Since `Option<&T>` is generated by picking one of its variants, `kani::any::<Option<&T>>()`
+/// is either `None`, which is represented by the null pointer, or a reference to a fresh value.
+impl<T> Arbitrary for &'static T
+where
+    T: Arbitrary + 'static,
+{
+    fn any() -> Self {
+        Box::leak(Box::new(T::any()))
+    }
+}
+
+/// Generate a mutable reference to a freshly allocated nondeterministic value.
+///
+/// Every reference points to a different allocation, so mutable references never alias.
+/// See [`crate::arena`] for references that may alias.
+impl<T> Arbitrary for &'static mut T
+where
+    T: Arbitrary + 'static,
+{
     fn any() -> Self {
-        const NANOS_PER_SEC: u32 = 1_000_000_000;
-        let nanos = u32::any();
-        crate::assume(nanos < NANOS_PER_SEC);
-        std::time::Duration::new(u64::any(), nanos)
+        Box::leak(Box::new(T::any()))
     }
 }
@@ -0,0 +1,131 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! A symbolic arena of nondeterministic values that references can point into.
+//!
+//! `kani::any::<&T>()` and `kani::any::<&mut T>()` allocate a fresh value for every reference,
+//! so two generated references never point to the same value. This is sound, but it may miss
+//! bugs in code that only fails when two arguments refer to the same object. An [`Arena`]
+//! covers these cases by picking each reference among a fixed number of values:
+//!
+//! ```no_run
+//! #[kani::proof]
+//! fn check_same_node() {
+//!     let arena: kani::arena::Arena<u8, 2> = kani::arena::Arena::new();
+//!     let (first, second) = (arena.any_ref(), arena.any_ref());
+//!     kani::cover!(core::ptr::eq(first, second));
+//! }
+//! ```
+//!
+//! # Soundness
+//!
+//! Shared references returned by [`Arena::any_ref`] may alias each other, which is allowed by
+//! Rust. Mutable references are a different story: the aliasing model of Rust requires a `&mut T`
+//! to be the only reference that can access its referent while it is in use. For that reason,
+//! [`Arena::any_mut`] only hands out values that haven't been handed out before, neither mutably
+//! nor shared, and the only way to get aliasing mutable references is the `unsafe`
+//! [`Arena::any_mut_aliasing`].
+//!
+//! Kani doesn't check the aliasing rules, so code that is verified with aliasing mutable
+//! references may be compiled in a way that doesn't match the verified behavior. Only opt into
+//! aliasing to model references that come from raw pointers or from `unsafe` code that is known
+//! to alias, and keep in mind that any result is only as good as this assumption.
+use crate::{Arbitrary, any, any_where};
+use std::cell::Cell;
+
+/// A fixed number of nondeterministic values of type `T` that live until the end of the harness.
+pub struct Arena<T, const N: usize> {
+    values: *mut [T; N],
+    /// How each value has been handed out.
+    loans: Cell<[Loan; N]>,
+}
+
+/// How a value of an arena has been handed out.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Loan {
+    Free,
+    Shared,
+    Mutable,
+}
+
+impl<T: Arbitrary, const N: usize> Arena<T, N> {
+    /// Allocate `N` nondeterministic values.
+    pub fn new() -> Self {
+        crate::assert(N > 0, "An arena must have at least one value");
+        Arena { values: Box::into_raw(Box::new(any())), loans: Cell::new([Loan::Free; N]) }
+    }
+}
+
+impl<T: Arbitrary, const N: usize> Default for Arena<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Arena<T, N> {
+    /// Return a reference to any value of the arena, which may alias other shared references
+    /// returned by this method.
+    ///
+    /// Values that have been handed out by [`Arena::any_mut`] are never returned.
+    pub fn any_ref(&self) -> &'static T {
+        let mut loans = self.loans.get();
+        crate::assert(
+            loans.iter().any(|loan| *loan != Loan::Mutable),
+            "Arena has no value left to borrow",
+        );
+        let index = any_where(|index: &usize| *index < N && loans[*index] != Loan::Mutable);
+        loans[index] = Loan::Shared;
+        self.loans.set(loans);
+        // SAFETY: The values are never deallocated, and this value is never mutably borrowed.
+        unsafe { &(*self.values)[index] }
+    }
+
+    /// Return a mutable reference to any value of the arena that hasn't been handed out before,
+    /// neither by this method nor by [`Arena::any_ref`].
+    ///
+    /// Verification fails if all the values have already been handed out.
+    pub fn any_mut(&self) -> &'static mut T {
+        let mut loans = self.loans.get();
+        crate::assert(
+            loans.iter().any(|loan| *loan == Loan::Free),
+            "Arena has no value left to borrow mutably",
+        );
+        let index = any_where(|index: &usize| *index < N && loans[*index] == Loan::Free);
+        loans[index] = Loan::Mutable;
+        self.loans.set(loans);
+        // SAFETY: The values are never deallocated, and this value is never handed out again.
+        unsafe { &mut (*self.values)[index] }
+    }
+
+    /// Return either `None` or a reference to any value of the arena, like [`Arena::any_ref`].
+    ///
+    /// `None` is always possible, and it's the only result once all the values have been handed
+    /// out by [`Arena::any_mut`].
+    pub fn any_option_ref(&self) -> Option<&'static T> {
+        let available = self.loans.get().iter().any(|loan| *loan != Loan::Mutable);
+        if available && any() { Some(self.any_ref()) } else { None }
+    }
+
+    /// Return either `None` or a mutable reference to any value of the arena that hasn't been
+    /// handed out before, like [`Arena::any_mut`].
+    ///
+    /// The value is only handed out if the result is `Some`, so a `None` doesn't prevent later
+    /// calls from borrowing it. Once all the values have been handed out, the result is `None`.
+    pub fn any_option_mut(&self) -> Option<&'static mut T> {
+        let available = self.loans.get().iter().any(|loan| *loan == Loan::Free);
+        if available && any() { Some(self.any_mut()) } else { None }
+    }
+
+    /// Return a mutable reference to any value of the arena, which may alias other references
+    /// returned by this arena.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the references handed out by this arena are not used in a way
+    /// that violates the aliasing rules of Rust. See the [module documentation](self) for the
+    /// implications on the verification results.
+    pub unsafe fn any_mut_aliasing(&self) -> &'static mut T {
+        let index = any_where(|index: &usize| *index < N);
+        // SAFETY: The values are never deallocated, and the caller upholds the aliasing rules.
+        unsafe { &mut (*self.values)[index] }
+    }
+}
@@ -10,10 +10,34 @@
     /// This allows us to run concrete playback unit tests in parallel.
     /// RefCell is necessary for mut statics.
     static CONCRETE_VALS: RefCell<Vec<Vec<u8>>> = RefCell::new(Vec::new());
+    /// Generator of the values requested after the concrete values run out, if a seed was given.
+    static SEEDED_VALS: RefCell<Option<SeededVals>> = const { RefCell::new(None) };
 }
 
+/// Environment variable that overrides the concrete values of a playback test.
+///
+/// This is used by `kani --minimize` to check whether other values also fail. The values are
+/// separated by `;`, and the bytes of each value by `,`, e.g. `1,0;255`.
+const CONCRETE_VALS_VAR: &str = "KANI_CONCRETE_VALS";
+
+/// Environment variable with the seed used to generate the values of `kani::any()` calls.
+///
+/// This is used by `kani --concretize-seed` to run a harness as a randomized test. The values are
+/// generated by a pseudo-random generator, so the same seed always reproduces the same run.
+const CONCRETIZE_SEED_VAR: &str = "KANI_CONCRETIZE_SEED";
+
 /// This function sets concrete values and plays back the user's proof harness.
 pub fn concrete_playback_run<F: Fn()>(mut local_concrete_vals: Vec<Vec<u8>>, proof_harness: F) {
+    if let Ok(vals) = std::env::var(CONCRETE_VALS_VAR) {
+        local_concrete_vals = parse_concrete_vals(&vals);
+    }
+    let seeded_vals = std::env::var(CONCRETIZE_SEED_VAR).ok().map(|seed| {
+        let seed = seed.parse().unwrap_or_else(|_| {
+            panic!("Invalid seed `{seed}` in `{CONCRETIZE_SEED_VAR}`, expected a `u64`")
+        });
+        SeededVals::new(seed)
+    });
+    SEEDED_VALS.with(|glob_seeded_vals| *glob_seeded_vals.borrow_mut() = seeded_vals);
     // Det vals in the user test case should be in the same order as the order of kani::any() calls.
     // Here, we need to reverse this order because det vals are popped off of the outer Vec,
     // so the chronological first det val should come last.
@@ -40,6 +64,74 @@ pub fn concrete_playback_run<F: Fn()>(mut local_concrete_vals: Vec<Vec<u8>>, pro
     });
 }
 
+/// The prefix of the lines that report the output recorded by [oracle_output].
+const ORACLE_OUTPUT_PREFIX: &str = "kani-oracle-output: ";
+
+/// Concrete playback implementation of `kani::oracle_output`, which prints the output so that
+/// `kani --oracle` can compare it with the expected one.
+pub fn oracle_output<T: std::fmt::Debug>(output: &T) {
+    println!("{ORACLE_OUTPUT_PREFIX}{output:?}");
+}
+
+/// Parse the concrete values given in the format of [CONCRETE_VALS_VAR].
+fn parse_concrete_vals(vals: &str) -> Vec<Vec<u8>> {
+    if vals.is_empty() {
+        return vec![];
+    }
+    vals.split(';')
+        .map(|val| {
+            val.split(',')
+                .filter(|byte| !byte.is_empty())
+                .map(|byte| {
+                    byte.parse().unwrap_or_else(|_| {
+                        panic!("Invalid byte `{byte}` in `{CONCRETE_VALS_VAR}`: `{vals}`")
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// A SplitMix64 pseudo-random generator of concrete values.
+///
+/// See <https://prng.di.unimi.it/splitmix64.c>. We use our own implementation to keep the same
+/// sequence of values for a given seed across Kani versions.
+struct SeededVals {
+    state: u64,
+}
+
+impl SeededVals {
+    fn new(seed: u64) -> Self {
+        SeededVals { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a value of `size` bytes.
+    ///
+    /// Half of the values are either `0` or `1`, which are common edge cases. This also ensures
+    /// that types with a restricted representation, like `bool`, get valid values often enough,
+    /// since invalid values violate the assumptions of their `Arbitrary` implementation.
+    fn next_val(&mut self, size: usize) -> Vec<u8> {
+        let mut bytes = vec![0; size];
+        let choice = self.next_u64();
+        if choice & 1 == 0 {
+            bytes[0] = ((choice >> 1) & 1) as u8;
+        } else {
+            for chunk in bytes.chunks_mut(8) {
+                chunk.copy_from_slice(&self.next_u64().to_le_bytes()[..chunk.len()]);
+            }
+        }
+        bytes
+    }
+}
+
 /// Iterate over `any_raw_internal` since CBMC produces assignment per element.
 pub(crate) unsafe fn any_raw_array<T: Copy, const N: usize>() -> [T; N] {
     [(); N].map(|_| crate::any_raw_internal::<T>())
@@ -58,7 +150,12 @@ pub(crate) unsafe fn any_raw_internal<T: Copy>() -> T {
     CONCRETE_VALS.with(|glob_concrete_vals| {
         let mut_ref_glob_concrete_vals = &mut *glob_concrete_vals.borrow_mut();
         next_concrete_val = if sz > 0 {
-            mut_ref_glob_concrete_vals.pop().expect("Not enough det vals found")
+            mut_ref_glob_concrete_vals.pop().unwrap_or_else(|| {
+                SEEDED_VALS.with(|glob_seeded_vals| {
+                    let seeded_vals = &mut *glob_seeded_vals.borrow_mut();
+                    seeded_vals.as_mut().expect("Not enough det vals found").next_val(sz)
+                })
+            })
         } else {
             vec![]
         };
@@ -206,6 +206,33 @@
 //! Unlike `proof_for_contract` multiple `stub_verified` attributes are allowed
 //! on the same proof harness though they must target different functions.
 //!
+//! ## Named Contracts
+//!
+//! A function may satisfy different specifications in different contexts. Instead of a single
+//! contract, such a function can declare multiple named contracts with the
+//! [`contract`](macro@contract) attribute. Each named contract bundles its own clauses:
+//!
+//! ```
+//! #[kani::contract(name = "small", requires = x < 100, ensures = |result: &u32| *result < 200)]
+//! #[kani::contract(name = "even", ensures = |result: &u32| *result % 2 == 0)]
+//! fn double(x: u32) -> u32 {
+//!     x.wrapping_mul(2)
+//! }
+//! ```
+//!
+//! Each named contract is checked independently by a harness that selects it:
+//!
+//! ```
+//! # #[kani::contract(name = "small", requires = x < 100, ensures = |result: &u32| *result < 200)]
+//! # fn double(x: u32) -> u32 { x.wrapping_mul(2) }
+//! #[kani::proof_for_contract(double, contract = "small")]
+//! fn check_double_small() {
+//!     double(kani::any());
+//! }
+//! ```
+//!
+//! Named contracts are not supported on methods of trait implementations.
+//!
 //! ## Inductive Verification
 //!
 //! Function contracts by default use inductive verification to efficiently
@@ -275,4 +302,16 @@
 //! Here, the value stored in `a` is precomputed and remembered after the function
 //! is called, even though the contents of `a` changed during the function execution.
 //!
-pub use super::{ensures, modifies, proof_for_contract, requires, stub_verified};
+//! Kani emits a warning if an ensures clause reads a mutable reference argument, but
+//! never refers to its value before the call with `old`, since the clause can only
+//! observe the mutated value. If that is intended, the warning can be silenced by
+//! annotating the function with `#[kani::allow_poststate_read]`.
+//!
+//! Kani also warns about `requires` and `ensures` clauses that are always true or always false,
+//! such as `requires(true)`, which are usually a copy-paste mistake. This warning can be silenced
+//! by annotating the function with `#[kani::allow_trivial_contract]`.
+//!
+pub use super::{
+    allow_poststate_read, allow_trivial_contract, contract, ensures, modifies, proof_for_contract,
+    requires, stub_verified,
+};
@@ -0,0 +1,294 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! A symbolic model of the file system that can be used to verify code that uses [`std::fs`].
+//!
+//! The model is enabled with `--model-fs -Z stubbing`, which replaces the following entry points
+//! by the models in this module:
+//!  - [`File::open`], [`File::create`] and [`File::metadata`].
+//!  - [`Read::read`](io::Read::read), [`Read::read_to_end`](io::Read::read_to_end) and
+//!    [`Read::read_to_string`](io::Read::read_to_string) for [`File`].
+//!  - [`Write::write`](io::Write::write) and [`Write::flush`](io::Write::flush) for [`File`].
+//!  - [`std::fs::read`], [`std::fs::read_to_string`], [`std::fs::write`] and [`std::fs::metadata`].
+//!  - [`Metadata::len`], [`Metadata::is_file`] and [`Metadata::is_dir`].
+//!
+//! The file system is kept in memory and starts with an unknown state: the first time a path is
+//! accessed, the model nondeterministically picks whether the file exists and what its contents
+//! are. Every following operation on the same path is consistent with that choice, e.g., if
+//! opening a file fails with [`io::ErrorKind::NotFound`], reading it will fail with the same
+//! error until the file is created.
+//!
+//! ```no_run
+//! use std::io::Read;
+//!
+//! #[kani::proof]
+//! fn check_round_trip() {
+//!     kani::fs::set_max_file_size(4);
+//!     std::fs::write("config", b"on").unwrap();
+//!     let mut contents = String::new();
+//!     std::fs::File::open("config").unwrap().read_to_string(&mut contents).unwrap();
+//!     assert_eq!(contents, "on");
+//! }
+//! ```
+//!
+//! Only regular files are modeled, and the size and number of the files that may exist before
+//! the harness starts are bounded by [`set_max_file_size`] and [`set_max_files`].
+//! Operations through a `&File` are not modeled.
+use crate::{any, any_where};
+use std::fs::{File, Metadata};
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, RawFd};
+use std::path::{Path, PathBuf};
+
+/// The file descriptor of the first file opened with the model. Descriptors are allocated in
+/// sequence and never reused, so each one refers to a single entry of the handles table.
+const FIRST_FD: RawFd = 1 << 20;
+
+/// The state of the modeled file system.
+static mut FILE_SYSTEM: FileSystem = FileSystem::new();
+
+struct FileSystem {
+    /// All the paths that have been accessed so far.
+    entries: Vec<Entry>,
+    /// The files opened so far, indexed by their descriptor minus [`FIRST_FD`].
+    handles: Vec<Handle>,
+    /// The maximum number of files that may exist before the harness starts.
+    max_files: usize,
+    /// The maximum size of the files that exist before the harness starts.
+    max_file_size: usize,
+}
+
+struct Entry {
+    path: PathBuf,
+    exists: bool,
+    contents: Vec<u8>,
+}
+
+struct Handle {
+    entry: usize,
+    position: usize,
+    readable: bool,
+    writable: bool,
+}
+
+impl FileSystem {
+    const fn new() -> Self {
+        FileSystem { entries: Vec::new(), handles: Vec::new(), max_files: 2, max_file_size: 8 }
+    }
+
+    /// Return the index of the entry of `path`, creating one with a nondeterministic state if
+    /// this is the first time the path is accessed.
+    fn entry(&mut self, path: &Path) -> usize {
+        if let Some(index) = self.entries.iter().position(|entry| entry.path == path) {
+            return index;
+        }
+        let existing = self.entries.iter().filter(|entry| entry.exists).count();
+        let exists = existing < self.max_files && any();
+        let contents = if exists { self.any_contents() } else { Vec::new() };
+        self.entries.push(Entry { path: path.to_path_buf(), exists, contents });
+        self.entries.len() - 1
+    }
+
+    fn any_contents(&self) -> Vec<u8> {
+        let max_file_size = self.max_file_size;
+        let len: usize = any_where(|len| *len <= max_file_size);
+        (0..len).map(|_| any()).collect()
+    }
+
+    /// Return the index of the entry of `path` if the file exists.
+    fn existing_entry(&mut self, path: &Path) -> io::Result<usize> {
+        let index = self.entry(path);
+        if self.entries[index].exists { Ok(index) } else { Err(io::ErrorKind::NotFound.into()) }
+    }
+
+    fn open(&mut self, entry: usize, readable: bool, writable: bool) -> File {
+        self.handles.push(Handle { entry, position: 0, readable, writable });
+        let fd = FIRST_FD + (self.handles.len() - 1) as RawFd;
+        // SAFETY: The descriptor is only used to identify the handle in the model.
+        unsafe { File::from_raw_fd(fd) }
+    }
+
+    fn handle(&mut self, file: &File) -> &mut Handle {
+        let index = file.as_raw_fd() - FIRST_FD;
+        crate::assert(
+            index >= 0 && (index as usize) < self.handles.len(),
+            "File was not opened with the file system model",
+        );
+        &mut self.handles[index as usize]
+    }
+
+    /// Read the remaining contents of `file` into `buf`.
+    fn read_to_end(&mut self, file: &File, buf: &mut Vec<u8>) -> io::Result<usize> {
+        let Handle { entry, position, readable, .. } = *self.handle(file);
+        if !readable {
+            return Err(io::ErrorKind::PermissionDenied.into());
+        }
+        let contents = &self.entries[entry].contents;
+        let remaining = &contents[position.min(contents.len())..];
+        buf.extend_from_slice(remaining);
+        let read = remaining.len();
+        self.handle(file).position += read;
+        Ok(read)
+    }
+}
+
+impl Entry {
+    fn len(&self) -> usize {
+        self.contents.len()
+    }
+}
+
+fn file_system() -> &'static mut FileSystem {
+    // SAFETY: Kani harnesses are single threaded, and the model never holds two references to
+    // the file system at the same time.
+    unsafe { &mut *std::ptr::addr_of_mut!(FILE_SYSTEM) }
+}
+
+/// Bound the number of files that may exist before the harness starts.
+///
+/// By default, at most 2 files exist. Files created by the harness are not limited.
+pub fn set_max_files(max: usize) {
+    file_system().max_files = max;
+}
+
+/// Bound the size in bytes of the files that may exist before the harness starts.
+///
+/// By default, these files have at most 8 bytes. Writes are not limited.
+pub fn set_max_file_size(max: usize) {
+    file_system().max_file_size = max;
+}
+
+/// Model of [`File::open`], which opens an existing file in read-only mode.
+pub fn open<P: AsRef<Path>>(path: P) -> io::Result<File> {
+    let fs = file_system();
+    let entry = fs.existing_entry(path.as_ref())?;
+    Ok(fs.open(entry, true, false))
+}
+
+/// Model of [`File::create`], which creates or truncates a file and opens it in write-only mode.
+pub fn create<P: AsRef<Path>>(path: P) -> io::Result<File> {
+    let fs = file_system();
+    let entry = fs.entry(path.as_ref());
+    fs.entries[entry].exists = true;
+    fs.entries[entry].contents.clear();
+    Ok(fs.open(entry, false, true))
+}
+
+/// Model of [`File::metadata`].
+pub fn file_metadata(file: &File) -> io::Result<Metadata> {
+    let fs = file_system();
+    let entry = fs.handle(file).entry;
+    Ok(new_metadata(fs.entries[entry].len() as u64))
+}
+
+/// Model of [`Read::read`](io::Read::read) for [`File`].
+pub fn file_read(file: &mut File, buf: &mut [u8]) -> io::Result<usize> {
+    let fs = file_system();
+    let Handle { entry, position, readable, .. } = *fs.handle(file);
+    if !readable {
+        return Err(io::ErrorKind::PermissionDenied.into());
+    }
+    let contents = &fs.entries[entry].contents;
+    let start = position.min(contents.len());
+    let read = buf.len().min(contents.len() - start);
+    buf[..read].copy_from_slice(&contents[start..start + read]);
+    fs.handle(file).position += read;
+    Ok(read)
+}
+
+/// Model of [`Read::read_to_end`](io::Read::read_to_end) for [`File`].
+pub fn file_read_to_end(file: &mut File, buf: &mut Vec<u8>) -> io::Result<usize> {
+    file_system().read_to_end(file, buf)
+}
+
+/// Model of [`Read::read_to_string`](io::Read::read_to_string) for [`File`].
+pub fn file_read_to_string(file: &mut File, buf: &mut String) -> io::Result<usize> {
+    let mut bytes = Vec::new();
+    let read = file_system().read_to_end(file, &mut bytes)?;
+    buf.push_str(&into_string(bytes)?);
+    Ok(read)
+}
+
+/// Model of [`Write::write`](io::Write::write) for [`File`].
+pub fn file_write(file: &mut File, buf: &[u8]) -> io::Result<usize> {
+    let fs = file_system();
+    let Handle { entry, position, writable, .. } = *fs.handle(file);
+    if !writable {
+        return Err(io::ErrorKind::PermissionDenied.into());
+    }
+    let contents = &mut fs.entries[entry].contents;
+    if contents.len() < position + buf.len() {
+        contents.resize(position + buf.len(), 0);
+    }
+    contents[position..position + buf.len()].copy_from_slice(buf);
+    fs.handle(file).position += buf.len();
+    Ok(buf.len())
+}
+
+/// Model of [`Write::flush`](io::Write::flush) for [`File`]. The model doesn't buffer writes.
+pub fn file_flush(_file: &mut File) -> io::Result<()> {
+    Ok(())
+}
+
+/// Model of [`std::fs::read`].
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    let fs = file_system();
+    let entry = fs.existing_entry(path.as_ref())?;
+    Ok(fs.entries[entry].contents.clone())
+}
+
+/// Model of [`std::fs::read_to_string`].
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    into_string(read(path)?)
+}
+
+/// Model of [`std::fs::write`], which creates or truncates the file before writing `contents`.
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    let fs = file_system();
+    let entry = fs.entry(path.as_ref());
+    fs.entries[entry].exists = true;
+    fs.entries[entry].contents = contents.as_ref().to_vec();
+    Ok(())
+}
+
+/// Model of [`std::fs::metadata`].
+pub fn metadata<P: AsRef<Path>>(path: P) -> io::Result<Metadata> {
+    let fs = file_system();
+    let entry = fs.existing_entry(path.as_ref())?;
+    Ok(new_metadata(fs.entries[entry].len() as u64))
+}
+
+/// Model of [`Metadata::len`] for metadata created by the model.
+pub fn metadata_len(metadata: &Metadata) -> u64 {
+    // SAFETY: See `new_metadata`.
+    unsafe { (metadata as *const Metadata as *const u64).read_unaligned() }
+}
+
+/// Model of [`Metadata::is_file`]. Only regular files are modeled.
+pub fn metadata_is_file(_metadata: &Metadata) -> bool {
+    true
+}
+
+/// Model of [`Metadata::is_dir`]. Only regular files are modeled.
+pub fn metadata_is_dir(_metadata: &Metadata) -> bool {
+    false
+}
+
+/// Create the metadata of a file with `len` bytes.
+///
+/// [`Metadata`] cannot be built outside of the standard library, so we store the length in the
+/// first bytes of a zeroed value. All the methods of [`Metadata`] that are used by the model are
+/// stubbed, so this representation is never interpreted by the standard library.
+fn new_metadata(len: u64) -> Metadata {
+    const { assert!(size_of::<Metadata>() >= size_of::<u64>()) };
+    // SAFETY: The metadata of every platform supported by Kani is plain data.
+    let mut metadata: Metadata = unsafe { std::mem::zeroed() };
+    unsafe { (&mut metadata as *mut Metadata as *mut u64).write_unaligned(len) };
+    metadata
+}
+
+/// Mimic the error returned by the standard library when a file is not valid UTF-8.
+fn into_string(bytes: Vec<u8>) -> io::Result<String> {
+    String::from_utf8(bytes).map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+    })
+}
@@ -0,0 +1,114 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for `#[kani::proof_invariant_preserved(Type)]`, which checks that the public methods
+//! of a type preserve its [`Invariant`].
+//!
+//! The attribute makes the harness generic over a type that implements [`PreservedCheck`], and
+//! the compiler instantiates the harness once for each public method of the type, with one of
+//! the check types of this module depending on how the method takes `self`.
+
+use crate::{Arbitrary, Invariant};
+use core::marker::PhantomData;
+
+/// The check performed by an instance of a `proof_invariant_preserved` harness.
+#[doc(hidden)]
+pub trait PreservedCheck {
+    fn check();
+}
+
+/// Call a function item with a receiver and a tuple with the other arguments.
+#[doc(hidden)]
+pub trait Method<Receiver, Args> {
+    type Output;
+
+    fn call(self, receiver: Receiver, args: Args) -> Self::Output;
+}
+
+macro_rules! impl_method {
+    ($($arg:ident),*) => {
+        impl<Func, Receiver, Output, $($arg),*> Method<Receiver, ($($arg,)*)> for Func
+        where
+            Func: FnOnce(Receiver, $($arg),*) -> Output,
+        {
+            type Output = Output;
+
+            #[allow(non_snake_case)]
+            fn call(self, receiver: Receiver, ($($arg,)*): ($($arg,)*)) -> Output {
+                self(receiver, $($arg),*)
+            }
+        }
+    };
+}
+
+impl_method!();
+impl_method!(A);
+impl_method!(A, B);
+impl_method!(A, B, C);
+impl_method!(A, B, C, D);
+impl_method!(A, B, C, D, E);
+impl_method!(A, B, C, D, E, F);
+
+/// Create the value of a function item type.
+fn function_item<M>() -> M {
+    const { assert!(core::mem::size_of::<M>() == 0, "expected a function item") };
+    // SAFETY: Function item types are zero-sized, and they have a single value.
+    unsafe { core::mem::zeroed() }
+}
+
+/// Check a method that takes `&self`.
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniPreservedByRef"]
+pub struct ByRef<T, Args, M>(PhantomData<(T, Args, M)>);
+
+impl<T, Args, M> PreservedCheck for ByRef<T, Args, M>
+where
+    T: Invariant + Arbitrary,
+    Args: Arbitrary,
+    M: for<'a> Method<&'a T, Args>,
+{
+    fn check() {
+        let value: T = crate::any();
+        crate::assume(value.is_safe());
+        function_item::<M>().call(&value, crate::any());
+        crate::assert(value.is_safe(), "the invariant must hold after the method returns");
+    }
+}
+
+/// Check a method that takes `&mut self`.
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniPreservedByMut"]
+pub struct ByMut<T, Args, M>(PhantomData<(T, Args, M)>);
+
+impl<T, Args, M> PreservedCheck for ByMut<T, Args, M>
+where
+    T: Invariant + Arbitrary,
+    Args: Arbitrary,
+    M: for<'a> Method<&'a mut T, Args>,
+{
+    fn check() {
+        let mut value: T = crate::any();
+        crate::assume(value.is_safe());
+        function_item::<M>().call(&mut value, crate::any());
+        crate::assert(value.is_safe(), "the invariant must hold after the method returns");
+    }
+}
+
+/// Check a method that takes `self` by value and returns `Self`.
+#[doc(hidden)]
+#[rustc_diagnostic_item = "KaniPreservedByValue"]
+pub struct ByValue<T, Args, M>(PhantomData<(T, Args, M)>);
+
+impl<T, Args, M> PreservedCheck for ByValue<T, Args, M>
+where
+    T: Invariant + Arbitrary,
+    Args: Arbitrary,
+    M: Method<T, Args, Output = T>,
+{
+    fn check() {
+        let value: T = crate::any();
+        crate::assume(value.is_safe());
+        let result = function_item::<M>().call(value, crate::any());
+        crate::assert(result.is_safe(), "the invariant must hold for the returned value");
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Symbolic iterators that can be used to verify iterator adapter chains.
+use crate::{Arbitrary, any, any_where};
+use core::iter::FusedIterator;
+
+/// Generates an iterator that yields up to `max` arbitrary values before it stops.
+///
+/// The number of elements produced is chosen nondeterministically when the iterator is created,
+/// so a harness that consumes it covers every length between `0` and `max` (inclusive).
+/// Once the iterator returns `None`, it will keep returning `None`.
+///
+/// Loops that consume this iterator need an unwind bound of at least `max + 1`.
+pub fn any_iter<T>(max: usize) -> AnyIter<T>
+where
+    T: Arbitrary,
+{
+    let len: usize = any_where(|sz| *sz <= max);
+    AnyIter { max, len, yielded: 0, _item: core::marker::PhantomData }
+}
+
+/// Iterator returned by [`any_iter`].
+#[derive(Clone, Debug)]
+pub struct AnyIter<T> {
+    /// The upper bound given by the user.
+    max: usize,
+    /// The nondeterministically chosen number of elements.
+    len: usize,
+    /// The number of elements produced so far.
+    yielded: usize,
+    _item: core::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: Arbitrary> Iterator for AnyIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.yielded < self.len {
+            self.yielded += 1;
+            Some(any())
+        } else {
+            None
+        }
+    }
+
+    /// The actual length is symbolic, so the hint only relies on the user provided bound.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.max - self.yielded))
+    }
+}
+
+impl<T: Arbitrary> FusedIterator for AnyIter<T> {}
@@ -19,23 +19,37 @@
 #![feature(ptr_metadata)]
 #![feature(f16)]
 #![feature(f128)]
+// Required for checking implementations of `Allocator`.
+#![feature(allocator_api)]
 
 // Allow us to use `kani::` to access crate features.
 extern crate self as kani;
 
 pub mod arbitrary;
+pub mod arena;
 #[cfg(feature = "concrete_playback")]
 mod concrete_playback;
+pub mod fs;
 pub mod futures;
 pub mod invariant;
+#[doc(hidden)]
+pub mod invariant_preserved;
+pub mod iter;
+pub mod roundtrip;
 pub mod shadow;
+pub mod state_machine;
+pub mod time;
+pub mod utf8;
 pub mod vec;
 
 mod models;
 
 #[cfg(feature = "concrete_playback")]
-pub use concrete_playback::concrete_playback_run;
+pub use concrete_playback::{concrete_playback_run, oracle_output};
 pub use invariant::Invariant;
+pub use iter::any_iter;
+pub use state_machine::StateMachine;
+pub use utf8::any_utf8;
 
 #[cfg(not(feature = "concrete_playback"))]
 /// NOP `concrete_playback` for type checking during verification mode.
@@ -43,6 +57,24 @@ pub fn concrete_playback_run<F: Fn()>(_: Vec<Vec<u8>>, _: F) {
     unreachable!("Concrete playback does not work during verification")
 }
 
+/// Record the output of a harness, so it can be compared with the expected outputs given to
+/// `--oracle`.
+///
+/// The output is compared using its `Debug` representation:
+///
+/// ```no_run
+/// # fn add(a: u32, b: u32) -> u32 { a.wrapping_add(b) }
+/// #[kani::proof]
+/// fn check_add() {
+///     let (a, b): (u32, u32) = (kani::any(), kani::any());
+///     kani::oracle_output(&add(a, b));
+/// }
+/// ```
+///
+/// This has no effect during verification.
+#[cfg(not(feature = "concrete_playback"))]
+pub fn oracle_output<T: core::fmt::Debug>(_output: &T) {}
+
 pub use futures::{RoundRobin, block_on, block_on_with_spawn, spawn, yield_now};
 
 // Kani proc macros must be in a separate crate
@@ -71,6 +103,32 @@ macro_rules! cover {
     };
 }
 
+/// `must_not_reach!()` marks a program point that verification must prove is never reached.
+///
+/// Unlike `core::unreachable!()`, reaching this point is not a panic. It is reported as a failed
+/// `unreachable` check, so it also fails harnesses annotated with `#[kani::should_panic]`:
+///
+/// ```no_run
+/// # #[kani::proof]
+/// # fn check_parity() {
+/// let x: u8 = kani::any();
+/// let parity = match x % 2 {
+///     0 => "even",
+///     1 => "odd",
+///     _ => kani::must_not_reach!("the remainder of a division by 2 is 0 or 1"),
+/// };
+/// # }
+/// ```
+#[macro_export]
+macro_rules! must_not_reach {
+    ($(,)?) => {
+        kani::unreachable("reached a point marked unreachable")
+    };
+    ($msg:literal $(,)?) => {
+        kani::unreachable(concat!("reached a point marked unreachable: ", $msg))
+    };
+}
+
 /// `implies!(premise => conclusion)` means that if the `premise` is true, so
 /// must be the `conclusion`.
 ///
@@ -83,6 +141,105 @@ macro_rules! implies {
     };
 }
 
+/// `snapshot!(expr)` stores a copy of the current value of `expr` so it can be compared against
+/// the value of the same expression at a later point of the harness.
+///
+/// This is the harness counterpart of the `old()` expressions used in function contracts:
+///
+/// ```no_run
+/// # #[kani::proof]
+/// # fn check_push() {
+/// let mut data: Vec<u8> = vec![kani::any()];
+/// let before = kani::snapshot!(data);
+/// data.push(kani::any());
+/// kani::assert(data.len() == before.len() + 1, "push adds one element");
+/// kani::assert(data[..before.len()] == before[..], "push keeps existing elements");
+/// # }
+/// ```
+///
+/// The value is copied with [`Clone::clone`], so the snapshot is independent of any mutation
+/// that happens after it was taken. The snapshot is bound to a local variable, and thus it is
+/// also included in counterexample traces. Snapshotting a type that does not implement [`Clone`]
+/// is a compilation error.
+#[macro_export]
+macro_rules! snapshot {
+    ($value:expr $(,)?) => {
+        kani::take_snapshot(&$value)
+    };
+}
+
+/// Implementation of the [`snapshot!`] macro.
+///
+/// This is a separate function so the error message for types that cannot be copied points to
+/// the missing `Clone` implementation.
+#[doc(hidden)]
+pub fn take_snapshot<T: Clone>(value: &T) -> T {
+    value.clone()
+}
+
+/// `assume_unchanged!(place)` assumes that `place` still holds the value it had before the
+/// preceding call to a function that is stubbed by its contract.
+///
+/// A verified stub havocs every location of its `modifies` clauses. When a contract is more
+/// conservative than needed, this macro restores what the caller knows about the locations that
+/// the function doesn't actually modify:
+///
+/// ```no_run
+/// # struct Pair { first: u32, second: u32 }
+/// #[kani::modifies(pair)]
+/// fn bump_first(pair: &mut Pair) {
+///     pair.first = pair.first.wrapping_add(1);
+/// }
+///
+/// #[kani::proof]
+/// #[kani::stub_verified(bump_first)]
+/// fn check_second() {
+///     let mut pair = Pair { first: kani::any(), second: 10 };
+///     bump_first(&mut pair);
+///     kani::assume_unchanged!(pair.second);
+///     kani::assert(pair.second == 10, "second is unchanged");
+/// }
+/// ```
+///
+/// The macro must directly follow the stubbed call, i.e., there can be no branching or other calls
+/// in between, and `place` must be valid before the call. Otherwise, the macro has no effect and
+/// the compiler emits a warning. The value is compared byte by byte.
+///
+/// Assuming that a location is unchanged when it is itself a target of a `modifies` clause
+/// contradicts the contract. In that case, the cover property added by the macro is reported as
+/// `SATISFIED`.
+#[macro_export]
+macro_rules! assume_unchanged {
+    ($place:expr $(,)?) => {
+        kani::internal::assume_unchanged(&$place, &$place)
+    };
+}
+
+/// `transition!(from, to)` marks a transition of a state machine from the state `from` to the
+/// state `to`, and checks that the transition is declared by the
+/// [`state_machine`](crate::state_machine) attribute of their type:
+///
+/// ```no_run
+/// # #[kani::state_machine(transitions = [Closed => Open, Open => Closed])]
+/// # #[derive(Clone, Copy)]
+/// # enum Door { Closed, Open }
+/// # #[kani::proof]
+/// # fn check_door() {
+/// let door = Door::Closed;
+/// let next = if kani::any() { Door::Open } else { Door::Closed };
+/// // Fails, since `Closed => Closed` is not declared.
+/// kani::transition!(door, next);
+/// # }
+/// ```
+///
+/// Both states are taken by reference, so the macro doesn't move them.
+#[macro_export]
+macro_rules! transition {
+    ($from:expr, $to:expr $(,)?) => {
+        kani::state_machine::check_transition(&$from, &$to)
+    };
+}
+
 pub(crate) use kani_macros::unstable_feature as unstable;
 
 pub mod contracts;
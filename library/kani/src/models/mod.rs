@@ -124,6 +124,186 @@ pub(super) unsafe fn simd_bitmask<T, U, E, const LANES: usize>(input: T) -> U
     struct Simd<T, const LANES: usize>([T; LANES]);
 }
 
+/// Models of the LLVM intrinsics that `core::arch` uses to implement some x86 vendor intrinsics.
+///
+/// Most of the SSE and SSE2 arithmetic intrinsics are implemented with the generic SIMD
+/// intrinsics, which Kani supports. The ones below are implemented with calls to foreign LLVM
+/// intrinsics instead, which Kani replaces by these models according to their link name.
+///
+/// Each model takes the type of its first argument and its return type as generic parameters, so
+/// it can be used in place of the foreign function, which takes types that are private to
+/// `core::arch`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[allow(dead_code, clippy::neg_cmp_op_on_partial_ord)]
+mod x86 {
+    use std::mem::size_of;
+
+    /// Read the lanes of a vector.
+    unsafe fn lanes<T, L, const N: usize>(vector: &T) -> [L; N] {
+        assert_eq!(
+            size_of::<T>(),
+            size_of::<[L; N]>(),
+            "Expected size of vector and lanes to match"
+        );
+        (vector as *const T as *const [L; N]).read_unaligned()
+    }
+
+    /// Create a vector from its lanes.
+    unsafe fn from_lanes<U, L, const N: usize>(lanes: [L; N]) -> U {
+        assert_eq!(
+            size_of::<U>(),
+            size_of::<[L; N]>(),
+            "Expected size of vector and lanes to match"
+        );
+        (&lanes as *const [L; N] as *const U).read_unaligned()
+    }
+
+    /// The comparison predicates of `CMPPS` and `CMPPD`, which are given by their immediate
+    /// operand. The result is always false for the ordered predicates if any operand is NaN, and
+    /// it is always true for the unordered ones.
+    macro_rules! compare {
+        ($a:expr, $b:expr, $imm8:expr) => {{
+            let (a, b) = ($a, $b);
+            match $imm8 {
+                0 => a == b,
+                1 => a < b,
+                2 => a <= b,
+                3 => a.is_nan() || b.is_nan(),
+                4 => a != b,
+                5 => !(a < b),
+                6 => !(a <= b),
+                7 => !a.is_nan() && !b.is_nan(),
+                imm8 => panic!("Invalid comparison predicate `{imm8}`"),
+            }
+        }};
+    }
+
+    /// The result of a comparison, which has all bits set if it holds.
+    fn mask_f32(value: bool) -> f32 {
+        f32::from_bits(if value { u32::MAX } else { 0 })
+    }
+
+    fn mask_f64(value: bool) -> f64 {
+        f64::from_bits(if value { u64::MAX } else { 0 })
+    }
+
+    /// Equivalent to `MINPS`, which returns the second operand if they are both zero or if any
+    /// of them is NaN.
+    fn min<F: PartialOrd>(a: F, b: F) -> F {
+        if a < b { a } else { b }
+    }
+
+    /// Equivalent to `MAXPS`, which returns the second operand if they are both zero or if any
+    /// of them is NaN.
+    fn max<F: PartialOrd>(a: F, b: F) -> F {
+        if a > b { a } else { b }
+    }
+
+    /// Model of `llvm.x86.sse.cmp.ps`.
+    #[rustc_diagnostic_item = "KaniModelX86SseCmpPs"]
+    pub(super) unsafe fn sse_cmp_ps<T, U>(a: T, b: T, imm8: i8) -> U {
+        let (a, b) = (lanes::<_, f32, 4>(&a), lanes::<_, f32, 4>(&b));
+        from_lanes(std::array::from_fn::<_, 4, _>(|i| mask_f32(compare!(a[i], b[i], imm8))))
+    }
+
+    /// Model of `llvm.x86.sse.cmp.ss`, which only compares the lowest lane, and copies the upper
+    /// lanes of the first operand.
+    #[rustc_diagnostic_item = "KaniModelX86SseCmpSs"]
+    pub(super) unsafe fn sse_cmp_ss<T, U>(a: T, b: T, imm8: i8) -> U {
+        let (mut a, b) = (lanes::<_, f32, 4>(&a), lanes::<_, f32, 4>(&b));
+        a[0] = mask_f32(compare!(a[0], b[0], imm8));
+        from_lanes(a)
+    }
+
+    /// Model of `llvm.x86.sse.min.ps`.
+    #[rustc_diagnostic_item = "KaniModelX86SseMinPs"]
+    pub(super) unsafe fn sse_min_ps<T, U>(a: T, b: T) -> U {
+        let (a, b) = (lanes::<_, f32, 4>(&a), lanes::<_, f32, 4>(&b));
+        from_lanes(std::array::from_fn::<_, 4, _>(|i| min(a[i], b[i])))
+    }
+
+    /// Model of `llvm.x86.sse.min.ss`.
+    #[rustc_diagnostic_item = "KaniModelX86SseMinSs"]
+    pub(super) unsafe fn sse_min_ss<T, U>(a: T, b: T) -> U {
+        let (mut a, b) = (lanes::<_, f32, 4>(&a), lanes::<_, f32, 4>(&b));
+        a[0] = min(a[0], b[0]);
+        from_lanes(a)
+    }
+
+    /// Model of `llvm.x86.sse.max.ps`.
+    #[rustc_diagnostic_item = "KaniModelX86SseMaxPs"]
+    pub(super) unsafe fn sse_max_ps<T, U>(a: T, b: T) -> U {
+        let (a, b) = (lanes::<_, f32, 4>(&a), lanes::<_, f32, 4>(&b));
+        from_lanes(std::array::from_fn::<_, 4, _>(|i| max(a[i], b[i])))
+    }
+
+    /// Model of `llvm.x86.sse.max.ss`.
+    #[rustc_diagnostic_item = "KaniModelX86SseMaxSs"]
+    pub(super) unsafe fn sse_max_ss<T, U>(a: T, b: T) -> U {
+        let (mut a, b) = (lanes::<_, f32, 4>(&a), lanes::<_, f32, 4>(&b));
+        a[0] = max(a[0], b[0]);
+        from_lanes(a)
+    }
+
+    /// Model of `llvm.x86.sse2.cmp.pd`.
+    #[rustc_diagnostic_item = "KaniModelX86Sse2CmpPd"]
+    pub(super) unsafe fn sse2_cmp_pd<T, U>(a: T, b: T, imm8: i8) -> U {
+        let (a, b) = (lanes::<_, f64, 2>(&a), lanes::<_, f64, 2>(&b));
+        from_lanes(std::array::from_fn::<_, 2, _>(|i| mask_f64(compare!(a[i], b[i], imm8))))
+    }
+
+    /// Model of `llvm.x86.sse2.cmp.sd`.
+    #[rustc_diagnostic_item = "KaniModelX86Sse2CmpSd"]
+    pub(super) unsafe fn sse2_cmp_sd<T, U>(a: T, b: T, imm8: i8) -> U {
+        let (mut a, b) = (lanes::<_, f64, 2>(&a), lanes::<_, f64, 2>(&b));
+        a[0] = mask_f64(compare!(a[0], b[0], imm8));
+        from_lanes(a)
+    }
+
+    /// Model of `llvm.x86.sse2.min.pd`.
+    #[rustc_diagnostic_item = "KaniModelX86Sse2MinPd"]
+    pub(super) unsafe fn sse2_min_pd<T, U>(a: T, b: T) -> U {
+        let (a, b) = (lanes::<_, f64, 2>(&a), lanes::<_, f64, 2>(&b));
+        from_lanes(std::array::from_fn::<_, 2, _>(|i| min(a[i], b[i])))
+    }
+
+    /// Model of `llvm.x86.sse2.min.sd`.
+    #[rustc_diagnostic_item = "KaniModelX86Sse2MinSd"]
+    pub(super) unsafe fn sse2_min_sd<T, U>(a: T, b: T) -> U {
+        let (mut a, b) = (lanes::<_, f64, 2>(&a), lanes::<_, f64, 2>(&b));
+        a[0] = min(a[0], b[0]);
+        from_lanes(a)
+    }
+
+    /// Model of `llvm.x86.sse2.max.pd`.
+    #[rustc_diagnostic_item = "KaniModelX86Sse2MaxPd"]
+    pub(super) unsafe fn sse2_max_pd<T, U>(a: T, b: T) -> U {
+        let (a, b) = (lanes::<_, f64, 2>(&a), lanes::<_, f64, 2>(&b));
+        from_lanes(std::array::from_fn::<_, 2, _>(|i| max(a[i], b[i])))
+    }
+
+    /// Model of `llvm.x86.sse2.max.sd`.
+    #[rustc_diagnostic_item = "KaniModelX86Sse2MaxSd"]
+    pub(super) unsafe fn sse2_max_sd<T, U>(a: T, b: T) -> U {
+        let (mut a, b) = (lanes::<_, f64, 2>(&a), lanes::<_, f64, 2>(&b));
+        a[0] = max(a[0], b[0]);
+        from_lanes(a)
+    }
+
+    /// Model of `llvm.x86.sse2.pmadd.wd`, which multiplies the 16-bit lanes and adds the adjacent
+    /// products into 32-bit lanes. The only case that overflows is when all four values are
+    /// `i16::MIN`, which wraps around to `i32::MIN`.
+    #[rustc_diagnostic_item = "KaniModelX86Sse2PmaddWd"]
+    pub(super) unsafe fn sse2_pmadd_wd<T, U>(a: T, b: T) -> U {
+        let (a, b) = (lanes::<_, i16, 8>(&a), lanes::<_, i16, 8>(&b));
+        from_lanes(std::array::from_fn::<_, 4, _>(|i| {
+            let low = a[2 * i] as i32 * b[2 * i] as i32;
+            let high = a[2 * i + 1] as i32 * b[2 * i + 1] as i32;
+            low.wrapping_add(high)
+        }))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::intrinsics as kani_intrinsic;
@@ -224,4 +404,121 @@ fn check_mask_harness() {
             unsafe { u64::from(kani_intrinsic::simd_bitmask::<_, u8, u32, 4>(mask.clone())) };
         assert_eq!(kani_mask, bitmask);
     }
+
+    /// Check that the x86 models are equivalent to the vendor intrinsics, including the handling
+    /// of NaN and signed zeros, and that they keep the upper lanes for the scalar variants.
+    #[cfg(target_arch = "x86_64")]
+    mod x86 {
+        use super::super::x86 as kani_x86;
+        use std::arch::x86_64::*;
+
+        const F32_VALUES: [f32; 8] = [0.0, -0.0, 1.5, -2.0, f32::NAN, f32::INFINITY, 1.5, -0.0];
+        const F64_VALUES: [f64; 4] = [-0.0, f64::NAN, 3.25, 0.0];
+
+        fn bits_ps(vector: __m128) -> [u32; 4] {
+            unsafe { std::mem::transmute(vector) }
+        }
+
+        fn bits_pd(vector: __m128d) -> [u64; 2] {
+            unsafe { std::mem::transmute(vector) }
+        }
+
+        /// All pairs of vectors built from the test values.
+        fn ps_pairs() -> Vec<(__m128, __m128)> {
+            let vectors: Vec<__m128> = F32_VALUES
+                .windows(4)
+                .map(|lanes| unsafe { _mm_setr_ps(lanes[0], lanes[1], lanes[2], lanes[3]) })
+                .collect();
+            vectors.iter().flat_map(|a| vectors.iter().map(|b| (*a, *b))).collect()
+        }
+
+        fn pd_pairs() -> Vec<(__m128d, __m128d)> {
+            let vectors: Vec<__m128d> = F64_VALUES
+                .windows(2)
+                .map(|lanes| unsafe { _mm_setr_pd(lanes[0], lanes[1]) })
+                .collect();
+            vectors.iter().flat_map(|a| vectors.iter().map(|b| (*a, *b))).collect()
+        }
+
+        #[test]
+        fn check_cmp_ps() {
+            for (a, b) in ps_pairs() {
+                unsafe {
+                    let cmp = |imm8| bits_ps(kani_x86::sse_cmp_ps::<_, __m128>(a, b, imm8));
+                    let cmp_ss = |imm8| bits_ps(kani_x86::sse_cmp_ss::<_, __m128>(a, b, imm8));
+                    assert_eq!(cmp(0), bits_ps(_mm_cmpeq_ps(a, b)));
+                    assert_eq!(cmp(1), bits_ps(_mm_cmplt_ps(a, b)));
+                    assert_eq!(cmp(2), bits_ps(_mm_cmple_ps(a, b)));
+                    assert_eq!(cmp(3), bits_ps(_mm_cmpunord_ps(a, b)));
+                    assert_eq!(cmp(4), bits_ps(_mm_cmpneq_ps(a, b)));
+                    assert_eq!(cmp(5), bits_ps(_mm_cmpnlt_ps(a, b)));
+                    assert_eq!(cmp(6), bits_ps(_mm_cmpnle_ps(a, b)));
+                    assert_eq!(cmp(7), bits_ps(_mm_cmpord_ps(a, b)));
+                    assert_eq!(cmp_ss(1), bits_ps(_mm_cmplt_ss(a, b)));
+                    assert_eq!(cmp_ss(4), bits_ps(_mm_cmpneq_ss(a, b)));
+                }
+            }
+        }
+
+        #[test]
+        fn check_min_max_ps() {
+            for (a, b) in ps_pairs() {
+                unsafe {
+                    let min = kani_x86::sse_min_ps::<_, __m128>(a, b);
+                    let max = kani_x86::sse_max_ps::<_, __m128>(a, b);
+                    assert_eq!(bits_ps(min), bits_ps(_mm_min_ps(a, b)));
+                    assert_eq!(bits_ps(max), bits_ps(_mm_max_ps(a, b)));
+                    let min = kani_x86::sse_min_ss::<_, __m128>(a, b);
+                    let max = kani_x86::sse_max_ss::<_, __m128>(a, b);
+                    assert_eq!(bits_ps(min), bits_ps(_mm_min_ss(a, b)));
+                    assert_eq!(bits_ps(max), bits_ps(_mm_max_ss(a, b)));
+                }
+            }
+        }
+
+        #[test]
+        fn check_pd() {
+            for (a, b) in pd_pairs() {
+                unsafe {
+                    for imm8 in 0..8 {
+                        let cmp = kani_x86::sse2_cmp_pd::<_, __m128d>(a, b, imm8);
+                        let cmp_sd = kani_x86::sse2_cmp_sd::<_, __m128d>(a, b, imm8);
+                        let (expected, expected_sd) = match imm8 {
+                            0 => (_mm_cmpeq_pd(a, b), _mm_cmpeq_sd(a, b)),
+                            1 => (_mm_cmplt_pd(a, b), _mm_cmplt_sd(a, b)),
+                            2 => (_mm_cmple_pd(a, b), _mm_cmple_sd(a, b)),
+                            3 => (_mm_cmpunord_pd(a, b), _mm_cmpunord_sd(a, b)),
+                            4 => (_mm_cmpneq_pd(a, b), _mm_cmpneq_sd(a, b)),
+                            5 => (_mm_cmpnlt_pd(a, b), _mm_cmpnlt_sd(a, b)),
+                            6 => (_mm_cmpnle_pd(a, b), _mm_cmpnle_sd(a, b)),
+                            _ => (_mm_cmpord_pd(a, b), _mm_cmpord_sd(a, b)),
+                        };
+                        assert_eq!(bits_pd(cmp), bits_pd(expected));
+                        assert_eq!(bits_pd(cmp_sd), bits_pd(expected_sd));
+                    }
+                    let min = kani_x86::sse2_min_pd::<_, __m128d>(a, b);
+                    let max = kani_x86::sse2_max_pd::<_, __m128d>(a, b);
+                    assert_eq!(bits_pd(min), bits_pd(_mm_min_pd(a, b)));
+                    assert_eq!(bits_pd(max), bits_pd(_mm_max_pd(a, b)));
+                    let min = kani_x86::sse2_min_sd::<_, __m128d>(a, b);
+                    let max = kani_x86::sse2_max_sd::<_, __m128d>(a, b);
+                    assert_eq!(bits_pd(min), bits_pd(_mm_min_sd(a, b)));
+                    assert_eq!(bits_pd(max), bits_pd(_mm_max_sd(a, b)));
+                }
+            }
+        }
+
+        #[test]
+        fn check_madd_epi16() {
+            unsafe {
+                let a = _mm_setr_epi16(i16::MIN, i16::MIN, 3, -4, i16::MAX, i16::MIN, 0, 7);
+                let b = _mm_setr_epi16(i16::MIN, i16::MIN, -5, 6, i16::MAX, i16::MAX, 1, -1);
+                let model: [i32; 4] =
+                    std::mem::transmute(kani_x86::sse2_pmadd_wd::<_, __m128i>(a, b));
+                let expected: [i32; 4] = std::mem::transmute(_mm_madd_epi16(a, b));
+                assert_eq!(model, expected);
+                assert_eq!(model[0], i32::MIN);
+            }
+        }
+    }
 }
@@ -0,0 +1,583 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for `#[kani::proof_roundtrip]`, which checks that serializing and deserializing any
+//! value of a type produces an equal value.
+//!
+//! The Kani library cannot depend on the `serde` crate used by the code under verification, since
+//! they would be different crates, so the default in-memory format is defined in the harness by
+//! the [`in_memory_format!`](crate::in_memory_format) macro.
+
+use crate::Arbitrary;
+
+/// Check that `deserialize` recovers a value that is equal to any value of type `T` encoded by
+/// `serialize`, according to `equal`.
+#[doc(hidden)]
+pub fn check_roundtrip<T: Arbitrary, Encoded, SerErr, DeErr>(
+    serialize: impl FnOnce(&T) -> Result<Encoded, SerErr>,
+    deserialize: impl FnOnce(&Encoded) -> Result<T, DeErr>,
+    equal: impl FnOnce(&T, &T) -> bool,
+) {
+    let original: T = crate::any();
+    let encoded = serialize(&original);
+    crate::assert(encoded.is_ok(), "serialization must succeed");
+    let Ok(encoded) = encoded else { return };
+    let decoded = deserialize(&encoded);
+    crate::assert(decoded.is_ok(), "deserialization of a serialized value must succeed");
+    let Ok(decoded) = decoded else { return };
+    crate::assert(equal(&original, &decoded), "deserialized value must be equal to the original");
+}
+
+/// Define a module `in_memory_format` with a minimal `serde` format, which stores the serialized
+/// value as a vector of tokens.
+///
+/// This is the default format of `#[kani::proof_roundtrip]`. It's expanded in the harness, so it
+/// uses the `serde` crate of the code under verification. Structs and tuples are stored as
+/// sequences of values, and enum variants by their index, similar to `bincode`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! in_memory_format {
+    () => {
+        mod in_memory_format {
+            use ::serde::{de, ser};
+            use ::std::fmt;
+            use ::std::string::{String, ToString};
+            use ::std::vec::Vec;
+
+            /// A value, or the start of a compound value, in the in-memory format.
+            #[derive(Clone, Debug, PartialEq)]
+            pub enum Token {
+                Bool(bool),
+                I64(i64),
+                I128(i128),
+                U64(u64),
+                U128(u128),
+                F32(f32),
+                F64(f64),
+                Char(char),
+                Str(String),
+                Bytes(Vec<u8>),
+                None,
+                /// Followed by the value.
+                Some,
+                Unit,
+                /// Followed by the value.
+                Newtype,
+                /// Followed by the given number of values.
+                Seq(usize),
+                /// Followed by the given number of key and value pairs.
+                Map(usize),
+                /// Followed by the content of the variant with the given index.
+                Variant(u32),
+            }
+
+            #[derive(Debug)]
+            pub struct Error(String);
+
+            impl fmt::Display for Error {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(&self.0)
+                }
+            }
+
+            impl ::std::error::Error for Error {}
+
+            impl ser::Error for Error {
+                fn custom<T: fmt::Display>(msg: T) -> Self {
+                    Error(msg.to_string())
+                }
+            }
+
+            impl de::Error for Error {
+                fn custom<T: fmt::Display>(msg: T) -> Self {
+                    Error(msg.to_string())
+                }
+            }
+
+            pub fn to_tokens<T: ?Sized + ser::Serialize>(value: &T) -> Result<Vec<Token>, Error> {
+                let mut serializer = Serializer { tokens: Vec::new(), compounds: Vec::new() };
+                value.serialize(&mut serializer)?;
+                Ok(serializer.tokens)
+            }
+
+            pub fn from_tokens<T: de::DeserializeOwned>(tokens: &[Token]) -> Result<T, Error> {
+                let mut deserializer = Deserializer { tokens };
+                let value = T::deserialize(&mut deserializer)?;
+                if deserializer.tokens.is_empty() {
+                    Ok(value)
+                } else {
+                    Err(de::Error::custom("trailing tokens"))
+                }
+            }
+
+            struct Serializer {
+                tokens: Vec<Token>,
+                /// The position and the number of elements of the compound values being
+                /// serialized, whose token is updated with the number of elements at the end.
+                compounds: Vec<(usize, usize)>,
+            }
+
+            impl Serializer {
+                fn push(&mut self, token: Token) -> Result<(), Error> {
+                    self.tokens.push(token);
+                    Ok(())
+                }
+
+                fn open(&mut self, token: Token) -> Result<&mut Self, Error> {
+                    self.compounds.push((self.tokens.len(), 0));
+                    self.tokens.push(token);
+                    Ok(self)
+                }
+
+                fn element<T: ?Sized + ser::Serialize>(&mut self, value: &T) -> Result<(), Error> {
+                    self.compounds.last_mut().unwrap().1 += 1;
+                    value.serialize(self)
+                }
+
+                fn close(&mut self) -> Result<(), Error> {
+                    let (index, count) = self.compounds.pop().unwrap();
+                    match &mut self.tokens[index] {
+                        Token::Seq(len) | Token::Map(len) => *len = count,
+                        _ => unreachable!(),
+                    }
+                    Ok(())
+                }
+            }
+
+            impl<'a> ser::Serializer for &'a mut Serializer {
+                type Ok = ();
+                type Error = Error;
+                type SerializeSeq = Self;
+                type SerializeTuple = Self;
+                type SerializeTupleStruct = Self;
+                type SerializeTupleVariant = Self;
+                type SerializeMap = Self;
+                type SerializeStruct = Self;
+                type SerializeStructVariant = Self;
+
+                fn serialize_bool(self, v: bool) -> Result<(), Error> {
+                    self.push(Token::Bool(v))
+                }
+                fn serialize_i8(self, v: i8) -> Result<(), Error> {
+                    self.push(Token::I64(v.into()))
+                }
+                fn serialize_i16(self, v: i16) -> Result<(), Error> {
+                    self.push(Token::I64(v.into()))
+                }
+                fn serialize_i32(self, v: i32) -> Result<(), Error> {
+                    self.push(Token::I64(v.into()))
+                }
+                fn serialize_i64(self, v: i64) -> Result<(), Error> {
+                    self.push(Token::I64(v))
+                }
+                fn serialize_i128(self, v: i128) -> Result<(), Error> {
+                    self.push(Token::I128(v))
+                }
+                fn serialize_u8(self, v: u8) -> Result<(), Error> {
+                    self.push(Token::U64(v.into()))
+                }
+                fn serialize_u16(self, v: u16) -> Result<(), Error> {
+                    self.push(Token::U64(v.into()))
+                }
+                fn serialize_u32(self, v: u32) -> Result<(), Error> {
+                    self.push(Token::U64(v.into()))
+                }
+                fn serialize_u64(self, v: u64) -> Result<(), Error> {
+                    self.push(Token::U64(v))
+                }
+                fn serialize_u128(self, v: u128) -> Result<(), Error> {
+                    self.push(Token::U128(v))
+                }
+                fn serialize_f32(self, v: f32) -> Result<(), Error> {
+                    self.push(Token::F32(v))
+                }
+                fn serialize_f64(self, v: f64) -> Result<(), Error> {
+                    self.push(Token::F64(v))
+                }
+                fn serialize_char(self, v: char) -> Result<(), Error> {
+                    self.push(Token::Char(v))
+                }
+                fn serialize_str(self, v: &str) -> Result<(), Error> {
+                    self.push(Token::Str(v.to_string()))
+                }
+                fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+                    self.push(Token::Bytes(v.to_vec()))
+                }
+                fn serialize_none(self) -> Result<(), Error> {
+                    self.push(Token::None)
+                }
+                fn serialize_some<T: ?Sized + ser::Serialize>(
+                    self,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.push(Token::Some)?;
+                    value.serialize(self)
+                }
+                fn serialize_unit(self) -> Result<(), Error> {
+                    self.push(Token::Unit)
+                }
+                fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+                    self.push(Token::Unit)
+                }
+                fn serialize_unit_variant(
+                    self,
+                    _name: &'static str,
+                    index: u32,
+                    _variant: &'static str,
+                ) -> Result<(), Error> {
+                    self.push(Token::Variant(index))?;
+                    self.push(Token::Unit)
+                }
+                fn serialize_newtype_struct<T: ?Sized + ser::Serialize>(
+                    self,
+                    _name: &'static str,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.push(Token::Newtype)?;
+                    value.serialize(self)
+                }
+                fn serialize_newtype_variant<T: ?Sized + ser::Serialize>(
+                    self,
+                    _name: &'static str,
+                    index: u32,
+                    _variant: &'static str,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.push(Token::Variant(index))?;
+                    value.serialize(self)
+                }
+                fn serialize_seq(self, _len: Option<usize>) -> Result<Self, Error> {
+                    self.open(Token::Seq(0))
+                }
+                fn serialize_tuple(self, _len: usize) -> Result<Self, Error> {
+                    self.open(Token::Seq(0))
+                }
+                fn serialize_tuple_struct(
+                    self,
+                    _name: &'static str,
+                    _len: usize,
+                ) -> Result<Self, Error> {
+                    self.open(Token::Seq(0))
+                }
+                fn serialize_tuple_variant(
+                    self,
+                    _name: &'static str,
+                    index: u32,
+                    _variant: &'static str,
+                    _len: usize,
+                ) -> Result<Self, Error> {
+                    self.push(Token::Variant(index))?;
+                    self.open(Token::Seq(0))
+                }
+                fn serialize_map(self, _len: Option<usize>) -> Result<Self, Error> {
+                    self.open(Token::Map(0))
+                }
+                fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+                    self.open(Token::Seq(0))
+                }
+                fn serialize_struct_variant(
+                    self,
+                    _name: &'static str,
+                    index: u32,
+                    _variant: &'static str,
+                    _len: usize,
+                ) -> Result<Self, Error> {
+                    self.push(Token::Variant(index))?;
+                    self.open(Token::Seq(0))
+                }
+            }
+
+            impl<'a> ser::SerializeSeq for &'a mut Serializer {
+                type Ok = ();
+                type Error = Error;
+                fn serialize_element<T: ?Sized + ser::Serialize>(
+                    &mut self,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.element(value)
+                }
+                fn end(self) -> Result<(), Error> {
+                    self.close()
+                }
+            }
+
+            impl<'a> ser::SerializeTuple for &'a mut Serializer {
+                type Ok = ();
+                type Error = Error;
+                fn serialize_element<T: ?Sized + ser::Serialize>(
+                    &mut self,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.element(value)
+                }
+                fn end(self) -> Result<(), Error> {
+                    self.close()
+                }
+            }
+
+            impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
+                type Ok = ();
+                type Error = Error;
+                fn serialize_field<T: ?Sized + ser::Serialize>(
+                    &mut self,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.element(value)
+                }
+                fn end(self) -> Result<(), Error> {
+                    self.close()
+                }
+            }
+
+            impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
+                type Ok = ();
+                type Error = Error;
+                fn serialize_field<T: ?Sized + ser::Serialize>(
+                    &mut self,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.element(value)
+                }
+                fn end(self) -> Result<(), Error> {
+                    self.close()
+                }
+            }
+
+            impl<'a> ser::SerializeMap for &'a mut Serializer {
+                type Ok = ();
+                type Error = Error;
+                fn serialize_key<T: ?Sized + ser::Serialize>(
+                    &mut self,
+                    key: &T,
+                ) -> Result<(), Error> {
+                    self.element(key)
+                }
+                fn serialize_value<T: ?Sized + ser::Serialize>(
+                    &mut self,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    value.serialize(&mut **self)
+                }
+                fn end(self) -> Result<(), Error> {
+                    self.close()
+                }
+            }
+
+            impl<'a> ser::SerializeStruct for &'a mut Serializer {
+                type Ok = ();
+                type Error = Error;
+                fn serialize_field<T: ?Sized + ser::Serialize>(
+                    &mut self,
+                    _key: &'static str,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.element(value)
+                }
+                fn end(self) -> Result<(), Error> {
+                    self.close()
+                }
+            }
+
+            impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
+                type Ok = ();
+                type Error = Error;
+                fn serialize_field<T: ?Sized + ser::Serialize>(
+                    &mut self,
+                    _key: &'static str,
+                    value: &T,
+                ) -> Result<(), Error> {
+                    self.element(value)
+                }
+                fn end(self) -> Result<(), Error> {
+                    self.close()
+                }
+            }
+
+            struct Deserializer<'de> {
+                tokens: &'de [Token],
+            }
+
+            impl<'de> Deserializer<'de> {
+                fn next(&mut self) -> Result<&'de Token, Error> {
+                    let (first, rest) = self
+                        .tokens
+                        .split_first()
+                        .ok_or_else(|| <Error as de::Error>::custom("unexpected end of input"))?;
+                    self.tokens = rest;
+                    Ok(first)
+                }
+            }
+
+            impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+                type Error = Error;
+
+                fn deserialize_any<V: de::Visitor<'de>>(
+                    self,
+                    visitor: V,
+                ) -> Result<V::Value, Error> {
+                    match self.next()? {
+                        Token::Bool(v) => visitor.visit_bool(*v),
+                        Token::I64(v) => visitor.visit_i64(*v),
+                        Token::I128(v) => visitor.visit_i128(*v),
+                        Token::U64(v) => visitor.visit_u64(*v),
+                        Token::U128(v) => visitor.visit_u128(*v),
+                        Token::F32(v) => visitor.visit_f32(*v),
+                        Token::F64(v) => visitor.visit_f64(*v),
+                        Token::Char(v) => visitor.visit_char(*v),
+                        Token::Str(v) => visitor.visit_borrowed_str(v),
+                        Token::Bytes(v) => visitor.visit_borrowed_bytes(v),
+                        Token::None => visitor.visit_none(),
+                        Token::Some => visitor.visit_some(self),
+                        Token::Unit => visitor.visit_unit(),
+                        Token::Newtype => visitor.visit_newtype_struct(self),
+                        Token::Seq(len) => {
+                            let mut elements = Elements { de: self, remaining: *len };
+                            let value = visitor.visit_seq(&mut elements)?;
+                            elements.end(value)
+                        }
+                        Token::Map(len) => {
+                            let mut entries = Elements { de: self, remaining: *len };
+                            let value = visitor.visit_map(&mut entries)?;
+                            entries.end(value)
+                        }
+                        Token::Variant(_) => Err(de::Error::custom("unexpected enum variant")),
+                    }
+                }
+
+                fn deserialize_enum<V: de::Visitor<'de>>(
+                    self,
+                    _name: &'static str,
+                    _variants: &'static [&'static str],
+                    visitor: V,
+                ) -> Result<V::Value, Error> {
+                    match self.next()? {
+                        Token::Variant(index) => {
+                            visitor.visit_enum(Variant { de: self, index: *index })
+                        }
+                        _ => Err(de::Error::custom("expected an enum variant")),
+                    }
+                }
+
+                ::serde::forward_to_deserialize_any! {
+                    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes
+                    byte_buf option unit unit_struct newtype_struct seq tuple tuple_struct map
+                    struct identifier ignored_any
+                }
+            }
+
+            /// The elements of a sequence, or the entries of a map.
+            struct Elements<'a, 'de> {
+                de: &'a mut Deserializer<'de>,
+                remaining: usize,
+            }
+
+            impl<'a, 'de> Elements<'a, 'de> {
+                fn end<T>(self, value: T) -> Result<T, Error> {
+                    if self.remaining == 0 {
+                        Ok(value)
+                    } else {
+                        Err(de::Error::custom("trailing elements"))
+                    }
+                }
+            }
+
+            impl<'a, 'de> de::SeqAccess<'de> for Elements<'a, 'de> {
+                type Error = Error;
+
+                fn next_element_seed<T: de::DeserializeSeed<'de>>(
+                    &mut self,
+                    seed: T,
+                ) -> Result<Option<T::Value>, Error> {
+                    if self.remaining == 0 {
+                        return Ok(None);
+                    }
+                    self.remaining -= 1;
+                    seed.deserialize(&mut *self.de).map(Some)
+                }
+
+                fn size_hint(&self) -> Option<usize> {
+                    Some(self.remaining)
+                }
+            }
+
+            impl<'a, 'de> de::MapAccess<'de> for Elements<'a, 'de> {
+                type Error = Error;
+
+                fn next_key_seed<K: de::DeserializeSeed<'de>>(
+                    &mut self,
+                    seed: K,
+                ) -> Result<Option<K::Value>, Error> {
+                    if self.remaining == 0 {
+                        return Ok(None);
+                    }
+                    self.remaining -= 1;
+                    seed.deserialize(&mut *self.de).map(Some)
+                }
+
+                fn next_value_seed<V: de::DeserializeSeed<'de>>(
+                    &mut self,
+                    seed: V,
+                ) -> Result<V::Value, Error> {
+                    seed.deserialize(&mut *self.de)
+                }
+
+                fn size_hint(&self) -> Option<usize> {
+                    Some(self.remaining)
+                }
+            }
+
+            struct Variant<'a, 'de> {
+                de: &'a mut Deserializer<'de>,
+                index: u32,
+            }
+
+            impl<'a, 'de> de::EnumAccess<'de> for Variant<'a, 'de> {
+                type Error = Error;
+                type Variant = Self;
+
+                fn variant_seed<V: de::DeserializeSeed<'de>>(
+                    self,
+                    seed: V,
+                ) -> Result<(V::Value, Self), Error> {
+                    let index: de::value::U32Deserializer<Error> =
+                        de::IntoDeserializer::into_deserializer(self.index);
+                    Ok((seed.deserialize(index)?, self))
+                }
+            }
+
+            impl<'a, 'de> de::VariantAccess<'de> for Variant<'a, 'de> {
+                type Error = Error;
+
+                fn unit_variant(self) -> Result<(), Error> {
+                    match self.de.next()? {
+                        Token::Unit => Ok(()),
+                        _ => Err(de::Error::custom("expected a unit variant")),
+                    }
+                }
+
+                fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(
+                    self,
+                    seed: T,
+                ) -> Result<T::Value, Error> {
+                    seed.deserialize(self.de)
+                }
+
+                fn tuple_variant<V: de::Visitor<'de>>(
+                    self,
+                    _len: usize,
+                    visitor: V,
+                ) -> Result<V::Value, Error> {
+                    de::Deserializer::deserialize_any(self.de, visitor)
+                }
+
+                fn struct_variant<V: de::Visitor<'de>>(
+                    self,
+                    _fields: &'static [&'static str],
+                    visitor: V,
+                ) -> Result<V::Value, Error> {
+                    de::Deserializer::deserialize_any(self.de, visitor)
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for `#[kani::state_machine]` and [`transition!`](crate::transition), which check that
+//! the code under verification only performs the transitions declared for its states.
+
+/// The states of a state machine, which declares the transitions between them.
+///
+/// This trait is usually implemented with the `#[kani::state_machine(transitions = [...])]`
+/// attribute.
+pub trait StateMachine {
+    /// Whether the transition from `from` to `to` is declared.
+    fn is_declared_transition(from: &Self, to: &Self) -> bool;
+}
+
+/// Implementation of the [`transition!`](crate::transition) macro.
+#[doc(hidden)]
+pub fn check_transition<S: StateMachine>(from: &S, to: &S) {
+    crate::assert(
+        S::is_declared_transition(from, to),
+        "the transition must be declared by the state machine",
+    );
+}
@@ -0,0 +1,80 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Models of the system clocks that can be used to verify time-dependent logic.
+//!
+//! The functions in this module are meant to be used as stubs (see `-Z stubbing`) for
+//! [`Instant::now`] and [`SystemTime::now`]:
+//!
+//! ```no_run
+//! use std::time::{Duration, Instant};
+//!
+//! #[kani::proof]
+//! #[kani::stub(std::time::Instant::now, kani::time::instant_now)]
+//! fn check_elapsed() {
+//!     kani::time::set_max_step(Duration::from_secs(5));
+//!     let start = Instant::now();
+//!     let elapsed = start.elapsed();
+//!     assert!(elapsed <= Duration::from_secs(5));
+//! }
+//! ```
+//!
+//! Every reading of the modeled clock returns a nondeterministic value. Readings of the
+//! [`Instant`] clock are monotonic within a path, i.e., each call returns a value that is
+//! greater than or equal to the previous one, and advances by at most the bound configured with
+//! [`set_max_step`].
+use crate::{any, any_where};
+use std::time::{Duration, Instant, SystemTime};
+
+/// The last value returned by [`instant_now`], stored as its offset from the UNIX epoch.
+static mut LAST_INSTANT: Option<SystemTime> = None;
+
+/// The maximum amount of time that may pass between two readings of the [`Instant`] clock.
+static mut MAX_STEP: Duration = Duration::MAX;
+
+/// Bound how far the modeled [`Instant`] clock may advance between two consecutive calls to
+/// [`instant_now`].
+///
+/// By default, the step is unbounded.
+pub fn set_max_step(max: Duration) {
+    unsafe { MAX_STEP = max };
+}
+
+/// Model of [`Instant::now`] that returns a nondeterministic but monotonic value.
+///
+/// The first call returns an arbitrary instant. Every following call returns an instant that is
+/// at most [`set_max_step`] ahead of the previous one.
+pub fn instant_now() -> Instant {
+    let now = match unsafe { LAST_INSTANT } {
+        None => symbolic_system_time(),
+        Some(last) => {
+            let max_step = unsafe { MAX_STEP };
+            let step: Duration = any_where(|step| *step <= max_step);
+            let next = last.checked_add(step);
+            crate::assume(next.is_some());
+            next.unwrap()
+        }
+    };
+    unsafe { LAST_INSTANT = Some(now) };
+    // SAFETY: On every target supported by Kani, both `Instant` and `SystemTime` are thin
+    // wrappers around the same `timespec` representation.
+    unsafe { std::mem::transmute::<SystemTime, Instant>(now) }
+}
+
+/// Model of [`SystemTime::now`] that returns a nondeterministic value.
+///
+/// The system clock is not guaranteed to be monotonic, so consecutive calls are unrelated.
+pub fn system_time_now() -> SystemTime {
+    symbolic_system_time()
+}
+
+/// Return an arbitrary system time that can be represented on the target.
+fn symbolic_system_time() -> SystemTime {
+    let offset: Duration = any();
+    let time = if any() {
+        SystemTime::UNIX_EPOCH.checked_add(offset)
+    } else {
+        SystemTime::UNIX_EPOCH.checked_sub(offset)
+    };
+    crate::assume(time.is_some());
+    time.unwrap()
+}
@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Symbolic strings that can be used to verify parsers and other string processing code.
+use crate::{any, any_where, assume};
+
+/// Generates an arbitrary valid UTF-8 string slice that is at most `max_len` bytes long.
+///
+/// The bytes of each character are generated directly from the UTF-8 encoding rules, instead of
+/// generating arbitrary bytes and assuming that they are valid UTF-8, which keeps the harnesses
+/// that use it tractable. Every string whose length is between `0` and `max_len` (inclusive) can
+/// be generated, including strings with multi-byte characters, so indexing or slicing the string
+/// at a byte that isn't a character boundary is reported as a panic of the code under
+/// verification.
+///
+/// Loops that generate the string need an unwind bound of at least `max_len + 1`.
+pub fn any_utf8(max_len: usize) -> &'static str {
+    let len: usize = any_where(|sz| *sz <= max_len);
+    let mut bytes = Vec::with_capacity(len);
+    while bytes.len() < len {
+        push_any_char(&mut bytes, len - bytes.len());
+    }
+    let bytes = Box::leak(bytes.into_boxed_slice());
+    // SAFETY: Every character pushed by `push_any_char` is encoded according to the UTF-8 rules.
+    unsafe { core::str::from_utf8_unchecked(bytes) }
+}
+
+/// Push the UTF-8 encoding of an arbitrary character that is at most `max_width` bytes long.
+///
+/// See <https://www.unicode.org/versions/latest/ch03.pdf#G7404> for the well-formed byte
+/// sequences.
+fn push_any_char(bytes: &mut Vec<u8>, max_width: usize) {
+    let width: usize = any_where(|width| *width >= 1 && *width <= max_width.min(4));
+    let first: u8 = any();
+    match width {
+        1 => assume(first <= 0x7F),
+        2 => assume((0xC2..=0xDF).contains(&first)),
+        3 => assume((0xE0..=0xEF).contains(&first)),
+        _ => assume((0xF0..=0xF4).contains(&first)),
+    }
+    bytes.push(first);
+    if width == 1 {
+        return;
+    }
+    // The range of the second byte excludes overlong encodings, surrogates and code points that
+    // are greater than `char::MAX`.
+    let second_range = match first {
+        0xE0 => 0xA0..=0xBF,
+        0xED => 0x80..=0x9F,
+        0xF0 => 0x90..=0xBF,
+        0xF4 => 0x80..=0x8F,
+        _ => 0x80..=0xBF,
+    };
+    bytes.push(any_where(|byte| second_range.contains(byte)));
+    for _ in 2..width {
+        bytes.push(any_where(|byte| (0x80..=0xBF).contains(byte)));
+    }
+}
@@ -20,6 +20,7 @@ macro_rules! generate_arbitrary {
         use core_path::ptr::{self, addr_of_mut};
         use $core as core_path;
 
+        #[rustc_diagnostic_item = "KaniArbitrary"]
         pub trait Arbitrary
         where
             Self: Sized,
@@ -172,6 +173,19 @@ fn any() -> Self {
             }
         }
 
+        /// Generate a valid `Duration`, i.e., its sub-second part is less than one second.
+        ///
+        /// The number of seconds is unconstrained, so the arithmetic on the generated value covers
+        /// the overflow of the `Duration` methods, e.g., `checked_add` returning `None`.
+        impl Arbitrary for core_path::time::Duration {
+            fn any() -> Self {
+                const NANOS_PER_SEC: u32 = 1_000_000_000;
+                let nanos = u32::any();
+                assume(nanos < NANOS_PER_SEC);
+                core_path::time::Duration::new(u64::any(), nanos)
+            }
+        }
+
         arbitrary_tuple!(A);
         arbitrary_tuple!(A, B);
         arbitrary_tuple!(A, B, C);
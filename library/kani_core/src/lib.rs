@@ -23,6 +23,7 @@
 mod arbitrary;
 mod mem;
 mod mem_init;
+mod pin;
 
 pub use kani_macros::*;
 
@@ -50,6 +51,10 @@ pub mod mem {
             mod mem_init {
                 kani_core::kani_mem_init!(core);
             }
+
+            mod pin {
+                kani_core::kani_pin!(core);
+            }
         }
     };
 
@@ -65,6 +70,10 @@ pub mod mem {
         mod mem_init {
             kani_core::kani_mem_init!(std);
         }
+
+        mod pin {
+            kani_core::kani_pin!(std);
+        }
     };
 }
 
@@ -165,6 +174,31 @@ pub const fn assert(cond: bool, msg: &'static str) {
         #[rustc_diagnostic_item = "KaniCover"]
         pub const fn cover(_cond: bool, _msg: &'static str) {}
 
+        /// Logical implication, which is `true` unless `premise` holds and `conclusion` doesn't.
+        ///
+        /// This is convenient to write the conditions of function contracts:
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// #[kani::ensures(|result: &u32| kani::implies(*cond, *result == value))]
+        /// fn select(cond: &bool, value: u32) -> u32 {
+        ///     if *cond { value } else { 0 }
+        /// }
+        /// ```
+        ///
+        /// Unlike the `implies!` macro, which expands to `!premise || conclusion`, this is a
+        /// function, so its conclusion is always evaluated, even when the premise doesn't hold.
+        /// Thus, the checks of the conclusion, such as division by zero or arithmetic overflow
+        /// checks, also apply when the premise doesn't hold. Use `implies!` if the conclusion is
+        /// only valid when the premise holds, e.g., `implies!(divisor != 0 => x / divisor > 0)`.
+        ///
+        /// During verification, the call is encoded as a single implication without branching.
+        #[inline(never)]
+        #[rustc_diagnostic_item = "KaniImplies"]
+        pub const fn implies(premise: bool, conclusion: bool) -> bool {
+            !premise || conclusion
+        }
+
         /// This creates an symbolic *valid* value of type `T`. You can assign the return value of this
         /// function to a variable that you want to make symbolic.
         ///
@@ -234,6 +268,29 @@ pub fn any_where<T: Arbitrary, F: FnOnce(&T) -> bool>(f: F) -> T {
             result
         }
 
+        /// Call `property` with two symbolic *valid* values of type `T`, in order to check a
+        /// property that relates any two values of that type.
+        ///
+        /// # Example:
+        ///
+        /// In the snippet below, we are verifying that the `Ord` implementation of every type
+        /// listed in `proof_over` is antisymmetric.
+        ///
+        /// ```no_run
+        /// # use crate::kani;
+        /// #
+        /// #[kani::proof_over(T = [u8, i32, char])]
+        /// fn check_ord<T: Ord + kani::Arbitrary>() {
+        ///     kani::check_pairs(|a: &T, b: &T| assert_eq!(a.cmp(b), b.cmp(a).reverse()));
+        /// }
+        /// ```
+        #[inline(always)]
+        pub fn check_pairs<T: Arbitrary, F: FnOnce(&T, &T)>(property: F) {
+            let first = T::any();
+            let second = T::any();
+            property(&first, &second)
+        }
+
         /// This function creates a symbolic value of type `T`. This may result in an invalid value.
         ///
         /// # Safety
@@ -284,16 +341,27 @@ pub const fn panic(message: &'static str) -> ! {
             panic!("{}", message)
         }
 
+        /// Function used to mark a program point that should never be reached.
+        ///
+        /// During verification this will get replaced by `assert(false)`, which is reported as an
+        /// `unreachable` check instead of a panic. For concrete executions, we just invoke the
+        /// regular `core::panic!()` function. This function is called by the
+        /// [`must_not_reach!`](crate::must_not_reach) macro.
+        #[inline(never)]
+        #[rustc_diagnostic_item = "KaniUnreachable"]
+        #[doc(hidden)]
+        pub const fn unreachable(message: &'static str) -> ! {
+            panic!("{}", message)
+        }
+
         /// An empty body that can be used to define Kani intrinsic functions.
         ///
         /// A Kani intrinsic is a function that is interpreted by Kani compiler.
-        /// While we could use `unreachable!()` or `panic!()` as the body of a kani intrinsic
-        /// function, both cause Kani to produce a warning since we don't support caller location.
-        /// (see https://github.com/model-checking/kani/issues/2010).
         ///
         /// This function is dead, since its caller is always  handled via a hook anyway,
         /// so we just need to put a body that rustc does not complain about.
-        /// An infinite loop works out nicely.
+        /// An infinite loop works out nicely, and unlike `unreachable!()` or `panic!()`, it doesn't
+        /// depend on the panic machinery of the standard library.
         fn kani_intrinsic<T>() -> T {
             #[allow(clippy::empty_loop)]
             loop {}
@@ -369,9 +437,607 @@ pub fn untracked_deref<T>(_: &T) -> T {
             #[rustc_diagnostic_item = "KaniInitContracts"]
             pub fn init_contracts() {}
 
+            /// The maximum number of `modifies` targets recorded for a call replaced by its
+            /// contract. Further targets are still havocked, but they are not recorded.
+            const MAX_MODIFIES_TARGETS: usize = 8;
+
+            /// The address and size of the `modifies` targets havocked by the last call replaced
+            /// by its contract.
+            static mut MODIFIES_TARGETS: [(*const u8, usize); MAX_MODIFIES_TARGETS] =
+                [(ptr::null(), 0); MAX_MODIFIES_TARGETS];
+
+            /// The number of valid entries of `MODIFIES_TARGETS`.
+            static mut MODIFIES_TARGETS_LEN: usize = 0;
+
+            /// Forget the `modifies` targets of the previous call replaced by its contract.
+            #[doc(hidden)]
+            pub fn clear_modifies_targets() {
+                unsafe { MODIFIES_TARGETS_LEN = 0 };
+            }
+
+            /// Record a `modifies` target that is about to be havocked, so `assume_unchanged`
+            /// can detect when it restricts a location explicitly modified by the contract.
+            #[doc(hidden)]
+            pub unsafe fn record_modifies_target<T: ?Sized>(pointer: *mut T) {
+                let target = (pointer as *const u8, core::mem::size_of_val(&*pointer));
+                if MODIFIES_TARGETS_LEN < MAX_MODIFIES_TARGETS {
+                    MODIFIES_TARGETS[MODIFIES_TARGETS_LEN] = target;
+                    MODIFIES_TARGETS_LEN += 1;
+                }
+            }
+
+            /// Placeholder for the value of the fixture of a harness group.
+            ///
+            /// The compiler replaces the calls in the harnesses of a group, i.e.,
+            /// `#[kani::proof(group = "...")]`, by a call to the fixture of the group.
+            #[doc(hidden)]
+            #[inline(never)]
+            #[rustc_diagnostic_item = "KaniFixture"]
+            pub fn fixture<T>() -> T {
+                super::kani_intrinsic()
+            }
+
+            /// Implementation of the `kani::assume_unchanged!` macro.
+            ///
+            /// The compiler redirects `snapshot` to a copy of the location taken before the
+            /// preceding call replaced by its contract, and we assume that the location has the
+            /// same bytes as the copy. We also add a cover property that is satisfied if the
+            /// location is exactly one of the `modifies` targets of that call, since that
+            /// contradicts the contract instead of refining it.
+            #[doc(hidden)]
+            #[inline(never)]
+            #[rustc_diagnostic_item = "KaniAssumeUnchanged"]
+            pub fn assume_unchanged<T>(current: &T, snapshot: &T) {
+                let size = core::mem::size_of::<T>();
+                let current = current as *const T as *const u8;
+                let snapshot = snapshot as *const T as *const u8;
+                let len = unsafe { MODIFIES_TARGETS_LEN };
+                let modified = (0..len).any(|i| unsafe { MODIFIES_TARGETS[i] } == (current, size));
+                super::cover(
+                    modified,
+                    "assume_unchanged! restricts a location named by a `modifies` clause",
+                );
+                let current = unsafe { core::slice::from_raw_parts(current, size) };
+                let snapshot = unsafe { core::slice::from_raw_parts(snapshot, size) };
+                super::assume(current == snapshot);
+            }
+
+            /// Assume that the result of a call is `Ok`.
+            ///
+            /// With `--assume-ok`, the compiler calls this function on the result of every call
+            /// in the crate under verification that returns a `Result`, which prunes the paths
+            /// where the call returns an error.
+            #[doc(hidden)]
+            #[inline(never)]
+            #[rustc_diagnostic_item = "KaniAssumeOk"]
+            pub fn assume_ok<T, E>(result: &Result<T, E>) {
+                super::assume(result.is_ok());
+            }
+
+            /// Store a copy of a value captured by the message of a failing `assert!`.
+            ///
+            /// The copy is never used, but its assignment is included in the counterexample
+            /// trace, which is used to render the value in the assertion message.
+            #[inline(never)]
+            #[doc(hidden)]
+            pub fn capture_value<T>(value: &T) {
+                // SAFETY: The copy is never dropped, so this doesn't duplicate ownership.
+                let captured_value = unsafe { ptr::read(value) };
+                core::mem::forget(captured_value);
+            }
+
+            /// Check that two equal values of type `T` have equal hashes.
+            #[doc(hidden)]
+            pub fn check_hash_eq<T: Arbitrary + Eq + core::hash::Hash>() {
+                let first: T = super::any();
+                let second: T = super::any();
+                super::assume(first == second);
+                super::assert(
+                    hash_of(&first) == hash_of(&second),
+                    "equal values must have equal hashes",
+                );
+            }
+
+            /// Select the order axioms checked by `#[kani::proof_ord_consistent(T)]`, depending on
+            /// whether `T` implements `Ord` or only `PartialOrd`.
+            ///
+            /// The check is called on a reference to this type, so the method of [TotalOrdCheck]
+            /// is selected if `T: Ord`, and the method of [PartialOrdCheck], which takes a double
+            /// reference, is only selected otherwise.
+            #[doc(hidden)]
+            pub struct OrdCheck<T>(pub core::marker::PhantomData<T>);
+
+            #[doc(hidden)]
+            pub trait TotalOrdCheck {
+                fn check(&self);
+            }
+
+            impl<T: Arbitrary + Ord> TotalOrdCheck for OrdCheck<T> {
+                fn check(&self) {
+                    check_ord_consistent::<T>()
+                }
+            }
+
+            #[doc(hidden)]
+            pub trait PartialOrdCheck {
+                fn check(&self);
+            }
+
+            impl<T: Arbitrary + PartialOrd> PartialOrdCheck for &OrdCheck<T> {
+                fn check(&self) {
+                    check_partial_ord_consistent::<T>()
+                }
+            }
+
+            /// Check that the `Ord` implementation of `T` is a total order that is consistent with
+            /// its `PartialOrd` and `Eq` implementations.
+            fn check_ord_consistent<T: Arbitrary + Ord>() {
+                let first: T = super::any();
+                let second: T = super::any();
+                let third: T = super::any();
+                check_partial_ord_axioms(&first, &second, &third);
+                super::assert(first == first, "`==` must be reflexive");
+                super::assert(
+                    first.partial_cmp(&second) == Some(first.cmp(&second)),
+                    "`partial_cmp` must be consistent with `cmp`",
+                );
+            }
+
+            /// Check that the `PartialOrd` implementation of `T` is a partial order that is
+            /// consistent with its `PartialEq` implementation.
+            ///
+            /// Values may be incomparable, such as NaN, so neither totality nor the reflexivity
+            /// of `==` are checked.
+            fn check_partial_ord_consistent<T: Arbitrary + PartialOrd>() {
+                let first: T = super::any();
+                let second: T = super::any();
+                let third: T = super::any();
+                check_partial_ord_axioms(&first, &second, &third);
+            }
+
+            fn check_partial_ord_axioms<T: PartialOrd>(a: &T, b: &T, c: &T) {
+                use core::cmp::Ordering;
+                let ord = a.partial_cmp(b);
+                super::assert(
+                    (a == b) == (ord == Some(Ordering::Equal)),
+                    "`==` must be consistent with `partial_cmp`",
+                );
+                super::assert(
+                    (a < b) == (ord == Some(Ordering::Less)),
+                    "`<` must be consistent with `partial_cmp`",
+                );
+                super::assert(
+                    (a > b) == (ord == Some(Ordering::Greater)),
+                    "`>` must be consistent with `partial_cmp`",
+                );
+                super::assert(
+                    (a <= b) == (a < b || a == b),
+                    "`<=` must be consistent with `<` and `==`",
+                );
+                super::assert(
+                    (a >= b) == (a > b || a == b),
+                    "`>=` must be consistent with `>` and `==`",
+                );
+                super::assert(
+                    ord == b.partial_cmp(a).map(Ordering::reverse),
+                    "`partial_cmp` must be antisymmetric",
+                );
+                super::assert(!(a < b && b < c) || a < c, "`<` must be transitive");
+                super::assert(!(a == b && b == c) || a == c, "`==` must be transitive");
+            }
+
+            /// Select the contract checked by `#[kani::proof_allocator(allocator)]`, depending on
+            /// whether the allocator implements `GlobalAlloc` or `Allocator`.
+            ///
+            /// The check is called on a reference to this type, so the method of
+            /// [GlobalAllocCheck] is selected if `A: GlobalAlloc`, and the method of
+            /// [AllocatorCheck], which takes a double reference, is only selected otherwise.
+            #[doc(hidden)]
+            pub struct AllocCheck<'a, A>(pub &'a A);
+
+            #[doc(hidden)]
+            pub trait GlobalAllocCheck {
+                fn check(&self);
+            }
+
+            impl<A: core::alloc::GlobalAlloc> GlobalAllocCheck for AllocCheck<'_, A> {
+                fn check(&self) {
+                    check_global_alloc(self.0)
+                }
+            }
+
+            #[doc(hidden)]
+            pub trait AllocatorCheck {
+                fn check(&self);
+            }
+
+            impl<A: core::alloc::Allocator> AllocatorCheck for &AllocCheck<'_, A> {
+                fn check(&self) {
+                    check_allocator(self.0)
+                }
+            }
+
+            /// Check that `allocator` upholds the contract of `GlobalAlloc` for two live
+            /// allocations with nondeterministic layouts, before deallocating them.
+            ///
+            /// Calling `GlobalAlloc::alloc` with a zero-sized layout is undefined behavior, so only
+            /// layouts with a non-zero size are requested.
+            fn check_global_alloc<A: core::alloc::GlobalAlloc>(allocator: &A) {
+                let first_layout = any_layout(false);
+                let second_layout = any_layout(false);
+                let first = unsafe { allocator.alloc(first_layout) };
+                let second = unsafe { allocator.alloc(second_layout) };
+                // A null pointer signals that the allocation failed.
+                if !first.is_null() {
+                    check_allocation(first, first_layout.size(), first_layout);
+                }
+                if !second.is_null() {
+                    check_allocation(second, second_layout.size(), second_layout);
+                }
+                if !first.is_null() && !second.is_null() {
+                    check_disjoint(first, first_layout.size(), second, second_layout.size());
+                }
+                unsafe {
+                    if !first.is_null() {
+                        allocator.dealloc(first, first_layout);
+                    }
+                    if !second.is_null() {
+                        allocator.dealloc(second, second_layout);
+                    }
+                }
+            }
+
+            /// Check that `allocator` upholds the contract of `Allocator` for two live
+            /// allocations with nondeterministic layouts, before deallocating them.
+            ///
+            /// Zero-sized layouts may be requested. The block returned for them must be aligned,
+            /// but it doesn't need to point to allocated memory.
+            fn check_allocator<A: core::alloc::Allocator>(allocator: &A) {
+                let first_layout = any_layout(true);
+                let second_layout = any_layout(true);
+                let first = allocator.allocate(first_layout).ok();
+                let second = allocator.allocate(second_layout).ok();
+                if let Some(block) = first {
+                    super::assert(
+                        block.len() >= first_layout.size(),
+                        "the allocated block must be at least as large as the size of the layout",
+                    );
+                    check_allocation(block.as_ptr() as *mut u8, block.len(), first_layout);
+                }
+                if let Some(block) = second {
+                    super::assert(
+                        block.len() >= second_layout.size(),
+                        "the allocated block must be at least as large as the size of the layout",
+                    );
+                    check_allocation(block.as_ptr() as *mut u8, block.len(), second_layout);
+                }
+                if let (Some(first), Some(second)) = (first, second) {
+                    check_disjoint(
+                        first.as_ptr() as *mut u8,
+                        first.len(),
+                        second.as_ptr() as *mut u8,
+                        second.len(),
+                    );
+                }
+                unsafe {
+                    if let Some(block) = first {
+                        allocator.deallocate(block.cast(), first_layout);
+                    }
+                    if let Some(block) = second {
+                        allocator.deallocate(block.cast(), second_layout);
+                    }
+                }
+            }
+
+            /// A nondeterministic valid layout, which has a non-zero size unless `zero_sized`.
+            fn any_layout(zero_sized: bool) -> core::alloc::Layout {
+                let size: usize = super::any();
+                let align_shift: u32 = super::any();
+                super::assume(zero_sized || size != 0);
+                super::assume(align_shift < usize::BITS);
+                let layout = core::alloc::Layout::from_size_align(size, 1 << align_shift);
+                super::assume(layout.is_ok());
+                layout.unwrap()
+            }
+
+            /// Check that the memory allocated for `layout` at `ptr` is aligned and that its `size`
+            /// bytes are allocated.
+            fn check_allocation(ptr: *mut u8, size: usize, layout: core::alloc::Layout) {
+                super::assert(
+                    ptr as usize % layout.align() == 0,
+                    "the allocated memory must be aligned to the alignment of the layout",
+                );
+                if size != 0 {
+                    super::assert(
+                        unsafe { super::mem::is_allocated(ptr as *const (), size) },
+                        "the allocated memory must be valid for its whole size",
+                    );
+                }
+            }
+
+            /// Check that the memory of two live allocations doesn't overlap.
+            fn check_disjoint(
+                first: *mut u8,
+                first_size: usize,
+                second: *mut u8,
+                second_size: usize,
+            ) {
+                if first_size == 0 || second_size == 0 {
+                    return;
+                }
+                let first_start = first as usize;
+                let second_start = second as usize;
+                let disjoint = super::mem::pointer_object(first)
+                    != super::mem::pointer_object(second)
+                    || first_start.saturating_add(first_size) <= second_start
+                    || second_start.saturating_add(second_size) <= first_start;
+                super::assert(disjoint, "live allocations must not overlap");
+            }
+
+            /// Check that mutating a clone of a value of type `T` with `mutate` doesn't change the
+            /// original value, even after the clone is dropped.
+            ///
+            /// The original is compared against a value that is generated independently, so the
+            /// comparison doesn't depend on `Clone` itself.
+            #[doc(hidden)]
+            pub fn check_clone_independent<T: Arbitrary + Clone + PartialEq, F: FnOnce(&mut T)>(
+                mutate: F,
+            ) {
+                let original: T = super::any();
+                let expected: T = super::any();
+                super::assume(original == expected);
+                let mut copy = original.clone();
+                mutate(&mut copy);
+                core::mem::drop(copy);
+                super::assert(
+                    original == expected,
+                    "mutating a clone must not change the original value",
+                );
+            }
+
+            /// Check that converting a value of type `Src` that satisfies `assume` to `Dst` with
+            /// `From` and back with `TryInto` returns the original value.
+            #[doc(hidden)]
+            pub fn check_lossless<Src, Dst, F>(assume: F)
+            where
+                Src: Arbitrary + Clone + PartialEq,
+                Dst: From<Src> + TryInto<Src>,
+                F: FnOnce(&Src) -> bool,
+            {
+                let original: Src = super::any();
+                super::assume(assume(&original));
+                let converted = Dst::from(original.clone());
+                let roundtrip: Option<Src> = converted.try_into().ok();
+                super::assert(
+                    roundtrip.is_some_and(|value| value == original),
+                    "converting the value back must return the original value",
+                );
+            }
+
+            /// Check that indexing a value of type `T` with an index that satisfies `bound` doesn't
+            /// panic.
+            #[doc(hidden)]
+            pub fn check_index_in_bounds<T, Idx, F>(bound: F)
+            where
+                T: Arbitrary + core::ops::Index<Idx>,
+                Idx: Arbitrary,
+                F: FnOnce(&T, &Idx) -> bool,
+            {
+                let value: T = super::any();
+                let index: Idx = super::any();
+                super::assume(bound(&value, &index));
+                let _element = &value[index];
+            }
+
+            /// Check that indexing a value of type `T` with an index that doesn't satisfy `bound`
+            /// always panics.
+            ///
+            /// This is the body of the `_out_of_bounds` harness generated by
+            /// `#[kani::proof_index_safe(T, bound = ...)]`, which is expected to panic. Returning
+            /// from the indexing is reported as an `unreachable` check, which is not a panic, so the
+            /// harness fails if any out-of-bounds index doesn't panic.
+            #[doc(hidden)]
+            pub fn check_index_out_of_bounds<T, Idx, F>(bound: F)
+            where
+                T: Arbitrary + core::ops::Index<Idx>,
+                Idx: Arbitrary,
+                F: FnOnce(&T, &Idx) -> bool,
+            {
+                let value: T = super::any();
+                let index: Idx = super::any();
+                super::assume(!bound(&value, &index));
+                let _element = &value[index];
+                super::unreachable("indexing with an out-of-bounds index must panic");
+            }
+
+            /// The maximum number of times that `#[kani::proof_future(F)]` polls the future
+            /// before it completes.
+            const MAX_FUTURE_POLLS: usize = 3;
+
+            /// Whether the future checked by `#[kani::proof_future(F)]` is being polled after it
+            /// returned `Ready`.
+            static mut POLLING_AFTER_READY: bool = false;
+
+            /// Check that a future of type `F`, whose state is generated by `Arbitrary`, registers
+            /// the waker of its context every time that it returns `Pending`, until it completes
+            /// or it's polled `MAX_FUTURE_POLLS` times. If the future completes, it's polled once
+            /// more, which must not cause undefined behavior, but which may panic.
+            ///
+            /// Since the state of the future is nondeterministic, so is the number of polls that
+            /// it takes to complete, and the assertions cover every prefix of the poll sequence.
+            #[doc(hidden)]
+            pub fn check_future<F: Arbitrary + core::future::Future>() {
+                let registered = core::cell::Cell::new(false);
+                // SAFETY: The waker doesn't outlive `registered`, and its vtable upholds the
+                // contract of `RawWakerVTable`.
+                let waker = unsafe { core::task::Waker::from_raw(tracking_waker(&registered)) };
+                let mut context = core::task::Context::from_waker(&waker);
+                let mut future: F = super::any();
+                // SAFETY: The future is shadowed, so it's never moved after being pinned.
+                let mut future = unsafe { core::pin::Pin::new_unchecked(&mut future) };
+                for _ in 0..MAX_FUTURE_POLLS {
+                    registered.set(false);
+                    if future.as_mut().poll(&mut context).is_ready() {
+                        // Polling a completed future may panic, so the compiler prunes the
+                        // panics of `poll` while this flag is set.
+                        unsafe { POLLING_AFTER_READY = true };
+                        let _ = future.as_mut().poll(&mut context);
+                        unsafe { POLLING_AFTER_READY = false };
+                        return;
+                    }
+                    super::assert(
+                        registered.get(),
+                        "a future that returns `Pending` must register the waker of its context",
+                    );
+                }
+            }
+
+            /// Prune the execution if `poll` is about to panic while the future checked by
+            /// `#[kani::proof_future(F)]` is polled after it returned `Ready`, since this is
+            /// allowed by the contract of `Future`.
+            ///
+            /// The compiler inserts a call to this function before every panic of the `poll`
+            /// implementations of the crate under verification.
+            #[doc(hidden)]
+            #[inline(never)]
+            #[rustc_diagnostic_item = "KaniPanicAfterReady"]
+            pub fn panic_after_ready(panics: bool) {
+                super::assume(!(panics && unsafe { POLLING_AFTER_READY }));
+            }
+
+            /// Check that formatting a value of type `T`, which is generated by `Arbitrary`, with
+            /// its `Display` implementation doesn't panic, even if the writer returns an error.
+            ///
+            /// `#[kani::proof_display_safe(T)]` also checks the `Debug` implementation of `T` with
+            /// [DebugSafeCheck] if there's one.
+            #[doc(hidden)]
+            pub fn check_display_safe<T: Arbitrary + core::fmt::Display>() {
+                let value: T = super::any();
+                let _ = core::fmt::write(&mut NondetWriter, format_args!("{value}"));
+            }
+
+            /// Select whether `#[kani::proof_display_safe(T)]` also checks the `Debug`
+            /// implementation of `T`.
+            ///
+            /// The check is called on a reference to this type, so the method of
+            /// [DebugSafeCheck] is selected if `T: Debug`, and the method of [NoDebugCheck], which
+            /// takes a double reference and does nothing, is only selected otherwise.
+            #[doc(hidden)]
+            pub struct DebugCheck<T>(pub core::marker::PhantomData<T>);
+
+            #[doc(hidden)]
+            pub trait DebugSafeCheck {
+                fn check(&self);
+            }
+
+            impl<T: Arbitrary + core::fmt::Debug> DebugSafeCheck for DebugCheck<T> {
+                fn check(&self) {
+                    let value: T = super::any();
+                    let _ = core::fmt::write(&mut NondetWriter, format_args!("{value:?}"));
+                }
+            }
+
+            #[doc(hidden)]
+            pub trait NoDebugCheck {
+                fn check(&self);
+            }
+
+            impl<T> NoDebugCheck for &DebugCheck<T> {
+                fn check(&self) {}
+            }
+
+            /// A writer that discards what is written to it and that fails nondeterministically,
+            /// so formatting is checked both when every write succeeds and when one of them fails.
+            struct NondetWriter;
+
+            impl core::fmt::Write for NondetWriter {
+                fn write_str(&mut self, _s: &str) -> core::fmt::Result {
+                    if super::any() { Ok(()) } else { Err(core::fmt::Error) }
+                }
+            }
+
+            /// The maximum number of items that `#[kani::proof_fused(I)]` takes from the iterator
+            /// before it returns `None`.
+            const MAX_FUSED_ITEMS: usize = 3;
+
+            /// The number of times that `#[kani::proof_fused(I)]` calls `next` after the iterator
+            /// returned `None`.
+            const FUSED_CALLS_AFTER_NONE: usize = 2;
+
+            /// Check that an iterator of type `I`, whose state is generated by `Arbitrary`, keeps
+            /// returning `None` once it returned `None`, as required by `FusedIterator`.
+            ///
+            /// Since the state of the iterator is nondeterministic, so is the number of items that
+            /// it yields before it's exhausted. The iterators that yield more than
+            /// `MAX_FUSED_ITEMS` items are not checked. Once exhausted, `next` is called more than
+            /// once, which covers iterators whose `None` depends on state that the calls after the
+            /// first `None` may change.
+            #[doc(hidden)]
+            pub fn check_fused<I: Arbitrary + core::iter::FusedIterator>() {
+                let mut iter: I = super::any();
+                let mut exhausted = false;
+                for _ in 0..=MAX_FUSED_ITEMS {
+                    if iter.next().is_none() {
+                        exhausted = true;
+                        break;
+                    }
+                }
+                super::assume(exhausted);
+                for _ in 0..FUSED_CALLS_AFTER_NONE {
+                    super::assert(
+                        iter.next().is_none(),
+                        "a fused iterator must keep returning `None` after it returned `None`",
+                    );
+                }
+            }
+
+            kani_core::default_valid_check!();
+
+            /// A waker that records in `registered` whether it was cloned or woken, which are the
+            /// ways a future can arrange to be polled again.
+            fn tracking_waker(registered: &core::cell::Cell<bool>) -> core::task::RawWaker {
+                use core::task::{RawWaker, RawWakerVTable};
+
+                unsafe fn register(data: *const ()) {
+                    unsafe { (*(data as *const core::cell::Cell<bool>)).set(true) };
+                }
+                unsafe fn clone_waker(data: *const ()) -> RawWaker {
+                    unsafe { register(data) };
+                    RawWaker::new(data, &VTABLE)
+                }
+                unsafe fn drop_waker(_data: *const ()) {}
+                static VTABLE: RawWakerVTable =
+                    RawWakerVTable::new(clone_waker, register, register, drop_waker);
+
+                RawWaker::new(registered as *const core::cell::Cell<bool> as *const (), &VTABLE)
+            }
+
+            /// Hash a value with the FNV-1a hash function, which is cheaper to verify than the
+            /// default hasher of the standard library.
+            fn hash_of<T: core::hash::Hash>(value: &T) -> u64 {
+                struct FnvHasher(u64);
+
+                impl core::hash::Hasher for FnvHasher {
+                    fn finish(&self) -> u64 {
+                        self.0
+                    }
+
+                    fn write(&mut self, bytes: &[u8]) {
+                        for byte in bytes {
+                            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+                        }
+                    }
+                }
+
+                let mut hasher = FnvHasher(0xcbf29ce484222325);
+                core::hash::Hash::hash(value, &mut hasher);
+                core::hash::Hasher::finish(&hasher)
+            }
+
             /// This should only be used within contracts. The intent is to
             /// perform type inference on a closure's argument
             #[doc(hidden)]
+            #[rustc_diagnostic_item = "KaniApplyClosure"]
             pub fn apply_closure<T, U: Fn(&T) -> bool>(f: U, x: &T) -> bool {
                 f(x)
             }
@@ -442,6 +1108,72 @@ fn run_loop_contract_fn<F: Fn() -> bool>(func: &F, _transformed: usize) -> bool
                 func()
             }
 
+            /// The steps left to a function annotated with `#[kani::terminates(within = N)]`.
+            ///
+            /// A counter is shared by all the recursive calls of the function, and it is reset
+            /// every time the outermost call starts.
+            #[doc(hidden)]
+            pub struct StepCounter {
+                remaining: usize,
+                depth: usize,
+            }
+
+            /// Keeps track of the active calls of a function annotated with `#[kani::terminates]`.
+            #[doc(hidden)]
+            pub struct StepGuard {
+                counter: *mut StepCounter,
+                outermost: bool,
+            }
+
+            impl StepCounter {
+                #[allow(clippy::new_without_default)]
+                pub const fn new() -> Self {
+                    StepCounter { remaining: 0, depth: 0 }
+                }
+
+                /// Start a call of the function. If this is the outermost call, the function has
+                /// `bound` steps left.
+                ///
+                /// # Safety
+                ///
+                /// The counter must be valid for the lifetime of the returned guard.
+                pub unsafe fn enter(counter: *mut StepCounter, bound: usize) -> StepGuard {
+                    let outermost = (*counter).depth == 0;
+                    if outermost {
+                        (*counter).remaining = bound;
+                    }
+                    (*counter).depth += 1;
+                    StepGuard { counter, outermost }
+                }
+
+                /// Take one step. Return whether the step was within the bound.
+                ///
+                /// # Safety
+                ///
+                /// The counter must be valid.
+                pub unsafe fn step(counter: *mut StepCounter) -> bool {
+                    if (*counter).remaining == 0 {
+                        false
+                    } else {
+                        (*counter).remaining -= 1;
+                        true
+                    }
+                }
+            }
+
+            impl StepGuard {
+                pub fn is_outermost(&self) -> bool {
+                    self.outermost
+                }
+            }
+
+            impl Drop for StepGuard {
+                fn drop(&mut self) {
+                    // SAFETY: The counter outlives the guard, as required by `StepCounter::enter`.
+                    unsafe { (*self.counter).depth -= 1 };
+                }
+            }
+
             /// This is used by contracts to select which version of the contract to use during codegen.
             #[doc(hidden)]
             pub type Mode = u8;
@@ -489,3 +1221,27 @@ pub(crate) const fn check(cond: bool, msg: &'static str) {
         }
     };
 }
+
+#[cfg(not(feature = "no_core"))]
+#[macro_export]
+macro_rules! default_valid_check {
+    () => {
+        /// Check that the default value of `T` satisfies its invariant.
+        #[doc(hidden)]
+        pub fn check_default_valid<T: Default + crate::kani::Invariant>() {
+            let value = T::default();
+            super::assert(
+                value.is_safe(),
+                "the default value must satisfy the invariant of its type",
+            );
+        }
+    };
+}
+
+/// Don't generate the default value check here since the `Invariant` trait is only defined by
+/// the Kani library.
+#[cfg(feature = "no_core")]
+#[macro_export]
+macro_rules! default_valid_check {
+    () => {};
+}
@@ -303,7 +303,7 @@ fn dangling(&self, _: Internal) -> *const () {
         /// Otherwise, it returns non-det boolean.
         #[rustc_diagnostic_item = "KaniIsAllocated"]
         #[inline(never)]
-        unsafe fn is_allocated(_ptr: *const (), _size: usize) -> bool {
+        pub(crate) unsafe fn is_allocated(_ptr: *const (), _size: usize) -> bool {
             kani_intrinsic()
         }
 
@@ -0,0 +1,77 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! This module provides instrumentation for checking that pinned values are not moved.
+//!
+//! Once a value of a type that doesn't implement `Unpin` is pinned, it must stay at the same
+//! location until it's dropped. The compiler inserts a call to `pin_pointer` when a `Pin` is
+//! created, a call to `unpin_pointer` before the pinned value is dropped, and a check that calls
+//! `is_pointer_pinned` before a value is moved out of a location through a pointer.
+//!
+//! Only one pinned location is tracked at a time. The location is chosen non-deterministically
+//! among the values pinned by the harness, so calls to `is_pointer_pinned` should only be used in
+//! assertion contexts.
+
+// Definitions in this module are not meant to be visible to the end user, only the compiler.
+#![allow(dead_code)]
+
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! kani_pin {
+    ($core:path) => {
+        /// Global object for tracking the location of a pinned value.
+        static mut PIN_STATE: PinState = PinState::new();
+
+        /// The currently tracked location of a pinned value.
+        struct PinState {
+            pub tracked_object_id: usize,
+            pub tracked_offset: usize,
+            pub pinned: bool,
+        }
+
+        impl PinState {
+            pub const fn new() -> Self {
+                Self { tracked_object_id: 0, tracked_offset: 0, pinned: false }
+            }
+
+            /// Return whether `ptr` points to the tracked location.
+            #[kanitool::disable_checks(pointer)]
+            pub fn is_tracked(&self, ptr: *const u8) -> bool {
+                self.tracked_object_id == super::mem::pointer_object(ptr)
+                    && self.tracked_offset == super::mem::pointer_offset(ptr)
+            }
+        }
+
+        /// Non-deterministically start tracking the location of a value that was just pinned, if
+        /// no other location is being tracked.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniPinPointer"]
+        fn pin_pointer<T>(ptr: *const T) {
+            unsafe {
+                if !PIN_STATE.pinned && super::any::<bool>() {
+                    PIN_STATE.tracked_object_id = super::mem::pointer_object(ptr);
+                    PIN_STATE.tracked_offset = super::mem::pointer_offset(ptr);
+                    PIN_STATE.pinned = true;
+                }
+            }
+        }
+
+        /// Stop tracking the location of a pinned value, since it's about to be dropped.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniUnpinPointer"]
+        fn unpin_pointer<T>(ptr: *const T) {
+            unsafe {
+                if PIN_STATE.pinned && PIN_STATE.is_tracked(ptr as *const u8) {
+                    PIN_STATE.pinned = false;
+                }
+            }
+        }
+
+        /// Return whether `ptr` points to the tracked location of a pinned value.
+        #[kanitool::disable_checks(pointer)]
+        #[rustc_diagnostic_item = "KaniIsPointerPinned"]
+        fn is_pointer_pinned<T>(ptr: *const T) -> bool {
+            unsafe { PIN_STATE.pinned && PIN_STATE.is_tracked(ptr as *const u8) }
+        }
+    };
+}
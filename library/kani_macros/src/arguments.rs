@@ -0,0 +1,98 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for harness arguments marked with `#[kani::any]`, e.g.:
+//!
+//! ```ignore
+//! #[kani::proof]
+//! fn check_config(#[kani::any] config: Config) { ... }
+//! ```
+//!
+//! For verification, each marked argument is removed from the signature of the harness and
+//! declared at the beginning of its body with a value generated by `kani::any()`. The variable
+//! keeps the name of the argument, so its value can be found in the counterexample.
+//!
+//! Harnesses that belong to a fixture group, i.e., `#[kani::proof(group = "...")]`, take the value
+//! of the fixture of the group as their remaining argument.
+
+use syn::{Attribute, FnArg, ItemFn, Meta};
+
+/// Whether the attribute is `#[kani::any]`.
+fn is_any_attribute(attr: &Attribute) -> bool {
+    let segments = &attr.path().segments;
+    matches!(attr.meta, Meta::Path(_))
+        && segments.len() == 2
+        && segments[0].ident == "kani"
+        && segments[1].ident == "any"
+}
+
+/// Replace the arguments of the harness that are marked with `#[kani::any]` by variables with a
+/// nondeterministic value.
+///
+/// The type of each argument must implement `kani::Arbitrary`, which is reported at the type of
+/// the argument otherwise. Arguments without the attribute are kept, and they are reported by the
+/// compiler.
+#[cfg(kani_sysroot)]
+pub fn declare_any_arguments(fn_item: &mut ItemFn) {
+    use syn::spanned::Spanned;
+    use syn::{Stmt, parse_quote_spanned};
+
+    let inputs = std::mem::take(&mut fn_item.sig.inputs);
+    let mut stmts: Vec<Stmt> = vec![];
+    for input in inputs {
+        match input {
+            FnArg::Typed(mut arg) if arg.attrs.iter().any(is_any_attribute) => {
+                arg.attrs.retain(|attr| !is_any_attribute(attr));
+                let (attrs, pat, ty) = (arg.attrs, arg.pat, arg.ty);
+                stmts.push(parse_quote_spanned!(ty.span()=>
+                    #(#attrs)* let #pat: #ty = kani::any::<#ty>();
+                ));
+            }
+            input => fn_item.sig.inputs.push(input),
+        }
+    }
+    fn_item.block.stmts.splice(0..0, stmts);
+}
+
+/// Replace the only remaining argument of a harness that belongs to a fixture group by a variable
+/// initialized with the value of the fixture, e.g., `fn check(db: Database)` declares:
+///
+/// ```ignore
+/// let db: Database = kani::internal::fixture::<Database>();
+/// ```
+///
+/// The compiler replaces the call to `kani::internal::fixture` by a call to the fixture of the
+/// group of the harness. This must be called after [declare_any_arguments].
+#[cfg(kani_sysroot)]
+pub fn declare_fixture_argument(fn_item: &mut ItemFn) {
+    use proc_macro_error2::abort;
+    use syn::spanned::Spanned;
+    use syn::{Stmt, parse_quote_spanned};
+
+    if fn_item.sig.inputs.len() != 1 {
+        abort!(
+            fn_item.sig,
+            "a harness in a fixture group must take the value of the fixture as its only \
+            argument without `#[kani::any]`, but found {} such arguments",
+            fn_item.sig.inputs.len()
+        );
+    }
+    let FnArg::Typed(arg) = fn_item.sig.inputs.pop().unwrap().into_value() else {
+        abort!(fn_item.sig, "a harness in a fixture group cannot take `self`");
+    };
+    let (attrs, pat, ty) = (arg.attrs, arg.pat, arg.ty);
+    let stmt: Stmt = parse_quote_spanned!(ty.span()=>
+        #(#attrs)* let #pat: #ty = kani::internal::fixture::<#ty>();
+    );
+    fn_item.block.stmts.insert(0, stmt);
+}
+
+/// Remove the `#[kani::any]` attributes, which are only meaningful for verification.
+#[cfg(not(kani_sysroot))]
+pub fn strip_any_attributes(fn_item: &mut ItemFn) {
+    for input in fn_item.sig.inputs.iter_mut() {
+        if let FnArg::Typed(arg) = input {
+            arg.attrs.retain(|attr| !is_any_attribute(attr));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::dyn_impls]` attribute, which registers the concrete types that
+//! `kani::any::<Box<dyn Trait>>()` may create.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Index, Token, Type, TypeParamBound, bracketed, parse_macro_input};
+
+/// The parsed arguments of `#[kani::dyn_impls(Trait = [A, B], Other + Send = [C])]`.
+struct DynImplsArgs {
+    traits: Vec<DynImpls>,
+}
+
+/// The candidates of one trait object type.
+struct DynImpls {
+    bounds: Punctuated<TypeParamBound, Token![+]>,
+    types: Vec<Type>,
+}
+
+impl Parse for DynImplsArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let traits = Punctuated::<DynImpls, Token![,]>::parse_terminated(input)?;
+        if traits.is_empty() {
+            return Err(input.error("expected at least one `Trait = [Type, ...]` argument"));
+        }
+        Ok(DynImplsArgs { traits: traits.into_iter().collect() })
+    }
+}
+
+impl Parse for DynImpls {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let bounds = Punctuated::parse_separated_nonempty(input)?;
+        input.parse::<Token![=]>()?;
+        let content;
+        let brackets = bracketed!(content in input);
+        let types = Punctuated::<Type, Token![,]>::parse_terminated(&content)?;
+        if types.is_empty() {
+            return Err(syn::Error::new(
+                brackets.span.join(),
+                format!("expected at least one type for `dyn {}`", bounds.to_token_stream()),
+            ));
+        }
+        Ok(DynImpls { bounds, types: types.into_iter().collect() })
+    }
+}
+
+/// Keep the annotated item, and generate an `Arbitrary` implementation for each trait object.
+///
+/// Each implementation looks like:
+/// ```ignore
+/// impl kani::Arbitrary for Box<dyn Trait> {
+///     fn any() -> Self {
+///         match kani::any::<usize>() {
+///             0 => Box::new(kani::any::<A>()),
+///             _ => Box::new(kani::any::<B>()),
+///         }
+///     }
+/// }
+/// ```
+/// The unsizing coercion of each arm creates the vtable of the chosen type.
+pub fn dyn_impls(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let DynImplsArgs { traits } = parse_macro_input!(attr as DynImplsArgs);
+    let item = TokenStream2::from(item);
+    let impls = traits.iter().map(arbitrary_impl);
+    quote!(
+        #item
+        #(#impls)*
+    )
+    .into()
+}
+
+fn arbitrary_impl(dyn_impls: &DynImpls) -> TokenStream2 {
+    let bounds = &dyn_impls.bounds;
+    let arms = dyn_impls.types.iter().enumerate().map(|(idx, ty)| {
+        let pattern = if idx + 1 < dyn_impls.types.len() {
+            Index::from(idx).into_token_stream()
+        } else {
+            quote!(_)
+        };
+        // Report a candidate that doesn't implement the trait or `Arbitrary` at its type.
+        quote_spanned! {ty.span()=>
+            #pattern => ::std::boxed::Box::new(kani::any::<#ty>()),
+        }
+    });
+    quote! {
+        impl kani::Arbitrary for ::std::boxed::Box<dyn #bounds> {
+            fn any() -> Self {
+                match kani::any::<usize>() {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
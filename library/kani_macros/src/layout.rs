@@ -0,0 +1,134 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `assert_layout!` macro, which checks the size, alignment and field
+//! offsets of a type against an expected layout.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Member, Token, Type, bracketed, parenthesized, parse_macro_input};
+
+/// The parsed arguments of `assert_layout!(Type, size = N, align = M, fields = [(name, offset)])`.
+struct LayoutArgs {
+    ty: Type,
+    size: Option<Expr>,
+    align: Option<Expr>,
+    fields: Vec<(Member, Expr)>,
+}
+
+impl Parse for LayoutArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        let mut args = LayoutArgs { ty, size: None, align: None, fields: vec![] };
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "size" if args.size.is_none() => args.size = Some(input.parse()?),
+                "align" if args.align.is_none() => args.align = Some(input.parse()?),
+                "fields" if args.fields.is_empty() => {
+                    let content;
+                    bracketed!(content in input);
+                    let fields = Punctuated::<FieldOffset, Token![,]>::parse_terminated(&content)?;
+                    args.fields = fields.into_iter().map(|f| (f.member, f.offset)).collect();
+                }
+                "size" | "align" | "fields" => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("duplicated `{key}` argument"),
+                    ));
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown argument `{key}`, expected `size`, `align` or `fields`"),
+                    ));
+                }
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("expected `,`"));
+        }
+        if args.size.is_none() && args.align.is_none() && args.fields.is_empty() {
+            return Err(input.error("expected at least one of `size`, `align` or `fields`"));
+        }
+        Ok(args)
+    }
+}
+
+/// A `(field, offset)` pair.
+struct FieldOffset {
+    member: Member,
+    offset: Expr,
+}
+
+impl Parse for FieldOffset {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let member = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let offset = content.parse()?;
+        Ok(FieldOffset { member, offset })
+    }
+}
+
+/// Generate one check per property of the layout.
+///
+/// Each check looks like:
+/// ```ignore
+/// {
+///     let actual: usize = ::core::mem::size_of::<Type>();
+///     if actual != (16) {
+///         kani::internal::capture_value(&actual);
+///     }
+///     kani::assert(actual == (16), "size of `Type` is {actual}, but expected 16");
+/// }
+/// ```
+/// So the actual value is rendered in the counterexample when the layout doesn't match.
+pub fn assert_layout(item: TokenStream) -> TokenStream {
+    let LayoutArgs { ty, size, align, fields } = parse_macro_input!(item as LayoutArgs);
+    let ty_name = escape_braces(ty.to_token_stream().to_string());
+    let mut checks = vec![];
+    if let Some(size) = size {
+        let msg = format!("size of `{ty_name}` is {{actual}}, but expected {}", expr_str(&size));
+        checks.push(check(quote!(::core::mem::size_of::<#ty>()), &size, &msg));
+    }
+    if let Some(align) = align {
+        let msg =
+            format!("alignment of `{ty_name}` is {{actual}}, but expected {}", expr_str(&align));
+        checks.push(check(quote!(::core::mem::align_of::<#ty>()), &align, &msg));
+    }
+    for (member, offset) in fields {
+        let field_name = escape_braces(member.to_token_stream().to_string());
+        let msg = format!(
+            "offset of `{ty_name}::{field_name}` is {{actual}}, but expected {}",
+            expr_str(&offset)
+        );
+        checks.push(check(quote!(::core::mem::offset_of!(#ty, #member)), &offset, &msg));
+    }
+    quote!({ #(#checks)* }).into()
+}
+
+fn check(actual: TokenStream2, expected: &Expr, msg: &str) -> TokenStream2 {
+    let msg = LitStr::new(msg, proc_macro2::Span::call_site());
+    quote!({
+        let actual: usize = #actual;
+        if actual != (#expected) {
+            kani::internal::capture_value(&actual);
+        }
+        kani::assert(actual == (#expected), #msg);
+    })
+}
+
+fn expr_str(expr: &Expr) -> String {
+    escape_braces(expr.to_token_stream().to_string())
+}
+
+/// Escape the braces of a string that will be used in a format string.
+fn escape_braces(s: String) -> String {
+    s.replace('{', "{{").replace('}', "}}")
+}
@@ -0,0 +1,111 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::state_machine]` attribute, which declares the transitions that
+//! the states of an enum may perform.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, ItemEnum, Token, bracketed, parse_macro_input};
+
+/// The parsed arguments of `#[kani::state_machine(transitions = [A => B, _ => A])]`.
+struct StateMachineArgs {
+    transitions: Vec<Transition>,
+}
+
+/// A declared transition, where `None` stands for any state.
+struct Transition {
+    from: Option<Ident>,
+    to: Option<Ident>,
+}
+
+impl Parse for StateMachineArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        if key != "transitions" {
+            return Err(syn::Error::new(key.span(), "expected `transitions = [...]`"));
+        }
+        input.parse::<Token![=]>()?;
+        let content;
+        let brackets = bracketed!(content in input);
+        let transitions = Punctuated::<Transition, Token![,]>::parse_terminated(&content)?;
+        if transitions.is_empty() {
+            return Err(syn::Error::new(
+                brackets.span.join(),
+                "expected at least one transition, e.g., `Idle => Running`",
+            ));
+        }
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument after the transitions"));
+        }
+        Ok(StateMachineArgs { transitions: transitions.into_iter().collect() })
+    }
+}
+
+impl Parse for Transition {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let from = parse_state(input)?;
+        input.parse::<Token![=>]>()?;
+        let to = parse_state(input)?;
+        Ok(Transition { from, to })
+    }
+}
+
+/// Parse the name of a variant, or `_` for any variant.
+fn parse_state(input: ParseStream) -> syn::Result<Option<Ident>> {
+    if input.parse::<Option<Token![_]>>()?.is_some() { Ok(None) } else { Ok(Some(input.parse()?)) }
+}
+
+/// Keep the annotated enum, and implement `kani::StateMachine` for it:
+/// ```ignore
+/// impl kani::StateMachine for State {
+///     fn is_declared_transition(from: &Self, to: &Self) -> bool {
+///         matches!((from, to), (Self::A { .. }, Self::B { .. }) | (_, Self::A { .. }))
+///     }
+/// }
+/// ```
+/// The patterns ignore the fields of the variants, so the transitions only depend on the
+/// variants of the states.
+pub fn state_machine(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let StateMachineArgs { transitions } = parse_macro_input!(attr as StateMachineArgs);
+    let item_enum = parse_macro_input!(item as ItemEnum);
+    let patterns = transitions
+        .iter()
+        .map(|Transition { from, to }| {
+            let from = state_pattern(&item_enum, from)?;
+            let to = state_pattern(&item_enum, to)?;
+            Ok(quote!((#from, #to)))
+        })
+        .collect::<syn::Result<Vec<_>>>();
+    let patterns = match patterns {
+        Ok(patterns) => patterns,
+        Err(err) => return err.into_compile_error().into(),
+    };
+    let name = &item_enum.ident;
+    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+    quote!(
+        #item_enum
+
+        impl #impl_generics kani::StateMachine for #name #ty_generics #where_clause {
+            fn is_declared_transition(from: &Self, to: &Self) -> bool {
+                matches!((from, to), #(#patterns)|*)
+            }
+        }
+    )
+    .into()
+}
+
+/// The pattern that matches a state, which must be a variant of the enum.
+fn state_pattern(item_enum: &ItemEnum, state: &Option<Ident>) -> syn::Result<TokenStream2> {
+    let Some(variant) = state else { return Ok(quote!(_)) };
+    if !item_enum.variants.iter().any(|candidate| candidate.ident == *variant) {
+        return Err(syn::Error::new(
+            variant.span(),
+            format!("`{variant}` is not a variant of `{}`", item_enum.ident),
+        ));
+    }
+    Ok(quote!(Self::#variant { .. }))
+}
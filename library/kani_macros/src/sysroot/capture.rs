@@ -0,0 +1,54 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Capture the values of the variables interpolated in an assertion message, so they can be
+//! rendered from the counterexample.
+
+use proc_macro::TokenStream;
+use proc_macro2::Ident;
+use quote::quote;
+use syn::LitStr;
+
+/// Expand `capture_values!("message {x} {y:?}")` to:
+/// ```ignore
+/// {
+///     kani::internal::capture_value(&x);
+///     kani::internal::capture_value(&y);
+/// }
+/// ```
+///
+/// Nothing is captured if the argument is not a string literal, or if any of the format
+/// arguments is not a variable name, e.g.: `{}` or `{0}`.
+pub fn capture_values(item: TokenStream) -> TokenStream {
+    let Ok(msg) = syn::parse::<LitStr>(item) else {
+        return quote!({}).into();
+    };
+    let captures = captured_args(&msg).unwrap_or_default();
+    quote!({
+        #(kani::internal::capture_value(&#captures);)*
+    })
+    .into()
+}
+
+/// Collect the identifiers inlined in the format string, in the order they appear.
+fn captured_args(msg: &LitStr) -> Option<Vec<Ident>> {
+    let value = msg.value();
+    let mut captures = vec![];
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+            }
+            '{' => {
+                let arg: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let name = arg.split(':').next().unwrap().trim();
+                syn::parse_str::<Ident>(name).ok()?;
+                // Use the span of the message so the variable is resolved in the user's scope.
+                captures.push(Ident::new(name, msg.span()));
+            }
+            _ => {}
+        }
+    }
+    Some(captures)
+}
@@ -36,8 +36,10 @@ pub fn handle_untouched(&mut self) {
         // sees those attributes and can use them to determine
         // `function_state`.
         let ItemFn { attrs, vis, sig, block } = &self.annotated_fn;
+        let clause_attr = self.clause_attr();
         self.output.extend(quote!(
             #(#attrs)*
+            #clause_attr
             #[kanitool::recursion_check = #recursion_name]
             #[kanitool::checked_with = #check_name]
             #[kanitool::replaced_with = #replace_name]
@@ -94,6 +96,9 @@ pub fn handle_expanded(&mut self) {
         let check_closure = expect_closure_in_match(&mut block.stmts, "check");
         self.expand_check(check_closure);
 
+        // Appending the clause keeps the clause attributes in the order they were declared, since
+        // the contract attributes are expanded outside in.
+        annotated_fn.attrs.push(self.clause_attr());
         self.output.extend(quote!(#annotated_fn));
     }
 
@@ -0,0 +1,283 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for contracts on trait methods, which are inherited by the methods that implement them.
+//!
+//! A required trait method cannot have a regular contract since it has no body. Instead, we
+//! collect all of its `requires` and `ensures` clauses into two hidden provided methods of the
+//! trait, which evaluate the clauses for a given set of arguments. For example:
+//!
+//! ```ignore
+//! trait Shape {
+//!     #[kani::requires(scale > 0)]
+//!     #[kani::ensures(|result: &u32| *result > 0)]
+//!     fn area(&self, scale: u32) -> u32;
+//! }
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! trait Shape {
+//!     fn area(&self, scale: u32) -> u32;
+//!
+//!     #[doc(hidden)]
+//!     fn __kani_trait_requires_area(&self, scale: &u32) -> bool where Self: Sized {
+//!         let scale = kani::internal::untracked_deref(scale);
+//!         let holds = true && (scale > 0);
+//!         core::mem::forget(scale);
+//!         holds
+//!     }
+//!
+//!     #[doc(hidden)]
+//!     fn __kani_trait_ensures_area(&self, scale: &u32, result_kani_internal: &u32) -> bool
+//!     where Self: Sized {
+//!         let scale = kani::internal::untracked_deref(scale);
+//!         let holds = true && kani::internal::apply_closure(|result: &u32| *result > 0, result_kani_internal);
+//!         core::mem::forget(scale);
+//!         holds
+//!     }
+//! }
+//! ```
+//!
+//! An implementation opts into the contract with `#[kani::refines(Shape)]`, which turns the trait
+//! clauses into regular contract clauses of the method. The method may weaken the `requires` and
+//! strengthen the `ensures` clauses with its own, in which case the contract checks that:
+//! 1. Every input accepted by the trait method is accepted by the override. This is asserted
+//!    before assuming the `requires` clauses of the override.
+//! 2. The result of the override satisfies the `ensures` clauses of the trait method whenever the
+//!    input satisfied the `requires` clauses of the trait method.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Expr, ExprClosure, FnArg, ItemFn, Pat, Path, ReturnType, Signature, TraitItemFn,
+    parse_macro_input, parse_quote,
+};
+
+use super::helpers::matches_path;
+use super::{ContractConditionsType, ContractFunctionState, INTERNAL_RESULT_IDENT};
+
+/// Generate the hidden clause methods for a required trait method with a contract.
+///
+/// `first` is the contract attribute being expanded. Since the trait method has no body to
+/// instrument, we also take the contract attributes that follow it, so all clauses are collected
+/// in the same methods. For this reason, the clauses must use the `kani::requires` and
+/// `kani::ensures` paths.
+pub fn trait_contract(
+    first: ContractConditionsType,
+    attr: TokenStream2,
+    mut trait_fn: TraitItemFn,
+) -> TokenStream2 {
+    match trait_contract_impl(first, attr, &mut trait_fn) {
+        Ok(output) => output,
+        Err(e) => e.into_compile_error(),
+    }
+}
+
+fn trait_contract_impl(
+    first: ContractConditionsType,
+    attr: TokenStream2,
+    trait_fn: &mut TraitItemFn,
+) -> syn::Result<TokenStream2> {
+    let mut requires: Vec<Expr> = vec![];
+    let mut ensures: Vec<ExprClosure> = vec![];
+    let mut add_clause = |kind, tokens: TokenStream2, span: Span| -> syn::Result<()> {
+        match kind {
+            ContractConditionsType::Requires => requires.push(syn::parse2(tokens)?),
+            ContractConditionsType::Ensures => ensures.push(syn::parse2(tokens)?),
+            ContractConditionsType::Modifies => {
+                return Err(syn::Error::new(
+                    span,
+                    "`modifies` clauses are not supported on trait methods without a default body",
+                ));
+            }
+        }
+        Ok(())
+    };
+    add_clause(first, attr, trait_fn.sig.ident.span())?;
+    let mut other_attrs = vec![];
+    for attr in std::mem::take(&mut trait_fn.attrs) {
+        match contract_attr_type(&attr) {
+            Some(kind) => add_clause(kind, attr.meta.require_list()?.tokens.clone(), attr.span())?,
+            None => other_attrs.push(attr),
+        }
+    }
+    trait_fn.attrs = other_attrs;
+
+    let args = arg_idents(&trait_fn.sig)?;
+    let deref_args =
+        args.iter().map(|arg| quote!(let #arg = kani::internal::untracked_deref(#arg);));
+    let forget_args = args.iter().map(|arg| quote!(core::mem::forget(#arg);));
+    let deref_args = quote!(#(#deref_args)*);
+    let forget_args = quote!(#(#forget_args)*);
+
+    let requires_sig = clause_sig(&trait_fn.sig, &requires_name(&trait_fn.sig.ident), None);
+    let result = Ident::new(INTERNAL_RESULT_IDENT, Span::call_site());
+    let output = output_type(&trait_fn.sig.output);
+    let ensures_sig = clause_sig(
+        &trait_fn.sig,
+        &ensures_name(&trait_fn.sig.ident),
+        Some(parse_quote!(#result: &#output)),
+    );
+    let ensures =
+        ensures.iter().map(|clause| quote!(kani::internal::apply_closure(#clause, #result)));
+
+    Ok(quote!(
+        #trait_fn
+
+        #[doc(hidden)]
+        #[allow(dead_code, non_snake_case, unused_variables)]
+        #requires_sig {
+            #deref_args
+            let holds = true #(&& (#requires))*;
+            #forget_args
+            holds
+        }
+
+        #[doc(hidden)]
+        #[allow(dead_code, non_snake_case, unused_variables)]
+        #ensures_sig {
+            #deref_args
+            let holds = true #(&& #ensures)*;
+            #forget_args
+            holds
+        }
+    ))
+}
+
+/// Make the method that implements a trait method inherit the contract of the trait method.
+///
+/// The attribute must come before the contract attributes of the method, since it replaces its
+/// `requires` clauses by a check that they are implied by the `requires` clauses of the trait.
+pub fn refines(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let trait_path = parse_macro_input!(attr as Path);
+    let mut item_fn = parse_macro_input!(item as ItemFn);
+    match refines_impl(&trait_path, &mut item_fn) {
+        Ok(attrs) => quote!(#attrs #item_fn).into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+fn refines_impl(trait_path: &Path, item_fn: &mut ItemFn) -> syn::Result<TokenStream2> {
+    if ContractFunctionState::from_attributes(&item_fn.attrs) != ContractFunctionState::Untouched {
+        return Err(syn::Error::new(
+            item_fn.sig.ident.span(),
+            "`#[kani::refines]` must be placed before the contract attributes of the function",
+        ));
+    }
+    let mut own_requires: Vec<Expr> = vec![];
+    let mut other_attrs = vec![];
+    for attr in std::mem::take(&mut item_fn.attrs) {
+        if contract_attr_type(&attr) == Some(ContractConditionsType::Requires) {
+            own_requires.push(attr.parse_args()?);
+        } else {
+            other_attrs.push(attr);
+        }
+    }
+    item_fn.attrs = other_attrs;
+
+    let sig = &item_fn.sig;
+    let span = sig.ident.span();
+    let args = arg_idents(sig)?;
+    let receiver = sig.receiver().map(|_| quote!(&self,));
+    let requires_fn = requires_name(&sig.ident);
+    let ensures_fn = ensures_name(&sig.ident);
+    let trait_requires = quote_spanned!(span=>
+        <Self as #trait_path>::#requires_fn(#receiver #(&#args),*)
+    );
+    let trait_ensures = quote_spanned!(span=>
+        <Self as #trait_path>::#ensures_fn(#receiver #(&#args,)* result)
+    );
+    let method = format!("{}::{}", quote!(#trait_path).to_string().replace(' ', ""), sig.ident);
+
+    let requires = if own_requires.is_empty() {
+        quote_spanned!(span=> #[kani::requires(#trait_requires)])
+    } else {
+        let msg = format!("the override of `{method}` must accept every input of the trait method");
+        quote_spanned!(span=>
+            #[kani::requires({
+                let override_requires = #((#own_requires))&&*;
+                kani::assert(!(#trait_requires) || override_requires, #msg);
+                override_requires
+            })]
+        )
+    };
+    let output = output_type(&sig.output);
+    Ok(quote_spanned!(span=>
+        #requires
+        #[kani::ensures(|result: &#output| !old(#trait_requires) || #trait_ensures)]
+    ))
+}
+
+/// Return the type of contract clause of the given attribute, if any.
+fn contract_attr_type(attr: &Attribute) -> Option<ContractConditionsType> {
+    let path = attr.path();
+    if matches_path(path, &["kani", "requires"]) {
+        Some(ContractConditionsType::Requires)
+    } else if matches_path(path, &["kani", "ensures"]) {
+        Some(ContractConditionsType::Ensures)
+    } else if matches_path(path, &["kani", "modifies"]) {
+        Some(ContractConditionsType::Modifies)
+    } else {
+        None
+    }
+}
+
+fn requires_name(method: &Ident) -> Ident {
+    format_ident!("__kani_trait_requires_{method}")
+}
+
+fn ensures_name(method: &Ident) -> Ident {
+    format_ident!("__kani_trait_ensures_{method}")
+}
+
+/// The names of the arguments of the method, excluding the receiver.
+///
+/// The clause methods take the arguments by reference, so each argument must be bound to an
+/// identifier.
+fn arg_idents(sig: &Signature) -> syn::Result<Vec<Ident>> {
+    sig.inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(typed) => Some(match typed.pat.as_ref() {
+                Pat::Ident(pat) if pat.subpat.is_none() => Ok(pat.ident.clone()),
+                pat => Err(syn::Error::new(
+                    pat.span(),
+                    "the arguments of a method with an inherited contract must be identifiers",
+                )),
+            }),
+        })
+        .collect()
+}
+
+/// The signature of a clause method, which takes the receiver by reference, the arguments of
+/// the trait method by reference, and the `extra` argument, if any.
+fn clause_sig(sig: &Signature, name: &Ident, extra: Option<FnArg>) -> Signature {
+    let mut clause_sig = sig.clone();
+    clause_sig.ident = name.clone();
+    clause_sig.asyncness = None;
+    clause_sig.output = parse_quote!(-> bool);
+    let receiver = sig.receiver().map(|_| -> FnArg { parse_quote!(&self) });
+    let args = sig.inputs.iter().filter_map(|arg| match arg {
+        FnArg::Receiver(_) => None,
+        FnArg::Typed(typed) => {
+            let pat = &typed.pat;
+            let ty = &typed.ty;
+            Some(parse_quote!(#pat: &#ty))
+        }
+    });
+    clause_sig.inputs = receiver.into_iter().chain(args).chain(extra).collect();
+    clause_sig.generics.make_where_clause().predicates.push(parse_quote!(Self: Sized));
+    clause_sig
+}
+
+fn output_type(output: &ReturnType) -> syn::Type {
+    match output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    }
+}
@@ -3,14 +3,18 @@
 
 //! Initialization routine for the contract handler
 
+use std::collections::{HashMap, HashSet};
+
 use proc_macro::{Diagnostic, TokenStream};
-use proc_macro2::TokenStream as TokenStream2;
-use syn::ItemFn;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+use syn::{Expr, ExprCall, ExprClosure, ExprPath, FnArg, ItemFn, Pat, Type};
 
 use super::{
     ContractConditionsData, ContractConditionsHandler, ContractConditionsType,
     ContractFunctionState,
-    helpers::{chunks_by, is_token_stream_2_comma, matches_path},
+    helpers::{chunks_by, is_token_stream_2_comma, matches_path, pat_to_bindings},
 };
 
 impl<'a> TryFrom<&'a syn::Attribute> for ContractFunctionState {
@@ -62,7 +66,9 @@ pub fn new(
                 ContractConditionsData::Requires { attr: syn::parse(attr)? }
             }
             ContractConditionsType::Ensures => {
-                ContractConditionsData::Ensures { attr: syn::parse(attr)? }
+                let attr = syn::parse(attr)?;
+                lint_poststate_reads(&attr, annotated_fn);
+                ContractConditionsData::Ensures { attr }
             }
             ContractConditionsType::Modifies => {
                 ContractConditionsData::new_modifies(attr, &mut output)
@@ -107,3 +113,107 @@ fn new_modifies(attr: TokenStream, output: &mut TokenStream2) -> Self {
         ContractConditionsData::Modifies { attr }
     }
 }
+
+/// Warn about `ensures` clauses that read a `&mut` argument, but never refer to its value before
+/// the call via `old(...)`.
+///
+/// Such clauses can only observe the value after the function has mutated it, which is rarely
+/// what the author wants. Users can opt out with `#[kani::allow_poststate_read]`.
+fn lint_poststate_reads(attr: &ExprClosure, annotated_fn: &ItemFn) {
+    let allowed = annotated_fn.attrs.iter().any(|attr| {
+        matches_path(attr.path(), &["kani", "allow_poststate_read"])
+            || matches_path(attr.path(), &["kanitool", "allow_poststate_read"])
+    });
+    if allowed {
+        return;
+    }
+    let mut visitor = PoststateVisitor {
+        mut_args: mut_ref_args(annotated_fn),
+        in_old: false,
+        snapshotted: HashSet::new(),
+        post_reads: HashMap::new(),
+    };
+    // The closure parameters (e.g. `result`) shadow the function arguments.
+    for (_, ident) in attr.inputs.iter().flat_map(pat_to_bindings) {
+        visitor.mut_args.remove(ident);
+    }
+    visitor.visit_expr(&attr.body);
+
+    let mut unsnapshotted: Vec<_> = visitor
+        .post_reads
+        .into_iter()
+        .filter(|(ident, _)| !visitor.snapshotted.contains(ident))
+        .collect();
+    unsnapshotted.sort_by_key(|(ident, _)| ident.to_string());
+    for (ident, span) in unsnapshotted {
+        span.unwrap()
+            .warning(format!(
+                "`ensures` clause reads mutable argument `{ident}` after the function returns, \
+                but never refers to its original value"
+            ))
+            .help(
+                "wrap the read in `old(...)` to refer to the value before the call, or add \
+                `#[kani::allow_poststate_read]` if reading the new value is intended",
+            )
+            .emit();
+    }
+}
+
+/// Collect the names of all arguments of `annotated_fn` that are mutable references.
+fn mut_ref_args(annotated_fn: &ItemFn) -> HashSet<Ident> {
+    annotated_fn
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Receiver(receiver)
+                if receiver.reference.is_some() && receiver.mutability.is_some() =>
+            {
+                Some(Ident::new("self", Span::call_site()))
+            }
+            FnArg::Typed(typed) => match (typed.pat.as_ref(), typed.ty.as_ref()) {
+                (Pat::Ident(pat), Type::Reference(reference)) if reference.mutability.is_some() => {
+                    Some(pat.ident.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Record which mutable arguments are read inside and outside of `old(...)` calls.
+struct PoststateVisitor {
+    mut_args: HashSet<Ident>,
+    in_old: bool,
+    snapshotted: HashSet<Ident>,
+    /// The first location where each argument was read outside of `old`.
+    post_reads: HashMap<Ident, Span>,
+}
+
+impl<'ast> Visit<'ast> for PoststateVisitor {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        let is_old = matches!(call.func.as_ref(), Expr::Path(ExprPath { qself: None, path, .. })
+            if matches_path(path, &["old"]));
+        if is_old && !self.in_old {
+            self.in_old = true;
+            syn::visit::visit_expr_call(self, call);
+            self.in_old = false;
+        } else {
+            syn::visit::visit_expr_call(self, call);
+        }
+    }
+
+    fn visit_expr_path(&mut self, expr: &'ast ExprPath) {
+        if let Some(ident) = expr.path.get_ident() {
+            if expr.qself.is_none() && self.mut_args.contains(ident) {
+                if self.in_old {
+                    self.snapshotted.insert(ident.clone());
+                } else {
+                    self.post_reads.entry(ident.clone()).or_insert_with(|| expr.span());
+                }
+            }
+        }
+        syn::visit::visit_expr_path(self, expr);
+    }
+}
@@ -45,6 +45,9 @@
 //! Note: We place marker attributes at the bottom of the attribute stack (innermost),
 //! otherwise they would not be visible to the future macro expansions.
 //!
+//! Each contract attribute also records its clause on the function, e.g.
+//! `#[kanitool::requires = "x < 10"]`, so the compiler can report the contract of a function.
+//!
 //! ## Check closure
 //!
 //! Generates a `__kani_<fn_name>_check` closure that assumes preconditions
@@ -128,10 +131,12 @@
 //!
 //! Turns into
 //! ```
+//! #[kanitool::requires = "divisor != 0"]
 //! #[kanitool::recursion_check = "__kani_recursion_check_div"]
 //! #[kanitool::checked_with = "__kani_check_div"]
 //! #[kanitool::replaced_with = "__kani_replace_div"]
 //! #[kanitool::modifies_wrapper = "__kani_modifies_div"]
+//! #[kanitool::ensures = "|result : &u32| *result <= dividend"]
 //! fn div(dividend: u32, divisor: u32) -> u32 {
 //!     #[inline(never)]
 //!     #[kanitool::fn_marker = "kani_register_contract"]
@@ -261,10 +266,14 @@
 //! This expands to
 //!
 //! ```
+//! #[kanitool::ensures = "|result| old(*ptr + 1) == *ptr"]
 //! #[kanitool::recursion_check = "__kani_recursion_check_modify"]
 //! #[kanitool::checked_with = "__kani_check_modify"]
 //! #[kanitool::replaced_with = "__kani_replace_modify"]
 //! #[kanitool::modifies_wrapper = "__kani_modifies_modify"]
+//! #[kanitool::ensures = "|result| old(*ptr + 1) == *ptr"]
+//! #[kanitool::requires = "*ptr < 100"]
+//! #[kanitool::modifies = "ptr"]
 //! fn modify(ptr: &mut u32) {
 //!     #[inline(never)]
 //!     #[kanitool::fn_marker = "kani_register_contract"]
@@ -393,18 +402,27 @@
 //! ```
 
 use proc_macro::TokenStream;
-use proc_macro2::{Ident, TokenStream as TokenStream2};
+use proc_macro2::{Ident, TokenStream as TokenStream2, TokenTree};
 use quote::quote;
-use syn::{Expr, ExprClosure, ItemFn, parse_macro_input, parse_quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    Attribute, Expr, ExprClosure, ItemFn, LitStr, Token, TraitItemFn, parse_macro_input,
+    parse_quote,
+};
 
 mod bootstrap;
 mod check;
 #[macro_use]
 mod helpers;
+mod inherit;
 mod initialize;
+mod named;
 mod replace;
 mod shared;
 
+pub use inherit::refines;
+pub use named::contract;
+
 const INTERNAL_RESULT_IDENT: &str = "result_kani_internal";
 
 pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -445,17 +463,59 @@ pub fn $name(attr: TokenStream, item: TokenStream) -> TokenStream {
 passthrough!(stub_verified, false);
 
 pub fn proof_for_contract(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let args = proc_macro2::TokenStream::from(attr);
+    let (target, contract_name) = split_contract_name(TokenStream2::from(attr));
+    let contract_name = match contract_name.map(syn::parse2::<ContractNameArg>).transpose() {
+        Ok(arg) => {
+            arg.map(|ContractNameArg { name }| quote!(#[kanitool::checked_contract = #name]))
+        }
+        Err(e) => return e.into_compile_error().into(),
+    };
     let mut fn_item = parse_macro_input!(item as ItemFn);
     fn_item.block.stmts.insert(0, parse_quote!(kani::internal::init_contracts();));
     quote!(
         #[allow(dead_code)]
-        #[kanitool::proof_for_contract = stringify!(#args)]
+        #[kanitool::proof_for_contract = stringify!(#target)]
+        #contract_name
         #fn_item
     )
     .into()
 }
 
+/// Split the arguments of `proof_for_contract` into the target path and the optional
+/// `contract = "name"` argument, which selects one of the named contracts of the target.
+///
+/// Paths may contain commas, e.g. `<Foo as Bar<A, B>>::baz`, so we look for the `contract` key.
+fn split_contract_name(args: TokenStream2) -> (TokenStream2, Option<TokenStream2>) {
+    let tokens: Vec<_> = args.into_iter().collect();
+    let split = tokens.windows(3).position(|window| {
+        matches!(&window[0], TokenTree::Punct(p) if p.as_char() == ',')
+            && matches!(&window[1], TokenTree::Ident(key) if key == "contract")
+            && matches!(&window[2], TokenTree::Punct(p) if p.as_char() == '=')
+    });
+    match split {
+        Some(idx) => (
+            tokens[..idx].iter().cloned().collect(),
+            Some(tokens[idx + 1..].iter().cloned().collect()),
+        ),
+        None => (tokens.into_iter().collect(), None),
+    }
+}
+
+/// The `contract = "name"` argument of `proof_for_contract`.
+struct ContractNameArg {
+    name: LitStr,
+}
+
+impl Parse for ContractNameArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+        let name = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(ContractNameArg { name })
+    }
+}
+
 /// Classifies the state a function is in the contract handling pipeline.
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum ContractFunctionState {
@@ -531,6 +591,23 @@ fn dispatch_on(mut self, state: ContractFunctionState) -> TokenStream2 {
         }
         self.output
     }
+
+    /// The attribute that records this clause on the function, so the compiler can report the
+    /// contract of the function, e.g. `#[kanitool::requires = "x < 10"]`.
+    fn clause_attr(&self) -> Attribute {
+        let attr_copy = &self.attr_copy;
+        match self.condition_type {
+            ContractConditionsData::Requires { .. } => {
+                parse_quote!(#[kanitool::requires = stringify!(#attr_copy)])
+            }
+            ContractConditionsData::Ensures { .. } => {
+                parse_quote!(#[kanitool::ensures = stringify!(#attr_copy)])
+            }
+            ContractConditionsData::Modifies { .. } => {
+                parse_quote!(#[kanitool::modifies = stringify!(#attr_copy)])
+            }
+        }
+    }
 }
 
 /// The main meat of handling requires/ensures contracts.
@@ -543,7 +620,16 @@ fn contract_main(
     is_requires: ContractConditionsType,
 ) -> TokenStream {
     let attr_copy = TokenStream2::from(attr.clone());
-    let mut item_fn = parse_macro_input!(item as ItemFn);
+    let mut item_fn = match syn::parse::<ItemFn>(item.clone()) {
+        Ok(item_fn) => item_fn,
+        // A required trait method has no body, so its contract is inherited by its implementations.
+        Err(e) => match syn::parse::<TraitItemFn>(item) {
+            Ok(trait_fn) if trait_fn.default.is_none() => {
+                return inherit::trait_contract(is_requires, attr_copy, trait_fn).into();
+            }
+            _ => return e.into_compile_error().into(),
+        },
+    };
     let function_state = ContractFunctionState::from_attributes(&item_fn.attrs);
     let handler = match ContractConditionsHandler::new(is_requires, attr, &mut item_fn, attr_copy) {
         Ok(handler) => handler,
@@ -0,0 +1,171 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Support for named contracts, which allow a function to have multiple independent contracts.
+//!
+//! For each named contract, we generate a hidden copy of the annotated function that is annotated
+//! with the clauses of the contract using the regular contract attributes. For example:
+//!
+//! ```ignore
+//! #[kani::contract(name = "small", requires = x < 10, ensures = |result: &u32| *result < 20)]
+//! fn double(x: u32) -> u32 {
+//!     x * 2
+//! }
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! #[kanitool::named_contract = "small"]
+//! fn double(x: u32) -> u32 {
+//!     x * 2
+//! }
+//!
+//! #[kani::requires(x < 10)]
+//! #[kani::ensures(|result: &u32| *result < 20)]
+//! #[kanitool::is_contract_generated(named)]
+//! #[doc(hidden)]
+//! #[allow(dead_code, non_snake_case)]
+//! fn __kani_contract_double_small(x: u32) -> u32 {
+//!     x * 2
+//! }
+//! ```
+//!
+//! A harness annotated with `#[kani::proof_for_contract(double, contract = "small")]` checks the
+//! contract of the copy, and the compiler redirects every call to `double` to the copy.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Block, Expr, ItemFn, LitStr, Stmt, Token, bracketed, parse_macro_input};
+
+use super::ContractFunctionState;
+use super::helpers::matches_path;
+
+/// The prefix of the functions generated for each named contract.
+const NAMED_CONTRACT_PREFIX: &str = "__kani_contract";
+
+/// The parsed arguments of `#[kani::contract(name = "...", <clause> = ...)]`.
+struct NamedContractArgs {
+    name: LitStr,
+    /// The clauses of the contract in the order they were declared.
+    clauses: Vec<TokenStream2>,
+}
+
+impl Parse for NamedContractArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut name = None;
+        let mut clauses = vec![];
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "name" if name.is_none() => {
+                    let lit: LitStr = input.parse()?;
+                    if syn::parse_str::<Ident>(&lit.value()).is_err() {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "the name of a contract must be a valid identifier",
+                        ));
+                    }
+                    name = Some(lit);
+                }
+                "name" => return Err(syn::Error::new(key.span(), "duplicated `name` argument")),
+                "requires" => {
+                    let expr: Expr = input.parse()?;
+                    clauses.push(quote!(#[kani::requires(#expr)]));
+                }
+                "ensures" => {
+                    let expr: Expr = input.parse()?;
+                    clauses.push(quote!(#[kani::ensures(#expr)]));
+                }
+                "modifies" => {
+                    let content;
+                    bracketed!(content in input);
+                    let targets = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+                    let targets = targets.iter();
+                    clauses.push(quote!(#[kani::modifies(#(#targets),*)]));
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!(
+                            "unknown argument `{key}`, expected `name`, `requires`, `ensures` or \
+                            `modifies`"
+                        ),
+                    ));
+                }
+            }
+            if input.parse::<Option<Token![,]>>()?.is_none() && !input.is_empty() {
+                return Err(input.error("expected `,`"));
+            }
+        }
+        let Some(name) = name else {
+            return Err(input.error("missing the `name` of the contract"));
+        };
+        if clauses.is_empty() {
+            return Err(syn::Error::new(
+                name.span(),
+                "expected at least one `requires`, `ensures` or `modifies` clause",
+            ));
+        }
+        Ok(NamedContractArgs { name, clauses })
+    }
+}
+
+pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let NamedContractArgs { name, clauses } = parse_macro_input!(attr as NamedContractArgs);
+    let item_fn = parse_macro_input!(item as ItemFn);
+    let ItemFn { attrs, vis, sig, block } = &item_fn;
+
+    let mut contract_sig = sig.clone();
+    contract_sig.ident = Ident::new(
+        &format!("{NAMED_CONTRACT_PREFIX}_{}_{}", sig.ident, name.value()),
+        sig.ident.span(),
+    );
+    let contract_block = original_block(&item_fn);
+    // Only keep the attributes that affect how the contract is checked. Any other contract
+    // attribute that is still to be expanded belongs to the original function.
+    let recursion_attrs = attrs.iter().filter(|attr| is_recursion_attr(attr));
+
+    quote!(
+        #(#attrs)*
+        #[kanitool::named_contract = #name]
+        #vis #sig #block
+
+        #(#clauses)*
+        #(#recursion_attrs)*
+        #[kanitool::is_contract_generated(named)]
+        #[doc(hidden)]
+        #[allow(dead_code, non_snake_case)]
+        #vis #contract_sig #contract_block
+    )
+    .into()
+}
+
+/// Return the original body of the annotated function.
+///
+/// If other contract attributes were already expanded, the original body is in the fallback arm
+/// of the contract mode `match`.
+fn original_block(item_fn: &ItemFn) -> Block {
+    if ContractFunctionState::from_attributes(&item_fn.attrs) == ContractFunctionState::Untouched {
+        return (*item_fn.block).clone();
+    }
+    item_fn
+        .block
+        .stmts
+        .iter()
+        .find_map(|stmt| {
+            let Stmt::Expr(Expr::Match(match_expr), ..) = stmt else { return None };
+            let Expr::Block(fallback) = match_expr.arms.last()?.body.as_ref() else { return None };
+            Some(fallback.block.clone())
+        })
+        .expect("Internal Failure: Expected to find the original body of the function")
+}
+
+fn is_recursion_attr(attr: &Attribute) -> bool {
+    matches_path(attr.path(), &["kani", "recursion"])
+        || matches_path(attr.path(), &["kanitool", "recursion"])
+}
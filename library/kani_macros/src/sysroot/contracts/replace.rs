@@ -15,11 +15,15 @@
 };
 
 impl<'a> ContractConditionsHandler<'a> {
-    /// Create initial set of replace statements which is the return havoc.
+    /// Create initial set of replace statements, which resets the record of the `modifies`
+    /// targets used by `kani::assume_unchanged!` and havocs the return value.
     fn initial_replace_stmts(&self) -> Vec<syn::Stmt> {
         let return_type = return_type_to_type(&self.annotated_fn.sig.output);
         let result = Ident::new(INTERNAL_RESULT_IDENT, Span::call_site());
-        vec![syn::parse_quote!(let #result : #return_type = kani::any_modifies();)]
+        vec![
+            syn::parse_quote!(kani::internal::clear_modifies_targets();),
+            syn::parse_quote!(let #result : #return_type = kani::any_modifies();),
+        ]
     }
 
     /// Split an existing replace body of the form
@@ -100,7 +104,11 @@ fn expand_replace_body(&self, before: &[Stmt], after: &[Stmt]) -> TokenStream {
                 let result = Ident::new(INTERNAL_RESULT_IDENT, Span::call_site());
                 quote!({
                     #(#before)*
-                    #(unsafe{kani::internal::write_any(kani::internal::Pointer::assignable(kani::internal::untracked_deref(&#attr)))};)*
+                    #(unsafe{
+                        let target = kani::internal::Pointer::assignable(kani::internal::untracked_deref(&#attr));
+                        kani::internal::record_modifies_target(target);
+                        kani::internal::write_any(target)
+                    };)*
                     #(#after)*
                     #result
                 })
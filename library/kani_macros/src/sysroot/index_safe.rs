@@ -0,0 +1,132 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::proof_index_safe(Type, bound = ..., index = ...)]` attribute.
+//!
+//! The attribute turns the function into a harness that generates a nondeterministic value of
+//! `Type` and a nondeterministic index that satisfies the bound, and checks that indexing the value
+//! doesn't panic, before running the body of the function. It also generates a second harness,
+//! with the `_out_of_bounds` suffix, that checks that indexing with an index that doesn't satisfy
+//! the bound always panics:
+//!
+//! ```ignore
+//! #[kani::proof_index_safe(Ring, bound = |ring: &Ring, index: &usize| *index < ring.len())]
+//! fn check_ring_index() {}
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! #[kanitool::proof]
+//! #[kanitool::proof_index_safe = "Ring, bound = |ring: &Ring, index: &usize| *index < ring.len()"]
+//! fn check_ring_index() {
+//!     {
+//!         kani::internal::check_index_in_bounds::<Ring, usize, _>(
+//!             |ring: &Ring, index: &usize| *index < ring.len(),
+//!         );
+//!     }
+//! }
+//!
+//! #[kanitool::proof]
+//! #[kanitool::proof_index_safe = "Ring, bound = |ring: &Ring, index: &usize| *index < ring.len()"]
+//! #[kanitool::should_panic]
+//! fn check_ring_index_out_of_bounds() {
+//!     {
+//!         kani::internal::check_index_out_of_bounds::<Ring, usize, _>(
+//!             |ring: &Ring, index: &usize| *index < ring.len(),
+//!         );
+//!     }
+//! }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Ident, ItemFn, Token, Type, parse_macro_input, parse_quote};
+
+use super::proof_with_check;
+
+/// The parsed arguments of `#[kani::proof_index_safe(Type, bound = ..., index = ...)]`.
+struct IndexSafeArgs {
+    ty: Type,
+    bound: Expr,
+    index: Type,
+}
+
+impl Parse for IndexSafeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        let mut bound = None;
+        let mut index = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let duplicated = if key == "bound" {
+                bound.replace(input.parse()?).is_some()
+            } else if key == "index" {
+                index.replace(input.parse()?).is_some()
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown argument `{key}`, expected `bound` or `index`"),
+                ));
+            };
+            if duplicated {
+                return Err(syn::Error::new(key.span(), format!("duplicated `{key}`")));
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument, expected `bound` or `index`"));
+        }
+        let Some(bound) = bound else {
+            return Err(input.error(
+                "missing `bound` argument, e.g., `bound = |value: &Type, index: &usize| ...`",
+            ));
+        };
+        Ok(IndexSafeArgs { ty, bound, index: index.unwrap_or_else(|| parse_quote!(usize)) })
+    }
+}
+
+pub fn proof_index_safe(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = TokenStream2::from(attr.clone());
+    let IndexSafeArgs { ty, bound, index } = parse_macro_input!(attr as IndexSafeArgs);
+    let fn_item = parse_macro_input!(item as ItemFn);
+    if !fn_item.sig.inputs.is_empty() {
+        return syn::Error::new_spanned(
+            &fn_item.sig.inputs,
+            "`#[kani::proof_index_safe]` cannot be applied to functions that take arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let attrs = &fn_item.attrs;
+    let vis = &fn_item.vis;
+    let out_of_bounds = format_ident!("{}_out_of_bounds", fn_item.sig.ident);
+    let out_of_bounds_fn: ItemFn = parse_quote!(
+        #[kanitool::should_panic]
+        #(#attrs)*
+        #vis fn #out_of_bounds() {}
+    );
+    let out_of_bounds_check =
+        quote!(kani::internal::check_index_out_of_bounds::<#ty, #index, _>(#bound););
+    let in_bounds_check = quote!(kani::internal::check_index_in_bounds::<#ty, #index, _>(#bound););
+    let in_bounds = TokenStream2::from(proof_with_check(
+        "proof_index_safe",
+        args.clone(),
+        in_bounds_check,
+        fn_item,
+    ));
+    let out_of_bounds = TokenStream2::from(proof_with_check(
+        "proof_index_safe",
+        args,
+        out_of_bounds_check,
+        out_of_bounds_fn,
+    ));
+    quote!(
+        #in_bounds
+
+        #out_of_bounds
+    )
+    .into()
+}
@@ -0,0 +1,95 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::proof_lossless(From<Src> for Dst, assume = ...)]` attribute.
+//!
+//! The attribute turns the function into a harness that generates a nondeterministic value of
+//! `Src`, converts it to `Dst` with `From`, and checks that converting the result back to `Src`
+//! with `TryInto` succeeds and produces the original value, before running the body of the
+//! function:
+//!
+//! ```ignore
+//! #[kani::proof_lossless(From<u8> for u16)]
+//! fn check_u8_to_u16() {}
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! #[kanitool::proof]
+//! #[kanitool::proof_lossless = "From<u8> for u16"]
+//! fn check_u8_to_u16() {
+//!     {
+//!         kani::internal::check_lossless::<u8, u16, _>(|_: &u8| true);
+//!     }
+//! }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Ident, ItemFn, Token, Type, parse_macro_input};
+
+use super::proof_with_check;
+
+/// The parsed arguments of `#[kani::proof_lossless(From<Src> for Dst, assume = ...)]`.
+struct LosslessArgs {
+    src: Type,
+    dst: Type,
+    assume: Option<Expr>,
+}
+
+impl Parse for LosslessArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let trait_name: Ident = input.parse()?;
+        if trait_name != "From" {
+            return Err(syn::Error::new(
+                trait_name.span(),
+                format!("expected `From<Src> for Dst`, but found `{trait_name}`"),
+            ));
+        }
+        input.parse::<Token![<]>()?;
+        let src = input.parse()?;
+        input.parse::<Token![>]>()?;
+        input.parse::<Token![for]>()?;
+        let dst = input.parse()?;
+        let mut assume = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            if key != "assume" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown argument `{key}`, expected `assume`"),
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            assume = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument, expected `assume`"));
+        }
+        Ok(LosslessArgs { src, dst, assume })
+    }
+}
+
+pub fn proof_lossless(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = TokenStream2::from(attr.clone());
+    let LosslessArgs { src, dst, assume } = parse_macro_input!(attr as LosslessArgs);
+    let fn_item = parse_macro_input!(item as ItemFn);
+    if !fn_item.sig.inputs.is_empty() {
+        return syn::Error::new_spanned(
+            &fn_item.sig.inputs,
+            "`#[kani::proof_lossless]` cannot be applied to functions that take arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let assume: TokenStream2 = match assume {
+        Some(assume) => quote!(#assume),
+        None => quote!(|_: &#src| true),
+    };
+    let check = quote!(kani::internal::check_lossless::<#src, #dst, _>(#assume););
+    proof_with_check("proof_lossless", args, check, fn_item)
+}
@@ -0,0 +1,130 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::proof_roundtrip(Type, format = ..., compare = ...)]` attribute.
+//!
+//! The attribute turns the function into a harness that generates a nondeterministic value of
+//! `Type`, serializes it, deserializes the result, and checks that the deserialized value is equal
+//! to the original one, before running the body of the function:
+//!
+//! ```ignore
+//! #[kani::proof_roundtrip(Point, format = Json)]
+//! fn check_point() {}
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! #[kanitool::proof]
+//! #[kanitool::proof_roundtrip = "Point, format = Json"]
+//! fn check_point() {
+//!     {
+//!         kani::roundtrip::check_roundtrip::<Point, _, _, _>(
+//!             |value: &Point| ::serde_json::to_vec(value),
+//!             |encoded: &_| ::serde_json::from_slice::<Point>(encoded),
+//!             |original: &Point, decoded: &Point| original == decoded,
+//!         );
+//!     }
+//! }
+//! ```
+//!
+//! The default format is an in-memory format that is defined in the harness by
+//! `kani::in_memory_format!()`, since the Kani library cannot depend on the `serde` crate of the
+//! code under verification.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, ItemFn, Path, Token, Type, parse_macro_input};
+
+use super::proof_with_check;
+
+/// The format used to serialize the value.
+enum Format {
+    /// The in-memory format of the Kani library.
+    InMemory,
+    /// A module with `to_vec` and `from_slice` functions, like `serde_json`.
+    Module(Path),
+}
+
+/// The parsed arguments of `#[kani::proof_roundtrip(Type, format = ..., compare = ...)]`.
+struct RoundtripArgs {
+    ty: Type,
+    format: Format,
+    compare: Option<Path>,
+}
+
+impl Parse for RoundtripArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        let mut format = None;
+        let mut compare = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let value: Path = input.parse()?;
+            let slot = if key == "format" {
+                &mut format
+            } else if key == "compare" {
+                &mut compare
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown argument `{key}`, expected `format` or `compare`"),
+                ));
+            };
+            if slot.replace(value).is_some() {
+                return Err(syn::Error::new(key.span(), format!("duplicated `{key}`")));
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument, expected `format` or `compare`"));
+        }
+        let format = match format {
+            None => Format::InMemory,
+            Some(path) if path.is_ident("InMemory") => Format::InMemory,
+            Some(path) if path.is_ident("Json") => Format::Module(syn::parse_quote!(::serde_json)),
+            Some(path) => Format::Module(path),
+        };
+        Ok(RoundtripArgs { ty, format, compare })
+    }
+}
+
+pub fn proof_roundtrip(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = TokenStream2::from(attr.clone());
+    let RoundtripArgs { ty, format, compare } = parse_macro_input!(attr as RoundtripArgs);
+    let fn_item = parse_macro_input!(item as ItemFn);
+    if !fn_item.sig.inputs.is_empty() {
+        return syn::Error::new_spanned(
+            &fn_item.sig.inputs,
+            "`#[kani::proof_roundtrip]` cannot be applied to functions that take arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let (format_def, serialize, deserialize): (TokenStream2, TokenStream2, TokenStream2) =
+        match format {
+            Format::InMemory => (
+                quote!(kani::in_memory_format!();),
+                quote!(in_memory_format::to_tokens(value)),
+                quote!(in_memory_format::from_tokens::<#ty>(encoded)),
+            ),
+            Format::Module(path) => {
+                (quote!(), quote!(#path::to_vec(value)), quote!(#path::from_slice::<#ty>(encoded)))
+            }
+        };
+    let compare = match compare {
+        Some(path) => quote!(#path),
+        None => quote!(|original: &#ty, decoded: &#ty| original == decoded),
+    };
+    let check = quote!(
+        #format_def
+        kani::roundtrip::check_roundtrip::<#ty, _, _, _>(
+            |value: &#ty| #serialize,
+            |encoded: &_| #deserialize,
+            #compare,
+        );
+    );
+    proof_with_check("proof_roundtrip", args, check, fn_item)
+}
@@ -0,0 +1,123 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::safety_requires(...)]` attribute.
+//!
+//! The attribute records the safety precondition of an `unsafe fn` and generates a hidden
+//! function next to it that takes the same arguments and evaluates the precondition. The compiler
+//! calls it before every call to the annotated function and checks its result:
+//!
+//! ```ignore
+//! #[kani::safety_requires(!ptr.is_null() && ptr.is_aligned())]
+//! unsafe fn read(ptr: *const u32) -> u32 {
+//!     *ptr
+//! }
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! #[kanitool::safety_requires = "! ptr.is_null() && ptr.is_aligned()"]
+//! unsafe fn read(ptr: *const u32) -> u32 {
+//!     *ptr
+//! }
+//!
+//! #[doc(hidden)]
+//! #[allow(dead_code, non_snake_case, unused_mut, forgetting_copy_types, forgetting_references)]
+//! unsafe fn __kani_safety_requires_read(ptr: *const u32) -> bool {
+//!     let holds = !ptr.is_null() && ptr.is_aligned();
+//!     ::core::mem::forget(ptr);
+//!     holds
+//! }
+//! ```
+//!
+//! The generated function gets a copy of the arguments of the call, so it forgets them instead
+//! of dropping them.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::{ToTokens, format_ident, quote};
+use syn::{Expr, FnArg, ItemFn, Pat, ReturnType, parse_macro_input, parse_quote};
+
+/// The prefix of the function generated to evaluate the safety precondition.
+const SAFETY_REQUIRES_PREFIX: &str = "__kani_safety_requires";
+
+pub fn safety_requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let condition = parse_macro_input!(attr as Expr);
+    let item_fn = parse_macro_input!(item as ItemFn);
+    match expand(condition, item_fn) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn expand(condition: Expr, item_fn: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let ItemFn { attrs, vis, sig, block } = &item_fn;
+    if sig.unsafety.is_none() {
+        return Err(syn::Error::new_spanned(
+            &sig.fn_token,
+            "`#[kani::safety_requires]` can only be used on `unsafe` functions",
+        ));
+    }
+    if let Some(asyncness) = sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span,
+            "`#[kani::safety_requires]` is not supported on `async` functions",
+        ));
+    }
+    if let Some(attr) = attrs.iter().find(|attr| {
+        attr.path().segments.last().is_some_and(|segment| segment.ident == "safety_requires")
+    }) {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "a function can only have one `#[kani::safety_requires]` attribute, combine the \
+            conditions with `&&` instead",
+        ));
+    }
+
+    let inputs = sig
+        .inputs
+        .iter()
+        .map(|arg| match arg {
+            FnArg::Receiver(receiver) => Ok(Ident::new("self", receiver.self_token.span)),
+            FnArg::Typed(typed) => match typed.pat.as_ref() {
+                Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                    Ok(pat.ident.clone())
+                }
+                pat => Err(syn::Error::new_spanned(
+                    pat,
+                    "`#[kani::safety_requires]` only supports arguments that are simple \
+                    variables",
+                )),
+            },
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut checker_sig = sig.clone();
+    checker_sig.ident = format_ident!("{SAFETY_REQUIRES_PREFIX}_{}", sig.ident);
+    checker_sig.constness = None;
+    checker_sig.abi = None;
+    checker_sig.output = ReturnType::Type(Default::default(), parse_quote!(bool));
+    let holds = Ident::new("__kani_holds", Span::mixed_site());
+    let condition_str = condition.to_token_stream().to_string();
+
+    Ok(quote!(
+        #(#attrs)*
+        #[kanitool::safety_requires = #condition_str]
+        #vis #sig #block
+
+        #[doc(hidden)]
+        #[allow(
+            dead_code,
+            non_snake_case,
+            unused_mut,
+            forgetting_copy_types,
+            forgetting_references
+        )]
+        #vis #checker_sig {
+            let #holds: bool = #condition;
+            #(::core::mem::forget(#inputs);)*
+            #holds
+        }
+    ))
+}
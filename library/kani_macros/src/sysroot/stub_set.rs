@@ -0,0 +1,71 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Declare named groups of stubs that can be applied to many harnesses with
+//! `#[kani::use_stubs(NAME)]`.
+
+use proc_macro::TokenStream;
+use proc_macro_error2::abort;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, Token, Visibility, bracketed, parenthesized, parse_macro_input};
+
+/// A stub set, i.e., `vis NAME = [(original, replacement), ...]`.
+struct StubSet {
+    vis: Visibility,
+    name: Ident,
+    /// The arguments of each stub, which are parsed by the compiler like the ones of
+    /// `#[kani::stub]`.
+    stubs: Vec<proc_macro2::TokenStream>,
+}
+
+impl Parse for StubSet {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        let _ = input.parse::<Token![=]>()?;
+        let content;
+        bracketed!(content in input);
+        let stubs = Punctuated::<StubArgs, Token![,]>::parse_terminated(&content)?;
+        Ok(StubSet { vis, name, stubs: stubs.into_iter().map(|stub| stub.0).collect() })
+    }
+}
+
+/// The arguments of a stub in a stub set, i.e., `(original, replacement)`.
+struct StubArgs(proc_macro2::TokenStream);
+
+impl Parse for StubArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        Ok(StubArgs(content.parse()?))
+    }
+}
+
+/// Expand `stub_set! { io = [(a, a_stub), (b, b_stub)] }` to:
+/// ```ignore
+/// #[doc(hidden)]
+/// #[allow(dead_code, non_snake_case)]
+/// #[kanitool::stub_set(a, a_stub)]
+/// #[kanitool::stub_set(b, b_stub)]
+/// fn io() {}
+/// ```
+///
+/// The function is only used to give the set a path that harnesses can refer to, and the paths
+/// of its stubs are resolved from the module where the set is declared.
+pub fn stub_set(item: TokenStream) -> TokenStream {
+    let sets = parse_macro_input!(item with Punctuated::<StubSet, Token![,]>::parse_terminated);
+    let sets = sets.into_iter().map(|StubSet { vis, name, stubs }| {
+        if stubs.is_empty() {
+            abort!(name, "stub set `{}` must have at least one stub", name);
+        }
+        quote!(
+            #[doc(hidden)]
+            #[allow(dead_code, non_snake_case)]
+            #(#[kanitool::stub_set(#stubs)])*
+            #vis fn #name() {}
+        )
+    });
+    quote!(#(#sets)*).into()
+}
@@ -0,0 +1,167 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Implementation of the `#[kani::terminates(within = N)]` attribute.
+//!
+//! The attribute lowers the function into one that counts the steps it takes in a step counter
+//! that is shared by all the recursive calls of the function. Every loop iteration and every
+//! recursive call takes one step, and taking more than `N` steps is a verification failure:
+//!
+//! ```ignore
+//! #[kani::terminates(within = 10)]
+//! fn sum(n: u32) -> u32 {
+//!     let mut total = 0;
+//!     for i in 0..n {
+//!         total += i;
+//!     }
+//!     total
+//! }
+//! ```
+//!
+//! Turns into:
+//!
+//! ```ignore
+//! fn sum(n: u32) -> u32 {
+//!     static mut __KANI_STEPS: kani::internal::StepCounter = kani::internal::StepCounter::new();
+//!     fn __kani_step() {
+//!         let within_bound = unsafe { kani::internal::StepCounter::step(/* &raw mut __KANI_STEPS */) };
+//!         kani::assert(within_bound, "function `sum` must terminate within 10 steps (n = {n})");
+//!         kani::assume(within_bound);
+//!     }
+//!     let __kani_steps = unsafe { kani::internal::StepCounter::enter(/* ... */, 10) };
+//!     if __kani_steps.is_outermost() {
+//!         kani::internal::capture_value(&n);
+//!     } else {
+//!         __kani_step();
+//!     }
+//!     let mut total = 0;
+//!     for i in 0..n {
+//!         __kani_step();
+//!         total += i;
+//!     }
+//!     total
+//! }
+//! ```
+//!
+//! The arguments of the outermost call are captured, so the input that exceeds the bound is
+//! rendered in the assertion message of the counterexample.
+//! Since the step budget is exhausted after `N` steps, the verifier doesn't need an unwinding
+//! bound to check the function.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream};
+use syn::visit_mut::{self, VisitMut};
+use syn::{Block, Expr, FnArg, ItemFn, LitStr, Pat, Token, parse_macro_input, parse_quote};
+
+/// The parsed arguments of `#[kani::terminates(within = N)]`.
+struct TerminatesArgs {
+    bound: Expr,
+}
+
+impl Parse for TerminatesArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        if key != "within" {
+            return Err(syn::Error::new(
+                key.span(),
+                format!("unknown argument `{key}`, expected `within`"),
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let bound = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument, expected only `within`"));
+        }
+        Ok(TerminatesArgs { bound })
+    }
+}
+
+pub fn terminates(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let TerminatesArgs { bound } = parse_macro_input!(attr as TerminatesArgs);
+    let mut item_fn = parse_macro_input!(item as ItemFn);
+    if let Some(qualifier) =
+        item_fn.sig.constness.map(|c| c.span).or_else(|| item_fn.sig.asyncness.map(|a| a.span))
+    {
+        return syn::Error::new(
+            qualifier,
+            "`#[kani::terminates]` is not supported on `const` or `async` functions",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let guard = Ident::new("__kani_steps", Span::mixed_site());
+    StepInserter.visit_block_mut(&mut item_fn.block);
+
+    // Capture the arguments that are simple variables, so they can be rendered in the message.
+    let inputs: Vec<_> = item_fn
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(typed) => match typed.pat.as_ref() {
+                Pat::Ident(pat) => Some(pat.ident.clone()),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let bound_str = bound.to_token_stream().to_string().replace('{', "{{").replace('}', "}}");
+    let mut msg =
+        format!("function `{}` must terminate within {bound_str} steps", item_fn.sig.ident);
+    if !inputs.is_empty() {
+        let values = inputs.iter().map(|input| format!("{input} = {{{input}}}"));
+        msg = format!("{msg} ({})", values.collect::<Vec<_>>().join(", "));
+    }
+    let msg = LitStr::new(&msg, Span::call_site());
+
+    let body = &item_fn.block;
+    let new_block: Block = parse_quote!({
+        static mut __KANI_STEPS: kani::internal::StepCounter = kani::internal::StepCounter::new();
+        fn __kani_step() {
+            let within_bound = unsafe {
+                kani::internal::StepCounter::step(::core::ptr::addr_of_mut!(__KANI_STEPS))
+            };
+            kani::assert(within_bound, #msg);
+            kani::assume(within_bound);
+        }
+        let #guard = unsafe {
+            kani::internal::StepCounter::enter(::core::ptr::addr_of_mut!(__KANI_STEPS), #bound)
+        };
+        if #guard.is_outermost() {
+            #(kani::internal::capture_value(&#inputs);)*
+        } else {
+            __kani_step();
+        }
+        #body
+    });
+    item_fn.block = Box::new(new_block);
+    item_fn.into_token_stream().into()
+}
+
+/// Take one step at the beginning of every iteration of the loops of a function.
+///
+/// Loops inside closures and nested items are not instrumented, since they may not run as part
+/// of the function.
+struct StepInserter;
+
+impl VisitMut for StepInserter {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Closure(_) | Expr::Async(_) => {}
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+        let body = match expr {
+            Expr::Loop(expr_loop) => &mut expr_loop.body,
+            Expr::While(expr_while) => &mut expr_while.body,
+            Expr::ForLoop(expr_for) => &mut expr_for.body,
+            _ => return,
+        };
+        body.stmts.insert(0, parse_quote!(__kani_step();));
+    }
+
+    fn visit_item_mut(&mut self, _item: &mut syn::Item) {}
+}
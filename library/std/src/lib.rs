@@ -36,6 +36,14 @@
 /// ```
 /// the assert message will be:
 /// "The sum of {} and {} is {}", a, b, c
+///
+/// If the message only uses variables captured by name, e.g.:
+/// ```ignore
+/// assert!(x < y, "x={x} y={y}");
+/// ```
+/// Kani renders the values of the captured variables in the counterexample when the assertion
+/// fails, e.g.:
+/// "x=3 y=2"
 #[cfg(not(feature = "concrete_playback"))]
 #[macro_export]
 macro_rules! assert {
@@ -43,6 +51,19 @@ macro_rules! assert {
         // The double negation is to resolve https://github.com/model-checking/kani/issues/2108
         kani::assert(!!$cond, concat!("assertion failed: ", stringify!($cond)));
     };
+    ($cond:expr, $msg:literal $(,)?) => {{
+        let cond: bool = !!$cond;
+        // Variables captured by the message, e.g. `x` in `"x={x}"`, are recorded only when the
+        // assertion fails, so their values can be rendered from the counterexample.
+        if !cond {
+            kani::capture_values!($msg);
+        }
+        kani::assert(cond, stringify!($msg));
+        // See the comment in the next case.
+        if false {
+            kani::__kani__workaround_core_assert!(true, $msg);
+        }
+    }};
     ($cond:expr, $($arg:tt)+) => {{
         kani::assert(!!$cond, concat!(stringify!($($arg)+)));
         // Process the arguments of the assert inside an unreachable block. This
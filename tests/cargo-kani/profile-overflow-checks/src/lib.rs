@@ -0,0 +1,19 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the default arithmetic overflow mode follows the `overflow-checks` setting of the
+//! Cargo profile.
+
+pub fn checksum(bytes: [u8; 2]) -> u8 {
+    bytes[0] + bytes[1]
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    #[kani::proof]
+    fn check_checksum_wraps() {
+        let bytes: [u8; 2] = kani::any();
+        assert_eq!(checksum(bytes), bytes[0].wrapping_add(bytes[1]));
+    }
+}
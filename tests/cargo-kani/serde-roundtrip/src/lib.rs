@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::proof_roundtrip]` verifies serialization round-trips of `serde` types with
+//! the in-memory format and with `serde_json`, and that a custom comparison can be used for NaN.
+//! Also check that it fails for a type whose serialization loses information.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, kani::Arbitrary)]
+pub struct Point {
+    x: i8,
+    y: u8,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, kani::Arbitrary)]
+pub struct Meters(u16);
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, kani::Arbitrary)]
+pub enum Shape {
+    Empty,
+    Dot(Point),
+    Segment(Point, Point),
+    Circle { center: Point, radius: Option<Meters> },
+}
+
+#[derive(Serialize, Deserialize, Debug, kani::Arbitrary)]
+pub struct Sample {
+    id: u32,
+    value: f32,
+}
+
+/// The cache isn't serialized, so it's reset to its default value by the deserialization.
+#[derive(Serialize, Deserialize, PartialEq, Debug, kani::Arbitrary)]
+pub struct Cached {
+    value: u8,
+    #[serde(skip)]
+    cache: u8,
+}
+
+fn same_sample(original: &Sample, decoded: &Sample) -> bool {
+    original.id == decoded.id
+        && (original.value == decoded.value || original.value.is_nan() && decoded.value.is_nan())
+}
+
+#[kani::proof_roundtrip(Shape)]
+fn check_shape_roundtrip() {}
+
+#[kani::proof_roundtrip(Sample, format = InMemory, compare = same_sample)]
+fn check_sample_roundtrip() {}
+
+#[kani::proof_roundtrip(Point, format = Json)]
+#[kani::unwind(8)]
+fn check_point_json() {}
+
+#[kani::proof_roundtrip(Cached)]
+fn check_cached_roundtrip() {}
@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::any_iter` can be used to verify iterator adapter chains, and that every
+//! length up to the bound is reachable.
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_adapter_chain() {
+    let iter = kani::any_iter::<u8>(4);
+    assert_eq!(iter.size_hint(), (0, Some(4)));
+    let evens: Vec<u16> = iter.map(u16::from).filter(|v| v % 2 == 0).map(|v| v * 2).collect();
+    assert!(evens.len() <= 4);
+    assert!(evens.iter().all(|v| v % 4 == 0));
+    kani::cover!(evens.is_empty());
+    kani::cover!(evens.len() == 4);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_length_bound() {
+    let mut iter = kani::any_iter::<char>(3);
+    let count = iter.by_ref().count();
+    assert!(count <= 3);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.size_hint(), (0, Some(3 - count)));
+    kani::cover!(count == 0);
+    kani::cover!(count == 3);
+    kani::cover!(count == 4, "unreachable length");
+}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that slicing a string generated by `kani::any_utf8` at a byte that isn't a character
+//! boundary is reported as a panic.
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_slice_first_byte() {
+    let s = kani::any_utf8(4);
+    if !s.is_empty() {
+        let first = &s[..1];
+        assert!(first.is_ascii());
+    }
+}
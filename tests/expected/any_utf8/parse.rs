@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::any_utf8` can be used to verify string parsing functions, and that
+//! characters of every width can be generated.
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_char_widths() {
+    let s = kani::any_utf8(4);
+    assert!(s.len() <= 4);
+    let mut chars = s.chars();
+    let first = chars.next();
+    kani::cover!(s.is_empty());
+    kani::cover!(first.is_some_and(|c| c.len_utf8() == 2), "two-byte character");
+    kani::cover!(first.is_some_and(|c| c.len_utf8() == 3), "three-byte character");
+    kani::cover!(first.is_some_and(|c| c.len_utf8() == 4), "four-byte character");
+    kani::cover!(s.len() == 5, "unreachable length");
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_parse_u8() {
+    let s = kani::any_utf8(3);
+    if let Ok(value) = s.parse::<u8>() {
+        assert!(s.bytes().all(|b| b.is_ascii_digit() || b == b'+'));
+        kani::cover!(value == u8::MAX);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_split() {
+    let s = kani::any_utf8(4);
+    let mut total = 0;
+    for part in s.split(',') {
+        total += part.len() + 1;
+    }
+    assert_eq!(total, s.len() + 1);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_split_at_char_boundary() {
+    let s = kani::any_utf8(4);
+    let mid: usize = kani::any_where(|mid| *mid <= s.len());
+    if s.is_char_boundary(mid) {
+        let (first, second) = s.split_at(mid);
+        assert_eq!(first.len() + second.len(), s.len());
+    }
+}
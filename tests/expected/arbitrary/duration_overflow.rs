@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the panicking arithmetic on nondeterministic `Duration` values reports the
+//! overflows documented by the standard library.
+
+use std::time::Duration;
+
+#[kani::proof]
+fn check_add_overflow() {
+    let lhs: Duration = kani::any();
+    let rhs: Duration = kani::any();
+    let _ = lhs + rhs;
+}
+
+#[kani::proof]
+fn check_mul_overflow() {
+    let duration: Duration = kani::any();
+    let factor: u32 = kani::any();
+    let _ = duration * factor;
+}
+
+/// The nanoseconds are carried into the seconds, which overflow if they're already at the maximum.
+#[kani::proof]
+fn check_new_overflow() {
+    let nanos: u32 = kani::any();
+    let _ = Duration::new(u64::MAX, nanos);
+}
@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the `arithmetic_overflow` attribute selects how overflow is modeled in each
+//! harness.
+
+fn increment(x: u8) -> u8 {
+    x + 1
+}
+
+#[kani::proof]
+#[kani::arithmetic_overflow(wrap)]
+fn check_wrap() {
+    let x: u8 = kani::any();
+    let next = increment(x);
+    assert!(x < u8::MAX || next == 0, "overflow wraps around");
+}
+
+#[kani::proof]
+#[kani::should_panic]
+fn check_panic_by_default() {
+    increment(kani::any());
+}
+
+#[kani::proof]
+#[kani::should_panic]
+#[kani::arithmetic_overflow(check)]
+fn check_overflow_is_not_a_panic() {
+    increment(kani::any());
+}
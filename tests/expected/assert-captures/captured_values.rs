@@ -0,0 +1,21 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the values of the variables captured by an assertion message are rendered when
+//! the assertion fails, including variables that only exist in the failing branch.
+
+#[kani::proof]
+fn check_captured_values() {
+    let x: u8 = kani::any_where(|x| *x == 5);
+    let y: u8 = 2;
+    if x > y {
+        let diff = x - y;
+        assert!(diff < 3, "diff={diff} x={x} y={y}");
+    }
+}
+
+#[kani::proof]
+fn check_no_captures() {
+    let x: u8 = kani::any();
+    assert!(x == x, "x={x}");
+    assert!(x < 10 || x >= 10, "the value is {}", x);
+}
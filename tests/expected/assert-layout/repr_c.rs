@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `kani::assert_layout!` verifies the layout of `#[repr(C)]` types and reports the
+//! actual values when they don't match the expectation.
+
+#[repr(C)]
+struct Header {
+    tag: u8,
+    len: u32,
+    flags: u16,
+}
+
+#[repr(C)]
+struct Pair(u16, u64);
+
+#[kani::proof]
+fn check_expected_layout() {
+    kani::assert_layout!(Header, size = 12, align = 4, fields = [(tag, 0), (len, 4), (flags, 8)]);
+    kani::assert_layout!(Pair, size = 16, fields = [(0, 0), (1, 8)]);
+}
+
+#[kani::proof]
+fn check_unexpected_layout() {
+    kani::assert_layout!(Header, size = 7, align = 1, fields = [(tag, 0), (len, 1), (flags, 5)]);
+}
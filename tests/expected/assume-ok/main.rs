@@ -0,0 +1,49 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --assume-ok
+//
+//! Check that `--assume-ok` prunes the error paths of the calls that return a `Result`,
+//! including the errors propagated by the `?` operator, and that the harnesses report the
+//! functions whose calls were assumed to return `Ok`.
+
+#[derive(Debug)]
+enum ParseError {
+    Empty,
+    TooLarge,
+}
+
+fn parse_digit(byte: u8) -> Result<u8, ParseError> {
+    if byte == 0 {
+        return Err(ParseError::Empty);
+    }
+    if byte > 9 {
+        return Err(ParseError::TooLarge);
+    }
+    Ok(byte)
+}
+
+fn parse_pair(first: u8, second: u8) -> Result<u8, ParseError> {
+    let tens = parse_digit(first)?;
+    let units = parse_digit(second)?;
+    Ok(tens * 10 + units)
+}
+
+#[kani::proof]
+fn check_success_path() {
+    let value = parse_pair(kani::any(), kani::any()).unwrap();
+    assert!(value <= 99);
+}
+
+#[kani::proof]
+fn check_error_path_is_pruned() {
+    let byte: u8 = kani::any();
+    let result = parse_digit(byte);
+    assert!(result.is_ok(), "the error path is pruned");
+    assert!((1..=9).contains(&byte));
+}
+
+#[kani::proof]
+fn check_any_result_is_not_restricted() {
+    let result: Result<u8, ()> = kani::any();
+    kani::cover!(result.is_err(), "kani::any can still return an error");
+}
@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --spurious-cas-failures
+//
+//! Check that atomic operations with weak orderings are approximated as `SeqCst` with a warning,
+//! and that `compare_exchange_weak` may fail spuriously with `--spurious-cas-failures`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[kani::proof]
+fn check_relaxed_counter() {
+    let start: usize = kani::any();
+    let counter = AtomicUsize::new(start);
+    let previous = counter.fetch_add(1, Ordering::Relaxed);
+    assert_eq!(previous, start);
+    assert_eq!(counter.load(Ordering::Acquire), start.wrapping_add(1));
+}
+
+#[kani::proof]
+fn check_spurious_failure() {
+    let counter = AtomicUsize::new(0);
+    let result = counter.compare_exchange_weak(0, 1, Ordering::SeqCst, Ordering::SeqCst);
+    kani::cover!(result == Err(0), "spurious failure");
+    kani::cover!(result == Ok(0), "success");
+    if result.is_err() {
+        assert_eq!(counter.load(Ordering::SeqCst), 0, "a failure leaves the value unchanged");
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that code under `#[cfg(kani)]` is verified and that code under `#[cfg(not(kani))]` is
+//! excluded, for the different ways harnesses and their helpers can be gated.
+
+/// Production code with a different implementation for verification.
+#[cfg(not(kani))]
+fn checksum(data: &[u8]) -> u8 {
+    // Unreachable during verification, so this failure must not be reported.
+    assert!(data.is_empty(), "production implementation must be excluded");
+    0
+}
+
+#[cfg(kani)]
+fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0, |sum, byte| sum.wrapping_add(*byte))
+}
+
+/// Production code that calls a function which is also a harness during verification.
+pub fn self_test() -> bool {
+    check_self_test();
+    true
+}
+
+/// Only a harness when verifying, and a regular function otherwise.
+#[cfg_attr(kani, kani::proof)]
+fn check_self_test() {
+    assert!(checksum(&[1, 2]) == 3);
+}
+
+#[kani::proof]
+fn check_production_calls_harness() {
+    // The harness is reachable as a regular function from other code.
+    assert!(self_test());
+}
+
+#[kani::proof]
+fn check_cfg_macro() {
+    assert!(cfg!(kani), "cfg!(kani) is set during verification");
+    if cfg!(not(kani)) {
+        checksum(&[1]);
+    }
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::checksum;
+
+    /// A helper that only exists for verification.
+    fn any_data() -> [u8; 2] {
+        kani::any()
+    }
+
+    #[kani::proof]
+    fn check_gated_module() {
+        let data = any_data();
+        assert!(checksum(&data) == data[0].wrapping_add(data[1]));
+    }
+
+    #[kani::proof]
+    fn check_gated_failure() {
+        let data = any_data();
+        assert!(checksum(&data) != 0, "expected failure in code under cfg(kani)");
+    }
+}
+
+/// Harnesses that are excluded from verification are not collected.
+#[cfg(not(kani))]
+mod production {
+    #[kani::proof]
+    fn check_excluded() {
+        unreachable!("harnesses under cfg(not(kani)) must not be verified");
+    }
+}
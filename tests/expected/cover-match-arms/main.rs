@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --cover-match-arms
+//
+//! Check that `--cover-match-arms` reports which arms of a `match` are reachable, including
+//! dead catch-all arms.
+
+#[derive(Clone, Copy, kani::Arbitrary)]
+enum Command {
+    Start,
+    Stop,
+    Pause,
+}
+
+fn run(command: Command) -> u8 {
+    match command {
+        Command::Start => 1,
+        Command::Stop => 2,
+        _ => 3,
+    }
+}
+
+fn digit(value: u8) -> u8 {
+    match value % 4 {
+        0 | 1 => 0,
+        2 => 1,
+        3 => 2,
+        _ => 10,
+    }
+}
+
+#[kani::proof]
+fn check_arms() {
+    let command: Command = kani::any();
+    kani::assume(!matches!(command, Command::Pause));
+    assert!(run(command) != 0);
+    assert!(digit(kani::any()) < 3);
+}
@@ -0,0 +1,20 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --deny-warnings=all
+//
+//! Check that `--deny-warnings=all` reports the warnings of every category as errors, and that
+//! the note names the flag that denied the warning.
+
+fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+fn verify_double() {
+    let x: u8 = kani::any();
+    assert_eq!(double(x) / 2, x as u16);
+}
+
+#[kani::proof]
+fn proof_double() {
+    verify_double();
+}
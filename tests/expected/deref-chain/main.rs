@@ -0,0 +1,69 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+//
+//! Check that the panics and the contracts of the `Deref` implementations that are reached
+//! through a chain of deref coercions are honored.
+
+use std::ops::Deref;
+
+/// A value that may not be set yet.
+struct Lazy(Option<String>);
+
+impl Deref for Lazy {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        match &self.0 {
+            Some(value) => value,
+            None => panic!("deref of an unset value"),
+        }
+    }
+}
+
+#[kani::proof]
+fn check_panicking_deref() {
+    let value = Box::new(Lazy(if kani::any() { Some(String::from("a")) } else { None }));
+    // `&Box<Lazy> -> &Lazy -> &String -> &str`
+    assert_eq!(value.len(), 1);
+}
+
+/// A string that is never empty.
+struct NonEmpty(String);
+
+impl NonEmpty {
+    fn is_valid(&self) -> bool {
+        !self.0.is_empty()
+    }
+}
+
+impl Deref for NonEmpty {
+    type Target = String;
+
+    #[kani::requires(self.is_valid())]
+    #[kani::ensures(|result: &&String| result.len() == self.0.len())]
+    fn deref(&self) -> &String {
+        &self.0
+    }
+}
+
+#[kani::proof_for_contract(<NonEmpty as Deref>::deref)]
+fn check_deref_contract() {
+    let value = NonEmpty(String::from(if kani::any() { "a" } else { "" }));
+    let _ = &*value;
+}
+
+#[kani::proof]
+#[kani::stub_verified(<NonEmpty as Deref>::deref)]
+fn check_chain_with_contract() {
+    let value = Box::new(NonEmpty(String::from("ab")));
+    // The call to `deref` in the chain is replaced by its contract.
+    assert_eq!(value.len(), 2);
+}
+
+#[kani::proof]
+#[kani::stub_verified(<NonEmpty as Deref>::deref)]
+fn check_chain_violates_contract() {
+    let value = Box::new(NonEmpty(String::new()));
+    let _ = value.len();
+}
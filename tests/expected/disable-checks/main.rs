@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the checks listed in `#[kani::disable_checks]` are only disabled for that harness,
+//! and that the verification result of the harness lists them.
+
+fn read_unaligned_bytes(data: &[u16; 4]) -> u16 {
+    let ptr = unsafe { (data.as_ptr() as *const u8).add(1) as *const u16 };
+    unsafe { std::ptr::read_volatile(ptr) }
+}
+
+fn increment(x: u8) -> u8 {
+    x + 1
+}
+
+#[kani::proof]
+#[kani::disable_checks(unaligned_access, arithmetic_overflow)]
+fn check_disabled() {
+    assert_eq!(read_unaligned_bytes(&[0; 4]), 0);
+    let x: u8 = kani::any();
+    assert!(x == u8::MAX || increment(x) > x);
+}
+
+#[kani::proof]
+fn check_enabled() {
+    assert_eq!(read_unaligned_bytes(&[0; 4]), 0);
+}
+
+#[kani::proof]
+fn check_overflow() {
+    increment(kani::any());
+}
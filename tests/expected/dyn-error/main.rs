@@ -0,0 +1,63 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that counterexamples render the concrete errors that `?` converts into a
+//! `Box<dyn Error>`, including errors whose `From` conversion panics.
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+struct ParseError {
+    position: u8,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid input at {}", self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug)]
+struct ConfigError;
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid configuration")
+    }
+}
+
+impl Error for ConfigError {}
+
+impl From<ParseError> for ConfigError {
+    fn from(error: ParseError) -> ConfigError {
+        assert!(error.position != 0, "parse errors always have a position");
+        ConfigError
+    }
+}
+
+fn parse(input: u8) -> Result<u8, ParseError> {
+    if input < 10 { Ok(input) } else { Err(ParseError { position: input - 10 }) }
+}
+
+fn parse_boxed(input: u8) -> Result<u8, Box<dyn Error>> {
+    Ok(parse(input)?)
+}
+
+fn parse_config(input: u8) -> Result<u8, ConfigError> {
+    Ok(parse(input)?)
+}
+
+#[kani::proof]
+fn check_boxed_error() {
+    let input: u8 = kani::any();
+    assert!(parse_boxed(input).is_ok(), "input is always valid");
+}
+
+#[kani::proof]
+fn check_panicking_from() {
+    let input: u8 = kani::any();
+    let _ = parse_config(input);
+}
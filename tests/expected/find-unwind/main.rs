@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --find-unwind=16
+//
+//! Check that `--find-unwind` reports the minimal unwind bound of each harness, and that it gives
+//! up once the cap is reached for a loop that doesn't terminate for some inputs.
+
+fn sum_up_to(n: u8) -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < n {
+        i += 1;
+        sum += i as u32;
+    }
+    sum
+}
+
+/// Count down by two, which never reaches zero for an odd input.
+fn count_down_by_two(mut n: u8) -> u32 {
+    let mut steps = 0;
+    while n != 0 {
+        n = n.wrapping_sub(2);
+        steps += 1;
+    }
+    steps
+}
+
+#[kani::proof]
+fn check_sum() {
+    let n: u8 = kani::any_where(|n| *n <= 5);
+    assert!(sum_up_to(n) <= 15);
+}
+
+#[kani::proof]
+fn check_count_down() {
+    let n: u8 = kani::any();
+    assert!(count_down_by_two(n) <= 128);
+}
@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+//
+//! Check that calls from a `#[kani::boundary]` module to functions outside of the module are
+//! replaced by their contracts, and that calls to functions without a contract are reported.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(|result: &u32| *result > x)]
+fn next(x: u32) -> u32 {
+    // The body is only used if the contract is not.
+    x + 1
+}
+
+fn double(x: u32) -> u32 {
+    x.wrapping_mul(2)
+}
+
+#[kani::boundary]
+mod client {
+    pub fn advance(x: u32) -> u32 {
+        let next = super::next(x);
+        // Only holds for the original body of `next`.
+        assert!(next == x + 1, "next is the successor");
+        super::double(next)
+    }
+}
+
+#[kani::proof]
+fn check_advance() {
+    let x = kani::any_where(|x: &u32| *x < 50);
+    client::advance(x);
+}
+
+#[kani::proof_for_contract(next)]
+fn check_next() {
+    next(kani::any());
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that Kani warns about the contract clauses that are declared more than once, including
+//! `modifies` clauses, and reports where each copy is declared.
+
+#[kani::requires(*ptr < 100)]
+#[kani::modifies(ptr)]
+#[kani::ensures(|_| *ptr > 0)]
+#[kani::modifies(ptr)]
+fn increment(ptr: &mut u32) {
+    *ptr += 1;
+}
+
+#[kani::requires(x < 100)]
+#[kani::ensures(|result: &u32| *result > x)]
+#[kani::requires(x < 100)]
+fn next(x: u32) -> u32 {
+    x + 1
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    let mut value = kani::any();
+    increment(&mut value);
+}
+
+#[kani::proof_for_contract(next)]
+fn check_next() {
+    next(kani::any());
+}
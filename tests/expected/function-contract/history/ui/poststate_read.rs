@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that Kani warns about `ensures` clauses that read the post-state of a mutable argument
+//! without ever referring to its original value, unless the lint is explicitly silenced.
+
+#[kani::ensures(|_| *ptr > 0)]
+#[kani::modifies(ptr)]
+fn set_positive(ptr: &mut u32) {
+    *ptr = 1;
+}
+
+#[kani::ensures(|_| *ptr == old(*ptr) + 1)]
+#[kani::requires(*ptr < 100)]
+#[kani::modifies(ptr)]
+fn increment(ptr: &mut u32) {
+    *ptr += 1;
+}
+
+#[kani::allow_poststate_read]
+#[kani::ensures(|_| *ptr == 10)]
+#[kani::modifies(ptr)]
+fn set_ten(ptr: &mut u32) {
+    *ptr = 10;
+}
+
+#[kani::proof_for_contract(set_positive)]
+fn check_set_positive() {
+    let mut i = kani::any();
+    set_positive(&mut i);
+}
+
+#[kani::proof_for_contract(increment)]
+fn check_increment() {
+    let mut i = kani::any();
+    increment(&mut i);
+}
+
+#[kani::proof_for_contract(set_ten)]
+fn check_set_ten() {
+    let mut i = kani::any();
+    set_ten(&mut i);
+}
@@ -0,0 +1,36 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `kani::assume_unchanged!` restores the value of a location that is part of a
+//! `modifies` target, and that assuming a whole `modifies` target is unchanged is detected.
+
+struct Pair {
+    first: u32,
+    second: u32,
+}
+
+#[kani::requires(pair.first < 100)]
+#[kani::modifies(pair)]
+#[kani::ensures(|_| pair.first < 101)]
+fn bump_first(pair: &mut Pair) {
+    pair.first += 1;
+}
+
+#[kani::proof]
+#[kani::stub_verified(bump_first)]
+fn check_second_unchanged() {
+    let mut pair = Pair { first: kani::any_where(|first| *first < 100), second: kani::any() };
+    let second = pair.second;
+    bump_first(&mut pair);
+    kani::assume_unchanged!(pair.second);
+    kani::assert(pair.second == second, "Unchanged");
+}
+
+#[kani::proof]
+#[kani::stub_verified(bump_first)]
+fn check_whole_target() {
+    let mut pair = Pair { first: kani::any_where(|first| *first < 100), second: kani::any() };
+    bump_first(&mut pair);
+    kani::assume_unchanged!(pair);
+}
@@ -0,0 +1,35 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that `#[kani::global_assume]` is re-established after a contract havocs a static, and
+//! that assumptions with side effects are reported.
+
+#[kani::global_assume(unsafe { BALANCE } >= 0)]
+static mut BALANCE: i64 = 0;
+
+#[kani::global_assume({ unsafe { CALLS = CALLS.wrapping_add(1) }; true })]
+static mut CALLS: u32 = 0;
+
+/// The contract doesn't say anything about the new balance.
+#[kani::requires(amount > 0)]
+#[kani::modifies(&raw mut BALANCE)]
+unsafe fn withdraw(amount: i64) {
+    if BALANCE >= amount {
+        BALANCE -= amount;
+    }
+}
+
+#[kani::proof]
+#[kani::stub_verified(withdraw)]
+fn check_balance() {
+    unsafe {
+        withdraw(kani::any_where(|amount| *amount > 0));
+        kani::assert(BALANCE >= 0, "balance is never negative");
+    }
+}
+
+#[kani::proof_for_contract(withdraw)]
+fn check_withdraw() {
+    unsafe { withdraw(kani::any()) }
+}
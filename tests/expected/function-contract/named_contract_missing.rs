@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that we report harnesses that select a named contract that doesn't exist.
+
+#[kani::contract(name = "positive", ensures = |result: &i32| *result > 0)]
+fn one() -> i32 {
+    1
+}
+
+#[kani::proof_for_contract(one, contract = "negative")]
+fn check_negative() {
+    one();
+}
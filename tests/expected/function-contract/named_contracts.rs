@@ -0,0 +1,44 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that each named contract of a function is checked independently, and that a harness
+//! only checks the contract it selects.
+
+#[kani::contract(name = "small", requires = x < 100, ensures = |result: &u32| *result < 200)]
+#[kani::contract(name = "even", ensures = |result: &u32| *result % 2 == 0)]
+fn double(x: u32) -> u32 {
+    x.wrapping_mul(2)
+}
+
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    #[kani::contract(
+        name = "increment",
+        requires = self.count < u32::MAX,
+        modifies = [&mut self.count],
+        ensures = |_| self.count == old(self.count) + 1
+    )]
+    fn bump(&mut self) {
+        self.count += 1;
+    }
+}
+
+#[kani::proof_for_contract(double, contract = "small")]
+fn check_small() {
+    double(kani::any());
+}
+
+#[kani::proof_for_contract(double, contract = "even")]
+fn check_even() {
+    double(kani::any());
+}
+
+#[kani::proof_for_contract(Counter::bump, contract = "increment")]
+fn check_bump() {
+    let mut counter = Counter { count: kani::any() };
+    counter.bump();
+}
@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that a `panic_free` harness only checks that the target doesn't panic when its
+//! preconditions hold, and that the panics of `may_panic` functions are allowed.
+
+// The postcondition is wrong, but it is not checked by a `panic_free` harness.
+#[kani::requires(divisor != 0)]
+#[kani::ensures(|result| *result == dividend)]
+fn div(dividend: u32, divisor: u32) -> u32 {
+    dividend / divisor
+}
+
+/// Panics if `value` is zero.
+#[kani::may_panic]
+fn expect_nonzero(value: u8) -> u8 {
+    assert!(value != 0, "the value is zero");
+    value
+}
+
+#[kani::requires(value < 100)]
+fn increment_nonzero(value: u8) -> u8 {
+    expect_nonzero(value) + 1
+}
+
+#[kani::requires(value < 100)]
+fn triple(value: u8) -> u8 {
+    value * 3
+}
+
+#[kani::proof_for_contract(div)]
+#[kani::panic_free]
+fn check_div() {
+    div(kani::any(), kani::any());
+}
+
+#[kani::proof_for_contract(increment_nonzero)]
+#[kani::panic_free]
+fn check_increment_nonzero() {
+    increment_nonzero(kani::any());
+}
+
+#[kani::proof_for_contract(triple)]
+#[kani::panic_free]
+fn check_triple() {
+    triple(kani::any());
+}
@@ -0,0 +1,79 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that the implementations of a trait method inherit its contract, and that the clauses
+//! they add must refine it: they may only weaken `requires` and strengthen `ensures`.
+
+extern crate kani;
+
+trait Counter {
+    #[kani::requires(step > 0 && step <= 10)]
+    #[kani::ensures(|result: &u32| *result > self.count())]
+    fn advance(&self, step: u32) -> u32;
+
+    fn count(&self) -> u32;
+}
+
+/// Inherits the contract as is.
+struct Plain(u32);
+
+impl Counter for Plain {
+    #[kani::refines(Counter)]
+    fn advance(&self, step: u32) -> u32 {
+        self.0 + step
+    }
+
+    fn count(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Accepts more steps, and guarantees more about the result.
+struct Saturating(u32);
+
+impl Counter for Saturating {
+    #[kani::refines(Counter)]
+    #[kani::requires(step > 0)]
+    #[kani::ensures(|result: &u32| *result >= self.0 + step.min(10))]
+    fn advance(&self, step: u32) -> u32 {
+        self.0 + step
+    }
+
+    fn count(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Rejects some of the steps accepted by the trait method.
+struct Even(u32);
+
+impl Counter for Even {
+    #[kani::refines(Counter)]
+    #[kani::requires(step % 2 == 0 && step > 0)]
+    fn advance(&self, step: u32) -> u32 {
+        self.0 + step
+    }
+
+    fn count(&self) -> u32 {
+        self.0
+    }
+}
+
+#[kani::proof_for_contract(<Plain as Counter>::advance)]
+fn check_plain() {
+    let counter = Plain(kani::any_where(|count| *count < 100));
+    counter.advance(kani::any());
+}
+
+#[kani::proof_for_contract(<Saturating as Counter>::advance)]
+fn check_saturating() {
+    let counter = Saturating(kani::any_where(|count| *count < 100));
+    counter.advance(kani::any_where(|step| *step < 100));
+}
+
+#[kani::proof_for_contract(<Even as Counter>::advance)]
+fn check_even() {
+    let counter = Even(kani::any_where(|count| *count < 100));
+    counter.advance(kani::any_where(|step| *step < 100));
+}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Check that Kani warns about `requires` and `ensures` clauses that are always true or always
+//! false, unless the warning is explicitly silenced.
+
+#[kani::requires(true)]
+#[kani::ensures(|result: &u8| *result <= x)]
+fn halve(x: u8) -> u8 {
+    x / 2
+}
+
+// The contract is vacuous, so the harness succeeds even though the postcondition never holds.
+#[kani::requires(x > 2)]
+#[kani::requires(1 == 2)]
+#[kani::ensures(|_| false)]
+fn identity(x: u8) -> u8 {
+    x
+}
+
+#[kani::allow_trivial_contract]
+#[kani::ensures(|_| true)]
+fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+#[kani::proof_for_contract(halve)]
+fn check_halve() {
+    halve(kani::any());
+}
+
+#[kani::proof_for_contract(identity)]
+fn check_identity() {
+    identity(kani::any());
+}
+
+#[kani::proof_for_contract(double)]
+fn check_double() {
+    double(kani::any());
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the arguments of a harness marked with `#[kani::any]` get a nondeterministic value.
+
+#[derive(kani::Arbitrary, Clone, Copy)]
+struct Config {
+    retries: u8,
+    verbose: bool,
+}
+
+fn attempts(config: &Config) -> u16 {
+    if config.verbose { config.retries as u16 + 1 } else { config.retries as u16 }
+}
+
+#[kani::proof]
+fn check_attempts(#[kani::any] config: Config) {
+    assert!(attempts(&config) <= 256);
+    kani::cover!(config.verbose && config.retries == u8::MAX);
+}
+
+#[kani::proof]
+fn check_several(#[kani::any] config: Config, #[kani::any] mut limit: u8) {
+    kani::assume(limit < u8::MAX);
+    limit += 1;
+    assert!(config.retries < limit, "retries exceed the limit");
+}
@@ -0,0 +1,40 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the harnesses of a group take the value of the fixture of the group, and that the
+//! values the fixture creates with `kani::any()` are independent between harnesses.
+
+struct Database {
+    entries: [Option<u8>; 4],
+}
+
+impl Database {
+    fn lookup(&self, key: usize) -> Option<u8> {
+        self.entries.get(key).copied().flatten()
+    }
+}
+
+#[kani::fixture(group = "db")]
+fn db() -> Database {
+    let mut entries: [Option<u8>; 4] = kani::any();
+    kani::assume(entries.iter().flatten().all(|value| *value < 100));
+    entries[0] = Some(0);
+    Database { entries }
+}
+
+#[kani::proof(group = "db")]
+fn check_first(db: Database) {
+    assert_eq!(db.lookup(0), Some(0));
+    assert!(db.lookup(5).is_none());
+}
+
+#[kani::proof(group = "db")]
+fn check_any_key(db: Database, #[kani::any] key: usize) {
+    assert!(db.lookup(key).is_none_or(|value| value < 100));
+    kani::cover!(db.lookup(key) == Some(99));
+}
+
+#[kani::proof(group = "db")]
+fn check_small(db: Database) {
+    assert!(db.lookup(1).is_none_or(|value| value < 50), "value is too large");
+}
@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that both arguments of `kani::implies` are evaluated, so the checks of the conclusion
+//! apply even when the premise doesn't hold, unlike the `kani::implies!` macro.
+
+#[kani::proof]
+fn check_eager_conclusion() {
+    let divisor: u8 = kani::any();
+    assert!(kani::implies(divisor != 0, 100 / divisor > 0));
+}
+
+#[kani::proof]
+fn check_short_circuit_macro() {
+    let divisor: u8 = kani::any();
+    assert!(kani::implies!(divisor != 0 => 100 / divisor < 101));
+}
@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that indexing a `Vec` gets a bounds check property of its own for each indexing
+//! operation, which names the index variable.
+
+#[kani::proof]
+fn check_vec_index() {
+    let v = vec![1u8, 2, 3];
+    let i: usize = kani::any();
+    assert!(v[i] > 0);
+}
+
+#[kani::proof]
+fn check_vec_index_in_bounds() {
+    let mut v = vec![1u8, 2, 3];
+    let i: usize = kani::any();
+    kani::assume(i < v.len());
+    v[i] = 0;
+    assert!(v[i] == 0);
+}
+
+#[kani::proof]
+fn check_chained_index() {
+    let m = vec![vec![1u8, 2], vec![3u8, 4]];
+    let i: usize = kani::any();
+    let j: usize = kani::any();
+    kani::assume(i < m.len());
+    assert!(m[i][j] > 0);
+}
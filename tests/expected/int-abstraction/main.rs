@@ -0,0 +1,43 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --int-abstraction=bits:8
+//
+//! Check that `--int-abstraction` restricts the integers of the crate under verification to the
+//! given number of bits, that the executions where a value overflows the reduced width are
+//! pruned, and that the abstraction is reported with the result of each harness. Constants and
+//! `usize` values aren't restricted, and a harness without any execution left is reported.
+
+#[kani::proof]
+fn check_unsigned_fits() {
+    let value: u64 = kani::any();
+    assert!(value <= u8::MAX as u64);
+}
+
+#[kani::proof]
+fn check_signed_fits() {
+    let value: i32 = kani::any();
+    assert!(value >= i8::MIN as i32 && value <= i8::MAX as i32);
+}
+
+#[kani::proof]
+fn check_overflow_is_pruned() {
+    let value: u32 = kani::any();
+    let doubled = value * 2;
+    kani::cover!(value > 127, "doubling a value larger than 127 overflows the reduced width");
+    assert!(doubled <= u8::MAX as u32);
+}
+
+#[kani::proof]
+fn check_constants_are_not_restricted() {
+    let limit = 1000u32;
+    let buffer = vec![0u8; 300];
+    assert_eq!(buffer.len(), 300);
+    kani::cover!(limit > u8::MAX as u32, "a wide constant is not restricted");
+}
+
+#[kani::proof]
+fn check_pruned_harness_is_reported() {
+    let value: u32 = kani::any();
+    let wide = value | 0x100;
+    assert!(wide < 0x100);
+}
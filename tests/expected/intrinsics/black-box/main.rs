@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the simplifications of CBMC don't fold through `black_box`: the assertion is only
+//! proved by the solver, so it remains after the simplification of the verification conditions.
+//! If the result of `black_box` was assigned the argument directly, constant propagation would
+//! prove it, and no verification condition would remain.
+
+use std::hint::black_box;
+
+#[kani::proof]
+fn check_blackbox_not_folded() {
+    let value: u8 = 10;
+    assert!(black_box(value) == 10);
+}
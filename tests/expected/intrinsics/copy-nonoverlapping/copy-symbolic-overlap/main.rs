@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Check that `copy_nonoverlapping` reports an overlap between regions whose
+// offsets and count are symbolic, while `copy` accepts the same regions.
+#[kani::proof]
+fn test_copy_nonoverlapping_symbolic() {
+    let mut arr: [u8; 8] = kani::any();
+    let src_off: usize = kani::any_where(|off| *off < 8);
+    let dst_off: usize = kani::any_where(|off| *off < 8);
+    let count: usize = kani::any_where(|count| *count <= 8 - src_off && *count <= 8 - dst_off);
+    let ptr = arr.as_mut_ptr();
+    unsafe {
+        core::ptr::copy(ptr.add(src_off), ptr.add(dst_off), count);
+        core::ptr::copy_nonoverlapping(ptr.add(src_off), ptr.add(dst_off), count);
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `ctlz_nonzero` and `cttz_nonzero` fail when called with 0, and that `ctlz` and
+//! `cttz` return the bit width of the argument for 0.
+#![feature(core_intrinsics)]
+use std::intrinsics::{ctlz, ctlz_nonzero, cttz, cttz_nonzero};
+
+#[kani::proof]
+fn check_ctlz_nonzero() {
+    let x: u16 = kani::any();
+    let _ = unsafe { ctlz_nonzero(x) };
+}
+
+#[kani::proof]
+fn check_cttz_nonzero() {
+    let x: i64 = kani::any();
+    let _ = unsafe { cttz_nonzero(x) };
+}
+
+#[kani::proof]
+fn check_zero() {
+    assert_eq!(ctlz(0u8), 8);
+    assert_eq!(cttz(0i32), 32);
+    assert_eq!(ctlz(0u128), 128);
+    assert_eq!(cttz(0usize), usize::BITS);
+}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the properties generated by a macro are reported at the macro invocation written
+//! by the user, even if they come from nested macros. The code passed to the macro keeps its own
+//! location.
+
+macro_rules! add {
+    ($a:expr, $b:expr) => {
+        $a + $b
+    };
+}
+
+macro_rules! sum {
+    ($a:expr, $b:expr, $c:expr) => {
+        add!(add!($a, $b), $c)
+    };
+}
+
+#[kani::proof]
+fn check_nested_macro() {
+    let x: u8 = kani::any();
+    let total = sum!(x, 100, 100);
+    assert!(total >= 200);
+}
+
+#[kani::proof]
+fn check_index_in_macro() {
+    let arr = [1u8, 2, 3];
+    let idx: usize = kani::any();
+    let _ = add!(arr[idx], 1);
+}
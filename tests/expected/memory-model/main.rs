@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --memory-model=segmented -Z unstable-options
+//
+//! Check that the segmented memory model reports pointer arithmetic that moves a pointer out of
+//! its object, while still allowing one-past-the-end and dangling pointers.
+
+use std::ptr::NonNull;
+
+#[kani::proof]
+fn check_cross_object() {
+    let first = [0u8; 4];
+    let _second = [0u8; 4];
+    let offset: usize = kani::any_where(|offset| *offset <= 8);
+    // Fails for any offset larger than 4, even though the pointer is never dereferenced.
+    let _ptr = first.as_ptr().wrapping_add(offset);
+}
+
+#[kani::proof]
+fn check_in_bounds() {
+    let array = [1u16; 4];
+    let offset: usize = kani::any_where(|offset| *offset <= array.len());
+    let end = unsafe { array.as_ptr().add(offset) };
+    assert_eq!(unsafe { end.offset_from(array.as_ptr()) }, offset as isize);
+}
+
+#[kani::proof]
+fn check_dangling() {
+    let dangling = NonNull::<u64>::dangling().as_ptr();
+    let moved = dangling.wrapping_add(10);
+    assert!(!moved.is_null());
+}
@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that Kani warns about functions that are named like harnesses, but are not annotated
+//! with `#[kani::proof]`.
+
+fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+fn verify_double() {
+    let x: u8 = kani::any();
+    assert_eq!(double(x) / 2, x as u16);
+}
+
+/// Functions that take arguments cannot be harnesses, so they are not reported.
+fn verify_double_of(x: u8) {
+    assert_eq!(double(x) / 2, x as u16);
+}
+
+#[kani::proof]
+fn proof_double() {
+    verify_double();
+    verify_double_of(kani::any());
+}
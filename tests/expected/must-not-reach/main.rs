@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that `kani::must_not_reach!()` is verified to be unreachable.
+
+fn classify(x: u8) -> &'static str {
+    match x % 3 {
+        0 => "zero",
+        1 => "one",
+        2 => "two",
+        _ => kani::must_not_reach!(),
+    }
+}
+
+#[kani::proof]
+fn check_unreachable_holds() {
+    assert!(!classify(kani::any()).is_empty());
+}
+
+#[kani::proof]
+fn check_unreachable_reached() {
+    let x: u8 = kani::any();
+    if x > 100 {
+        kani::must_not_reach!("inputs are at most 100");
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that counterexamples name the concrete type behind the `impl Trait` returned by the
+//! functions that were called, including trait methods (RPITIT).
+
+trait Shape {
+    fn corners(&self) -> u8;
+}
+
+struct Square;
+
+impl Shape for Square {
+    fn corners(&self) -> u8 {
+        4
+    }
+}
+
+struct Triangle;
+
+impl Shape for Triangle {
+    fn corners(&self) -> u8 {
+        3
+    }
+}
+
+fn make_shape() -> impl Shape {
+    Triangle
+}
+
+trait Factory {
+    fn build(&self) -> impl Shape;
+}
+
+struct SquareFactory;
+
+impl Factory for SquareFactory {
+    fn build(&self) -> impl Shape {
+        Square
+    }
+}
+
+#[kani::proof]
+fn check_opaque_return() {
+    let shape = make_shape();
+    assert!(shape.corners() == 4, "every shape is a square");
+}
+
+#[kani::proof]
+fn check_rpitit() {
+    let shape = SquareFactory.build();
+    assert!(shape.corners() == 3, "every shape is a triangle");
+}
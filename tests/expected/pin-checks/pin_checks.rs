@@ -0,0 +1,72 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z pin-checks
+//! Check that `-Z pin-checks` detects pinned values that are moved out of their location, and that
+//! the location of a pinned self-referential value stays stable.
+
+use std::marker::PhantomPinned;
+use std::mem;
+use std::pin::{Pin, pin};
+use std::ptr;
+
+/// A value that points to one of its own fields once it's linked.
+struct SelfRef {
+    value: u32,
+    this: *const u32,
+    _pinned: PhantomPinned,
+}
+
+impl SelfRef {
+    fn unlinked(value: u32) -> Self {
+        SelfRef { value, this: ptr::null(), _pinned: PhantomPinned }
+    }
+
+    fn link(self: Pin<&mut Self>) {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.this = &this.value;
+    }
+
+    fn linked_value(self: Pin<&Self>) -> u32 {
+        unsafe { *self.this }
+    }
+}
+
+#[kani::proof]
+fn check_box_pin_address_stable() {
+    let mut boxed = Box::pin(SelfRef::unlinked(kani::any()));
+    boxed.as_mut().link();
+    let addr: *const SelfRef = &*boxed;
+    // Moving the box doesn't move the pinned value.
+    let moved = boxed;
+    assert_eq!(addr, &*moved as *const SelfRef);
+    assert_eq!(moved.as_ref().linked_value(), moved.value);
+}
+
+#[kani::proof]
+fn check_stack_pin() {
+    let mut pinned = pin!(SelfRef::unlinked(kani::any()));
+    pinned.as_mut().link();
+    assert_eq!(pinned.as_ref().linked_value(), pinned.value);
+}
+
+#[kani::proof]
+fn check_replace_unpinned() {
+    let mut value = SelfRef::unlinked(1);
+    let old = mem::replace(&mut value, SelfRef::unlinked(2));
+    assert_eq!(old.value + 1, value.value);
+}
+
+#[kani::proof]
+fn check_replace_pinned() {
+    let mut boxed = Box::pin(SelfRef::unlinked(1));
+    boxed.as_mut().link();
+    let inner = unsafe { boxed.as_mut().get_unchecked_mut() };
+    let _old = mem::replace(inner, SelfRef::unlinked(2));
+}
+
+#[kani::proof]
+fn check_swap_pinned() {
+    let mut first = pin!(SelfRef::unlinked(1));
+    let mut second = SelfRef::unlinked(2);
+    unsafe { mem::swap(first.as_mut().get_unchecked_mut(), &mut second) };
+}
@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z pointer-offset-checks
+//! Check that `-Z pointer-offset-checks` detects `offset`, `add` and `sub` calls that move a
+//! pointer out of bounds of its allocation, even if the result is never dereferenced.
+
+#[kani::proof]
+fn check_add_up_to_end() {
+    let arr = [1u32, 2, 3];
+    let idx: usize = kani::any_where(|idx| *idx <= 3);
+    let ptr = unsafe { arr.as_ptr().add(idx) };
+    assert_eq!(ptr as usize - arr.as_ptr() as usize, idx * 4);
+}
+
+#[kani::proof]
+fn check_add_past_end() {
+    let arr = [1u32, 2, 3];
+    let _ptr = unsafe { arr.as_ptr().add(4) };
+}
+
+#[kani::proof]
+fn check_symbolic_negative_offset() {
+    let arr = [1u8; 4];
+    let mid = unsafe { arr.as_ptr().add(2) };
+    let count: isize = kani::any_where(|count| *count >= -3 && *count <= 2);
+    let ptr = unsafe { mid.offset(count) };
+    assert!(ptr >= arr.as_ptr());
+}
+
+#[kani::proof]
+fn check_sub_before_start() {
+    let arr = [1u16; 2];
+    let _ptr = unsafe { arr.as_ptr().sub(1) };
+}
@@ -0,0 +1,95 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `proof_allocator` detects allocators that return misaligned or overlapping memory,
+//! and that it requests zero-sized layouts from implementations of `Allocator`.
+
+#![feature(allocator_api)]
+
+use std::alloc::{AllocError, Allocator, GlobalAlloc, Layout, System};
+use std::cell::{Cell, UnsafeCell};
+use std::ptr::{self, NonNull};
+
+const ARENA_SIZE: usize = 32;
+
+/// A bump allocator over a fixed arena, which can be configured to have bugs.
+struct BumpAllocator {
+    arena: UnsafeCell<[u8; ARENA_SIZE]>,
+    next: Cell<usize>,
+    /// Whether to align the allocations, which a correct allocator must do.
+    align: bool,
+    /// Whether to move past the allocations, which a correct allocator must do.
+    bump: bool,
+}
+
+impl BumpAllocator {
+    fn new() -> Self {
+        BumpAllocator {
+            arena: UnsafeCell::new([0; ARENA_SIZE]),
+            next: Cell::new(0),
+            align: true,
+            bump: true,
+        }
+    }
+
+    fn ignoring_alignment() -> Self {
+        BumpAllocator { align: false, ..BumpAllocator::new() }
+    }
+
+    fn reusing_memory() -> Self {
+        BumpAllocator { bump: false, ..BumpAllocator::new() }
+    }
+}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let base = self.arena.get() as usize;
+        let Some(mut start) = base.checked_add(self.next.get()) else { return ptr::null_mut() };
+        if self.align {
+            match start.checked_next_multiple_of(layout.align()) {
+                Some(aligned) => start = aligned,
+                None => return ptr::null_mut(),
+            }
+        }
+        let offset = start - base;
+        match offset.checked_add(layout.size()) {
+            Some(end) if end <= ARENA_SIZE => {
+                if self.bump {
+                    self.next.set(end);
+                }
+                self.arena.get().cast::<u8>().wrapping_add(offset)
+            }
+            _ => ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+/// The system allocator, which fails to allocate more than 64 bytes.
+struct SmallSystemAllocator;
+
+unsafe impl Allocator for SmallSystemAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() > 64 || layout.align() > 16 {
+            return Err(AllocError);
+        }
+        System.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { System.deallocate(ptr, layout) }
+    }
+}
+
+#[kani::proof_allocator(BumpAllocator::new())]
+fn check_bump_allocator() {}
+
+#[kani::proof_allocator(BumpAllocator::ignoring_alignment())]
+fn check_misaligned_allocator() {}
+
+#[kani::proof_allocator(BumpAllocator::reusing_memory())]
+fn check_overlapping_allocator() {}
+
+#[kani::proof_allocator(SmallSystemAllocator)]
+fn check_small_system_allocator() {}
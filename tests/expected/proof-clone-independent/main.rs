@@ -0,0 +1,70 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `proof_clone_independent` detects a clone that shares mutable state with the
+//! original value, and that it warns about types with interior mutability.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// A value whose `Clone` implementation copies the pointer instead of the data.
+struct Shallow {
+    data: *mut u8,
+}
+
+impl kani::Arbitrary for Shallow {
+    fn any() -> Self {
+        Shallow { data: Box::into_raw(Box::new(kani::any())) }
+    }
+}
+
+impl Clone for Shallow {
+    fn clone(&self) -> Self {
+        Shallow { data: self.data }
+    }
+}
+
+impl PartialEq for Shallow {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { *self.data == *other.data }
+    }
+}
+
+impl Shallow {
+    fn set(&mut self, value: u8) {
+        unsafe { *self.data = value };
+    }
+}
+
+/// A counter whose `Clone` implementation copies the shared value into a new `Rc`.
+struct Counter {
+    count: Rc<Cell<u8>>,
+}
+
+impl kani::Arbitrary for Counter {
+    fn any() -> Self {
+        Counter { count: Rc::new(Cell::new(kani::any())) }
+    }
+}
+
+impl Clone for Counter {
+    fn clone(&self) -> Self {
+        Counter { count: Rc::new(Cell::new(self.count.get())) }
+    }
+}
+
+impl PartialEq for Counter {
+    fn eq(&self, other: &Self) -> bool {
+        self.count.get() == other.count.get()
+    }
+}
+
+#[kani::proof_clone_independent(Shallow)]
+fn check_shallow(value: &mut Shallow) {
+    value.set(kani::any());
+}
+
+#[kani::proof_clone_independent(Counter)]
+fn check_counter(counter: &mut Counter) {
+    counter.count.set(counter.count.get().wrapping_add(1));
+}
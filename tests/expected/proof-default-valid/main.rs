@@ -0,0 +1,65 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `proof_default_valid` detects a `Default` implementation that violates the
+//! invariant of its type, including for a concrete instantiation of a generic type.
+
+use kani::Invariant;
+
+/// A range whose start must not be greater than its end.
+struct Range {
+    start: u8,
+    end: u8,
+}
+
+impl Default for Range {
+    fn default() -> Self {
+        Range { start: 0, end: 0 }
+    }
+}
+
+impl Invariant for Range {
+    fn is_safe(&self) -> bool {
+        self.start <= self.end
+    }
+}
+
+/// A month, which is numbered from 1, but whose derived default is 0.
+#[derive(Default)]
+struct Month(u8);
+
+impl Invariant for Month {
+    fn is_safe(&self) -> bool {
+        self.0 >= 1 && self.0 <= 12
+    }
+}
+
+/// A non-empty stack, whose default only exists if the element has a default.
+struct NonEmpty<T> {
+    first: T,
+    len: usize,
+}
+
+impl<T: Default> Default for NonEmpty<T> {
+    fn default() -> Self {
+        NonEmpty { first: T::default(), len: 1 }
+    }
+}
+
+impl<T: Invariant> Invariant for NonEmpty<T> {
+    fn is_safe(&self) -> bool {
+        self.len >= 1 && self.first.is_safe()
+    }
+}
+
+#[kani::proof_default_valid(Range)]
+fn check_range() {}
+
+#[kani::proof_default_valid(Month)]
+fn check_month() {}
+
+#[kani::proof_default_valid(NonEmpty<u8>)]
+fn check_non_empty() {}
+
+#[kani::proof_default_valid(NonEmpty<Month>)]
+fn check_non_empty_month() {}
@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `proof_display_safe` detects `Display` and `Debug` implementations that panic,
+//! including the ones that panic when the writer returns an error.
+
+use std::fmt;
+
+/// A percentage that is displayed correctly for any value.
+#[derive(kani::Arbitrary, Debug)]
+struct Percent(u8);
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+/// A day of the week that is only displayed correctly for valid values.
+#[derive(kani::Arbitrary)]
+struct Weekday(u8);
+
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        f.write_str(NAMES[self.0 as usize])
+    }
+}
+
+/// A label that panics if the writer returns an error.
+#[derive(kani::Arbitrary)]
+struct Label(bool);
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", if self.0 { "on" } else { "off" }).unwrap();
+        Ok(())
+    }
+}
+
+#[kani::proof_display_safe(Percent)]
+fn check_percent() {}
+
+#[kani::proof_display_safe(Weekday)]
+fn check_weekday() {}
+
+#[kani::proof_display_safe(Label)]
+fn check_label() {}
@@ -0,0 +1,66 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `proof_fused` detects iterators that return an item after they returned `None`,
+//! including iterators that only do so after a few more calls.
+
+use std::iter::FusedIterator;
+
+/// An iterator that yields a number of items and then stays exhausted.
+#[derive(kani::Arbitrary)]
+struct Countdown {
+    remaining: u8,
+}
+
+impl Iterator for Countdown {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            None
+        } else {
+            self.remaining -= 1;
+            Some(self.remaining)
+        }
+    }
+}
+
+impl FusedIterator for Countdown {}
+
+/// An iterator that restarts once it returned `None` twice, so the first call after the first
+/// `None` still returns `None`.
+struct Restarting {
+    remaining: u8,
+    nones: u8,
+}
+
+impl kani::Arbitrary for Restarting {
+    fn any() -> Self {
+        Restarting { remaining: kani::any(), nones: 0 }
+    }
+}
+
+impl Iterator for Restarting {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.remaining == 0 {
+            self.nones += 1;
+            if self.nones <= 2 {
+                return None;
+            }
+            self.nones = 0;
+            self.remaining = 1;
+        }
+        self.remaining -= 1;
+        Some(self.remaining)
+    }
+}
+
+impl FusedIterator for Restarting {}
+
+#[kani::proof_fused(Countdown)]
+fn check_countdown() {}
+
+#[kani::proof_fused(Restarting)]
+fn check_restarting() {}
@@ -0,0 +1,64 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `proof_future` detects futures that return `Pending` without registering the waker
+//! of their context, and that a future may panic when it's polled after it completed.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A future that completes after a number of polls, and panics if it's polled afterwards.
+struct Countdown {
+    remaining: u8,
+    done: bool,
+}
+
+impl kani::Arbitrary for Countdown {
+    fn any() -> Self {
+        Countdown { remaining: kani::any(), done: false }
+    }
+}
+
+impl Future for Countdown {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.done {
+            panic!("polled after completion");
+        }
+        if self.remaining == 0 {
+            self.done = true;
+            Poll::Ready(())
+        } else {
+            self.remaining -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// A future that returns `Pending` once, but never arranges to be polled again.
+#[derive(kani::Arbitrary)]
+struct Forgetful {
+    polled: bool,
+}
+
+impl Future for Forgetful {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.polled {
+            Poll::Ready(())
+        } else {
+            self.polled = true;
+            Poll::Pending
+        }
+    }
+}
+
+#[kani::proof_future(Countdown)]
+fn check_countdown() {}
+
+#[kani::proof_future(Forgetful)]
+fn check_forgetful() {}
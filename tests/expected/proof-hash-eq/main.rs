@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `proof_hash_eq` detects a `Hash` implementation that is inconsistent with `Eq`, and
+//! that it warns about types with interior mutability.
+
+use std::cell::Cell;
+use std::hash::{Hash, Hasher};
+
+/// A name that is compared case-insensitively, but hashed case-sensitively.
+#[derive(kani::Arbitrary)]
+struct Name {
+    initial: u8,
+}
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Self) -> bool {
+        self.initial.eq_ignore_ascii_case(&other.initial)
+    }
+}
+
+impl Eq for Name {}
+
+impl Hash for Name {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.initial.hash(state);
+    }
+}
+
+/// A counter whose hash only depends on its value, but that also tracks how often it was read.
+struct Counter {
+    value: u8,
+    reads: Cell<u8>,
+}
+
+impl kani::Arbitrary for Counter {
+    fn any() -> Self {
+        Counter { value: kani::any(), reads: Cell::new(kani::any()) }
+    }
+}
+
+impl PartialEq for Counter {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Counter {}
+
+impl Hash for Counter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.reads.set(self.reads.get().wrapping_add(1));
+        self.value.hash(state);
+    }
+}
+
+#[kani::proof_hash_eq(Name)]
+fn check_name() {}
+
+#[kani::proof_hash_eq(Counter)]
+fn check_counter() {}
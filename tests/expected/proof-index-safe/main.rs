@@ -0,0 +1,47 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_index_safe` reports an `Index` implementation that panics for an index in
+//! bounds, and one that doesn't panic for an index out of bounds.
+
+use std::ops::Index;
+
+#[derive(kani::Arbitrary)]
+struct Window {
+    items: [u8; 4],
+    len: u8,
+}
+
+impl Window {
+    fn len(&self) -> usize {
+        (self.len as usize).min(self.items.len())
+    }
+}
+
+impl Index<usize> for Window {
+    type Output = u8;
+
+    fn index(&self, index: usize) -> &u8 {
+        &self.items[..self.len()][index]
+    }
+}
+
+/// The bound accepts one index too many.
+#[kani::proof_index_safe(Window, bound = |window: &Window, index: &usize| *index <= window.len())]
+fn check_window_off_by_one() {}
+
+#[derive(kani::Arbitrary)]
+struct Cycle {
+    items: [u8; 4],
+}
+
+/// Indices past the end of the cycle wrap around instead of panicking.
+impl Index<usize> for Cycle {
+    type Output = u8;
+
+    fn index(&self, index: usize) -> &u8 {
+        &self.items[index % self.items.len()]
+    }
+}
+
+#[kani::proof_index_safe(Cycle, bound = |cycle: &Cycle, index: &usize| *index < cycle.items.len())]
+fn check_cycle_index() {}
@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_invariant_preserved` reports the methods that break the invariant of the
+//! type, and warns about the methods it cannot check.
+
+#[derive(kani::Arbitrary)]
+pub struct Percent(u8);
+
+impl kani::Invariant for Percent {
+    fn is_safe(&self) -> bool {
+        self.0 <= 100
+    }
+}
+
+impl Percent {
+    pub fn halve(&mut self) {
+        self.0 /= 2;
+    }
+
+    /// Doesn't check that the result is at most 100%.
+    pub fn add(&mut self, other: u8) {
+        self.0 = self.0.saturating_add(other);
+    }
+
+    pub fn parse(&mut self, value: &str) {
+        self.0 = value.parse().unwrap_or(0).min(100);
+    }
+}
+
+#[kani::proof_invariant_preserved(Percent)]
+fn check_percent() {}
@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_lossless` fails for conversions that lose information, and that the failure
+//! goes away when the input is restricted to the range where the conversion is lossless.
+
+#[derive(Clone, Copy, PartialEq, kani::Arbitrary)]
+struct Percent(u8);
+
+struct Ratio(u8);
+
+/// Saturates values larger than 100%.
+impl From<Percent> for Ratio {
+    fn from(value: Percent) -> Self {
+        Ratio(value.0.min(100))
+    }
+}
+
+impl TryFrom<Ratio> for Percent {
+    type Error = ();
+
+    fn try_from(value: Ratio) -> Result<Self, Self::Error> {
+        Ok(Percent(value.0))
+    }
+}
+
+#[kani::proof_lossless(From<Percent> for Ratio)]
+fn check_any_percent() {}
+
+#[kani::proof_lossless(From<Percent> for Ratio, assume = |p: &Percent| p.0 <= 100)]
+fn check_valid_percent() {}
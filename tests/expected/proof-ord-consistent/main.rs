@@ -0,0 +1,55 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `proof_ord_consistent` detects an `Ord` implementation that is inconsistent with
+//! `PartialOrd`, and that it only checks the partial order axioms for types that aren't `Ord`.
+
+use std::cmp::Ordering;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, kani::Arbitrary)]
+struct Version {
+    major: u8,
+    minor: u8,
+}
+
+/// A priority whose `cmp` was reversed to be used in a max-heap, but whose `partial_cmp` wasn't.
+#[derive(PartialEq, Eq, PartialOrd, kani::Arbitrary)]
+struct Priority {
+    level: u8,
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.level.cmp(&self.level)
+    }
+}
+
+/// A reading that is compared by its absolute value, but whose equality is derived.
+#[derive(PartialEq, kani::Arbitrary)]
+struct Reading {
+    value: i8,
+}
+
+impl PartialOrd for Reading {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.unsigned_abs().partial_cmp(&other.value.unsigned_abs())
+    }
+}
+
+/// A temperature that contains a float, so some values are incomparable.
+#[derive(PartialEq, PartialOrd, kani::Arbitrary)]
+struct Temperature {
+    celsius: f32,
+}
+
+#[kani::proof_ord_consistent(Version)]
+fn check_version() {}
+
+#[kani::proof_ord_consistent(Priority)]
+fn check_priority() {}
+
+#[kani::proof_ord_consistent(Reading)]
+fn check_reading() {}
+
+#[kani::proof_ord_consistent(Temperature)]
+fn check_temperature() {}
@@ -0,0 +1,37 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_over` can verify a harness over the boundary values of a const parameter,
+//! and that the results name the value that failed.
+
+/// A buffer that is implicitly expected to have at least one element.
+struct Buffer<const N: usize> {
+    data: [u8; N],
+}
+
+impl<const N: usize> Buffer<N> {
+    fn first(&self) -> u8 {
+        self.data[0]
+    }
+
+    fn first_or_zero(&self) -> u8 {
+        self.data.first().copied().unwrap_or(0)
+    }
+}
+
+#[kani::proof_over(N = boundary)]
+fn check_first<const N: usize>() {
+    let buffer = Buffer::<N> { data: kani::any() };
+    let _ = buffer.first();
+}
+
+#[kani::proof_over(N = boundary)]
+fn check_first_or_zero<const N: usize>() {
+    let buffer = Buffer::<N> { data: [1; N] };
+    assert_eq!(buffer.first_or_zero(), if N == 0 { 0 } else { 1 });
+}
+
+#[kani::proof_over(OFFSET = boundary)]
+fn check_signed<const OFFSET: i8>() {
+    let value: i8 = kani::any_where(|value| *value >= 0 && *value < 100);
+    assert!(value + OFFSET >= 0, "negative offset");
+}
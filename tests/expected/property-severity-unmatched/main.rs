@@ -0,0 +1,11 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --property-severity arithmetic_overflw=warning
+//! Check that Kani warns about a `--property-severity` class that matches no property, such as
+//! a misspelled one, which leaves the severity of the intended properties unchanged.
+
+#[kani::proof]
+fn check_overflow() {
+    let x: u8 = kani::any();
+    let _ = x + 1;
+}
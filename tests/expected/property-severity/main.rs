@@ -0,0 +1,18 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --property-severity arithmetic_overflow=warning
+//! Check that the failures of properties with the `warning` severity are reported, but don't
+//! fail the verification, while the other properties are still errors.
+
+#[kani::proof]
+fn check_overflow_is_warning() {
+    let x: u8 = kani::any();
+    let _ = x + 1;
+}
+
+#[kani::proof]
+fn check_assertion_is_error() {
+    let x: u8 = kani::any();
+    let y = x + 1;
+    assert!(y != 10, "y is never 10");
+}
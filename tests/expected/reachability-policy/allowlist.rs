@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --allowlist clamp
+//
+//! Check that the functions of the crate that are not in the `--allowlist` are not analyzed, and
+//! that they return a nondeterministic value instead. Functions whose return type doesn't
+//! implement `kani::Arbitrary` are still analyzed.
+
+fn limit() -> u8 {
+    100
+}
+
+fn clamp(value: u8) -> u8 {
+    let limit = limit();
+    if value > limit { limit } else { value }
+}
+
+fn first(data: &[u8]) -> &u8 {
+    &data[0]
+}
+
+#[kani::proof]
+fn check_clamp() {
+    let value = kani::any();
+    let clamped = clamp(value);
+    assert!(clamped <= value, "clamp never increases its input");
+    kani::cover!(clamped > 100, "the limit is unknown");
+}
+
+#[kani::proof]
+fn check_first() {
+    let data = [1, 2, 3];
+    assert!(*first(&data) == 1, "first is analyzed");
+}
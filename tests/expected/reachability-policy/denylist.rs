@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --denylist parse::checksum
+//
+//! Check that a function in the `--denylist` is not analyzed, and that it returns a
+//! nondeterministic value instead.
+
+mod parse {
+    pub fn checksum(data: &[u8]) -> u32 {
+        data.iter().fold(0, |acc: u32, byte| acc.wrapping_mul(31).wrapping_add(*byte as u32))
+    }
+
+    pub fn is_valid(data: &[u8]) -> bool {
+        checksum(data) == 0xcafe
+    }
+}
+
+#[kani::proof]
+fn check_is_valid() {
+    let data = [1, 2, 3];
+    let valid = parse::is_valid(&data);
+    kani::cover!(valid, "valid checksum");
+    kani::cover!(!valid, "invalid checksum");
+}
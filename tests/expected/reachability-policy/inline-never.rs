@@ -0,0 +1,62 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options -Z function-contracts --inline-never-boundaries
+//
+//! Check that the functions marked `#[inline(never)]` are replaced by their contract with
+//! `--inline-never-boundaries`, and that the ones without a contract return a nondeterministic
+//! value and are reported. Functions without a contract that return a `bool` are pruned, but the
+//! ones that return a reference are analyzed instead, since `kani::any()` can't return one.
+
+#[kani::requires(x < 100)]
+#[kani::ensures(|result: &u32| *result > x)]
+#[inline(never)]
+fn next(x: u32) -> u32 {
+    x + 1
+}
+
+#[inline(never)]
+fn checksum(data: &[u8]) -> u32 {
+    data.iter().fold(0, |acc: u32, byte| acc.wrapping_mul(31).wrapping_add(*byte as u32))
+}
+
+#[inline(never)]
+fn is_even(x: u32) -> bool {
+    x % 2 == 0
+}
+
+#[inline(never)]
+fn largest(data: &[u8; 3]) -> &u8 {
+    data.iter().max().unwrap()
+}
+
+fn advance(x: u32) -> u32 {
+    let next = next(x);
+    // Only holds for the original body of `next`.
+    kani::cover!(next != x + 1, "next is not the successor");
+    next
+}
+
+#[kani::proof]
+fn check_advance() {
+    let x = kani::any_where(|x: &u32| *x < 50);
+    assert!(advance(x) > x);
+}
+
+#[kani::proof]
+fn check_checksum() {
+    let data = [1, 2, 3];
+    kani::cover!(checksum(&data) == 0xcafe, "valid checksum");
+}
+
+#[kani::proof]
+fn check_is_even() {
+    let x: u32 = kani::any();
+    let even = is_even(x);
+    kani::cover!(even && x == 1, "one is even");
+}
+
+#[kani::proof]
+fn check_largest() {
+    let data = [1, 5, 3];
+    assert!(*largest(&data) == 5, "largest is analyzed");
+}
@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the safety precondition of an `unsafe fn` is checked at each of its call sites,
+//! including the calls in safe wrappers and in methods, instead of being assumed.
+
+#[kani::safety_requires(index < slice.len())]
+unsafe fn get_unchecked(slice: &[u8], index: usize) -> u8 {
+    unsafe { *slice.as_ptr().add(index) }
+}
+
+/// A safe wrapper that establishes the precondition.
+fn get(slice: &[u8], index: usize) -> Option<u8> {
+    if index < slice.len() { Some(unsafe { get_unchecked(slice, index) }) } else { None }
+}
+
+struct Buffer {
+    data: [u8; 4],
+}
+
+impl Buffer {
+    #[kani::safety_requires(len <= self.data.len())]
+    unsafe fn prefix(&self, len: usize) -> &[u8] {
+        unsafe { self.data.get_unchecked(..len) }
+    }
+}
+
+#[kani::proof]
+fn check_safe_wrapper() {
+    let data: [u8; 4] = kani::any();
+    let index: usize = kani::any();
+    if let Some(byte) = get(&data, index) {
+        assert_eq!(byte, data[index]);
+    }
+}
+
+#[kani::proof]
+fn check_method() {
+    let buffer = Buffer { data: kani::any() };
+    let len: usize = kani::any_where(|len| *len <= 4);
+    let prefix = unsafe { buffer.prefix(len) };
+    assert_eq!(prefix.len(), len);
+}
+
+/// The first call establishes the precondition, but the second one doesn't when `index == 3`.
+#[kani::proof]
+fn check_violated_by_caller() {
+    let data: [u8; 4] = kani::any();
+    let index: usize = kani::any_where(|index| *index < 4);
+    let _ = unsafe { get_unchecked(&data, index) };
+    let _ = unsafe { get_unchecked(&data, index + 1) };
+}
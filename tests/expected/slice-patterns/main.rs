@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that slice patterns with rest bindings are verified over slices with a symbolic length,
+//! and that an arm whose pattern requires more elements than the slice can have is unreachable.
+
+fn first_and_last(values: &[u8]) -> Option<(u8, u8)> {
+    match values {
+        [first, .., last] => Some((*first, *last)),
+        [only] => Some((*only, *only)),
+        [] => None,
+    }
+}
+
+fn split_first(values: &[u8]) -> Option<(u8, &[u8])> {
+    match values {
+        [first, rest @ ..] => Some((*first, rest)),
+        [] => None,
+    }
+}
+
+#[kani::proof]
+fn check_first_and_last() {
+    let array: [u8; 3] = kani::any();
+    let slice = kani::slice::any_slice_of_array(&array);
+    match first_and_last(slice) {
+        Some((first, last)) => {
+            assert_eq!(first, slice[0]);
+            assert_eq!(last, slice[slice.len() - 1]);
+        }
+        None => assert!(slice.is_empty()),
+    }
+}
+
+#[kani::proof]
+fn check_split_first() {
+    let array: [u8; 3] = kani::any();
+    let slice = kani::slice::any_slice_of_array(&array);
+    if let Some((first, rest)) = split_first(slice) {
+        assert_eq!(first, slice[0]);
+        assert_eq!(rest.len(), slice.len() - 1);
+    }
+}
+
+#[kani::proof]
+fn check_too_long_pattern() {
+    let array: [u8; 3] = kani::any();
+    let slice = kani::slice::any_slice_of_array(&array);
+    if let [_, _, _, fourth, ..] = slice {
+        kani::cover!(*fourth == 0, "the fourth element is reachable");
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `kani::transition!` fails the verification when a transition that isn't declared
+//! by `#[kani::state_machine]` is feasible, including transitions guarded by a nondeterministic
+//! condition.
+
+#[kani::state_machine(transitions = [
+    Idle => Connecting,
+    Connecting => Connected,
+    _ => Idle,
+])]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum State {
+    Idle,
+    Connecting,
+    Connected(u32),
+}
+
+struct Client {
+    state: State,
+}
+
+impl Client {
+    fn set_state(&mut self, next: State) {
+        kani::transition!(self.state, next);
+        self.state = next;
+    }
+
+    fn connect(&mut self, accepted: bool, id: u32) {
+        match self.state {
+            State::Idle => self.set_state(State::Connecting),
+            State::Connecting if accepted => self.set_state(State::Connected(id)),
+            State::Connecting => self.set_state(State::Idle),
+            State::Connected(_) => {}
+        }
+    }
+
+    /// Goes back to `Connecting` without going through `Idle`, which isn't declared.
+    fn reconnect(&mut self) {
+        if let State::Connected(_) = self.state {
+            self.set_state(State::Connecting);
+        }
+    }
+
+    fn close(&mut self) {
+        self.set_state(State::Idle);
+    }
+}
+
+#[kani::proof]
+fn check_connect() {
+    let mut client = Client { state: State::Idle };
+    client.connect(kani::any(), kani::any());
+    client.connect(kani::any(), kani::any());
+    client.close();
+    assert_eq!(client.state, State::Idle);
+}
+
+#[kani::proof]
+fn check_reconnect() {
+    let mut client = Client { state: State::Idle };
+    client.connect(kani::any(), kani::any());
+    client.connect(kani::any(), kani::any());
+    if kani::any() {
+        client.reconnect();
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `#[kani::terminates]` counts loop iterations and recursive calls, and that the
+//! failure reports the input that exceeds the bound.
+
+#[kani::terminates(within = 5)]
+fn sum(n: u8) -> u32 {
+    let mut total = 0;
+    for i in 0..n {
+        total += i as u32;
+    }
+    total
+}
+
+#[kani::terminates(within = 10)]
+fn count_down(n: u8) -> u8 {
+    if n == 0 { 0 } else { count_down(n - 1) }
+}
+
+/// Never terminates if `x` can't reach zero by subtracting `step`.
+#[kani::terminates(within = 8)]
+fn drain(mut x: u8, step: u8) -> u8 {
+    let mut steps = 0;
+    while x != 0 {
+        x = x.wrapping_sub(step);
+        steps += 1;
+    }
+    steps
+}
+
+#[kani::proof]
+fn check_loop_within_bound() {
+    let n = kani::any_where(|n| *n <= 5);
+    assert!(sum(n) <= 10);
+}
+
+#[kani::proof]
+fn check_recursion_within_bound() {
+    // Each call resets the counter once the previous outermost call returns.
+    assert_eq!(count_down(kani::any_where(|n| *n <= 10)), 0);
+    assert_eq!(count_down(10), 0);
+}
+
+#[kani::proof]
+fn check_recursion_exceeds_bound() {
+    count_down(kani::any_where(|n| *n == 11));
+}
+
+#[kani::proof]
+fn check_loop_exceeds_bound() {
+    drain(3, kani::any());
+}
@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the state of a type-state machine is rendered when a property fails.
+
+use std::marker::PhantomData;
+
+struct Closed;
+struct Open;
+
+struct Door<State> {
+    opened: u8,
+    _state: PhantomData<State>,
+}
+
+impl Door<Closed> {
+    fn new() -> Self {
+        Door { opened: 0, _state: PhantomData }
+    }
+
+    fn open(self) -> Door<Open> {
+        Door { opened: self.opened + 1, _state: PhantomData }
+    }
+}
+
+impl Door<Open> {
+    fn close(self) -> Door<Closed> {
+        Door { opened: self.opened, _state: PhantomData }
+    }
+}
+
+#[kani::proof]
+fn check_door() {
+    let door = Door::new();
+    let door = door.open();
+    if kani::any() {
+        let door = door.close();
+        assert!(door.opened == 1);
+    } else {
+        assert!(door.opened == 0, "the door was never opened");
+    }
+}
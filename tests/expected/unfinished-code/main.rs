@@ -0,0 +1,48 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --check-unfinished-code
+//
+//! Check that `--check-unfinished-code` reports the `todo!()` and `unimplemented!()` calls that
+//! are reachable, separately from other panics.
+
+enum Shape {
+    Circle(u8),
+    Square(u8),
+    Triangle(u8),
+}
+
+fn area(shape: &Shape) -> u32 {
+    match shape {
+        Shape::Circle(_) => todo!(),
+        Shape::Square(side) => *side as u32 * *side as u32,
+        Shape::Triangle(_) => unimplemented!(),
+    }
+}
+
+fn perimeter(shape: &Shape) -> u32 {
+    match shape {
+        Shape::Square(side) => 4 * *side as u32,
+        _ => todo!(),
+    }
+}
+
+#[kani::proof]
+fn check_area() {
+    let side: u8 = kani::any();
+    let shape = if kani::any() { Shape::Circle(side) } else { Shape::Triangle(side) };
+    area(&shape);
+}
+
+#[kani::proof]
+fn check_perimeter() {
+    let side: u8 = kani::any();
+    assert!(perimeter(&Shape::Square(side)) >= side as u32);
+}
+
+#[kani::proof]
+#[kani::should_panic]
+fn check_panic_is_not_unfinished() {
+    let side: u8 = kani::any();
+    kani::assume(side > 0);
+    assert!(area(&Shape::Square(side)) == 0);
+}
@@ -0,0 +1,56 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z uninit-checks
+
+//! Checks that Kani reports which bytes are uninitialized when `MaybeUninit::assume_init` and
+//! its variants are called on a value that is not fully initialized.
+
+use std::mem::MaybeUninit;
+use std::ptr::addr_of_mut;
+
+#[repr(C)]
+struct Header {
+    tag: u8,
+    len: u32,
+    flags: u16,
+}
+
+/// Initialize the fields of the header one by one.
+unsafe fn init_fields(header: *mut Header, init_flags: bool) {
+    addr_of_mut!((*header).tag).write(1);
+    addr_of_mut!((*header).len).write(10);
+    if init_flags {
+        addr_of_mut!((*header).flags).write(0);
+    }
+}
+
+#[kani::proof]
+fn check_assume_init_all_fields() {
+    let mut header = MaybeUninit::<Header>::uninit();
+    let header = unsafe {
+        init_fields(header.as_mut_ptr(), true);
+        header.assume_init()
+    };
+    assert_eq!(header.len, 10);
+}
+
+#[kani::proof]
+fn check_assume_init_missing_field() {
+    let mut header = MaybeUninit::<Header>::uninit();
+    let _header = unsafe {
+        init_fields(header.as_mut_ptr(), false);
+        header.assume_init() // ~ERROR: `flags` is uninitialized.
+    };
+}
+
+#[kani::proof]
+fn check_assume_init_new() {
+    let value = unsafe { MaybeUninit::new(42u32).assume_init() };
+    assert_eq!(value, 42);
+}
+
+#[kani::proof]
+fn check_assume_init_ref_uninit() {
+    let value = MaybeUninit::<u32>::uninit();
+    let _value = unsafe { value.assume_init_ref() }; // ~ERROR: `value` is uninitialized.
+}
@@ -175,3 +175,57 @@ unsafe fn helper(u1: U, u2: U) {
     let u2 = U { b: 0 }; // `u2` is initialized for 4 bytes.
     helper(u1, u2);
 }
+
+/// Taking the address of a union doesn't mark its padding as initialized.
+#[kani::proof]
+unsafe fn union_address_of_should_pass() {
+    let u = U { a: 0 };
+    let _ptr = addr_of!(u);
+    let non_padding = u.a;
+    assert!(non_padding == 0);
+}
+
+/// Taking the address of a union doesn't mark its padding as initialized.
+#[kani::proof]
+unsafe fn union_address_of_should_fail() {
+    let u = U { a: 0 };
+    let _ptr = addr_of!(u);
+    let padding = u.b;
+}
+
+/// Reading non-padding data via simple union access if union is returned from another function.
+#[kani::proof]
+unsafe fn union_return_should_pass() {
+    fn make() -> U {
+        U { b: 0 } // `u` is initialized for 4 bytes.
+    }
+    let u = make();
+    let non_padding = u.b;
+    assert!(non_padding == 0);
+}
+
+/// Reading padding data via simple union access if union is returned from another function.
+#[kani::proof]
+unsafe fn union_return_should_fail() {
+    fn make() -> U {
+        U { a: 0 } // `u` is initialized for 2 bytes.
+    }
+    let u = make();
+    let padding = u.b; // Read 4 bytes from `u`.
+}
+
+#[repr(C)]
+struct Pair {
+    first: u32,
+    second: u16,
+}
+
+/// Taking the address of a field behind a pointer to the uninitialized value of a `MaybeUninit`
+/// doesn't read the value.
+#[kani::proof]
+unsafe fn union_field_address_should_pass() {
+    let pair = std::mem::MaybeUninit::<Pair>::uninit();
+    let ptr = pair.as_ptr();
+    let second = &raw const (*ptr).second;
+    assert!(second.cast::<u8>() == ptr.cast::<u8>().add(4));
+}
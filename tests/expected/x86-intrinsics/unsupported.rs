@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the vendor intrinsics without a model are reported with the name of the LLVM
+//! intrinsic that implements them.
+#![cfg(target_arch = "x86_64")]
+
+use std::arch::x86_64::*;
+
+#[kani::proof]
+fn check_rcp_ps() {
+    let a: f32 = kani::any();
+    let result: [f32; 4] = unsafe { std::mem::transmute(_mm_rcp_ps(_mm_set1_ps(a))) };
+    assert!(result[0] != 0.0);
+}
@@ -0,0 +1,69 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the arithmetic on nondeterministic `Duration` values matches the arithmetic on
+//! their number of nanoseconds, including the overflow of the checked and saturating methods.
+
+use std::time::Duration;
+
+fn nanos(duration: Duration) -> u128 {
+    duration.as_secs() as u128 * 1_000_000_000 + duration.subsec_nanos() as u128
+}
+
+#[kani::proof]
+fn check_any_duration_is_valid() {
+    let duration: Duration = kani::any();
+    assert!(duration.subsec_nanos() < 1_000_000_000);
+    assert_eq!(duration.as_nanos(), nanos(duration));
+}
+
+#[kani::proof]
+fn check_checked_add() {
+    let lhs: Duration = kani::any();
+    let rhs: Duration = kani::any();
+    let sum = nanos(lhs) + nanos(rhs);
+    match lhs.checked_add(rhs) {
+        Some(result) => assert_eq!(nanos(result), sum),
+        None => assert!(sum > nanos(Duration::MAX)),
+    }
+}
+
+#[kani::proof]
+fn check_saturating_add() {
+    let lhs: Duration = kani::any();
+    let rhs: Duration = kani::any();
+    let sum = nanos(lhs) + nanos(rhs);
+    let result = lhs.saturating_add(rhs);
+    assert_eq!(nanos(result), sum.min(nanos(Duration::MAX)));
+}
+
+#[kani::proof]
+fn check_checked_sub() {
+    let lhs: Duration = kani::any();
+    let rhs: Duration = kani::any();
+    match lhs.checked_sub(rhs) {
+        Some(result) => assert_eq!(nanos(result), nanos(lhs) - nanos(rhs)),
+        None => assert!(lhs < rhs),
+    }
+}
+
+#[kani::proof]
+fn check_checked_mul() {
+    let duration: Duration = kani::any();
+    let factor: u32 = kani::any();
+    let product = nanos(duration) * factor as u128;
+    match duration.checked_mul(factor) {
+        Some(result) => assert_eq!(nanos(result), product),
+        None => assert!(product > nanos(Duration::MAX)),
+    }
+}
+
+#[kani::proof]
+fn check_new_carries_nanos() {
+    let secs: u64 = kani::any();
+    let nanos: u32 = kani::any();
+    kani::assume(secs <= u64::MAX - 4);
+    let duration = Duration::new(secs, nanos);
+    assert_eq!(duration.as_secs(), secs + (nanos / 1_000_000_000) as u64);
+    assert_eq!(duration.subsec_nanos(), nanos % 1_000_000_000);
+}
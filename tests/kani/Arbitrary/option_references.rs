@@ -0,0 +1,63 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `kani::any()` can generate optional references, which are represented with the
+//! null pointer optimization, and that the mutable ones are unique.
+
+extern crate kani;
+use kani::arena::Arena;
+use std::mem::{size_of, transmute};
+
+#[kani::proof]
+fn check_option_ref_niche() {
+    assert_eq!(size_of::<Option<&u32>>(), size_of::<&u32>());
+    let value: Option<&u32> = kani::any();
+    let ptr: *const u32 = unsafe { transmute(value) };
+    assert_eq!(ptr.is_null(), value.is_none());
+    kani::cover!(value.is_none());
+    kani::cover!(value.is_some_and(|v| *v == 10));
+}
+
+#[kani::proof]
+fn check_option_mut_is_unique() {
+    let first: Option<&mut u8> = kani::any();
+    let second: Option<&mut u8> = kani::any();
+    if let (Some(a), Some(b)) = (first, second) {
+        assert!(!std::ptr::eq(a, b));
+        *a = 1;
+        *b = 2;
+        assert_eq!(*a, 1);
+    }
+}
+
+#[kani::proof]
+fn check_arena_option_ref() {
+    let arena: Arena<u8, 2> = Arena::new();
+    let first = arena.any_option_ref();
+    let second = arena.any_option_ref();
+    kani::cover!(first.is_none());
+    kani::cover!(matches!((first, second), (Some(a), Some(b)) if std::ptr::eq(a, b)));
+}
+
+#[kani::proof]
+fn check_arena_option_mut() {
+    let arena: Arena<u8, 1> = Arena::new();
+    let first = arena.any_option_mut();
+    let second = arena.any_option_mut();
+    // A single value can't be handed out twice.
+    assert!(first.is_none() || second.is_none());
+    kani::cover!(first.is_none() && second.is_some());
+    if let Some(value) = first {
+        *value = 7;
+        assert!(arena.any_option_ref().is_none());
+    }
+}
+
+#[kani::proof]
+fn check_arena_option_mut_after_ref() {
+    let arena: Arena<u8, 1> = Arena::new();
+    let shared = arena.any_ref();
+    // The only value is shared, so it can't be handed out mutably.
+    assert!(arena.any_option_mut().is_none());
+    kani::cover!(*shared == 3);
+}
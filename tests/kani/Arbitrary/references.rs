@@ -0,0 +1,72 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that `kani::any()` can generate references, and that references generated from an
+//! arena may alias only when it is allowed.
+
+extern crate kani;
+use kani::arena::Arena;
+
+fn swap_if_greater(a: &mut u8, b: &mut u8) {
+    if *a > *b {
+        std::mem::swap(a, b);
+    }
+}
+
+#[kani::proof]
+fn check_any_ref() {
+    let value: &u8 = kani::any();
+    let other: &u8 = kani::any();
+    assert!(!std::ptr::eq(value, other));
+    kani::cover!(*value == *other);
+}
+
+#[kani::proof]
+fn check_any_mut_is_unique() {
+    let a: &mut u8 = kani::any();
+    let b: &mut u8 = kani::any();
+    swap_if_greater(a, b);
+    assert!(*a <= *b);
+}
+
+#[kani::proof]
+fn check_arena_shared_refs_may_alias() {
+    let arena: Arena<u8, 2> = Arena::new();
+    let first = arena.any_ref();
+    let second = arena.any_ref();
+    kani::cover!(std::ptr::eq(first, second));
+    kani::cover!(!std::ptr::eq(first, second));
+}
+
+#[kani::proof]
+fn check_arena_mut_refs_do_not_alias() {
+    let arena: Arena<u8, 3> = Arena::new();
+    let first = arena.any_mut();
+    let second = arena.any_mut();
+    assert!(!std::ptr::eq(first, second));
+    assert!(!std::ptr::eq(arena.any_ref(), first));
+    *first = 1;
+    *second = 2;
+    assert_eq!(*first, 1);
+}
+
+#[kani::proof]
+fn check_arena_mut_ref_does_not_alias_shared_ref() {
+    let arena: Arena<u8, 2> = Arena::new();
+    let shared = arena.any_ref();
+    let value = *shared;
+    let unique = arena.any_mut();
+    assert!(!std::ptr::eq(shared, unique));
+    *unique = value.wrapping_add(1);
+    assert_eq!(*shared, value);
+}
+
+#[kani::proof]
+fn check_arena_mut_refs_aliasing() {
+    let arena: Arena<u8, 2> = Arena::new();
+    let first = unsafe { arena.any_mut_aliasing() };
+    let second = unsafe { arena.any_mut_aliasing() } as *mut u8;
+    *first = 1;
+    unsafe { *second = 2 };
+    kani::cover!(*first == 2);
+}
@@ -0,0 +1,82 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani can verify trait methods whose signature uses generic associated types,
+//! including ones whose value depends on the lifetime of the caller.
+
+/// An iterator that lends items which borrow from the iterator itself.
+trait LendingIterator {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+/// Iterate over mutable windows of a slice.
+struct WindowsMut<'s> {
+    slice: &'s mut [u8],
+    start: usize,
+}
+
+impl<'s> LendingIterator for WindowsMut<'s> {
+    type Item<'a>
+        = &'a mut [u8]
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        let window = self.slice.get_mut(self.start..self.start + 2)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+/// A trait with a GAT that is parameterized by a type.
+trait Container {
+    type Wrapped<T>;
+
+    fn wrap<T>(&self, value: T) -> Self::Wrapped<T>;
+}
+
+struct OptionContainer;
+
+impl Container for OptionContainer {
+    type Wrapped<T> = Option<T>;
+
+    fn wrap<T>(&self, value: T) -> Option<T> {
+        Some(value)
+    }
+}
+
+fn sum_windows<I>(iter: &mut I) -> u32
+where
+    I: for<'a> LendingIterator<Item<'a> = &'a mut [u8]>,
+{
+    let mut sum = 0;
+    while let Some(window) = iter.next() {
+        window[1] = window[0];
+        sum += window[1] as u32;
+    }
+    sum
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_lending_iterator() {
+    let mut data: [u8; 3] = kani::any();
+    let first = data[0];
+    let mut iter = WindowsMut { slice: &mut data, start: 0 };
+    let sum = sum_windows(&mut iter);
+    assert_eq!(sum, 2 * first as u32);
+    assert_eq!(data, [first; 3]);
+}
+
+#[kani::proof]
+fn check_type_gat() {
+    let value: u8 = kani::any();
+    let container = OptionContainer;
+    assert_eq!(container.wrap(value), Some(value));
+    let wrap: fn(&OptionContainer, u8) -> Option<u8> = OptionContainer::wrap;
+    assert_eq!(wrap(&container, value), Some(value));
+}
@@ -0,0 +1,42 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_clone_independent` succeeds for types that own their data.
+
+#[derive(Clone, PartialEq, kani::Arbitrary)]
+struct Pixel {
+    red: u8,
+    green: u8,
+    blue: u8,
+}
+
+impl Pixel {
+    fn invert(&mut self) {
+        self.red = !self.red;
+        self.green = !self.green;
+        self.blue = !self.blue;
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct Row {
+    pixels: Vec<Pixel>,
+}
+
+impl kani::Arbitrary for Row {
+    fn any() -> Self {
+        Row { pixels: vec![kani::any(), kani::any()] }
+    }
+}
+
+#[kani::proof_clone_independent(Pixel)]
+fn check_pixel(pixel: &mut Pixel) {
+    pixel.invert();
+}
+
+#[kani::proof_clone_independent(Row)]
+#[kani::unwind(3)]
+fn check_row(row: &mut Row) {
+    let idx: usize = kani::any_where(|idx| *idx < row.pixels.len());
+    row.pixels[idx].invert();
+    row.pixels.push(kani::any());
+}
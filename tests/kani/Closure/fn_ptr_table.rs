@@ -0,0 +1,62 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that the bodies of closures coerced to function pointers are verified, including when
+//! the pointer is selected from an array with a symbolic index.
+
+const OPS: [fn(u8) -> u8; 3] = [|x| x / 2, |x| x.saturating_add(1), |x| x.min(10)];
+
+static CHECKS: [fn(u8) -> bool; 2] = [|x| x < 200, |x| x % 2 == 0];
+
+fn apply(ops: &[fn(u8) -> u8], idx: usize, input: u8) -> u8 {
+    ops[idx](input)
+}
+
+#[track_caller]
+fn caller_line(x: u8) -> u32 {
+    let _ = x;
+    std::panic::Location::caller().line()
+}
+
+#[kani::proof]
+fn check_runtime_table() {
+    let ops: [fn(u8) -> u8; 3] = [|x| x.wrapping_mul(2), |x| x ^ 0xFF, |x| x.wrapping_sub(1)];
+    let idx: usize = kani::any_where(|idx| *idx < ops.len());
+    let input: u8 = kani::any();
+    let output = apply(&ops, idx, input);
+    match idx {
+        0 => assert_eq!(output, input.wrapping_mul(2)),
+        1 => assert_eq!(output, !input),
+        _ => assert_eq!(output, input.wrapping_sub(1)),
+    }
+}
+
+#[kani::proof]
+fn check_const_table() {
+    let idx: usize = kani::any_where(|idx| *idx < OPS.len());
+    let input: u8 = kani::any_where(|input| *input > 20);
+    let output = apply(&OPS, idx, input);
+    assert!(output <= input.saturating_add(1));
+    kani::cover!(output == 10);
+}
+
+#[kani::proof]
+fn check_static_table() {
+    let idx: usize = kani::any_where(|idx| *idx < CHECKS.len());
+    let input: u8 = kani::any_where(|input| *input == 100);
+    assert!(CHECKS[idx](input));
+}
+
+/// A closure that panics must not be silently skipped.
+#[kani::proof]
+#[kani::should_panic]
+fn check_panicking_closure() {
+    let ops: [fn(u8) -> u8; 2] = [|x| x, |x| x + 1];
+    let idx: usize = kani::any_where(|idx| *idx < 2);
+    apply(&ops, idx, u8::MAX);
+}
+
+#[kani::proof]
+fn check_track_caller_ptr() {
+    let f: fn(u8) -> u32 = caller_line;
+    assert!(f(kani::any()) > 0);
+}
@@ -0,0 +1,85 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani verifies the `Deref` implementations that are reached through chains of deref
+//! coercions, including chains that end in a trait object.
+
+use std::ops::{Deref, DerefMut};
+
+/// A name whose content can only grow.
+struct Name {
+    content: String,
+    derefs: std::cell::Cell<u8>,
+}
+
+impl Deref for Name {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        self.derefs.set(self.derefs.get() + 1);
+        &self.content
+    }
+}
+
+impl DerefMut for Name {
+    fn deref_mut(&mut self) -> &mut String {
+        self.derefs.set(self.derefs.get() + 1);
+        &mut self.content
+    }
+}
+
+fn len_of(name: &str) -> usize {
+    name.len()
+}
+
+#[kani::proof]
+fn check_box_to_str() {
+    let len: u8 = kani::any_where(|len| *len <= 4);
+    let name = Box::new(Name { content: "a".repeat(len as usize), derefs: Default::default() });
+    // `&Box<Name> -> &Name -> &String -> &str`
+    assert_eq!(len_of(&name), len as usize);
+    // The method of `str` is called through the same chain.
+    assert!(name.is_char_boundary(len as usize));
+    assert_eq!(name.derefs.get(), 2);
+}
+
+#[kani::proof]
+fn check_box_to_str_mut() {
+    let mut name = Box::new(Name { content: String::from("ab"), derefs: Default::default() });
+    // `&mut Box<Name> -> &mut Name -> &mut String -> &mut str`
+    name.make_ascii_uppercase();
+    name.push('c');
+    assert_eq!(name.content, "ABc");
+    assert_eq!(name.derefs.get(), 2);
+}
+
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+struct Square(u16);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 as u32 * self.0 as u32
+    }
+}
+
+/// A wrapper that derefs to a trait object.
+struct AnyShape(Box<dyn Shape>);
+
+impl Deref for AnyShape {
+    type Target = dyn Shape;
+
+    fn deref(&self) -> &(dyn Shape + 'static) {
+        &*self.0
+    }
+}
+
+#[kani::proof]
+fn check_box_to_dyn() {
+    let side: u16 = kani::any();
+    let shape = Box::new(AnyShape(Box::new(Square(side))));
+    // `&Box<AnyShape> -> &AnyShape -> &dyn Shape`
+    assert_eq!(shape.area(), side as u32 * side as u32);
+}
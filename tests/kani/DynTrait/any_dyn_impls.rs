@@ -0,0 +1,84 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `kani::any()` creates the trait objects registered with `#[kani::dyn_impls]`.
+
+#[kani::dyn_impls(Shape = [Circle, Square, Empty], Named + Send = [Circle])]
+trait Shape {
+    fn area(&self) -> u32;
+    fn id(&self) -> u8;
+}
+
+trait Named {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(kani::Arbitrary)]
+struct Circle {
+    radius: u8,
+}
+
+#[derive(kani::Arbitrary)]
+struct Square {
+    side: u8,
+}
+
+#[derive(kani::Arbitrary)]
+struct Empty;
+
+impl Shape for Circle {
+    fn area(&self) -> u32 {
+        3 * self.radius as u32 * self.radius as u32
+    }
+    fn id(&self) -> u8 {
+        0
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.side as u32 * self.side as u32
+    }
+    fn id(&self) -> u8 {
+        1
+    }
+}
+
+impl Shape for Empty {
+    fn area(&self) -> u32 {
+        0
+    }
+    fn id(&self) -> u8 {
+        2
+    }
+}
+
+impl Named for Circle {
+    fn name(&self) -> &'static str {
+        "circle"
+    }
+}
+
+#[kani::proof]
+fn check_any_shape() {
+    let shape: Box<dyn Shape> = kani::any();
+    assert!(shape.id() <= 2);
+    assert!(shape.area() <= 3 * 255 * 255);
+    if shape.id() == 2 {
+        assert_eq!(shape.area(), 0);
+    }
+}
+
+#[kani::proof]
+fn check_every_shape() {
+    let shape: Box<dyn Shape> = kani::any();
+    kani::cover!(shape.id() == 0);
+    kani::cover!(shape.id() == 1);
+    kani::cover!(shape.id() == 2);
+}
+
+#[kani::proof]
+fn check_named() {
+    let named: Box<dyn Named + Send> = kani::any();
+    assert_eq!(named.name(), "circle");
+}
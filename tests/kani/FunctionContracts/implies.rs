@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Checks that `kani::implies` can be used in contracts, including nested implications. The
+//! conclusion of the function form is always evaluated, so the macro form is used when the
+//! conclusion is only valid if the premise holds.
+// kani-flags: -Zfunction-contracts
+
+#[kani::requires(kani::implies(divisor == 0, fallback))]
+#[kani::ensures(|result: &Option<u8>| kani::implies!(divisor != 0 => *result == Some(value / divisor)))]
+#[kani::ensures(|result: &Option<u8>| kani::implies(divisor == 0, kani::implies(fallback, result.is_none())))]
+fn checked_div(value: u8, divisor: u8, fallback: bool) -> Option<u8> {
+    if divisor == 0 { None } else { Some(value / divisor) }
+}
+
+#[kani::proof_for_contract(checked_div)]
+fn check_checked_div() {
+    let _ = checked_div(kani::any(), kani::any(), kani::any());
+}
+
+#[kani::proof]
+fn check_truth_table() {
+    assert!(kani::implies(false, false));
+    assert!(kani::implies(false, true));
+    assert!(!kani::implies(true, false));
+    assert!(kani::implies(true, true));
+
+    let (a, b, c): (bool, bool, bool) = kani::any();
+    assert_eq!(kani::implies(a, kani::implies(b, c)), kani::implies(a && b, c));
+    assert_eq!(kani::implies(a, b), kani::implies!(a => b));
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts
+
+//! Checks that the harnesses declared in an impl block can use `Self` to refer to the functions
+//! with a contract that they verify and that they replace by their contract.
+
+struct Meter(u32);
+
+impl Meter {
+    #[kani::requires(x < 100)]
+    #[kani::ensures(|result: &u32| *result >= x)]
+    fn scale(x: u32) -> u32 {
+        x * 2
+    }
+
+    fn reading(&self) -> u32 {
+        Self::scale(self.0)
+    }
+
+    #[kani::proof_for_contract(Self::scale)]
+    fn check_scale() {
+        Self::scale(kani::any());
+    }
+
+    #[kani::proof]
+    #[kani::stub_verified(Self::scale)]
+    fn check_reading() {
+        let meter = Meter(kani::any_where(|val| *val < 100));
+        assert!(meter.reading() >= meter.0);
+    }
+}
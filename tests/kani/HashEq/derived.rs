@@ -0,0 +1,25 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_hash_eq` succeeds for types whose `Hash` and `Eq` implementations are derived.
+
+#[derive(PartialEq, Eq, Hash, kani::Arbitrary)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(PartialEq, Eq, Hash, kani::Arbitrary)]
+enum Shape {
+    Dot(Point),
+    Segment(Point, Point),
+    Empty,
+}
+
+#[kani::proof_hash_eq(Point)]
+fn check_point() {}
+
+#[kani::proof_hash_eq(Shape)]
+fn check_shape() {
+    let shape: Shape = kani::any();
+    assert!(shape == shape);
+}
@@ -0,0 +1,92 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that Kani can verify functions that return `impl Trait`, including nested, recursive and
+//! trait method (RPITIT) opaque types.
+
+fn evens(limit: u8) -> impl Iterator<Item = u8> {
+    (0..limit).filter(|n| n % 2 == 0)
+}
+
+fn boxed_counter(start: u32) -> Box<impl FnMut() -> u32> {
+    let mut count = start;
+    Box::new(move || {
+        count += 1;
+        count
+    })
+}
+
+trait Depth {
+    fn depth(&self) -> u8;
+}
+
+impl Depth for () {
+    fn depth(&self) -> u8 {
+        0
+    }
+}
+
+impl Depth for Box<dyn Depth> {
+    fn depth(&self) -> u8 {
+        self.as_ref().depth() + 1
+    }
+}
+
+/// Recursive `impl Trait` functions must box the recursive result.
+fn nested(depth: u8) -> impl Depth {
+    if depth == 0 {
+        Box::new(()) as Box<dyn Depth>
+    } else {
+        Box::new(nested(depth - 1)) as Box<dyn Depth>
+    }
+}
+
+trait Shape {
+    fn corners(&self) -> impl Iterator<Item = (i8, i8)>;
+
+    fn corner_count(&self) -> impl Into<usize> {
+        self.corners().count()
+    }
+}
+
+struct Square {
+    side: i8,
+}
+
+impl Shape for Square {
+    fn corners(&self) -> impl Iterator<Item = (i8, i8)> {
+        [(0, 0), (self.side, 0), (self.side, self.side), (0, self.side)].into_iter()
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn check_rpit() {
+    let limit: u8 = kani::any_where(|limit| *limit <= 4);
+    assert!(evens(limit).all(|n| n % 2 == 0));
+    assert_eq!(evens(limit).count(), (limit as usize + 1) / 2);
+}
+
+#[kani::proof]
+fn check_nested_rpit() {
+    let start: u32 = kani::any_where(|start| *start < 10);
+    let mut counter = boxed_counter(start);
+    assert_eq!(counter(), start + 1);
+    assert_eq!(counter(), start + 2);
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_recursive_rpit() {
+    let depth: u8 = kani::any_where(|depth| *depth < 3);
+    assert_eq!(nested(depth).depth(), depth + 1);
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+fn check_rpitit() {
+    let square = Square { side: kani::any_where(|side| *side > 0) };
+    assert!(square.corners().all(|(x, y)| x >= 0 && y >= 0));
+    let count: usize = square.corner_count().into();
+    assert_eq!(count, 4);
+}
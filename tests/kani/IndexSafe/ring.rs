@@ -0,0 +1,51 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_index_safe` succeeds for collections whose `Index` implementation panics
+//! exactly for the indices that are out of bounds, including indices that aren't `usize`.
+
+use std::ops::Index;
+
+#[derive(kani::Arbitrary)]
+struct Ring {
+    items: [u8; 4],
+    len: u8,
+    start: u8,
+}
+
+impl Ring {
+    fn len(&self) -> usize {
+        (self.len as usize).min(self.items.len())
+    }
+}
+
+impl Index<usize> for Ring {
+    type Output = u8;
+
+    fn index(&self, index: usize) -> &u8 {
+        assert!(index < self.len(), "index out of bounds");
+        &self.items[(self.start as usize + index) % self.items.len()]
+    }
+}
+
+#[kani::proof_index_safe(Ring, bound = |ring: &Ring, index: &usize| *index < ring.len())]
+fn check_ring_index() {}
+
+#[derive(kani::Arbitrary)]
+struct Grid {
+    cells: [[bool; 3]; 2],
+}
+
+impl Index<(usize, usize)> for Grid {
+    type Output = bool;
+
+    fn index(&self, (row, column): (usize, usize)) -> &bool {
+        &self.cells[row][column]
+    }
+}
+
+fn in_grid(_grid: &Grid, (row, column): &(usize, usize)) -> bool {
+    *row < 2 && *column < 3
+}
+
+#[kani::proof_index_safe(Grid, bound = in_grid, index = (usize, usize))]
+fn check_grid_index() {}
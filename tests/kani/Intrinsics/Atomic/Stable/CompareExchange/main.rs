@@ -54,3 +54,23 @@ fn main() {
     assert!(a8.load(Ordering::Relaxed) == false);
     assert!(a9.load(Ordering::Relaxed) == false);
 }
+
+/// The operation fails and leaves the value unchanged if the current value is not the expected
+/// one.
+#[kani::proof]
+fn check_failure() {
+    let current: bool = kani::any();
+    let atomic = AtomicBool::new(current);
+    let result = atomic.compare_exchange(!current, current, Ordering::SeqCst, Ordering::Relaxed);
+    assert!(result == Err(current));
+    assert!(atomic.load(Ordering::Relaxed) == current);
+}
+
+/// The weak version doesn't fail spuriously unless `--spurious-cas-failures` is used.
+#[kani::proof]
+fn check_weak() {
+    let atomic = AtomicBool::new(true);
+    let result = atomic.compare_exchange_weak(true, false, Ordering::Relaxed, Ordering::Relaxed);
+    assert!(result == Ok(true));
+    assert!(!atomic.load(Ordering::Relaxed));
+}
@@ -0,0 +1,62 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Z unstable-options --spurious-cas-failures
+
+// Check that all variants of the `atomic_cxchgweak_*` intrinsic (unstable
+// version) may fail spuriously with `--spurious-cas-failures`, and that they
+// return the expected result otherwise.
+
+#![feature(core_intrinsics)]
+use std::intrinsics::{
+    atomic_cxchgweak_acqrel_acquire, atomic_cxchgweak_acqrel_relaxed,
+    atomic_cxchgweak_acqrel_seqcst, atomic_cxchgweak_acquire_acquire,
+    atomic_cxchgweak_acquire_relaxed, atomic_cxchgweak_acquire_seqcst,
+    atomic_cxchgweak_relaxed_acquire, atomic_cxchgweak_relaxed_relaxed,
+    atomic_cxchgweak_relaxed_seqcst, atomic_cxchgweak_release_acquire,
+    atomic_cxchgweak_release_relaxed, atomic_cxchgweak_release_seqcst,
+    atomic_cxchgweak_seqcst_acquire, atomic_cxchgweak_seqcst_relaxed,
+    atomic_cxchgweak_seqcst_seqcst,
+};
+
+/// Stores a value if the current value is the same as the old value, but may
+/// fail spuriously. Returns (val, ok) where
+///  * val: the old value
+///  * ok:  bool indicating whether the operation was successful or not
+macro_rules! check_cxchgweak {
+    ($cxchgweak:ident) => {{
+        let mut a = 0 as u8;
+        let ptr_a: *mut u8 = &mut a;
+        unsafe {
+            let (val, ok) = $cxchgweak(ptr_a, 0, 1);
+            assert!(val == 0);
+            assert!(*ptr_a == if ok { 1 } else { 0 });
+            kani::cover!(ok);
+            kani::cover!(!ok);
+
+            // The operation always fails if the values are different.
+            let current = *ptr_a;
+            let (val, ok) = $cxchgweak(ptr_a, 2, 3);
+            assert!(val == current && !ok);
+            assert!(*ptr_a == current);
+        }
+    }};
+}
+
+#[kani::proof]
+fn main() {
+    check_cxchgweak!(atomic_cxchgweak_acqrel_acquire);
+    check_cxchgweak!(atomic_cxchgweak_acqrel_relaxed);
+    check_cxchgweak!(atomic_cxchgweak_acqrel_seqcst);
+    check_cxchgweak!(atomic_cxchgweak_acquire_acquire);
+    check_cxchgweak!(atomic_cxchgweak_acquire_relaxed);
+    check_cxchgweak!(atomic_cxchgweak_acquire_seqcst);
+    check_cxchgweak!(atomic_cxchgweak_relaxed_acquire);
+    check_cxchgweak!(atomic_cxchgweak_relaxed_relaxed);
+    check_cxchgweak!(atomic_cxchgweak_relaxed_seqcst);
+    check_cxchgweak!(atomic_cxchgweak_release_acquire);
+    check_cxchgweak!(atomic_cxchgweak_release_relaxed);
+    check_cxchgweak!(atomic_cxchgweak_release_seqcst);
+    check_cxchgweak!(atomic_cxchgweak_seqcst_acquire);
+    check_cxchgweak!(atomic_cxchgweak_seqcst_relaxed);
+    check_cxchgweak!(atomic_cxchgweak_seqcst_seqcst);
+}
@@ -0,0 +1,38 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// Check that rotating by a number of bits that is greater than or equal to the bit width of the
+// type is the same as rotating by the remainder of the division by the bit width.
+#![feature(core_intrinsics)]
+use std::intrinsics::{rotate_left, rotate_right};
+
+macro_rules! test_wide_shift {
+    ( $fn_name:ident, $ty:ty ) => {
+        #[kani::proof]
+        fn $fn_name() {
+            let x: $ty = kani::any();
+            let n: u32 = kani::any();
+            kani::assume(n >= <$ty>::BITS);
+            assert_eq!(rotate_left(x, n), rotate_left(x, n % <$ty>::BITS));
+            assert_eq!(rotate_right(x, n), rotate_right(x, n % <$ty>::BITS));
+            // Rotating by a multiple of the bit width is the identity.
+            assert_eq!(rotate_left(x, <$ty>::BITS), x);
+            assert_eq!(rotate_right(x, <$ty>::BITS * 3), x);
+        }
+    };
+}
+
+test_wide_shift!(check_wide_shift_u8, u8);
+test_wide_shift!(check_wide_shift_u16, u16);
+test_wide_shift!(check_wide_shift_u32, u32);
+test_wide_shift!(check_wide_shift_u64, u64);
+test_wide_shift!(check_wide_shift_u128, u128);
+test_wide_shift!(check_wide_shift_usize, usize);
+
+#[kani::proof]
+fn check_wide_shift_concrete() {
+    assert_eq!(rotate_left(0b1000_0001u8, 9), 0b0000_0011);
+    assert_eq!(rotate_right(0b1000_0001u8, 9), 0b1100_0000);
+    assert_eq!(rotate_left(1u32, u32::MAX), 1 << 31);
+    assert_eq!(rotate_right(1u32, u32::MAX), 2);
+}
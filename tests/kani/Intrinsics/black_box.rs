@@ -18,3 +18,34 @@ fn check_zst_blackbox() {
     let nothing = black_box(void);
     assert!(void == nothing);
 }
+
+/// The result of `black_box` is equal to its argument, even though Kani doesn't fold through it.
+#[kani::proof]
+fn check_blackbox_scalars() {
+    let x: u32 = kani::any();
+    assert_eq!(black_box(x), x);
+    assert_eq!(black_box(x).wrapping_add(1), x.wrapping_add(1));
+    assert!(black_box(true));
+    assert_eq!(black_box('k'), 'k');
+    assert_eq!(black_box(-1i128), -1);
+}
+
+/// Pointers can still be dereferenced after going through `black_box`.
+#[kani::proof]
+fn check_blackbox_pointer() {
+    let value: u8 = kani::any();
+    let ptr = black_box(&value as *const u8);
+    assert_eq!(unsafe { *ptr }, value);
+}
+
+/// Since the loop bound is not a constant anymore, the loop needs an unwind bound, but the
+/// number of iterations is still the one given to `black_box`.
+#[kani::proof]
+#[kani::unwind(4)]
+fn check_blackbox_loop_bound() {
+    let mut iterations = 0;
+    for _ in 0..black_box(3) {
+        iterations += 1;
+    }
+    assert_eq!(iterations, 3);
+}
@@ -72,3 +72,21 @@ fn test_bswap_u128() {
 // unless we restrict their values considerably (due to how bytes are
 // extracted), making the signed versions not very interesting to test here.
 // https://github.com/model-checking/kani/issues/934
+
+// The signed versions can be checked against the byte representation instead.
+macro_rules! test_bswap_bytes {
+    ($ty:ty) => {
+        let x: $ty = kani::any();
+        assert!(std::intrinsics::bswap(x) == <$ty>::from_le_bytes(x.to_be_bytes()));
+    };
+}
+
+#[kani::proof]
+fn test_bswap_signed() {
+    test_bswap_bytes!(i8);
+    test_bswap_bytes!(i16);
+    test_bswap_bytes!(i32);
+    test_bswap_bytes!(i64);
+    test_bswap_bytes!(i128);
+    test_bswap_bytes!(isize);
+}
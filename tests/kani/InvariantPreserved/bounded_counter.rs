@@ -0,0 +1,56 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `proof_invariant_preserved` verifies every kind of method that preserves the
+//! invariant of the type.
+
+#[derive(kani::Arbitrary)]
+pub struct BoundedCounter {
+    count: u8,
+    limit: u8,
+}
+
+impl kani::Invariant for BoundedCounter {
+    fn is_safe(&self) -> bool {
+        self.count <= self.limit
+    }
+}
+
+impl BoundedCounter {
+    pub fn count(&self) -> u8 {
+        self.count
+    }
+
+    pub fn increment(&mut self) {
+        if self.count < self.limit {
+            self.count += 1;
+        }
+    }
+
+    pub fn add(&mut self, amount: u8, saturate: bool) {
+        let count = self.count.saturating_add(amount);
+        if count <= self.limit {
+            self.count = count;
+        } else if saturate {
+            self.count = self.limit;
+        }
+    }
+
+    pub fn reset(self) -> Self {
+        BoundedCounter { count: 0, limit: self.limit }
+    }
+
+    /// Not checked, since it doesn't return `Self`.
+    pub fn into_count(self) -> u8 {
+        self.count
+    }
+
+    /// Not checked, since it's private.
+    #[allow(dead_code)]
+    fn overflow(&mut self) {
+        self.count = self.limit.wrapping_add(1);
+    }
+}
+
+#[kani::proof_invariant_preserved(BoundedCounter)]
+fn check_bounded_counter() {}
@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_lossless` succeeds for the lossless numeric conversions of the standard
+//! library, and for conversions that are only lossless on a sub-range of their input.
+
+#[kani::proof_lossless(From<u8> for u16)]
+fn check_u8_to_u16() {}
+
+#[kani::proof_lossless(From<i16> for i64)]
+fn check_i16_to_i64() {}
+
+#[kani::proof_lossless(From<u32> for u128)]
+fn check_u32_to_u128() {}
+
+#[kani::proof_lossless(From<char> for u32)]
+fn check_char_to_u32() {}
+
+#[derive(Clone, PartialEq, kani::Arbitrary)]
+struct Millis(u32);
+
+struct Seconds(u32);
+
+impl From<Millis> for Seconds {
+    fn from(value: Millis) -> Self {
+        Seconds(value.0 / 1000)
+    }
+}
+
+impl TryFrom<Seconds> for Millis {
+    type Error = ();
+
+    fn try_from(value: Seconds) -> Result<Self, Self::Error> {
+        value.0.checked_mul(1000).map(Millis).ok_or(())
+    }
+}
+
+#[kani::proof_lossless(From<Millis> for Seconds, assume = |ms: &Millis| ms.0 % 1000 == 0)]
+fn check_whole_seconds() {
+    let ms = Millis(kani::any_where(|ms| *ms < 5000));
+    assert!(Seconds::from(ms).0 < 5);
+}
@@ -0,0 +1,23 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that const generic harnesses are verified for every value listed in `proof_over`,
+//! including zero-sized arrays.
+
+fn sum<const N: usize>(array: [u8; N]) -> u32 {
+    array.iter().map(|x| *x as u32).sum()
+}
+
+#[kani::proof_over(N = [0, 1, 2, 16])]
+fn check_sum<const N: usize>() {
+    let array: [u8; N] = kani::any();
+    assert!(sum(array) <= 255 * N as u32);
+    assert_eq!(std::mem::size_of_val(&array), N);
+}
+
+#[kani::proof_over(N = [0, 3], OFFSET = [-1, 1])]
+fn check_multiple_params<const N: usize, const OFFSET: i8>() {
+    let array = [OFFSET; N];
+    assert!(array.iter().all(|x| *x == OFFSET));
+    assert_eq!(array.len(), N);
+}
@@ -0,0 +1,27 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that generic harnesses are verified for every type listed in `proof_over`, and that
+//! `kani::check_pairs` can be used to check the properties implied by their bounds.
+
+use std::cmp::Ordering;
+
+fn max_of<T: Ord>(a: T, b: T) -> T {
+    if a >= b { a } else { b }
+}
+
+#[kani::proof_over(T = [u8, i32, char, bool, (u8, i8), Option<u16>])]
+fn check_ord_antisymmetric<T: Ord + kani::Arbitrary>() {
+    kani::check_pairs(|a: &T, b: &T| {
+        assert_eq!(a.cmp(b), b.cmp(a).reverse());
+        assert_eq!(a.cmp(b) == Ordering::Equal, a == b);
+    });
+}
+
+#[kani::proof_over(T = [u8, i64], N = [1, 3])]
+fn check_max_of<T: Ord + Copy + kani::Arbitrary, const N: usize>() {
+    let values: [T; N] = kani::any();
+    let max = values.into_iter().reduce(max_of).unwrap();
+    assert!(values.iter().all(|value| *value <= max));
+    assert!(values.contains(&max));
+}
@@ -0,0 +1,96 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that the SSE intrinsics that are implemented with LLVM intrinsics can be verified, and
+//! that they follow the semantics of each lane.
+#![cfg(target_arch = "x86_64")]
+
+use std::arch::x86_64::*;
+
+fn to_bits(vector: __m128) -> [u32; 4] {
+    unsafe { std::mem::transmute(vector) }
+}
+
+fn from_array(lanes: [f32; 4]) -> __m128 {
+    unsafe { _mm_setr_ps(lanes[0], lanes[1], lanes[2], lanes[3]) }
+}
+
+fn mask(value: bool) -> u32 {
+    if value { u32::MAX } else { 0 }
+}
+
+#[kani::proof]
+fn check_cmp_ps() {
+    let a: [f32; 4] = kani::any();
+    let b: [f32; 4] = kani::any();
+    let (va, vb) = (from_array(a), from_array(b));
+    let lt = to_bits(unsafe { _mm_cmplt_ps(va, vb) });
+    let gt = to_bits(unsafe { _mm_cmpgt_ps(va, vb) });
+    let neq = to_bits(unsafe { _mm_cmpneq_ps(va, vb) });
+    let unord = to_bits(unsafe { _mm_cmpunord_ps(va, vb) });
+    for i in 0..4 {
+        assert_eq!(lt[i], mask(a[i] < b[i]));
+        assert_eq!(gt[i], mask(a[i] > b[i]));
+        assert_eq!(neq[i], mask(a[i] != b[i]));
+        assert_eq!(unord[i], mask(a[i].is_nan() || b[i].is_nan()));
+    }
+}
+
+#[kani::proof]
+fn check_cmp_ss() {
+    let a: [f32; 4] = kani::any();
+    let b: [f32; 4] = kani::any();
+    let result = to_bits(unsafe { _mm_cmple_ss(from_array(a), from_array(b)) });
+    assert_eq!(result[0], mask(a[0] <= b[0]));
+    for i in 1..4 {
+        assert_eq!(result[i], a[i].to_bits());
+    }
+}
+
+#[kani::proof]
+fn check_min_max_ps() {
+    let a: [f32; 4] = kani::any();
+    let b: [f32; 4] = kani::any();
+    let min = to_bits(unsafe { _mm_min_ps(from_array(a), from_array(b)) });
+    let max = to_bits(unsafe { _mm_max_ps(from_array(a), from_array(b)) });
+    for i in 0..4 {
+        // The second operand is returned if any of them is NaN.
+        if a[i].is_nan() || b[i].is_nan() {
+            assert_eq!(min[i], b[i].to_bits());
+            assert_eq!(max[i], b[i].to_bits());
+        } else {
+            assert_eq!(f32::from_bits(min[i]), a[i].min(b[i]));
+            assert_eq!(f32::from_bits(max[i]), a[i].max(b[i]));
+        }
+    }
+}
+
+#[kani::proof]
+fn check_add_mul_pd() {
+    let a: [f64; 2] = kani::any();
+    let b: [f64; 2] = kani::any();
+    kani::assume(a.iter().chain(b.iter()).all(|lane| lane.is_finite()));
+    let (va, vb) = unsafe { (_mm_setr_pd(a[0], a[1]), _mm_setr_pd(b[0], b[1])) };
+    let sum: [f64; 2] = unsafe { std::mem::transmute(_mm_add_pd(va, vb)) };
+    let product: [f64; 2] = unsafe { std::mem::transmute(_mm_mul_pd(va, vb)) };
+    let eq: [u64; 2] = unsafe { std::mem::transmute(_mm_cmpeq_pd(va, vb)) };
+    for i in 0..2 {
+        assert_eq!(sum[i], a[i] + b[i]);
+        assert_eq!(product[i], a[i] * b[i]);
+        assert_eq!(eq[i], if a[i] == b[i] { u64::MAX } else { 0 });
+    }
+}
+
+#[kani::proof]
+fn check_madd_epi16() {
+    let a: [i16; 8] = kani::any();
+    let b: [i16; 8] = kani::any();
+    let result: [i32; 4] = unsafe {
+        std::mem::transmute(_mm_madd_epi16(std::mem::transmute(a), std::mem::transmute(b)))
+    };
+    for i in 0..4 {
+        let low = a[2 * i] as i32 * b[2 * i] as i32;
+        let high = a[2 * i + 1] as i32 * b[2 * i + 1] as i32;
+        assert_eq!(result[i], low.wrapping_add(high));
+    }
+}
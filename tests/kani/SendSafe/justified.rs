@@ -0,0 +1,24 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_send_safe` accepts types whose manual `Send` implementation only relies on
+//! uniquely owned pointers and thread-safe shared state.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+pub struct Buffer {
+    data: *mut u8,
+    len: usize,
+    shared: Arc<AtomicU8>,
+}
+
+unsafe impl Send for Buffer {}
+
+#[kani::proof_send_safe(Buffer)]
+fn check_buffer() {
+    let mut value: u8 = kani::any();
+    let buffer = Buffer { data: &mut value, len: 1, shared: Arc::new(AtomicU8::new(0)) };
+    buffer.shared.store(unsafe { *buffer.data }, Ordering::Relaxed);
+    assert_eq!(buffer.len, 1);
+    assert_eq!(buffer.shared.load(Ordering::Relaxed), value);
+}
@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `kani::snapshot!` keeps the value of an expression at the point it was taken,
+//! so it can be related to later values of the same expression.
+
+#[kani::proof]
+fn check_counter() {
+    let mut counter: u32 = kani::any_where(|c| *c < 100);
+    let before = kani::snapshot!(counter);
+    let delta: u32 = kani::any_where(|d| *d < 10);
+    counter += delta;
+    kani::assert(counter == before + delta, "counter advanced by delta");
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_vec_push() {
+    let mut data: Vec<u8> = vec![kani::any()];
+    let before = kani::snapshot!(data);
+    data.push(kani::any());
+    assert_eq!(data.len(), before.len() + 1);
+    assert_eq!(data[0], before[0]);
+}
+
+#[kani::proof]
+fn check_snapshot_clone_expr() {
+    let mut data = [kani::any::<i8>(); 2];
+    let before = kani::snapshot!(data.clone());
+    data[1] = data[1].wrapping_add(1);
+    assert_eq!(data[0], before[0]);
+    assert_ne!(data[1], before[1]);
+}
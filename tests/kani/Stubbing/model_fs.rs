@@ -0,0 +1,61 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing --model-fs
+//
+//! Check that `--model-fs` replaces the `std::fs` entry points by a symbolic file system that
+//! is consistent across operations.
+
+use std::fs::File;
+use std::io::{ErrorKind, Read, Write};
+
+#[kani::proof]
+fn check_write_then_read() {
+    std::fs::write("config", b"on").unwrap();
+    assert_eq!(std::fs::read_to_string("config").unwrap(), "on");
+    assert_eq!(std::fs::metadata("config").unwrap().len(), 2);
+
+    let mut file = File::create("log").unwrap();
+    file.write_all(b"abc").unwrap();
+    file.flush().unwrap();
+    let mut contents = vec![];
+    File::open("log").unwrap().read_to_end(&mut contents).unwrap();
+    assert_eq!(contents, b"abc");
+}
+
+#[kani::proof]
+fn check_missing_file_is_consistent() {
+    let opened = File::open("data");
+    let read = std::fs::read("data");
+    assert_eq!(opened.is_ok(), read.is_ok());
+    if let Err(err) = opened {
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert_eq!(read.as_ref().unwrap_err().kind(), ErrorKind::NotFound);
+        assert!(std::fs::metadata("data").is_err());
+    }
+    kani::cover!(read.is_ok());
+}
+
+#[kani::proof]
+fn check_existing_file_is_bounded() {
+    kani::fs::set_max_file_size(2);
+    if let Ok(mut file) = File::open("data") {
+        let len = file.metadata().unwrap().len();
+        assert!(len <= 2);
+        let mut buf = [0; 4];
+        let read = file.read(&mut buf).unwrap();
+        assert_eq!(read as u64, len);
+        assert_eq!(std::fs::read("data").unwrap(), &buf[..read]);
+        assert!(file.write(b"x").is_err());
+        kani::cover!(len == 2);
+    }
+}
+
+#[kani::proof]
+fn check_max_files() {
+    kani::fs::set_max_files(1);
+    let first = std::fs::metadata("first").is_ok();
+    let second = std::fs::metadata("second").is_ok();
+    assert!(!(first && second));
+    kani::cover!(first || second);
+}
@@ -0,0 +1,55 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! This tests that the stubs of a harness declared in an impl block can use `Self` to refer to
+//! the self type of the impl, including in qualified paths of trait methods.
+
+struct Counter(u32);
+
+trait Step {
+    fn step(&self) -> u32;
+    fn reset(&mut self);
+}
+
+impl Step for Counter {
+    fn step(&self) -> u32 {
+        self.0 + 1
+    }
+
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+}
+
+impl Counter {
+    fn double(&self) -> u32 {
+        self.0 * 2
+    }
+
+    fn step_by_ten(&self) -> u32 {
+        self.0 + 10
+    }
+
+    fn reset_to_one(&mut self) {
+        self.0 = 1;
+    }
+
+    #[kani::proof]
+    #[kani::stub(Self::double, Self::step_by_ten)]
+    fn check_inherent_method() {
+        let counter = Counter(kani::any_where(|val| *val < 100));
+        assert_eq!(counter.double(), counter.0 + 10);
+    }
+
+    #[kani::proof]
+    #[kani::stub(<Self as Step>::step, Self::step_by_ten)]
+    #[kani::stub(Self::reset, Self::reset_to_one)]
+    fn check_trait_method() {
+        let mut counter = Counter(kani::any_where(|val| *val < 100));
+        assert_eq!(counter.step(), counter.0 + 10);
+        counter.reset();
+        assert_eq!(counter.0, 1);
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! This tests stubbing the drop glue of a type whose fields also implement `Drop`.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static FIELD_DROPS: AtomicU32 = AtomicU32::new(0);
+
+/// A field that counts how many times it was dropped.
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        FIELD_DROPS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+struct Connection {
+    counted: Counted,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        panic!("Closing the connection cannot be verified");
+    }
+}
+
+fn skip_drop(_conn: &mut Connection) {}
+
+fn drop_fields(conn: &mut Connection) {
+    unsafe { std::ptr::drop_in_place(&mut conn.counted) };
+}
+
+#[kani::proof]
+#[kani::stub_drop(Connection, with = skip_drop)]
+fn check_skip_fields() {
+    drop(Connection { counted: Counted });
+    assert_eq!(FIELD_DROPS.load(Ordering::Relaxed), 0);
+}
+
+#[kani::proof]
+#[kani::stub_drop(Connection, with = drop_fields)]
+fn check_drop_fields() {
+    drop(Connection { counted: Counted });
+    assert_eq!(FIELD_DROPS.load(Ordering::Relaxed), 1);
+}
+
+#[kani::proof]
+#[kani::stub_drop(Connection, with = drop_fields)]
+fn check_nested_drop() {
+    let boxed = Box::new([Connection { counted: Counted }, Connection { counted: Counted }]);
+    drop(boxed);
+    assert_eq!(FIELD_DROPS.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,72 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that the stubs of a stub set declared with `kani::stub_set!` are applied to the
+//! harnesses that use it, and that the paths of the stubs are resolved from the module of the set.
+
+mod io {
+    pub fn read() -> u8 {
+        unimplemented!("reading from a device cannot be verified")
+    }
+
+    pub fn write(_byte: u8) -> bool {
+        unimplemented!("writing to a device cannot be verified")
+    }
+
+    fn read_stub() -> u8 {
+        42
+    }
+
+    fn write_stub(byte: u8) -> bool {
+        byte == 42
+    }
+
+    kani::stub_set! {
+        pub model = [(read, read_stub), (write, write_stub)],
+    }
+}
+
+mod clock {
+    pub fn now() -> u64 {
+        unimplemented!("the clock cannot be verified")
+    }
+
+    fn now_stub() -> u64 {
+        10
+    }
+
+    kani::stub_set! {
+        pub model = [(now, now_stub)],
+    }
+}
+
+fn echo() -> bool {
+    io::write(io::read())
+}
+
+fn other_now() -> u64 {
+    20
+}
+
+#[kani::proof]
+#[kani::use_stubs(io::model)]
+fn check_io() {
+    assert!(echo());
+}
+
+#[kani::proof]
+#[kani::use_stubs(io::model, clock::model)]
+fn check_io_and_clock() {
+    assert!(echo());
+    assert_eq!(clock::now(), 10);
+}
+
+#[kani::proof]
+#[kani::use_stubs(io::model)]
+#[kani::stub(clock::now, other_now)]
+fn check_set_with_stub() {
+    assert!(echo());
+    assert_eq!(clock::now(), 20);
+}
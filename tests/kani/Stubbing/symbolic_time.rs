@@ -0,0 +1,39 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that the clock models from `kani::time` can be used to stub `Instant::now` and
+//! `SystemTime::now`, and that the `Instant` model is monotonic.
+
+use std::time::{Duration, Instant, SystemTime};
+
+#[kani::proof]
+#[kani::stub(std::time::Instant::now, kani::time::instant_now)]
+fn check_instant_monotonic() {
+    let first = Instant::now();
+    let second = Instant::now();
+    let third = Instant::now();
+    assert!(first <= second);
+    assert!(second <= third);
+    kani::cover!(first < third);
+}
+
+#[kani::proof]
+#[kani::stub(std::time::Instant::now, kani::time::instant_now)]
+fn check_bounded_elapsed() {
+    kani::time::set_max_step(Duration::from_millis(100));
+    let start = Instant::now();
+    let elapsed = start.elapsed();
+    assert!(elapsed <= Duration::from_millis(100));
+    kani::cover!(elapsed == Duration::ZERO);
+    kani::cover!(elapsed == Duration::from_millis(100));
+}
+
+#[kani::proof]
+#[kani::stub(std::time::SystemTime::now, kani::time::system_time_now)]
+fn check_system_time() {
+    let now = SystemTime::now();
+    kani::cover!(now < SystemTime::UNIX_EPOCH);
+    kani::cover!(now > SystemTime::UNIX_EPOCH);
+}
@@ -0,0 +1,63 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! This tests stubbing the implementation of trait methods, with qualified paths and with paths
+//! through the type, which prefer inherent methods over trait methods.
+
+struct Counter(u32);
+
+trait Step {
+    fn step(&self) -> u32;
+    fn reset(&mut self);
+}
+
+impl Step for Counter {
+    fn step(&self) -> u32 {
+        self.0 + 1
+    }
+
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+}
+
+impl Counter {
+    /// Inherent method with the same name as the trait method.
+    fn step(&self) -> u32 {
+        self.0 + 2
+    }
+}
+
+fn step_by_ten(counter: &Counter) -> u32 {
+    counter.0 + 10
+}
+
+fn reset_to_one(counter: &mut Counter) {
+    counter.0 = 1;
+}
+
+#[kani::proof]
+#[kani::stub(<Counter as Step>::step, step_by_ten)]
+fn check_qualified_trait_method() {
+    let counter = Counter(kani::any_where(|val| *val < 100));
+    assert_eq!(Step::step(&counter), counter.0 + 10);
+    assert_eq!(counter.step(), counter.0 + 2);
+}
+
+#[kani::proof]
+#[kani::stub(Counter::step, step_by_ten)]
+fn check_inherent_method() {
+    let counter = Counter(kani::any_where(|val| *val < 100));
+    assert_eq!(Step::step(&counter), counter.0 + 1);
+    assert_eq!(counter.step(), counter.0 + 10);
+}
+
+#[kani::proof]
+#[kani::stub(Counter::reset, reset_to_one)]
+fn check_unqualified_trait_method() {
+    let mut counter = Counter(kani::any());
+    counter.reset();
+    assert_eq!(counter.0, 1);
+}
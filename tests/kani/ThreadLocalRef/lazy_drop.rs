@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// Checks that thread locals are initialized on their first access.
+
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static INITS: Cell<u32> = const { Cell::new(0) };
+    static LOG: RefCell<Vec<u32>> = {
+        INITS.set(INITS.get() + 1);
+        RefCell::new(Vec::new())
+    };
+}
+
+#[kani::proof]
+#[kani::unwind(3)]
+fn check_lazy_init() {
+    assert_eq!(INITS.get(), 0);
+    let value: u32 = kani::any();
+    LOG.with_borrow_mut(|log| log.push(value));
+    assert_eq!(INITS.get(), 1);
+    LOG.with_borrow_mut(|log| log.push(value / 2));
+    assert_eq!(INITS.get(), 1);
+    LOG.with_borrow(|log| {
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0], value);
+    });
+}
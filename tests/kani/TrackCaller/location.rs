@@ -0,0 +1,84 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `Location::caller()` reports the location of the call site, and that the location
+//! propagates through nested `#[track_caller]` functions up to the outermost call.
+
+use std::panic::Location;
+
+#[track_caller]
+fn caller_line() -> u32 {
+    Location::caller().line()
+}
+
+#[track_caller]
+fn nested_caller_line() -> u32 {
+    caller_line()
+}
+
+/// Doesn't track its caller, so the location is the one of the call inside of it.
+fn untracked_caller_line() -> (u32, u32) {
+    (line!(), caller_line())
+}
+
+#[track_caller]
+fn caller_file() -> &'static str {
+    Location::caller().file()
+}
+
+struct Checked(u8);
+
+impl Checked {
+    #[track_caller]
+    fn get(&self) -> (u8, u32) {
+        (self.0, Location::caller().line())
+    }
+}
+
+trait Tracked {
+    #[track_caller]
+    fn line(&self) -> u32;
+}
+
+impl Tracked for Checked {
+    fn line(&self) -> u32 {
+        Location::caller().line()
+    }
+}
+
+#[kani::proof]
+fn check_direct_call() {
+    let (line, reported) = (line!(), caller_line());
+    assert_eq!(reported, line);
+}
+
+#[kani::proof]
+fn check_nested_call() {
+    let (line, reported) = (line!(), nested_caller_line());
+    assert_eq!(reported, line);
+}
+
+#[kani::proof]
+fn check_untracked_call() {
+    let (line, reported) = untracked_caller_line();
+    assert_eq!(reported, line);
+}
+
+#[kani::proof]
+fn check_file() {
+    assert!(caller_file().ends_with("location.rs"));
+}
+
+#[kani::proof]
+fn check_method_call() {
+    let value = Checked(kani::any());
+    let (line, (inner, reported)) = (line!(), value.get());
+    assert_eq!(inner, value.0);
+    assert_eq!(reported, line);
+}
+
+#[kani::proof]
+fn check_dyn_call() {
+    let value: &dyn Tracked = &Checked(kani::any());
+    let (line, reported) = (line!(), value.line());
+    assert_eq!(reported, line);
+}
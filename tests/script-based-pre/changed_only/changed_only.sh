@@ -0,0 +1,48 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--changed-only` only verifies the harnesses whose reachable code changed since
+# the previous run, and that the other harnesses report their cached result.
+
+OUT_DIR=tmp_changed_only
+SRC=${OUT_DIR}/lib.rs
+
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}
+cat > ${SRC} << 'EOF_SRC'
+fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+fn halve(x: u8) -> u8 {
+    x / 2
+}
+
+#[kani::proof]
+fn check_double() {
+    let x: u8 = kani::any();
+    assert!(double(x) >= x as u16);
+}
+
+#[kani::proof]
+fn check_halve() {
+    let x: u8 = kani::any();
+    assert!(halve(x) <= x);
+}
+EOF_SRC
+
+function check_kani {
+    echo "[$1]"
+    kani ${SRC} --target-dir ${OUT_DIR} -Z unstable-options --changed-only \
+        | grep "Checking harness\|VERIFICATION:-"
+}
+
+check_kani "first run"
+check_kani "no change"
+
+# Change a function that is only reachable from `check_halve`.
+sed -i 's/x \/ 2/x >> 1/' ${SRC}
+check_kani "changed halve"
+
+rm -rf ${OUT_DIR}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_seeded() {
+    let x: u32 = kani::any();
+    kani::assume(x > 1);
+    let y: u32 = kani::any();
+    assert!(x.wrapping_add(y) % 4 != 0);
+}
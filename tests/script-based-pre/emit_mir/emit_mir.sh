@@ -0,0 +1,43 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--emit-mir` writes the MIR of each function reachable from the harness, and that
+# the transformed MIR marks the code added by the contract instrumentation.
+
+OUT_DIR=tmp_emit_mir
+SRC=${OUT_DIR}/lib.rs
+
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}
+cat > ${SRC} << 'EOF_SRC'
+#[kani::requires(x < 100)]
+#[kani::ensures(|result| *result > x)]
+fn increment(x: u8) -> u8 {
+    x + 1
+}
+
+#[kani::proof]
+#[kani::stub_verified(increment)]
+fn check_increment() {
+    let x: u8 = kani::any_where(|x| *x < 10);
+    assert!(increment(x) > 0);
+}
+EOF_SRC
+
+cd ${OUT_DIR}
+
+echo "[original]"
+kani lib.rs -Z function-contracts -Z unstable-options --emit-mir original 2>&1 \
+    | grep "wrote the original MIR\|VERIFICATION:-"
+ls kani-mir/check_increment | grep -c "^check_increment.mir$"
+grep -c "injected by Kani" kani-mir/check_increment/*.mir | grep -v ":0$" | wc -l
+
+echo "[transformed]"
+rm -rf kani-mir
+kani lib.rs -Z function-contracts -Z unstable-options --emit-mir transformed 2>&1 \
+    | grep "wrote the transformed MIR\|VERIFICATION:-"
+grep -l "injected by Kani" kani-mir/check_increment/*.mir | wc -l | xargs test 0 -lt && echo "marked"
+
+cd ..
+rm -rf ${OUT_DIR}
@@ -0,0 +1,71 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--export-reproducer` writes a crate with only the dependencies of the harness, and
+# that verifying this crate reproduces the failure.
+
+OUT_DIR=tmp_export_reproducer
+SRC=${OUT_DIR}/lib.rs
+
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}
+cat > ${SRC} << 'EOF_SRC'
+mod shapes {
+    /// A square, with its derives.
+    #[derive(Clone, PartialEq)]
+    pub struct Square {
+        pub side: u8,
+    }
+
+    impl Square {
+        pub fn new(side: u8) -> Self {
+            Square { side }
+        }
+
+        pub fn perimeter(&self) -> u16 {
+            self.side as u16 * 4
+        }
+
+        pub fn area(&self) -> u16 {
+            self.side as u16 * self.side as u16
+        }
+    }
+}
+
+macro_rules! limit {
+    ($name:ident, $value:expr) => {
+        fn $name() -> u16 {
+            $value
+        }
+    };
+}
+
+limit!(max_area, 10_000);
+
+fn unrelated() -> u8 {
+    42
+}
+
+#[kani::proof]
+fn check_area() {
+    let square = shapes::Square::new(kani::any());
+    assert!(square.area() < max_area());
+}
+EOF_SRC
+
+echo "[original]"
+kani ${SRC} -Z unstable-options --export-reproducer ${OUT_DIR}/reproducer 2>&1 \
+    | grep "wrote the reproducer\|VERIFICATION:-"
+
+REPRODUCER=${OUT_DIR}/reproducer/check_area.rs
+echo "[content]"
+grep -c "fn area" ${REPRODUCER}
+grep -c "fn max_area" ${REPRODUCER}
+grep -c "#\[derive(Clone, PartialEq)\]" ${REPRODUCER}
+grep -c "fn perimeter\|fn unrelated" ${REPRODUCER}
+
+echo "[reproducer]"
+kani ${REPRODUCER} | grep "VERIFICATION:-"
+
+rm -rf ${OUT_DIR}
@@ -0,0 +1,12 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Check that the properties of a harness are listed in the metadata file without running CBMC.
+set -eu
+
+kani --only-codegen --keep-temps properties.rs
+jq -r '.proof_harnesses[].properties[]
+    | select(.location.file | endswith("properties.rs"))
+    | "\(.property_class) \(.location.line): \(.description)"' properties.kani-metadata.json
+rm -f properties.kani-metadata.json libproperties.rlib properties*.symtab.out properties*.json
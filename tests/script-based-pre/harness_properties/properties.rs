@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+#[kani::proof]
+fn check_properties() {
+    let x: u8 = kani::any();
+    kani::cover!(x == 0);
+    let y = x + 1;
+    assert!(y != 0, "no wrap around");
+}
@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--no-missing-proof-warning` disables the warning about functions that are named
+# like harnesses, but are not annotated with `#[kani::proof]`.
+
+set -eu
+
+WARNING="is named like a harness, but it is not a harness"
+
+if ! kani verify.rs 2>&1 | grep -q "${WARNING}"; then
+    echo "error: the missing proof warning is not reported by default"
+    exit 1
+fi
+
+KANI_OUTPUT=`kani verify.rs --no-missing-proof-warning 2>&1`
+
+if echo "${KANI_OUTPUT}" | grep -q "${WARNING}"; then
+    echo "error: \`--no-missing-proof-warning\` didn't disable the warning"
+    exit 1
+else
+    echo "success: \`--no-missing-proof-warning\` disabled the warning"
+fi
+echo "${KANI_OUTPUT}" | grep "VERIFICATION:-"
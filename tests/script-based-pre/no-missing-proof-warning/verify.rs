@@ -0,0 +1,16 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+fn double(x: u8) -> u16 {
+    x as u16 * 2
+}
+
+fn verify_double() {
+    let x: u8 = kani::any();
+    assert_eq!(double(x) / 2, x as u16);
+}
+
+#[kani::proof]
+fn proof_double() {
+    verify_double();
+}
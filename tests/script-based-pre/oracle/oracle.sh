@@ -0,0 +1,48 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--oracle` plays back the recorded inputs before verification, and that it reports
+# the cases whose output doesn't match the expected one.
+
+OUT_DIR=tmp_oracle
+SRC=${OUT_DIR}/lib.rs
+ORACLE=${OUT_DIR}/oracle.json
+
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}
+cat > ${SRC} << 'EOF_SRC'
+fn midpoint(a: u8, b: u8) -> u8 {
+    a / 2 + b / 2
+}
+
+#[kani::proof]
+fn check_midpoint() {
+    let a: u8 = kani::any();
+    let b: u8 = kani::any();
+    kani::oracle_output(&midpoint(a, b));
+}
+EOF_SRC
+
+echo "[matching oracle]"
+cat > ${ORACLE} << 'EOF_ORACLE'
+[
+    { "harness": "check_midpoint", "inputs": [[10], [20]], "expected": "15" },
+    { "harness": "check_midpoint", "inputs": [[255], [255]], "expected": "254" }
+]
+EOF_ORACLE
+kani ${SRC} --target-dir ${OUT_DIR} -Z concrete-playback --oracle ${ORACLE} \
+    | grep "^Checked\|^VERIFICATION"
+
+echo "[mismatching oracle]"
+cat > ${ORACLE} << 'EOF_ORACLE'
+[
+    { "harness": "check_midpoint", "inputs": [[3], [5]], "expected": "4" },
+    { "harness": "check_other", "inputs": [], "expected": "()" }
+]
+EOF_ORACLE
+kani ${SRC} --target-dir ${OUT_DIR} -Z concrete-playback --oracle ${ORACLE} 2>&1 \
+    | grep -v "^Kani Rust Verifier\|^Checking harness\|^VERIFICATION"
+echo "exit status: ${PIPESTATUS[0]}"
+
+rm -rf ${OUT_DIR}
@@ -0,0 +1,50 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--post-verification-hook` runs the given command for each harness with its result
+# on the standard input, and that a failing hook only aborts the run with `--fail-on-hook-error`.
+
+OUT_DIR=tmp_post_verification_hook
+SRC=${OUT_DIR}/lib.rs
+LOG=${OUT_DIR}/results.jsonl
+
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}
+cat > ${SRC} << 'EOF_SRC'
+#[kani::proof]
+fn check_pass() {
+    let x: u8 = kani::any();
+    assert!(x / 2 <= x);
+}
+
+#[kani::proof]
+fn check_fail() {
+    let x: u8 = kani::any();
+    assert!(x < 100, "x is small");
+}
+EOF_SRC
+
+kani ${SRC} --target-dir ${OUT_DIR} -Z unstable-options \
+    --post-verification-hook "cat >> ${LOG} && echo >> ${LOG}" > /dev/null
+
+echo "[results]"
+python3 -c "
+import json
+results = [json.loads(line) for line in open('${LOG}')]
+for result in sorted(results, key=lambda result: result['harness']):
+    failed = [prop['description'] for prop in result['failed_properties']]
+    print(result['harness'], result['status'], failed)
+"
+
+echo "[failing hook]"
+kani ${SRC} --target-dir ${OUT_DIR} -Z unstable-options --harness check_pass \
+    --post-verification-hook "exit 3" 2>&1 | grep "WARNING"
+echo "exit status: ${PIPESTATUS[0]}"
+
+echo "[fail on hook error]"
+kani ${SRC} --target-dir ${OUT_DIR} -Z unstable-options --harness check_pass \
+    --post-verification-hook "exit 3" --fail-on-hook-error > /dev/null 2>&1
+echo "exit status: $?"
+
+rm -rf ${OUT_DIR}
@@ -0,0 +1,37 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--record-replay` writes a replay file with the seed of the randomized layout,
+# and that `--replay` reproduces the recorded outcome and fails if it differs.
+
+OUT_DIR=tmp_replay
+SRC=${OUT_DIR}/lib.rs
+REPLAY=${OUT_DIR}/check_tuple.kani-replay.json
+
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}
+cat > ${SRC} << 'EOF_SRC'
+#[kani::proof]
+fn check_tuple() {
+    let pair: (u8, u32) = kani::any();
+    assert!(pair.0 as u32 <= pair.1);
+}
+EOF_SRC
+
+echo "[record]"
+kani ${SRC} --target-dir ${OUT_DIR} -Z unstable-options --record-replay --randomize-layout \
+    | grep "Checking harness\|VERIFICATION:-\|Replay"
+grep -o '"layout_seed": [0-9]\+' ${REPLAY} | sed 's/[0-9]\+/<seed>/'
+grep '"outcome"' ${REPLAY}
+
+echo "[replay]"
+kani ${SRC} --target-dir ${OUT_DIR} -Z unstable-options --replay ${REPLAY} \
+    | grep "Checking harness\|VERIFICATION:-\|Replay"
+
+echo "[different outcome]"
+sed -i 's/"outcome": "failure"/"outcome": "success"/' ${REPLAY}
+kani ${SRC} --target-dir ${OUT_DIR} -Z unstable-options --replay ${REPLAY} 2>&1 \
+    | grep "Checking harness\|VERIFICATION:-\|outcome"
+
+rm -rf ${OUT_DIR}
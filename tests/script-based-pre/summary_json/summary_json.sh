@@ -0,0 +1,45 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that `--summary-json` prints the number of properties of each outcome and writes them
+# to the given file.
+
+OUT_DIR=tmp_summary_json
+SRC=${OUT_DIR}/lib.rs
+SUMMARY=${OUT_DIR}/summary.json
+
+rm -rf ${OUT_DIR}
+mkdir -p ${OUT_DIR}
+cat > ${SRC} << 'EOF_SRC'
+#[kani::proof]
+fn check_pass() {
+    let x: u8 = kani::any();
+    assert!(x / 2 <= x);
+    kani::cover!(x == 0);
+}
+
+#[kani::proof]
+fn check_fail() {
+    let x: u8 = kani::any();
+    assert!(x < 100);
+    if x > 200 && x < 100 {
+        assert!(false);
+    }
+}
+EOF_SRC
+
+kani ${SRC} --target-dir ${OUT_DIR} -Z unstable-options --summary-json ${SUMMARY} \
+    | grep "^Property summary\|^ - check\|^Total:"
+
+echo "[json]"
+python3 -c "
+import json
+summary = json.load(open('${SUMMARY}'))
+print('complete:', summary['complete'])
+print('total_harnesses:', summary['total_harnesses'])
+for harness in summary['harnesses']:
+    print(harness['harness'], harness['status'], harness['properties']['failed'])
+"
+
+rm -rf ${OUT_DIR}
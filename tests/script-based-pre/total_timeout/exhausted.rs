@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// This test checks that the harnesses are reported as skipped when the `--total-timeout` is
+// exhausted before they are verified, and that the run fails even though no harness failed.
+
+#[kani::proof]
+fn check_first() {
+    assert_ne!(42, 17);
+}
+
+#[kani::proof]
+fn check_second() {
+    assert!(false);
+}
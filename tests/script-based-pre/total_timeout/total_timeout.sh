@@ -0,0 +1,10 @@
+#!/usr/bin/env bash
+# Copyright Kani Contributors
+# SPDX-License-Identifier: Apache-2.0 OR MIT
+
+# Checks that a run whose harnesses were skipped because the `--total-timeout` was exhausted
+# fails, so it doesn't pass in CI without verifying anything.
+
+set -u
+
+kani exhausted.rs --total-timeout 0 -Z unstable-options
@@ -0,0 +1,33 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: -Zfunction-contracts -Zstubbing
+
+//! Check that Kani reports the attributes that cannot be used on the same item, pointing to all
+//! the attributes involved, and that all the errors are reported in one compilation.
+
+#[kani::requires(x < 100)]
+fn double(x: u8) -> u8 {
+    x * 2
+}
+
+#[kani::proof]
+#[kani::proof_for_contract(double)]
+fn check_both_kinds() {
+    double(kani::any());
+}
+
+#[kani::proof_for_contract(double)]
+#[kani::panic_free]
+#[kani::should_panic]
+fn check_panic_free_should_panic() {
+    double(kani::any());
+}
+
+#[kani::proof]
+#[kani::unwind(2)]
+#[kani::unwind(3)]
+fn check_two_unwinds() {}
+
+#[kani::proof]
+#[kani::stub(check_self_stub, check_two_unwinds)]
+fn check_self_stub() {}
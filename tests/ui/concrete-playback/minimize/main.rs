@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+// kani-flags: --enable-unstable --concrete-playback=print --minimize
+
+//! Check that `--minimize` shrinks each input of a failing harness to the simplest value that
+//! still fails.
+
+#[kani::proof]
+pub fn harness() {
+    let small: u8 = kani::any();
+    let negative: i16 = kani::any();
+    assert!(small < 100 || negative > -10, "both inputs are out of range");
+}
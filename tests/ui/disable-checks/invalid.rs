@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check that `#[kani::disable_checks]` only accepts the names of built-in checks.
+
+#[kani::proof]
+#[kani::disable_checks(pointer_overflow, bounds)]
+fn check_unknown() {}
+
+#[kani::proof]
+#[kani::disable_checks()]
+fn check_empty() {}
@@ -0,0 +1,29 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --only-codegen
+
+//! Check the errors for types that cannot be registered with `#[kani::dyn_impls]`.
+
+#[kani::dyn_impls(Shape = [Circle, NotArbitrary])]
+trait Shape {}
+
+#[kani::dyn_impls(Other = [Circle, NotShape])]
+trait Other {}
+
+#[derive(kani::Arbitrary)]
+struct Circle;
+
+struct NotArbitrary;
+
+#[derive(kani::Arbitrary)]
+struct NotShape;
+
+impl Shape for Circle {}
+impl Shape for NotArbitrary {}
+impl Other for Circle {}
+
+#[kani::proof]
+fn check() {
+    let _shape: Box<dyn Shape> = kani::any();
+}
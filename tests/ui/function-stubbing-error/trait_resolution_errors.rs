@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! This tests the error messages for paths of trait methods that cannot be resolved.
+
+pub struct Bar;
+
+pub struct Baz;
+
+pub trait Foo {
+    fn name(&self) -> &'static str;
+}
+
+pub trait Qux {
+    fn name(&self) -> &'static str;
+}
+
+impl Foo for Bar {
+    fn name(&self) -> &'static str {
+        "Foo"
+    }
+}
+
+impl Qux for Bar {
+    fn name(&self) -> &'static str {
+        "Qux"
+    }
+}
+
+pub fn stub_name(_: &Bar) -> &'static str {
+    "stub"
+}
+
+#[kani::proof]
+#[kani::stub(Bar::name, stub_name)]
+#[kani::stub(<Bar>::name, stub_name)]
+#[kani::stub(<Baz as Foo>::name, stub_name)]
+#[kani::stub(Self::name, stub_name)]
+fn invalid_trait_paths() {}
@@ -30,8 +30,8 @@ pub fn stub_foo() -> bool {
     true
 }
 
-/// We still do not support stubbing for trait methods.
-/// <https://github.com/model-checking/kani/issues/1997>
+/// Trait methods are stubbed in the implementation for the type, so we cannot stub default
+/// methods or methods of generic implementations.
 #[kani::proof]
 #[kani::stub(<Bar as Foo>::foo, stub_foo)]
 #[kani::stub(<Bar as Foo>::bar, stub_foo)]
@@ -0,0 +1,13 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that the type of an argument marked with `#[kani::any]` must implement `Arbitrary`.
+
+struct Config {
+    retries: u8,
+}
+
+#[kani::proof]
+fn check_config(#[kani::any] config: Config) {
+    assert!(config.retries < 10);
+}
@@ -0,0 +1,14 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+//! Check that Kani reports an error for a harness whose group doesn't have a fixture.
+
+#[kani::fixture(group = "config")]
+fn config() -> u8 {
+    kani::any()
+}
+
+#[kani::proof(group = "conifg")]
+fn check_config(config: u8) {
+    assert!(config <= u8::MAX);
+}
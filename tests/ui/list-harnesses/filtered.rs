@@ -0,0 +1,32 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --list-harnesses --harness check_ -Z unstable-options -Z stubbing
+//
+// This test checks that `--list-harnesses` lists the harnesses selected by `--harness` with
+// their configuration, without verifying them.
+
+fn answer() -> u32 {
+    42
+}
+
+fn zero() -> u32 {
+    0
+}
+
+#[kani::proof]
+#[kani::unwind(5)]
+#[kani::solver(minisat)]
+fn check_unwind() {
+    assert!(answer() == 0);
+}
+
+#[kani::proof]
+#[kani::stub(answer, zero)]
+fn check_stub() {
+    assert!(answer() == 0);
+}
+
+#[kani::proof]
+fn ignored() {
+    assert!(answer() == 0);
+}
@@ -0,0 +1,8 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --list-harnesses json
+//
+// This test checks that `--list-harnesses` requires `-Z unstable-options`.
+
+#[kani::proof]
+fn check() {}
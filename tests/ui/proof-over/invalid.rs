@@ -0,0 +1,31 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Check the errors reported for invalid uses of `proof_over`.
+
+#[kani::proof_over(N = [0, 256])]
+fn check_too_large<const N: u8>() {}
+
+#[kani::proof_over(N = [1])]
+fn check_missing<const N: usize, const M: usize>() {}
+
+#[kani::proof_over(M = [1])]
+fn check_unknown() {}
+
+#[kani::proof_over(N = [1])]
+fn check_type_param<T, const N: usize>() {}
+
+#[kani::proof_over(T = [u8, f32])]
+fn check_unsatisfied_bounds<T: Ord>() {}
+
+#[kani::proof_over(T = [1], N = [u8])]
+fn check_wrong_kind<T, const N: usize>() {}
+
+#[kani::proof_over(N = [])]
+fn check_no_values<const N: usize>() {}
+
+#[kani::proof_over(N = all)]
+fn check_unknown_set<const N: usize>() {}
+
+#[kani::proof_over(T = boundary)]
+fn check_type_boundary<T>() {}
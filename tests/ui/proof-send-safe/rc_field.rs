@@ -0,0 +1,28 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that `proof_send_safe` reports fields of a `Send` type that can reach interior
+//! mutability that is not thread-safe.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+struct Inner {
+    counter: Rc<u8>,
+}
+
+pub struct Wrapper {
+    id: u32,
+    inner: Inner,
+    shared: *const Cell<u8>,
+}
+
+unsafe impl Send for Wrapper {}
+
+#[kani::proof_send_safe(Wrapper)]
+fn check_wrapper() {
+    let wrapper =
+        Wrapper { id: kani::any(), inner: Inner { counter: Rc::new(0) }, shared: std::ptr::null() };
+    assert_eq!(*wrapper.inner.counter, 0);
+    let _ = wrapper.id;
+    let _ = wrapper.shared;
+}
@@ -0,0 +1,15 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checks that reaching `kani::must_not_reach!()` fails verification even if the harness is
+//! expected to panic.
+
+#[kani::proof]
+#[kani::should_panic]
+fn check() {
+    match kani::any::<u8>() {
+        0 => panic!("panicked on the `0` arm!"),
+        1 => kani::must_not_reach!("the `1` arm"),
+        _ => {}
+    }
+}
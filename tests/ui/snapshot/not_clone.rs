@@ -0,0 +1,12 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//! Check that there's a compilation error if the user tries to snapshot a value whose type does
+//! not implement `Clone`.
+
+struct NotClone(u8);
+
+#[kani::proof]
+fn check_not_clone() {
+    let value = NotClone(kani::any());
+    let _before = kani::snapshot!(value);
+}
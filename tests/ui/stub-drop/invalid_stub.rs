@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that Kani rejects drop stubs with the wrong signature or for types without drop glue.
+
+struct WithDrop;
+
+impl Drop for WithDrop {
+    fn drop(&mut self) {}
+}
+
+struct NoDrop;
+
+fn by_ref(_val: &WithDrop) {}
+
+fn no_drop(_val: &mut NoDrop) {}
+
+#[kani::proof]
+#[kani::stub_drop(WithDrop, with = by_ref)]
+fn check_signature() {
+    let _val = WithDrop;
+}
+
+#[kani::proof]
+#[kani::stub_drop(NoDrop, with = no_drop)]
+fn check_no_drop() {
+    let _val = NoDrop;
+}
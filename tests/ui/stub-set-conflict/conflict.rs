@@ -0,0 +1,30 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that Kani rejects stub sets that replace the same function by different stubs, and
+//! that it points to both sets.
+
+fn read() -> u8 {
+    unimplemented!()
+}
+
+fn zero() -> u8 {
+    0
+}
+
+fn one() -> u8 {
+    1
+}
+
+kani::stub_set! {
+    zeros = [(read, zero)],
+    ones = [(read, one)],
+}
+
+#[kani::proof]
+#[kani::use_stubs(zeros, ones)]
+fn check_conflict() {
+    assert!(read() < 2);
+}
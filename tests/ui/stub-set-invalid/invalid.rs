@@ -0,0 +1,26 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: -Z stubbing
+//
+//! Check that Kani rejects arguments of `kani::use_stubs` that are not stub sets.
+
+fn read() -> u8 {
+    unimplemented!()
+}
+
+fn zero() -> u8 {
+    0
+}
+
+#[kani::proof]
+#[kani::use_stubs(zero)]
+fn check_not_a_set() {
+    assert_eq!(read(), 0);
+}
+
+#[kani::proof]
+#[kani::use_stubs(missing)]
+fn check_missing_set() {
+    assert_eq!(read(), 0);
+}
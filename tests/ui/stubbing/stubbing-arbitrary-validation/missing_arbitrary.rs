@@ -0,0 +1,34 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --harness harness -Z stubbing
+//
+//! This tests that we report a type that doesn't implement `kani::Arbitrary` when the stub
+//! requires creating nondeterministic values of it.
+
+struct Config {
+    retries: u8,
+}
+
+fn load<T: Default>() -> T {
+    T::default()
+}
+
+fn any_value<T: kani::Arbitrary>() -> T {
+    kani::any()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { retries: 3 }
+    }
+}
+
+#[kani::proof]
+#[kani::stub(load, any_value)]
+fn harness() {
+    let config: Config = load();
+    let retries: u8 = load();
+    let name: String = load();
+    assert!(config.retries <= 3 || retries > 0 || name.is_empty());
+}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --target thumbv7em-none-eabihf -Zunstable-options
+//
+// This test checks that Kani fails early when the target given to `--target` cannot be modeled.
+
+#[kani::proof]
+fn check_pointer_width() {
+    assert_eq!(std::mem::size_of::<usize>(), 4);
+}
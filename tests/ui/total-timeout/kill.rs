@@ -0,0 +1,17 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --total-timeout 5s --total-timeout-policy kill -Z unstable-options
+//
+// Check that the `kill` policy stops the harness that is being verified when the total timeout is
+// reached, and reports it as skipped.
+
+#[kani::proof]
+fn check_killed() {
+    // construct a problem that requires a long time to solve
+    let (a1, b1, c1): (u64, u64, u64) = kani::any();
+    let (a2, b2, c2): (u64, u64, u64) = kani::any();
+    let p1 = a1.saturating_mul(b1).saturating_mul(c1);
+    let p2 = a2.saturating_mul(b2).saturating_mul(c2);
+    // (a1 == a2 && b1 == b2 && c1 == c2) implies p1 == p2
+    assert!(a1 != a2 || b1 != b2 || c1 != c2 || p1 == p2)
+}
@@ -0,0 +1,10 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+// kani-flags: --total-timeout 10m
+//
+// This test checks that the `--total-timeout` option requires `-Z unstable-options`.
+
+#[kani::proof]
+fn check_unstable() {
+    assert!(true);
+}
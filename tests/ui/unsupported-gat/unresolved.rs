@@ -0,0 +1,41 @@
+// Copyright Kani Contributors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+//
+// kani-flags: --only-codegen
+
+//! Check that Kani reports a call that it cannot resolve when the signature of the callee uses a
+//! generic associated type.
+//!
+//! The impl of `Family` for `Boxed` only applies if `Boxed: Copy`, which the harness assumes but
+//! which doesn't hold, so the call to `Boxed::make` has no implementation to resolve to.
+
+#![feature(trivial_bounds)]
+#![allow(trivial_bounds)]
+
+trait Family {
+    type Member<T>;
+
+    fn make<T: Default>() -> Self::Member<T>;
+}
+
+struct Boxed;
+
+impl Family for Boxed
+where
+    Boxed: Copy,
+{
+    type Member<T> = Box<T>;
+
+    fn make<T: Default>() -> Box<T> {
+        Box::default()
+    }
+}
+
+#[kani::proof]
+fn check_make()
+where
+    Boxed: Copy,
+{
+    let member = Boxed::make::<u8>();
+    assert_eq!(*member, 0);
+}